@@ -4,4 +4,5 @@ extern crate test;
 
 //åmod reactive;
 //mod ssr;
+mod oco;
 mod todomvc;
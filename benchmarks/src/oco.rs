@@ -0,0 +1,92 @@
+use std::rc::Rc;
+use test::Bencher;
+
+// cloning text the way it was stored before `Oco`
+#[bench]
+fn string_clone(b: &mut Bencher) {
+    let s = String::from("A static title");
+    b.iter(|| {
+        for _ in 0..1000 {
+            test::black_box(s.clone());
+        }
+    });
+}
+
+#[bench]
+fn oco_borrowed_clone(b: &mut Bencher) {
+    use leptos::Oco;
+
+    let s: Oco<'static, str> = "A static title".into();
+    b.iter(|| {
+        for _ in 0..1000 {
+            test::black_box(s.clone());
+        }
+    });
+}
+
+#[bench]
+fn oco_counted_clone(b: &mut Bencher) {
+    use leptos::Oco;
+
+    let mut s: Oco<'static, str> = String::from("A static title").into();
+    s.upgrade_inplace();
+    b.iter(|| {
+        for _ in 0..1000 {
+            test::black_box(s.clone());
+        }
+    });
+}
+
+// reading a `TextProp` the way it was stored before `Oco`
+#[bench]
+fn string_text_prop_get(b: &mut Bencher) {
+    let s = "A static title".to_string();
+    let prop: Rc<dyn Fn() -> String> = Rc::new(move || s.clone());
+    b.iter(|| {
+        for _ in 0..1000 {
+            test::black_box(prop());
+        }
+    });
+}
+
+#[bench]
+fn text_prop_get_oco(b: &mut Bencher) {
+    use leptos::TextProp;
+
+    let prop = TextProp::from("A static title");
+    b.iter(|| {
+        for _ in 0..1000 {
+            test::black_box(prop.get_oco());
+        }
+    });
+}
+
+#[bench]
+fn leptos_ssr_attributes(b: &mut Bencher) {
+    use leptos::*;
+
+    b.iter(|| {
+        leptos_dom::HydrationCtx::reset_id();
+        _ = create_scope(create_runtime(), |cx| {
+            let rendered = (0..100)
+                .map(|n| {
+                    html::li(cx)
+                        .attr("class", "item")
+                        .attr("title", "A static title")
+                        .attr("data-index", n)
+                        .style("color", "red")
+                        .child("Item")
+                })
+                .collect_view(cx);
+            let rendered = html::ul(cx)
+                .child(rendered)
+                .into_view(cx)
+                .render_to_string(cx);
+
+            assert!(rendered.contains(
+                "<li class=\"item\" title=\"A static title\" \
+                 data-index=\"0\" style=\"color: red;\""
+            ));
+        });
+    });
+}
@@ -161,6 +161,60 @@ fn leptos_scope_creation_and_disposal(b: &mut Bencher) {
     runtime.dispose();
 }
 
+#[bench]
+fn leptos_hash_map_signal_single_update(b: &mut Bencher) {
+    use leptos::*;
+    use std::collections::HashMap;
+    let runtime = create_runtime();
+
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let map = create_rw_signal(
+        cx,
+        (0..10_000usize).map(|n| (n, n)).collect::<HashMap<_, _>>(),
+    );
+    let acc = Rc::new(Cell::new(0));
+    for n in 0..10_000usize {
+        let acc = Rc::clone(&acc);
+        create_isomorphic_effect(cx, move |_| {
+            acc.set(acc.get() + map.with(|map| map[&n]));
+        });
+    }
+
+    b.iter(|| {
+        map.update(|map| *map.get_mut(&5_000).unwrap() += 1);
+    });
+
+    disposer.dispose();
+    runtime.dispose();
+}
+
+#[bench]
+fn leptos_signal_map_single_update(b: &mut Bencher) {
+    use leptos::*;
+    let runtime = create_runtime();
+
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let map = create_signal_map(cx);
+    for n in 0..10_000usize {
+        map.insert(n, n);
+    }
+    let acc = Rc::new(Cell::new(0));
+    for n in 0..10_000usize {
+        let acc = Rc::clone(&acc);
+        let entry = map.get(n);
+        create_isomorphic_effect(cx, move |_| {
+            acc.set(acc.get() + entry.get().unwrap());
+        });
+    }
+
+    b.iter(|| {
+        map.update(&5_000, |n| *n += 1);
+    });
+
+    disposer.dispose();
+    runtime.dispose();
+}
+
 #[bench]
 fn rs_deep_update(b: &mut Bencher) {
     use reactive_signals::{Scope, Signal, signal, runtimes::ClientRuntime, types::Func};
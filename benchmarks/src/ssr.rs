@@ -38,6 +38,49 @@ fn leptos_ssr_bench(b: &mut Bencher) {
 	});
 }
 
+#[bench]
+fn leptos_ssr_repeated_class_bench(b: &mut Bencher) {
+	const ROW_CLASS: &str = "px-4 py-2 text-sm font-medium text-gray-900 \
+		whitespace-nowrap border-b border-gray-200 hover:bg-gray-50 \
+		dark:text-white dark:border-gray-700";
+
+	b.iter(|| {
+		use leptos::*;
+		// rendered with `ssr::render_to_string`, which shares repeated
+		// owned attribute values for the length of the render
+		let rendered = leptos::ssr::render_to_string(|cx| {
+			// the same owned class string on every row, as when it is
+			// computed from props
+			let row_class = ROW_CLASS.to_string();
+			let rows = (0..1000)
+				.map(|n| {
+					view! { cx,
+						<tr class=row_class.clone()>
+							<td class=ROW_CLASS>{n}</td>
+							<td class=row_class.clone()>"Row"</td>
+						</tr>
+					}
+				})
+				.collect_view(cx);
+			let builder_rows = (0..1000)
+				.map(|n| {
+					html::tr(cx)
+						.attr("class", row_class.clone())
+						.child(html::td(cx).attr("class", ROW_CLASS).child(n))
+				})
+				.collect_view(cx);
+			view! { cx,
+				<table>
+					<tbody>{rows}</tbody>
+					<tbody>{builder_rows}</tbody>
+				</table>
+			}
+		});
+
+		assert_eq!(rendered.matches(ROW_CLASS).count(), 5000);
+	});
+}
+
 #[bench]
 fn tera_ssr_bench(b: &mut Bencher) {
     use serde::{Deserialize, Serialize};
@@ -154,3 +154,35 @@ fn inc() {
         .outer_html())
     );
 }
+
+#[wasm_bindgen_test]
+fn unmount_one_of_two_roots() {
+    let document = leptos::document();
+    let first_wrapper = document.create_element("section").unwrap();
+    let second_wrapper = document.create_element("section").unwrap();
+    let _ = document.body().unwrap().append_child(&first_wrapper);
+    let _ = document.body().unwrap().append_child(&second_wrapper);
+
+    let first = mount_to(
+        first_wrapper.clone().unchecked_into(),
+        |cx| view! { cx, <SimpleCounter initial_value=0 step=1/> },
+    );
+    let _second = mount_to(
+        second_wrapper.clone().unchecked_into(),
+        |cx| view! { cx, <SimpleCounter initial_value=0 step=1/> },
+    );
+
+    // unmounting the first root removes its nodes...
+    first.dispose();
+    assert_eq!(first_wrapper.inner_html(), "");
+
+    // ...but the second root's delegated events still work
+    let inc = second_wrapper
+        .query_selector("button:last-child")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>();
+    inc.click();
+    let span = second_wrapper.query_selector("span").unwrap().unwrap();
+    assert_eq!(span.text_content().unwrap(), "Value: 1!");
+}
@@ -5,12 +5,9 @@ use leptos_router::*;
 
 cfg_if! {
     if #[cfg(feature = "ssr")] {
-        use std::sync::atomic::{AtomicI32, Ordering};
-        use broadcaster::BroadcastChannel;
-        static COUNT: AtomicI32 = AtomicI32::new(0);
-
         lazy_static::lazy_static! {
-            pub static ref COUNT_CHANNEL: BroadcastChannel<i32> = BroadcastChannel::new();
+            // shared by every connection, and broadcast to them when it changes
+            pub static ref COUNT: ServerSignal<i32> = create_server_signal("count");
         }
     }
 }
@@ -18,7 +15,7 @@ cfg_if! {
 // "/api" is an optional prefix that allows you to locate server functions wherever you'd like on the server
 #[server(GetServerCount, "/api")]
 pub async fn get_server_count() -> Result<i32, ServerFnError> {
-    Ok(COUNT.load(Ordering::Relaxed))
+    Ok(COUNT.get())
 }
 
 #[server(AdjustServerCount, "/api")]
@@ -26,17 +23,18 @@ pub async fn adjust_server_count(
     delta: i32,
     msg: String,
 ) -> Result<i32, ServerFnError> {
-    let new = COUNT.load(Ordering::Relaxed) + delta;
-    COUNT.store(new, Ordering::Relaxed);
-    _ = COUNT_CHANNEL.send(&new).await;
+    let mut new = 0;
+    COUNT.update(|count| {
+        *count += delta;
+        new = *count;
+    });
     println!("message = {:?}", msg);
     Ok(new)
 }
 
 #[server(ClearServerCount, "/api")]
 pub async fn clear_server_count() -> Result<i32, ServerFnError> {
-    COUNT.store(0, Ordering::Relaxed);
-    _ = COUNT_CHANNEL.send(&0).await;
+    COUNT.set(0);
     Ok(0)
 }
 #[component]
@@ -70,7 +68,7 @@ pub fn Counters(cx: Scope) -> impl IntoView {
                     <Route path="" view=Counter/>
                     <Route path="form" view=FormCounter/>
                     <Route path="multi" view=MultiuserCounter/>
-                    <Route path="multi" view=NotFound/>
+                    <Route path="*any" view=NotFound/>
                 </Routes>
             </main>
         </Router>
@@ -83,29 +81,18 @@ pub fn Counters(cx: Scope) -> impl IntoView {
 // This is the typical pattern for a CRUD app
 #[component]
 pub fn Counter(cx: Scope) -> impl IntoView {
-    let dec = create_action(cx, |_| adjust_server_count(-1, "decing".into()));
-    let inc = create_action(cx, |_| adjust_server_count(1, "incing".into()));
-    let clear = create_action(cx, |_| clear_server_count());
-    let counter = create_resource(
-        cx,
-        move || {
-            (
-                dec.version().get(),
-                inc.version().get(),
-                clear.version().get(),
-            )
-        },
-        |_| get_server_count(),
-    );
-
-    let value = move || {
-        counter
-            .read(cx)
-            .map(|count| count.unwrap_or(0))
-            .unwrap_or(0)
-    };
+    let counter = create_resource(cx, || (), |_| get_server_count());
+    let dec = create_action(cx, |_| adjust_server_count(-1, "decing".into()))
+        .invalidates(counter);
+    let inc = create_action(cx, |_| adjust_server_count(1, "incing".into()))
+        .invalidates(counter);
+    let clear =
+        create_action(cx, |_| clear_server_count()).invalidates(counter);
+
+    let value =
+        move || counter.get().map(|count| count.unwrap_or(0)).unwrap_or(0);
     let error_msg = move || {
-        counter.read(cx).and_then(|res| match res {
+        counter.get().and_then(|res| match res {
             Ok(_) => None,
             Err(e) => Some(e),
         })
@@ -138,20 +125,21 @@ pub fn Counter(cx: Scope) -> impl IntoView {
 // but uses HTML forms to submit the actions
 #[component]
 pub fn FormCounter(cx: Scope) -> impl IntoView {
-    let adjust = create_server_action::<AdjustServerCount>(cx);
-    let clear = create_server_action::<ClearServerCount>(cx);
-
     let counter = create_resource(
         cx,
-        move || (adjust.version().get(), clear.version().get()),
+        || (),
         |_| {
             log::debug!("FormCounter running fetcher");
             get_server_count()
         },
     );
+    let adjust =
+        create_server_action::<AdjustServerCount>(cx).invalidates(counter);
+    let clear =
+        create_server_action::<ClearServerCount>(cx).invalidates(counter);
     let value = move || {
         log::debug!("FormCounter looking for value");
-        counter.read(cx).and_then(|n| n.ok()).unwrap_or(0)
+        counter.get().and_then(|n| n.ok()).unwrap_or(0)
     };
 
     view! { cx,
@@ -181,7 +169,7 @@ pub fn FormCounter(cx: Scope) -> impl IntoView {
 }
 
 // This is a kind of "multi-user" counter
-// It relies on a stream of server-sent events (SSE) for the counter's value
+// It relies on a `ServerSignal`, which streams server-sent events (SSE) for the counter's value
 // Whenever another user updates the value, it will update here
 // This is the primitive pattern for live chat, collaborative editing, etc.
 #[component]
@@ -192,34 +180,10 @@ pub fn MultiuserCounter(cx: Scope) -> impl IntoView {
         create_action(cx, |_| adjust_server_count(1, "inc inc moose".into()));
     let clear = create_action(cx, |_| clear_server_count());
 
-    #[cfg(not(feature = "ssr"))]
-    let multiplayer_value = {
-        use futures::StreamExt;
-
-        let mut source =
-            gloo_net::eventsource::futures::EventSource::new("/api/events")
-                .expect("couldn't connect to SSE stream");
-        let s = create_signal_from_stream(
-            cx,
-            source
-                .subscribe("message")
-                .unwrap()
-                .map(|value| match value {
-                    Ok(value) => value
-                        .1
-                        .data()
-                        .as_string()
-                        .expect("expected string value"),
-                    Err(_) => "0".to_string(),
-                }),
-        );
-
-        on_cleanup(cx, move || source.close());
-        s
-    };
-
-    #[cfg(feature = "ssr")]
-    let (multiplayer_value, _) = create_signal(cx, None::<i32>);
+    // follows the server's `COUNT`, starting from the value it had when this
+    // page was rendered
+    let multiplayer_value =
+        use_server_signal::<i32>(cx, "count", "/api/events");
 
     view! { cx,
         <div>
@@ -231,7 +195,7 @@ pub fn MultiuserCounter(cx: Scope) -> impl IntoView {
                 <button on:click=move |_| clear.dispatch(())>"Clear"</button>
                 <button on:click=move |_| dec.dispatch(())>"-1"</button>
                 <span>
-                    "Multiplayer Value: " {move || multiplayer_value.get().unwrap_or_default()}
+                    "Multiplayer Value: " {multiplayer_value}
                 </span>
                 <button on:click=move |_| inc.dispatch(())>"+1"</button>
             </div>
@@ -15,14 +15,9 @@ cfg_if! {
         async fn counter_events() -> impl Responder {
             use futures::StreamExt;
 
-            let stream =
-                futures::stream::once(async { crate::counters::get_server_count().await.unwrap_or(0) })
-                    .chain(COUNT_CHANNEL.clone())
-                    .map(|value| {
-                        Ok(web::Bytes::from(format!(
-                            "event: message\ndata: {value}\n\n"
-                        ))) as Result<web::Bytes>
-                    });
+            let stream = COUNT
+                .events()
+                .map(|event| Ok(web::Bytes::from(event)) as Result<web::Bytes>);
             HttpResponse::Ok()
                 .insert_header(("Content-Type", "text/event-stream"))
                 .streaming(stream)
@@ -118,3 +118,72 @@ fn inc() {
          --><!-- </EachItem> --><!-- </Each> --></ul>"
     );
 }
+
+#[wasm_bindgen_test]
+fn event_handlers_removed_on_unmount() {
+    use std::{cell::Cell, rc::Rc};
+
+    #[component]
+    fn Clickable(cx: Scope) -> impl IntoView {
+        let (clicks, set_clicks) = create_signal(cx, 0);
+        view! { cx,
+            <button on:click=move |_| set_clicks.update(|n| *n += 1)>
+                {move || clicks.get()}
+            </button>
+            <input on:input=move |_| set_clicks.set(0)/>
+        }
+    }
+
+    let baseline = leptos::leptos_dom::live_event_handlers();
+    let set_show = Rc::new(Cell::new(None));
+    mount_to_body({
+        let set_show = Rc::clone(&set_show);
+        move |cx| {
+            let (show, set) = create_signal(cx, false);
+            set_show.set(Some(set));
+            view! { cx,
+                <Show when=move || show.get() fallback=|_| ()>
+                    <Clickable/>
+                </Show>
+            }
+        }
+    });
+    let set_show = set_show.get().unwrap();
+
+    for _ in 0..1000 {
+        set_show.set(true);
+        set_show.set(false);
+    }
+
+    assert_eq!(leptos::leptos_dom::live_event_handlers(), baseline);
+}
+
+#[wasm_bindgen_test]
+fn view_event_handlers_removed_on_unmount() {
+    use std::{cell::Cell, rc::Rc};
+
+    let baseline = leptos::leptos_dom::live_event_handlers();
+    let set_show = Rc::new(Cell::new(None));
+    mount_to_body({
+        let set_show = Rc::clone(&set_show);
+        move |cx| {
+            let (show, set) = create_signal(cx, false);
+            set_show.set(Some(set));
+            view! { cx,
+                <Show when=move || show.get() fallback=|_| ()>
+                    {view! { cx, <button>"Click"</button> }
+                        .into_view(cx)
+                        .on(ev::click, |_| ())}
+                </Show>
+            }
+        }
+    });
+    let set_show = set_show.get().unwrap();
+
+    for _ in 0..100 {
+        set_show.set(true);
+        set_show.set(false);
+    }
+
+    assert_eq!(leptos::leptos_dom::live_event_handlers(), baseline);
+}
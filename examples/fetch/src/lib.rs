@@ -69,7 +69,7 @@ pub fn fetch_example(cx: Scope) -> impl IntoView {
     // and by using the ErrorBoundary fallback to catch Err(_)
     // so we'll just implement our happy path and let the framework handle the rest
     let cats_view = move || {
-        cats.read(cx).map(|data| {
+        cats.get().map(|data| {
             data.map(|data| {
                 data.iter()
                     .map(|s| view! { cx, <p><img src={s}/></p> })
@@ -38,7 +38,7 @@ pub fn Stories(cx: Scope) -> impl IntoView {
 
     let hide_more_link = move |cx| {
         pending()
-            || stories.read(cx).unwrap_or(None).unwrap_or_default().len() < 28
+            || stories.get().unwrap_or(None).unwrap_or_default().len() < 28
     };
 
     view! {
@@ -87,7 +87,7 @@ pub fn Stories(cx: Scope) -> impl IntoView {
                         fallback=move || view! { cx,  <p>"Loading..."</p> }
                         set_pending=set_pending.into()
                     >
-                        {move || match stories.read(cx) {
+                        {move || match stories.get() {
                             None => None,
                             Some(None) => Some(view! { cx,  <p>"Error loading stories."</p> }.into_any()),
                             Some(Some(stories)) => {
@@ -23,7 +23,7 @@ pub fn Story(cx: Scope) -> impl IntoView {
     );
     let meta_description = move || {
         story
-            .read(cx)
+            .get()
             .and_then(|story| story.map(|story| story.title))
             .unwrap_or_else(|| "Loading story...".to_string())
     };
@@ -32,7 +32,7 @@ pub fn Story(cx: Scope) -> impl IntoView {
         <>
             <Meta name="description" content=meta_description/>
                 <Suspense fallback=|| view! { cx, "Loading..." }>
-                    {move || story.read(cx).map(|story| match story {
+                    {move || story.get().map(|story| match story {
                         None => view! { cx,  <div class="item-view">"Error loading this story."</div> },
                         Some(story) => view! { cx,
                             <div class="item-view">
@@ -84,11 +84,11 @@ pub fn ContactList(cx: Scope) -> impl IntoView {
         log!("cleaning up <ContactList/>");
     });
 
-    let location = use_location(cx);
+    let location = expect_location(cx);
     let contacts =
         create_resource(cx, move || location.search.get(), get_contacts);
     let contacts = move || {
-        contacts.read(cx).map(|contacts| {
+        contacts.get().map(|contacts| {
             // this data doesn't change frequently so we can use .map().collect() instead of a keyed <For/>
             contacts
                 .into_iter()
@@ -149,7 +149,7 @@ pub fn Contact(cx: Scope) -> impl IntoView {
         log!("params = {:#?}", params.get());
     });
 
-    let contact_display = move || match contact.read(cx) {
+    let contact_display = move || match contact.get() {
         // None => loading, but will be caught by Suspense fallback
         // I'm only doing this explicitly for the example
         None => None,
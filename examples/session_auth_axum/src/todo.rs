@@ -148,7 +148,7 @@ pub fn TodoApp(cx: Scope) -> impl IntoView {
                     fallback=move || view! {cx, <span>"Loading..."</span>}
                 >
                 {move || {
-                    user.read(cx).map(|user| match user {
+                    user.get().map(|user| match user {
                         Err(e) => view! {cx,
                             <A href="/signup">"Signup"</A>", "
                             <A href="/login">"Login"</A>", "
@@ -218,7 +218,7 @@ pub fn Todos(cx: Scope) -> impl IntoView {
                     {move || {
                         let existing_todos = {
                             move || {
-                                todos.read(cx)
+                                todos.get()
                                     .map(move |todos| match todos {
                                         Err(e) => {
                                             view! { cx, <pre class="error">"Server Error: " {e.to_string()}</pre>}.into_view(cx)
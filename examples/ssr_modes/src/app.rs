@@ -44,7 +44,7 @@ fn HomePage(cx: Scope) -> impl IntoView {
     let posts =
         create_resource(cx, || (), |_| async { list_post_metadata().await });
     let posts_view = move || {
-        posts.with(cx, |posts| posts
+        posts.map(|posts| posts
             .clone()
             .map(|posts| {
                 posts.iter()
@@ -87,7 +87,7 @@ fn Post(cx: Scope) -> impl IntoView {
     });
 
     let post_view = move || {
-        post.with(cx, |post| {
+        post.map(|post| {
             post.clone().map(|post| {
                 view! { cx,
                     // render content
@@ -49,7 +49,7 @@ fn HomePage(cx: Scope) -> impl IntoView {
         <Suspense fallback=move || view! { cx, <p>"Loading posts..."</p> }>
             <ul>
                 {move || {
-                    posts.with(cx, |posts| posts
+                    posts.map(|posts| posts
                         .clone()
                         .map(|posts| {
                             posts.iter()
@@ -92,7 +92,7 @@ fn Post(cx: Scope) -> impl IntoView {
     // pass it in under the `<Suspense/>` so that it is correct
     let post_view = move |cx| {
         move || {
-            post.with(cx, |post| {
+            post.map(|post| {
                 post.clone().map(|post| {
                     view! { cx,
                         // render content
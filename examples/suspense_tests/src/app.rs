@@ -126,13 +126,13 @@ fn Nested(cx: Scope) -> impl IntoView {
         <div>
             <Suspense fallback=|| "Loading 1...">
                 {move || {
-                    one_second.read(cx).map(|_| view! {cx,
+                    one_second.get().map(|_| view! {cx,
                         <p id="loaded-1">"One Second: Loaded 1!"</p>
                     })
                 }}
                 <Suspense fallback=|| "Loading 2...">
                     {move || {
-                        two_second.read(cx).map(|_| view! { cx,
+                        two_second.get().map(|_| view! { cx,
                             <p id="loaded-2">"Two Second: Loaded 2!"</p>
                             <button on:click=move |_| set_count.update(|n| *n += 1)>
                                 {count}
@@ -154,20 +154,20 @@ fn NestedResourceInside(cx: Scope) -> impl IntoView {
         <div>
             <Suspense fallback=|| "Loading 1...">
                    {move || {
-                    one_second.read(cx).map(|_| {
+                    one_second.get().map(|_| {
                         let two_second = create_resource(cx, || (), move |_| async move {
                             leptos::log!("creating two_second resource");
                             second_wait_fn(WAIT_TWO_SECONDS).await
                         });
                         view! { cx,
-                            {move || one_second.read(cx).map(|_|
+                            {move || one_second.get().map(|_|
                                 view! {cx,
                                     <p id="loaded-1">"One Second: Loaded 1!"</p>
                                 }
                             )}
                             <Suspense fallback=|| "Loading 2...">
                                 {move || {
-                                    two_second.read(cx).map(|x| view! { cx,
+                                    two_second.get().map(|x| view! { cx,
                                         <span id="loaded-2">"Loaded 2 (created inside first suspense)!: " {format!("{x:?}")}</span>
                                         <button on:click=move |_| set_count.update(|n| *n += 1)>
                                             {count}
@@ -193,7 +193,7 @@ fn Parallel(cx: Scope) -> impl IntoView {
         <div>
             <Suspense fallback=|| "Loading 1...">
                 {move || {
-                    one_second.read(cx).map(move |_| view! { cx,
+                    one_second.get().map(move |_| view! { cx,
                         <p id="loaded-1">"One Second: Loaded 1!"</p>
                         <button on:click=move |_| set_count.update(|n| *n += 1)>
                             {count}
@@ -203,7 +203,7 @@ fn Parallel(cx: Scope) -> impl IntoView {
             </Suspense>
             <Suspense fallback=|| "Loading 2...">
                 {move || {
-                    two_second.read(cx).map(move |_| view! { cx,
+                    two_second.get().map(move |_| view! { cx,
                         <p id="loaded-2">"Two Second: Loaded 2!"</p>
                         <button id="second-count" on:click=move |_| set_count.update(|n| *n += 1)>
                             {count}
@@ -224,7 +224,7 @@ fn Single(cx: Scope) -> impl IntoView {
         <div>
             <Suspense fallback=|| "Loading 1...">
             {move || {
-                one_second.read(cx).map(|_| view! {cx,
+                one_second.get().map(|_| view! {cx,
                     <p id="loaded-1">"One Second: Loaded 1!"</p>
                 })
             }}
@@ -263,7 +263,7 @@ fn InsideComponentChild(cx: Scope) -> impl IntoView {
     view! { cx,
         <Suspense fallback=|| "Loading 1...">
         {move || {
-            one_second.read(cx).map(|_| view! {cx,
+            one_second.get().map(|_| view! {cx,
                 <p id="loaded-1">"One Second: Loaded 1!"</p>
             })
         }}
@@ -134,7 +134,7 @@ pub fn Todos(cx: Scope) -> impl IntoView {
                 {move || {
                     let existing_todos = {
                         move || {
-                            todos.read(cx)
+                            todos.get()
                                 .map(move |todos| match todos {
                                     Err(e) => {
                                         view! { cx, <pre class="error">"Server Error: " {e.to_string()}</pre>}.into_view(cx)
@@ -16,17 +16,21 @@ use actix_web::{
 use futures::{Stream, StreamExt};
 use http::StatusCode;
 use leptos::{
-    leptos_server::{server_fn_by_path, Payload},
-    server_fn::Encoding,
+    leptos_server::{server_fn_by_path, Payload, ServerFnContext},
+    server_fn::{form, Encoding},
     ssr::render_to_stream_with_prefix_undisposed_with_context_and_block_replacement,
     *,
 };
-use leptos_integration_utils::{build_async_response, html_parts_separated};
+pub use leptos_integration_utils::isr::{self, revalidate_path, IsrCache};
+use leptos_integration_utils::{
+    build_async_response, flush_chunks, html_parts_separated,
+    isr::{CachedPage, IsrLookup},
+};
 use leptos_meta::*;
 use leptos_router::*;
 use parking_lot::RwLock;
 use regex::Regex;
-use std::{fmt::Display, future::Future, sync::Arc};
+use std::{fmt::Display, future::Future, sync::Arc, time::SystemTime};
 use tracing::instrument;
 /// This struct lets you define headers and override the status of the Response from an Element or a Server Function
 /// Typically contained inside of a ResponseOptions. Setting this is useful for cookies and custom responses.
@@ -171,10 +175,79 @@ pub fn handle_server_fns() -> Route {
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 pub fn handle_server_fns_with_context(
     additional_context: impl Fn(leptos::Scope) + 'static + Clone + Send,
+) -> Route {
+    handle_server_fns_inner(additional_context, default_error_status)
+}
+
+/// An Actix [Route](actix_web::Route) that listens for requests with Leptos server function
+/// arguments, like [handle_server_fns], and responds with the status code returned by
+/// `error_status` when a server function returns an error.
+///
+/// By default, an error is sent with the status code of [ServerFnError::status_code]. A status
+/// set by the server function itself with [ResponseOptions::set_status] is used instead of
+/// either. The client deserializes the error from the body of the response for any status
+/// code that isn't successful.
+///
+/// ```
+/// use actix_web::{http::StatusCode, App};
+/// use leptos::*;
+///
+/// # if false { // don't actually try to run a server in a doctest...
+/// let app = App::new().route(
+///     "/api/{tail:.*}",
+///     leptos_actix::handle_server_fns_with_error_mapper(
+///         |err: &ServerFnError| match err {
+///             ServerFnError::MissingArg(_) => StatusCode::UNPROCESSABLE_ENTITY,
+///             err => StatusCode::from_u16(err.status_code()).unwrap(),
+///         },
+///     ),
+/// );
+/// # }
+/// ```
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [ResponseOptions]
+/// - [HttpRequest](actix_web::HttpRequest)
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn handle_server_fns_with_error_mapper(
+    error_status: impl Fn(&ServerFnError) -> StatusCode + 'static + Clone,
+) -> Route {
+    handle_server_fns_with_context_and_error_mapper(|_cx| {}, error_status)
+}
+
+/// An Actix [Route](actix_web::Route) that listens for requests with Leptos server function
+/// arguments, like [handle_server_fns_with_context], and responds with the status code
+/// returned by `error_status` when a server function returns an error, like
+/// [handle_server_fns_with_error_mapper].
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [ResponseOptions]
+/// - [HttpRequest](actix_web::HttpRequest)
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn handle_server_fns_with_context_and_error_mapper(
+    additional_context: impl Fn(leptos::Scope) + 'static + Clone + Send,
+    error_status: impl Fn(&ServerFnError) -> StatusCode + 'static + Clone,
+) -> Route {
+    handle_server_fns_inner(additional_context, error_status)
+}
+
+// the status of an error response, unless the server function or the app
+// set another one
+fn default_error_status(err: &ServerFnError) -> StatusCode {
+    StatusCode::from_u16(err.status_code())
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+fn handle_server_fns_inner(
+    additional_context: impl Fn(leptos::Scope) + 'static + Clone + Send,
+    error_status: impl Fn(&ServerFnError) -> StatusCode + 'static + Clone,
 ) -> Route {
     web::to(
         move |req: HttpRequest, params: web::Path<String>, body: web::Bytes| {
             let additional_context = additional_context.clone();
+            let error_status = error_status.clone();
             async move {
                 let additional_context = additional_context.clone();
 
@@ -197,6 +270,31 @@ pub fn handle_server_fns_with_context(
                     // provide HttpRequest as context in server scope
                     provide_context(cx, req.clone());
                     provide_context(cx, res_options.clone());
+                    let cookie_header = req
+                        .headers()
+                        .get(header::COOKIE)
+                        .and_then(|value| value.to_str().ok())
+                        .map(String::from);
+                    leptos::cookie::provide_server_cookies(
+                        cx,
+                        cookie_header,
+                        {
+                            let res_options = res_options.clone();
+                            move |cookie| {
+                                if let Ok(value) =
+                                    header::HeaderValue::from_str(&cookie)
+                                {
+                                    res_options.append_header(
+                                        header::SET_COOKIE,
+                                        value,
+                                    );
+                                }
+                            }
+                        },
+                    );
+                    // the server function runs in a child of this scope, as
+                    // it does of the root scope when called while rendering
+                    ServerFnContext::provide(cx);
 
                     // we consume the body here (using the web::Bytes extractor), but it is required for things
                     // like MultipartForm
@@ -238,11 +336,16 @@ pub fn handle_server_fns_with_context(
                                 let has_location_set =
                                     res_parts.headers.get("Location").is_some();
                                 if !has_location_set {
-                                    let referer = req
-                                        .headers()
-                                        .get("Referer")
-                                        .and_then(|value| value.to_str().ok())
-                                        .unwrap_or("/");
+                                    let referer = form::redirect_path(data)
+                                        .or_else(|| {
+                                            req.headers()
+                                                .get("Referer")
+                                                .and_then(|value| {
+                                                    value.to_str().ok()
+                                                })
+                                                .map(String::from)
+                                        })
+                                        .unwrap_or_else(|| "/".to_string());
                                     res = HttpResponse::SeeOther();
                                     res.insert_header(("Location", referer))
                                         .content_type("application/json");
@@ -268,10 +371,10 @@ pub fn handle_server_fns_with_context(
                                     res.content_type("application/cbor");
                                     res.body(Bytes::from(data))
                                 }
+                                // the arguments are url-encoded, but the
+                                // result is serialized as JSON
                                 Payload::Url(data) => {
-                                    res.content_type(
-                                        "application/x-www-form-urlencoded",
-                                    );
+                                    res.content_type("application/json");
                                     res.body(data)
                                 }
                                 Payload::Json(data) => {
@@ -280,28 +383,40 @@ pub fn handle_server_fns_with_context(
                                 }
                             }
                         }
-                        Err(e) => HttpResponse::InternalServerError().body(
-                            serde_json::to_string(&e)
-                                .unwrap_or_else(|_| e.to_string()),
-                        ),
+                        Err(e) => {
+                            // a status set by the server function wins
+                            let status = use_context::<ResponseOptions>(cx)
+                                .and_then(|res_options| {
+                                    res_options.0.read().status
+                                })
+                                .unwrap_or_else(|| error_status(&e));
+                            HttpResponse::build(status)
+                                .content_type("application/json")
+                                .body(
+                                    serde_json::to_string(&e)
+                                        .unwrap_or_else(|_| e.to_string()),
+                                )
+                        }
                     };
                     // clean up the scope
                     disposer.dispose();
                     runtime.dispose();
                     res
                 } else {
-                    HttpResponse::BadRequest().body(format!(
-                        "Could not find a server function at the route {:?}. \
-                         \n\nIt's likely that either 
-                         1. The API prefix you specify in the `#[server]` \
-                         macro doesn't match the prefix at which your server \
-                         function handler is mounted, or \n2. You are on a \
-                         platform that doesn't support automatic server \
-                         function registration and you need to call \
-                         ServerFn::register_explicit() on the server function \
-                         type, somewhere in your `main` function.",
-                        req.path()
-                    ))
+                    HttpResponse::BadRequest()
+                        .content_type("text/plain; charset=utf-8")
+                        .body(format!(
+                            "Could not find a server function at the route {:?}. \
+                             \n\nIt's likely that either 
+                             1. The API prefix you specify in the `#[server]` \
+                             macro doesn't match the prefix at which your server \
+                             function handler is mounted, or \n2. You are on a \
+                             platform that doesn't support automatic server \
+                             function registration and you need to call \
+                             ServerFn::register_explicit() on the server function \
+                             type, somewhere in your `main` function.",
+                            req.path()
+                        ))
                 }
             }
         },
@@ -586,8 +701,9 @@ where
             let app = {
                 let app_fn = app_fn.clone();
                 let res_options = res_options.clone();
+                let options = options.clone();
                 move |cx| {
-                    provide_contexts(cx, &req, res_options);
+                    provide_contexts(cx, &options, &req, res_options);
                     (app_fn)(cx).into_view(cx)
                 }
             };
@@ -643,8 +759,9 @@ where
             let app = {
                 let app_fn = app_fn.clone();
                 let res_options = res_options.clone();
+                let options = options.clone();
                 move |cx| {
-                    provide_contexts(cx, &req, res_options);
+                    provide_contexts(cx, &options, &req, res_options);
                     (app_fn)(cx).into_view(cx)
                 }
             };
@@ -695,8 +812,9 @@ where
             let app = {
                 let app_fn = app_fn.clone();
                 let res_options = res_options.clone();
+                let options = options.clone();
                 move |cx| {
-                    provide_contexts(cx, &req, res_options);
+                    provide_contexts(cx, &options, &req, res_options);
                     (app_fn)(cx).into_view(cx)
                 }
             };
@@ -719,22 +837,231 @@ where
     }
 }
 
+/// Returns an Actix [Route](actix_web::Route) that listens for a `GET` request and serves
+/// your application with incremental static regeneration (ISR), caching the rendered pages
+/// in the given [IsrCache].
+///
+/// The first request for a page renders it like [render_app_async], and caches the HTML,
+/// including the resources that the client needs to hydrate it. The following requests are
+/// served the cached page right away. Once it is older than the cache’s time to live, or has
+/// been revalidated with [revalidate_path], the cached page is still served, and is rendered
+/// again in the background. Only one request renders a page at a time.
+///
+/// Each page is cached by its path and query. Only `200 OK` responses are cached, and without
+/// their `Set-Cookie` headers, so the cached pages should not depend on who requested them.
+/// Cached pages are served with `Cache-Control` and `Age` headers that let a CDN cache them too.
+///
+/// ```
+/// use actix_web::{App, HttpServer};
+/// use leptos::*;
+/// use leptos_actix::isr::{IsrCache, MemoryStore};
+/// use std::{net::SocketAddr, time::Duration};
+///
+/// #[component]
+/// fn MyApp(cx: Scope) -> impl IntoView {
+///     view! { cx, <main>"Hello, world!"</main> }
+/// }
+///
+/// # if false { // don't actually try to run a server in a doctest...
+/// #[actix_web::main]
+/// async fn main() -> std::io::Result<()> {
+///     let conf = get_configuration(Some("Cargo.toml")).await.unwrap();
+///     let addr = conf.leptos_options.site_addr.clone();
+///     let isr = IsrCache::new(MemoryStore::default(), Duration::from_secs(60));
+///     HttpServer::new(move || {
+///         let leptos_options = &conf.leptos_options;
+///
+///         App::new().route(
+///             "/blog/{tail:.*}",
+///             leptos_actix::render_app_isr(
+///                 leptos_options.to_owned(),
+///                 isr.clone(),
+///                 |cx| view! { cx, <MyApp/> },
+///             ),
+///         )
+///     })
+///     .bind(&addr)?
+///     .run()
+///     .await
+/// }
+/// # }
+/// ```
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [ResponseOptions]
+/// - [HttpRequest](actix_web::HttpRequest)
+/// - [MetaContext](leptos_meta::MetaContext)
+/// - [RouterIntegrationContext](leptos_router::RouterIntegrationContext)
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn render_app_isr<IV>(
+    options: LeptosOptions,
+    isr: Arc<IsrCache>,
+    app_fn: impl Fn(leptos::Scope) -> IV + Clone + 'static,
+) -> Route
+where
+    IV: IntoView,
+{
+    render_app_isr_with_context(options, isr, |_cx| {}, app_fn)
+}
+
+/// Returns an Actix [Route](actix_web::Route) that listens for a `GET` request and serves
+/// your application with incremental static regeneration (ISR), caching the rendered pages
+/// in the given [IsrCache].
+///
+/// This function allows you to provide additional information to Leptos for your route.
+/// It could be used to pass in Path Info, Connection Info, or anything your heart desires.
+/// Otherwise, it is identical to [render_app_isr].
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [ResponseOptions]
+/// - [HttpRequest](actix_web::HttpRequest)
+/// - [MetaContext](leptos_meta::MetaContext)
+/// - [RouterIntegrationContext](leptos_router::RouterIntegrationContext)
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn render_app_isr_with_context<IV>(
+    options: LeptosOptions,
+    isr: Arc<IsrCache>,
+    additional_context: impl Fn(leptos::Scope) + 'static + Clone + Send,
+    app_fn: impl Fn(leptos::Scope) -> IV + Clone + 'static,
+) -> Route
+where
+    IV: IntoView,
+{
+    let handler = move |req: HttpRequest| {
+        let render = {
+            let options = options.clone();
+            let app_fn = app_fn.clone();
+            let additional_context = additional_context.clone();
+            move |req: HttpRequest| {
+                render_isr_page(options, app_fn, additional_context, req)
+            }
+        };
+        let isr = Arc::clone(&isr);
+
+        async move {
+            let key = req
+                .uri()
+                .path_and_query()
+                .map(|path| path.as_str().to_string())
+                .unwrap_or_else(|| req.path().to_string());
+
+            match isr.lookup(&key) {
+                IsrLookup::Fresh(page) => isr_response(&isr, page),
+                IsrLookup::Stale(page, regeneration) => {
+                    if let Some(regeneration) = regeneration {
+                        actix_web::rt::spawn(async move {
+                            let (_, page) = render(req).await;
+                            regeneration.finish(page);
+                        });
+                    }
+                    isr_response(&isr, page)
+                }
+                IsrLookup::Missing(Ok(regeneration)) => {
+                    let (res, page) = render(req).await;
+                    let cacheable = page.is_cacheable();
+                    regeneration.finish(page.clone());
+                    if cacheable {
+                        isr_response(&isr, page)
+                    } else {
+                        res
+                    }
+                }
+                IsrLookup::Missing(Err(waiter)) => match waiter.wait().await {
+                    Ok(page) => isr_response(&isr, page),
+                    // the request that was rendering the page failed
+                    Err(_) => render(req).await.0,
+                },
+            }
+        }
+    };
+    web::get().to(handler)
+}
+
+// renders a page like `render_app_async_with_context`, returning both the
+// response and the page to cache
+async fn render_isr_page<IV>(
+    options: LeptosOptions,
+    app_fn: impl Fn(leptos::Scope) -> IV + Clone + 'static,
+    additional_context: impl Fn(leptos::Scope) + 'static + Clone + Send,
+    req: HttpRequest,
+) -> (HttpResponse<BoxBody>, CachedPage)
+where
+    IV: IntoView,
+{
+    let started_at = SystemTime::now();
+    let res_options = ResponseOptions::default();
+    let app = {
+        let res_options = res_options.clone();
+        let options = options.clone();
+        move |cx| {
+            provide_contexts(cx, &options, &req, res_options);
+            (app_fn)(cx).into_view(cx)
+        }
+    };
+
+    let res =
+        render_app_async_helper(&options, app, res_options, additional_context)
+            .await;
+    let (res, body) = res.into_parts();
+    let html = actix_web::body::to_bytes(body)
+        .await
+        .map(|html| String::from_utf8_lossy(&html).into_owned())
+        .unwrap_or_default();
+    let headers = res.headers().iter().filter_map(|(name, value)| {
+        Some((name.to_string(), value.to_str().ok()?.to_string()))
+    });
+    let page = CachedPage::new(
+        res.status().as_u16(),
+        headers,
+        html.clone(),
+        started_at,
+    );
+    (res.set_body(BoxBody::new(html)), page)
+}
+
+fn isr_response(isr: &IsrCache, page: CachedPage) -> HttpResponse<BoxBody> {
+    let mut res = HttpResponse::build(
+        StatusCode::from_u16(page.status).unwrap_or(StatusCode::OK),
+    );
+    for (name, value) in page.headers.iter() {
+        res.append_header((name.as_str(), value.as_str()));
+    }
+    res.insert_header((header::CACHE_CONTROL, isr.cache_control(&page)));
+    res.insert_header((header::AGE, page.age().as_secs()));
+    res.body(page.html)
+}
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 fn provide_contexts(
     cx: leptos::Scope,
+    options: &LeptosOptions,
     req: &HttpRequest,
     res_options: ResponseOptions,
 ) {
     let path = leptos_corrected_path(req);
 
     let integration = ServerIntegration { path };
+    let cookie_header = req
+        .headers()
+        .get(header::COOKIE)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    provide_context(cx, options.clone());
     provide_context(cx, RouterIntegrationContext::new(integration));
     provide_context(cx, MetaContext::new());
-    provide_context(cx, res_options);
+    provide_context(cx, res_options.clone());
     provide_context(cx, req.clone());
+    leptos::cookie::provide_server_cookies(cx, cookie_header, move |cookie| {
+        if let Ok(value) = header::HeaderValue::from_str(&cookie) {
+            res_options.append_header(header::SET_COOKIE, value);
+        }
+    });
     provide_server_redirect(cx, move |path| redirect(cx, path));
     #[cfg(feature = "nonce")]
     leptos::nonce::provide_nonce(cx);
+    ServerFnContext::provide(cx);
 }
 
 fn leptos_corrected_path(req: &HttpRequest) -> String {
@@ -785,6 +1112,17 @@ async fn stream_app_in_order(
 
     build_stream_response(options, res_options, stream, runtime, scope).await
 }
+/// Disposes of a runtime when dropped. If the client disconnects, the
+/// response stream is dropped before it finishes, and this aborts any
+/// resources that are still loading.
+struct DisposeOnDrop(RuntimeId);
+
+impl Drop for DisposeOnDrop {
+    fn drop(&mut self) {
+        self.0.dispose();
+    }
+}
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 async fn build_stream_response(
     options: &LeptosOptions,
@@ -805,6 +1143,7 @@ async fn build_stream_response(
         use_context::<MetaContext>(cx).as_ref(),
     );
 
+    let runtime = DisposeOnDrop(runtime);
     let mut stream = Box::pin(
         futures::stream::once(async move { head.clone() })
             .chain(
@@ -812,10 +1151,12 @@ async fn build_stream_response(
                     .chain(stream),
             )
             .chain(futures::stream::once(async move {
-                runtime.dispose();
-                tail.to_string()
-            }))
-            .map(|html| Ok(web::Bytes::from(html)) as Result<web::Bytes>),
+                // update any metadata that changed while the stream was resolving
+                let head_update =
+                    generate_head_update_script(cx).unwrap_or_default();
+                drop(runtime);
+                format!("{head_update}{tail}")
+            })),
     );
 
     // Get the first and second in the stream, which renders the app shell, and thus allows Resources to run
@@ -830,6 +1171,9 @@ async fn build_stream_response(
     let complete_stream =
         futures::stream::iter([first_chunk.unwrap(), second_chunk.unwrap()])
             .chain(stream);
+    let complete_stream =
+        flush_chunks(complete_stream, options.flush_stream_chunks)
+            .map(|html| Ok(web::Bytes::from(html)) as Result<web::Bytes>);
     let mut res = HttpResponse::Ok()
         .content_type("text/html")
         .streaming(complete_stream);
@@ -880,6 +1224,81 @@ async fn render_app_async_helper(
     res
 }
 
+/// Serves the `/live_reload` websocket on the `reload_port` from the [LeptosOptions], so that
+/// pages rendered in a dev environment reload when the server is rebuilt and restarted, or
+/// when watch tooling connects and sends a [`LiveReloadMessage`](leptos_integration_utils::LiveReloadMessage).
+///
+/// This must be called from within the Actix runtime. If the port is taken, for example because
+/// `cargo leptos watch` is already serving it, a warning is logged instead.
+/// ```ignore
+/// let conf = get_configuration(None).await.unwrap();
+/// leptos_actix::serve_live_reload(&conf.leptos_options);
+/// ```
+#[cfg(feature = "dev")]
+pub fn serve_live_reload(options: &LeptosOptions) {
+    let Ok(port) = u16::try_from(options.reload_port) else {
+        tracing::warn!("invalid live reload port {}", options.reload_port);
+        return;
+    };
+    let addr = std::net::SocketAddr::new(options.site_addr.ip(), port);
+    let server = actix_web::HttpServer::new(|| {
+        actix_web::App::new().route("/live_reload", web::get().to(live_reload))
+    })
+    .workers(1)
+    .bind(addr);
+    match server {
+        Ok(server) => {
+            actix_web::rt::spawn(server.run());
+        }
+        Err(e) => tracing::warn!("couldn't serve live reload on {addr}: {e}"),
+    }
+}
+
+/// An Actix handler for the `/live_reload` websocket, which sends [`LiveReloadMessage`](leptos_integration_utils::LiveReloadMessage)s
+/// to the page, and relays the ones it receives to every connected page. See [serve_live_reload].
+#[cfg(feature = "dev")]
+pub async fn live_reload(
+    req: HttpRequest,
+    mut payload: web::Payload,
+) -> Result<HttpResponse, actix_web::Error> {
+    use actix_codec::{Decoder, Encoder};
+    use actix_http::{body::BodyStream, ws};
+    use leptos_integration_utils::LiveReload;
+
+    let mut res = ws::handshake(req.head())?;
+
+    // relay the messages sent by this client
+    actix_web::rt::spawn(async move {
+        let mut codec = ws::Codec::new();
+        let mut buf = web::BytesMut::new();
+        while let Some(Ok(chunk)) = payload.next().await {
+            buf.extend_from_slice(&chunk);
+            while let Ok(Some(frame)) = codec.decode(&mut buf) {
+                match frame {
+                    ws::Frame::Text(text) => {
+                        if let Ok(text) = String::from_utf8(text.to_vec()) {
+                            LiveReload::global().broadcast(text);
+                        }
+                    }
+                    ws::Frame::Close(_) => return,
+                    _ => {}
+                }
+            }
+        }
+    });
+
+    // and send it every message that is broadcast
+    let mut codec = ws::Codec::new();
+    let frames = LiveReload::global().subscribe().map(move |text| {
+        let mut buf = web::BytesMut::new();
+        codec
+            .encode(ws::Message::Text(text.into()), &mut buf)
+            .map(|_| buf.freeze())
+    });
+    let res = res.message_body(BoxBody::new(BodyStream::new(frames)))?;
+    Ok(HttpResponse::from(res))
+}
+
 /// Generates a list of all routes defined in Leptos's Router in your app. We can then use this to automatically
 /// create routes in Actix's App without having to use wildcard matching or fallbacks. Takes in your root app Element
 /// as an argument so it can walk you app tree. This version is tailored to generated Actix compatible paths.
@@ -1012,6 +1431,14 @@ where
         for listing in paths.iter() {
             let path = listing.path();
             let mode = listing.mode();
+            // lets the app find out how it is being rendered, with use_ssr_mode()
+            let additional_context = {
+                let additional_context = additional_context.clone();
+                move |cx: leptos::Scope| {
+                    provide_context(cx, mode);
+                    additional_context(cx);
+                }
+            };
 
             for method in listing.methods() {
                 router = router.route(
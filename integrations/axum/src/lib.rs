@@ -6,7 +6,7 @@
 //! directory in the Leptos repository.
 
 use axum::{
-    body::{Body, Bytes, Full, StreamBody},
+    body::{Body, Bytes, Full, HttpBody, StreamBody},
     extract::{FromRef, FromRequestParts, Path, RawQuery},
     http::{
         header::{HeaderName, HeaderValue},
@@ -25,17 +25,25 @@ use http::{
 };
 use hyper::body;
 use leptos::{
-    leptos_server::{server_fn_by_path, Payload},
-    server_fn::Encoding,
+    leptos_server::{server_fn_by_path, Payload, ServerFnContext},
+    server_fn::{form, Encoding},
     ssr::*,
     *,
 };
-use leptos_integration_utils::{build_async_response, html_parts_separated};
-use leptos_meta::{generate_head_metadata_separated, MetaContext};
+pub use leptos_integration_utils::isr::{self, revalidate_path, IsrCache};
+use leptos_integration_utils::{
+    build_async_response, flush_chunks, html_parts_separated,
+    isr::{CachedPage, IsrLookup},
+};
+use leptos_meta::{
+    generate_head_metadata_separated, generate_head_update_script, MetaContext,
+};
 use leptos_router::*;
 use once_cell::sync::OnceCell;
 use parking_lot::RwLock;
-use std::{io, pin::Pin, sync::Arc, thread::available_parallelism};
+use std::{
+    io, pin::Pin, sync::Arc, thread::available_parallelism, time::SystemTime,
+};
 use tokio::task::LocalSet;
 use tokio_util::task::LocalPoolHandle;
 use tracing::Instrument;
@@ -192,7 +200,99 @@ pub async fn handle_server_fns(
     RawQuery(query): RawQuery,
     req: Request<Body>,
 ) -> impl IntoResponse {
-    handle_server_fns_inner(fn_name, headers, query, |_| {}, req).await
+    handle_server_fns_inner(
+        fn_name,
+        headers,
+        query,
+        |_| {},
+        default_error_status,
+        req,
+    )
+    .await
+}
+
+/// Returns an Axum [Handler](axum::handler::Handler) that listens for a request with Leptos
+/// server function arguments, like [handle_server_fns], and responds with the status code
+/// returned by `error_status` when a server function returns an error.
+///
+/// By default, an error is sent with the status code of [ServerFnError::status_code]. A status
+/// set by the server function itself with [ResponseOptions::set_status] is used instead of
+/// either. The client deserializes the error from the body of the response for any status
+/// code that isn't successful.
+///
+/// ```
+/// use axum::{http::StatusCode, routing::post, Router};
+/// use leptos::*;
+///
+/// # if false { // don't actually try to run a server in a doctest...
+/// let app: Router = Router::new().route(
+///     "/api/*fn_name",
+///     post(leptos_axum::handle_server_fns_with_error_mapper(
+///         |err: &ServerFnError| match err {
+///             ServerFnError::MissingArg(_) => StatusCode::UNPROCESSABLE_ENTITY,
+///             err => StatusCode::from_u16(err.status_code()).unwrap(),
+///         },
+///     )),
+/// );
+/// # }
+/// ```
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [RequestParts]
+/// - [ResponseOptions]
+pub fn handle_server_fns_with_error_mapper(
+    error_status: impl Fn(&ServerFnError) -> StatusCode + Clone + Send + 'static,
+) -> impl Fn(
+    Path<String>,
+    HeaderMap,
+    RawQuery,
+    Request<Body>,
+) -> PinnedServerFnResponse
+       + Clone
+       + Send
+       + 'static {
+    handle_server_fns_with_context_and_error_mapper(|_| {}, error_status)
+}
+
+/// Returns an Axum [Handler](axum::handler::Handler) that listens for a request with Leptos
+/// server function arguments, like [handle_server_fns_with_context], and responds with the
+/// status code returned by `error_status` when a server function returns an error, like
+/// [handle_server_fns_with_error_mapper].
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [RequestParts]
+/// - [ResponseOptions]
+pub fn handle_server_fns_with_context_and_error_mapper(
+    additional_context: impl Fn(leptos::Scope) + 'static + Clone + Send,
+    error_status: impl Fn(&ServerFnError) -> StatusCode + Clone + Send + 'static,
+) -> impl Fn(
+    Path<String>,
+    HeaderMap,
+    RawQuery,
+    Request<Body>,
+) -> PinnedServerFnResponse
+       + Clone
+       + Send
+       + 'static {
+    move |Path(fn_name), headers, RawQuery(query), req| {
+        Box::pin(handle_server_fns_inner(
+            fn_name,
+            headers,
+            query,
+            additional_context.clone(),
+            error_status.clone(),
+            req,
+        ))
+    }
+}
+
+// the status of an error response, unless the server function or the app
+// set another one
+fn default_error_status(err: &ServerFnError) -> StatusCode {
+    StatusCode::from_u16(err.status_code())
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
 }
 
 /// An Axum handlers to listens for a request with Leptos server function arguments in the body,
@@ -217,8 +317,15 @@ pub async fn handle_server_fns_with_context(
     additional_context: impl Fn(leptos::Scope) + 'static + Clone + Send,
     req: Request<Body>,
 ) -> impl IntoResponse {
-    handle_server_fns_inner(fn_name, headers, query, additional_context, req)
-        .await
+    handle_server_fns_inner(
+        fn_name,
+        headers,
+        query,
+        additional_context,
+        default_error_status,
+        req,
+    )
+    .await
 }
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 async fn handle_server_fns_inner(
@@ -226,8 +333,9 @@ async fn handle_server_fns_inner(
     headers: HeaderMap,
     query: Option<String>,
     additional_context: impl Fn(leptos::Scope) + 'static + Clone + Send,
+    error_status: impl Fn(&ServerFnError) -> StatusCode + Send + 'static,
     req: Request<Body>,
-) -> impl IntoResponse {
+) -> Response<Full<Bytes>> {
     // Axum Path extractor doesn't remove the first slash from the path, while Actix does
     let fn_name = fn_name
         .strip_prefix('/')
@@ -238,7 +346,7 @@ async fn handle_server_fns_inner(
     let pool_handle = get_leptos_pool();
     pool_handle.spawn_pinned(move || {
         async move {
-            let res = if let Some(server_fn) =
+            let mut res = if let Some(server_fn) =
                 server_fn_by_path(fn_name.as_str())
             {
                 let runtime = create_runtime();
@@ -247,10 +355,29 @@ async fn handle_server_fns_inner(
                 additional_context(cx);
 
                 let (req, req_parts) = generate_request_and_parts(req).await;
+                let cookie_header = req_parts
+                    .headers
+                    .get(header::COOKIE)
+                    .and_then(|value| value.to_str().ok())
+                    .map(String::from);
                 provide_context(cx, req_parts.clone());
                 provide_context(cx, ExtractorHelper::from(req));
                 // Add this so that we can set headers and status of the response
-                provide_context(cx, ResponseOptions::default());
+                let res_options = ResponseOptions::default();
+                provide_context(cx, res_options.clone());
+                leptos::cookie::provide_server_cookies(
+                    cx,
+                    cookie_header,
+                    move |cookie| {
+                        if let Ok(value) = HeaderValue::from_str(&cookie) {
+                            res_options
+                                .append_header(header::SET_COOKIE, value);
+                        }
+                    },
+                );
+                // the server function runs in a child of this scope, as it
+                // does of the root scope when called while rendering
+                ServerFnContext::provide(cx);
 
                 let query: &Bytes = &query.unwrap_or("".to_string()).into();
                 let data = match &server_fn.encoding() {
@@ -286,10 +413,14 @@ async fn handle_server_fns_inner(
                         }
                         // otherwise, it's probably a <form> submit or something: redirect back to the referrer
                         else {
-                            let referer = headers
-                                .get("Referer")
-                                .and_then(|value| value.to_str().ok())
-                                .unwrap_or("/");
+                            let referer = form::redirect_path(data)
+                                .or_else(|| {
+                                    headers
+                                        .get("Referer")
+                                        .and_then(|value| value.to_str().ok())
+                                        .map(String::from)
+                                })
+                                .unwrap_or_else(|| "/".to_string());
 
                             res = res
                                 .status(StatusCode::SEE_OTHER)
@@ -309,31 +440,39 @@ async fn handle_server_fns_inner(
                             Payload::Binary(data) => res
                                 .header("Content-Type", "application/cbor")
                                 .body(Full::from(data)),
+                            // the arguments are url-encoded, but the result
+                            // is serialized as JSON
                             Payload::Url(data) => res
-                                .header(
-                                    "Content-Type",
-                                    "application/x-www-form-urlencoded",
-                                )
+                                .header("Content-Type", "application/json")
                                 .body(Full::from(data)),
                             Payload::Json(data) => res
                                 .header("Content-Type", "application/json")
                                 .body(Full::from(data)),
                         }
                     }
-                    Err(e) => Response::builder()
-                        .status(StatusCode::INTERNAL_SERVER_ERROR)
-                        .body(Full::from(
-                            serde_json::to_string(&e)
-                                .unwrap_or_else(|_| e.to_string()),
-                        )),
+                    Err(e) => {
+                        // a status set by the server function wins
+                        let status = use_context::<ResponseOptions>(cx)
+                            .and_then(|res_options| res_options.0.read().status)
+                            .unwrap_or_else(|| error_status(&e));
+                        Response::builder()
+                            .status(status)
+                            .header("Content-Type", "application/json")
+                            .body(Full::from(
+                                serde_json::to_string(&e)
+                                    .unwrap_or_else(|_| e.to_string()),
+                            ))
+                    }
                 };
                 // clean up the scope
                 disposer.dispose();
                 runtime.dispose();
                 res
             } else {
-                Response::builder().status(StatusCode::BAD_REQUEST).body(
-                    Full::from(format!(
+                Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .header("Content-Type", "text/plain; charset=utf-8")
+                    .body(Full::from(format!(
                         "Could not find a server function at the route \
                          {fn_name}. \n\nIt's likely that either 
                          1. The API prefix you specify in the `#[server]` \
@@ -343,10 +482,15 @@ async fn handle_server_fns_inner(
                          function registration and you need to call \
                          ServerFn::register_explicit() on the server function \
                          type, somewhere in your `main` function.",
-                    )),
-                )
+                    )))
             }
             .expect("could not build Response");
+            // the whole body is known, so compression middleware can tell
+            // how large it is before deciding to compress it
+            if let Some(len) = res.body().size_hint().exact() {
+                res.headers_mut()
+                    .insert(header::CONTENT_LENGTH, HeaderValue::from(len));
+            }
 
             _ = tx.send(res);
         }
@@ -358,6 +502,9 @@ async fn handle_server_fns_inner(
 pub type PinnedHtmlStream =
     Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send>>;
 
+pub type PinnedServerFnResponse =
+    Pin<Box<dyn Future<Output = Response<Full<Bytes>>> + Send + 'static>>;
+
 /// Returns an Axum [Handler](axum::handler::Handler) that listens for a `GET` request and tries
 /// to route it using [leptos_router], serving an HTML stream of your application.
 ///
@@ -605,6 +752,7 @@ where
             let res_options3 = default_res_options.clone();
             let local_pool = get_leptos_pool();
             let (tx, rx) = futures::channel::mpsc::channel(8);
+            let flush_stream_chunks = options.flush_stream_chunks;
 
             let current_span = tracing::Span::current();
             local_pool.spawn_pinned(move || async move {
@@ -616,8 +764,9 @@ where
 
                     let full_path = format!("http://leptos.dev{path}");
                     let (req, req_parts) = generate_request_and_parts(req).await;
+                    let options = options.clone();
                     move |cx| {
-                        provide_contexts(cx, full_path, req_parts, req.into(), default_res_options);
+                        provide_contexts(cx, &options, full_path, req_parts, req.into(), default_res_options);
                         app_fn(cx).into_view(cx)
                     }
                 };
@@ -634,7 +783,7 @@ where
                     runtime.dispose();
             }.instrument(current_span));
 
-            generate_response(res_options3, rx)
+            generate_response(res_options3, rx, flush_stream_chunks)
         })
     }
 }
@@ -643,8 +792,9 @@ where
 async fn generate_response(
     res_options: ResponseOptions,
     rx: Receiver<String>,
+    flush_stream_chunks: bool,
 ) -> Response<StreamBody<PinnedHtmlStream>> {
-    let mut stream = Box::pin(rx.map(|html| Ok(Bytes::from(html))));
+    let mut stream = Box::pin(rx);
 
     // Get the first and second chunks in the stream, which renders the app shell, and thus allows Resources to run
     let first_chunk = stream.next().await;
@@ -656,11 +806,17 @@ async fn generate_response(
     let complete_stream =
         futures::stream::iter([first_chunk.unwrap(), second_chunk.unwrap()])
             .chain(stream);
+    let complete_stream = flush_chunks(complete_stream, flush_stream_chunks)
+        .map(|html| Ok(Bytes::from(html)));
 
     let mut res = Response::new(StreamBody::new(
         Box::pin(complete_stream) as PinnedHtmlStream
     ));
 
+    res.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/html; charset=utf-8"),
+    );
     if let Some(status) = res_options.status {
         *res.status_mut() = status
     }
@@ -691,9 +847,15 @@ async fn forward_stream(
     _ = tx.send(head).await;
     _ = tx.send(first_app_chunk).await;
     while let Some(fragment) = shell.next().await {
-        _ = tx.send(fragment).await;
+        // the client has disconnected, so stop rendering; disposing of the
+        // runtime aborts any resources that are still loading
+        if tx.send(fragment).await.is_err() {
+            return;
+        }
     }
-    _ = tx.send(tail.to_string()).await;
+    // update any metadata that changed while the stream was resolving
+    let head_update = generate_head_update_script(cx).unwrap_or_default();
+    _ = tx.send(format!("{head_update}{tail}")).await;
 
     // Extract the value of ResponseOptions from here
     let res_options = use_context::<ResponseOptions>(cx).unwrap();
@@ -770,14 +932,16 @@ where
                 let full_path = format!("http://leptos.dev{path}");
 
                 let (tx, rx) = futures::channel::mpsc::channel(8);
+                let flush_stream_chunks = options.flush_stream_chunks;
                 let local_pool = get_leptos_pool();
                 let current_span = tracing::Span::current();
                 local_pool.spawn_pinned(|| async move {
                     let app = {
                         let full_path = full_path.clone();
                         let (req, req_parts) = generate_request_and_parts(req).await;
+                        let options = options.clone();
                         move |cx| {
-                            provide_contexts(cx, full_path, req_parts, req.into(), default_res_options);
+                            provide_contexts(cx, &options, full_path, req_parts, req.into(), default_res_options);
                             app_fn(cx).into_view(cx)
                         }
                     };
@@ -794,7 +958,7 @@ where
                     runtime.dispose();
                 }.instrument(current_span));
 
-                generate_response(res_options3, rx).await
+                generate_response(res_options3, rx, flush_stream_chunks).await
             }
         })
     }
@@ -803,20 +967,33 @@ where
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 fn provide_contexts(
     cx: Scope,
+    options: &LeptosOptions,
     path: String,
     req_parts: RequestParts,
     extractor: ExtractorHelper,
     default_res_options: ResponseOptions,
 ) {
     let integration = ServerIntegration { path };
+    let cookie_header = req_parts
+        .headers
+        .get(header::COOKIE)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    provide_context(cx, options.clone());
     provide_context(cx, RouterIntegrationContext::new(integration));
     provide_context(cx, MetaContext::new());
     provide_context(cx, req_parts);
     provide_context(cx, extractor);
-    provide_context(cx, default_res_options);
+    provide_context(cx, default_res_options.clone());
+    leptos::cookie::provide_server_cookies(cx, cookie_header, move |cookie| {
+        if let Ok(value) = HeaderValue::from_str(&cookie) {
+            default_res_options.append_header(header::SET_COOKIE, value);
+        }
+    });
     provide_server_redirect(cx, move |path| redirect(cx, path));
     #[cfg(feature = "nonce")]
     leptos::nonce::provide_nonce(cx);
+    ServerFnContext::provide(cx);
 }
 
 /// Returns an Axum [Handler](axum::handler::Handler) that listens for a `GET` request and tries
@@ -950,8 +1127,9 @@ where
                         let app = {
                             let full_path = full_path.clone();
                             let (req, req_parts) = generate_request_and_parts(req).await;
+                            let options = options.clone();
                             move |cx| {
-                                provide_contexts(cx, full_path, req_parts, req.into(), default_res_options);
+                                provide_contexts(cx, &options, full_path, req_parts, req.into(), default_res_options);
                                 app_fn(cx).into_view(cx)
                             }
                         };
@@ -982,6 +1160,10 @@ where
                 let html = rx.await.expect("to complete HTML rendering");
 
                 let mut res = Response::new(html);
+                res.headers_mut().insert(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("text/html; charset=utf-8"),
+                );
 
                 let res_options = res_options3.0.read();
 
@@ -997,6 +1179,249 @@ where
     }
 }
 
+/// Returns an Axum [Handler](axum::handler::Handler) that serves your application with
+/// incremental static regeneration (ISR), caching the rendered pages in the given [IsrCache].
+///
+/// The first request for a page renders it like [render_app_async], and caches the HTML,
+/// including the resources that the client needs to hydrate it. The following requests are
+/// served the cached page right away. Once it is older than the cache’s time to live, or has
+/// been revalidated with [revalidate_path], the cached page is still served, and is rendered
+/// again in the background. Only one request renders a page at a time.
+///
+/// Each page is cached by its path and query. Only `200 OK` responses are cached, and without
+/// their `Set-Cookie` headers, so the cached pages should not depend on who requested them.
+/// Cached pages are served with `Cache-Control` and `Age` headers that let a CDN cache them too.
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use leptos::*;
+/// use leptos_axum::isr::{IsrCache, MemoryStore};
+/// use std::time::Duration;
+///
+/// #[component]
+/// fn MyApp(cx: Scope) -> impl IntoView {
+///     view! { cx, <main>"Hello, world!"</main> }
+/// }
+///
+/// # if false { // don't actually try to run a server in a doctest...
+/// #[tokio::main]
+/// async fn main() {
+///     let conf = get_configuration(Some("Cargo.toml")).await.unwrap();
+///     let leptos_options = conf.leptos_options;
+///     let addr = leptos_options.site_addr.clone();
+///     let isr = IsrCache::new(MemoryStore::default(), Duration::from_secs(60));
+///
+///     let app = Router::new().route(
+///         "/blog/*slug",
+///         get(leptos_axum::render_app_isr(leptos_options, isr, |cx| {
+///             view! { cx, <MyApp/> }
+///         })),
+///     );
+///
+///     axum::Server::bind(&addr)
+///         .serve(app.into_make_service())
+///         .await
+///         .unwrap();
+/// }
+/// # }
+/// ```
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [RequestParts]
+/// - [ResponseOptions]
+/// - [MetaContext](leptos_meta::MetaContext)
+/// - [RouterIntegrationContext](leptos_router::RouterIntegrationContext)
+#[tracing::instrument(level = "info", fields(error), skip_all)]
+pub fn render_app_isr<IV>(
+    options: LeptosOptions,
+    isr: Arc<IsrCache>,
+    app_fn: impl Fn(leptos::Scope) -> IV + Clone + Send + 'static,
+) -> impl Fn(
+    Request<Body>,
+) -> Pin<Box<dyn Future<Output = Response<String>> + Send + 'static>>
+       + Clone
+       + Send
+       + 'static
+where
+    IV: IntoView,
+{
+    render_app_isr_with_context(options, isr, |_| {}, app_fn)
+}
+
+/// Returns an Axum [Handler](axum::handler::Handler) that serves your application with
+/// incremental static regeneration (ISR), caching the rendered pages in the given [IsrCache].
+///
+/// This version allows us to pass Axum State/Extension/Extractor or other infro from Axum or network
+/// layers above Leptos itself, like [render_app_async_with_context]. Otherwise, this function is
+/// identical to [render_app_isr].
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [RequestParts]
+/// - [ResponseOptions]
+/// - [MetaContext](leptos_meta::MetaContext)
+/// - [RouterIntegrationContext](leptos_router::RouterIntegrationContext)
+#[tracing::instrument(level = "info", fields(error), skip_all)]
+pub fn render_app_isr_with_context<IV>(
+    options: LeptosOptions,
+    isr: Arc<IsrCache>,
+    additional_context: impl Fn(leptos::Scope) + 'static + Clone + Send,
+    app_fn: impl Fn(leptos::Scope) -> IV + Clone + Send + 'static,
+) -> impl Fn(
+    Request<Body>,
+) -> Pin<Box<dyn Future<Output = Response<String>> + Send + 'static>>
+       + Clone
+       + Send
+       + 'static
+where
+    IV: IntoView,
+{
+    let render =
+        render_app_async_with_context(options, additional_context, app_fn);
+
+    move |req: Request<Body>| {
+        let render = render.clone();
+        let isr = Arc::clone(&isr);
+        Box::pin(async move {
+            let key = req
+                .uri()
+                .path_and_query()
+                .map(|path| path.as_str().to_string())
+                .unwrap_or_else(|| req.uri().path().to_string());
+
+            match isr.lookup(&key) {
+                IsrLookup::Fresh(page) => isr_response(&isr, page),
+                IsrLookup::Stale(page, regeneration) => {
+                    if let Some(regeneration) = regeneration {
+                        tokio::spawn(async move {
+                            let started_at = SystemTime::now();
+                            let res = render(req).await;
+                            regeneration.finish(cached_page(&res, started_at));
+                        });
+                    }
+                    isr_response(&isr, page)
+                }
+                IsrLookup::Missing(Ok(regeneration)) => {
+                    let started_at = SystemTime::now();
+                    let res = render(req).await;
+                    let page = cached_page(&res, started_at);
+                    let cacheable = page.is_cacheable();
+                    regeneration.finish(page.clone());
+                    if cacheable {
+                        isr_response(&isr, page)
+                    } else {
+                        res
+                    }
+                }
+                IsrLookup::Missing(Err(waiter)) => match waiter.wait().await {
+                    Ok(page) => isr_response(&isr, page),
+                    // the request that was rendering the page failed
+                    Err(_) => render(req).await,
+                },
+            }
+        })
+    }
+}
+
+fn cached_page(res: &Response<String>, started_at: SystemTime) -> CachedPage {
+    let headers = res.headers().iter().filter_map(|(name, value)| {
+        Some((name.to_string(), value.to_str().ok()?.to_string()))
+    });
+    CachedPage::new(
+        res.status().as_u16(),
+        headers,
+        res.body().clone(),
+        started_at,
+    )
+}
+
+fn isr_response(isr: &IsrCache, page: CachedPage) -> Response<String> {
+    let mut res = Response::new(String::new());
+    *res.status_mut() =
+        StatusCode::from_u16(page.status).unwrap_or(StatusCode::OK);
+    for (name, value) in page.headers.iter() {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            res.headers_mut().append(name, value);
+        }
+    }
+    if let Ok(cache_control) = HeaderValue::from_str(&isr.cache_control(&page))
+    {
+        res.headers_mut()
+            .insert(header::CACHE_CONTROL, cache_control);
+    }
+    res.headers_mut()
+        .insert(header::AGE, HeaderValue::from(page.age().as_secs()));
+    *res.body_mut() = page.html;
+    res
+}
+
+/// Serves the `/live_reload` websocket on the `reload_port` from the [LeptosOptions], so that
+/// pages rendered in a dev environment reload when the server is rebuilt and restarted, or
+/// when watch tooling connects and sends a [`LiveReloadMessage`](leptos_integration_utils::LiveReloadMessage).
+///
+/// This must be called from within a Tokio runtime. If the port is taken, for example because
+/// `cargo leptos watch` is already serving it, a warning is logged instead.
+/// ```ignore
+/// let conf = get_configuration(None).await.unwrap();
+/// leptos_axum::serve_live_reload(&conf.leptos_options);
+/// ```
+#[cfg(feature = "dev")]
+pub fn serve_live_reload(options: &LeptosOptions) {
+    let Ok(port) = u16::try_from(options.reload_port) else {
+        tracing::warn!("invalid live reload port {}", options.reload_port);
+        return;
+    };
+    let addr = std::net::SocketAddr::new(options.site_addr.ip(), port);
+    tokio::spawn(async move {
+        let app = axum::Router::new().route("/live_reload", get(live_reload));
+        match axum::Server::try_bind(&addr) {
+            Ok(server) => {
+                if let Err(e) = server.serve(app.into_make_service()).await {
+                    tracing::error!("live reload server stopped: {e}");
+                }
+            }
+            Err(e) => {
+                tracing::warn!("couldn't serve live reload on {addr}: {e}")
+            }
+        }
+    });
+}
+
+/// An Axum handler for the `/live_reload` websocket, which sends [`LiveReloadMessage`](leptos_integration_utils::LiveReloadMessage)s
+/// to the page, and relays the ones it receives to every connected page. See [serve_live_reload].
+#[cfg(feature = "dev")]
+pub async fn live_reload(
+    ws: axum::extract::WebSocketUpgrade,
+) -> axum::response::Response {
+    use axum::extract::ws::Message;
+    use leptos_integration_utils::LiveReload;
+
+    ws.on_upgrade(|socket| async move {
+        let (mut sender, mut receiver) = socket.split();
+        let mut messages = LiveReload::global().subscribe();
+        let forward = async move {
+            while let Some(msg) = messages.next().await {
+                if sender.send(Message::Text(msg)).await.is_err() {
+                    break;
+                }
+            }
+        };
+        let relay = async move {
+            while let Some(Ok(msg)) = receiver.next().await {
+                if let Message::Text(text) = msg {
+                    LiveReload::global().broadcast(text);
+                }
+            }
+        };
+        futures::pin_mut!(forward, relay);
+        futures::future::select(forward, relay).await;
+    })
+}
+
 /// Generates a list of all routes defined in Leptos's Router in your app. We can then use this to automatically
 /// create routes in Axum's Router without having to use wildcard matching or fallbacks. Takes in your root app Element
 /// as an argument so it can walk you app tree. This version is tailored to generate Axum compatible paths.
@@ -1146,11 +1571,20 @@ where
         let mut router = self;
         for listing in paths.iter() {
             let path = listing.path();
+            let mode = listing.mode();
+            // lets the app find out how it is being rendered, with use_ssr_mode()
+            let additional_context = {
+                let additional_context = additional_context.clone();
+                move |cx: leptos::Scope| {
+                    provide_context(cx, mode);
+                    additional_context(cx);
+                }
+            };
 
             for method in listing.methods() {
                 router = router.route(
                     path,
-                    match listing.mode() {
+                    match mode {
                         SsrMode::OutOfOrder => {
                             let s = render_app_to_stream_with_context(
                                 LeptosOptions::from_ref(options),
@@ -0,0 +1,95 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use leptos::*;
+use leptos_router::*;
+use std::sync::atomic::{AtomicI32, Ordering};
+use tower::ServiceExt;
+
+static COUNT: AtomicI32 = AtomicI32::new(0);
+
+#[server(AddCount, "/api")]
+pub async fn add_count(amount: i32) -> Result<i32, ServerFnError> {
+    Ok(COUNT.fetch_add(amount, Ordering::SeqCst) + amount)
+}
+
+#[component]
+fn Counter(cx: Scope) -> impl IntoView {
+    let add_count = create_server_action::<AddCount>(cx);
+    view! { cx,
+        <ActionForm action=add_count>
+            <input type="number" name="amount"/>
+            <input type="submit" value="Add"/>
+        </ActionForm>
+    }
+}
+
+#[component]
+fn App(cx: Scope) -> impl IntoView {
+    view! { cx,
+        <Router>
+            <Routes>
+                <Route path="/counter" view=Counter/>
+            </Routes>
+        </Router>
+    }
+}
+
+// returns the value of the first `attr` after `after` in `html`
+fn attr_after<'a>(html: &'a str, after: &str, attr: &str) -> &'a str {
+    let start = html.find(after).expect("element should be rendered");
+    let html = &html[start..];
+    let value =
+        &html[html.find(&format!("{attr}=\"")).unwrap() + attr.len() + 2..];
+    &value[..value.find('"').unwrap()]
+}
+
+#[tokio::test]
+async fn action_form_works_without_wasm() {
+    let options = LeptosOptions::builder().output_name("action_form").build();
+    let app = Router::new()
+        .route("/api/*fn_name", post(leptos_axum::handle_server_fns))
+        .route(
+            "/counter",
+            get(leptos_axum::render_app_to_stream(options, |cx| {
+                view! { cx, <App/> }
+            })),
+        );
+
+    let res = app
+        .clone()
+        .oneshot(Request::get("/counter").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let html = hyper::body::to_bytes(res.into_body()).await.unwrap();
+    let html = String::from_utf8(html.to_vec()).unwrap();
+
+    assert_eq!(attr_after(&html, "<form", "method"), "post");
+    let action = attr_after(&html, "<form", "action");
+    assert!(action.starts_with("/api/add_count"), "{action}");
+    let redirect = attr_after(&html, "name=\"__leptos_redirect\"", "value");
+    assert_eq!(redirect, "/counter");
+
+    // submit the form like a browser would before WASM has loaded
+    let res = app
+        .oneshot(
+            Request::post(action)
+                .header(
+                    header::CONTENT_TYPE,
+                    "application/x-www-form-urlencoded",
+                )
+                .body(Body::from(format!(
+                    "__leptos_redirect={redirect}&amount=3"
+                )))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::SEE_OTHER);
+    assert_eq!(res.headers()[header::LOCATION], "/counter");
+    assert_eq!(COUNT.load(Ordering::SeqCst), 3);
+}
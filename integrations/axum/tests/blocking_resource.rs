@@ -0,0 +1,77 @@
+use axum::{body::Body, http::Request, routing::get, Router};
+use futures::channel::oneshot;
+use hyper::body::{Buf, HttpBody};
+use leptos::*;
+use leptos_meta::*;
+use std::sync::Mutex;
+use tower::ServiceExt;
+
+// lets the test decide when the slow, non-blocking resource resolves
+static RELEASE_REVIEWS: Mutex<Option<oneshot::Receiver<()>>> = Mutex::new(None);
+
+async fn product_name() -> String {
+    tokio::task::yield_now().await;
+    "Leptos Mug".to_string()
+}
+
+async fn reviews() -> String {
+    let release = RELEASE_REVIEWS.lock().unwrap().take();
+    if let Some(release) = release {
+        _ = release.await;
+    }
+    "Five stars".to_string()
+}
+
+#[component]
+fn Product(cx: Scope) -> impl IntoView {
+    provide_meta_context(cx);
+    let product = create_blocking_resource(cx, || (), |_| product_name());
+    let reviews = create_resource(cx, || (), |_| reviews());
+
+    view! { cx,
+        // read outside any <Suspense/>, but still in the initial <head>
+        <Title text=move || {
+            product.get().map(|name| format!("{name} | Shop")).unwrap_or_default()
+        }/>
+        <Suspense fallback=move || view! { cx, <p>"Loading reviews..."</p> }>
+            {move || reviews.get().map(|reviews| view! { cx, <p>{reviews}</p> })}
+        </Suspense>
+    }
+}
+
+async fn next_chunk(body: &mut (impl HttpBody + Unpin)) -> Option<String> {
+    let chunk = body.data().await?.ok()?;
+    Some(String::from_utf8_lossy(chunk.chunk()).into_owned())
+}
+
+#[tokio::test]
+async fn blocking_resources_are_in_the_first_chunk() {
+    let (release, released) = oneshot::channel();
+    *RELEASE_REVIEWS.lock().unwrap() = Some(released);
+
+    let options = LeptosOptions::builder().output_name("blocking").build();
+    let app = Router::new().route(
+        "/product",
+        get(leptos_axum::render_app_to_stream(options, |cx| {
+            view! { cx, <Product/> }
+        })),
+    );
+
+    let res = app
+        .oneshot(Request::get("/product").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let mut body = res.into_body();
+    let head = next_chunk(&mut body).await.unwrap();
+    assert!(head.contains("<title>Leptos Mug | Shop</title>"), "{head}");
+    let shell = next_chunk(&mut body).await.unwrap();
+    assert!(shell.contains("Loading reviews..."), "{shell}");
+    assert!(!shell.contains("Five stars"), "{shell}");
+
+    release.send(()).unwrap();
+    let mut rest = String::new();
+    while let Some(chunk) = next_chunk(&mut body).await {
+        rest.push_str(&chunk);
+    }
+    assert!(rest.contains("Five stars"), "{rest}");
+}
@@ -0,0 +1,122 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use flate2::read::GzDecoder;
+use leptos::*;
+use std::io::Read;
+use tower::ServiceExt;
+use tower_http::compression::CompressionLayer;
+
+#[server(ListItems, "/api")]
+pub async fn list_items(count: usize) -> Result<Vec<String>, ServerFnError> {
+    if count > 1_000 {
+        return Err(ServerFnError::ServerError("too many items".into()));
+    }
+    Ok((0..count).map(|i| format!("item number {i}")).collect())
+}
+
+fn request(body: &str, encoding: Option<&str>) -> Request<Body> {
+    let req = Request::post(format!("/api/{}", ListItems::url()))
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .header(header::ACCEPT, "application/json");
+    match encoding {
+        Some(encoding) => req.header(header::ACCEPT_ENCODING, encoding),
+        None => req,
+    }
+    .body(Body::from(body.to_string()))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn server_fn_responses_are_compressed_by_a_compression_layer() {
+    let app = Router::new()
+        .route("/api/*fn_name", post(leptos_axum::handle_server_fns))
+        .layer(CompressionLayer::new());
+
+    let res = app
+        .oneshot(request("count=200", Some("gzip")))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[header::CONTENT_ENCODING], "gzip");
+    assert_eq!(res.headers()[header::CONTENT_TYPE], "application/json");
+
+    let compressed = hyper::body::to_bytes(res.into_body()).await.unwrap();
+    let mut json = String::new();
+    GzDecoder::new(&compressed[..])
+        .read_to_string(&mut json)
+        .unwrap();
+    assert!(compressed.len() < json.len());
+    let items: Vec<String> = serde_json::from_str(&json).unwrap();
+    assert_eq!(items.len(), 200);
+    assert_eq!(items[199], "item number 199");
+}
+
+#[tokio::test]
+async fn server_fn_responses_have_a_type_and_length() {
+    let app = Router::new()
+        .route("/api/*fn_name", post(leptos_axum::handle_server_fns));
+
+    for (body, status) in [
+        ("count=3", StatusCode::OK),
+        ("count=5000", StatusCode::INTERNAL_SERVER_ERROR),
+    ] {
+        let res = app.clone().oneshot(request(body, None)).await.unwrap();
+        assert_eq!(res.status(), status);
+        assert_eq!(res.headers()[header::CONTENT_TYPE], "application/json");
+        let len: usize = res.headers()[header::CONTENT_LENGTH]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body.len(), len);
+    }
+}
+
+fn poll_all(
+    stream: impl futures::Stream<Item = String>,
+) -> Vec<Option<String>> {
+    use futures::{task::noop_waker_ref, StreamExt};
+    use std::task::{Context, Poll};
+
+    let mut stream = Box::pin(stream);
+    let mut cx = Context::from_waker(noop_waker_ref());
+    let mut polled = Vec::new();
+    loop {
+        match stream.poll_next_unpin(&mut cx) {
+            Poll::Ready(Some(chunk)) => polled.push(Some(chunk)),
+            Poll::Ready(None) => return polled,
+            Poll::Pending => polled.push(None),
+        }
+    }
+}
+
+#[test]
+fn flush_chunks_yields_after_each_chunk_or_joins_ready_ones() {
+    use leptos_integration_utils::flush_chunks;
+
+    let chunks = || {
+        futures::stream::iter(["<head>", "<body>", "</body>"].map(String::from))
+    };
+
+    // each chunk is followed by `Pending`, which makes a compressor flush it
+    assert_eq!(
+        poll_all(flush_chunks(chunks(), true)),
+        vec![
+            Some("<head>".to_string()),
+            None,
+            Some("<body>".to_string()),
+            None,
+            Some("</body>".to_string()),
+            None,
+        ]
+    );
+    assert_eq!(
+        poll_all(flush_chunks(chunks(), false)),
+        vec![Some("<head><body></body>".to_string())]
+    );
+}
@@ -0,0 +1,83 @@
+use axum::{body::Body, http::Request, routing::get, Router};
+use hyper::body::{Buf, HttpBody};
+use leptos::*;
+use leptos_meta::*;
+use tower::ServiceExt;
+
+const PAYLOAD: &str = "</script><script>alert(1)</script><!--\u{2028}";
+
+async fn untrusted() -> String {
+    tokio::task::yield_now().await;
+    PAYLOAD.to_string()
+}
+
+#[component]
+fn Untrusted(cx: Scope) -> impl IntoView {
+    provide_meta_context(cx);
+    let value = create_resource(cx, || (), |_| untrusted());
+
+    view! { cx,
+        <Title text=PAYLOAD/>
+        <p title=PAYLOAD>{PAYLOAD}</p>
+        <p>{move || PAYLOAD.to_string()}</p>
+        <Suspense fallback=|| ()>
+            {move || value.get().map(|value| view! { cx,
+                <span data-value=value.clone()>{value.clone()}</span>
+            })}
+        </Suspense>
+    }
+}
+
+async fn render(in_order: bool) -> String {
+    let options = LeptosOptions::builder().output_name("escaping").build();
+    let app = |cx| view! { cx, <Untrusted/> };
+    let handler = if in_order {
+        get(leptos_axum::render_app_to_stream_in_order(options, app))
+    } else {
+        get(leptos_axum::render_app_to_stream(options, app))
+    };
+    let res = Router::new()
+        .route("/", handler)
+        .oneshot(Request::get("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let mut body = res.into_body();
+    let mut html = String::new();
+    while let Some(chunk) = body.data().await {
+        html.push_str(&String::from_utf8_lossy(chunk.unwrap().chunk()));
+    }
+    html
+}
+
+fn assert_inert(html: &str) {
+    assert!(!html.contains("<script>alert(1)"), "{html}");
+    assert!(!html.contains("<!--\u{2028}"), "{html}");
+    assert!(
+        html.contains(
+            "<title>&lt;/script&gt;&lt;script&gt;alert(1)&lt;/script&gt;"
+        ),
+        "{html}"
+    );
+    assert!(
+        html.contains(
+            " title=\"&lt;/script&gt;&lt;script&gt;alert(1)&lt;/script&gt;"
+        ),
+        "{html}"
+    );
+    // the resource's value is sent to the client escaped for the <script>
+    assert!(
+        html.contains(r"\u003c/script\u003e\u003cscript\u003ealert(1)"),
+        "{html}"
+    );
+    assert!(html.contains(r"\u003c!--\u2028"), "{html}");
+}
+
+#[tokio::test]
+async fn untrusted_values_are_inert_when_streamed_out_of_order() {
+    assert_inert(&render(false).await);
+}
+
+#[tokio::test]
+async fn untrusted_values_are_inert_when_streamed_in_order() {
+    assert_inert(&render(true).await);
+}
@@ -0,0 +1,92 @@
+use axum::{body::Body, http::Request, routing::get, Router};
+use hyper::body::{Buf, HttpBody};
+use leptos::*;
+use leptos_axum::isr::{IsrCache, MemoryStore};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use tower::ServiceExt;
+
+static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+#[component]
+fn Post(cx: Scope) -> impl IntoView {
+    let render = RENDERS.fetch_add(1, Ordering::SeqCst) + 1;
+    view! { cx, <p>"render " {render}</p> }
+}
+
+struct Page {
+    cache_control: Option<String>,
+    age: Option<u64>,
+    html: String,
+}
+
+async fn get_page(router: &Router, uri: &str) -> Page {
+    let res = router
+        .clone()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let cache_control = res
+        .headers()
+        .get("cache-control")
+        .map(|value| value.to_str().unwrap().to_string());
+    let age = res
+        .headers()
+        .get("age")
+        .map(|value| value.to_str().unwrap().parse().unwrap());
+    let mut body = res.into_body();
+    let mut html = String::new();
+    while let Some(chunk) = body.data().await {
+        html.push_str(&String::from_utf8_lossy(chunk.unwrap().chunk()));
+    }
+    Page {
+        cache_control,
+        age,
+        html,
+    }
+}
+
+#[tokio::test]
+async fn pages_are_cached_and_regenerated_once_revalidated() {
+    let options = LeptosOptions::builder().output_name("isr").build();
+    let isr = IsrCache::new(MemoryStore::default(), Duration::from_secs(60));
+    let router = Router::new().route(
+        "/posts/*slug",
+        get(leptos_axum::render_app_isr(options, isr, |cx| {
+            view! { cx, <Post/> }
+        })),
+    );
+
+    let page = get_page(&router, "/posts/hello").await;
+    let first = page.html;
+    assert!(first.contains("render "), "{first}");
+    assert_eq!(
+        page.cache_control.as_deref(),
+        Some("public, max-age=0, s-maxage=60, stale-while-revalidate=60")
+    );
+    assert_eq!(page.age, Some(0));
+    // ages are counted in whole seconds
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let page = get_page(&router, "/posts/hello").await;
+    assert_eq!(first, page.html);
+    assert!(page.age >= Some(1), "{:?}", page.age);
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 1);
+
+    leptos_axum::revalidate_path("/posts/hello");
+    // the stale page is served while it is rendered again
+    let stale = get_page(&router, "/posts/hello").await.html;
+    assert_eq!(first, stale);
+    // later requests get the new page, without rendering it again
+    let mut regenerated = stale;
+    for _ in 0..100 {
+        regenerated = get_page(&router, "/posts/hello").await.html;
+        if regenerated != first {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_ne!(first, regenerated);
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 2);
+}
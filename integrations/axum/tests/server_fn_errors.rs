@@ -0,0 +1,124 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use leptos::*;
+use tower::ServiceExt;
+
+#[server(Double, "/api")]
+pub async fn double(amount: i32) -> Result<i32, ServerFnError> {
+    if amount < 0 {
+        return Err(ServerFnError::ServerError("negative amount".into()));
+    }
+    Ok(amount * 2)
+}
+
+#[server(Forbidden, "/api")]
+pub async fn forbidden(cx: Scope) -> Result<(), ServerFnError> {
+    let res_options = use_context::<leptos_axum::ResponseOptions>(cx).unwrap();
+    res_options.set_status(StatusCode::FORBIDDEN);
+    Err(ServerFnError::ServerError("not allowed".into()))
+}
+
+// provided as context by the handler
+#[derive(Clone, Copy)]
+struct MaxAmount(i32);
+
+#[server(Limited, "/api")]
+pub async fn limited(cx: Scope, amount: i32) -> Result<i32, ServerFnError> {
+    let MaxAmount(max) = expect_context(cx);
+    if amount > max {
+        return Err(ServerFnError::ServerError("too much".into()));
+    }
+    Ok(amount)
+}
+
+// calls the server function at `url` under `/api` and returns the status and
+// the deserialized error
+async fn call(
+    app: &Router,
+    url: &str,
+    body: &str,
+) -> (StatusCode, ServerFnError) {
+    let res = app
+        .clone()
+        .oneshot(
+            Request::post(format!("/api/{url}"))
+                .header(
+                    header::CONTENT_TYPE,
+                    "application/x-www-form-urlencoded",
+                )
+                .header(header::ACCEPT, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = res.status();
+    let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn server_fn_errors_are_mapped_to_statuses() {
+    let app = Router::new()
+        .route("/api/*fn_name", post(leptos_axum::handle_server_fns));
+
+    let (status, err) = call(&app, Double::url(), "amount=-1").await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(matches!(err, ServerFnError::ServerError(_)), "{err:?}");
+
+    let (status, err) = call(&app, Double::url(), "amount=many").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(matches!(err, ServerFnError::Deserialization(_)), "{err:?}");
+
+    // a status set by the server function wins
+    let (status, _) = call(&app, Forbidden::url(), "").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn server_fn_error_statuses_can_be_overridden() {
+    let app = Router::new().route(
+        "/api/*fn_name",
+        post(leptos_axum::handle_server_fns_with_error_mapper(
+            |err: &ServerFnError| match err {
+                ServerFnError::ServerError(_) => {
+                    StatusCode::SERVICE_UNAVAILABLE
+                }
+                err => StatusCode::from_u16(err.status_code()).unwrap(),
+            },
+        )),
+    );
+
+    let (status, _) = call(&app, Double::url(), "amount=-1").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let (status, _) = call(&app, Double::url(), "amount=many").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = call(&app, Forbidden::url(), "").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn server_fn_error_statuses_can_be_overridden_with_context() {
+    let app = Router::new().route(
+        "/api/*fn_name",
+        post(
+            leptos_axum::handle_server_fns_with_context_and_error_mapper(
+                |cx| provide_context(cx, MaxAmount(10)),
+                |err: &ServerFnError| match err {
+                    ServerFnError::ServerError(_) => {
+                        StatusCode::PAYLOAD_TOO_LARGE
+                    }
+                    err => StatusCode::from_u16(err.status_code()).unwrap(),
+                },
+            ),
+        ),
+    );
+
+    let (status, err) = call(&app, Limited::url(), "amount=11").await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(matches!(err, ServerFnError::ServerError(_)), "{err:?}");
+}
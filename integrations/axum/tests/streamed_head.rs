@@ -0,0 +1,59 @@
+use axum::{body::Body, http::Request, routing::get, Router};
+use hyper::body::{Buf, HttpBody};
+use leptos::*;
+use leptos_meta::*;
+use tower::ServiceExt;
+
+async fn product_name() -> String {
+    tokio::task::yield_now().await;
+    "Leptos \"Mug\"".to_string()
+}
+
+#[component]
+fn Product(cx: Scope) -> impl IntoView {
+    provide_meta_context(cx);
+    let product = create_resource(cx, || (), |_| product_name());
+
+    view! { cx,
+        <Title text="Shop"/>
+        <Suspense fallback=move || view! { cx, <p>"Loading..."</p> }>
+            {move || product.get().map(|name| view! { cx,
+                <Title text=format!("{name} | Shop")/>
+                <Meta name="description" content=name.clone()/>
+                <p>{name.clone()}</p>
+            })}
+        </Suspense>
+    }
+}
+
+#[tokio::test]
+async fn metadata_from_suspense_is_updated_at_the_end_of_the_stream() {
+    let options = LeptosOptions::builder().output_name("streamed").build();
+    let app = Router::new().route(
+        "/product",
+        get(leptos_axum::render_app_to_stream(options, |cx| {
+            view! { cx, <Product/> }
+        })),
+    );
+
+    let res = app
+        .oneshot(Request::get("/product").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let mut body = res.into_body();
+    let mut html = String::new();
+    while let Some(chunk) = body.data().await {
+        html.push_str(&String::from_utf8_lossy(chunk.unwrap().chunk()));
+    }
+
+    assert!(html.contains("<title>Shop</title>"), "{html}");
+    let update = &html[html.rfind("<script>").unwrap()..];
+    assert!(
+        update.contains(r#"document.title="Leptos \"Mug\" | Shop";"#),
+        "{update}"
+    );
+    let meta =
+        r#"insertAdjacentHTML("beforeend","\u003cmeta name=\"description\""#;
+    assert!(update.contains(meta), "{update}");
+    assert!(update.contains("</script></body>"), "{update}");
+}
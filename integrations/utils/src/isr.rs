@@ -0,0 +1,402 @@
+//! Incremental static regeneration (ISR): pages are rendered on the first
+//! request for them, cached, and served from the cache until they are
+//! older than a time to live, or are revalidated with [`revalidate_path`].
+//!
+//! A page that is past its time to live is still served from the cache,
+//! and is rendered again in the background, so no request waits for it.
+//! Only one request renders a given page at a time: the requests that come
+//! in while a page that isn’t cached yet is being rendered wait for that
+//! render, and a stale page is only rendered again once.
+//!
+//! This module is runtime-agnostic: the server integrations use an
+//! [`IsrCache`] to decide whether to render a page, and render it with
+//! their usual async renderer, so the cached HTML includes the data that
+//! the client needs to hydrate it.
+
+use futures::channel::oneshot;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::PathBuf,
+    sync::{Arc, Mutex, RwLock, Weak},
+    time::{Duration, SystemTime},
+};
+
+/// A page as it is stored in an [`IsrStore`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedPage {
+    /// The status code of the response.
+    pub status: u16,
+    /// The headers of the response, except for `Set-Cookie`, which is never
+    /// cached.
+    pub headers: Vec<(String, String)>,
+    /// The HTML of the page.
+    pub html: String,
+    /// When the page started rendering, which is as old as the data in it
+    /// can be.
+    pub rendered_at: SystemTime,
+}
+
+impl CachedPage {
+    /// A page that has just been rendered, after starting at `started_at`.
+    pub fn new(
+        status: u16,
+        headers: impl IntoIterator<Item = (String, String)>,
+        html: String,
+        started_at: SystemTime,
+    ) -> Self {
+        Self {
+            status,
+            headers: headers
+                .into_iter()
+                .filter(|(name, _)| !name.eq_ignore_ascii_case("set-cookie"))
+                .collect(),
+            html,
+            rendered_at: started_at,
+        }
+    }
+
+    /// How long ago the page was rendered.
+    pub fn age(&self) -> Duration {
+        self.rendered_at.elapsed().unwrap_or_default()
+    }
+
+    /// Whether the page can be cached, which is only the case for a
+    /// `200 OK`: errors and redirects are rendered for every request.
+    pub fn is_cacheable(&self) -> bool {
+        self.status == 200
+    }
+}
+
+/// Where an [`IsrCache`] keeps its pages, keyed by the path and query of the
+/// request.
+///
+/// The methods are called from the request handlers, so they should return
+/// quickly.
+pub trait IsrStore: Send + Sync + 'static {
+    /// Returns the page cached for `key`, if any.
+    fn get(&self, key: &str) -> Option<CachedPage>;
+
+    /// Caches a page for `key`, replacing any previous one.
+    fn set(&self, key: &str, page: CachedPage);
+
+    /// Removes the page cached for `key`, if any.
+    fn remove(&self, key: &str);
+}
+
+/// An [`IsrStore`] that keeps the pages in memory, so they are lost when the
+/// server restarts.
+#[derive(Default)]
+pub struct MemoryStore(RwLock<HashMap<String, CachedPage>>);
+
+impl IsrStore for MemoryStore {
+    fn get(&self, key: &str) -> Option<CachedPage> {
+        self.0
+            .read()
+            .expect("ISR memory store is poisoned")
+            .get(key)
+            .cloned()
+    }
+
+    fn set(&self, key: &str, page: CachedPage) {
+        self.0
+            .write()
+            .expect("ISR memory store is poisoned")
+            .insert(key.to_string(), page);
+    }
+
+    fn remove(&self, key: &str) {
+        self.0
+            .write()
+            .expect("ISR memory store is poisoned")
+            .remove(key);
+    }
+}
+
+/// An [`IsrStore`] that keeps each page as a JSON file in a directory, so
+/// they survive restarts.
+pub struct DiskStore {
+    dir: PathBuf,
+}
+
+impl DiskStore {
+    /// Stores the pages in `dir`, which is created if it doesn’t exist.
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    // every character but ASCII letters and digits is escaped, so that each
+    // key has its own file name
+    fn file(&self, key: &str) -> PathBuf {
+        let mut name = String::with_capacity(key.len() + 5);
+        for byte in key.bytes() {
+            if byte.is_ascii_alphanumeric() {
+                name.push(byte as char);
+            } else {
+                name.push_str(&format!("_{byte:02X}"));
+            }
+        }
+        name.push_str(".json");
+        self.dir.join(name)
+    }
+}
+
+impl IsrStore for DiskStore {
+    fn get(&self, key: &str) -> Option<CachedPage> {
+        let json = fs::read(self.file(key)).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+
+    fn set(&self, key: &str, page: CachedPage) {
+        let file = self.file(key);
+        // written next to the page and renamed over it, so that a page is
+        // never read while it is half-written
+        let partial = file.with_extension("json.partial");
+        let written = serde_json::to_vec(&page)
+            .map_err(io::Error::from)
+            .and_then(|json| fs::write(&partial, json))
+            .and_then(|_| fs::rename(&partial, &file));
+        if let Err(e) = written {
+            tracing::error!("could not cache {key} in {file:?}: {e}");
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        _ = fs::remove_file(self.file(key));
+    }
+}
+
+/// Caches rendered pages for incremental static regeneration.
+///
+/// Create one cache per time to live, and pass it to the ISR handlers of
+/// the server integration for the routes it should cache.
+///
+/// ```
+/// use leptos_integration_utils::isr::{IsrCache, MemoryStore};
+/// use std::time::Duration;
+///
+/// let blog = IsrCache::new(MemoryStore::default(), Duration::from_secs(60));
+/// ```
+pub struct IsrCache {
+    store: Box<dyn IsrStore>,
+    ttl: Duration,
+    // the requests waiting for each page that is being rendered
+    in_flight: Mutex<HashMap<String, Vec<oneshot::Sender<CachedPage>>>>,
+    // when each path was last revalidated with `revalidate_path`, for as long
+    // as a page rendered before that could still be fresh
+    revalidated: Mutex<HashMap<String, SystemTime>>,
+}
+
+// every cache, so that `revalidate_path` can reach them
+static CACHES: Mutex<Vec<Weak<IsrCache>>> = Mutex::new(Vec::new());
+
+impl IsrCache {
+    /// Creates a cache that keeps its pages in `store`, and renders them
+    /// again once they are older than `ttl`.
+    pub fn new(store: impl IsrStore, ttl: Duration) -> Arc<Self> {
+        let cache = Arc::new(Self {
+            store: Box::new(store),
+            ttl,
+            in_flight: Default::default(),
+            revalidated: Default::default(),
+        });
+        let mut caches = CACHES.lock().expect("ISR caches are poisoned");
+        caches.retain(|cache| cache.strong_count() > 0);
+        caches.push(Arc::downgrade(&cache));
+        cache
+    }
+
+    /// How long a page is served before it is rendered again.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Looks up the page for `key`, the path and query of the request, and
+    /// tells the caller what to do with it.
+    pub fn lookup(self: &Arc<Self>, key: &str) -> IsrLookup {
+        // the store is read without holding the lock, so that a slow store
+        // doesn't hold up the lookups of other pages
+        if let Some(page) = self.store.get(key) {
+            return self.cached(key, page);
+        }
+
+        {
+            let mut in_flight =
+                self.in_flight.lock().expect("ISR cache is poisoned");
+            if let Some(waiting) = in_flight.get_mut(key) {
+                let (tx, rx) = oneshot::channel();
+                waiting.push(tx);
+                return IsrLookup::Missing(Err(PageWaiter(rx)));
+            }
+            in_flight.insert(key.to_string(), Vec::new());
+        }
+        let regeneration = self.regeneration(key);
+
+        // the page may have been rendered and cached since the store was
+        // read, as pages are cached before they stop being in flight
+        match self.store.get(key) {
+            Some(page) => {
+                regeneration.hand_over(&page);
+                self.cached(key, page)
+            }
+            None => IsrLookup::Missing(Ok(regeneration)),
+        }
+    }
+
+    fn cached(self: &Arc<Self>, key: &str, page: CachedPage) -> IsrLookup {
+        if !self.is_stale(key, &page) {
+            return IsrLookup::Fresh(page);
+        }
+        let regeneration = {
+            let mut in_flight =
+                self.in_flight.lock().expect("ISR cache is poisoned");
+            (!in_flight.contains_key(key)).then(|| {
+                in_flight.insert(key.to_string(), Vec::new());
+            })
+        };
+        IsrLookup::Stale(page, regeneration.map(|_| self.regeneration(key)))
+    }
+
+    /// The `Cache-Control` header for a page served from this cache, which
+    /// lets a CDN cache it for the rest of its time to live, and then serve
+    /// it for up to as long again while it is revalidated.
+    pub fn cache_control(&self, page: &CachedPage) -> String {
+        // ages are counted in whole seconds, like the `Age` header
+        let ttl = self.ttl.as_secs();
+        format!(
+            "public, max-age=0, s-maxage={}, stale-while-revalidate={ttl}",
+            ttl.saturating_sub(page.age().as_secs()),
+        )
+    }
+
+    /// Marks the pages for `path` as stale, whatever their query.
+    pub fn revalidate(&self, path: &str) {
+        let mut revalidated =
+            self.revalidated.lock().expect("ISR cache is poisoned");
+        // a page rendered before an older revalidation is stale anyway
+        revalidated.retain(|_, at| {
+            at.elapsed().map_or(true, |elapsed| elapsed < self.ttl)
+        });
+        revalidated.insert(path.to_string(), SystemTime::now());
+    }
+
+    fn is_stale(&self, key: &str, page: &CachedPage) -> bool {
+        let path = key.split('?').next().unwrap_or(key);
+        page.age() >= self.ttl
+            || self
+                .revalidated
+                .lock()
+                .expect("ISR cache is poisoned")
+                .get(path)
+                .is_some_and(|revalidated| page.rendered_at <= *revalidated)
+    }
+
+    fn regeneration(self: &Arc<Self>, key: &str) -> Regeneration {
+        Regeneration {
+            cache: Arc::clone(self),
+            key: Some(key.to_string()),
+        }
+    }
+}
+
+impl fmt::Debug for IsrCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IsrCache").field("ttl", &self.ttl).finish()
+    }
+}
+
+/// Marks the pages for `path`, like `"/blog/hello-world"`, as stale in every
+/// [`IsrCache`] in this process, whatever their query.
+///
+/// The next request for one of them is still served the cached page, and
+/// renders it again in the background. This can be called from a server
+/// function, e.g., when a CMS sends a webhook because a post was edited.
+pub fn revalidate_path(path: &str) {
+    let caches = CACHES.lock().expect("ISR caches are poisoned");
+    for cache in caches.iter().filter_map(Weak::upgrade) {
+        cache.revalidate(path);
+    }
+}
+
+/// What to do with a request, as returned by [`IsrCache::lookup`].
+#[derive(Debug)]
+pub enum IsrLookup {
+    /// The cached page can be served as is.
+    Fresh(CachedPage),
+    /// The cached page can be served, but is stale. If there is a
+    /// [`Regeneration`], the caller should render the page again in the
+    /// background, and then [finish](Regeneration::finish) it; otherwise,
+    /// another request is already doing so.
+    Stale(CachedPage, Option<Regeneration>),
+    /// The page isn’t cached. Either the caller should render it and
+    /// [finish](Regeneration::finish) the [`Regeneration`], or another
+    /// request is rendering it and the [`PageWaiter`] resolves to the page
+    /// once it is done.
+    Missing(Result<Regeneration, PageWaiter>),
+}
+
+/// The right to render a page, so that only one request renders it at a
+/// time. If it is dropped without being finished, the requests that wait for
+/// the page render it themselves.
+#[derive(Debug)]
+pub struct Regeneration {
+    cache: Arc<IsrCache>,
+    key: Option<String>,
+}
+
+impl Regeneration {
+    /// Caches the rendered page and passes it to the requests that were
+    /// waiting for it, if it [can be cached](CachedPage::is_cacheable).
+    /// Otherwise, those requests render the page themselves, as it may
+    /// depend on the request.
+    pub fn finish(self, page: CachedPage) {
+        if page.is_cacheable() {
+            if let Some(key) = &self.key {
+                self.cache.store.set(key, page.clone());
+            }
+            self.hand_over(&page);
+        }
+    }
+
+    // passes a cached page to the requests that were waiting for it
+    fn hand_over(mut self, page: &CachedPage) {
+        let Some(key) = self.key.take() else {
+            return;
+        };
+        let waiting = self
+            .cache
+            .in_flight
+            .lock()
+            .expect("ISR cache is poisoned")
+            .remove(&key)
+            .unwrap_or_default();
+        for tx in waiting {
+            _ = tx.send(page.clone());
+        }
+    }
+}
+
+impl Drop for Regeneration {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            if let Ok(mut in_flight) = self.cache.in_flight.lock() {
+                in_flight.remove(&key);
+            }
+        }
+    }
+}
+
+/// Resolves to a page that another request is rendering, or to `Err` if that
+/// request failed to render it, or rendered a page that can't be cached.
+#[derive(Debug)]
+pub struct PageWaiter(oneshot::Receiver<CachedPage>);
+
+impl PageWaiter {
+    /// Waits for the page.
+    pub async fn wait(self) -> Result<CachedPage, oneshot::Canceled> {
+        self.0.await
+    }
+}
@@ -1,39 +1,85 @@
 use futures::{Stream, StreamExt};
 use leptos::{nonce::use_nonce, use_context, RuntimeId, Scope, ScopeId};
-use leptos_config::LeptosOptions;
+use leptos_config::{Env, LeptosOptions};
 use leptos_meta::MetaContext;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pub mod isr;
+#[cfg(feature = "dev")]
+mod live_reload;
+#[cfg(feature = "dev")]
+pub use live_reload::*;
 
 extern crate tracing;
 
+/// Whether running under `cargo leptos watch`, which serves its own
+/// `/live_reload` websocket.
+fn watching() -> bool {
+    std::env::var("LEPTOS_WATCH").is_ok()
+}
+
+/// Whether to include the live reload client in the page: when running under
+/// `cargo leptos watch`, or in a dev environment with the `dev` feature enabled.
+///
+/// The `dev` client is only included by [`html_parts_separated`], which
+/// gives its inline script the page's nonce, so that it still runs when the
+/// page has a strict Content Security Policy.
+fn live_reload_enabled(options: &LeptosOptions) -> bool {
+    watching() || (cfg!(feature = "dev") && options.env == Env::DEV)
+}
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
-fn autoreload(nonce_str: &str, options: &LeptosOptions) -> String {
+fn autoreload(
+    nonce_str: &str,
+    options: &LeptosOptions,
+    enabled: bool,
+) -> String {
     let site_ip = &options.site_addr.ip().to_string();
     let reload_port = options.reload_port;
-    match std::env::var("LEPTOS_WATCH").is_ok() {
+    match enabled {
         true => format!(
             r#"
                 <script crossorigin=""{nonce_str}>(function () {{
                     {}
-                    let ws = new WebSocket('ws://{site_ip}:{reload_port}/live_reload');
-                    ws.onmessage = (ev) => {{
-                        let msg = JSON.parse(ev.data);
-                        if (msg.all) window.location.reload();
-                        if (msg.css) {{
-                            let found = false;
-                            document.querySelectorAll("link").forEach((link) => {{
-                                if (link.getAttribute('href').includes(msg.css)) {{
-                                    let newHref = '/' + msg.css + '?version=' + new Date().getMilliseconds();
-                                    link.setAttribute('href', newHref);
-                                    found = true;
-                                }}
-                            }});
-                            if (!found) console.warn(`CSS hot-reload: Could not find a <link href=/\"${{msg.css}}\"> element`);
+                    let delay = 250;
+                    let reconnecting = false;
+                    function connect() {{
+                        let ws = new WebSocket('ws://{site_ip}:{reload_port}/live_reload');
+                        ws.onopen = () => {{
+                            // the server was restarted while we were disconnected
+                            if (reconnecting) window.location.reload();
+                            delay = 250;
+                        }};
+                        ws.onmessage = (ev) => {{
+                            let msg = JSON.parse(ev.data);
+                            if (msg === 'reload' || msg.all) window.location.reload();
+                            let css = msg.css && (msg.css.path || msg.css);
+                            if (css) {{
+                                let found = false;
+                                document.querySelectorAll("link").forEach((link) => {{
+                                    if (link.getAttribute('href').includes(css)) {{
+                                        let newHref = '/' + css + '?version=' + new Date().getTime();
+                                        link.setAttribute('href', newHref);
+                                        found = true;
+                                    }}
+                                }});
+                                if (!found) console.warn(`CSS hot-reload: Could not find a <link href=/\"${{css}}\"> element`);
+                            }};
+                            if(msg.view) {{
+                                patch(msg.view);
+                            }}
+                        }};
+                        ws.onclose = () => {{
+                            if (!reconnecting) console.warn('Live-reload disconnected. Reconnecting...');
+                            reconnecting = true;
+                            setTimeout(connect, delay);
+                            delay = Math.min(delay * 2, 5000);
                         }};
-                        if(msg.view) {{
-                            patch(msg.view);
-                        }}
-                    }};
-                    ws.onclose = () => console.warn('Live-reload stopped. Manual reload necessary.');
+                    }}
+                    connect();
                 }})()
                 </script>
                 "#,
@@ -60,7 +106,7 @@ pub fn html_parts(
         wasm_output_name.push_str("_bg");
     }
 
-    let leptos_autoreload = autoreload("", options);
+    let leptos_autoreload = autoreload("", options, watching());
 
     let html_metadata =
         meta.and_then(|mc| mc.html.as_string()).unwrap_or_default();
@@ -102,14 +148,12 @@ pub fn html_parts_separated(
         wasm_output_name.push_str("_bg");
     }
 
-    let leptos_autoreload = autoreload(&nonce, options);
+    let leptos_autoreload =
+        autoreload(&nonce, options, live_reload_enabled(options));
 
-    let html_metadata =
-        meta.and_then(|mc| mc.html.as_string()).unwrap_or_default();
-    let head = meta
-        .as_ref()
-        .map(|meta| meta.dehydrate())
-        .unwrap_or_default();
+    let parts = meta.map(MetaContext::dehydrate).unwrap_or_default();
+    let html_metadata = &parts.html_attrs;
+    let head = parts.to_string();
     let head = format!(
         r#"<!DOCTYPE html>
             <html{html_metadata}>
@@ -158,3 +202,64 @@ pub async fn build_async_response(
 
     format!("{head}</head><body{body_meta}>{buf}{tail}")
 }
+
+/// The most chunks that are joined into one when
+/// [`LeptosOptions::flush_stream_chunks`] is `false`.
+const MAX_COALESCED_CHUNKS: usize = 64;
+
+/// Prepares a stream of HTML chunks to be sent as a response body, given the
+/// value of [`LeptosOptions::flush_stream_chunks`].
+///
+/// By default, the stream yields to the server after each chunk, before it
+/// checks for the next one. A compression middleware, which flushes what it
+/// has compressed so far whenever the body has nothing more ready, then
+/// sends each chunk, e.g., each resolved `<Suspense/>`, as soon as it is
+/// rendered. Otherwise, chunks that are ready at the same time are joined,
+/// so that they are compressed together.
+pub fn flush_chunks<S>(
+    stream: S,
+    flush_stream_chunks: bool,
+) -> impl Stream<Item = String>
+where
+    S: Stream<Item = String>,
+{
+    if flush_stream_chunks {
+        futures::future::Either::Left(FlushPerChunk {
+            inner: Box::pin(stream),
+            flushed: true,
+        })
+    } else {
+        futures::future::Either::Right(
+            stream
+                .ready_chunks(MAX_COALESCED_CHUNKS)
+                .map(|chunks| chunks.concat()),
+        )
+    }
+}
+
+// returns `Pending` once after each chunk, so that a compressor flushes it
+struct FlushPerChunk<S> {
+    inner: Pin<Box<S>>,
+    flushed: bool,
+}
+
+impl<S> Stream for FlushPerChunk<S>
+where
+    S: Stream,
+{
+    type Item = S::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if !self.flushed {
+            self.flushed = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let chunk = futures::ready!(self.inner.as_mut().poll_next(cx));
+        self.flushed = chunk.is_none();
+        Poll::Ready(chunk)
+    }
+}
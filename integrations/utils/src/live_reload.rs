@@ -0,0 +1,69 @@
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+
+/// A message that tells the browser to reload, sent to the `/live_reload`
+/// websocket, which relays it to every page that is connected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiveReloadMessage {
+    /// Reloads the whole page. Sent as `"reload"`.
+    Reload,
+    /// Swaps the stylesheet with the given path, like `pkg/app.css`, without
+    /// reloading the page. Sent as `{"css":{"path":"pkg/app.css"}}`.
+    Css {
+        /// The path of the stylesheet, relative to the site root.
+        path: String,
+    },
+}
+
+impl LiveReloadMessage {
+    /// Serializes the message in the format expected by the browser.
+    pub fn to_json(&self) -> String {
+        match self {
+            LiveReloadMessage::Reload => r#""reload""#.to_string(),
+            LiveReloadMessage::Css { path } => format!(
+                r#"{{"css":{{"path":{}}}}}"#,
+                serde_json::Value::from(path.as_str())
+            ),
+        }
+    }
+}
+
+/// Relays live reload messages to every connected websocket.
+///
+/// The integrations’ `/live_reload` websocket endpoints subscribe to it,
+/// and broadcast every text message they receive, so that external watch
+/// tooling can connect and send a [`LiveReloadMessage`].
+#[derive(Debug, Default)]
+pub struct LiveReload {
+    clients: Mutex<Vec<UnboundedSender<String>>>,
+}
+
+impl LiveReload {
+    /// The relay shared by every live reload endpoint of the process.
+    pub fn global() -> &'static LiveReload {
+        static LIVE_RELOAD: Lazy<LiveReload> = Lazy::new(LiveReload::default);
+        &LIVE_RELOAD
+    }
+
+    /// Receives every message that is broadcast from now on.
+    pub fn subscribe(&self) -> UnboundedReceiver<String> {
+        let (tx, rx) = unbounded();
+        self.clients.lock().unwrap().push(tx);
+        rx
+    }
+
+    /// Sends a message to every connected page.
+    pub fn notify(&self, message: &LiveReloadMessage) {
+        self.broadcast(message.to_json());
+    }
+
+    /// Sends raw text to every subscriber, dropping those that have
+    /// disconnected.
+    pub fn broadcast(&self, text: String) {
+        self.clients
+            .lock()
+            .unwrap()
+            .retain(|client| client.unbounded_send(text.clone()).is_ok());
+    }
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "dev")]
+
+use futures::StreamExt;
+use leptos::*;
+use leptos_config::{Env, LeptosOptions};
+use leptos_integration_utils::{
+    html_parts_separated, LiveReload, LiveReloadMessage,
+};
+
+#[test]
+fn messages_are_serialized_for_the_client() {
+    assert_eq!(LiveReloadMessage::Reload.to_json(), r#""reload""#);
+    let css = LiveReloadMessage::Css {
+        path: r#"pkg/"app".css"#.to_string(),
+    };
+    assert_eq!(css.to_json(), r#"{"css":{"path":"pkg/\"app\".css"}}"#);
+}
+
+#[test]
+fn messages_are_relayed_to_every_subscriber() {
+    let relay = LiveReload::default();
+    let first = relay.subscribe();
+    let second = relay.subscribe();
+    drop(relay.subscribe());
+
+    relay.notify(&LiveReloadMessage::Reload);
+    relay.broadcast("from watch tooling".to_string());
+    drop(relay);
+
+    for messages in [first, second] {
+        let messages =
+            futures::executor::block_on(messages.collect::<Vec<_>>());
+        assert_eq!(messages, [r#""reload""#, "from watch tooling"]);
+    }
+}
+
+fn head(env: Env) -> String {
+    let options = LeptosOptions::builder().output_name("app").env(env).build();
+    let runtime = create_runtime();
+    let (head, nonce) = run_scope(runtime, move |cx| {
+        leptos::nonce::provide_nonce(cx);
+        let nonce = leptos::nonce::use_nonce(cx).unwrap();
+        let (head, _) = html_parts_separated(cx, &options, None);
+        (head, nonce.to_string())
+    });
+    runtime.dispose();
+    assert!(head.contains(&format!("nonce=\"{nonce}\"")), "{head}");
+    head
+}
+
+#[test]
+fn client_script_has_the_page_nonce_in_dev() {
+    if std::env::var("LEPTOS_WATCH").is_ok() {
+        return;
+    }
+
+    let dev = head(Env::DEV);
+    let script = dev
+        .split("<script")
+        .find(|script| script.contains("/live_reload"))
+        .expect("the live reload client should be included in dev");
+    assert!(script.starts_with(" crossorigin=\"\" nonce=\""), "{script}");
+
+    assert!(!head(Env::PROD).contains("/live_reload"));
+}
@@ -13,13 +13,15 @@ use futures::{
 use http::{header, method::Method, uri::Uri, version::Version, StatusCode};
 use hyper::body;
 use leptos::{
-    leptos_server::{server_fn_by_path, Payload},
-    server_fn::Encoding,
+    leptos_server::{server_fn_by_path, Payload, ServerFnContext},
+    server_fn::{form, Encoding},
     ssr::*,
     *,
 };
 use leptos_integration_utils::{build_async_response, html_parts_separated};
-use leptos_meta::{generate_head_metadata_separated, MetaContext};
+use leptos_meta::{
+    generate_head_metadata_separated, generate_head_update_script, MetaContext,
+};
 use leptos_router::*;
 use parking_lot::RwLock;
 use std::{pin::Pin, sync::Arc};
@@ -156,7 +158,89 @@ pub async fn generate_request_parts(req: Request) -> RequestParts {
 /// - [RequestParts]
 /// - [ResponseOptions]
 pub async fn handle_server_fns(req: Request) -> Result<Response> {
-    handle_server_fns_inner(req, |_| {}).await
+    handle_server_fns_inner(req, |_| {}, default_error_status).await
+}
+
+/// Returns a Viz handler that listens for a request with Leptos server function arguments,
+/// like [handle_server_fns], and responds with the status code returned by `error_status`
+/// when a server function returns an error.
+///
+/// By default, an error is sent with the status code of [ServerFnError::status_code]. A status
+/// set by the server function itself with [ResponseOptions::set_status] is used instead of
+/// either. The client deserializes the error from the body of the response for any status
+/// code that isn't successful.
+///
+/// ```
+/// use http::StatusCode;
+/// use leptos::*;
+/// use viz::Router;
+///
+/// # if false { // don't actually try to run a server in a doctest...
+/// let app = Router::new().post(
+///     "/api/:fn_name*",
+///     leptos_viz::handle_server_fns_with_error_mapper(
+///         |err: &ServerFnError| match err {
+///             ServerFnError::MissingArg(_) => StatusCode::UNPROCESSABLE_ENTITY,
+///             err => StatusCode::from_u16(err.status_code()).unwrap(),
+///         },
+///     ),
+/// );
+/// # }
+/// ```
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [RequestParts]
+/// - [ResponseOptions]
+pub fn handle_server_fns_with_error_mapper(
+    error_status: impl Fn(&ServerFnError) -> StatusCode
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+) -> impl Fn(Request) -> Pin<Box<dyn Future<Output = Result<Response>> + Send>>
+       + Clone
+       + Send
+       + Sync
+       + 'static {
+    handle_server_fns_with_context_and_error_mapper(|_| {}, error_status)
+}
+
+/// Returns a Viz handler that listens for a request with Leptos server function arguments,
+/// like [handle_server_fns_with_context], and responds with the status code returned by
+/// `error_status` when a server function returns an error, like
+/// [handle_server_fns_with_error_mapper].
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [RequestParts]
+/// - [ResponseOptions]
+pub fn handle_server_fns_with_context_and_error_mapper(
+    additional_context: impl Fn(leptos::Scope) + Clone + Send + Sync + 'static,
+    error_status: impl Fn(&ServerFnError) -> StatusCode
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+) -> impl Fn(Request) -> Pin<Box<dyn Future<Output = Result<Response>> + Send>>
+       + Clone
+       + Send
+       + Sync
+       + 'static {
+    move |req| {
+        Box::pin(handle_server_fns_inner(
+            req,
+            additional_context.clone(),
+            error_status.clone(),
+        ))
+    }
+}
+
+// the status of an error response, unless the server function or the app
+// set another one
+fn default_error_status(err: &ServerFnError) -> StatusCode {
+    StatusCode::from_u16(err.status_code())
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
 }
 
 /// A Viz handlers to listens for a request with Leptos server function arguments in the body,
@@ -177,12 +261,13 @@ pub async fn handle_server_fns_with_context(
     req: Request,
     additional_context: impl Fn(leptos::Scope) + Clone + Send + 'static,
 ) -> Result<Response> {
-    handle_server_fns_inner(req, additional_context).await
+    handle_server_fns_inner(req, additional_context, default_error_status).await
 }
 
 async fn handle_server_fns_inner(
     req: Request,
     additional_context: impl Fn(leptos::Scope) + Clone + Send + 'static,
+    error_status: impl Fn(&ServerFnError) -> StatusCode + Send + 'static,
 ) -> Result<Response> {
     let fn_name = req.params::<String>()?;
     let headers = req.headers().clone();
@@ -204,10 +289,34 @@ async fn handle_server_fns_inner(
                             additional_context(cx);
 
                             let req_parts = generate_request_parts(req).await;
+                            let cookie_header = req_parts
+                                .headers
+                                .get(header::COOKIE)
+                                .and_then(|value| value.to_str().ok())
+                                .map(String::from);
                             // Add this so we can get details about the Request
                             provide_context(cx, req_parts.clone());
                             // Add this so that we can set headers and status of the response
-                            provide_context(cx, ResponseOptions::default());
+                            let res_options = ResponseOptions::default();
+                            provide_context(cx, res_options.clone());
+                            leptos::cookie::provide_server_cookies(
+                                cx,
+                                cookie_header,
+                                move |cookie| {
+                                    if let Ok(value) =
+                                        HeaderValue::from_str(&cookie)
+                                    {
+                                        res_options.append_header(
+                                            header::SET_COOKIE,
+                                            value,
+                                        );
+                                    }
+                                },
+                            );
+                            // the server function runs in a child of this
+                            // scope, as it does of the root scope when called
+                            // while rendering
+                            ServerFnContext::provide(cx);
 
                             let data = match &server_fn.encoding() {
                                 Encoding::Url | Encoding::Cbor => {
@@ -257,12 +366,16 @@ async fn handle_server_fns_inner(
                                     }
                                     // otherwise, it's probably a <form> submit or something: redirect back to the referrer
                                     else {
-                                        let referer = headers
-                                            .get("Referer")
-                                            .and_then(|value| {
-                                                value.to_str().ok()
+                                        let referer = form::redirect_path(data)
+                                            .or_else(|| {
+                                                headers
+                                                    .get("Referer")
+                                                    .and_then(|value| {
+                                                        value.to_str().ok()
+                                                    })
+                                                    .map(String::from)
                                             })
-                                            .unwrap_or("/");
+                                            .unwrap_or_else(|| "/".to_string());
 
                                         res = res
                                             .status(StatusCode::SEE_OTHER)
@@ -295,12 +408,29 @@ async fn handle_server_fns_inner(
                                             .body(Body::from(data)),
                                     }
                                 }
-                                Err(e) => Response::builder()
-                                    .status(StatusCode::INTERNAL_SERVER_ERROR)
-                                    .body(Body::from(
-                                        serde_json::to_string(&e)
-                                            .unwrap_or_else(|_| e.to_string()),
-                                    )),
+                                Err(e) => {
+                                    // a status set by the server function wins
+                                    let status =
+                                        use_context::<ResponseOptions>(cx)
+                                            .and_then(|res_options| {
+                                                res_options.0.read().status
+                                            })
+                                            .unwrap_or_else(|| {
+                                                error_status(&e)
+                                            });
+                                    Response::builder()
+                                        .status(status)
+                                        .header(
+                                            header::CONTENT_TYPE,
+                                            "application/json",
+                                        )
+                                        .body(Body::from(
+                                            serde_json::to_string(&e)
+                                                .unwrap_or_else(|_| {
+                                                    e.to_string()
+                                                }),
+                                        ))
+                                }
                             };
                             // clean up the scope
                             disposer.dispose();
@@ -589,8 +719,9 @@ where
                                             let app = {
                                                 let full_path = full_path.clone();
                                                 let req_parts = generate_request_parts(req).await;
+                                                let options = options.clone();
                                                 move |cx| {
-                                                    provide_contexts(cx, full_path, req_parts, default_res_options);
+                                                    provide_contexts(cx, &options, full_path, req_parts, default_res_options);
                                                     app_fn(cx).into_view(cx)
                                                 }
                                             };
@@ -664,9 +795,16 @@ async fn forward_stream(
     _ = tx.send(head).await;
     let mut shell = Box::pin(bundle);
     while let Some(fragment) = shell.next().await {
-        _ = tx.send(fragment).await;
+        // the client has disconnected, so stop rendering; disposing of the
+        // runtime aborts any resources that are still loading
+        if tx.send(fragment).await.is_err() {
+            runtime.dispose();
+            return;
+        }
     }
-    _ = tx.send(tail.to_string()).await;
+    // update any metadata that changed while the stream was resolving
+    let head_update = generate_head_update_script(cx).unwrap_or_default();
+    _ = tx.send(format!("{head_update}{tail}")).await;
 
     // Extract the value of ResponseOptions from here
     let res_options = use_context::<ResponseOptions>(cx).unwrap();
@@ -757,8 +895,9 @@ where
                                             let app = {
                                                 let full_path = full_path.clone();
                                                 let req_parts = generate_request_parts(req).await;
+                                                let options = options.clone();
                                                 move |cx| {
-                                                    provide_contexts(cx, full_path, req_parts, default_res_options);
+                                                    provide_contexts(cx, &options, full_path, req_parts, default_res_options);
                                                     app_fn(cx).into_view(cx)
                                                 }
                                             };
@@ -786,18 +925,31 @@ where
 
 fn provide_contexts(
     cx: Scope,
+    options: &LeptosOptions,
     path: String,
     req_parts: RequestParts,
     default_res_options: ResponseOptions,
 ) {
     let integration = ServerIntegration { path };
+    let cookie_header = req_parts
+        .headers
+        .get(header::COOKIE)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    provide_context(cx, options.clone());
     provide_context(cx, RouterIntegrationContext::new(integration));
     provide_context(cx, MetaContext::new());
     provide_context(cx, req_parts);
-    provide_context(cx, default_res_options);
+    provide_context(cx, default_res_options.clone());
+    leptos::cookie::provide_server_cookies(cx, cookie_header, move |cookie| {
+        if let Ok(value) = HeaderValue::from_str(&cookie) {
+            default_res_options.append_header(header::SET_COOKIE, value);
+        }
+    });
     provide_server_redirect(cx, move |path| redirect(cx, path));
     #[cfg(feature = "nonce")]
     leptos::nonce::provide_nonce(cx);
+    ServerFnContext::provide(cx);
 }
 
 /// Returns a Viz [Handler](viz::Handler) that listens for a `GET` request and tries
@@ -944,8 +1096,9 @@ where
                                             let app = {
                                                 let full_path = full_path.clone();
                                                 let req_parts = generate_request_parts(req).await;
+                                                let options = options.clone();
                                                 move |cx| {
-                                                    provide_contexts(cx, full_path, req_parts, default_res_options);
+                                                    provide_contexts(cx, &options, full_path, req_parts, default_res_options);
                                                     app_fn(cx).into_view(cx)
                                                 }
                                             };
@@ -1129,6 +1282,14 @@ impl LeptosRoutes for Router {
         paths.iter().fold(self, |router, listing| {
             let path = listing.path();
             let mode = listing.mode();
+            // lets the app find out how it is being rendered, with use_ssr_mode()
+            let additional_context = {
+                let additional_context = additional_context.clone();
+                move |cx: leptos::Scope| {
+                    provide_context(cx, mode);
+                    additional_context(cx);
+                }
+            };
 
             listing.methods().fold(router, |router, method| match mode {
                 SsrMode::OutOfOrder => {
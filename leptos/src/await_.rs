@@ -108,7 +108,7 @@ where
     let view = store_value(cx, children);
     view! { cx,
         <Suspense fallback=|| ()>
-            {move || res.with(cx, |data| view.with_value(|view| view(cx, data)))}
+            {move || res.map(|data| view.with_value(|view| view(cx, data)))}
         </Suspense>
     }
 }
@@ -0,0 +1,182 @@
+//! Callbacks define a standard way to store functions and closures, so that they can be passed
+//! down through component props.
+//!
+//! # Types
+//! This module provides two callback types:
+//! - [`Callback`] is a reference-counted function that is cheap to clone. It is the type to use
+//!   for component props.
+//! - [`StoredCallback`] stores a function in the reactive system, so that it is `Copy` and can be
+//!   moved into several closures without cloning.
+//!
+//! # Usage
+//! Because [`Callback`] implements `From` for any closure, you can use `#[prop(into)]` so that
+//! callers can pass a closure directly.
+//!
+//! ```
+//! # use leptos::*;
+//! #[component]
+//! fn MyButton(
+//!     cx: Scope,
+//!     #[prop(into)] on_select: Callback<usize>,
+//! ) -> impl IntoView {
+//!     view! { cx,
+//!         <button on:click=move |_| on_select.call(42)>"Select"</button>
+//!     }
+//! }
+//!
+//! # #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+//! # run_scope(create_runtime(), |cx| {
+//! let (selected, set_selected) = create_signal(cx, None);
+//! view! { cx,
+//!     <MyButton on_select=move |id| set_selected.set(Some(id))/>
+//! }
+//! # ;
+//! # });
+//! # }
+//! ```
+//!
+//! A [`Callback`] can be passed on to a child component as it is, which only clones the
+//! reference-counted pointer: the function itself is allocated once, when the callback is
+//! created, and not each time a component renders.
+
+use leptos_reactive::{store_value, Scope, StoredValue};
+use std::{fmt, rc::Rc};
+
+/// A reference-counted function that takes an `In` and returns an `Out`.
+///
+/// Cloning a `Callback` is cheap, and clones call the same function. Two callbacks are equal if
+/// they call the same function.
+///
+/// ```
+/// # use leptos::*;
+/// let add_one = Callback::new(|n: i32| n + 1);
+/// let also_add_one = add_one.clone();
+/// assert_eq!(also_add_one.call(1), 2);
+/// assert_eq!(add_one, also_add_one);
+/// ```
+pub struct Callback<In: 'static, Out: 'static = ()>(Rc<dyn Fn(In) -> Out>);
+
+impl<In, Out> Callback<In, Out> {
+    /// Creates a new callback from the given function.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(In) -> Out + 'static,
+    {
+        Self(Rc::new(f))
+    }
+
+    /// Calls the callback with the given input.
+    #[inline(always)]
+    pub fn call(&self, input: In) -> Out {
+        (self.0)(input)
+    }
+}
+
+impl<In, Out> Clone for Callback<In, Out> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<In, Out> fmt::Debug for Callback<In, Out> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Callback").finish()
+    }
+}
+
+impl<In, Out> PartialEq for Callback<In, Out> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<In, Out> Eq for Callback<In, Out> {}
+
+impl<F, In, Out> From<F> for Callback<In, Out>
+where
+    F: Fn(In) -> Out + 'static,
+{
+    fn from(f: F) -> Self {
+        Self::new(f)
+    }
+}
+
+/// A [`Callback`] stored in the reactive system with [`store_value`], which makes it `Copy`.
+///
+/// Use this when a callback needs to be moved into several closures. Like a
+/// [`StoredValue`], it is disposed along with the [`Scope`] in which it was created.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (count, set_count) = create_signal(cx, 0);
+/// let add = StoredCallback::new(cx, move |n: i32| set_count.update(|c| *c += n));
+/// let add_one = move || add.call(1);
+/// let add_two = move || add.call(2);
+/// add_one();
+/// add_two();
+/// assert_eq!(count.get(), 3);
+/// # });
+/// ```
+pub struct StoredCallback<In: 'static, Out: 'static = ()>(
+    StoredValue<Callback<In, Out>>,
+);
+
+impl<In, Out> StoredCallback<In, Out> {
+    /// Stores the given function in the given [`Scope`].
+    pub fn new<F>(cx: Scope, f: F) -> Self
+    where
+        F: Fn(In) -> Out + 'static,
+    {
+        Self::from_callback(cx, Callback::new(f))
+    }
+
+    /// Stores an existing [`Callback`] in the given [`Scope`].
+    pub fn from_callback(cx: Scope, callback: Callback<In, Out>) -> Self {
+        Self(store_value(cx, callback))
+    }
+
+    /// Calls the callback with the given input.
+    ///
+    /// # Panics
+    /// Panics if the [`Scope`] in which the callback was stored has been disposed.
+    pub fn call(&self, input: In) -> Out {
+        self.0.get_value().call(input)
+    }
+
+    /// Returns the stored [`Callback`], so that it can be passed to a component prop.
+    ///
+    /// # Panics
+    /// Panics if the [`Scope`] in which the callback was stored has been disposed.
+    pub fn callback(&self) -> Callback<In, Out> {
+        self.0.get_value()
+    }
+}
+
+impl<In, Out> Clone for StoredCallback<In, Out> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<In, Out> Copy for StoredCallback<In, Out> {}
+
+impl<In, Out> fmt::Debug for StoredCallback<In, Out> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StoredCallback").field(&self.0).finish()
+    }
+}
+
+impl<In, Out> PartialEq for StoredCallback<In, Out> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<In, Out> Eq for StoredCallback<In, Out> {}
+
+impl<In, Out> From<StoredCallback<In, Out>> for Callback<In, Out> {
+    fn from(stored: StoredCallback<In, Out>) -> Self {
+        stored.callback()
+    }
+}
@@ -0,0 +1,104 @@
+use core::time::Duration;
+use leptos::component;
+use leptos_dom::{IntoView, View};
+use leptos_macro::view;
+use leptos_reactive::{
+    create_effect, create_rw_signal, on_cleanup, runtime_stats,
+    signal_prelude::*, RuntimeStats, Scope,
+};
+
+/// Shows the [`runtime_stats`] of the app in a small box in the corner of
+/// the page, refreshed on an interval.
+///
+/// This is meant to be rendered temporarily, while looking for memory leaks:
+/// click around the app and watch for numbers that only ever grow. In debug
+/// mode, it also shows the number of live event handlers and of scopes
+/// listed by [`leak_report`](leptos_reactive::leak_report).
+///
+/// ```rust
+/// # use leptos::*;
+/// # use core::time::Duration;
+/// # #[component]
+/// # pub fn App(cx: Scope) -> impl IntoView {
+/// view! { cx,
+///     <main>"..."</main>
+///     <DebugOverlay interval=Duration::from_millis(500)/>
+/// }
+/// # }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all)
+)]
+#[component]
+pub fn DebugOverlay(
+    /// The scope the component is running in
+    cx: Scope,
+    /// How often the numbers are refreshed. Defaults to one second.
+    #[prop(optional)]
+    interval: Option<Duration>,
+) -> impl IntoView {
+    let stats = create_rw_signal(cx, runtime_stats(cx));
+    #[cfg(debug_assertions)]
+    let leaked = create_rw_signal(cx, leptos_reactive::leak_report(cx).len());
+
+    create_effect(cx, move |_| {
+        // there is nothing to refresh outside the browser
+        if !leptos_dom::is_browser() {
+            return;
+        }
+        let handle = leptos_dom::helpers::set_interval_with_handle(
+            move || {
+                stats.set(runtime_stats(cx));
+                #[cfg(debug_assertions)]
+                leaked.set(leptos_reactive::leak_report(cx).len());
+            },
+            interval.unwrap_or(Duration::from_secs(1)),
+        )
+        .expect("set interval in DebugOverlay");
+        on_cleanup(cx, move || handle.clear());
+    });
+
+    let row = move |label: &'static str, value: fn(&RuntimeStats) -> usize| {
+        view! { cx,
+            <tr>
+                <th>{label}</th>
+                <td>{move || stats.with(value)}</td>
+            </tr>
+        }
+        .into_view(cx)
+    };
+    #[allow(unused_mut)]
+    let mut rows: Vec<View> = vec![
+        row("scopes", |stats| stats.scopes),
+        row("signals", |stats| stats.signals),
+        row("memos", |stats| stats.memos),
+        row("effects", |stats| stats.effects),
+        row("resources", |stats| stats.resources),
+        row("stored values", |stats| stats.stored_values),
+    ];
+    // event handlers and leaks are only tracked in debug mode
+    #[cfg(debug_assertions)]
+    rows.push(row("event handlers", |stats| stats.event_handlers));
+    #[cfg(debug_assertions)]
+    rows.push(
+        view! { cx,
+            <tr>
+                <th>"leaked scopes"</th>
+                <td>{move || leaked.get()}</td>
+            </tr>
+        }
+        .into_view(cx),
+    );
+
+    view! { cx,
+        <table
+            class="leptos-debug-overlay"
+            style="position: fixed; right: 0; bottom: 0; z-index: 2147483647; \
+                   padding: 0.5em; background: rgba(0, 0, 0, 0.8); color: white; \
+                   font: 12px monospace; text-align: left; pointer-events: none"
+        >
+            {rows}
+        </table>
+    }
+}
@@ -10,6 +10,15 @@ use leptos_reactive::{
 /// This component lets you define a fallback that should be rendered in that
 /// error case, allowing you to handle errors within a section of the interface.
 ///
+/// Any `Send + Sync` type that implements [`std::error::Error`] can be returned,
+/// so a component can return `Result<impl IntoView, MyError>` and use `?`. Each
+/// error is kept in the boundary’s [`Errors`] under the key of the place in the
+/// view where it was returned. Removing one error, for example with a “Retry”
+/// button next to it in the fallback, runs only the view that returned it
+/// again, and clearing them with [`Errors::clear`] runs each of those views
+/// again. A view can only run again if it is rendered in a reactive closure,
+/// like `{move || load_user(id)}`.
+///
 /// ```
 /// # use leptos_reactive::*;
 /// # use leptos_macro::*;
@@ -8,6 +8,13 @@ use std::hash::Hash;
 /// This is much more efficient than naively iterating over nodes with `.iter().map(|n| view! { cx,  ... })...`,
 /// as it avoids re-creating DOM nodes that are not being changed.
 ///
+/// When hydrating, each server-rendered row is claimed by its key rather than
+/// by its position, so the list on the client may be in a different order
+/// than on the server. Rows the server did not render are rendered on the
+/// client, and server-rendered rows with keys the client doesn't have are
+/// removed. For this to work, a key must hash to the same value on the server
+/// and the client.
+///
 /// ```
 /// # use leptos::*;
 ///
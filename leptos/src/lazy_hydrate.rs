@@ -0,0 +1,68 @@
+use crate::Children;
+use leptos::component;
+pub use leptos_dom::lazy_hydration::HydrateOn;
+use leptos_dom::{lazy_hydration::lazy_hydrate, IntoView};
+use leptos_reactive::Scope;
+
+/// Defers the hydration of its children, so that parts of a server-rendered
+/// page that don’t need to be interactive right away, like a footer or a
+/// cookie banner, don’t delay the hydration of the rest of it.
+///
+/// On the server, the children are rendered as usual, inside a
+/// `<leptos-lazy-hydrate>` element that doesn’t affect the layout. In the
+/// browser, the server-rendered HTML is left untouched until the trigger
+/// given by `on` fires, and only then are the children run and their
+/// listeners attached:
+/// - [`HydrateOn::Idle`], the default, once the browser is idle;
+/// - [`HydrateOn::Visible`], once the children enter the viewport;
+/// - [`HydrateOn::Interaction`], once the user first clicks, touches, types
+///   or moves the focus inside the children.
+///
+/// Whatever the trigger, an event that reaches the children before they are
+/// hydrated hydrates them right away, and is then handled as if they had
+/// been hydrated all along. A `<LazyHydrate/>` inside another one only
+/// starts waiting for its trigger once the outer one has been hydrated.
+///
+/// When the component is rendered in the browser without hydration, for
+/// example after navigating to another page, the children are rendered right
+/// away.
+///
+/// Resources should be created outside of a `<LazyHydrate/>`, and read
+/// inside it, as resources created by the children in the browser won’t
+/// match the ones that were serialized by the server.
+///
+/// ```rust
+/// # use leptos::*;
+/// # #[component]
+/// # fn CookieBanner(cx: Scope) -> impl IntoView {}
+/// # #[component]
+/// # pub fn App(cx: Scope) -> impl IntoView {
+/// view! { cx,
+///     <main>"…"</main>
+///     <LazyHydrate on=HydrateOn::Visible>
+///         <footer>
+///             <button on:click=|_| ()>"Back to top"</button>
+///         </footer>
+///     </LazyHydrate>
+///     <LazyHydrate on=HydrateOn::Interaction>
+///         <CookieBanner/>
+///     </LazyHydrate>
+/// }
+/// # }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all)
+)]
+#[component]
+pub fn LazyHydrate(
+    /// The scope the component is running in
+    cx: Scope,
+    /// When the children are hydrated
+    #[prop(optional)]
+    on: HydrateOn,
+    /// The components whose hydration is deferred
+    children: Children,
+) -> impl IntoView {
+    lazy_hydrate(cx, on, children)
+}
@@ -0,0 +1,101 @@
+use crate::Children;
+use leptos::component;
+use leptos_dom::{
+    create_node_ref,
+    helpers::{use_intersection_observer, IntersectionOptions},
+    html, HydrationCtx, IntoView,
+};
+use leptos_macro::view;
+use leptos_reactive::{signal_prelude::*, Scope};
+use std::cell::Cell;
+
+/// A component that shows its fallback until the fallback has entered the
+/// viewport, and then shows its children instead.
+///
+/// The children are only created once they are shown, in a new child scope,
+/// and only once, so this can be used to defer images, embeds or expensive
+/// components that are below the fold.
+///
+/// On the server, the fallback is rendered by default. Set `ssr_children` to
+/// render the children instead, for example so that they are indexed by search
+/// engines; they are then hydrated right away in the browser.
+///
+/// ```rust
+/// # use leptos::*;
+/// # #[component]
+/// # pub fn App(cx: Scope) -> impl IntoView {
+/// view! { cx,
+///     <LazyLoad
+///         fallback=|cx| view! { cx, <div class="placeholder"/> }
+///         root_margin="200px"
+///     >
+///         <img src="/large-image.jpg"/>
+///     </LazyLoad>
+/// }
+/// # }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all)
+)]
+#[component]
+pub fn LazyLoad<F, IV>(
+    /// The scope the component is running in
+    cx: Scope,
+    /// The components that are shown once the fallback is visible
+    children: Children,
+    /// A closure that returns what gets rendered until it is visible
+    fallback: F,
+    /// A margin around the viewport, like `"200px"`, that makes the children
+    /// load before the fallback is actually visible
+    #[prop(optional, into)]
+    root_margin: Option<String>,
+    /// Whether to render the children rather than the fallback on the server
+    #[prop(optional)]
+    ssr_children: bool,
+) -> impl IntoView
+where
+    F: Fn(Scope) -> IV + 'static,
+    IV: IntoView,
+{
+    let visible = create_rw_signal(
+        cx,
+        ssr_children
+            && (leptos_dom::is_server() || HydrationCtx::is_hydrating()),
+    );
+    let sentinel = create_node_ref::<html::Div>(cx);
+    let children = Cell::new(Some(children));
+
+    let observer = use_intersection_observer(
+        cx,
+        sentinel,
+        IntersectionOptions {
+            root_margin,
+            ..Default::default()
+        },
+        move |entries| {
+            if entries.iter().any(|entry| entry.is_intersecting()) {
+                visible.set(true);
+            }
+        },
+    );
+
+    move || {
+        if visible.get() {
+            observer.disconnect();
+            // the children can only be created once, so they must not
+            // rerender this when they read a signal
+            cx.untrack(|| {
+                children
+                    .take()
+                    .map(|children| {
+                        cx.run_child_scope(|cx| children(cx).into_view(cx)).0
+                    })
+                    .into_view(cx)
+            })
+        } else {
+            view! { cx, <div node_ref=sentinel>{fallback(cx)}</div> }
+                .into_view(cx)
+        }
+    }
+}
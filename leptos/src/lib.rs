@@ -88,14 +88,31 @@
 //!   from the server to the client.
 //! - `miniserde` In SSR/hydrate mode, uses [miniserde](https://docs.rs/miniserde/latest/miniserde/) to serialize resources and send them
 //!   from the server to the client.
-//! - `tracing` Adds additional support for [`tracing`](https://docs.rs/tracing/latest/tracing/) to components.
+//! - `tracing` Adds additional support for [`tracing`](https://docs.rs/tracing/latest/tracing/): a span
+//!   for each component, server function call, resource fetch and `<Suspense/>` flush, and events
+//!   for hydration mismatches and effect panics.
 //! - `default-tls` Use default native TLS support. (Only applies when using server functions with a non-WASM client like a desktop app.)
 //! - `rustls` Use `rustls`. (Only applies when using server functions with a non-WASM client like a desktop app.)
+//! - `panic-overlay` In debug builds in the browser, shows panics in an overlay on the page, as well as
+//!   logging them to the console. See [`set_dev_overlay`] to turn the overlay off.
+//! - `server-signal` Enables `use_server_signal` and, on the server, `create_server_signal`, for live
+//!   state that is shared by every connected client.
+//! - `server-cache` Allows server functions to cache their results on the server with
+//!   `#[server(MyFn, "/api", cache = "30s")]`, and enables [`invalidate_server_cache`].
+//! - `debug-timing` Records when each resource was created, fetched and serialized, which can be read
+//!   with [`resource_timings`] and is summarized in an HTML comment at the end of server-rendered pages.
 //! - `template_macro` Enables the [`template!`](leptos_macro::template) macro, which offers faster DOM node creation for some use cases in `csr`.
 //!
 //! **Important Note:** You must enable one of `csr`, `hydrate`, or `ssr` to tell Leptos
 //! which mode your app is operating in. You should only enable one of these per build target,
 //! i.e., you should not have both `hydrate` and `ssr` enabled for your server binary, only `ssr`.
+//! Enabling both in the same build is a compile error.
+//!
+//! A server-rendered app is built twice: once as a server binary with `ssr`, and once as a
+//! WASM library with `hydrate` that the server sends to the browser. This is the setup that
+//! [`cargo-leptos`](https://github.com/leptos-rs/cargo-leptos) uses, with `bin-features = ["ssr"]`
+//! and `lib-features = ["hydrate"]`. An `ssr` build leaves out the browser-only code, like
+//! the JavaScript glue for spawning futures and reading the hydration data.
 //!
 //! # A Simple Counter
 //!
@@ -142,10 +159,22 @@
 //! # }
 //! ```
 
+#[cfg(all(feature = "ssr", feature = "hydrate"))]
+compile_error!(
+    "the `ssr` and `hydrate` features of `leptos` can't be enabled in the \
+     same build. Build the server binary with `ssr` and the WASM library \
+     that is sent to the browser with `hydrate`, for example with \
+     cargo-leptos's `bin-features = [\"ssr\"]` and `lib-features = \
+     [\"hydrate\"]`. If your crate enables one of them by default, make \
+     sure that the other build disables default features."
+);
+
 mod additional_attributes;
 pub use additional_attributes::*;
 mod await_;
 pub use await_::*;
+pub mod callback;
+pub use callback::{Callback, StoredCallback};
 pub use leptos_config::{self, get_configuration, LeptosOptions};
 #[cfg(not(all(
     target_arch = "wasm32",
@@ -156,46 +185,69 @@ pub mod ssr {
     pub use leptos_dom::{ssr::*, ssr_in_order::*};
 }
 pub use leptos_dom::{
-    self, create_node_ref, debug_warn, document, error, ev, helpers::*, html,
-    log, math, mount_to, mount_to_body, nonce, svg, warn, window, Attribute,
-    Class, CollectView, Errors, Fragment, HtmlElement, IntoAttribute,
-    IntoClass, IntoProperty, IntoStyle, IntoView, NodeRef, Property, View,
+    self, cookie, create_node_ref, debug_warn, document, error, ev, helpers::*,
+    hotkeys, html, hydrate_to, js_sys, log, math, mount_to, mount_to_body,
+    nonce, set_dev_overlay, svg, warn, wasm_bindgen, web_sys, window,
+    Attribute, Class, CollectView, DynChild, Either, EitherOf3, EitherOf4,
+    EitherOf5, EitherOf6, EitherOf7, EitherOf8, ErrorKey, Errors, Fragment,
+    HtmlElement, IntoAttribute, IntoClass, IntoProperty, IntoStyle, IntoView,
+    NodeRef, Property, View,
 };
 
 /// Types to make it easier to handle errors in your application.
 pub mod error {
     pub use server_fn::error::{Error, Result};
 }
+#[cfg(all(target_arch = "wasm32", feature = "template_macro"))]
+pub use leptos_macro::template;
 #[cfg(not(any(target_arch = "wasm32", feature = "template_macro")))]
 pub use leptos_macro::view as template;
 pub use leptos_macro::{component, server, slot, view, Params};
 pub use leptos_reactive::*;
+#[cfg(feature = "broadcast")]
+pub use leptos_server::invalidate_on_broadcast;
 pub use leptos_server::{
     self, create_action, create_multi_action, create_server_action,
     create_server_multi_action, Action, MultiAction, ServerFn, ServerFnError,
     ServerFnErrorErr,
 };
+#[cfg(all(feature = "server-signal", feature = "ssr"))]
+pub use leptos_server::{create_server_signal, ServerSignal};
+#[cfg(feature = "server-signal")]
+pub use leptos_server::{use_server_signal, PatchOp, ServerSignalMessage};
 pub use server_fn::{self, ServerFn as _};
+#[cfg(feature = "server-cache")]
+pub use server_fn::{invalidate_all_server_cache, invalidate_server_cache};
 pub use typed_builder;
-#[cfg(all(target_arch = "wasm32", feature = "template_macro"))]
-pub use {leptos_macro::template, wasm_bindgen, web_sys};
 mod error_boundary;
 pub use error_boundary::*;
 mod animated_show;
+mod debug_overlay;
 mod for_loop;
+mod lazy_hydrate;
+mod lazy_load;
+mod noscript;
+mod provider;
 mod show;
 pub use animated_show::*;
+pub use debug_overlay::*;
 pub use for_loop::*;
+pub use lazy_hydrate::*;
+pub use lazy_load::*;
+pub use noscript::*;
+pub use provider::*;
 pub use show::*;
 pub use suspense_component::*;
 mod suspense_component;
 mod text_prop;
 mod transition;
+mod use_id;
 pub use text_prop::TextProp;
 #[cfg(any(debug_assertions, feature = "ssr"))]
 #[doc(hidden)]
 pub use tracing;
 pub use transition::*;
+pub use use_id::*;
 extern crate self as leptos;
 
 /// The most common type for the `children` property on components,
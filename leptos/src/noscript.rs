@@ -0,0 +1,57 @@
+use crate::Children;
+use leptos::component;
+use leptos_dom::{document, html, HydrationCtx, IntoView, View};
+use leptos_reactive::Scope;
+
+/// Renders its children inside a
+/// [`<noscript>`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/noscript)
+/// on the server, and nothing in the browser.
+///
+/// This is useful for telling users who have JavaScript turned off about
+/// anything on the page that only works with it. When the page is hydrated,
+/// the server-rendered `<noscript>` is removed, without being matched against
+/// the client’s view, so its children never run in the browser.
+///
+/// ```rust
+/// # use leptos::*;
+/// # #[component]
+/// # pub fn App(cx: Scope) -> impl IntoView {
+/// view! { cx,
+///     <button on:click=|_| ()>"Copy link"</button>
+///     <NoScript>
+///         <p>"Copying the link requires JavaScript."</p>
+///     </NoScript>
+/// }
+/// # }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all)
+)]
+#[component]
+pub fn NoScript(
+    /// The scope the component is running in
+    cx: Scope,
+    /// The components shown when JavaScript is not available
+    children: Children,
+) -> impl IntoView {
+    // the `<noscript>` and its children use their own hydration keys, which
+    // the client can skip without rendering them
+    let key = HydrationCtx::peek();
+    HydrationCtx::next_component();
+
+    let views: Vec<View> = if leptos_dom::is_server() {
+        vec![html::noscript(cx).child(children(cx)).into_view(cx)]
+    } else {
+        let id = HydrationCtx::id();
+        if HydrationCtx::is_hydrating() {
+            if let Some(el) = document().get_element_by_id(&format!("_{id}")) {
+                el.remove();
+            }
+        }
+        vec![]
+    };
+
+    HydrationCtx::continue_from(key);
+    views
+}
@@ -0,0 +1,60 @@
+use crate::Children;
+use leptos::component;
+use leptos_dom::IntoView;
+use leptos_reactive::{provide_context, Scope};
+
+/// Provides a context value of type `T` to its children, and only to its
+/// children.
+///
+/// This creates a new child [`Scope`], provides the value there with
+/// [`provide_context`], and renders the children inside it. Any context of
+/// the same type that was provided higher up in the tree is shadowed for the
+/// children, but is left untouched for everything else.
+///
+/// ```rust
+/// # use leptos::*;
+/// #[derive(Clone)]
+/// struct Config(&'static str);
+///
+/// #[component]
+/// fn ShowConfig(cx: Scope) -> impl IntoView {
+///     let config = expect_context::<Config>(cx);
+///     view! { cx, <p>{config.0}</p> }
+/// }
+///
+/// # run_scope(create_runtime(), |cx| {
+/// provide_context(cx, Config("outer"));
+///
+/// view! { cx,
+///   // shows "inner"
+///   <Provider value=Config("inner")>
+///     <ShowConfig/>
+///   </Provider>
+///   // shows "outer"
+///   <ShowConfig/>
+/// }
+/// # ;
+/// # });
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all)
+)]
+#[component]
+pub fn Provider<T>(
+    /// The scope the component is running in
+    cx: Scope,
+    /// The value to be provided to the children.
+    value: T,
+    /// The children, which will be able to access the value.
+    children: Children,
+) -> impl IntoView
+where
+    T: Clone + 'static,
+{
+    let (view, _) = cx.run_child_scope(|cx| {
+        provide_context(cx, value);
+        children(cx).into_view(cx)
+    });
+    view
+}
@@ -1,7 +1,16 @@
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use leptos_dom::helpers::{set_timeout_with_handle, TimeoutHandle};
 use leptos_dom::{DynChild, HydrationCtx, IntoView};
 use leptos_macro::component;
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use leptos_reactive::{
+    create_effect, create_memo, create_rw_signal, on_cleanup, Memo, RwSignal,
+    SignalGet, SignalUpdate, SignalWith,
+};
 use leptos_reactive::{provide_context, Scope, SuspenseContext};
-use std::rc::Rc;
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use std::cell::Cell;
+use std::{rc::Rc, time::Duration};
 
 /// If any [Resources](leptos_reactive::Resource) are read in the `children` of this
 /// component, it will show the `fallback` while they are loading. Once all are resolved,
@@ -11,6 +20,13 @@ use std::rc::Rc;
 /// those resources are read under the suspense), so you cannot assume that resources have
 /// `Some` value in `children`.
 ///
+/// In the browser, `fallback_delay` and `min_fallback_duration` can be used to avoid a brief
+/// flash of the fallback when resources load quickly: the fallback is only shown once loading
+/// has taken longer than `fallback_delay`, and once shown, it stays for at least
+/// `min_fallback_duration`. Until the resources have loaded for the first time, nothing is shown
+/// during the delay, rather than `children` without their data. Both are ignored during server
+/// rendering.
+///
 /// ```
 /// # use leptos_reactive::*;
 /// # use leptos_macro::*;
@@ -27,7 +43,7 @@ use std::rc::Rc;
 ///   <div>
 ///     <Suspense fallback=move || view! { cx, <p>"Loading (Suspense Fallback)..."</p> }>
 ///       {move || {
-///           cats.read(cx).map(|data| match data {
+///           cats.get().map(|data| match data {
 ///             None => view! { cx,  <pre>"Error"</pre> }.into_view(cx),
 ///             Some(cats) => cats
 ///                 .iter()
@@ -57,6 +73,15 @@ pub fn Suspense<F, E, V>(
     fallback: F,
     /// Children will be displayed once all `async` [Resources](leptos_reactive::Resource) have resolved.
     children: Box<dyn Fn(Scope) -> V>,
+    /// How long resources can be loading before the fallback is shown. Until then, the
+    /// `children` continue to be shown if they have been shown before, and nothing is shown
+    /// otherwise. Defaults to no delay.
+    #[prop(optional)]
+    fallback_delay: Duration,
+    /// The minimum amount of time the fallback is shown for, once it has been shown.
+    /// Defaults to no minimum.
+    #[prop(optional)]
+    min_fallback_duration: Duration,
 ) -> impl IntoView
 where
     F: Fn() -> E + 'static,
@@ -71,6 +96,12 @@ where
 
     let current_id = HydrationCtx::next_component();
 
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    let showing =
+        fallback_visibility(cx, context, fallback_delay, min_fallback_duration);
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    let _ = (fallback_delay, min_fallback_duration);
+
     let child = DynChild::new({
         let children = Rc::new(orig_children(cx).into_view(cx));
         #[cfg(not(any(feature = "csr", feature = "hydrate")))]
@@ -78,10 +109,10 @@ where
         move || {
             #[cfg(any(feature = "csr", feature = "hydrate"))]
             {
-                if context.ready() {
-                    (*children).clone()
-                } else {
-                    fallback().into_view(cx)
+                match showing.get() {
+                    Showing::Fallback => fallback().into_view(cx),
+                    Showing::Nothing => ().into_view(cx),
+                    Showing::Children => (*children).clone(),
                 }
             }
             #[cfg(not(any(feature = "csr", feature = "hydrate")))]
@@ -157,3 +188,321 @@ where
 
     leptos_dom::View::Suspense(current_id, core_component)
 }
+
+// what should currently be shown, taking the fallback's delay and minimum duration into
+// account; without either, the fallback is shown whenever any resources are pending
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn fallback_visibility(
+    cx: Scope,
+    context: SuspenseContext,
+    delay: Duration,
+    min_duration: Duration,
+) -> Memo<Showing> {
+    let timing = create_rw_signal(
+        cx,
+        FallbackTiming::new(delay, min_duration, HydrationCtx::is_hydrating()),
+    );
+
+    if !delay.is_zero() || !min_duration.is_zero() {
+        let timers = Rc::new(FallbackTimers::default());
+        create_effect(cx, {
+            let timers = Rc::clone(&timers);
+            move |_| {
+                let pending = !context.ready();
+                if !pending {
+                    if let Some(handle) = timers.delay.take() {
+                        handle.clear();
+                    }
+                }
+                let timer =
+                    timing.try_update(|timing| timing.set_pending(pending));
+                start_fallback_timer(timing, &timers, timer.flatten());
+            }
+        });
+        on_cleanup(cx, move || {
+            [timers.delay.take(), timers.min_duration.take()]
+                .into_iter()
+                .flatten()
+                .for_each(|handle| handle.clear());
+        });
+    }
+
+    create_memo(cx, move |_| {
+        let pending = !context.ready();
+        timing.with(|timing| timing.showing(pending))
+    })
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+#[derive(Default)]
+struct FallbackTimers {
+    delay: Cell<Option<TimeoutHandle>>,
+    min_duration: Cell<Option<TimeoutHandle>>,
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn start_fallback_timer(
+    timing: RwSignal<FallbackTiming>,
+    timers: &Rc<FallbackTimers>,
+    timer: Option<FallbackTimer>,
+) {
+    if let Some(timer) = timer {
+        let handle = set_timeout_with_handle(
+            {
+                let timers = Rc::clone(timers);
+                move || {
+                    // the timing may have been disposed along with the <Suspense/>
+                    let next = timing.try_update(|timing| match timer {
+                        FallbackTimer::Delay(_) => timing.delay_elapsed(),
+                        FallbackTimer::MinDuration(_) => {
+                            timing.min_duration_elapsed();
+                            None
+                        }
+                    });
+                    start_fallback_timer(timing, &timers, next.flatten());
+                }
+            },
+            timer.duration(),
+        )
+        .ok();
+        match timer {
+            FallbackTimer::Delay(_) => timers.delay.set(handle),
+            FallbackTimer::MinDuration(_) => timers.min_duration.set(handle),
+        }
+    }
+}
+
+/// What a `<Suspense/>` shows.
+#[cfg(any(feature = "csr", feature = "hydrate", test))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Showing {
+    Fallback,
+    /// Resources are loading for the first time, but the fallback's delay hasn't elapsed yet.
+    Nothing,
+    Children,
+}
+
+/// The state of a fallback with a delay before it is shown and a minimum duration once shown.
+#[cfg(any(feature = "csr", feature = "hydrate", test))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FallbackTiming {
+    delay: Duration,
+    min_duration: Duration,
+    pending: bool,
+    delay_elapsed: bool,
+    holding: bool,
+    /// Whether resources have ever finished loading, so that the children have been shown.
+    resolved: bool,
+}
+
+/// A timer that should be started after a change in [`FallbackTiming`].
+#[cfg(any(feature = "csr", feature = "hydrate", test))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FallbackTimer {
+    Delay(Duration),
+    MinDuration(Duration),
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate", test))]
+impl FallbackTimer {
+    #[allow(dead_code)] // not used in tests
+    fn duration(self) -> Duration {
+        match self {
+            FallbackTimer::Delay(duration)
+            | FallbackTimer::MinDuration(duration) => duration,
+        }
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate", test))]
+impl FallbackTiming {
+    // while hydrating, the fallback is shown without a delay, to match the server-rendered HTML
+    fn new(delay: Duration, min_duration: Duration, hydrating: bool) -> Self {
+        Self {
+            delay,
+            min_duration,
+            pending: false,
+            delay_elapsed: delay.is_zero() || hydrating,
+            holding: false,
+            resolved: false,
+        }
+    }
+
+    fn showing_fallback(&self, pending: bool) -> bool {
+        (pending && self.delay_elapsed) || self.holding
+    }
+
+    fn showing(&self, pending: bool) -> Showing {
+        if self.showing_fallback(pending) {
+            Showing::Fallback
+        } else if pending && !self.resolved {
+            Showing::Nothing
+        } else {
+            Showing::Children
+        }
+    }
+
+    fn set_pending(&mut self, pending: bool) -> Option<FallbackTimer> {
+        if !pending {
+            self.resolved = true;
+        }
+        if pending == self.pending {
+            return None;
+        }
+        self.pending = pending;
+        if !pending {
+            self.delay_elapsed = self.delay.is_zero();
+            None
+        } else if self.delay_elapsed || self.holding {
+            // the fallback is already visible, so keep it without waiting again
+            self.delay_elapsed = true;
+            self.start_holding()
+        } else {
+            Some(FallbackTimer::Delay(self.delay))
+        }
+    }
+
+    fn delay_elapsed(&mut self) -> Option<FallbackTimer> {
+        // resources resolved before the delay elapsed, so the fallback is never shown
+        if !self.pending || self.delay_elapsed {
+            return None;
+        }
+        self.delay_elapsed = true;
+        self.start_holding()
+    }
+
+    fn min_duration_elapsed(&mut self) {
+        self.holding = false;
+    }
+
+    fn start_holding(&mut self) -> Option<FallbackTimer> {
+        if self.min_duration.is_zero() || self.holding {
+            None
+        } else {
+            self.holding = true;
+            Some(FallbackTimer::MinDuration(self.min_duration))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELAY: Duration = Duration::from_millis(100);
+    const MIN: Duration = Duration::from_millis(500);
+
+    #[test]
+    fn no_delay_shows_fallback_while_pending() {
+        let mut timing =
+            FallbackTiming::new(Duration::ZERO, Duration::ZERO, false);
+        assert!(timing.showing_fallback(true));
+        assert_eq!(timing.set_pending(true), None);
+        assert!(timing.showing_fallback(true));
+        assert_eq!(timing.set_pending(false), None);
+        assert!(!timing.showing_fallback(false));
+    }
+
+    #[test]
+    fn fallback_is_not_shown_if_resolved_before_delay() {
+        let mut timing = FallbackTiming::new(DELAY, MIN, false);
+        assert!(!timing.showing_fallback(true));
+        assert_eq!(timing.set_pending(true), Some(FallbackTimer::Delay(DELAY)));
+        assert!(!timing.showing_fallback(true));
+        assert_eq!(timing.set_pending(false), None);
+        // the timer fires late, after the content has rendered
+        assert_eq!(timing.delay_elapsed(), None);
+        assert!(!timing.showing_fallback(false));
+    }
+
+    #[test]
+    fn nothing_is_shown_during_the_first_delay() {
+        let mut timing = FallbackTiming::new(DELAY, MIN, false);
+        timing.set_pending(true);
+        assert_eq!(timing.showing(true), Showing::Nothing);
+        timing.set_pending(false);
+        assert_eq!(timing.showing(false), Showing::Children);
+
+        // once the children have been shown, they stay during a later delay
+        timing.set_pending(true);
+        assert_eq!(timing.showing(true), Showing::Children);
+        timing.delay_elapsed();
+        assert_eq!(timing.showing(true), Showing::Fallback);
+    }
+
+    #[test]
+    fn fallback_is_held_for_min_duration() {
+        let mut timing = FallbackTiming::new(DELAY, MIN, false);
+        timing.set_pending(true);
+        assert_eq!(
+            timing.delay_elapsed(),
+            Some(FallbackTimer::MinDuration(MIN))
+        );
+        assert!(timing.showing_fallback(true));
+        timing.set_pending(false);
+        assert!(timing.showing_fallback(false));
+        timing.min_duration_elapsed();
+        assert!(!timing.showing_fallback(false));
+    }
+
+    #[test]
+    fn fallback_stays_visible_when_pending_again_while_held() {
+        let mut timing = FallbackTiming::new(DELAY, MIN, false);
+        timing.set_pending(true);
+        timing.delay_elapsed();
+        timing.set_pending(false);
+        assert_eq!(timing.set_pending(true), None);
+        timing.min_duration_elapsed();
+        assert!(timing.showing_fallback(true));
+    }
+
+    #[test]
+    fn fallback_is_shown_at_once_while_hydrating() {
+        let mut timing = FallbackTiming::new(DELAY, MIN, true);
+        assert!(timing.showing_fallback(true));
+        assert_eq!(
+            timing.set_pending(true),
+            Some(FallbackTimer::MinDuration(MIN))
+        );
+        timing.set_pending(false);
+        timing.min_duration_elapsed();
+        // after hydration, the delay applies as usual
+        assert_eq!(timing.set_pending(true), Some(FallbackTimer::Delay(DELAY)));
+        assert!(!timing.showing_fallback(true));
+    }
+
+    #[test]
+    fn nested_boundary_resolving_first_does_not_show_outer_fallback() {
+        let mut outer = FallbackTiming::new(DELAY * 2, MIN, false);
+        let mut inner = FallbackTiming::new(DELAY, MIN, false);
+        outer.set_pending(true);
+        inner.set_pending(true);
+
+        // the inner resource resolves, then the outer one, before either delay elapses
+        inner.set_pending(false);
+        outer.set_pending(false);
+
+        assert_eq!(inner.delay_elapsed(), None);
+        assert_eq!(outer.delay_elapsed(), None);
+        assert!(!inner.showing_fallback(false));
+        assert!(!outer.showing_fallback(false));
+    }
+
+    #[test]
+    fn inner_fallback_shown_under_resolved_outer_is_held() {
+        let mut outer = FallbackTiming::new(DELAY * 2, MIN, false);
+        let mut inner = FallbackTiming::new(DELAY, MIN, false);
+        outer.set_pending(true);
+        inner.set_pending(true);
+
+        // the outer resolves first; the inner one is still loading after its delay
+        outer.set_pending(false);
+        assert_eq!(
+            inner.delay_elapsed(),
+            Some(FallbackTimer::MinDuration(MIN))
+        );
+        assert_eq!(outer.delay_elapsed(), None);
+        assert!(!outer.showing_fallback(false));
+        assert!(inner.showing_fallback(true));
+    }
+}
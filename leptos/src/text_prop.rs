@@ -1,14 +1,22 @@
+use leptos_reactive::Oco;
 use std::{fmt::Debug, rc::Rc};
 
 /// Describes a value that is either a static or a reactive string, i.e.,
 /// a [String], a [&str], or a reactive `Fn() -> String`.
 #[derive(Clone)]
-pub struct TextProp(Rc<dyn Fn() -> String>);
+pub struct TextProp(Rc<dyn Fn() -> Oco<'static, str>>);
 
 impl TextProp {
     /// Accesses the current value of the property.
     #[inline(always)]
     pub fn get(&self) -> String {
+        self.get_oco().into_owned()
+    }
+
+    /// Accesses the current value of the property without copying it, if it
+    /// is a static or reference-counted string.
+    #[inline(always)]
+    pub fn get_oco(&self) -> Oco<'static, str> {
         (self.0)()
     }
 }
@@ -21,13 +29,28 @@ impl Debug for TextProp {
 
 impl From<String> for TextProp {
     fn from(s: String) -> Self {
+        // stored as an `Rc<str>`, so that each access is only a cheap clone
+        let s: Oco<'static, str> = Oco::Counted(Rc::from(s));
         TextProp(Rc::new(move || s.clone()))
     }
 }
 
-impl From<&str> for TextProp {
-    fn from(s: &str) -> Self {
-        let s = s.to_string();
+impl From<&'static str> for TextProp {
+    fn from(s: &'static str) -> Self {
+        // literals are borrowed, so they are never allocated
+        TextProp(Rc::new(move || Oco::Borrowed(s)))
+    }
+}
+
+impl From<Rc<str>> for TextProp {
+    fn from(s: Rc<str>) -> Self {
+        TextProp(Rc::new(move || Oco::Counted(Rc::clone(&s))))
+    }
+}
+
+impl From<Oco<'static, str>> for TextProp {
+    fn from(mut s: Oco<'static, str>) -> Self {
+        s.upgrade_inplace();
         TextProp(Rc::new(move || s.clone()))
     }
 }
@@ -38,6 +61,6 @@ where
 {
     #[inline(always)]
     fn from(s: F) -> Self {
-        TextProp(Rc::new(s))
+        TextProp(Rc::new(move || Oco::Owned(s())))
     }
 }
@@ -7,6 +7,7 @@ use leptos_reactive::{
 use std::{
     cell::{Cell, RefCell},
     rc::Rc,
+    time::Duration,
 };
 
 /// If any [Resource](leptos_reactive::Resource)s are read in the `children` of this
@@ -42,7 +43,7 @@ use std::{
 ///       set_pending=set_pending.into()
 ///     >
 ///       {move || {
-///           cats.read(cx).map(|data| match data {
+///           cats.get().map(|data| match data {
 ///             None => view! { cx,  <pre>"Error"</pre> }.into_view(cx),
 ///             Some(cats) => cats
 ///                 .iter()
@@ -77,6 +78,14 @@ pub fn Transition<F, E>(
     set_pending: Option<SignalSetter<bool>>,
     /// Will be displayed once all resources have resolved.
     children: Box<dyn Fn(Scope) -> Fragment>,
+    /// How long resources can be loading for the first time before the fallback is shown.
+    /// See [`Suspense`](crate::Suspense).
+    #[prop(optional)]
+    fallback_delay: Duration,
+    /// The minimum amount of time the fallback is shown for, once it has been shown.
+    /// See [`Suspense`](crate::Suspense).
+    #[prop(optional)]
+    min_fallback_duration: Duration,
 ) -> impl IntoView
 where
     F: Fn() -> E + 'static,
@@ -144,6 +153,8 @@ where
                 });
                 frag
             }))
+            .fallback_delay(fallback_delay)
+            .min_fallback_duration(min_fallback_duration)
             .build(),
     )
 }
@@ -0,0 +1,89 @@
+use leptos_dom::{HydrationCtx, HydrationKey};
+use leptos_reactive::{provide_context, use_context, Oco, Scope, ScopeId};
+use std::{cell::RefCell, collections::HashMap, panic::Location, rc::Rc};
+
+/// Returns an identifier that is unique across the document, and that is the
+/// same when a component is rendered on the server and when it is hydrated in
+/// the browser. This makes it possible to link elements together with
+/// attributes like `id`, `for` and `aria-describedby`.
+///
+/// The identifier is derived from the component’s position in the view
+/// tree, so each instance of a component, and each row rendered by
+/// [`<For/>`](crate::For), gets its own. Calling `use_id` again from the same
+/// place in the same [`Scope`], like in a closure that runs again, returns
+/// the same identifier; to get several identifiers from one place, use
+/// [`use_id_with_prefix`].
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn EmailField(cx: Scope) -> impl IntoView {
+///     let id = use_id(cx);
+///     let hint = format!("{id}-hint");
+///
+///     view! { cx,
+///         <label for=id.to_string()>"Email"</label>
+///         <input id=id.to_string() type="email" aria-describedby=hint.clone()/>
+///         <p id=hint>"We’ll never share your email."</p>
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_id(cx: Scope) -> Oco<'static, str> {
+    use_id_with_prefix(cx, "leptos-")
+}
+
+/// Returns an identifier like [`use_id`], starting with the given `prefix`
+/// rather than `leptos-`.
+///
+/// Calls with different prefixes return different identifiers, even when
+/// they are made from the same place.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let ids = ["name", "email"].map(|field| use_id_with_prefix(cx, field));
+/// assert!(ids[0].starts_with("name"));
+/// assert_ne!(ids[0], ids[1]);
+/// # });
+/// ```
+#[track_caller]
+pub fn use_id_with_prefix(cx: Scope, prefix: &str) -> Oco<'static, str> {
+    let ids = match use_context::<ScopeIds>(cx) {
+        // contexts are inherited, so the one found may be a parent scope’s
+        Some(ids) if ids.scope == cx.id => ids,
+        _ => {
+            let ids = ScopeIds {
+                scope: cx.id,
+                // taking a key, rather than peeking at the next one, keeps
+                // sibling scopes that haven’t rendered anything apart
+                key: HydrationCtx::id(),
+                ids: Default::default(),
+            };
+            provide_context(cx, ids.clone());
+            ids
+        }
+    };
+
+    let mut map = ids.ids.borrow_mut();
+    let next = map.len();
+    map.entry((Location::caller(), prefix.to_string()))
+        .or_insert_with(|| {
+            Oco::Counted(Rc::from(format!("{prefix}{}-{next}", ids.key)))
+        })
+        .clone()
+}
+
+/// The identifiers handed out in a single [`Scope`], by the place they were
+/// requested from.
+#[derive(Clone)]
+struct ScopeIds {
+    scope: ScopeId,
+    key: HydrationKey,
+    #[allow(clippy::type_complexity)]
+    ids: Rc<
+        RefCell<
+            HashMap<(&'static Location<'static>, String), Oco<'static, str>>,
+        >,
+    >,
+}
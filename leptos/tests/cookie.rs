@@ -0,0 +1,77 @@
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn use_cookie_reads_and_writes_server_cookies() {
+    use leptos::{cookie::*, *};
+    use std::{cell::RefCell, rc::Rc};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let set_cookies = Rc::new(RefCell::new(Vec::new()));
+        provide_server_cookies(
+            cx,
+            Some("session=abc; locale=en".to_string()),
+            {
+                let set_cookies = Rc::clone(&set_cookies);
+                move |cookie| set_cookies.borrow_mut().push(cookie)
+            },
+        );
+
+        let (locale, set_locale) = use_cookie_with_options(
+            cx,
+            "locale",
+            CookieOptions::default()
+                .max_age(3600)
+                .same_site(SameSite::Lax),
+        );
+        assert_eq!(locale.get(), Some("en".to_string()));
+
+        set_locale.set(Some("fr".to_string()));
+        assert_eq!(locale.get(), Some("fr".to_string()));
+
+        // options can be given for a single write
+        set_locale.set_with_options(
+            Some("de".to_string()),
+            &CookieOptions::default().max_age(60).secure(true),
+        );
+        assert_eq!(locale.get(), Some("de".to_string()));
+
+        set_locale.set(None);
+        assert_eq!(locale.get(), None);
+
+        assert_eq!(
+            *set_cookies.borrow(),
+            vec![
+                "locale=fr; Path=/; Max-Age=3600; SameSite=Lax".to_string(),
+                "locale=de; Path=/; Max-Age=60; Secure".to_string(),
+                "locale=; Path=/; Max-Age=0; SameSite=Lax".to_string(),
+            ]
+        );
+
+        let (missing, _) = use_cookie(cx, "theme");
+        assert_eq!(missing.get(), None);
+    });
+}
+
+#[test]
+fn parse_cookie_header() {
+    use leptos::cookie::parse_cookie;
+
+    assert_eq!(parse_cookie("a=1; b=2", "b"), Some("2".to_string()));
+    assert_eq!(parse_cookie("a=1;b=2=3", "b"), Some("2=3".to_string()));
+    assert_eq!(parse_cookie("ab=1", "a"), None);
+}
+
+#[test]
+fn cookie_values_are_percent_encoded() {
+    use leptos::cookie::{parse_cookie, CookieOptions};
+
+    let value = "a;b=c, \"d\" 100% é";
+    let cookie = CookieOptions::default().to_set_cookie("name", Some(value));
+    assert_eq!(
+        cookie,
+        "name=a%3Bb=c%2C%20%22d%22%20100%25%20%C3%A9; Path=/"
+    );
+    let (pair, _) = cookie.split_once("; ").unwrap();
+    assert_eq!(parse_cookie(pair, "name").as_deref(), Some(value));
+    // a `%` that doesn't start an escape is kept
+    assert_eq!(parse_cookie("name=100%", "name").as_deref(), Some("100%"));
+}
@@ -0,0 +1,65 @@
+use leptos::hotkeys::*;
+
+const NONE: [bool; 4] = [false; 4];
+const CTRL: [bool; 4] = [true, false, false, false];
+const CTRL_SHIFT: [bool; 4] = [true, false, true, false];
+const META: [bool; 4] = [false, false, false, true];
+
+#[test]
+fn hotkeys_are_parsed_from_combo_strings() {
+    let palette: Hotkey = "Ctrl+Shift+P".parse().unwrap();
+    assert!(palette.matches_key("P", CTRL_SHIFT, false));
+    assert!(!palette.matches_key("p", CTRL, false));
+
+    // aliases are normalized on both sides
+    let escape: Hotkey = "esc".parse().unwrap();
+    assert_eq!(escape.key(), "escape");
+    assert!(escape.matches_key("Escape", NONE, false));
+    assert!(!escape.matches_key("Escape", CTRL, false));
+    assert!("down".parse::<Hotkey>().unwrap().matches_key(
+        "ArrowDown",
+        NONE,
+        false
+    ));
+    assert!("ctrl++"
+        .parse::<Hotkey>()
+        .unwrap()
+        .matches_key("+", CTRL, false));
+
+    assert_eq!(
+        "ctrl+".parse::<Hotkey>(),
+        Err(HotkeyParseError::MissingKey("ctrl+".to_string()))
+    );
+    assert_eq!(
+        "hyper+k".parse::<Hotkey>(),
+        Err(HotkeyParseError::UnknownModifier("hyper".to_string()))
+    );
+}
+
+#[test]
+fn mod_is_cmd_on_apple_platforms_and_ctrl_elsewhere() {
+    let search: Hotkey = "mod+k".parse().unwrap();
+    assert!(search.matches_key("k", META, true));
+    assert!(!search.matches_key("k", CTRL, true));
+    assert!(search.matches_key("k", CTRL, false));
+    assert!(!search.matches_key("k", META, false));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn use_hotkeys_does_nothing_on_the_server() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        use_hotkeys(cx, "mod+k, ctrl+p", |_| unreachable!());
+        use_hotkeys_with_options(
+            cx,
+            "escape",
+            HotkeyOptions::default()
+                .enabled(false)
+                .in_inputs(true)
+                .prevent_default(false),
+            |_| unreachable!(),
+        );
+    });
+}
@@ -157,3 +157,861 @@ fn ssr_option() {
             .contains("<option id=\"_0-1\"></option>"));
     });
 }
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_provider_shadows_context() {
+    use leptos::*;
+
+    #[derive(Clone)]
+    struct Config(&'static str);
+
+    #[component]
+    fn ShowConfig(cx: Scope) -> impl IntoView {
+        let config = expect_context::<Config>(cx);
+        view! { cx, <p>{config.0}</p> }
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        provide_context(cx, Config("outer"));
+        let rendered = view! {
+            cx,
+            <div>
+                <Provider value=Config("inner")>
+                    <ShowConfig/>
+                </Provider>
+                <ShowConfig/>
+            </div>
+        };
+
+        let rendered = rendered.into_view(cx).render_to_string(cx);
+        let inner = rendered.find("inner").expect("inner context rendered");
+        let outer = rendered.find("outer").expect("outer context rendered");
+        assert!(inner < outer);
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_attribute_string_types() {
+    use leptos::*;
+    use std::rc::Rc;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let rendered = html::div(cx)
+            .attr("title", "say \"hi\"")
+            .attr("data-owned", String::from("owned"))
+            .attr("data-counted", Rc::<str>::from("counted"))
+            .attr("data-missing", None::<Oco<'static, str>>)
+            .style("color", Oco::Borrowed("red"));
+
+        assert!(rendered.into_view(cx).render_to_string(cx).contains(
+            " title=\"say &quot;hi&quot;\" data-owned=\"owned\" \
+             data-counted=\"counted\" style=\"color: red;\""
+        ));
+    });
+}
+
+#[test]
+fn text_props_borrow_literals() {
+    use leptos::*;
+
+    let literal = TextProp::from("literal");
+    assert!(literal.get_oco().is_borrowed());
+
+    let owned = TextProp::from(String::from("owned"));
+    assert!(owned.get_oco().is_counted());
+    assert_eq!(owned.get(), "owned");
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_style_map() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (x, _) = create_signal(cx, 10);
+        let (hidden, _) = create_signal(cx, false);
+        let rendered = view! { cx,
+            <div style=move || [
+                ("transform", Some(format!("translateX({}px)", x.get()))),
+                ("visibility", hidden.get().then(|| "hidden".to_string())),
+            ]/>
+            <div style=vec![("background", "url(\"a.png\")")]/>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(
+            rendered.contains("transform: translateX(10px);"),
+            "{rendered}"
+        );
+        assert!(
+            rendered.contains("background: url(&quot;a.png&quot;);"),
+            "{rendered}"
+        );
+        assert!(!rendered.contains("visibility"), "{rendered}");
+        assert!(!rendered.contains(";;"), "{rendered}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_boolean_and_optional_attributes() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (enabled, _) = create_signal(cx, false);
+        let (label, _) = create_signal(cx, None::<String>);
+        let open: Option<bool> = None;
+        let rendered = view! { cx,
+            <button
+                disabled=move || !enabled.get()
+                hidden=move || enabled.get()
+                aria-label=move || label.get()
+                data-count=3
+                data-open=open
+                data-closed=Some(true)
+            />
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(rendered.contains(" disabled"), "{rendered}");
+        assert!(!rendered.contains("hidden"), "{rendered}");
+        assert!(!rendered.contains("false"), "{rendered}");
+        assert!(!rendered.contains("aria-label"), "{rendered}");
+        assert!(rendered.contains("data-count=\"3\""), "{rendered}");
+        assert!(!rendered.contains("data-open"), "{rendered}");
+        assert!(rendered.contains(" data-closed"), "{rendered}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_form_state_renders_as_attributes() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (name, _) = create_signal(cx, "Ada");
+        let (subscribed, _) = create_signal(cx, true);
+        let rendered = view! { cx,
+            <form>
+                <input value=move || name.get()/>
+                <input type="checkbox" checked=move || subscribed.get()/>
+                <option value="b" selected=true>"B"</option>
+            </form>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(rendered.contains("value=\"Ada\""), "{rendered}");
+        assert!(rendered.contains(" checked"), "{rendered}");
+        assert!(rendered.contains(" selected"), "{rendered}");
+
+        let built = html::input(cx)
+            .form_attr("value", "Ada")
+            .form_attr("checked", false)
+            .into_view(cx)
+            .render_to_string(cx);
+        assert!(built.contains("value=\"Ada\""), "{built}");
+        assert!(!built.contains("checked"), "{built}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_textarea_value_renders_as_escaped_text() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (notes, _) = create_signal(cx, "a < b");
+        let rendered = view! { cx,
+            <form>
+                <textarea value="<b>hi</b>"/>
+                <textarea value=move || notes.get()/>
+            </form>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(!rendered.contains("value="), "{rendered}");
+        assert!(
+            rendered.contains(">&lt;b&gt;hi&lt;&#x2F;b&gt;</textarea>"),
+            "{rendered}"
+        );
+        assert!(rendered.contains(">a &lt; b</textarea>"), "{rendered}");
+
+        let built = html::textarea(cx)
+            .form_attr("value", "a < b")
+            .into_view(cx)
+            .render_to_string(cx);
+        assert!(!built.contains("value="), "{built}");
+        assert!(built.contains(">a &lt; b</textarea>"), "{built}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_builder_matches_view_macro() {
+    use leptos::{leptos_dom::HydrationCtx, *};
+
+    #[component]
+    fn Card(cx: Scope, title: &'static str) -> impl IntoView {
+        let (id, _) = create_signal(cx, 7);
+        html::div(cx)
+            .attr("title", "card")
+            .attr("data-id", move || id.get())
+            .child(html::h2(cx).child(title))
+            .on(ev::click, |_| ())
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        HydrationCtx::reset_id();
+        let built = html::section(cx)
+            .child(Card(cx, CardProps { title: "Hello" }))
+            .into_view(cx)
+            .render_to_string(cx);
+
+        HydrationCtx::reset_id();
+        let (id, _) = create_signal(cx, 7);
+        let expected = view! { cx,
+            <section>
+                {
+                    leptos::leptos_dom::Component::new("Card", move |cx| {
+                        view! { cx,
+                            <div title="card" data-id=move || id.get() on:click=|_| ()>
+                                <h2>"Hello"</h2>
+                            </div>
+                        }
+                    })
+                }
+            </section>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert_eq!(built, expected);
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_container_views() {
+    use leptos::{leptos_dom::HydrationCtx, *};
+
+    fn item(cx: Scope, n: i32) -> impl IntoView {
+        if n % 2 == 0 {
+            Either::Left(html::b(cx).child(n))
+        } else {
+            Either::Right(n)
+        }
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        HydrationCtx::reset_id();
+        let rendered = html::div(cx)
+            .child([item(cx, 0), item(cx, 1)])
+            .child(vec![item(cx, 2)])
+            .child((None::<&str>, Some("!")))
+            .into_view(cx)
+            .render_to_string(cx);
+
+        // each dynamic list is delimited by its own hydration markers
+        assert_eq!(
+            rendered,
+            "<div id=\"_0-1\">\
+             <!--hk=_0-3o|leptos--start--><b id=\"_0-2\">0</b>1\
+             <!--hk=_0-3c|leptos--end-->\
+             <!--hk=_0-5o|leptos--start--><b id=\"_0-4\">2</b>\
+             <!--hk=_0-5c|leptos--end-->\
+             <!--hk=_0-7o|leptos--start--><!--hk=_0-6c|leptos-unit-->!\
+             <!--hk=_0-7c|leptos--end-->\
+             </div>"
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn debug_overlay_renders_runtime_stats() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let rendered = view! { cx, <DebugOverlay/> };
+        let html = rendered.into_view(cx).render_to_string(cx);
+
+        assert!(html.contains("class=\"leptos-debug-overlay\""), "{html}");
+        assert!(html.contains(">scopes</th>"), "{html}");
+        assert!(html.contains(">event handlers</th>"), "{html}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn event_modifiers_are_not_rendered() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let rendered = view! { cx,
+            <form on:submit:prevent_default=|_| ()>
+                <button on:click:prevent_default:stop_propagation=|_| ()>"Save"</button>
+                <input on:input:undelegated:stop_propagation=|ev| {
+                    _ = event_target_value(&ev);
+                }/>
+            </form>
+        };
+        let html = rendered.into_view(cx).render_to_string(cx);
+
+        assert!(html.contains("<button id=\"_0-2\">Save</button>"), "{html}");
+        assert!(!html.contains("prevent_default"), "{html}");
+        assert!(!html.contains("stop_propagation"), "{html}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn for_items_are_hydrated_by_key() {
+    use leptos::*;
+
+    // returns the hydration ids of the element with each text
+    fn ids(order: [&'static str; 2]) -> (String, Vec<String>) {
+        let html = leptos::ssr::render_to_string(move |cx| {
+            view! { cx,
+                <ul>
+                    <For
+                        each=move || order
+                        key=|row| *row
+                        view=|cx, row| view! { cx, <li>{row}</li> }
+                    />
+                </ul>
+                <p>"after"</p>
+            }
+        });
+        let id_of = |text: &str| {
+            let end = html.find(&format!("\">{text}<")).unwrap();
+            let start = html[..end].rfind("id=\"").unwrap() + 4;
+            html[start..end].to_string()
+        };
+        let ids = ["A", "B", "after"].into_iter().map(id_of).collect();
+        (html.to_string(), ids)
+    }
+
+    let (server, server_ids) = ids(["B", "A"]);
+    let (_, client_ids) = ids(["A", "B"]);
+
+    // a row has the same key wherever it is in the list, and the order of
+    // the list doesn't change the keys that come after it
+    assert_eq!(server_ids, client_ids);
+    assert_ne!(server_ids[0], server_ids[1]);
+    // the keys are hashes, which the browser has to compute the same way; see
+    // leptos_dom/tests/each_hydration.rs
+    assert_eq!(server_ids[1], "_14463946626115202761-1");
+    assert!(server.contains("|leptos-each-->"), "{server}");
+    assert_eq!(
+        server.matches("|leptos-each-item-->").count(),
+        2,
+        "{server}"
+    );
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn lazy_load_renders_fallback_or_children_on_server() {
+    use leptos::*;
+    use std::{cell::Cell, rc::Rc};
+
+    let render = |ssr_children: bool| {
+        let created = Rc::new(Cell::new(0));
+        let html = leptos::ssr::render_to_string({
+            let created = Rc::clone(&created);
+            move |cx| {
+                view! { cx,
+                    <LazyLoad
+                        fallback=|cx| view! { cx, <p>"Placeholder"</p> }
+                        ssr_children
+                    >
+                        {
+                            created.set(created.get() + 1);
+                            view! { cx, <p>"Content"</p> }
+                        }
+                    </LazyLoad>
+                }
+            }
+        });
+        (html.to_string(), created.get())
+    };
+
+    let (html, created) = render(false);
+    assert!(html.contains(">Placeholder</p></div>"), "{html}");
+    assert!(!html.contains("Content"), "{html}");
+    assert_eq!(created, 0);
+
+    let (html, created) = render(true);
+    assert!(html.contains(">Content</p>"), "{html}");
+    assert!(!html.contains("Placeholder"), "{html}");
+    assert_eq!(created, 1);
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn callbacks_are_passed_through_components_without_reallocating() {
+    use leptos::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[component]
+    fn Inner(
+        cx: Scope,
+        #[prop(into)] on_select: Callback<usize>,
+        received: Rc<RefCell<Vec<Callback<usize>>>>,
+    ) -> impl IntoView {
+        on_select.call(1);
+        received.borrow_mut().push(on_select);
+        view! { cx, <button>"Select"</button> }
+    }
+
+    #[component]
+    fn Outer(
+        cx: Scope,
+        #[prop(into)] on_select: Callback<usize>,
+        received: Rc<RefCell<Vec<Callback<usize>>>>,
+    ) -> impl IntoView {
+        view! { cx, <Inner on_select received/> }
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (selected, set_selected) = create_signal(cx, 0);
+        let on_select = Callback::from(move |id| {
+            set_selected.update(|selected| *selected += id)
+        });
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        for _ in 0..2 {
+            let html = view! { cx,
+                <Outer on_select=on_select.clone() received=Rc::clone(&received)/>
+            }
+            .into_view(cx)
+            .render_to_string(cx);
+            assert!(html.contains(">Select</button>"), "{html}");
+        }
+
+        assert_eq!(selected.get(), 2);
+        assert!(received.borrow().iter().all(|cb| *cb == on_select));
+
+        let stored = StoredCallback::from_callback(cx, on_select.clone());
+        let add_one = move || stored.call(1);
+        let add_two = move || stored.call(2);
+        add_one();
+        add_two();
+        assert_eq!(selected.get(), 5);
+        assert_eq!(Callback::from(stored), on_select);
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn view_macro_comments_component_refs_and_raw_text() {
+    use leptos::*;
+
+    #[component]
+    fn Card(
+        cx: Scope,
+        #[prop(optional)] node_ref: Option<NodeRef<html::Section>>,
+    ) -> impl IntoView {
+        view! { cx, <section node_ref=node_ref>"Card"</section> }
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let card = create_node_ref(cx);
+        let html = view! { cx,
+            <div>
+                <!-- comments can be unquoted, with <tags> & "strings" -->
+                <pre>r#"fn main() {
+    println!("{} < {}", 1, 2);
+}"#</pre>
+                <!-- "or quoted" -->
+                <Card _ref=card/>
+                <Card/>
+            </div>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(!html.contains("comments"), "{html}");
+        assert!(!html.contains("quoted"), "{html}");
+        assert!(html.contains(
+            "fn main() {\n    println!(&quot;{} &lt; {}&quot;, 1, \
+             2);\n}</pre>"
+        ));
+        assert_eq!(html.matches(">Card</section>").count(), 2, "{html}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn use_id_is_deterministic_and_unique() {
+    use leptos::*;
+
+    #[component]
+    fn Field(cx: Scope) -> impl IntoView {
+        let id = move || use_id(cx);
+        // the same call site in the same scope always gets the same id
+        assert_eq!(id(), id());
+        let hint = use_id_with_prefix(cx, "hint-");
+        view! { cx,
+            <input id=id().to_string() aria-describedby=hint.to_string()/>
+            <p id=hint.to_string()>"hint"</p>
+        }
+    }
+
+    fn render() -> String {
+        leptos::ssr::render_to_string(|cx| {
+            view! { cx,
+                <Field/>
+                <Field/>
+                <For
+                    each=|| [1, 2]
+                    key=|row| *row
+                    view=|cx, _| view! { cx, <Field/> }
+                />
+            }
+        })
+        .to_string()
+    }
+
+    let ids = |html: &str| {
+        html.split("id=\"")
+            .skip(1)
+            .filter_map(|rest| rest.split('"').next())
+            .filter(|id| !id.starts_with('_'))
+            .map(String::from)
+            .collect::<Vec<_>>()
+    };
+
+    let html = render();
+    let ids = ids(&html);
+    assert_eq!(ids.len(), 8, "{html}");
+    assert!(ids[0].starts_with("leptos-"), "{html}");
+    assert!(ids[1].starts_with("hint-"), "{html}");
+
+    let mut unique = ids.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), 8, "{html}");
+
+    // a second render, like hydration in the browser, gets the same ids
+    assert_eq!(render(), html);
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate", feature = "nightly")))]
+#[test]
+fn signals_render_like_closures() {
+    use leptos::*;
+
+    let with_signals = leptos::ssr::render_to_string(|cx| {
+        let count = create_rw_signal(cx, 3);
+        let label = create_memo(cx, move |_| format!("count-{}", count.get()));
+        let big = Signal::derive(cx, move || count.get() > 1);
+        let color: MaybeSignal<String> = "red".into();
+        view! { cx,
+            <p title=label class:big=big style:color=color>
+                {count} " / " {label}
+            </p>
+        }
+    });
+    let with_closures = leptos::ssr::render_to_string(|cx| {
+        let count = create_rw_signal(cx, 3);
+        let label = create_memo(cx, move |_| format!("count-{}", count.get()));
+        let big = Signal::derive(cx, move || count.get() > 1);
+        let color: MaybeSignal<String> = "red".into();
+        view! { cx,
+            <p
+                title=move || label.get()
+                class:big=move || big.get()
+                style:color=move || color.get()
+            >
+                {move || count.get()} " / " {move || label.get()}
+            </p>
+        }
+    });
+
+    assert_eq!(with_signals, with_closures);
+    assert!(with_signals.contains("title=\"count-3\""), "{with_signals}");
+    assert!(with_signals.contains("class=\" big\""), "{with_signals}");
+    assert!(with_signals.contains("color: red;"), "{with_signals}");
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_slots_are_collected_by_name() {
+    use leptos::*;
+
+    #[slot]
+    struct Badge {
+        text: &'static str,
+    }
+
+    #[slot]
+    struct Tab {
+        label: &'static str,
+        #[prop(optional)]
+        badge: Vec<Badge>,
+        children: ChildrenFn,
+    }
+
+    #[slot]
+    struct Footer {
+        children: ChildrenFn,
+    }
+
+    #[component]
+    fn Tabs(
+        cx: Scope,
+        #[prop(optional)] tab: Vec<Tab>,
+        #[prop(optional)] footer: Option<Footer>,
+        children: Children,
+    ) -> impl IntoView {
+        let tabs = tab
+            .into_iter()
+            .map(|tab| {
+                let badges = tab
+                    .badge
+                    .iter()
+                    .map(|badge| badge.text)
+                    .collect::<Vec<_>>()
+                    .join(",");
+                view! { cx,
+                    <section data-label=tab.label data-badges=badges>
+                        {(tab.children)(cx)}
+                    </section>
+                }
+            })
+            .collect::<Vec<_>>();
+        view! { cx,
+            <div>
+                {tabs}
+                {children(cx)}
+                {footer.map(|footer| (footer.children)(cx))}
+            </div>
+        }
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let rendered = view! { cx,
+            <Tabs>
+                <Tab slot label="one">
+                    <Badge slot text="new"/>
+                    <Badge slot text="hot"/>
+                    "First"
+                </Tab>
+                <p>"between"</p>
+                <Tab slot label="two">"Second"</Tab>
+            </Tabs>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        let one = rendered.find("data-label=\"one\"").expect(&rendered);
+        let two = rendered.find("data-label=\"two\"").expect(&rendered);
+        let between = rendered.find("between").expect(&rendered);
+        assert!(one < two && two < between, "{rendered}");
+        assert!(rendered.contains("data-badges=\"new,hot\""), "{rendered}");
+        assert!(rendered.contains("data-badges=\"\""), "{rendered}");
+        assert!(rendered.contains("First"), "{rendered}");
+        assert!(rendered.contains("Second"), "{rendered}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_error_boundary_renders_fallback_for_returned_errors() {
+    use leptos::*;
+    use std::fmt;
+
+    #[derive(Debug)]
+    struct NotFound(&'static str);
+
+    impl fmt::Display for NotFound {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "no user named {}", self.0)
+        }
+    }
+
+    impl std::error::Error for NotFound {}
+
+    #[component]
+    fn User(cx: Scope, name: &'static str) -> impl IntoView {
+        let user = if name == "ada" { Ok(name) } else { Err(NotFound(name)) };
+        user.map(|name| view! { cx, <p>{name}</p> })
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let render = |name: &'static str| {
+            view! { cx,
+                <ErrorBoundary fallback=|cx, errors| view! { cx,
+                    <ul>
+                        {move || errors.get()
+                            .into_iter()
+                            .map(|(_, error)| view! { cx, <li>{error.to_string()}</li> })
+                            .collect_view(cx)}
+                    </ul>
+                }>
+                    <User name=name/>
+                </ErrorBoundary>
+            }
+            .into_view(cx)
+            .render_to_string(cx)
+            .to_string()
+        };
+
+        let found = render("ada");
+        assert!(found.contains(">ada</p>"), "{found}");
+        assert!(!found.contains("<li"), "{found}");
+
+        let missing = render("bob");
+        assert!(missing.contains(">no user named bob</li>"), "{missing}");
+        assert!(missing.contains("style=\"display: none"), "{missing}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_dynamic_attributes_render_the_same_when_repeated() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let class = "card card--wide \"quoted\" & <escaped>".to_string();
+        let title: Option<String> = None;
+
+        let with_macro = (0..2)
+            .map(|_| {
+                view! { cx,
+                    <div class=class.clone() title=title.clone() data-label=class.clone()/>
+                }
+            })
+            .collect_view(cx)
+            .render_to_string(cx)
+            .to_string();
+        let with_builder = (0..2)
+            .map(|_| {
+                html::div(cx)
+                    .attr("class", class.clone())
+                    .attr("title", title.clone())
+                    .attr("data-label", class.clone())
+            })
+            .collect_view(cx)
+            .render_to_string(cx)
+            .to_string();
+
+        let escaped = "card card--wide &quot;quoted&quot; &amp; &lt;escaped&gt;";
+        for html in [&with_macro, &with_builder] {
+            assert_eq!(html.matches(escaped).count(), 4, "{html}");
+            assert!(!html.contains("title"), "{html}");
+        }
+        assert!(
+            with_macro.contains(&format!("data-label=\"{escaped}\"")),
+            "{with_macro}"
+        );
+        assert!(
+            with_builder.contains(&format!("data-label=\"{escaped}\"")),
+            "{with_builder}"
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_interned_attributes_render_byte_identical_output() {
+    use leptos::*;
+
+    fn rows(cx: Scope) -> View {
+        // long enough to be shared between the rows
+        let class = "row row--striped \"quoted\" & <escaped>".to_string();
+        (0..3)
+            .map(|n| {
+                html::tr(cx)
+                    .attr("class", class.clone())
+                    .attr("data-label", Some(class.clone()))
+                    .child(n)
+            })
+            .collect_view(cx)
+    }
+
+    // rendered outside of a render, so without the intern table
+    leptos_dom::HydrationCtx::reset_id();
+    let plain = run_scope(create_runtime(), |cx| {
+        rows(cx).render_to_string(cx).to_string()
+    });
+    let interned = leptos::ssr::render_to_string(rows);
+
+    assert_eq!(plain, interned);
+    let escaped = "row row--striped &quot;quoted&quot; &amp; &lt;escaped&gt;";
+    assert_eq!(interned.matches(escaped).count(), 6, "{interned}");
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn noscript_children_do_not_shift_hydration_keys() {
+    use leptos::*;
+
+    // the client renders no children, so what follows must get the same key
+    let span_id = |html: &str| {
+        let start = html.find("<span id=\"").unwrap() + 10;
+        html[start..][..html[start..].find('"').unwrap()].to_string()
+    };
+
+    let with_children = leptos::ssr::render_to_string(|cx| {
+        view! { cx,
+            <div>
+                <NoScript>
+                    <p>"Saving drafts requires JavaScript."</p>
+                    <a href="/help">"Help"</a>
+                </NoScript>
+                <span>"Draft"</span>
+            </div>
+        }
+    });
+    let without_children = leptos::ssr::render_to_string(|cx| {
+        view! { cx,
+            <div>
+                <NoScript>"JS"</NoScript>
+                <span>"Draft"</span>
+            </div>
+        }
+    });
+
+    assert!(
+        with_children.contains("<noscript id=\"_"),
+        "{with_children}"
+    );
+    assert!(
+        with_children.contains("Saving drafts requires JavaScript.</p>"),
+        "{with_children}"
+    );
+    assert_eq!(span_id(&with_children), span_id(&without_children));
+}
+
+#[test]
+fn lazy_hydrate_renders_its_children_on_the_server() {
+    use leptos::*;
+
+    let html = leptos::ssr::render_to_string(|cx| {
+        view! { cx,
+            <div>
+                <LazyHydrate on=HydrateOn::Visible>
+                    <footer>
+                        <button on:click=|_| ()>"Back to top"</button>
+                    </footer>
+                </LazyHydrate>
+            </div>
+        }
+    });
+
+    assert!(
+        html.contains("<leptos-lazy-hydrate style=\"display: contents\""),
+        "{html}"
+    );
+    // where the client continues hydrating the rest of the page from
+    assert!(html.contains(" data-hk-end=\""), "{html}");
+    assert!(html.contains("Back to top</button>"), "{html}");
+}
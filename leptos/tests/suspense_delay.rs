@@ -0,0 +1,112 @@
+// run with `wasm-pack test --headless --firefox --features csr`
+#![cfg(all(target_arch = "wasm32", feature = "csr"))]
+
+use leptos::*;
+use std::time::Duration;
+use wasm_bindgen_futures::{js_sys::Promise, JsFuture};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    JsFuture::from(Promise::new(&mut |resolve, _| {
+        window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    }))
+    .await
+    .unwrap();
+}
+
+fn text() -> String {
+    document()
+        .body()
+        .unwrap()
+        .text_content()
+        .unwrap_or_default()
+}
+
+// an outer <Suspense/> whose resource loads in `outer_ms`, around an inner one whose resource
+// loads in `inner_ms`; children that are shown before their resource has loaded say so
+fn mount_nested(outer_ms: i32, inner_ms: i32) {
+    document().body().unwrap().set_inner_html("");
+    mount_to_body(move |cx| {
+        let outer = create_local_resource(
+            cx,
+            || (),
+            move |_| async move {
+                sleep(outer_ms).await;
+                "outer data"
+            },
+        );
+        let inner = create_local_resource(
+            cx,
+            || (),
+            move |_| async move {
+                sleep(inner_ms).await;
+                "inner data"
+            },
+        );
+        view! { cx,
+            <Suspense
+                fallback=|| "outer loading"
+                fallback_delay=Duration::from_millis(100)
+            >
+                <p>{move || outer.get().unwrap_or("outer unresolved")}</p>
+                <Suspense
+                    fallback=|| "inner loading"
+                    fallback_delay=Duration::from_millis(50)
+                >
+                    <p>{move || inner.get().unwrap_or("inner unresolved")}</p>
+                </Suspense>
+            </Suspense>
+        }
+    });
+}
+
+#[wasm_bindgen_test]
+async fn inner_boundary_resolving_during_outer_delay_never_shows_a_fallback() {
+    mount_nested(150, 20);
+
+    sleep(60).await;
+    let shown = text();
+    assert!(!shown.contains("unresolved"), "{shown}");
+    assert!(!shown.contains("loading"), "{shown}");
+
+    // the outer resource is still loading once its delay has elapsed
+    sleep(60).await;
+    let shown = text();
+    assert!(shown.contains("outer loading"), "{shown}");
+    assert!(!shown.contains("inner loading"), "{shown}");
+
+    sleep(80).await;
+    let shown = text();
+    assert!(shown.contains("outer data"), "{shown}");
+    assert!(shown.contains("inner data"), "{shown}");
+    assert!(!shown.contains("loading"), "{shown}");
+    assert!(!shown.contains("unresolved"), "{shown}");
+}
+
+#[wasm_bindgen_test]
+async fn inner_fallback_is_shown_inside_resolved_outer_content() {
+    mount_nested(20, 150);
+
+    // the outer content is shown, but not the inner content without its data
+    sleep(35).await;
+    let shown = text();
+    assert!(shown.contains("outer data"), "{shown}");
+    assert!(!shown.contains("unresolved"), "{shown}");
+    assert!(!shown.contains("loading"), "{shown}");
+
+    // the inner delay has elapsed; the outer fallback is never shown
+    sleep(45).await;
+    let shown = text();
+    assert!(shown.contains("outer data"), "{shown}");
+    assert!(shown.contains("inner loading"), "{shown}");
+    assert!(!shown.contains("outer loading"), "{shown}");
+
+    sleep(120).await;
+    let shown = text();
+    assert!(shown.contains("inner data"), "{shown}");
+    assert!(!shown.contains("loading"), "{shown}");
+}
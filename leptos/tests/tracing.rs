@@ -0,0 +1,94 @@
+#![cfg(all(feature = "tracing", feature = "ssr"))]
+
+use leptos::*;
+use std::sync::{Arc, Mutex};
+use tracing::{
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+// records every span that is entered, with its name
+#[derive(Default)]
+struct Spans {
+    names: Mutex<Vec<&'static str>>,
+    entered: Arc<Mutex<Vec<(u64, &'static str)>>>,
+}
+
+impl Subscriber for Spans {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut names = self.names.lock().unwrap();
+        names.push(span.metadata().name());
+        Id::from_u64(names.len() as u64)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        let id = span.into_u64();
+        let name = self.names.lock().unwrap()[id as usize - 1];
+        self.entered.lock().unwrap().push((id, name));
+    }
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[component]
+fn Inner(cx: Scope, label: &'static str) -> impl IntoView {
+    view! { cx, <li>{label}</li> }
+}
+
+#[component]
+fn Outer(cx: Scope) -> impl IntoView {
+    view! { cx,
+        <ul>
+            <Inner label="a"/>
+            <Inner label="b"/>
+        </ul>
+    }
+}
+
+// the spans of `Outer` and `Inner` entered while rendering, each once, in
+// the order they were first entered
+fn component_spans(render: impl FnOnce()) -> Vec<&'static str> {
+    let spans = Spans::default();
+    let entered = Arc::clone(&spans.entered);
+    tracing::subscriber::with_default(spans, render);
+    let mut seen = Vec::new();
+    let mut spans = Vec::new();
+    for (id, name) in entered.lock().unwrap().iter() {
+        if matches!(*name, "<Outer />" | "<Inner />") && !seen.contains(id) {
+            seen.push(*id);
+            spans.push(*name);
+        }
+    }
+    spans
+}
+
+#[test]
+fn each_component_render_enters_a_span_named_after_it() {
+    let spans = component_spans(|| {
+        _ = leptos::ssr::render_to_string(|cx| view! { cx, <Outer/> });
+    });
+    assert_eq!(spans, ["<Outer />", "<Inner />", "<Inner />"]);
+}
+
+#[test]
+fn rendering_twice_enters_new_spans() {
+    let spans = component_spans(|| {
+        _ = leptos::ssr::render_to_string(
+            |cx| view! { cx, <Inner label="a"/> },
+        );
+        _ = leptos::ssr::render_to_string(
+            |cx| view! { cx, <Inner label="b"/> },
+        );
+    });
+    assert_eq!(spans, ["<Inner />", "<Inner />"]);
+}
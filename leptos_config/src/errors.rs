@@ -13,6 +13,8 @@ pub enum LeptosConfigError {
     ConfigError(String),
     #[error("Config Error: {0}")]
     EnvVarError(String),
+    #[error("Config Error: {0} is required but was not set")]
+    MissingKey(&'static str),
 }
 impl From<config::ConfigError> for LeptosConfigError {
     fn from(e: config::ConfigError) -> Self {
@@ -23,7 +23,10 @@ pub struct ConfFile {
 /// It's used in our actix, axum, and viz integrations to generate the
 /// correct path for WASM, JS, and Websockets, as well as other configuration tasks.
 /// It shares keys with cargo-leptos, to allow for easy interoperability
-#[derive(TypedBuilder, Debug, Clone, serde::Deserialize)]
+///
+/// The integrations also provide it as context while rendering, so components can read it
+/// with `use_context::<LeptosOptions>(cx)`, e.g., to build the URLs of assets in `site_pkg_dir`.
+#[derive(TypedBuilder, Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct LeptosOptions {
     /// The name of the WASM and JS files generated by wasm-bindgen. Defaults to the crate name with underscores instead of dashes
@@ -56,38 +59,75 @@ pub struct LeptosOptions {
     #[builder(default = default_reload_port())]
     #[serde(default = "default_reload_port")]
     pub reload_port: u32,
+    /// Whether the integrations flush each chunk of a streamed response as soon as
+    /// it is ready, e.g., the HTML for each `<Suspense/>` as it resolves. This keeps
+    /// streaming prompt when the response is compressed by a middleware.
+    /// Set it to `false` to let chunks that are ready at the same time be sent, and
+    /// compressed, together, which compresses better but may delay chunks.
+    /// Defaults to `true`
+    #[builder(default = default_flush_stream_chunks())]
+    #[serde(default = "default_flush_stream_chunks")]
+    pub flush_stream_chunks: bool,
 }
 
 impl LeptosOptions {
-    fn try_from_env() -> Result<Self, LeptosConfigError> {
-        let output_name = env_w_default(
-            "LEPTOS_OUTPUT_NAME",
-            std::option_env!("LEPTOS_OUTPUT_NAME",).unwrap_or_default(),
-        )?;
+    /// Loads the options from `LEPTOS_*` environment variables, like
+    /// `LEPTOS_OUTPUT_NAME` or `LEPTOS_SITE_ADDR`, which are set by cargo-leptos.
+    /// Unset variables fall back to their defaults, except for
+    /// `LEPTOS_OUTPUT_NAME`, which can also be set at compile time.
+    ///
+    /// Returns an error naming the variable if a required one is missing or
+    /// empty, or a value can’t be parsed.
+    pub fn from_env() -> Result<Self, LeptosConfigError> {
+        Self::from_vars(|key| std::env::var(key))
+    }
+
+    fn from_vars(
+        var: impl Fn(&str) -> Result<String, VarError>,
+    ) -> Result<Self, LeptosConfigError> {
+        let default_output_name =
+            std::option_env!("LEPTOS_OUTPUT_NAME").unwrap_or_default();
+        let mut output_name =
+            var_w_default(&var, "LEPTOS_OUTPUT_NAME", default_output_name)?;
+        if output_name.is_empty() {
+            output_name = default_output_name.to_string();
+        }
         if output_name.is_empty() {
-            eprintln!(
-                "It looks like you're trying to compile Leptos without the \
-                 LEPTOS_OUTPUT_NAME environment variable being set. There are \
-                 two options\n 1. cargo-leptos is not being used, but \
-                 get_configuration() is being passed None. This needs to be \
-                 changed to Some(\"Cargo.toml\")\n 2. You are compiling \
-                 Leptos without LEPTOS_OUTPUT_NAME being set with \
-                 cargo-leptos. This shouldn't be possible!"
-            );
+            return Err(LeptosConfigError::MissingKey("LEPTOS_OUTPUT_NAME"));
         }
         Ok(LeptosOptions {
             output_name,
-            site_root: env_w_default("LEPTOS_SITE_ROOT", "target/site")?,
-            site_pkg_dir: env_w_default("LEPTOS_SITE_PKG_DIR", "pkg")?,
-            env: Env::default(),
-            site_addr: env_w_default("LEPTOS_SITE_ADDR", "127.0.0.1:3000")?
-                .parse()?,
-            reload_port: env_w_default("LEPTOS_RELOAD_PORT", "3001")?
-                .parse()?,
+            site_root: var_w_default(&var, "LEPTOS_SITE_ROOT", "target/site")?,
+            site_pkg_dir: var_w_default(&var, "LEPTOS_SITE_PKG_DIR", "pkg")?,
+            env: Env::try_from(var_w_default(&var, "LEPTOS_ENV", "dev")?)
+                .map_err(|e| {
+                    LeptosConfigError::EnvVarError(format!("LEPTOS_ENV: {e}"))
+                })?,
+            site_addr: parse_var(&var, "LEPTOS_SITE_ADDR", "127.0.0.1:3000")?,
+            reload_port: parse_var(&var, "LEPTOS_RELOAD_PORT", "3001")?,
+            flush_stream_chunks: parse_var(
+                &var,
+                "LEPTOS_FLUSH_STREAM_CHUNKS",
+                "true",
+            )?,
         })
     }
 }
 
+impl Default for LeptosOptions {
+    /// The same options as [`LeptosOptions::builder`], with the output name
+    /// that was set with `LEPTOS_OUTPUT_NAME` at compile time, if any. This
+    /// lets options written as a struct literal keep their defaults as fields
+    /// are added, with `..Default::default()`.
+    fn default() -> Self {
+        Self::builder()
+            .output_name(
+                std::option_env!("LEPTOS_OUTPUT_NAME").unwrap_or_default(),
+            )
+            .build()
+    }
+}
+
 fn default_site_root() -> String {
     ".".to_string()
 }
@@ -108,17 +148,36 @@ fn default_reload_port() -> u32 {
     3001
 }
 
-fn env_w_default(
+fn default_flush_stream_chunks() -> bool {
+    true
+}
+
+fn var_w_default(
+    var: &impl Fn(&str) -> Result<String, VarError>,
     key: &str,
     default: &str,
 ) -> Result<String, LeptosConfigError> {
-    match std::env::var(key) {
+    match var(key) {
         Ok(val) => Ok(val),
         Err(VarError::NotPresent) => Ok(default.to_string()),
         Err(e) => Err(LeptosConfigError::EnvVarError(format!("{key}: {e}"))),
     }
 }
 
+fn parse_var<T>(
+    var: &impl Fn(&str) -> Result<String, VarError>,
+    key: &str,
+    default: &str,
+) -> Result<T, LeptosConfigError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    var_w_default(var, key, default)?
+        .parse()
+        .map_err(|e| LeptosConfigError::EnvVarError(format!("{key}: {e}")))
+}
+
 /// An enum that can be used to define the environment Leptos is running in.
 /// Setting this to the `PROD` variant will not include the WebSocket code for `cargo-leptos` watch mode.
 /// Defaults to `DEV`.
@@ -245,7 +304,7 @@ pub async fn get_config_from_file<P: AsRef<Path>>(
 /// Loads [LeptosOptions] from environment variables or rely on the defaults
 pub fn get_config_from_env() -> Result<ConfFile, LeptosConfigError> {
     Ok(ConfFile {
-        leptos_options: LeptosOptions::try_from_env()?,
+        leptos_options: LeptosOptions::from_env()?,
     })
 }
 
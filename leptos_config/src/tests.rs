@@ -1,5 +1,7 @@
-use crate::{env_w_default, from_str, Env, LeptosOptions};
-use std::{net::SocketAddr, str::FromStr};
+use crate::{
+    errors::LeptosConfigError, from_str, var_w_default, Env, LeptosOptions,
+};
+use std::{collections::HashMap, env::VarError, net::SocketAddr, str::FromStr};
 
 #[test]
 fn from_str_env() {
@@ -19,18 +21,28 @@ fn from_str_env() {
 fn env_w_default_test() {
     std::env::set_var("LEPTOS_CONFIG_ENV_TEST", "custom");
     assert_eq!(
-        env_w_default("LEPTOS_CONFIG_ENV_TEST", "default").unwrap(),
+        var_w_default(
+            &|key| std::env::var(key),
+            "LEPTOS_CONFIG_ENV_TEST",
+            "default"
+        )
+        .unwrap(),
         String::from("custom")
     );
     std::env::remove_var("LEPTOS_CONFIG_ENV_TEST");
     assert_eq!(
-        env_w_default("LEPTOS_CONFIG_ENV_TEST", "default").unwrap(),
+        var_w_default(
+            &|key| std::env::var(key),
+            "LEPTOS_CONFIG_ENV_TEST",
+            "default"
+        )
+        .unwrap(),
         String::from("default")
     );
 }
 
 #[test]
-fn try_from_env_test() {
+fn from_env_test() {
     // Test config values from environment variables
     std::env::set_var("LEPTOS_OUTPUT_NAME", "app_test");
     std::env::set_var("LEPTOS_SITE_ROOT", "my_target/site");
@@ -38,7 +50,7 @@ fn try_from_env_test() {
     std::env::set_var("LEPTOS_SITE_ADDR", "0.0.0.0:80");
     std::env::set_var("LEPTOS_RELOAD_PORT", "8080");
 
-    let config = LeptosOptions::try_from_env().unwrap();
+    let config = LeptosOptions::from_env().unwrap();
     assert_eq!(config.output_name, "app_test");
 
     assert_eq!(config.site_root, "my_target/site");
@@ -49,3 +61,73 @@ fn try_from_env_test() {
     );
     assert_eq!(config.reload_port, 8080);
 }
+
+fn from_vars(
+    vars: &[(&str, &str)],
+) -> Result<LeptosOptions, LeptosConfigError> {
+    let vars = vars.iter().copied().collect::<HashMap<_, _>>();
+    LeptosOptions::from_vars(|key| {
+        vars.get(key)
+            .map(|value| value.to_string())
+            .ok_or(VarError::NotPresent)
+    })
+}
+
+#[test]
+fn from_vars_reads_env() {
+    let config =
+        from_vars(&[("LEPTOS_OUTPUT_NAME", "app"), ("LEPTOS_ENV", "PROD")])
+            .unwrap();
+    assert_eq!(config.env, Env::PROD);
+    assert_eq!(config.site_pkg_dir, "pkg");
+
+    let config = from_vars(&[("LEPTOS_OUTPUT_NAME", "app")]).unwrap();
+    assert_eq!(config.env, Env::DEV);
+    assert!(config.flush_stream_chunks);
+
+    let config = from_vars(&[
+        ("LEPTOS_OUTPUT_NAME", "app"),
+        ("LEPTOS_FLUSH_STREAM_CHUNKS", "false"),
+    ])
+    .unwrap();
+    assert!(!config.flush_stream_chunks);
+}
+
+#[test]
+fn from_vars_requires_an_output_name() {
+    if option_env!("LEPTOS_OUTPUT_NAME").is_none() {
+        for vars in [&[][..], &[("LEPTOS_OUTPUT_NAME", "")]] {
+            assert!(matches!(
+                from_vars(vars),
+                Err(LeptosConfigError::MissingKey("LEPTOS_OUTPUT_NAME"))
+            ));
+        }
+    }
+}
+
+#[test]
+fn from_vars_reports_offending_key() {
+    for (key, value) in [
+        ("LEPTOS_SITE_ADDR", "localhost"),
+        ("LEPTOS_RELOAD_PORT", "port"),
+        ("LEPTOS_ENV", "staging"),
+    ] {
+        let err = from_vars(&[("LEPTOS_OUTPUT_NAME", "app"), (key, value)])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(key), "{err}");
+    }
+}
+
+#[test]
+fn default_options_match_the_builder() {
+    let options = LeptosOptions {
+        output_name: "app".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(options.site_root, ".");
+    assert_eq!(options.site_pkg_dir, "pkg");
+    assert_eq!(options.env, Env::DEV);
+    assert_eq!(options.reload_port, 3001);
+    assert!(options.flush_stream_chunks);
+}
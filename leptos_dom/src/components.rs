@@ -1,5 +1,6 @@
 mod dyn_child;
 mod each;
+mod either;
 mod errors;
 mod fragment;
 mod unit;
@@ -12,6 +13,7 @@ use crate::{
 use crate::{mount_child, prepare_to_move, MountKind, Mountable};
 pub use dyn_child::*;
 pub use each::*;
+pub use either::*;
 pub use errors::*;
 pub use fragment::*;
 use leptos_reactive::Scope;
@@ -268,6 +270,8 @@ where
             children_fn,
         } = self;
 
+        #[cfg(debug_assertions)]
+        let _rendering = RenderingComponent::enter(name.clone());
         let mut repr = ComponentRepr::new_with_id(name, id);
 
         // disposed automatically when the parent scope is disposed
@@ -280,3 +284,45 @@ where
         repr.into_view(cx)
     }
 }
+
+#[cfg(debug_assertions)]
+thread_local! {
+    // the names of the components whose bodies are running, innermost last
+    static RENDERING: std::cell::RefCell<Vec<Cow<'static, str>>> =
+        Default::default();
+}
+
+// pops the component from `RENDERING` when its body has run, even if it
+// panicked
+#[cfg(debug_assertions)]
+struct RenderingComponent;
+
+#[cfg(debug_assertions)]
+impl RenderingComponent {
+    fn enter(name: Cow<'static, str>) -> Self {
+        RENDERING.with(|rendering| rendering.borrow_mut().push(name));
+        Self
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Drop for RenderingComponent {
+    fn drop(&mut self) {
+        RENDERING.with(|rendering| rendering.borrow_mut().pop());
+    }
+}
+
+/// Returns the name of the component whose body is running, if any, so that
+/// hooks can name the component they were called from in their errors.
+///
+/// This is always `None` in release builds.
+pub fn current_component_name() -> Option<Cow<'static, str>> {
+    #[cfg(debug_assertions)]
+    {
+        RENDERING.with(|rendering| rendering.borrow().last().cloned())
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        None
+    }
+}
@@ -7,9 +7,9 @@ use leptos_reactive::Scope;
 use std::{borrow::Cow, cell::RefCell, fmt, ops::Deref, rc::Rc};
 cfg_if! {
   if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-    use crate::{mount_child, prepare_to_move, unmount_child, MountKind, Mountable, Text};
-    use leptos_reactive::{create_effect, ScopeDisposer};
-    use wasm_bindgen::JsCast;
+    use crate::{mount_child, prepare_to_move, unmount_child, ComponentRepr, MountKind, Mountable, Text};
+    use leptos_reactive::{create_render_effect, ScopeDisposer};
+    use wasm_bindgen::{JsCast, JsValue};
   }
 }
 
@@ -122,6 +122,14 @@ impl DynChildRepr {
 }
 
 /// Represents any [`View`] that can change over time.
+///
+/// When the child goes from one list to another, the mounted items at the
+/// start and end of the list that render the same nodes are kept, and only
+/// the items in between are replaced. This is not keyed diffing: only text
+/// and elements without event listeners, reactive bindings, node refs or
+/// dynamic children are kept, as the kept nodes would otherwise still run
+/// the closures of the render that created them. Use `<For/>` when items
+/// need their own identity.
 pub struct DynChild<CF, N>
 where
     CF: Fn() -> N + 'static,
@@ -183,17 +191,18 @@ where
             let span = tracing::Span::current();
 
             #[cfg(all(target_arch = "wasm32", feature = "web"))]
-            create_effect(
+            create_render_effect(
                 cx,
                 move |prev_run: Option<(
                     Option<web_sys::Node>,
-                    ScopeDisposer,
+                    ChildScopes,
                 )>| {
                     #[cfg(debug_assertions)]
                     let _guard = span.enter();
 
                     let (new_child, disposer) =
                         cx.run_child_scope(|cx| child_fn().into_view(cx));
+                    let disposer = ChildScopes::new(disposer, &new_child);
 
                     let mut child_borrow = child.borrow_mut();
 
@@ -201,9 +210,6 @@ where
                     if let Some((prev_t, prev_disposer)) = prev_run {
                         let child = child_borrow.take().unwrap();
 
-                        // Dispose of the scope
-                        prev_disposer.dispose();
-
                         // We need to know if our child wasn't moved elsewhere.
                         // If it was, `DynChild` no longer "owns" that child, and
                         // is therefore no longer sound to unmount it from the DOM
@@ -217,6 +223,24 @@ where
                                 .as_ref()
                                 != Some(&closing);
 
+                        // If both are lists, only the items that render
+                        // differently are replaced, and the scopes of the
+                        // items that are kept stay alive
+                        let reused_scopes = if prev_t.is_none()
+                            && !HydrationCtx::is_hydrating()
+                            && !was_child_moved
+                            && child != new_child
+                            && matches!(
+                                (&child, &new_child),
+                                (View::Component(_), View::Component(_))
+                            ) {
+                            Some(prev_disposer)
+                        } else {
+                            // Dispose of the scope
+                            prev_disposer.dispose();
+                            None
+                        };
+
                         // If the previous child was a text node, we would like to
                         // make use of it again if our current child is also a text
                         // node
@@ -287,6 +311,23 @@ where
                             // Technically, I think this check shouldn't be necessary, but
                             // I can imagine some edge case that the child changes while
                             // hydration is ongoing
+                            if let Some(prev_disposer) = reused_scopes {
+                                let (
+                                    View::Component(mut prev),
+                                    View::Component(next),
+                                ) = (child, new_child)
+                                else {
+                                    unreachable!()
+                                };
+                                let disposer = disposer.patch(
+                                    prev_disposer,
+                                    &mut prev,
+                                    next,
+                                );
+                                **child_borrow = Some(View::Component(prev));
+                                return (None, disposer);
+                            }
+
                             if !HydrationCtx::is_hydrating() {
                                 let same_child = child == new_child;
                                 if !was_child_moved && !same_child {
@@ -418,6 +459,175 @@ cfg_if! {
     if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
         use web_sys::Node;
 
+        /// The child scopes that the view rendered by a [`DynChild`] belongs to.
+        ///
+        /// When a list is patched, the items that are kept still belong to the
+        /// scope of the run that rendered them, so a scope is only disposed
+        /// once none of its items are mounted anymore.
+        pub(crate) struct ChildScopes {
+            current: Rc<ScopeDisposer>,
+            // if the view is a list, the scope of each of its items
+            items: Vec<Rc<ScopeDisposer>>,
+        }
+
+        impl ChildScopes {
+            fn new(disposer: ScopeDisposer, view: &View) -> Self {
+                let current = Rc::new(disposer);
+                let items = match view {
+                    View::Component(list) => {
+                        vec![Rc::clone(&current); list.children.len()]
+                    }
+                    _ => Vec::new(),
+                };
+                Self { current, items }
+            }
+
+            /// Disposes of every scope the view belongs to.
+            fn dispose(self) {
+                for scope in self.items {
+                    release(scope);
+                }
+                release(self.current);
+            }
+
+            /// Updates the mounted list `prev` to render like `next`, keeping
+            /// the common prefix and suffix of items that render the same and
+            /// only replacing the items in between. The list's own markers
+            /// stay in place, so its range is unchanged for hydration.
+            ///
+            /// The scopes of the replaced items are disposed, unless items
+            /// that are kept belong to them too.
+            fn patch(
+                self,
+                prev_scopes: ChildScopes,
+                prev: &mut ComponentRepr,
+                next: ComponentRepr,
+            ) -> ChildScopes {
+                let ChildScopes {
+                    current: prev_current,
+                    items: mut prev_items,
+                } = prev_scopes;
+                prev_items.resize_with(prev.children.len(), || {
+                    Rc::clone(&prev_current)
+                });
+
+                let (prefix, suffix) =
+                    unchanged_ends(&prev.children, &next.children, renders_same);
+
+                // remove the items that changed...
+                let prev_end = prev.children.len() - suffix;
+                for child in &prev.children[prefix..prev_end] {
+                    let closing = child.get_closing_node();
+                    unmount_child(&child.get_opening_node(), &closing);
+                    closing.unchecked_into::<web_sys::Element>().remove();
+                }
+
+                // ...and mount their replacements in the same place
+                let before = prev.children.get(prev_end).map_or_else(
+                    || prev.closing.node.clone(),
+                    Mountable::get_opening_node,
+                );
+                let mut next_children = next.children;
+                let next_end = next_children.len() - suffix;
+                let inserted =
+                    next_children.drain(prefix..next_end).collect::<Vec<_>>();
+                for child in &inserted {
+                    mount_child(MountKind::Before(&before), child);
+                }
+
+                let replaced = prev_items
+                    .splice(
+                        prefix..prev_end,
+                        std::iter::repeat_with(|| Rc::clone(&self.current))
+                            .take(inserted.len()),
+                    )
+                    .collect::<Vec<_>>();
+                prev.children.splice(prefix..prev_end, inserted);
+
+                for scope in replaced {
+                    release(scope);
+                }
+                release(prev_current);
+
+                ChildScopes {
+                    current: self.current,
+                    items: prev_items,
+                }
+            }
+        }
+
+        // disposes of the scope if nothing else holds onto it
+        fn release(scope: Rc<ScopeDisposer>) {
+            if let Ok(scope) = Rc::try_unwrap(scope) {
+                scope.dispose();
+            }
+        }
+
+        // set on an element when an event listener, a reactive binding or a
+        // `NodeRef` is attached to it
+        const BOUND_KEY: &str = "$$$leptos_bound";
+
+        /// Marks an element as running closures from the render that created
+        /// it, so that a list update never keeps it in place of a new one.
+        pub(crate) fn mark_bound(el: &web_sys::Element) {
+            _ = js_sys::Reflect::set(
+                el,
+                &JsValue::from_str(BOUND_KEY),
+                &JsValue::TRUE,
+            );
+        }
+
+        fn is_bound(el: &web_sys::Element) -> bool {
+            js_sys::Reflect::has(el, &JsValue::from_str(BOUND_KEY))
+                .unwrap_or(true)
+        }
+
+        // whether the element, or anything in it, is bound or has dynamic
+        // children, which are mounted between comment markers
+        fn has_bindings(el: &web_sys::Element, outer_html: &str) -> bool {
+            if outer_html.contains("<!--") || is_bound(el) {
+                return true;
+            }
+            let Ok(descendants) = el.query_selector_all("*") else {
+                return true;
+            };
+            (0..descendants.length()).any(|i| {
+                descendants
+                    .get(i)
+                    .map_or(false, |node| is_bound(node.unchecked_ref()))
+            })
+        }
+
+        /// Whether two views render the same nodes and the mounted one
+        /// doesn't hold onto any closures, in which case it can be kept
+        /// instead of mounting the other.
+        fn renders_same(prev: &View, next: &View) -> bool {
+            match (prev, next) {
+                (View::Text(prev), View::Text(next)) => {
+                    prev.content == next.content
+                }
+                (View::Element(prev), View::Element(next)) => {
+                    let html = prev.element.outer_html();
+                    html == next.element.outer_html()
+                        && !has_bindings(&prev.element, &html)
+                        && !has_bindings(&next.element, &html)
+                }
+                (View::Component(prev), View::Component(next)) => {
+                    prev.children.len() == next.children.len()
+                        && prev
+                            .children
+                            .iter()
+                            .zip(&next.children)
+                            .all(|(prev, next)| renders_same(prev, next))
+                }
+                (
+                    View::CoreComponent(crate::CoreComponent::Unit(_)),
+                    View::CoreComponent(crate::CoreComponent::Unit(_)),
+                ) => true,
+                _ => false,
+            }
+        }
+
         pub(crate) trait NonViewMarkerSibling {
             fn next_non_view_marker_sibling(&self) -> Option<Node>;
 
@@ -461,3 +671,61 @@ cfg_if! {
         }
     }
 }
+
+/// The lengths of the common prefix and suffix of `prev` and `next`, which
+/// never overlap, so that only the items in between need to be replaced.
+#[cfg_attr(not(all(target_arch = "wasm32", feature = "web")), allow(dead_code))]
+fn unchanged_ends<T>(
+    prev: &[T],
+    next: &[T],
+    same: impl Fn(&T, &T) -> bool,
+) -> (usize, usize) {
+    let prefix = prev
+        .iter()
+        .zip(next)
+        .take_while(|(prev, next)| same(prev, next))
+        .count();
+    let suffix = prev[prefix..]
+        .iter()
+        .rev()
+        .zip(next[prefix..].iter().rev())
+        .take_while(|(prev, next)| same(prev, next))
+        .count();
+    (prefix, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unchanged_ends;
+
+    fn ends(prev: &[&str], next: &[&str]) -> (usize, usize) {
+        unchanged_ends(prev, next, |prev, next| prev == next)
+    }
+
+    #[test]
+    fn appending_keeps_every_item() {
+        assert_eq!(ends(&["a", "b"], &["a", "b", "c"]), (2, 0));
+    }
+
+    #[test]
+    fn changing_an_item_keeps_the_ones_around_it() {
+        assert_eq!(ends(&["a", "b", "c", "d"], &["a", "x", "c", "d"]), (1, 2));
+    }
+
+    #[test]
+    fn removing_an_item_keeps_the_ones_around_it() {
+        assert_eq!(ends(&["a", "b", "c"], &["a", "c"]), (1, 1));
+    }
+
+    #[test]
+    fn repeated_items_are_not_counted_twice() {
+        // the prefix and suffix can't both claim the middle "a"
+        assert_eq!(ends(&["a", "a"], &["a", "a", "a"]), (2, 0));
+        assert_eq!(ends(&["a", "a", "a"], &["a"]), (1, 0));
+    }
+
+    #[test]
+    fn a_new_list_replaces_every_item() {
+        assert_eq!(ends(&["a", "b"], &["c", "d"]), (0, 0));
+    }
+}
@@ -1,8 +1,16 @@
-#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
-use crate::hydration::HydrationKey;
-use crate::{hydration::HydrationCtx, Comment, CoreComponent, IntoView, View};
+use crate::{
+    hydration::{HydrationCtx, HydrationKey},
+    Comment, CoreComponent, IntoView, View,
+};
 use leptos_reactive::Scope;
-use std::{borrow::Cow, cell::RefCell, fmt, hash::Hash, ops::Deref, rc::Rc};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    rc::Rc,
+};
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use web::*;
 
@@ -75,6 +83,14 @@ impl fmt::Debug for EachRepr {
     }
 }
 
+impl EachRepr {
+    /// The hydration marker written before the items of the list.
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    pub(crate) fn list_marker(&self) -> String {
+        format!("<!--hk={}|leptos-each-->", key_marker_id(&self.id))
+    }
+}
+
 impl Default for EachRepr {
     fn default() -> Self {
         let id = HydrationCtx::id();
@@ -176,6 +192,9 @@ pub(crate) struct EachItem {
     closing: Option<Comment>,
     #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
     pub(crate) id: HydrationKey,
+    /// The hydration key derived from the item's key.
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    key: HydrationKey,
 }
 
 impl fmt::Debug for EachItem {
@@ -230,12 +249,10 @@ impl EachItem {
                 fragment.append_with_node_1(&closing.node).unwrap();
             }
 
-            // if child view is Text and if we are hydrating, we do not
-            // need to mount it. otherwise, mount it here
-            if !HydrationCtx::is_hydrating() || !matches!(child, View::Text(_))
-            {
-                mount_child(MountKind::Before(&closing.node), &child);
-            }
+            // a Text view is not hydrated, so it is always mounted; when
+            // hydrating, the server-rendered text is discarded when the item
+            // is claimed
+            mount_child(MountKind::Before(&closing.node), &child);
 
             Some(fragment)
         } else {
@@ -252,8 +269,17 @@ impl EachItem {
             closing: markers.0,
             #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
             id,
+            #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+            key: Default::default(),
         }
     }
+
+    /// The hydration marker written before the item, which contains the
+    /// hydration key derived from the item's key.
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    pub(crate) fn key_marker(&self) -> String {
+        format!("<!--hk={}|leptos-each-item-->", key_marker_id(&self.key))
+    }
 }
 
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
@@ -312,6 +338,107 @@ impl EachItem {
             node.unchecked_into::<web_sys::Element>().remove();
         }
     }
+
+    /// Moves the nodes of an item that was hydrated from the server-rendered
+    /// HTML out of the document, so that it can be mounted in the client's
+    /// order.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    fn prepare_for_claim(&self) {
+        #[cfg(debug_assertions)]
+        if let (Some(fragment), Some(opening)) =
+            (&self.document_fragment, &self.opening)
+        {
+            fragment.append_child(&opening.node).unwrap();
+        }
+
+        self.prepare_for_move();
+    }
+}
+
+/// Continues hydration from a key derived from the list's own key and the
+/// item's key, and returns it. Items are hydrated by key rather than by their
+/// position, so the server and the client can render them in different
+/// orders, and every key is only hydrated if the server rendered it.
+fn item_hydration_key<K: Hash>(list: &HydrationKey, key: &K) -> HydrationKey {
+    let mut hasher = StableHasher::default();
+    list.hash(&mut hasher);
+    key.hash(&mut hasher);
+    // the top bit keeps these apart from the fragments of components, which
+    // count up from zero
+    let fragment = hasher.finish() | 1 << 63;
+    let key = HydrationKey { fragment, id: 0 };
+    HydrationCtx::continue_from(key);
+    key
+}
+
+/// The id of the hydration marker for the list with `key`, or its item with
+/// `key`.
+#[cfg(any(
+    not(all(target_arch = "wasm32", feature = "web")),
+    feature = "hydrate"
+))]
+fn key_marker_id(key: &HydrationKey) -> String {
+    format!("_{key}l")
+}
+
+/// Returns the server-rendered marker with `key`, if the page is hydrating.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn take_key_marker(key: &HydrationKey) -> Option<web_sys::Node> {
+    #[cfg(feature = "hydrate")]
+    if HydrationCtx::is_hydrating() {
+        return crate::hydration::get_marker(&key_marker_id(key))
+            .map(JsCast::unchecked_into);
+    }
+
+    _ = key;
+    None
+}
+
+/// Removes the server-rendered rows that were not claimed by any item, which
+/// are left between the list marker and the end of the list.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn remove_unclaimed_rows(start: &web_sys::Node, end: &web_sys::Node) {
+    let Some(parent) = start.parent_node() else {
+        return;
+    };
+    while let Some(sibling) = start.next_sibling() {
+        if &sibling == end {
+            break;
+        }
+        _ = parent.remove_child(&sibling);
+    }
+    _ = parent.remove_child(start);
+}
+
+/// An FNV-1a hasher that hashes `usize` and `isize` as 64-bit integers, so
+/// that keys hash to the same value on the server and in the browser.
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write(&(i as u64).to_le_bytes());
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write(&(i as i64).to_le_bytes());
+    }
 }
 
 /// A component for efficiently rendering an iterable.
@@ -372,10 +499,11 @@ where
             key_fn,
         } = self;
 
-        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
-        let _ = key_fn;
-
         let component = EachRepr::default();
+        let list_id = HydrationCtx::peek();
+
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        let list_marker = take_key_marker(&list_id);
 
         #[cfg(all(debug_assertions, target_arch = "wasm32", feature = "web"))]
         let opening = component.opening.node.clone().unchecked_into();
@@ -389,6 +517,7 @@ where
             cx,
             move |prev_hash_run: Option<HashRun<FxIndexSet<K>>>| {
                 let mut children_borrow = children.borrow_mut();
+                let first_run = prev_hash_run.is_none();
 
                 #[cfg(all(
                     not(debug_assertions),
@@ -459,10 +588,26 @@ where
                 let fragment = crate::document().create_document_fragment();
 
                 for item in items_iter {
-                    hashed_items.insert(key_fn(&item));
-                    let (each_item, _) = cx.run_child_scope(|cx| {
-                        EachItem::new(cx, each_fn(cx, item).into_view(cx))
-                    });
+                    let key = key_fn(&item);
+                    let item_id = item_hydration_key(&list_id, &key);
+                    hashed_items.insert(key);
+                    let render = || {
+                        cx.run_child_scope(|cx| {
+                            EachItem::new(cx, each_fn(cx, item).into_view(cx))
+                        })
+                        .0
+                    };
+                    // claim the item's server-rendered row if there is one,
+                    // and render it on the client otherwise
+                    let each_item = if !HydrationCtx::is_hydrating() {
+                        render()
+                    } else if take_key_marker(&item_id).is_some() {
+                        let each_item = render();
+                        each_item.prepare_for_claim();
+                        each_item
+                    } else {
+                        HydrationCtx::without_hydrating(render)
+                    };
                     #[cfg(all(target_arch = "wasm32", feature = "web"))]
                     {
                         _ = fragment
@@ -471,6 +616,13 @@ where
 
                     children_borrow.push(Some(each_item));
                 }
+                HydrationCtx::continue_from(list_id);
+
+                if first_run {
+                    if let Some(list_marker) = &list_marker {
+                        remove_unclaimed_rows(list_marker, &closing);
+                    }
+                }
 
                 #[cfg(all(target_arch = "wasm32", feature = "web"))]
                 closing
@@ -487,15 +639,19 @@ where
             *component.children.borrow_mut() = (items_fn)()
                 .into_iter()
                 .map(|child| {
+                    let key = item_hydration_key(&list_id, &key_fn(&child));
                     cx.run_child_scope(|cx| {
-                        Some(EachItem::new(
+                        let mut item = EachItem::new(
                             cx,
                             (each_fn)(cx, child).into_view(cx),
-                        ))
+                        );
+                        item.key = key;
+                        Some(item)
                     })
                     .0
                 })
                 .collect();
+            HydrationCtx::continue_from(list_id);
         }
 
         View::CoreComponent(CoreComponent::Each(component))
@@ -0,0 +1,100 @@
+use crate::{IntoView, View};
+use leptos_reactive::Scope;
+
+/// A view that is one of two different types.
+///
+/// This lets the branches of an `if` or `match` return different concrete
+/// view types without boxing them or converting each of them into a
+/// [`View`] first.
+///
+/// ```
+/// # use leptos_dom::*;
+/// # use leptos_reactive::*;
+/// # let runtime = create_runtime();
+/// # create_scope(runtime, |cx| {
+/// fn greeting(cx: Scope, name: Option<&'static str>) -> impl IntoView {
+///     match name {
+///         Some(name) => Either::Left(html::strong(cx).child(name)),
+///         None => Either::Right("stranger"),
+///     }
+/// }
+/// # });
+/// # runtime.dispose();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either<A, B> {
+    /// The first view type.
+    Left(A),
+    /// The second view type.
+    Right(B),
+}
+
+impl<A, B> IntoView for Either<A, B>
+where
+    A: IntoView,
+    B: IntoView,
+{
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        instrument(level = "info", name = "Either", skip_all)
+    )]
+    fn into_view(self, cx: Scope) -> View {
+        match self {
+            Either::Left(a) => a.into_view(cx),
+            Either::Right(b) => b.into_view(cx),
+        }
+    }
+}
+
+macro_rules! impl_either_of {
+  ($(#[$meta:meta])* $name:ident { $($ty:ident),* $(,)? }) => {
+    $(#[$meta])*
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum $name<$($ty),*> {
+      $(
+        #[allow(missing_docs)]
+        $ty($ty),
+      )*
+    }
+
+    impl<$($ty),*> IntoView for $name<$($ty),*>
+    where
+      $($ty: IntoView),*
+    {
+      #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        instrument(level = "info", name = "EitherOf", skip_all)
+      )]
+      fn into_view(self, cx: Scope) -> View {
+        match self {
+          $($name::$ty(view) => view.into_view(cx),)*
+        }
+      }
+    }
+  };
+}
+
+impl_either_of! {
+  /// A view that is one of three different types. See [`Either`].
+  EitherOf3 { A, B, C }
+}
+impl_either_of! {
+  /// A view that is one of four different types. See [`Either`].
+  EitherOf4 { A, B, C, D }
+}
+impl_either_of! {
+  /// A view that is one of five different types. See [`Either`].
+  EitherOf5 { A, B, C, D, E }
+}
+impl_either_of! {
+  /// A view that is one of six different types. See [`Either`].
+  EitherOf6 { A, B, C, D, E, F }
+}
+impl_either_of! {
+  /// A view that is one of seven different types. See [`Either`].
+  EitherOf7 { A, B, C, D, E, F, G }
+}
+impl_either_of! {
+  /// A view that is one of eight different types. See [`Either`].
+  EitherOf8 { A, B, C, D, E, F, G, H }
+}
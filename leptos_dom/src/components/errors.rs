@@ -1,15 +1,53 @@
 use crate::{HydrationCtx, IntoView};
 use cfg_if::cfg_if;
-use leptos_reactive::{signal_prelude::*, use_context, RwSignal};
+use indexmap::IndexMap;
+use leptos_reactive::{signal_prelude::*, use_context, RwSignal, Trigger};
 use server_fn::error::Error;
-use std::{borrow::Cow, collections::HashMap};
+use std::{borrow::Cow, collections::HashMap, fmt};
 
-/// A struct to hold all the possible errors that could be provided by child Views
+/// The errors that have been returned by the views inside an
+/// `<ErrorBoundary/>`, in the order they occurred, each under the
+/// [`ErrorKey`] of the place in the view that returned it.
+///
+/// The boundary provides them as an `RwSignal<Errors>` in context, and passes
+/// the same signal to its fallback. Removing one error with
+/// [`Errors::remove`] runs the view that returned it again, e.g., to retry
+/// one row of a list without touching the others:
+///
+/// ```rust,ignore
+/// view! { cx,
+///     <ErrorBoundary fallback=|cx, errors| view! { cx,
+///         <ul>
+///             {move || errors.get().into_iter().map(|(key, error)| view! { cx,
+///                 <li>
+///                     {error.to_string()}
+///                     <button on:click=move |_| {
+///                         errors.update(|errors| _ = errors.remove(&key));
+///                     }>"Retry"</button>
+///                 </li>
+///             }).collect_view(cx)}
+///         </ul>
+///     }>
+///         {rows.into_iter().map(|id| view! { cx, <div>{move || load_row(id)}</div> }).collect_view(cx)}
+///     </ErrorBoundary>
+/// }
+/// ```
+///
+/// Only a `Result` that is rendered in a reactive closure, like
+/// `{move || load_row(id)}` above, can be run again. To run all of the views
+/// that returned errors again, use [`Errors::clear`].
 #[derive(Debug, Clone, Default)]
-#[repr(transparent)]
-pub struct Errors(HashMap<ErrorKey, Error>);
+pub struct Errors {
+    errors: IndexMap<ErrorKey, Error>,
+    // reruns the view that returned each error, in the browser
+    retries: HashMap<ErrorKey, Trigger>,
+}
 
 /// A unique key for an error that occurs at a particular location in the user interface.
+///
+/// The errors returned by views are keyed by their position in the view.
+/// Errors inserted with [`Errors::insert`] can use any key, e.g., the ID of
+/// the row that failed, as `ErrorKey::from(format!("row-{id}"))`.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct ErrorKey(Cow<'static, str>);
@@ -24,19 +62,35 @@ where
     }
 }
 
+impl fmt::Display for ErrorKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 impl IntoIterator for Errors {
     type Item = (ErrorKey, Error);
     type IntoIter = IntoIter;
 
     #[inline(always)]
     fn into_iter(self) -> Self::IntoIter {
-        IntoIter(self.0.into_iter())
+        IntoIter(self.errors.into_iter())
+    }
+}
+
+impl<'a> IntoIterator for &'a Errors {
+    type Item = (&'a ErrorKey, &'a Error);
+    type IntoIter = Iter<'a>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
 /// An owning iterator over all the errors contained in the [Errors] struct.
 #[repr(transparent)]
-pub struct IntoIter(std::collections::hash_map::IntoIter<ErrorKey, Error>);
+pub struct IntoIter(indexmap::map::IntoIter<ErrorKey, Error>);
 
 impl Iterator for IntoIter {
     type Item = (ErrorKey, Error);
@@ -51,7 +105,7 @@ impl Iterator for IntoIter {
 
 /// An iterator over all the errors contained in the [Errors] struct.
 #[repr(transparent)]
-pub struct Iter<'a>(std::collections::hash_map::Iter<'a, ErrorKey, Error>);
+pub struct Iter<'a>(indexmap::map::Iter<'a, ErrorKey, Error>);
 
 impl<'a> Iterator for Iter<'a> {
     type Item = (&'a ErrorKey, &'a Error);
@@ -75,9 +129,10 @@ where
         match self {
             Ok(stuff) => {
                 if let Some(errors) = errors {
-                    errors.update(|errors| {
-                        errors.0.remove(&id);
-                    });
+                    if errors.with_untracked(|errors| errors.contains_key(&id))
+                    {
+                        errors.update(|errors| errors.forget(&id));
+                    }
                 }
                 stuff.into_view(cx)
             }
@@ -85,28 +140,42 @@ where
                 let error = error.into();
                 match errors {
                     Some(errors) => {
-                        errors.update({
-                            #[cfg(all(
-                                target_arch = "wasm32",
-                                feature = "web"
-                            ))]
-                            let id = id.clone();
-                            move |errors: &mut Errors| errors.insert(id, error)
-                        });
-
-                        // remove the error from the list if this drops,
-                        // i.e., if it's in a DynChild that switches from Err to Ok
-                        // Only can run on the client, will panic on the server
                         cfg_if! {
                           if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-                            use leptos_reactive::{on_cleanup, queue_microtask};
+                            use leptos_reactive::{create_trigger, on_cleanup, queue_microtask};
+
+                            // removing the error reruns the closure that is
+                            // rendering this view, if there is one
+                            let retry = create_trigger(cx);
+                            leptos_reactive::SpecialNonReactiveZone::enter();
+                            retry.track();
+                            leptos_reactive::SpecialNonReactiveZone::exit();
+                            errors.update({
+                                let id = id.clone();
+                                move |errors: &mut Errors| {
+                                    errors.insert(id.clone(), error);
+                                    errors.retries.insert(id, retry);
+                                }
+                            });
+
+                            // remove the error from the list if this drops,
+                            // i.e., if it's in a DynChild that switches from Err to Ok,
+                            // unless it has been replaced by the view that
+                            // was rendered instead
                             on_cleanup(cx, move || {
                               queue_microtask(move || {
-                                errors.update(|errors: &mut Errors| {
-                                  errors.remove(&id);
+                                let is_current = errors.try_with_untracked(|errors| {
+                                  errors.retries.get(&id) == Some(&retry)
                                 });
+                                if is_current == Some(true) {
+                                  errors.update(|errors| errors.forget(&id));
+                                }
                               });
                             });
+                          } else {
+                            errors.update(move |errors: &mut Errors| {
+                              errors.insert(id, error)
+                            });
                           }
                         }
                     }
@@ -129,15 +198,33 @@ impl Errors {
     /// Returns `true` if there are no errors.
     #[inline(always)]
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.errors.is_empty()
     }
 
-    /// Add an error to Errors that will be processed by `<ErrorBoundary/>`
+    /// The number of errors.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if there is an error with the given key.
+    pub fn contains_key(&self, key: &ErrorKey) -> bool {
+        self.errors.contains_key(key)
+    }
+
+    /// Returns the error with the given key, if there is one.
+    pub fn get(&self, key: &ErrorKey) -> Option<&Error> {
+        self.errors.get(key)
+    }
+
+    /// Add an error to Errors that will be processed by `<ErrorBoundary/>`.
+    /// An error that replaces one with the same key keeps its position.
     pub fn insert<E>(&mut self, key: ErrorKey, error: E)
     where
         E: Into<Error>,
     {
-        self.0.insert(key, error.into());
+        self.retries.remove(&key);
+        self.errors.insert(key, error.into());
     }
 
     /// Add an error with the default key for errors outside the reactive system
@@ -145,17 +232,55 @@ impl Errors {
     where
         E: Into<Error>,
     {
-        self.0.insert(Default::default(), error.into());
+        self.insert(Default::default(), error);
     }
 
-    /// Remove an error to Errors that will be processed by `<ErrorBoundary/>`
+    /// Removes the error with the given key, and runs the view that returned
+    /// it again, if it was returned by a view that is rendered in a reactive
+    /// closure. The other errors, and the views that returned them, are left
+    /// as they are.
     pub fn remove(&mut self, key: &ErrorKey) -> Option<Error> {
-        self.0.remove(key)
+        let error = self.errors.shift_remove(key);
+        if let Some(retry) = self.retries.remove(key) {
+            Self::retry(retry);
+        }
+        error
     }
 
-    /// An iterator over all the errors, in arbitrary order.
+    /// Removes every error, and runs each view that returned one again, like
+    /// [`Errors::remove`].
+    pub fn clear(&mut self) {
+        self.errors.clear();
+        for (_, retry) in self.retries.drain() {
+            Self::retry(retry);
+        }
+    }
+
+    /// An iterator over all the errors, in the order they occurred.
     #[inline(always)]
     pub fn iter(&self) -> Iter<'_> {
-        Iter(self.0.iter())
+        Iter(self.errors.iter())
+    }
+
+    // runs the view that returned an error again, after the update that
+    // removes the error, which the view that runs again will update too
+    fn retry(retry: Trigger) {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            leptos_reactive::queue_microtask(move || {
+                _ = retry.try_notify();
+            });
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = retry;
+        }
+    }
+
+    // removes an error without running its view again, because the view has
+    // already run again or has been disposed of
+    fn forget(&mut self, key: &ErrorKey) {
+        self.errors.shift_remove(key);
+        self.retries.remove(key);
     }
 }
@@ -0,0 +1,360 @@
+use leptos_reactive::{
+    create_rw_signal, provide_context, store_value, use_context, Scope, Signal,
+    SignalGetUntracked, SignalSet, StoredValue,
+};
+use std::{fmt, rc::Rc};
+
+/// Creates a signal that reads and writes the cookie with the given name.
+///
+/// This reads the same value on the server and in the browser, so it can be
+/// used for things like a selected locale or theme without causing a flash
+/// of incorrect content during hydration.
+/// - On the server, the value is read from the request’s `Cookie` header,
+///   which is made available by the server integrations (see
+///   [`provide_server_cookies`]). Setting the value appends a `Set-Cookie`
+///   header to the response.
+/// - In the browser, the value is read from and written to `document.cookie`.
+///
+/// Setting the value to `None` deletes the cookie by setting it with an
+/// expired `Max-Age`. Values are percent-encoded when they are written, and
+/// decoded when they are read, so they can contain any character.
+///
+/// ```rust,ignore
+/// #[component]
+/// pub fn LocalePicker(cx: Scope) -> impl IntoView {
+///     let (locale, set_locale) = use_cookie(cx, "locale");
+///
+///     view! { cx,
+///         <p>"Current locale: " {move || locale.get().unwrap_or_default()}</p>
+///         <button on:click=move |_| set_locale.set(Some("fr".into()))>
+///             "Français"
+///         </button>
+///     }
+/// }
+/// ```
+pub fn use_cookie(
+    cx: Scope,
+    name: impl Into<String>,
+) -> (Signal<Option<String>>, CookieSetter) {
+    use_cookie_with_options(cx, name, CookieOptions::default())
+}
+
+/// Creates a signal that reads and writes the cookie with the given name,
+/// using the given [`CookieOptions`] whenever the cookie is set or deleted
+/// without options of its own.
+///
+/// See [`use_cookie`] for more details.
+pub fn use_cookie_with_options(
+    cx: Scope,
+    name: impl Into<String>,
+    options: CookieOptions,
+) -> (Signal<Option<String>>, CookieSetter) {
+    let name = name.into();
+    let server_cookies = use_context::<ServerCookies>(cx);
+
+    let initial = if crate::is_browser() {
+        read_document_cookie(&name)
+    } else {
+        server_cookies
+            .as_ref()
+            .and_then(|cookies| cookies.get(&name))
+    };
+    let cookie = create_rw_signal(cx, initial);
+
+    let set: SetCookie = Box::new(move |value, options| {
+        let header = options.to_set_cookie(&name, value.as_deref());
+        if crate::is_browser() {
+            write_document_cookie(&header);
+        } else if let Some(cookies) = &server_cookies {
+            (cookies.set_cookie)(header);
+        } else {
+            crate::debug_warn!(
+                "Setting the cookie {name:?} on the server requires \
+                 `ServerCookies` to be provided by a server integration."
+            );
+        }
+        if cookie.get_untracked() != value {
+            cookie.set(value);
+        }
+    });
+
+    let setter = CookieSetter {
+        set: store_value(cx, set),
+        options: store_value(cx, options),
+    };
+    (cookie.into(), setter)
+}
+
+// writes a value, or deletes the cookie for `None`, with the given options
+type SetCookie = Box<dyn Fn(Option<String>, &CookieOptions)>;
+
+/// Sets or deletes the cookie returned by [`use_cookie`].
+///
+/// [`set`](SignalSet::set) uses the options the cookie was created with,
+/// while [`set_with_options`](CookieSetter::set_with_options) uses other
+/// ones for a single write, like a longer `Max-Age` when the user asks to
+/// be remembered.
+pub struct CookieSetter {
+    set: StoredValue<SetCookie>,
+    options: StoredValue<CookieOptions>,
+}
+
+impl Clone for CookieSetter {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl Copy for CookieSetter {}
+
+impl fmt::Debug for CookieSetter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CookieSetter")
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl CookieSetter {
+    /// Sets the cookie, or deletes it if `value` is `None`, with the given
+    /// options instead of the ones it was created with.
+    pub fn set_with_options(
+        &self,
+        value: Option<String>,
+        options: &CookieOptions,
+    ) {
+        self.set.with_value(|set| set(value, options));
+    }
+}
+
+impl SignalSet<Option<String>> for CookieSetter {
+    fn set(&self, new_value: Option<String>) {
+        _ = self.try_set(new_value);
+    }
+
+    fn try_set(&self, new_value: Option<String>) -> Option<Option<String>> {
+        let Some(options) = self.options.try_get_value() else {
+            return Some(new_value);
+        };
+        let mut new_value = Some(new_value);
+        _ = self
+            .set
+            .try_with_value(|set| set(new_value.take().flatten(), &options));
+        new_value
+    }
+}
+
+/// Options that are used when setting or deleting a cookie, either for every
+/// write with [`use_cookie_with_options`] or for one with
+/// [`CookieSetter::set_with_options`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CookieOptions {
+    /// The number of seconds until the cookie expires (`Max-Age`). If `None`,
+    /// the cookie will be a session cookie.
+    pub max_age: Option<i64>,
+    /// The `Path` for which the cookie is valid. Defaults to `/`.
+    pub path: Option<String>,
+    /// The `SameSite` policy for the cookie.
+    pub same_site: Option<SameSite>,
+    /// Whether the cookie should only be sent over HTTPS (`Secure`).
+    pub secure: bool,
+}
+
+impl CookieOptions {
+    /// Sets the number of seconds until the cookie expires.
+    pub fn max_age(mut self, seconds: i64) -> Self {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    /// Sets the `Path` for which the cookie is valid.
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Sets the `SameSite` policy for the cookie.
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+
+    /// Sets whether the cookie should only be sent over HTTPS.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Builds the value of a `Set-Cookie` header (or `document.cookie`
+    /// assignment) for the cookie with the given name. A `value` of `None`
+    /// deletes the cookie. The value is percent-encoded.
+    pub fn to_set_cookie(&self, name: &str, value: Option<&str>) -> String {
+        let mut cookie =
+            format!("{name}={}", encode_value(value.unwrap_or_default()));
+        let path = self.path.as_deref().unwrap_or("/");
+        cookie.push_str("; Path=");
+        cookie.push_str(path);
+        match (value, self.max_age) {
+            (None, _) => cookie.push_str("; Max-Age=0"),
+            (Some(_), Some(max_age)) => {
+                cookie.push_str(&format!("; Max-Age={max_age}"))
+            }
+            (Some(_), None) => {}
+        }
+        if let Some(same_site) = self.same_site {
+            cookie.push_str("; SameSite=");
+            cookie.push_str(same_site.as_str());
+        }
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+        cookie
+    }
+}
+
+/// The `SameSite` attribute of a cookie.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SameSite {
+    /// `SameSite=Strict`
+    Strict,
+    /// `SameSite=Lax`
+    Lax,
+    /// `SameSite=None`
+    None,
+}
+
+impl SameSite {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
+    }
+}
+
+/// The cookies sent with the current request, and a way to set cookies on the
+/// response. This is provided during server rendering by the server
+/// integrations, and used by [`use_cookie`].
+#[derive(Clone)]
+pub struct ServerCookies {
+    header: Option<String>,
+    set_cookie: Rc<dyn Fn(String)>,
+}
+
+impl ServerCookies {
+    /// Returns the value of the cookie with the given name, if it was sent
+    /// with the request.
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.header
+            .as_deref()
+            .and_then(|header| parse_cookie(header, name))
+    }
+}
+
+impl fmt::Debug for ServerCookies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerCookies")
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+/// Provides the request’s `Cookie` header and a function that appends a
+/// `Set-Cookie` header to the response, so that [`use_cookie`] can be used
+/// during server rendering. This is called by the server integrations
+/// (`leptos_actix`, `leptos_axum`, and `leptos_viz`).
+pub fn provide_server_cookies(
+    cx: Scope,
+    cookie_header: Option<String>,
+    set_cookie: impl Fn(String) + 'static,
+) {
+    provide_context(
+        cx,
+        ServerCookies {
+            header: cookie_header,
+            set_cookie: Rc::new(set_cookie),
+        },
+    )
+}
+
+/// Finds the value of the cookie with the given name in a `Cookie` header
+/// (or in the value of `document.cookie`), and percent-decodes it.
+pub fn parse_cookie(header: &str, name: &str) -> Option<String> {
+    header.split(';').find_map(|pair| {
+        let (key, value) = pair.trim().split_once('=')?;
+        (key == name).then(|| decode_value(value))
+    })
+}
+
+// percent-encodes everything but the characters a cookie value can contain
+// https://www.rfc-editor.org/rfc/rfc6265#section-4.1.1
+fn encode_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'%' | b'"' | b',' | b';' | b'\\' => {
+                encoded.push_str(&format!("%{byte:02X}"))
+            }
+            0x21..=0x7E => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+// decodes `%XX` sequences, leaving any other `%` as it is
+fn decode_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn read_document_cookie(name: &str) -> Option<String> {
+    cfg_if::cfg_if! {
+        if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+            use wasm_bindgen::JsCast;
+
+            crate::document()
+                .unchecked_into::<web_sys::HtmlDocument>()
+                .cookie()
+                .ok()
+                .and_then(|cookies| parse_cookie(&cookies, name))
+        } else {
+            _ = name;
+            None
+        }
+    }
+}
+
+fn write_document_cookie(cookie: &str) {
+    cfg_if::cfg_if! {
+        if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+            use wasm_bindgen::JsCast;
+
+            _ = crate::document()
+                .unchecked_into::<web_sys::HtmlDocument>()
+                .set_cookie(cookie);
+        } else {
+            _ = cookie;
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Escaping for everything Leptos writes into server-rendered HTML.
+//!
+//! Every server-side rendering path goes through one of these three
+//! functions, depending on where the value ends up:
+//! - [`escape_text`] for text content,
+//! - [`escape_attr`] for the values of double-quoted attributes,
+//! - [`escape_script_json`] for JSON (or JavaScript string literals)
+//!   written into a `<script>`, like serialized resources.
+//!
+//! Each returns its input unchanged, without allocating, if there is
+//! nothing to escape. Otherwise the spans between the characters that need
+//! escaping are copied over in one go.
+
+use std::borrow::Cow;
+
+const TEXT_SPECIALS: &[u8] = b"&<>";
+const ATTR_SPECIALS: &[u8] = b"&<>\"";
+// `0xE2` is the first byte of U+2028 and U+2029 in UTF-8
+const SCRIPT_SPECIALS: &[u8] = b"&<>\xE2";
+
+/// Escapes text so that it can be used as the content of an HTML element.
+///
+/// ```
+/// # use leptos_dom::escape::escape_text;
+/// assert_eq!(escape_text("a < b && c"), "a &lt; b &amp;&amp; c");
+/// assert_eq!(
+///     escape_text("</script><script>alert(1)</script>"),
+///     "&lt;/script&gt;&lt;script&gt;alert(1)&lt;/script&gt;"
+/// );
+/// ```
+pub fn escape_text(text: &str) -> Cow<'_, str> {
+    escape(text, TEXT_SPECIALS, text_replacement)
+}
+
+/// Escapes text as in [`escape_text`], writing it into `buf`.
+pub fn escape_text_into(buf: &mut String, text: &str) {
+    escape_into(buf, text, TEXT_SPECIALS, text_replacement)
+}
+
+/// Escapes a value so that it can be used inside a double-quoted attribute.
+///
+/// ```
+/// # use leptos_dom::escape::escape_attr;
+/// assert_eq!(escape_attr(r#"" onload="alert(1)"#), "&quot; onload=&quot;alert(1)");
+/// ```
+pub fn escape_attr(value: &str) -> Cow<'_, str> {
+    escape(value, ATTR_SPECIALS, attr_replacement)
+}
+
+/// Escapes a value as in [`escape_attr`], writing it into `buf`.
+pub fn escape_attr_into(buf: &mut String, value: &str) {
+    escape_into(buf, value, ATTR_SPECIALS, attr_replacement)
+}
+
+/// Escapes JSON, or a JavaScript string literal, so that it can be written
+/// into a `<script>` element.
+///
+/// `<`, `>` and `&` are replaced with their `\u` escapes, so that the
+/// script can't be closed with `</script>` or turned into a comment with
+/// `<!--`, and the line separators U+2028 and U+2029, which older
+/// JavaScript engines don't allow in string literals, are escaped too. The
+/// escapes are only valid inside JSON strings, which is the only place
+/// these characters can appear in valid JSON.
+///
+/// ```
+/// # use leptos_dom::escape::escape_script_json;
+/// assert_eq!(
+///     escape_script_json(r#""</script><!--""#),
+///     r#""\u003c/script\u003e\u003c!--""#
+/// );
+/// ```
+pub fn escape_script_json(json: &str) -> Cow<'_, str> {
+    escape(json, SCRIPT_SPECIALS, script_replacement)
+}
+
+fn text_replacement(bytes: &[u8], idx: usize) -> Option<(&'static str, usize)> {
+    match bytes[idx] {
+        b'&' => Some(("&amp;", 1)),
+        b'<' => Some(("&lt;", 1)),
+        b'>' => Some(("&gt;", 1)),
+        _ => None,
+    }
+}
+
+fn attr_replacement(bytes: &[u8], idx: usize) -> Option<(&'static str, usize)> {
+    match bytes[idx] {
+        b'"' => Some(("&quot;", 1)),
+        _ => text_replacement(bytes, idx),
+    }
+}
+
+fn script_replacement(
+    bytes: &[u8],
+    idx: usize,
+) -> Option<(&'static str, usize)> {
+    match &bytes[idx..] {
+        [b'&', ..] => Some(("\\u0026", 1)),
+        [b'<', ..] => Some(("\\u003c", 1)),
+        [b'>', ..] => Some(("\\u003e", 1)),
+        [0xE2, 0x80, 0xA8, ..] => Some(("\\u2028", 3)),
+        [0xE2, 0x80, 0xA9, ..] => Some(("\\u2029", 3)),
+        _ => None,
+    }
+}
+
+type Replacement = fn(&[u8], usize) -> Option<(&'static str, usize)>;
+
+fn escape<'a>(
+    value: &'a str,
+    specials: &[u8],
+    replacement: Replacement,
+) -> Cow<'a, str> {
+    let bytes = value.as_bytes();
+    let mut idx = 0;
+    while let Some(found) = find_special(bytes, idx, specials) {
+        if replacement(bytes, found).is_some() {
+            let mut buf = String::with_capacity(value.len() + 16);
+            escape_into(&mut buf, value, specials, replacement);
+            return Cow::Owned(buf);
+        }
+        idx = found + 1;
+    }
+    Cow::Borrowed(value)
+}
+
+fn escape_into(
+    buf: &mut String,
+    value: &str,
+    specials: &[u8],
+    replacement: Replacement,
+) {
+    let bytes = value.as_bytes();
+    let mut copied_up_to = 0;
+    let mut idx = 0;
+    while let Some(found) = find_special(bytes, idx, specials) {
+        idx = found + 1;
+        if let Some((escaped, len)) = replacement(bytes, found) {
+            // specials are ASCII or the first byte of a character, so these
+            // are always character boundaries
+            buf.push_str(&value[copied_up_to..found]);
+            buf.push_str(escaped);
+            copied_up_to = found + len;
+            idx = copied_up_to;
+        }
+    }
+    buf.push_str(&value[copied_up_to..]);
+}
+
+/// Returns the index of the first byte at or after `from` that is one of
+/// `specials`.
+///
+/// Text rarely needs escaping, so this checks eight bytes at a time,
+/// skipping over whole words that contain none of the special bytes.
+fn find_special(bytes: &[u8], from: usize, specials: &[u8]) -> Option<usize> {
+    const LO: u64 = 0x0101_0101_0101_0101;
+    const HI: u64 = 0x8080_8080_8080_8080;
+
+    let mut idx = from;
+    while let Some(chunk) = bytes.get(idx..idx + 8) {
+        let word = u64::from_le_bytes(chunk.try_into().unwrap());
+        let has_special = specials.iter().any(|&special| {
+            // a byte of `diff` is zero where `word` has `special`
+            let diff = word ^ (LO * special as u64);
+            (diff.wrapping_sub(LO) & !diff & HI) != 0
+        });
+        if has_special {
+            break;
+        }
+        idx += 8;
+    }
+    bytes
+        .get(idx..)?
+        .iter()
+        .position(|byte| specials.contains(byte))
+        .map(|pos| idx + pos)
+}
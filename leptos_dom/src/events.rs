@@ -1,5 +1,7 @@
 pub mod typed;
 
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+use leptos_reactive::{on_cleanup, Scope};
 use std::{borrow::Cow, cell::RefCell, collections::HashSet};
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use wasm_bindgen::{
@@ -11,21 +13,36 @@ thread_local! {
     pub(crate) static GLOBAL_EVENTS: RefCell<HashSet<Cow<'static, str>>> = RefCell::new(HashSet::new());
 }
 
+/// Returns the number of event handlers that have been added to elements
+/// and not yet removed.
+///
+/// Handlers are removed when the [`Scope`](leptos_reactive::Scope) that was
+/// running when they were added is disposed, so this can be used to check
+/// that a component does not leak handlers after it is unmounted. It is only
+/// available in debug mode, and is always `0` on the server. See also
+/// [`runtime_stats`](leptos_reactive::runtime_stats).
+#[cfg(debug_assertions)]
+pub fn live_event_handlers() -> usize {
+    leptos_reactive::live_event_handler_count()
+}
+
 // Used in template macro
 #[doc(hidden)]
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 #[inline(always)]
 pub fn add_event_helper<E: crate::ev::EventDescriptor + 'static>(
+    cx: Scope,
     target: &web_sys::Element,
     event: E,
     #[allow(unused_mut)] // used for tracing in debug
     mut event_handler: impl FnMut(E::EventType) + 'static,
 ) {
     let event_name = event.name();
-    let event_handler = Box::new(event_handler);
+    let event_handler = event.wrap_handler(Box::new(event_handler));
 
     if E::BUBBLES {
         add_event_listener(
+            Some(cx),
             target,
             event.event_delegation_key(),
             event_name,
@@ -34,6 +51,7 @@ pub fn add_event_helper<E: crate::ev::EventDescriptor + 'static>(
         );
     } else {
         add_event_listener_undelegated(
+            Some(cx),
             target,
             &event_name,
             event_handler,
@@ -43,9 +61,15 @@ pub fn add_event_helper<E: crate::ev::EventDescriptor + 'static>(
 }
 
 /// Adds an event listener to the target DOM element using implicit event delegation.
+///
+/// If a [`Scope`] is given, the listener is removed when the scope that is
+/// currently running is disposed, or the given scope if none is running. This
+/// is the scope of a `DynChild` or `Each` child that renders the element, so
+/// re-rendering the child removes the listeners of the elements it replaces.
 #[doc(hidden)]
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub fn add_event_listener<E>(
+    cx: Option<Scope>,
     target: &web_sys::Element,
     key: Cow<'static, str>,
     event_name: Cow<'static, str>,
@@ -70,12 +94,37 @@ pub fn add_event_listener<E>(
     let cb = Closure::wrap(cb as Box<dyn FnMut(E)>).into_js_value();
     let key = intern(&key);
     _ = js_sys::Reflect::set(target, &JsValue::from_str(&key), &cb);
+    crate::mark_bound(target);
     add_delegated_event_listener(&key, event_name, options);
+
+    #[cfg(debug_assertions)]
+    leptos_reactive::count_event_handler(true);
+
+    if let Some(cx) = cx {
+        let cx = Scope::current().unwrap_or(cx);
+        let target = target.clone();
+        let key = JsValue::from_str(key);
+        on_cleanup(cx, move || {
+            // the element may have been given a new handler for this event
+            // since, in which case that one should be kept
+            if js_sys::Reflect::get(&target, &key).ok().as_ref() == Some(&cb) {
+                _ = js_sys::Reflect::delete_property(&target, &key);
+            }
+
+            #[cfg(debug_assertions)]
+            leptos_reactive::count_event_handler(false);
+        });
+    }
 }
 
+/// Adds an event listener directly to the target DOM element.
+///
+/// If a [`Scope`] is given, the listener is removed when the scope that is
+/// currently running is disposed, as with [`add_event_listener`].
 #[doc(hidden)]
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub(crate) fn add_event_listener_undelegated<E>(
+    cx: Option<Scope>,
     target: &web_sys::Element,
     event_name: &str,
     #[cfg(debug_assertions)] mut cb: Box<dyn FnMut(E)>,
@@ -98,6 +147,7 @@ pub(crate) fn add_event_listener_undelegated<E>(
 
     let event_name = intern(event_name);
     let cb = Closure::wrap(cb as Box<dyn FnMut(E)>).into_js_value();
+    crate::mark_bound(target);
     if let Some(options) = options {
         _ = target
             .add_event_listener_with_callback_and_add_event_listener_options(
@@ -109,6 +159,29 @@ pub(crate) fn add_event_listener_undelegated<E>(
         _ = target
             .add_event_listener_with_callback(event_name, cb.unchecked_ref());
     }
+
+    #[cfg(debug_assertions)]
+    leptos_reactive::count_event_handler(true);
+
+    if let Some(cx) = cx {
+        let cx = Scope::current().unwrap_or(cx);
+        let target = target.clone();
+        let capture = options.as_ref().map_or(false, |options| {
+            js_sys::Reflect::get(options, &JsValue::from_str("capture"))
+                .map_or(false, |capture| capture.is_truthy())
+        });
+        let event_name = event_name.to_string();
+        on_cleanup(cx, move || {
+            _ = target.remove_event_listener_with_callback_and_bool(
+                &event_name,
+                cb.unchecked_ref(),
+                capture,
+            );
+
+            #[cfg(debug_assertions)]
+            leptos_reactive::count_event_handler(false);
+        });
+    }
 }
 
 // cf eventHandler in ryansolid/dom-expressions
@@ -27,6 +27,17 @@ pub trait EventDescriptor: Clone {
     fn options(&self) -> &Option<web_sys::AddEventListenerOptions> {
         &None
     }
+
+    /// Wraps the handler for this event before it is added as a listener.
+    /// This is only used by modifiers like [`prevent_default`], and returns
+    /// the handler unchanged otherwise.
+    #[inline(always)]
+    fn wrap_handler(
+        &self,
+        handler: Box<dyn FnMut(Self::EventType)>,
+    ) -> Box<dyn FnMut(Self::EventType)> {
+        handler
+    }
 }
 
 /// Overrides the [`EventDescriptor::BUBBLES`] value to always return
@@ -49,6 +60,75 @@ impl<Ev: EventDescriptor> EventDescriptor for undelegated<Ev> {
     }
 
     const BUBBLES: bool = false;
+
+    #[inline(always)]
+    fn wrap_handler(
+        &self,
+        handler: Box<dyn FnMut(Self::EventType)>,
+    ) -> Box<dyn FnMut(Self::EventType)> {
+        self.0.wrap_handler(handler)
+    }
+}
+
+macro_rules! event_modifier {
+    ($(#[$meta:meta])* $modifier:ident) => {
+        $(#[$meta])*
+        #[derive(Clone)]
+        #[allow(non_camel_case_types)]
+        pub struct $modifier<Ev: EventDescriptor>(pub Ev);
+
+        impl<Ev> EventDescriptor for $modifier<Ev>
+        where
+            Ev: EventDescriptor,
+            Ev::EventType: wasm_bindgen::JsCast + 'static,
+        {
+            type EventType = Ev::EventType;
+
+            const BUBBLES: bool = Ev::BUBBLES;
+
+            #[inline(always)]
+            fn name(&self) -> Cow<'static, str> {
+                self.0.name()
+            }
+
+            #[inline(always)]
+            fn event_delegation_key(&self) -> Cow<'static, str> {
+                self.0.event_delegation_key()
+            }
+
+            #[inline(always)]
+            fn options(&self) -> &Option<web_sys::AddEventListenerOptions> {
+                self.0.options()
+            }
+
+            fn wrap_handler(
+                &self,
+                handler: Box<dyn FnMut(Self::EventType)>,
+            ) -> Box<dyn FnMut(Self::EventType)> {
+                let mut handler = self.0.wrap_handler(handler);
+                Box::new(move |ev| {
+                    wasm_bindgen::JsCast::unchecked_ref::<web_sys::Event>(&ev)
+                        .$modifier();
+                    handler(ev);
+                })
+            }
+        }
+    };
+}
+
+event_modifier! {
+    /// Calls [`Event::prevent_default`](web_sys::Event::prevent_default)
+    /// before running the handler. In the `view` macro, this is written as
+    /// `on:submit:prevent_default`.
+    prevent_default
+}
+
+event_modifier! {
+    /// Calls [`Event::stop_propagation`](web_sys::Event::stop_propagation)
+    /// before running the handler, which also stops delegated events from
+    /// reaching the handlers of parent elements. In the `view` macro, this
+    /// is written as `on:click:stop_propagation`.
+    stop_propagation
 }
 
 /// A custom event.
@@ -1,8 +1,12 @@
 //! A variety of DOM utility functions.
 
-use crate::{events::typed as ev, is_server, window};
-use leptos_reactive::{on_cleanup, Scope};
-use std::time::Duration;
+use crate::{
+    events::typed as ev, html::ElementDescriptor, is_server, window, NodeRef,
+};
+use leptos_reactive::{
+    create_rw_signal, create_signal, on_cleanup, Resource, Scope, Signal,
+};
+use std::{cell::RefCell, rc::Rc, time::Duration};
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
 
 /// Sets a property on a DOM element.
@@ -48,37 +52,95 @@ pub fn location_pathname() -> Option<String> {
 }
 
 /// Helper function to extract [`Event.target`](https://developer.mozilla.org/en-US/docs/Web/API/Event/target)
-/// from any event.
+/// from any event, as the element type `T`.
+///
+/// ```rust,ignore
+/// let on_input = move |ev: ev::Event| {
+///     let input = event_target::<web_sys::HtmlInputElement>(&ev);
+///     set_value.set(input.value());
+/// };
+/// ```
+///
+/// ## Panics
+/// Panics if the event has no target, or if its target is not a `T`.
+#[track_caller]
 pub fn event_target<T>(event: &web_sys::Event) -> T
 where
     T: JsCast,
 {
-    event.target().unwrap_throw().unchecked_into::<T>()
+    let Some(target) = event.target() else {
+        panic!(
+            "event_target::<{}>: the `{}` event has no target",
+            std::any::type_name::<T>(),
+            event.type_()
+        );
+    };
+    target.dyn_into::<T>().unwrap_or_else(|target| {
+        panic!(
+            "event_target::<{}>: the target of the `{}` event is not a `{}`, \
+             but {target:?}",
+            std::any::type_name::<T>(),
+            event.type_(),
+            std::any::type_name::<T>(),
+        )
+    })
 }
 
 /// Helper function to extract `event.target.value` from an event.
 ///
-/// This is useful in the `on:input` or `on:change` listeners for an `<input>` element.
+/// This is useful in the `on:input` or `on:change` listeners for an
+/// `<input>`, `<textarea>` or `<select>` element.
+///
+/// ## Panics
+/// Panics if the target of the event has no `value`.
+#[track_caller]
 pub fn event_target_value<T>(event: &T) -> String
 where
     T: JsCast,
 {
-    event
-        .unchecked_ref::<web_sys::Event>()
-        .target()
-        .unwrap_throw()
-        .unchecked_into::<web_sys::HtmlInputElement>()
-        .value()
+    let event = event.unchecked_ref::<web_sys::Event>();
+    event_target_property(event, "value")
+        .as_string()
+        .unwrap_or_else(|| {
+            panic!(
+                "event_target_value: the target of the `{}` event has no \
+                 `value`; expected an <input>, <textarea> or <select>",
+                event.type_()
+            )
+        })
 }
 
 /// Helper function to extract `event.target.checked` from an event.
 ///
 /// This is useful in the `on:change` listeners for an `<input type="checkbox">` element.
-pub fn event_target_checked(ev: &web_sys::Event) -> bool {
-    ev.target()
-        .unwrap()
-        .unchecked_into::<web_sys::HtmlInputElement>()
-        .checked()
+///
+/// ## Panics
+/// Panics if the target of the event has no `checked` property.
+#[track_caller]
+pub fn event_target_checked<T>(event: &T) -> bool
+where
+    T: JsCast,
+{
+    let event = event.unchecked_ref::<web_sys::Event>();
+    event_target_property(event, "checked")
+        .as_bool()
+        .unwrap_or_else(|| {
+            panic!(
+                "event_target_checked: the target of the `{}` event has no \
+                 `checked` property; expected an <input type=\"checkbox\"> \
+                 or <input type=\"radio\">",
+                event.type_()
+            )
+        })
+}
+
+#[track_caller]
+fn event_target_property(event: &web_sys::Event, property: &str) -> JsValue {
+    let Some(target) = event.target() else {
+        panic!("the `{}` event has no target", event.type_());
+    };
+    js_sys::Reflect::get(&target, &JsValue::from_str(property))
+        .unwrap_or(JsValue::UNDEFINED)
 }
 
 /// Handle that is generated by [request_animation_frame_with_handle] and can
@@ -265,10 +327,7 @@ pub fn debounce<T: 'static>(
     #[cfg(debug_assertions)] mut cb: impl FnMut(T) + 'static,
     #[cfg(not(debug_assertions))] cb: impl FnMut(T) + 'static,
 ) -> impl FnMut(T) {
-    use std::{
-        cell::{Cell, RefCell},
-        rc::Rc,
-    };
+    use std::cell::Cell;
 
     cfg_if::cfg_if! {
       if #[cfg(debug_assertions)] {
@@ -447,3 +506,722 @@ pub fn ssr_event_listener<E: crate::ev::EventDescriptor + 'static>(
     _ = event;
     _ = event_handler;
 }
+
+/// Options for [`use_intersection_observer`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IntersectionOptions {
+    /// A margin around the viewport, in the syntax of the CSS `margin`
+    /// property like `"200px 0px"`, that grows or shrinks the area in which
+    /// the element counts as visible.
+    pub root_margin: Option<String>,
+    /// The ratios of the element that must be visible for the callback to be
+    /// called. Defaults to `[0.0]`, i.e., as soon as any part is visible.
+    pub threshold: Vec<f64>,
+}
+
+impl IntersectionOptions {
+    /// Sets the margin around the viewport.
+    pub fn root_margin(mut self, root_margin: impl Into<String>) -> Self {
+        self.root_margin = Some(root_margin.into());
+        self
+    }
+
+    /// Adds a ratio of the element that must be visible for the callback to
+    /// be called.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold.push(threshold);
+        self
+    }
+}
+
+/// Handle that is generated by [use_intersection_observer] and can be used
+/// to stop observing the element.
+#[derive(Clone, Debug, Default)]
+pub struct IntersectionObserverHandle(
+    Rc<RefCell<Option<web_sys::IntersectionObserver>>>,
+);
+
+impl IntersectionObserverHandle {
+    /// Stops observing the element. The observer is also disconnected when
+    /// the scope it was created in is disposed.
+    pub fn disconnect(&self) {
+        if let Some(observer) = self.0.take() {
+            observer.disconnect();
+        }
+    }
+}
+
+/// Calls `callback` whenever the element in `target` enters or leaves the
+/// viewport, using an
+/// [`IntersectionObserver`](https://developer.mozilla.org/en-US/docs/Web/API/Intersection_Observer_API).
+///
+/// The element is observed once it has been mounted in the node ref, and
+/// again if the node ref is filled with another element. The observer is
+/// disconnected when `cx` is disposed. On the server, this does nothing.
+///
+/// ```
+/// use leptos::*;
+///
+/// #[component]
+/// fn Feed(cx: Scope) -> impl IntoView {
+///     let (pages, set_pages) = create_signal(cx, 1);
+///     let sentinel = create_node_ref::<html::Div>(cx);
+///     use_intersection_observer(
+///         cx,
+///         sentinel,
+///         IntersectionOptions::default().root_margin("200px"),
+///         move |entries| {
+///             if entries.iter().any(|entry| entry.is_intersecting()) {
+///                 set_pages.update(|pages| *pages += 1);
+///             }
+///         },
+///     );
+///
+///     view! { cx,
+///         <For each=move || 0..pages.get() key=|page| *page view=|cx, page| view! { cx, <p>"Page " {page}</p> }/>
+///         <div node_ref=sentinel/>
+///     }
+/// }
+/// ```
+pub fn use_intersection_observer<T>(
+    cx: Scope,
+    target: NodeRef<T>,
+    options: IntersectionOptions,
+    callback: impl Fn(Vec<web_sys::IntersectionObserverEntry>) + 'static,
+) -> IntersectionObserverHandle
+where
+    T: ElementDescriptor + Clone + 'static,
+{
+    let handle = IntersectionObserverHandle::default();
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        let callback = Closure::<
+            dyn Fn(js_sys::Array, web_sys::IntersectionObserver),
+        >::new(move |entries: js_sys::Array, _| {
+            callback(entries.iter().map(JsCast::unchecked_into).collect())
+        })
+        .into_js_value();
+
+        leptos_reactive::create_effect(cx, {
+            let handle = handle.clone();
+            move |_| {
+                let Some(el) = target.get() else {
+                    return;
+                };
+                handle.disconnect();
+
+                let init = web_sys::IntersectionObserverInit::new();
+                if let Some(root_margin) = &options.root_margin {
+                    init.set_root_margin(root_margin);
+                }
+                if !options.threshold.is_empty() {
+                    let threshold = options
+                        .threshold
+                        .iter()
+                        .copied()
+                        .map(JsValue::from_f64)
+                        .collect::<js_sys::Array>();
+                    init.set_threshold(&threshold);
+                }
+                let observer = web_sys::IntersectionObserver::new_with_options(
+                    callback.unchecked_ref(),
+                    &init,
+                );
+                match observer {
+                    Ok(observer) => {
+                        let el = el.into_any();
+                        observer.observe(&el);
+                        *handle.0.borrow_mut() = Some(observer);
+                    }
+                    Err(e) => crate::error!(
+                        "[use_intersection_observer] could not create the \
+                         observer: {e:?}"
+                    ),
+                }
+            }
+        });
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = (target, options, callback);
+    }
+
+    on_cleanup(cx, {
+        let handle = handle.clone();
+        move || handle.disconnect()
+    });
+
+    handle
+}
+
+/// Whether the page is visible to the user, as reported by
+/// [`document.visibilityState`](https://developer.mozilla.org/en-US/docs/Web/API/Document/visibilityState).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum VisibilityState {
+    /// The page is at least partially visible, i.e., it is the foreground
+    /// tab of a window that is not minimized.
+    #[default]
+    Visible,
+    /// The page is not visible, e.g., because it is in a background tab or
+    /// the window is minimized.
+    Hidden,
+}
+
+impl VisibilityState {
+    fn current() -> Self {
+        if !is_server() && crate::document().hidden() {
+            Self::Hidden
+        } else {
+            Self::Visible
+        }
+    }
+}
+
+/// Returns a signal that tracks whether the page is visible, updated on the
+/// document’s `visibilitychange` event. The listener is removed when `cx` is
+/// disposed. On the server, the page is always [`VisibilityState::Visible`].
+///
+/// ```
+/// use leptos::*;
+///
+/// #[component]
+/// fn Video(cx: Scope) -> impl IntoView {
+///     let visibility = use_document_visibility(cx);
+///     let playing = move || visibility.get() == VisibilityState::Visible;
+///
+///     view! { cx, <p>{move || if playing() { "Playing" } else { "Paused" }}</p> }
+/// }
+/// ```
+pub fn use_document_visibility(cx: Scope) -> Signal<VisibilityState> {
+    let (visibility, set_visibility) =
+        create_signal(cx, VisibilityState::current());
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        use leptos_reactive::SignalSet;
+
+        let listener = Closure::<dyn Fn()>::new(move || {
+            set_visibility.set(VisibilityState::current())
+        })
+        .into_js_value();
+        _ = crate::document().add_event_listener_with_callback(
+            "visibilitychange",
+            listener.unchecked_ref(),
+        );
+        on_cleanup(cx, move || {
+            _ = crate::document().remove_event_listener_with_callback(
+                "visibilitychange",
+                listener.unchecked_ref(),
+            );
+        });
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = set_visibility;
+    }
+
+    visibility.into()
+}
+
+/// Returns a signal that tracks whether the browser is online, as reported
+/// by [`navigator.onLine`](https://developer.mozilla.org/en-US/docs/Web/API/Navigator/onLine)
+/// and updated on the window’s `online` and `offline` events. The listeners
+/// are removed when `cx` is disposed. On the server, this is always `true`.
+///
+/// ```
+/// use leptos::*;
+///
+/// #[component]
+/// fn OfflineBanner(cx: Scope) -> impl IntoView {
+///     let online = use_online(cx);
+///
+///     view! { cx,
+///         <Show when=move || !online.get() fallback=|_| ()>
+///             <p>"You are offline. Changes will be saved when you reconnect."</p>
+///         </Show>
+///     }
+/// }
+/// ```
+pub fn use_online(cx: Scope) -> Signal<bool> {
+    let is_online = || is_server() || window().navigator().on_line();
+    let (online, set_online) = create_signal(cx, is_online());
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        use leptos_reactive::SignalSet;
+
+        let listener =
+            Closure::<dyn Fn()>::new(move || set_online.set(is_online()))
+                .into_js_value();
+        for event in ["online", "offline"] {
+            _ = window().add_event_listener_with_callback(
+                event,
+                listener.unchecked_ref(),
+            );
+        }
+        on_cleanup(cx, move || {
+            for event in ["online", "offline"] {
+                _ = window().remove_event_listener_with_callback(
+                    event,
+                    listener.unchecked_ref(),
+                );
+            }
+        });
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = set_online;
+    }
+
+    online.into()
+}
+
+/// Refetches `resource` every `interval`, for data that should stay fresh
+/// while the page is open, like a dashboard or a notification count.
+///
+/// Polling is suspended while the page is hidden (see
+/// [`use_document_visibility`]) or the browser is offline (see
+/// [`use_online`]), and the resource is refetched immediately when the page
+/// becomes visible and online again. Polling stops when `cx` is disposed.
+/// On the server, this does nothing.
+///
+/// ```
+/// use leptos::*;
+/// use std::time::Duration;
+///
+/// # async fn unread_count() -> usize { 0 }
+/// #[component]
+/// fn Inbox(cx: Scope) -> impl IntoView {
+///     let unread = create_resource(cx, || (), |_| unread_count());
+///     poll_interval(cx, unread, Duration::from_secs(30));
+///
+///     view! { cx,
+///         <Suspense fallback=|| ()>
+///             {move || unread.get().map(|n| view! { cx, <p>{n} " unread"</p> })}
+///         </Suspense>
+///     }
+/// }
+/// ```
+pub fn poll_interval<S, T>(
+    cx: Scope,
+    resource: Resource<S, T>,
+    interval: Duration,
+) where
+    S: Clone + 'static,
+    T: 'static,
+{
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        use leptos_reactive::SignalGet;
+
+        let visibility = use_document_visibility(cx);
+        let online = use_online(cx);
+        let handle = Rc::new(RefCell::new(None::<IntervalHandle>));
+
+        leptos_reactive::create_effect(cx, {
+            let handle = Rc::clone(&handle);
+            move |was_active: Option<bool>| {
+                let active = visibility.get() == VisibilityState::Visible
+                    && online.get();
+                if active {
+                    // catch up on whatever changed while polling was paused
+                    if was_active == Some(false) {
+                        resource.refetch();
+                    }
+                    if handle.borrow().is_none() {
+                        *handle.borrow_mut() = set_interval_with_handle(
+                            move || resource.refetch(),
+                            interval,
+                        )
+                        .ok();
+                    }
+                } else if let Some(handle) = handle.take() {
+                    handle.clear();
+                }
+                active
+            }
+        });
+
+        on_cleanup(cx, move || {
+            if let Some(handle) = handle.take() {
+                handle.clear();
+            }
+        });
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = (cx, resource, interval);
+    }
+}
+
+// size and position changes smaller than this, in CSS pixels, are ignored, so
+// that sub-pixel jitter doesn't rerun everything that depends on them
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+const SUBPIXEL_THRESHOLD: f64 = 0.5;
+
+/// The size of an element, as tracked by [`use_element_size`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SignalSize {
+    /// The width of the element’s content box, in CSS pixels.
+    pub width: Signal<f64>,
+    /// The height of the element’s content box, in CSS pixels.
+    pub height: Signal<f64>,
+}
+
+/// Returns signals that track the size of the element in `target`, using a
+/// [`ResizeObserver`](https://developer.mozilla.org/en-US/docs/Web/API/ResizeObserver).
+///
+/// The element is observed once it has been mounted in the node ref, and
+/// again if the node ref is filled with another element; until then, the
+/// size is zero. Changes of less than half a pixel are ignored. Elements
+/// that are targeted by more than one of these hooks are only observed once,
+/// and the element stops being observed when `cx` is disposed. On the
+/// server, the size is always zero.
+///
+/// ```
+/// use leptos::*;
+///
+/// #[component]
+/// fn Chart(cx: Scope) -> impl IntoView {
+///     let container = create_node_ref::<html::Div>(cx);
+///     let size = use_element_size(cx, container);
+///
+///     view! { cx,
+///         <div node_ref=container class="chart">
+///             <svg
+///                 width=move || size.width.get()
+///                 height=move || size.height.get()
+///             />
+///         </div>
+///     }
+/// }
+/// ```
+pub fn use_element_size<T>(cx: Scope, target: NodeRef<T>) -> SignalSize
+where
+    T: ElementDescriptor + Clone + 'static,
+{
+    let width = create_rw_signal(cx, 0.0);
+    let height = create_rw_signal(cx, 0.0);
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    observe_resizes(cx, target, move |entry| {
+        let rect = entry.content_rect();
+        set_if_moved(width, rect.width());
+        set_if_moved(height, rect.height());
+    });
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = target;
+    }
+
+    SignalSize {
+        width: width.read_only().into(),
+        height: height.read_only().into(),
+    }
+}
+
+/// The position and size of an element relative to the viewport, as tracked
+/// by [`use_element_bounding`].
+///
+/// These are the values returned by
+/// [`getBoundingClientRect()`](https://developer.mozilla.org/en-US/docs/Web/API/Element/getBoundingClientRect),
+/// in CSS pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SignalBounding {
+    /// The x coordinate of the element’s origin.
+    pub x: Signal<f64>,
+    /// The y coordinate of the element’s origin.
+    pub y: Signal<f64>,
+    /// The distance from the top of the viewport to the element’s top edge.
+    pub top: Signal<f64>,
+    /// The distance from the left of the viewport to the element’s right edge.
+    pub right: Signal<f64>,
+    /// The distance from the top of the viewport to the element’s bottom edge.
+    pub bottom: Signal<f64>,
+    /// The distance from the left of the viewport to the element’s left edge.
+    pub left: Signal<f64>,
+    /// The width of the element’s border box.
+    pub width: Signal<f64>,
+    /// The height of the element’s border box.
+    pub height: Signal<f64>,
+}
+
+/// Returns signals that track the position and size of the element in
+/// `target` relative to the viewport.
+///
+/// They are updated when the element is resized, as with
+/// [`use_element_size`], and when the page or any element is scrolled or the
+/// window is resized, at most once per animation frame. Changes of less than
+/// half a pixel are ignored. The listeners are removed when `cx` is disposed.
+/// On the server, every value is always zero.
+///
+/// ```
+/// use leptos::*;
+///
+/// #[component]
+/// fn Tooltip(cx: Scope) -> impl IntoView {
+///     let anchor = create_node_ref::<html::Button>(cx);
+///     let bounding = use_element_bounding(cx, anchor);
+///     let style = move || {
+///         format!(
+///             "position: fixed; top: {}px; left: {}px",
+///             bounding.bottom.get(),
+///             bounding.left.get()
+///         )
+///     };
+///
+///     view! { cx,
+///         <button node_ref=anchor>"Info"</button>
+///         <div class="tooltip" style=style>"More information"</div>
+///     }
+/// }
+/// ```
+pub fn use_element_bounding<T>(cx: Scope, target: NodeRef<T>) -> SignalBounding
+where
+    T: ElementDescriptor + Clone + 'static,
+{
+    let [x, y, top, right, bottom, left, width, height] =
+        [(); 8].map(|_| create_rw_signal(cx, 0.0));
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        let update = move || {
+            let Some(el) = target.get_untracked() else {
+                return;
+            };
+            let rect = el.into_any().get_bounding_client_rect();
+            for (signal, value) in [
+                (x, rect.x()),
+                (y, rect.y()),
+                (top, rect.top()),
+                (right, rect.right()),
+                (bottom, rect.bottom()),
+                (left, rect.left()),
+                (width, rect.width()),
+                (height, rect.height()),
+            ] {
+                set_if_moved(signal, value);
+            }
+        };
+        // measures at most once per animation frame
+        let pending = Rc::new(std::cell::Cell::new(false));
+        let schedule = Rc::new(move || {
+            if !pending.replace(true) {
+                let pending = Rc::clone(&pending);
+                request_animation_frame(move || {
+                    pending.set(false);
+                    update();
+                });
+            }
+        });
+
+        observe_resizes(cx, target, {
+            let schedule = Rc::clone(&schedule);
+            move |_| schedule()
+        });
+
+        // scroll events don't bubble, so they are captured to also see the
+        // ones of the element's scrollable ancestors
+        let listener =
+            Closure::<dyn Fn()>::new(move || schedule()).into_js_value();
+        for event in ["scroll", "resize"] {
+            _ = window().add_event_listener_with_callback_and_bool(
+                event,
+                listener.unchecked_ref(),
+                true,
+            );
+        }
+        on_cleanup(cx, move || {
+            for event in ["scroll", "resize"] {
+                _ = window().remove_event_listener_with_callback_and_bool(
+                    event,
+                    listener.unchecked_ref(),
+                    true,
+                );
+            }
+        });
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = target;
+    }
+
+    SignalBounding {
+        x: x.read_only().into(),
+        y: y.read_only().into(),
+        top: top.read_only().into(),
+        right: right.read_only().into(),
+        bottom: bottom.read_only().into(),
+        left: left.read_only().into(),
+        width: width.read_only().into(),
+        height: height.read_only().into(),
+    }
+}
+
+// a callback may still run after its scope has been disposed, e.g., in an
+// animation frame that was already requested
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn set_if_moved(signal: leptos_reactive::RwSignal<f64>, value: f64) {
+    use leptos_reactive::{SignalGetUntracked, SignalSet};
+
+    let moved = signal
+        .try_get_untracked()
+        .map(|current| (current - value).abs() >= SUBPIXEL_THRESHOLD)
+        .unwrap_or(false);
+    if moved {
+        signal.set(value);
+    }
+}
+
+// calls `callback` whenever the element in `target` is resized, including
+// once when it starts being observed
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn observe_resizes<T>(
+    cx: Scope,
+    target: NodeRef<T>,
+    callback: impl Fn(&web_sys::ResizeObserverEntry) + 'static,
+) where
+    T: ElementDescriptor + Clone + 'static,
+{
+    let callback: Rc<dyn Fn(&web_sys::ResizeObserverEntry)> = Rc::new(callback);
+    let subscription = Rc::new(std::cell::Cell::new(None::<usize>));
+
+    leptos_reactive::create_effect(cx, {
+        let subscription = Rc::clone(&subscription);
+        move |_| {
+            let Some(el) = target.get() else {
+                return;
+            };
+            if let Some(id) = subscription.take() {
+                resize_observer::unobserve(id);
+            }
+            let el: web_sys::Element = (*el.into_any()).clone().into();
+            subscription
+                .set(resize_observer::observe(el, Rc::clone(&callback)));
+        }
+    });
+
+    on_cleanup(cx, move || {
+        if let Some(id) = subscription.take() {
+            resize_observer::unobserve(id);
+        }
+    });
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod resize_observer {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+    use wasm_bindgen::{prelude::Closure, JsCast};
+
+    type Callback = Rc<dyn Fn(&web_sys::ResizeObserverEntry)>;
+
+    struct Observed {
+        element: web_sys::Element,
+        callbacks: Vec<(usize, Callback)>,
+    }
+
+    thread_local! {
+        // one observer for the page, which observes each element only once
+        static OBSERVER: RefCell<Option<web_sys::ResizeObserver>> =
+            RefCell::new(None);
+        static OBSERVED: RefCell<Vec<Observed>> = RefCell::new(Vec::new());
+        static NEXT_ID: Cell<usize> = Cell::new(0);
+    }
+
+    pub(super) fn observe(
+        element: web_sys::Element,
+        callback: Callback,
+    ) -> Option<usize> {
+        let observer = OBSERVER.with(|observer| {
+            let mut observer = observer.borrow_mut();
+            if observer.is_none() {
+                let notify = Closure::<dyn Fn(js_sys::Array)>::new(notify)
+                    .into_js_value();
+                match web_sys::ResizeObserver::new(notify.unchecked_ref()) {
+                    Ok(created) => *observer = Some(created),
+                    Err(e) => crate::error!(
+                        "[use_element_size] could not create the observer: \
+                         {e:?}"
+                    ),
+                }
+            }
+            observer.clone()
+        })?;
+
+        let id = NEXT_ID.with(|next| next.replace(next.get() + 1));
+        OBSERVED.with(|observed| {
+            let mut observed = observed.borrow_mut();
+            match observed.iter_mut().find(|o| o.element == element) {
+                Some(o) => o.callbacks.push((id, callback)),
+                None => observed.push(Observed {
+                    element: element.clone(),
+                    callbacks: vec![(id, callback)],
+                }),
+            }
+        });
+        // observing an element again reports its size again, including to
+        // the callback that was just added
+        observer.observe(&element);
+        Some(id)
+    }
+
+    pub(super) fn unobserve(id: usize) {
+        let unobserved = OBSERVED.with(|observed| {
+            let mut observed = observed.borrow_mut();
+            let index = observed.iter().position(|o| {
+                o.callbacks
+                    .iter()
+                    .any(|(callback_id, _)| *callback_id == id)
+            })?;
+            observed[index]
+                .callbacks
+                .retain(|(callback_id, _)| *callback_id != id);
+            if observed[index].callbacks.is_empty() {
+                Some(observed.remove(index).element)
+            } else {
+                None
+            }
+        });
+        if let Some(element) = unobserved {
+            OBSERVER.with(|observer| {
+                if let Some(observer) = &*observer.borrow() {
+                    observer.unobserve(&element);
+                }
+            });
+        }
+    }
+
+    fn notify(entries: js_sys::Array) {
+        for entry in entries.iter() {
+            let entry = entry.unchecked_into::<web_sys::ResizeObserverEntry>();
+            let target = entry.target();
+            // the callbacks may observe or unobserve elements themselves
+            let callbacks = OBSERVED.with(|observed| {
+                observed
+                    .borrow()
+                    .iter()
+                    .find(|o| o.element == target)
+                    .map(|o| {
+                        o.callbacks
+                            .iter()
+                            .map(|(_, callback)| Rc::clone(callback))
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default()
+            });
+            for callback in callbacks {
+                callback(&entry);
+            }
+        }
+    }
+}
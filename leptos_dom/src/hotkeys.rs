@@ -0,0 +1,373 @@
+use leptos_reactive::{MaybeSignal, Scope};
+use std::{error::Error, fmt, str::FromStr};
+
+/// Calls `callback` when the user presses one of the given key combinations,
+/// for as long as `cx` is alive.
+///
+/// `keys` is a comma-separated list of combinations like `"cmd+k"`,
+/// `"ctrl+shift+p"` or `"escape"`; see [`Hotkey`] for the syntax. Keys typed
+/// into an `<input>`, `<textarea>`, `<select>` or editable element are
+/// ignored unless [`HotkeyOptions::in_inputs`] is set.
+///
+/// If more than one scope registers the same combination, the one registered
+/// most recently wins, and the previous one is used again once it is
+/// disposed. This is usually what you want: a modal opened over a page
+/// handles `Escape` until it is closed. On the server, this does nothing.
+///
+/// ```
+/// use leptos::{hotkeys::*, *};
+///
+/// #[component]
+/// fn Palette(cx: Scope) -> impl IntoView {
+///     let (open, set_open) = create_signal(cx, false);
+///     use_hotkeys(cx, "mod+k", move |_| set_open.set(true));
+///     use_hotkeys_with_options(
+///         cx,
+///         "escape",
+///         HotkeyOptions::default().enabled(open).in_inputs(true),
+///         move |_| set_open.set(false),
+///     );
+///
+///     view! { cx,
+///         <Show when=move || open.get() fallback=|_| ()>
+///             <input placeholder="Search commands"/>
+///         </Show>
+///     }
+/// }
+/// ```
+pub fn use_hotkeys(
+    cx: Scope,
+    keys: &str,
+    callback: impl Fn(web_sys::KeyboardEvent) + 'static,
+) {
+    use_hotkeys_with_options(cx, keys, HotkeyOptions::default(), callback)
+}
+
+/// Calls `callback` when the user presses one of the given key combinations,
+/// with the given [`HotkeyOptions`].
+///
+/// See [`use_hotkeys`] for more details.
+pub fn use_hotkeys_with_options(
+    cx: Scope,
+    keys: &str,
+    options: HotkeyOptions,
+    callback: impl Fn(web_sys::KeyboardEvent) + 'static,
+) {
+    let hotkeys = keys
+        .split(',')
+        .filter_map(|combo| match combo.parse::<Hotkey>() {
+            Ok(hotkey) => Some(hotkey),
+            Err(e) => {
+                crate::error!("[use_hotkeys] {e}");
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    if hotkeys.is_empty() || crate::is_server() {
+        return;
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        let id = registry::register(hotkeys, options, callback);
+        leptos_reactive::on_cleanup(cx, move || registry::unregister(id));
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = (cx, hotkeys, options, callback);
+    }
+}
+
+/// Options for [`use_hotkeys_with_options`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HotkeyOptions {
+    /// Whether the shortcut is active. A disabled shortcut doesn’t hide
+    /// another one registered for the same keys. Defaults to `true`.
+    pub enabled: MaybeSignal<bool>,
+    /// Whether the shortcut also works while the user is typing into an
+    /// `<input>`, `<textarea>`, `<select>` or editable element. Defaults to
+    /// `false`.
+    pub in_inputs: bool,
+    /// Whether the browser’s default action for the keys, like bookmarking
+    /// the page for `ctrl+d`, is prevented. Defaults to `true`.
+    pub prevent_default: bool,
+}
+
+impl Default for HotkeyOptions {
+    fn default() -> Self {
+        Self {
+            enabled: MaybeSignal::Static(true),
+            in_inputs: false,
+            prevent_default: true,
+        }
+    }
+}
+
+impl HotkeyOptions {
+    /// Sets whether the shortcut is active.
+    pub fn enabled(mut self, enabled: impl Into<MaybeSignal<bool>>) -> Self {
+        self.enabled = enabled.into();
+        self
+    }
+
+    /// Sets whether the shortcut also works while the user is typing.
+    pub fn in_inputs(mut self, in_inputs: bool) -> Self {
+        self.in_inputs = in_inputs;
+        self
+    }
+
+    /// Sets whether the browser’s default action for the keys is prevented.
+    pub fn prevent_default(mut self, prevent_default: bool) -> Self {
+        self.prevent_default = prevent_default;
+        self
+    }
+}
+
+/// A key combination, parsed from a string like `"ctrl+shift+p"`.
+///
+/// The combination is a `+`-separated list of modifiers followed by one key,
+/// in any case:
+/// - The modifiers are `ctrl` (or `control`), `alt` (or `option`), `shift`,
+///   and `meta` (or `cmd`, `command`, `super`). `mod` is `cmd` on Apple
+///   platforms and `ctrl` elsewhere.
+/// - The key is the [`key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key)
+///   of the event, like `k`, `/`, `enter` or `arrowdown`. `esc`, `up`,
+///   `down`, `left`, `right`, `space` and `plus` can be used as shorthands.
+///
+/// A combination only matches when exactly its modifiers are pressed, so
+/// `"k"` doesn’t match `ctrl+k`.
+///
+/// ```
+/// use leptos::hotkeys::Hotkey;
+///
+/// let hotkey: Hotkey = "Ctrl+Shift+P".parse().unwrap();
+/// assert_eq!(hotkey.key(), "p");
+/// assert!("ctrl+".parse::<Hotkey>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Hotkey {
+    key: String,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+    primary: bool,
+}
+
+impl Hotkey {
+    /// The key, without modifiers, as it would be reported by the event in
+    /// lowercase.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Whether a key with the given modifiers matches this combination,
+    /// on an Apple platform if `apple` is `true`.
+    #[doc(hidden)]
+    pub fn matches_key(
+        &self,
+        key: &str,
+        [ctrl, alt, shift, meta]: [bool; 4],
+        apple: bool,
+    ) -> bool {
+        let (expected_ctrl, expected_meta) = if apple {
+            (self.ctrl, self.meta || self.primary)
+        } else {
+            (self.ctrl || self.primary, self.meta)
+        };
+        normalize_key(key) == self.key
+            && ctrl == expected_ctrl
+            && alt == self.alt
+            && shift == self.shift
+            && meta == expected_meta
+    }
+}
+
+impl FromStr for Hotkey {
+    type Err = HotkeyParseError;
+
+    fn from_str(combo: &str) -> Result<Self, Self::Err> {
+        let combo = combo.trim();
+        // `+` is the separator, so `ctrl++` ends with the `+` key
+        let (modifiers, key) = match combo.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None => combo.rsplit_once('+').unwrap_or(("", combo)),
+        };
+        if key.trim().is_empty() {
+            return Err(HotkeyParseError::MissingKey(combo.to_string()));
+        }
+
+        let mut hotkey = Hotkey {
+            key: normalize_key(key.trim()),
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+            primary: false,
+        };
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            let flag = match modifier.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => &mut hotkey.ctrl,
+                "alt" | "option" => &mut hotkey.alt,
+                "shift" => &mut hotkey.shift,
+                "meta" | "cmd" | "command" | "super" => &mut hotkey.meta,
+                "mod" => &mut hotkey.primary,
+                _ => {
+                    return Err(HotkeyParseError::UnknownModifier(
+                        modifier.trim().to_string(),
+                    ))
+                }
+            };
+            *flag = true;
+        }
+        Ok(hotkey)
+    }
+}
+
+// the name of a key as it is written in a combination
+fn normalize_key(key: &str) -> String {
+    let key = key.to_lowercase();
+    match key.as_str() {
+        "esc" => "escape".to_string(),
+        "up" | "down" | "left" | "right" => format!("arrow{key}"),
+        " " | "spacebar" => "space".to_string(),
+        "plus" => "+".to_string(),
+        _ => key,
+    }
+}
+
+/// An error that occurred while parsing a [`Hotkey`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HotkeyParseError {
+    /// The combination has no key, e.g., `"ctrl+"`.
+    MissingKey(String),
+    /// A modifier isn’t one of the supported ones.
+    UnknownModifier(String),
+}
+
+impl fmt::Display for HotkeyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HotkeyParseError::MissingKey(combo) => {
+                write!(f, "the key combination {combo:?} has no key")
+            }
+            HotkeyParseError::UnknownModifier(modifier) => {
+                write!(f, "{modifier:?} is not a modifier key")
+            }
+        }
+    }
+}
+
+impl Error for HotkeyParseError {}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod registry {
+    use super::{Hotkey, HotkeyOptions};
+    use leptos_reactive::SignalGetUntracked;
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+    use wasm_bindgen::JsCast;
+
+    struct Binding {
+        id: usize,
+        hotkeys: Vec<Hotkey>,
+        options: HotkeyOptions,
+        callback: Rc<dyn Fn(web_sys::KeyboardEvent)>,
+    }
+
+    thread_local! {
+        // in the order they were registered, so the last match wins
+        static BINDINGS: RefCell<Vec<Binding>> = RefCell::new(Vec::new());
+        static NEXT_ID: Cell<usize> = Cell::new(0);
+        static LISTENING: Cell<bool> = Cell::new(false);
+    }
+
+    pub(super) fn register(
+        hotkeys: Vec<Hotkey>,
+        options: HotkeyOptions,
+        callback: impl Fn(web_sys::KeyboardEvent) + 'static,
+    ) -> usize {
+        if !LISTENING.with(|listening| listening.replace(true)) {
+            crate::helpers::window_event_listener(crate::ev::keydown, dispatch);
+        }
+        let id = NEXT_ID.with(|next| next.replace(next.get() + 1));
+        BINDINGS.with(|bindings| {
+            bindings.borrow_mut().push(Binding {
+                id,
+                hotkeys,
+                options,
+                callback: Rc::new(callback),
+            })
+        });
+        id
+    }
+
+    pub(super) fn unregister(id: usize) {
+        BINDINGS.with(|bindings| {
+            bindings.borrow_mut().retain(|binding| binding.id != id)
+        });
+    }
+
+    fn dispatch(ev: web_sys::KeyboardEvent) {
+        let modifiers =
+            [ev.ctrl_key(), ev.alt_key(), ev.shift_key(), ev.meta_key()];
+        let key = ev.key();
+        let apple = is_apple();
+        let in_input = is_editable(ev.target());
+
+        let matched = BINDINGS.with(|bindings| {
+            bindings
+                .borrow()
+                .iter()
+                .rev()
+                .find(|binding| {
+                    (binding.options.in_inputs || !in_input)
+                        && binding
+                            .hotkeys
+                            .iter()
+                            .any(|h| h.matches_key(&key, modifiers, apple))
+                        && binding
+                            .options
+                            .enabled
+                            .try_get_untracked()
+                            .unwrap_or(false)
+                })
+                .map(|binding| {
+                    (
+                        Rc::clone(&binding.callback),
+                        binding.options.prevent_default,
+                    )
+                })
+        });
+        // the callback may register or unregister shortcuts itself
+        if let Some((callback, prevent_default)) = matched {
+            if prevent_default {
+                ev.prevent_default();
+            }
+            callback(ev);
+        }
+    }
+
+    fn is_editable(target: Option<web_sys::EventTarget>) -> bool {
+        let Some(el) = target
+            .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+        else {
+            return false;
+        };
+        matches!(el.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+            || el.is_content_editable()
+    }
+
+    fn is_apple() -> bool {
+        crate::window()
+            .navigator()
+            .platform()
+            .map(|platform| {
+                platform.starts_with("Mac") || platform.starts_with("iP")
+            })
+            .unwrap_or(false)
+    }
+}
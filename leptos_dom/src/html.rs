@@ -29,7 +29,10 @@ cfg_if! {
     #[doc(hidden)]
     pub fn as_meta_tag<T>(f: impl FnOnce() -> T) -> T {
         IS_META.with(|m| m.set(true));
-        let v = f();
+        // head tags are claimed from the server's HTML by `leptos_meta`,
+        // with their own keys, so they are always created with all of their
+        // attributes; this still uses up their hydration keys
+        let v = HydrationCtx::without_hydrating(f);
         IS_META.with(|m| m.set(false));
         v
     }
@@ -64,8 +67,10 @@ use crate::{
     ev::EventDescriptor,
     hydration::HydrationCtx,
     macro_helpers::{IntoAttribute, IntoClass, IntoProperty, IntoStyle},
-    Element, Fragment, IntoView, NodeRef, Text, View,
+    Element, IntoView, NodeRef, Text, View,
 };
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+use leptos_reactive::Oco;
 use leptos_reactive::Scope;
 use std::{borrow::Cow, fmt};
 
@@ -233,6 +238,12 @@ impl Custom {
                         "element with id {id} not found, ignoring it for \
                          hydration"
                     );
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        hydration_key = %id,
+                        element = %name,
+                        "hydration mismatch: element not found"
+                    );
                 }
 
                 crate::document().create_element(&name).unwrap()
@@ -301,7 +312,7 @@ cfg_if! {
     pub struct HtmlElement<El: ElementDescriptor> {
       pub(crate) cx: Scope,
       pub(crate) element: El,
-      pub(crate) attrs: SmallVec<[(Cow<'static, str>, Cow<'static, str>); 4]>,
+      pub(crate) attrs: SmallVec<[(Cow<'static, str>, Oco<'static, str>); 4]>,
       #[educe(Debug(ignore))]
       pub(crate) children: ElementChildren,
       #[cfg(debug_assertions)]
@@ -476,20 +487,27 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
         {
             let mut this = self;
 
-            this.attrs.push(("id".into(), id));
+            this.attrs.push(("id".into(), id.into()));
 
             this
         }
     }
 
     /// Binds the element reference to [`NodeRef`].
+    ///
+    /// This also accepts an `Option<NodeRef<_>>`, which does nothing if it is `None`. This
+    /// allows a component to take an optional `node_ref` prop and forward it to its root element.
     #[inline(always)]
-    pub fn node_ref(self, node_ref: NodeRef<El>) -> Self
+    pub fn node_ref(self, node_ref: impl Into<Option<NodeRef<El>>>) -> Self
     where
         Self: Clone,
     {
         #[cfg(all(target_arch = "wasm32", feature = "web"))]
-        node_ref.load(&self);
+        if let Some(node_ref) = node_ref.into() {
+            let el: &web_sys::HtmlElement = self.element.as_ref();
+            crate::mark_bound(el);
+            node_ref.load(&self);
+        }
 
         #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
         let _ = node_ref;
@@ -516,6 +534,7 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
 
             let this = self.clone();
             let el = self.element.as_ref().clone();
+            crate::mark_bound(&el);
 
             wasm_bindgen_futures::spawn_local(async move {
                 while !crate::document().body().unwrap().contains(Some(&el)) {
@@ -614,6 +633,7 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
             }
             match attr {
                 Attribute::String(value) => {
+                    let value = crate::ssr::intern_attr_value(this.cx, value);
                     this.attrs.push((name, value));
                 }
                 Attribute::Bool(include) => {
@@ -623,6 +643,13 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
                 }
                 Attribute::Option(_, maybe) => {
                     if let Some(value) = maybe {
+                        let value =
+                            crate::ssr::intern_attr_value(this.cx, value);
+                        this.attrs.push((name, value));
+                    }
+                }
+                Attribute::Style(_) => {
+                    if let Some(value) = attr.as_nameless_value_string() {
                         this.attrs.push((name, value));
                     }
                 }
@@ -633,6 +660,55 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
         }
     }
 
+    /// Sets `value`, `checked`, or `selected` on a form control, in a way
+    /// that keeps it in sync with the control's current state.
+    ///
+    /// As attributes, these only set a control's *initial* state: once the
+    /// user has typed into an `<input>`, changing its `value` attribute no
+    /// longer changes what it shows. So in the browser, a reactive value is
+    /// set as the matching property, which always does; any other value is
+    /// set as an attribute, like [`attr`](HtmlElement::attr). On the server
+    /// it renders an attribute, so the server-rendered form starts out in
+    /// the right state. A `<textarea>` has no `value` attribute, so its value
+    /// is rendered as its text content instead. A `<select>`’s `value` is
+    /// set as a property once the current task is done, after its
+    /// `<option>`s have been added.
+    ///
+    /// The `view` macro uses this for `value`, `checked`, and `selected` on
+    /// `<input>`, `<textarea>`, `<select>`, and `<option>`; write
+    /// `attr:value` to set the attribute instead.
+    #[track_caller]
+    #[cfg_attr(all(target_arch = "wasm32", feature = "web"), inline(always))]
+    pub fn form_attr(
+        self,
+        name: impl Into<Cow<'static, str>>,
+        attr: impl IntoAttribute,
+    ) -> Self {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            form_attr_helper(
+                self.element.as_ref(),
+                name.into(),
+                attr.into_attribute(self.cx),
+            );
+            self
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            let name = name.into();
+            if name == "value" && self.element.name() == "textarea" {
+                let value = attr
+                    .into_attribute(self.cx)
+                    .into_nameless_value_string()
+                    .unwrap_or_default();
+                self.child(value.to_string())
+            } else {
+                self.attr(name, attr)
+            }
+        }
+    }
+
     /// Adds a class to an element.
     ///
     /// **Note**: In the builder syntax, this will be overwritten by the `class`
@@ -679,7 +755,7 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
                 {
                     *value = format!("{value} {name}").into();
                 } else {
-                    this.attrs.push(("class".into(), name));
+                    this.attrs.push(("class".into(), name.into()));
                 }
             }
 
@@ -716,7 +792,9 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
         {
             use smallvec::SmallVec;
 
-            let class_list = self.element.as_ref().class_list();
+            let el: &web_sys::HtmlElement = self.element.as_ref();
+            let class_list = el.class_list();
+            crate::mark_bound(el);
 
             leptos_reactive::create_effect(
                 self.cx,
@@ -914,10 +992,11 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
             let event_name = event.name();
 
             let key = event.event_delegation_key();
-            let event_handler = Box::new(event_handler);
+            let event_handler = event.wrap_handler(Box::new(event_handler));
 
             if E::BUBBLES {
                 add_event_listener(
+                    Some(self.cx),
                     self.element.as_ref(),
                     key,
                     event_name,
@@ -926,6 +1005,7 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
                 );
             } else {
                 add_event_listener_undelegated(
+                    Some(self.cx),
                     self.element.as_ref(),
                     &event_name,
                     event_handler,
@@ -1057,11 +1137,12 @@ impl<El: ElementDescriptor> IntoView for HtmlElement<El> {
     fn into_view(self, _: Scope) -> View {
         #[cfg(all(target_arch = "wasm32", feature = "web"))]
         {
-            View::Element(Element::new(self.element))
+            View::Element(Element::new(self.cx, self.element))
         }
         #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
         {
             let Self {
+                cx,
                 element,
                 mut attrs,
                 children,
@@ -1072,7 +1153,7 @@ impl<El: ElementDescriptor> IntoView for HtmlElement<El> {
 
             let id = *element.hydration_id();
 
-            let mut element = Element::new(element);
+            let mut element = Element::new(cx, element);
 
             if attrs.iter_mut().any(|(name, _)| name == "id") {
                 attrs.push(("leptos-hk".into(), format!("_{id}").into()));
@@ -1093,17 +1174,6 @@ impl<El: ElementDescriptor> IntoView for HtmlElement<El> {
     }
 }
 
-impl<El: ElementDescriptor, const N: usize> IntoView for [HtmlElement<El>; N] {
-    #[cfg_attr(
-        any(debug_assertions, feature = "ssr"),
-        instrument(level = "trace", name = "[HtmlElement; N]", skip_all)
-    )]
-    fn into_view(self, cx: Scope) -> View {
-        Fragment::new(self.into_iter().map(|el| el.into_view(cx)).collect())
-            .into_view(cx)
-    }
-}
-
 /// Creates any custom element, such as `<my-element>`.
 pub fn custom<El: ElementDescriptor>(cx: Scope, el: El) -> HtmlElement<Custom> {
     HtmlElement::new(
@@ -1293,6 +1363,12 @@ fn create_leptos_element(
                 crate::warn!(
                     "element with id {id} not found, ignoring it for hydration"
                 );
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    hydration_key = %id,
+                    element = tag,
+                    "hydration mismatch: element not found"
+                );
             }
 
             clone_element()
@@ -1413,6 +1489,8 @@ generate_html_tags![
   p HtmlParagraphElement,
   /// The `<pre>` HTML element represents preformatted text which is to be presented exactly as written in the HTML file. The text is typically rendered using a non-proportional, or "monospaced, font. Whitespace inside this element is displayed as written.
   pre HtmlPreElement,
+  /// The `<search>` HTML element is a container representing the parts of the document or application with form controls or other content related to performing a search or filtering operation.
+  search HtmlElement,
   /// The `<ul>` HTML element represents an unordered list of items, typically rendered as a bulleted list.
   ul HtmlUListElement,
   // ==========================
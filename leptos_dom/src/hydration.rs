@@ -1,3 +1,5 @@
+use crate::escape::escape_script_json;
+use leptos_reactive::ResourceId;
 use std::{cell::RefCell, fmt::Display};
 
 #[cfg(all(target_arch = "wasm32", feature = "hydrate"))]
@@ -75,8 +77,10 @@ pub(crate) use hydration::*;
 pub struct HydrationKey {
     /// ID of the current key.
     pub id: usize,
-    /// ID of the current fragment.
-    pub fragment: usize,
+    /// ID of the current fragment. It is 64 bits wide on every target, so
+    /// that the keys of the items in a `<For/>`, which are hashes, are the
+    /// same on the server and in the browser.
+    pub fragment: u64,
 }
 
 impl Display for HydrationKey {
@@ -127,6 +131,20 @@ impl HydrationCtx {
         ID.with(|i| *i.borrow_mut() = id);
     }
 
+    /// Turns hydration on, whether or not the `_0-1` marker was found, so
+    /// that a root known to have been rendered on the server claims its
+    /// nodes.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    pub(crate) fn start_hydrating() {
+        #[cfg(feature = "hydrate")]
+        {
+            IS_HYDRATING.with(|is_hydrating| {
+                *is_hydrating.borrow_mut() =
+                    once_cell::unsync::Lazy::new(|| true);
+            })
+        }
+    }
+
     #[cfg(all(target_arch = "wasm32", feature = "web"))]
     pub(crate) fn stop_hydrating() {
         #[cfg(feature = "hydrate")]
@@ -137,6 +155,49 @@ impl HydrationCtx {
         }
     }
 
+    /// Runs `f` with hydration turned off, so that it creates new DOM nodes
+    /// instead of claiming them from the server-sent HTML.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    pub(crate) fn without_hydrating<T>(f: impl FnOnce() -> T) -> T {
+        #[cfg(feature = "hydrate")]
+        if Self::is_hydrating() {
+            let set_hydrating = |hydrating: bool| {
+                IS_HYDRATING.with(|is_hydrating| {
+                    *is_hydrating.borrow_mut() = if hydrating {
+                        once_cell::unsync::Lazy::new(|| true)
+                    } else {
+                        once_cell::unsync::Lazy::new(|| false)
+                    };
+                })
+            };
+            set_hydrating(false);
+            let value = f();
+            set_hydrating(true);
+            return value;
+        }
+        f()
+    }
+
+    /// Runs `f` in hydration mode, continuing from `id`, so that it claims
+    /// nodes that were rendered on the server after the rest of the page
+    /// has been hydrated. The current `id` is restored afterwards.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    pub(crate) fn hydrate_from<T>(
+        id: HydrationKey,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        let was_hydrating = Self::is_hydrating();
+        let previous = Self::peek();
+        Self::continue_from(id);
+        Self::start_hydrating();
+        let value = f();
+        if !was_hydrating {
+            Self::stop_hydrating();
+        }
+        Self::continue_from(previous);
+        value
+    }
+
     /// Whether the UI is currently in the process of hydrating from the server-sent HTML.
     pub fn is_hydrating() -> bool {
         #[cfg(all(target_arch = "wasm32", feature = "hydrate"))]
@@ -162,3 +223,67 @@ impl HydrationCtx {
         }
     }
 }
+
+// The server's side of the handshake in which resource values are streamed
+// to the client, which is in `leptos_reactive`. These are public so that the
+// handshake can be tested against the real scripts.
+
+/// The script that starts the stream, which tells the client which
+/// resources the server will send the values of, and sets up the maps that
+/// the values are handed over in.
+#[doc(hidden)]
+pub fn render_stream_start(
+    nonce_str: &str,
+    pending_resources: &[ResourceId],
+) -> String {
+    let pending_resources = serde_json::to_string(pending_resources).unwrap();
+    let pending_resources = escape_script_json(&pending_resources);
+    format!(
+        "<script{nonce_str}>__LEPTOS_PENDING_RESOURCES = \
+         {pending_resources};__LEPTOS_RESOLVED_RESOURCES = new \
+         Map();__LEPTOS_RESOURCE_RESOLVERS = new \
+         Map();__LEPTOS_STREAM_DONE = false;</script>"
+    )
+}
+
+/// The script that sends the value of a resource, serialized as `json`,
+/// to the resolver that is waiting for it, or stores it until the resource
+/// is hydrated.
+#[doc(hidden)]
+pub fn render_serializer(
+    nonce_str: &str,
+    id: ResourceId,
+    json: &str,
+) -> String {
+    // the ID is the key of the resource in both maps, as a JSON string
+    let id =
+        serde_json::to_string(&serde_json::to_string(&id).unwrap()).unwrap();
+    let id = escape_script_json(&id);
+    // the value is passed to the resolver as a JSON string
+    let json = serde_json::to_string(json).unwrap();
+    let json = escape_script_json(&json);
+    format!(
+        r#"<script{nonce_str}>
+              (function() {{ let id = {id}; let val = {json};
+              let resolve = __LEPTOS_RESOURCE_RESOLVERS.get(id);
+              if(resolve) {{
+                  __LEPTOS_RESOURCE_RESOLVERS.delete(id);
+                  resolve(val);
+              }} else {{
+                  __LEPTOS_RESOLVED_RESOURCES.set(id, val);
+              }} }})();
+          </script>"#,
+    )
+}
+
+/// The script that ends the stream, after the value of every resource has
+/// been sent. Resources that are still waiting for a value are told that it
+/// isn’t coming, so that they load on the client instead.
+#[doc(hidden)]
+pub fn render_stream_done(nonce_str: &str) -> String {
+    format!(
+        "<script{nonce_str}>__LEPTOS_STREAM_DONE = \
+         true;__LEPTOS_RESOURCE_RESOLVERS.forEach((resolve) => \
+         resolve());__LEPTOS_RESOURCE_RESOLVERS.clear();</script>"
+    )
+}
@@ -0,0 +1,264 @@
+//! Deferring the hydration of parts of a server-rendered page, so that the
+//! parts that are interactive right away are hydrated first.
+
+use crate::{html, HydrationCtx, HydrationKey, IntoView, View};
+use leptos_reactive::Scope;
+
+/// When a lazily-hydrated subtree, created with [`lazy_hydrate`], is
+/// hydrated.
+///
+/// Whatever the trigger, a subtree is also hydrated as soon as the user
+/// interacts with it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HydrateOn {
+    /// Once the browser is idle, as reported by
+    /// [`requestIdleCallback`](https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback).
+    #[default]
+    Idle,
+    /// Once any part of the subtree enters the viewport.
+    Visible,
+    /// Only when the user first interacts with the subtree, with a pointer,
+    /// the keyboard or by moving the focus into it.
+    Interaction,
+}
+
+// the element that wraps a lazily-hydrated subtree
+const WRAPPER_TAG: &str = "leptos-lazy-hydrate";
+// the attribute with the hydration key that follows the subtree on the
+// server, from which the rest of the page continues without it
+const END_KEY_ATTR: &str = "data-hk-end";
+
+/// Renders `children` inside a `<leptos-lazy-hydrate>` element, which is
+/// styled with `display: contents` so that it doesn’t affect the layout, and
+/// defers their hydration until the given trigger.
+///
+/// On the server, and when rendered in the browser without hydration, the
+/// children are rendered right away, as usual. When the page is hydrated,
+/// the server-rendered children are left as they are, and their reactive
+/// system is only created, claiming the existing nodes, once the trigger
+/// fires, so nothing flickers.
+///
+/// Any event that reaches the subtree before it is hydrated, like a click,
+/// is held at the wrapper while the subtree is hydrated, and then goes on to
+/// its target, where it is handled by the freshly hydrated listeners.
+///
+/// A lazy subtree inside another one only starts waiting for its own
+/// trigger once the outer one has been hydrated.
+pub fn lazy_hydrate<F, V>(cx: Scope, on: HydrateOn, children: F) -> View
+where
+    F: FnOnce(Scope) -> V + 'static,
+    V: IntoView,
+{
+    let wrapper = html::custom(cx, html::Custom::new(WRAPPER_TAG))
+        .attr("style", "display: contents");
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    if HydrationCtx::is_hydrating() {
+        let start = HydrationCtx::peek();
+        let end = wrapper
+            .get_attribute(END_KEY_ATTR)
+            .and_then(|key| parse_key(&key));
+        // without the end key, the rest of the page can only be hydrated
+        // after the children
+        if let Some(end) = end {
+            client::defer(cx, (*wrapper).clone(), on, start, children);
+            HydrationCtx::continue_from(end);
+            return wrapper.into_view(cx);
+        }
+    }
+    _ = on;
+
+    let (children, _) = cx.run_child_scope(|cx| children(cx).into_view(cx));
+    let wrapper = if crate::is_server() {
+        let end = HydrationCtx::peek();
+        wrapper.attr(END_KEY_ATTR, format!("{}-{}", end.fragment, end.id))
+    } else {
+        wrapper
+    };
+    wrapper.child(children).into_view(cx)
+}
+
+#[allow(dead_code)] // only used when hydrating
+fn parse_key(key: &str) -> Option<HydrationKey> {
+    let (fragment, id) = key.split_once('-')?;
+    Some(HydrationKey {
+        fragment: fragment.parse().ok()?,
+        id: id.parse().ok()?,
+    })
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod client {
+    use super::HydrateOn;
+    use crate::{
+        helpers::{request_idle_callback_with_handle, set_timeout_with_handle},
+        HydrationCtx, HydrationKey, IntoView, View,
+    };
+    use leptos_reactive::{on_cleanup, Scope};
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+    use wasm_bindgen::{prelude::Closure, JsCast};
+
+    // the events that hydrate a subtree before they reach their target
+    const INTERACTION_EVENTS: [&str; 7] = [
+        "pointerdown",
+        "keydown",
+        "focusin",
+        "click",
+        "input",
+        "change",
+        "submit",
+    ];
+
+    #[derive(Default)]
+    struct Pending {
+        hydrate: Option<Box<dyn FnOnce() -> View>>,
+        // removes the listeners, observers and callbacks that wait for the
+        // trigger
+        stop_waiting: Vec<Box<dyn FnOnce()>>,
+        // kept so that its scopes aren’t disposed until `cx` is
+        view: Option<View>,
+    }
+
+    type Shared = Rc<RefCell<Pending>>;
+
+    pub(super) fn defer<F, V>(
+        cx: Scope,
+        wrapper: web_sys::HtmlElement,
+        on: HydrateOn,
+        start: HydrationKey,
+        children: F,
+    ) where
+        F: FnOnce(Scope) -> V + 'static,
+        V: IntoView,
+    {
+        let pending = Shared::default();
+        pending.borrow_mut().hydrate = Some(Box::new(move || {
+            HydrationCtx::hydrate_from(start, || {
+                cx.run_child_scope(|cx| {
+                    cx.untrack(|| children(cx).into_view(cx))
+                })
+                .0
+            })
+        }));
+
+        // the capture phase reaches the wrapper before the event’s target,
+        // whose listeners are attached while the subtree is hydrated
+        for event in INTERACTION_EVENTS {
+            let listener = Closure::<dyn Fn(web_sys::Event)>::new({
+                let pending = Rc::clone(&pending);
+                move |_| hydrate(&pending)
+            })
+            .into_js_value();
+            _ = wrapper.add_event_listener_with_callback_and_bool(
+                event,
+                listener.unchecked_ref(),
+                true,
+            );
+            let wrapper = wrapper.clone();
+            stop_waiting(&pending, move || {
+                _ = wrapper.remove_event_listener_with_callback_and_bool(
+                    event,
+                    listener.unchecked_ref(),
+                    true,
+                );
+            });
+        }
+
+        match on {
+            HydrateOn::Idle => when_idle(&pending),
+            HydrateOn::Visible => when_visible(&pending, &wrapper),
+            HydrateOn::Interaction => {}
+        }
+
+        on_cleanup(cx, move || {
+            let (stop, view) = {
+                let mut pending = pending.borrow_mut();
+                pending.hydrate = None;
+                (
+                    std::mem::take(&mut pending.stop_waiting),
+                    pending.view.take(),
+                )
+            };
+            stop.into_iter().for_each(|stop| stop());
+            drop(view);
+        });
+    }
+
+    fn stop_waiting(pending: &Shared, stop: impl FnOnce() + 'static) {
+        pending.borrow_mut().stop_waiting.push(Box::new(stop));
+    }
+
+    fn hydrate(pending: &Shared) {
+        let (hydrate, stop) = {
+            let mut pending = pending.borrow_mut();
+            (
+                pending.hydrate.take(),
+                std::mem::take(&mut pending.stop_waiting),
+            )
+        };
+        stop.into_iter().for_each(|stop| stop());
+        if let Some(hydrate) = hydrate {
+            let view = hydrate();
+            pending.borrow_mut().view = Some(view);
+        }
+    }
+
+    fn when_idle(pending: &Shared) {
+        let callback = {
+            let pending = Rc::clone(pending);
+            move || hydrate(&pending)
+        };
+        match request_idle_callback_with_handle(callback.clone()) {
+            Ok(handle) => stop_waiting(pending, move || handle.cancel()),
+            // e.g., Safari doesn’t support `requestIdleCallback`
+            Err(_) => {
+                if let Ok(handle) = set_timeout_with_handle(
+                    callback,
+                    Duration::from_millis(200),
+                ) {
+                    stop_waiting(pending, move || handle.clear());
+                }
+            }
+        }
+    }
+
+    fn when_visible(pending: &Shared, wrapper: &web_sys::HtmlElement) {
+        // the wrapper has no box of its own, so its children are observed
+        let children = wrapper.children();
+        if children.length() == 0 {
+            return when_idle(pending);
+        }
+
+        let callback = Closure::<dyn Fn(js_sys::Array)>::new({
+            let pending = Rc::clone(pending);
+            move |entries: js_sys::Array| {
+                let visible = entries.iter().any(|entry| {
+                    entry
+                        .unchecked_into::<web_sys::IntersectionObserverEntry>()
+                        .is_intersecting()
+                });
+                if visible {
+                    hydrate(&pending);
+                }
+            }
+        })
+        .into_js_value();
+        let observer = match web_sys::IntersectionObserver::new(
+            callback.unchecked_ref(),
+        ) {
+            Ok(observer) => observer,
+            Err(e) => {
+                crate::error!(
+                    "[lazy_hydrate] could not create the observer: {e:?}"
+                );
+                return when_idle(pending);
+            }
+        };
+        for index in 0..children.length() {
+            if let Some(child) = children.item(index) {
+                observer.observe(&child);
+            }
+        }
+        stop_waiting(pending, move || observer.disconnect());
+    }
+}
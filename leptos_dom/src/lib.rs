@@ -10,16 +10,23 @@
 pub extern crate tracing;
 
 mod components;
+/// Utilities for reading and writing cookies on both the server and the client.
+pub mod cookie;
+pub mod escape;
 mod events;
 pub mod helpers;
+/// Keyboard shortcuts that are scoped to the components that register them.
+pub mod hotkeys;
 pub mod html;
 mod hydration;
+pub mod lazy_hydration;
 mod logging;
 mod macro_helpers;
 pub mod math;
 mod node_ref;
 /// Utilities for exporting nonces to be used for a Content Security Policy.
 pub mod nonce;
+mod panic_overlay;
 pub mod ssr;
 pub mod ssr_in_order;
 pub mod svg;
@@ -28,13 +35,17 @@ use cfg_if::cfg_if;
 pub use components::*;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub use events::add_event_helper;
+#[cfg(debug_assertions)]
+pub use events::live_event_handlers;
 pub use events::typed as ev;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use events::{add_event_listener, add_event_listener_undelegated};
 pub use html::HtmlElement;
 use html::{AnyElement, ElementDescriptor};
 pub use hydration::{HydrationCtx, HydrationKey};
-use leptos_reactive::Scope;
+#[doc(hidden)]
+pub use hydration::{render_serializer, render_stream_done, render_stream_start};
+use leptos_reactive::{Scope, ScopeDisposer};
 #[cfg(not(feature = "nightly"))]
 use leptos_reactive::{
     MaybeProp, MaybeSignal, Memo, ReadSignal, RwSignal, Signal, SignalGet,
@@ -44,6 +55,7 @@ pub use macro_helpers::*;
 pub use node_ref::*;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use once_cell::unsync::Lazy as LazyCell;
+pub use panic_overlay::set_dev_overlay;
 #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
 use smallvec::SmallVec;
 use std::{borrow::Cow, fmt};
@@ -53,6 +65,7 @@ pub use transparent::*;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use wasm_bindgen::JsCast;
 use wasm_bindgen::UnwrapThrowExt;
+pub use {js_sys, wasm_bindgen, web_sys};
 
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 thread_local! {
@@ -254,6 +267,8 @@ cfg_if! {
       pub name: Cow<'static, str>,
       #[doc(hidden)]
       pub element: web_sys::HtmlElement,
+      /// The scope that created the element, which owns its event handlers.
+      cx: Scope,
       #[cfg(debug_assertions)]
       /// Optional marker for the view macro source of the element.
       pub view_marker: Option<String>
@@ -274,7 +289,7 @@ cfg_if! {
     pub struct Element {
       name: Cow<'static, str>,
       is_void: bool,
-      attrs: SmallVec<[(Cow<'static, str>, Cow<'static, str>); 4]>,
+      attrs: SmallVec<[(Cow<'static, str>, leptos_reactive::Oco<'static, str>); 4]>,
       children: ElementChildren,
       id: HydrationKey,
       #[cfg(debug_assertions)]
@@ -379,18 +394,21 @@ impl IntoView for Element {
 
 impl Element {
     #[track_caller]
-    fn new<El: ElementDescriptor>(el: El) -> Self {
+    fn new<El: ElementDescriptor>(cx: Scope, el: El) -> Self {
         cfg_if! {
           if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
               Self {
                 #[cfg(debug_assertions)]
                 name: el.name(),
                 element: el.as_ref().clone(),
+                cx,
                 #[cfg(debug_assertions)]
                 view_marker: None
               }
           }
           else {
+            _ = cx;
+
             Self {
               name: el.name(),
               is_void: el.is_void(),
@@ -458,6 +476,12 @@ impl Comment {
                             "component with id {id} not found, ignoring it for \
                              hydration"
                         );
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(
+                            hydration_key = %id,
+                            component = %content,
+                            "hydration mismatch: component not found"
+                        );
                     }
                 }
 
@@ -566,13 +590,14 @@ impl IntoView for &View {
     }
 }
 
-impl<const N: usize> IntoView for [View; N] {
+impl<V: IntoView, const N: usize> IntoView for [V; N] {
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "info", name = "[Node; N]", skip_all)
     )]
     fn into_view(self, cx: Scope) -> View {
-        Fragment::new(self.into_iter().collect()).into_view(cx)
+        Fragment::new(self.into_iter().map(|v| v.into_view(cx)).collect())
+            .into_view(cx)
     }
 }
 
@@ -742,10 +767,12 @@ impl View {
           if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
             match &self {
               Self::Element(el) => {
+                let event_handler = event.wrap_handler(event_handler);
                 if E::BUBBLES {
-                  add_event_listener(&el.element, event.event_delegation_key(), event.name(), event_handler, &None);
+                  add_event_listener(Some(el.cx), &el.element, event.event_delegation_key(), event.name(), event_handler, &None);
                 } else {
                   add_event_listener_undelegated(
+                    Some(el.cx),
                     &el.element,
                     &event.name(),
                     event_handler,
@@ -873,7 +900,9 @@ where
 
     cfg_if! {
       if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-        mount_to(crate::document().body().expect("body element to exist"), f)
+        let disposer =
+          mount_to(crate::document().body().expect("body element to exist"), f);
+        std::mem::forget(disposer);
       } else {
         _ = f;
         crate::warn!("`mount_to_body` should not be called outside the browser.");
@@ -881,36 +910,129 @@ where
     }
 }
 
-/// Runs the provided closure and mounts the result to the provided element.
-pub fn mount_to<F, N>(parent: web_sys::HtmlElement, f: F)
+/// Runs the provided closure and mounts the result to the provided element,
+/// after any children it already has.
+///
+/// Each call creates its own root [`Scope`], so several independent roots
+/// can be mounted on the same page. Dropping the returned [`ScopeDisposer`]
+/// leaves the root mounted; calling [`dispose`](ScopeDisposer::dispose) on
+/// it removes the root’s nodes from the element, runs its
+/// [`on_cleanup`](leptos_reactive::on_cleanup) functions and removes its
+/// event handlers. Other roots keep working, as the event listeners that
+/// are shared between roots are only registered once and never removed.
+///
+/// ```ignore
+/// let widget = document().get_element_by_id("widget").unwrap();
+/// let disposer = mount_to(widget.unchecked_into(), |cx| {
+///     view! { cx, <Counter/> }
+/// });
+/// // later...
+/// disposer.dispose();
+/// ```
+pub fn mount_to<F, N>(parent: web_sys::HtmlElement, f: F) -> ScopeDisposer
 where
     F: FnOnce(Scope) -> N + 'static,
     N: IntoView,
 {
     cfg_if! {
       if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-        let disposer = leptos_reactive::create_scope(
-          leptos_reactive::create_runtime(),
-          move |cx| {
-            let node = f(cx).into_view(cx);
-
-            HydrationCtx::stop_hydrating();
-
-            parent.append_child(&node.get_mountable_node()).unwrap();
-
-            std::mem::forget(node);
-          },
-        );
-
-        std::mem::forget(disposer);
+        mount_root(parent, f)
       } else {
         _ = parent;
         _ = f;
         crate::warn!("`mount_to` should not be called outside the browser.");
+        unmounted_root()
+      }
+    }
+}
+
+/// Runs the provided closure and hydrates the result from the server-rendered
+/// HTML inside the provided element.
+///
+/// This is like [`mount_to`], but for an element whose contents were
+/// rendered by Leptos on the server, for example to make one part of an
+/// otherwise static page interactive. The returned [`ScopeDisposer`]
+/// unmounts the root in the same way.
+///
+/// Hydration keys are matched in order, so if several roots on the page are
+/// hydrated, they should be hydrated in the same order they were rendered
+/// in on the server, without resetting the hydration key in between.
+pub fn hydrate_to<F, N>(parent: web_sys::HtmlElement, f: F) -> ScopeDisposer
+where
+    F: FnOnce(Scope) -> N + 'static,
+    N: IntoView,
+{
+    cfg_if! {
+      if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+        HydrationCtx::start_hydrating();
+        mount_root(parent, f)
+      } else {
+        _ = parent;
+        _ = f;
+        crate::warn!("`hydrate_to` should not be called outside the browser.");
+        unmounted_root()
       }
     }
 }
 
+// the disposer returned when there is nothing to mount to, whose runtime has
+// already been disposed of
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+fn unmounted_root() -> ScopeDisposer {
+    let runtime = leptos_reactive::create_runtime();
+    let disposer = leptos_reactive::create_scope(runtime, |_| {});
+    runtime.dispose();
+    disposer
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn mount_root<F, N>(parent: web_sys::HtmlElement, f: F) -> ScopeDisposer
+where
+    F: FnOnce(Scope) -> N + 'static,
+    N: IntoView,
+{
+    #[cfg(all(feature = "panic-overlay", debug_assertions))]
+    panic_overlay::install();
+
+    leptos_reactive::create_scope(
+      leptos_reactive::create_runtime(),
+      move |cx| {
+        let hydrating = HydrationCtx::is_hydrating();
+        let node = f(cx).into_view(cx);
+
+        HydrationCtx::stop_hydrating();
+
+        // a hydrated root owns everything in `parent`; a new one owns
+        // whatever comes after the children `parent` already had
+        let previous_last = parent.last_child();
+        parent.append_child(&node.get_mountable_node()).unwrap();
+        let first = match previous_last {
+          Some(last) if !hydrating => last.next_sibling(),
+          _ => parent.first_child(),
+        };
+        let last = parent.last_child();
+
+        leptos_reactive::on_cleanup(cx, move || {
+          if let (Some(first), Some(last)) = (first, last) {
+            if let Ok(range) = crate::document().create_range() {
+              if range.set_start_before(&first).is_ok()
+                && range.set_end_after(&last).is_ok()
+              {
+                _ = range.delete_contents();
+              }
+            }
+          }
+          // the runtime belongs to this root, so it goes with it, once
+          // the root's scope has finished being disposed of
+          let runtime = cx.runtime;
+          leptos_reactive::queue_microtask(move || runtime.dispose());
+        });
+
+        std::mem::forget(node);
+      },
+    )
+}
+
 thread_local! {
     pub(crate) static WINDOW: web_sys::Window = web_sys::window().unwrap_throw();
 
@@ -1,7 +1,10 @@
-use leptos_reactive::Scope;
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+use super::into_style::style_expression;
+use super::{IntoStyle, Style};
+use leptos_reactive::{Oco, Scope};
 use std::{borrow::Cow, rc::Rc};
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
-use wasm_bindgen::UnwrapThrowExt;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
 /// Represents the different possible values an attribute node could have.
 ///
@@ -11,13 +14,18 @@ use wasm_bindgen::UnwrapThrowExt;
 #[derive(Clone)]
 pub enum Attribute {
     /// A plain string value.
-    String(Cow<'static, str>),
+    String(Oco<'static, str>),
     /// A (presumably reactive) function, which will be run inside an effect to do targeted updates to the attribute.
     Fn(Scope, Rc<dyn Fn() -> Attribute>),
     /// An optional string value, which sets the attribute to the value if `Some` and removes the attribute if `None`.
-    Option(Scope, Option<Cow<'static, str>>),
-    /// A boolean attribute, which sets the attribute if `true` and removes the attribute if `false`.
+    Option(Scope, Option<Oco<'static, str>>),
+    /// A boolean attribute, which sets the attribute with no value if `true` and removes the
+    /// attribute if `false`. Browsers treat any value of a boolean attribute like `disabled`,
+    /// even `"false"`, as `true`, so `false` must leave it out entirely.
     Bool(bool),
+    /// A list of style properties and their values. As the `style` attribute, each property
+    /// is set individually, and properties that are missing from the next value are removed.
+    Style(Vec<(Oco<'static, str>, Oco<'static, str>)>),
 }
 
 impl Attribute {
@@ -45,12 +53,15 @@ impl Attribute {
             Attribute::Bool(include) => {
                 Cow::Borrowed(if *include { attr_name } else { "" })
             }
+            Attribute::Style(props) => {
+                format!("{attr_name}=\"{}\"", style_string(props)).into()
+            }
         }
     }
 
     /// Converts the attribute to its HTML value at that moment, not including
     /// the attribute name, so it can be rendered on the server.
-    pub fn as_nameless_value_string(&self) -> Option<Cow<'static, str>> {
+    pub fn as_nameless_value_string(&self) -> Option<Oco<'static, str>> {
         match self {
             Attribute::String(value) => Some(value.clone()),
             Attribute::Fn(_, f) => {
@@ -68,6 +79,18 @@ impl Attribute {
                     None
                 }
             }
+            Attribute::Style(props) => Some(style_string(props).into()),
+        }
+    }
+
+    /// Converts the attribute to its HTML value, like
+    /// [`as_nameless_value_string`](Self::as_nameless_value_string), but
+    /// without copying a string value.
+    pub fn into_nameless_value_string(self) -> Option<Oco<'static, str>> {
+        match self {
+            Attribute::String(value) => Some(value),
+            Attribute::Option(_, value) => value,
+            other => other.as_nameless_value_string(),
         }
     }
 }
@@ -79,6 +102,7 @@ impl PartialEq for Attribute {
             (Self::Fn(_, _), Self::Fn(_, _)) => false,
             (Self::Option(_, l0), Self::Option(_, r0)) => l0 == r0,
             (Self::Bool(l0), Self::Bool(r0)) => l0 == r0,
+            (Self::Style(l0), Self::Style(r0)) => l0 == r0,
             _ => false,
         }
     }
@@ -93,6 +117,7 @@ impl std::fmt::Debug for Attribute {
                 f.debug_tuple("Option").field(arg0).finish()
             }
             Self::Bool(arg0) => f.debug_tuple("Bool").field(arg0).finish(),
+            Self::Style(arg0) => f.debug_tuple("Style").field(arg0).finish(),
         }
     }
 }
@@ -144,33 +169,51 @@ impl IntoAttribute for Option<Attribute> {
     impl_into_attr_boxed! {}
 }
 
-impl IntoAttribute for String {
+impl IntoAttribute for Oco<'static, str> {
     #[inline(always)]
     fn into_attribute(self, _: Scope) -> Attribute {
-        Attribute::String(Cow::Owned(self))
+        Attribute::String(self)
     }
 
     impl_into_attr_boxed! {}
 }
 
-impl IntoAttribute for &'static str {
+impl IntoAttribute for Option<Oco<'static, str>> {
     #[inline(always)]
-    fn into_attribute(self, _: Scope) -> Attribute {
-        Attribute::String(Cow::Borrowed(self))
+    fn into_attribute(self, cx: Scope) -> Attribute {
+        Attribute::Option(cx, self)
     }
 
     impl_into_attr_boxed! {}
 }
 
-impl IntoAttribute for Cow<'static, str> {
-    #[inline(always)]
-    fn into_attribute(self, _: Scope) -> Attribute {
-        Attribute::String(self)
-    }
+macro_rules! string_attr_type {
+    ($attr_type:ty) => {
+        impl IntoAttribute for $attr_type {
+            #[inline(always)]
+            fn into_attribute(self, _: Scope) -> Attribute {
+                Attribute::String(self.into())
+            }
 
-    impl_into_attr_boxed! {}
+            impl_into_attr_boxed! {}
+        }
+
+        impl IntoAttribute for Option<$attr_type> {
+            #[inline(always)]
+            fn into_attribute(self, cx: Scope) -> Attribute {
+                Attribute::Option(cx, self.map(Into::into))
+            }
+
+            impl_into_attr_boxed! {}
+        }
+    };
 }
 
+string_attr_type!(String);
+string_attr_type!(&'static str);
+string_attr_type!(Cow<'static, str>);
+string_attr_type!(Rc<str>);
+
 impl IntoAttribute for bool {
     #[inline(always)]
     fn into_attribute(self, _: Scope) -> Attribute {
@@ -180,31 +223,117 @@ impl IntoAttribute for bool {
     impl_into_attr_boxed! {}
 }
 
-impl IntoAttribute for Option<String> {
+// `None` leaves the attribute out, like `false`
+impl IntoAttribute for Option<bool> {
     #[inline(always)]
-    fn into_attribute(self, cx: Scope) -> Attribute {
-        Attribute::Option(cx, self.map(Cow::Owned))
+    fn into_attribute(self, _: Scope) -> Attribute {
+        Attribute::Bool(self.unwrap_or(false))
     }
 
     impl_into_attr_boxed! {}
 }
 
-impl IntoAttribute for Option<&'static str> {
+/// A list of style properties and their values, which the `style` attribute
+/// accepts in place of a string.
+pub trait IntoStyleMap {
+    /// Converts the list into an [`Attribute::Style`].
+    fn into_style_map(self, cx: Scope) -> Attribute;
+}
+
+impl<K, V, const N: usize> IntoStyleMap for [(K, V); N]
+where
+    K: Into<Oco<'static, str>>,
+    V: IntoStyle,
+{
     #[inline(always)]
-    fn into_attribute(self, cx: Scope) -> Attribute {
-        Attribute::Option(cx, self.map(Cow::Borrowed))
+    fn into_style_map(self, cx: Scope) -> Attribute {
+        style_map(cx, self)
     }
-
-    impl_into_attr_boxed! {}
 }
 
-impl IntoAttribute for Option<Cow<'static, str>> {
+impl<K, V> IntoStyleMap for Vec<(K, V)>
+where
+    K: Into<Oco<'static, str>>,
+    V: IntoStyle,
+{
     #[inline(always)]
-    fn into_attribute(self, cx: Scope) -> Attribute {
-        Attribute::Option(cx, self)
+    fn into_style_map(self, cx: Scope) -> Attribute {
+        style_map(cx, self)
     }
+}
 
-    impl_into_attr_boxed! {}
+impl<T, U> IntoStyleMap for T
+where
+    T: Fn() -> U + 'static,
+    U: IntoStyleMap,
+{
+    fn into_style_map(self, cx: Scope) -> Attribute {
+        let modified_fn = Rc::new(move || (self)().into_style_map(cx));
+        Attribute::Fn(cx, modified_fn)
+    }
+}
+
+// Lets the `view` macro convert a `style` value with `IntoStyleMap` if it is
+// a list of properties, and with `IntoAttribute` otherwise.
+// Implementation based on spez, like `tracing_property`
+#[doc(hidden)]
+pub struct StyleAttributeValue<T>(pub std::cell::Cell<Option<T>>);
+
+#[doc(hidden)]
+pub trait StyleMapMatch {
+    fn to_style_attribute(&self, cx: Scope) -> Attribute;
+}
+
+impl<T: IntoStyleMap> StyleMapMatch for &StyleAttributeValue<T> {
+    fn to_style_attribute(&self, cx: Scope) -> Attribute {
+        self.0.take().unwrap().into_style_map(cx)
+    }
+}
+
+#[doc(hidden)]
+pub trait StyleAttributeMatch {
+    fn to_style_attribute(&self, cx: Scope) -> Attribute;
+}
+
+impl<T: IntoAttribute> StyleAttributeMatch for StyleAttributeValue<T> {
+    fn to_style_attribute(&self, cx: Scope) -> Attribute {
+        self.0.take().unwrap().into_attribute(cx)
+    }
+}
+
+// properties whose value is `None` are left out, so they are removed
+fn style_map<K, V>(
+    cx: Scope,
+    props: impl IntoIterator<Item = (K, V)>,
+) -> Attribute
+where
+    K: Into<Oco<'static, str>>,
+    V: IntoStyle,
+{
+    let props = props
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let mut value = value.into_style(cx);
+            while let Style::Fn(_, f) = value {
+                value = f();
+            }
+            let value = match value {
+                Style::Value(value) => Some(value),
+                Style::Option(value) => value,
+                Style::Fn(_, _) => unreachable!(),
+            };
+            value.map(|value| (name.into(), value))
+        })
+        .collect();
+    Attribute::Style(props)
+}
+
+fn style_string(props: &[(Oco<'static, str>, Oco<'static, str>)]) -> String {
+    props
+        .iter()
+        .map(|(name, value)| format!("{name}: {value}"))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 impl<T, U> IntoAttribute for T
@@ -292,6 +421,40 @@ attr_type!(f32);
 attr_type!(f64);
 attr_type!(char);
 
+#[cfg(not(feature = "nightly"))]
+macro_rules! signal_attr_type {
+    ($signal_type:ty, $value_type:ty) => {
+        impl<T> IntoAttribute for $signal_type
+        where
+            T: Clone,
+            $value_type: IntoAttribute,
+        {
+            fn into_attribute(self, cx: Scope) -> Attribute {
+                use leptos_reactive::SignalGet;
+
+                let modified_fn =
+                    Rc::new(move || self.get().into_attribute(cx));
+                Attribute::Fn(cx, modified_fn)
+            }
+
+            impl_into_attr_boxed! {}
+        }
+    };
+}
+
+#[cfg(not(feature = "nightly"))]
+signal_attr_type!(leptos_reactive::ReadSignal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_attr_type!(leptos_reactive::RwSignal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_attr_type!(leptos_reactive::Memo<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_attr_type!(leptos_reactive::Signal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_attr_type!(leptos_reactive::MaybeSignal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_attr_type!(leptos_reactive::MaybeProp<T>, Option<T>);
+
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 #[doc(hidden)]
 #[inline(never)]
@@ -300,13 +463,23 @@ pub fn attribute_helper(
     name: Cow<'static, str>,
     value: Attribute,
 ) {
+    use crate::HydrationCtx;
     use leptos_reactive::create_render_effect;
     match value {
         Attribute::Fn(cx, f) => {
+            crate::mark_bound(el);
             let el = el.clone();
             create_render_effect(cx, move |old| {
                 let new = f();
-                if old.as_ref() != Some(&new) {
+                // while hydrating, the server has already rendered the first
+                // value, so it is only written if the server's disagrees
+                let claimed = old.is_none()
+                    && HydrationCtx::is_hydrating()
+                    && attribute_matches(&el, &name, &new);
+                if old.as_ref() != Some(&new) && !claimed {
+                    if name == "style" {
+                        remove_stale_styles(&el, old.as_ref(), &new);
+                    }
                     attribute_expression(&el, &name, new.clone(), true);
                 }
                 new
@@ -316,6 +489,102 @@ pub fn attribute_helper(
     };
 }
 
+/// Sets `value`, `checked`, or `selected` on a form control. A reactive value
+/// is set as a property, which reflects the control's current state; any
+/// other value is set as an attribute, which sets its initial state.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[doc(hidden)]
+#[inline(never)]
+pub fn form_attr_helper(
+    el: &web_sys::Element,
+    name: Cow<'static, str>,
+    value: Attribute,
+) {
+    use crate::HydrationCtx;
+    use leptos_reactive::create_render_effect;
+
+    match value {
+        Attribute::Fn(cx, f) => {
+            crate::mark_bound(el);
+            let el = el.clone();
+            create_render_effect(cx, move |_| {
+                let mut new = f();
+                while let Attribute::Fn(_, f) = new {
+                    new = f();
+                }
+                form_property_expression(&el, &name, &new);
+            });
+        }
+        // a `<textarea>`'s initial value is its text content, which the
+        // server has already rendered
+        _ if name == "value"
+            && el.tag_name().eq_ignore_ascii_case("textarea") =>
+        {
+            if !HydrationCtx::is_hydrating() {
+                el.set_text_content(
+                    value.as_nameless_value_string().as_deref(),
+                );
+            }
+        }
+        // a `<select>` has no attribute that sets its value
+        _ if name == "value"
+            && el.tag_name().eq_ignore_ascii_case("select") =>
+        {
+            form_property_expression(el, &name, &value)
+        }
+        _ => attribute_expression(el, &name, value, false),
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[inline(never)]
+fn form_property_expression(
+    el: &web_sys::Element,
+    prop_name: &str,
+    value: &Attribute,
+) {
+    use wasm_bindgen::JsValue;
+
+    let value = value.as_nameless_value_string();
+    let value = if prop_name == "value" {
+        JsValue::from_str(value.as_deref().unwrap_or_default())
+    } else {
+        // like the attributes, `checked` and `selected` are set by any value
+        JsValue::from_bool(value.is_some())
+    };
+    let prop_name = JsValue::from_str(wasm_bindgen::intern(prop_name));
+    let set = {
+        let el = el.clone();
+        move || {
+            // only set changed values, so that an `<input>` that is already
+            // showing the value doesn't lose its cursor position
+            let current = js_sys::Reflect::get(&el, &prop_name);
+            if current.as_ref() != Ok(&value) {
+                js_sys::Reflect::set(&el, &prop_name, &value).unwrap_throw();
+            }
+        }
+    };
+
+    // a `<select>` can only take a value that matches one of its `<option>`s,
+    // which are added after its attributes are set
+    if el.tag_name().eq_ignore_ascii_case("select") {
+        leptos_reactive::queue_microtask(set);
+    } else {
+        set();
+    }
+}
+
+// interns a `&'static str` value, which is likely to be set on many
+// elements, so that it is only copied into JS once; owned values are passed
+// as they are, as interning them would keep each one around forever
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn intern_static<'a>(value: &'a Oco<'static, str>) -> &'a str {
+    match value {
+        Oco::Borrowed(value) => wasm_bindgen::intern(value),
+        _ => value,
+    }
+}
+
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 #[inline(never)]
 pub(crate) fn attribute_expression(
@@ -329,7 +598,7 @@ pub(crate) fn attribute_expression(
     if force || !HydrationCtx::is_hydrating() {
         match value {
             Attribute::String(value) => {
-                let value = wasm_bindgen::intern(&value);
+                let value = intern_static(&value);
                 if attr_name == "inner_html" {
                     el.set_inner_html(value);
                 } else {
@@ -344,7 +613,7 @@ pub(crate) fn attribute_expression(
                     let attr_name = wasm_bindgen::intern(attr_name);
                     match value {
                         Some(value) => {
-                            let value = wasm_bindgen::intern(&value);
+                            let value = intern_static(&value);
                             el.set_attribute(attr_name, value).unwrap_throw();
                         }
                         None => el.remove_attribute(attr_name).unwrap_throw(),
@@ -353,13 +622,83 @@ pub(crate) fn attribute_expression(
             }
             Attribute::Bool(value) => {
                 let attr_name = wasm_bindgen::intern(attr_name);
-                if value {
-                    el.set_attribute(attr_name, attr_name).unwrap_throw();
+                el.toggle_attribute_with_force(attr_name, value)
+                    .unwrap_throw();
+            }
+            Attribute::Style(props) => {
+                if attr_name == "style" {
+                    let style_list =
+                        el.unchecked_ref::<web_sys::HtmlElement>().style();
+                    for (name, value) in &props {
+                        style_expression(&style_list, name, Some(value), true);
+                    }
                 } else {
-                    el.remove_attribute(attr_name).unwrap_throw();
+                    let attr_name = wasm_bindgen::intern(attr_name);
+                    el.set_attribute(attr_name, &style_string(&props))
+                        .unwrap_throw();
                 }
             }
             _ => panic!("Remove nested Fn in Attribute"),
         }
     }
 }
+
+// whether the element already has the attribute as `value` would set it,
+// following the same rules as `attribute_expression`
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn attribute_matches(
+    el: &web_sys::Element,
+    attr_name: &str,
+    value: &Attribute,
+) -> bool {
+    if attr_name == "inner_html" {
+        return false;
+    }
+    match value {
+        Attribute::String(value) | Attribute::Option(_, Some(value)) => {
+            el.get_attribute(attr_name).as_deref() == Some(&**value)
+        }
+        Attribute::Option(_, None) => !el.has_attribute(attr_name),
+        Attribute::Bool(value) => el.has_attribute(attr_name) == *value,
+        _ => false,
+    }
+}
+
+// when a reactive `style` changes between a string and a list of properties,
+// or drops some properties from its list, removes the properties the old value
+// set that the new one doesn't, leaving any set by `style:prop=` alone
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn remove_stale_styles(
+    el: &web_sys::Element,
+    old: Option<&Attribute>,
+    new: &Attribute,
+) {
+    let kept = match new {
+        Attribute::Style(new) => new.as_slice(),
+        _ => &[],
+    };
+    let style_list = el.unchecked_ref::<web_sys::HtmlElement>().style();
+    match old {
+        Some(Attribute::Style(old)) => {
+            for (name, _) in old {
+                if !kept.iter().any(|(kept, _)| kept == name) {
+                    style_expression(&style_list, name, None, true);
+                }
+            }
+        }
+        Some(old) if matches!(new, Attribute::Style(_)) => {
+            let old = old.as_nameless_value_string().unwrap_or_default();
+            for declaration in old.split(';') {
+                let name = declaration.split(':').next().unwrap_or("").trim();
+                if !name.is_empty()
+                    && !kept.iter().any(|(kept, _)| kept == name)
+                {
+                    style_expression(&style_list, name, None, true);
+                }
+            }
+        }
+        // a string sets the whole attribute, which replaces the old string's
+        // properties anyway
+        _ => {}
+    }
+}
@@ -38,6 +38,31 @@ where
     }
 }
 
+#[cfg(not(feature = "nightly"))]
+macro_rules! signal_class_type {
+    ($signal_type:ty) => {
+        impl IntoClass for $signal_type {
+            #[inline(always)]
+            fn into_class(self, cx: Scope) -> Class {
+                use leptos_reactive::SignalGet;
+
+                Class::Fn(cx, Box::new(move || self.get()))
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "nightly"))]
+signal_class_type!(leptos_reactive::ReadSignal<bool>);
+#[cfg(not(feature = "nightly"))]
+signal_class_type!(leptos_reactive::RwSignal<bool>);
+#[cfg(not(feature = "nightly"))]
+signal_class_type!(leptos_reactive::Memo<bool>);
+#[cfg(not(feature = "nightly"))]
+signal_class_type!(leptos_reactive::Signal<bool>);
+#[cfg(not(feature = "nightly"))]
+signal_class_type!(leptos_reactive::MaybeSignal<bool>);
+
 impl Class {
     /// Converts the class to its HTML value at that moment so it can be rendered on the server.
     pub fn as_value_string(&self, class_name: &'static str) -> &'static str {
@@ -85,6 +110,7 @@ pub fn class_helper(
     let class_list = el.class_list();
     match value {
         Class::Fn(cx, f) => {
+            crate::mark_bound(el);
             create_render_effect(cx, move |old| {
                 let new = f();
                 if old.as_ref() != Some(&new)
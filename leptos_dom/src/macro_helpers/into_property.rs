@@ -42,6 +42,37 @@ impl<T: IntoProperty> IntoProperty for (Scope, T) {
     }
 }
 
+#[cfg(not(feature = "nightly"))]
+macro_rules! signal_prop_type {
+    ($signal_type:ty, $value_type:ty) => {
+        impl<T> IntoProperty for $signal_type
+        where
+            T: Clone,
+            $value_type: Into<JsValue>,
+        {
+            fn into_property(self, cx: Scope) -> Property {
+                use leptos_reactive::SignalGet;
+
+                let modified_fn = Box::new(move || self.get().into());
+                Property::Fn(cx, modified_fn)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "nightly"))]
+signal_prop_type!(leptos_reactive::ReadSignal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_prop_type!(leptos_reactive::RwSignal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_prop_type!(leptos_reactive::Memo<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_prop_type!(leptos_reactive::Signal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_prop_type!(leptos_reactive::MaybeSignal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_prop_type!(leptos_reactive::MaybeProp<T>, Option<T>);
+
 macro_rules! prop_type {
     ($prop_type:ty) => {
         impl IntoProperty for $prop_type {
@@ -94,6 +125,7 @@ pub(crate) fn property_helper(
 
     match value {
         Property::Fn(cx, f) => {
+            crate::mark_bound(el);
             let el = el.clone();
             create_render_effect(cx, move |_| {
                 let new = f();
@@ -1,13 +1,13 @@
-use leptos_reactive::Scope;
+use leptos_reactive::{Oco, Scope};
 use std::{borrow::Cow, rc::Rc};
 
 /// todo docs
 #[derive(Clone)]
 pub enum Style {
     /// A plain string value.
-    Value(Cow<'static, str>),
+    Value(Oco<'static, str>),
     /// An optional string value, which sets the property to the value if `Some` and removes the property if `None`.
-    Option(Option<Cow<'static, str>>),
+    Option(Option<Oco<'static, str>>),
     /// A (presumably reactive) function, which will be run inside an effect to update the style.
     Fn(Scope, Rc<dyn Fn() -> Style>),
 }
@@ -39,33 +39,29 @@ pub trait IntoStyle {
     fn into_style(self, cx: Scope) -> Style;
 }
 
-impl IntoStyle for &'static str {
-    #[inline(always)]
-    fn into_style(self, _cx: Scope) -> Style {
-        Style::Value(self.into())
-    }
-}
-
-impl IntoStyle for String {
-    #[inline(always)]
-    fn into_style(self, _cx: Scope) -> Style {
-        Style::Value(self.into())
-    }
-}
+macro_rules! string_style_type {
+    ($style_type:ty) => {
+        impl IntoStyle for $style_type {
+            #[inline(always)]
+            fn into_style(self, _cx: Scope) -> Style {
+                Style::Value(self.into())
+            }
+        }
 
-impl IntoStyle for Option<&'static str> {
-    #[inline(always)]
-    fn into_style(self, _cx: Scope) -> Style {
-        Style::Option(self.map(Cow::Borrowed))
-    }
+        impl IntoStyle for Option<$style_type> {
+            #[inline(always)]
+            fn into_style(self, _cx: Scope) -> Style {
+                Style::Option(self.map(Into::into))
+            }
+        }
+    };
 }
 
-impl IntoStyle for Option<String> {
-    #[inline(always)]
-    fn into_style(self, _cx: Scope) -> Style {
-        Style::Option(self.map(Cow::Owned))
-    }
-}
+string_style_type!(&'static str);
+string_style_type!(String);
+string_style_type!(Cow<'static, str>);
+string_style_type!(Rc<str>);
+string_style_type!(Oco<'static, str>);
 
 impl<T, U> IntoStyle for T
 where
@@ -79,6 +75,37 @@ where
     }
 }
 
+#[cfg(not(feature = "nightly"))]
+macro_rules! signal_style_type {
+    ($signal_type:ty, $value_type:ty) => {
+        impl<T> IntoStyle for $signal_type
+        where
+            T: Clone,
+            $value_type: IntoStyle,
+        {
+            fn into_style(self, cx: Scope) -> Style {
+                use leptos_reactive::SignalGet;
+
+                let modified_fn = Rc::new(move || self.get().into_style(cx));
+                Style::Fn(cx, modified_fn)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "nightly"))]
+signal_style_type!(leptos_reactive::ReadSignal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_style_type!(leptos_reactive::RwSignal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_style_type!(leptos_reactive::Memo<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_style_type!(leptos_reactive::Signal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_style_type!(leptos_reactive::MaybeSignal<T>, T);
+#[cfg(not(feature = "nightly"))]
+signal_style_type!(leptos_reactive::MaybeProp<T>, Option<T>);
+
 impl Style {
     /// Converts the style to its HTML value at that moment so it can be rendered on the server.
     pub fn as_value_string(
@@ -125,6 +152,7 @@ pub fn style_helper(
     let style_list = el.style();
     match value {
         Style::Fn(cx, f) => {
+            crate::mark_bound(el);
             create_render_effect(cx, move |old| {
                 let mut new = f();
                 while let Style::Fn(_, f) = new {
@@ -155,7 +183,7 @@ pub fn style_helper(
 pub(crate) fn style_expression(
     style_list: &web_sys::CssStyleDeclaration,
     style_name: &str,
-    value: Option<&Cow<'static, str>>,
+    value: Option<&Oco<'static, str>>,
     force: bool,
 ) {
     use crate::HydrationCtx;
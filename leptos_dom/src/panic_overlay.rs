@@ -0,0 +1,198 @@
+//! A development-only overlay that shows panics on the page.
+//!
+//! With the `panic-overlay` feature enabled, [`mount_to`](crate::mount_to)
+//! installs a panic hook that logs the panic and its stack trace with
+//! `console.error`, as `console_error_panic_hook` would, and also shows them
+//! in an overlay on top of the page, with the location of the panic and the
+//! component it happened in (when `tracing` spans are being recorded), so
+//! that a panic does not just leave the page frozen. Any panic hook that was
+//! installed before, like `console_error_panic_hook` itself, still runs
+//! first.
+//!
+//! The overlay is only compiled in debug builds in the browser: in release
+//! builds, and on the server, none of this code is included.
+
+#[cfg(all(
+    feature = "panic-overlay",
+    debug_assertions,
+    target_arch = "wasm32",
+    feature = "web"
+))]
+use std::cell::Cell;
+
+#[cfg(all(
+    feature = "panic-overlay",
+    debug_assertions,
+    target_arch = "wasm32",
+    feature = "web"
+))]
+thread_local! {
+    static ENABLED: Cell<bool> = Cell::new(true);
+    static INSTALLED: Cell<bool> = Cell::new(false);
+}
+
+/// Sets whether panics are shown in an overlay on the page, when the
+/// `panic-overlay` feature is enabled. Panics are still logged to the
+/// console either way. The overlay is enabled by default.
+///
+/// This does nothing in release builds, where the overlay is compiled out.
+pub fn set_dev_overlay(enabled: bool) {
+    cfg_if::cfg_if! {
+        if #[cfg(all(
+            feature = "panic-overlay",
+            debug_assertions,
+            target_arch = "wasm32",
+            feature = "web"
+        ))] {
+            ENABLED.with(|e| e.set(enabled));
+        } else {
+            _ = enabled;
+        }
+    }
+}
+
+/// Installs the panic hook, once.
+#[cfg(all(
+    feature = "panic-overlay",
+    debug_assertions,
+    target_arch = "wasm32",
+    feature = "web"
+))]
+pub(crate) fn install() {
+    if INSTALLED.with(|installed| installed.replace(true)) {
+        return;
+    }
+
+    chain_hook(|message| {
+        let message = format!("{message}\n\nStack:\n\n{}", stack_trace());
+        web_sys::console::error_1(&message.as_str().into());
+
+        if ENABLED.with(Cell::get) {
+            // the span of the component that was rendering, if any
+            let component = tracing::Span::current()
+                .metadata()
+                .map(|metadata| metadata.name());
+            show(&message, component);
+        }
+    });
+}
+
+// sets a panic hook that runs the previous hook, and then `hook` with the
+// panic's message
+#[cfg(any(
+    test,
+    all(
+        feature = "panic-overlay",
+        debug_assertions,
+        target_arch = "wasm32",
+        feature = "web"
+    )
+))]
+fn chain_hook(hook: impl Fn(&str) + Send + Sync + 'static) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        previous(info);
+        hook(&info.to_string());
+    }));
+}
+
+// the JavaScript stack trace where the panic happened
+#[cfg(all(
+    feature = "panic-overlay",
+    debug_assertions,
+    target_arch = "wasm32",
+    feature = "web"
+))]
+fn stack_trace() -> String {
+    let error = js_sys::Error::new("");
+    js_sys::Reflect::get(&error, &"stack".into())
+        .ok()
+        .and_then(|stack| stack.as_string())
+        .unwrap_or_default()
+}
+
+#[cfg(all(
+    feature = "panic-overlay",
+    debug_assertions,
+    target_arch = "wasm32",
+    feature = "web"
+))]
+fn show(message: &str, component: Option<&str>) {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let document = crate::document();
+    let Some(body) = document.body() else {
+        return;
+    };
+    let Ok(overlay) = document.create_element("div") else {
+        return;
+    };
+    _ = overlay.set_attribute("role", "alert");
+    _ = overlay.set_attribute(
+        "style",
+        "position: fixed; inset: 0; z-index: 2147483647; overflow: auto; \
+         padding: 2rem; background: rgba(24, 24, 27, 0.95); color: #fafafa; \
+         font-family: monospace; font-size: 14px;",
+    );
+
+    let heading = document.create_element("h2").ok();
+    if let Some(heading) = &heading {
+        heading.set_text_content(Some(&match component {
+            Some(component) => format!("Panicked in <{component}/>"),
+            None => "Panicked".to_string(),
+        }));
+        _ = heading.set_attribute("style", "color: #f87171; margin-top: 0;");
+        _ = overlay.append_child(heading);
+    }
+
+    if let Ok(pre) = document.create_element("pre") {
+        pre.set_text_content(Some(message));
+        _ = pre.set_attribute("style", "white-space: pre-wrap;");
+        _ = overlay.append_child(&pre);
+    }
+
+    if let Ok(button) = document.create_element("button") {
+        button.set_text_content(Some("Dismiss"));
+        let dismiss = Closure::once_into_js({
+            let overlay = overlay.clone();
+            move || overlay.remove()
+        });
+        _ = button
+            .add_event_listener_with_callback("click", dismiss.unchecked_ref());
+        _ = overlay.append_child(&button);
+    }
+
+    _ = body.append_child(&overlay);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::chain_hook;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn the_previous_panic_hook_still_runs() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let original = std::panic::take_hook();
+        std::panic::set_hook({
+            let calls = Arc::clone(&calls);
+            Box::new(move |_| {
+                calls.lock().unwrap().push("previous".to_string())
+            })
+        });
+        chain_hook({
+            let calls = Arc::clone(&calls);
+            move |message| calls.lock().unwrap().push(message.to_string())
+        });
+
+        _ = std::panic::catch_unwind(|| {
+            panic!("boom");
+        });
+        std::panic::set_hook(original);
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls[0], "previous");
+        assert!(calls[1].contains("boom"), "{}", calls[1]);
+        assert!(calls[1].contains("panic_overlay.rs"), "{}", calls[1]);
+    }
+}
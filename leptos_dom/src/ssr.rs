@@ -3,17 +3,25 @@
 //! Server-side HTML rendering utilities.
 
 use crate::{
+    components::EachItem,
+    escape::{escape_attr_into, escape_text_into},
     html::{ElementChildren, StringOrView},
-    CoreComponent, HydrationCtx, IntoView, View,
+    hydration::{render_serializer, render_stream_done, render_stream_start},
+    Attribute, CoreComponent, HydrationCtx, HydrationKey, IntoView, View,
 };
-use cfg_if::cfg_if;
 use futures::{stream::FuturesUnordered, Future, Stream, StreamExt};
-use itertools::Itertools;
 use leptos_reactive::*;
-use std::{borrow::Cow, pin::Pin};
+use std::{
+    borrow::Cow, cell::RefCell, collections::HashSet, fmt::Write, pin::Pin,
+    rc::Rc,
+};
 
 type PinnedFuture<T> = Pin<Box<dyn Future<Output = T>>>;
 
+// when streaming, the shell is cut into chunks of at least this many bytes,
+// each of which ends where an element does
+const SHELL_CHUNK_SIZE: usize = 8 * 1024;
+
 /// Renders the given function to a static HTML string.
 ///
 /// ```
@@ -39,6 +47,7 @@ where
     HydrationCtx::reset_id();
 
     let html = leptos_reactive::run_scope(runtime, |cx| {
+        provide_interned_attrs(cx);
         f(cx).into_view(cx).render_to_string(cx)
     });
 
@@ -50,7 +59,7 @@ where
 /// Renders a function to a stream of HTML strings.
 ///
 /// This renders:
-/// 1) the application shell
+/// 1) the application shell, in chunks that each end at an element boundary
 ///   a) HTML for everything that is not under a `<Suspense/>`,
 ///   b) the `fallback` for any `<Suspense/>` component that is not already resolved, and
 ///   c) JavaScript necessary to receive streaming [Resource](leptos_reactive::Resource) data.
@@ -74,7 +83,7 @@ pub fn render_to_stream(
 ///
 /// This renders:
 /// 1) the prefix
-/// 2) the application shell
+/// 2) the application shell, in chunks that each end at an element boundary
 ///   a) HTML for everything that is not under a `<Suspense/>`,
 ///   b) the `fallback` for any `<Suspense/>` component that is not already resolved, and
 ///   c) JavaScript necessary to receive streaming [Resource](leptos_reactive::Resource) data.
@@ -103,7 +112,7 @@ pub fn render_to_stream_with_prefix(
 ///
 /// This renders:
 /// 1) the prefix
-/// 2) the application shell
+/// 2) the application shell, in chunks that each end at an element boundary
 ///   a) HTML for everything that is not under a `<Suspense/>`,
 ///   b) the `fallback` for any `<Suspense/>` component that is not already resolved, and
 ///   c) JavaScript necessary to receive streaming [Resource](leptos_reactive::Resource) data.
@@ -129,7 +138,7 @@ pub fn render_to_stream_with_prefix_undisposed(
 ///
 /// This renders:
 /// 1) the prefix
-/// 2) the application shell
+/// 2) the application shell, in chunks that each end at an element boundary
 ///   a) HTML for everything that is not under a `<Suspense/>`,
 ///   b) the `fallback` for any `<Suspense/>` component that is not already resolved, and
 ///   c) JavaScript necessary to receive streaming [Resource](leptos_reactive::Resource) data.
@@ -166,7 +175,7 @@ pub fn render_to_stream_with_prefix_undisposed_with_context(
 ///
 /// This renders:
 /// 1) the prefix
-/// 2) the application shell
+/// 2) the application shell, in chunks that each end at an element boundary
 ///   a) HTML for everything that is not under a `<Suspense/>`,
 ///   b) the `fallback` for any `<Suspense/>` component that is not already resolved, and
 ///   c) JavaScript necessary to receive streaming [Resource](leptos_reactive::Resource) data.
@@ -190,27 +199,39 @@ pub fn render_to_stream_with_prefix_undisposed_with_context_and_block_replacemen
     // create the runtime
     let runtime = create_runtime();
 
-    let ((shell, pending_resources, pending_fragments, serializers), scope, _) =
-        run_scope_undisposed(runtime, {
-            move |cx| {
-                // Add additional context items
-                additional_context(cx);
-                // the actual app body/template code
-                // this does NOT contain any of the data being loaded asynchronously in resources
-                let shell = view(cx).render_to_string(cx);
-
-                let resources = cx.pending_resources();
-                let pending_resources =
-                    serde_json::to_string(&resources).unwrap();
-
-                (
-                    shell,
-                    pending_resources,
-                    cx.pending_fragments(),
-                    cx.serialization_resolvers(),
-                )
-            }
-        });
+    let (
+        (
+            shell,
+            pending_resources,
+            pending_fragments,
+            serializers,
+            blocking_resources_ready,
+        ),
+        scope,
+        _,
+    ) = run_scope_undisposed(runtime, {
+        move |cx| {
+            #[cfg(feature = "debug-timing")]
+            provide_context(cx, resource_timings(cx));
+            provide_interned_attrs(cx);
+            // Add additional context items
+            additional_context(cx);
+            // the actual app body/template code
+            // this does NOT contain any of the data being loaded asynchronously in resources
+            // it is only rendered into HTML as the stream is read
+            let shell = view(cx).render_to_chunks(cx);
+
+            let pending_resources = cx.pending_resources();
+
+            (
+                shell,
+                pending_resources,
+                cx.pending_fragments(),
+                cx.serialization_resolvers(),
+                cx.blocking_resources_ready(),
+            )
+        }
+    });
     let cx = Scope { runtime, id: scope };
     let nonce_str = crate::nonce::use_nonce(cx)
         .map(|nonce| format!(" nonce=\"{nonce}\""))
@@ -231,70 +252,103 @@ pub fn render_to_stream_with_prefix_undisposed_with_context_and_block_replacemen
         }
     }
 
-    let stream = futures::stream::once(
-        // HTML for the view function and script to store resources
-        {
-            let nonce_str = nonce_str.clone();
-            async move {
-                let resolvers = format!(
-                    "<script{nonce_str}>__LEPTOS_PENDING_RESOURCES = \
-                     {pending_resources};__LEPTOS_RESOLVED_RESOURCES = new \
-                     Map();__LEPTOS_RESOURCE_RESOLVERS = new Map();</script>"
-                );
-
-                if replace_blocks {
-                    let mut blocks =
-                        Vec::with_capacity(blocking_fragments.len());
-                    while let Some((blocked_id, blocked_fragment)) =
-                        blocking_fragments.next().await
-                    {
-                        blocks.push((blocked_id, blocked_fragment));
-                    }
+    let resolvers = render_stream_start(&nonce_str, &pending_resources);
 
-                    let prefix = prefix(cx);
+    // HTML for the view function and script to store resources
+    let shell: Pin<Box<dyn Stream<Item = String>>> = if replace_blocks {
+        Box::pin(futures::stream::once(async move {
+            // blocking resources that are not read under a <Suspense/>
+            // can still change the <head>
+            blocking_resources_ready.await;
 
-                    let mut shell = shell;
+            let mut blocks = Vec::with_capacity(blocking_fragments.len());
+            while let Some((blocked_id, blocked_fragment)) =
+                blocking_fragments.next().await
+            {
+                blocks.push((blocked_id, blocked_fragment));
+            }
 
-                    for (blocked_id, blocked_fragment) in blocks {
-                        let open = format!("<!--suspense-open-{blocked_id}-->");
-                        let close =
-                            format!("<!--suspense-close-{blocked_id}-->");
-                        let (first, rest) =
-                            shell.split_once(&open).unwrap_or_default();
-                        let (_fallback, rest) =
-                            rest.split_once(&close).unwrap_or_default();
+            let prefix = prefix(cx);
 
-                        shell =
-                            format!("{first}{blocked_fragment}{rest}").into();
-                    }
+            let mut shell = shell.collect::<String>();
 
-                    format!("{prefix}{shell}{resolvers}")
-                } else {
-                    let mut blocking = String::new();
-                    let mut blocking_fragments = fragments_to_chunks(
-                        nonce_str.clone(),
-                        blocking_fragments,
-                    );
+            for (blocked_id, blocked_fragment) in blocks {
+                let open = format!("<!--suspense-open-{blocked_id}-->");
+                let close = format!("<!--suspense-close-{blocked_id}-->");
+                let (first, rest) = shell.split_once(&open).unwrap_or_default();
+                let (_fallback, rest) =
+                    rest.split_once(&close).unwrap_or_default();
 
-                    while let Some(fragment) = blocking_fragments.next().await {
-                        blocking.push_str(&fragment);
-                    }
-                    let prefix = prefix(cx);
-                    format!("{prefix}{shell}{resolvers}{blocking}")
-                }
+                shell = format!("{first}{blocked_fragment}{rest}");
             }
-        },
-    )
-    .chain(ooo_body_stream_recurse(
-        cx,
-        nonce_str,
-        fragments,
-        serializers,
-    ));
+
+            format!("{prefix}{shell}{resolvers}")
+        }))
+    } else {
+        let mut shell = shell;
+        let nonce_str = nonce_str.clone();
+        Box::pin(
+            futures::stream::once(async move {
+                // blocking resources that are not read under a <Suspense/>
+                // can still change the <head>
+                blocking_resources_ready.await;
+
+                let mut blocking = String::new();
+                let mut blocking_fragments =
+                    fragments_to_chunks(nonce_str, blocking_fragments);
+                while let Some(fragment) = blocking_fragments.next().await {
+                    blocking.push_str(&fragment);
+                }
+                let prefix = prefix(cx);
+
+                // only the first chunk of the shell is rendered now; each
+                // of the others is rendered when the stream reaches it
+                let first = shell.next().unwrap_or_default();
+                let rest =
+                    futures::stream::iter(shell).chain(futures::stream::once(
+                        async move { format!("{resolvers}{blocking}") },
+                    ));
+                futures::stream::once(async move { format!("{prefix}{first}") })
+                    .chain(rest)
+            })
+            .flatten(),
+        )
+    };
+
+    let stream = shell
+        .chain(ooo_body_stream_recurse(
+            cx,
+            nonce_str.clone(),
+            fragments,
+            serializers,
+        ))
+        .chain(futures::stream::once(async move {
+            render_stream_done(&nonce_str)
+        }))
+        .chain(resource_timings_comment(cx));
 
     (stream, runtime, scope)
 }
 
+/// With the `debug-timing` feature, a stream with an HTML comment that
+/// summarizes when each resource was fetched and serialized, to go at the
+/// end of the page.
+#[cfg(feature = "debug-timing")]
+pub(crate) fn resource_timings_comment(
+    cx: Scope,
+) -> impl Stream<Item = String> {
+    // taken now, as the runtime may be disposed before the stream ends
+    let timings = resource_timings(cx);
+    futures::stream::once(async move { timings.to_html_comment() })
+}
+
+#[cfg(not(feature = "debug-timing"))]
+pub(crate) fn resource_timings_comment(
+    _cx: Scope,
+) -> impl Stream<Item = String> {
+    futures::stream::empty()
+}
+
 fn ooo_body_stream_recurse(
     cx: Scope,
     nonce_str: String,
@@ -380,304 +434,357 @@ fn fragments_to_chunks(
     })
 }
 
-impl View {
-    /// Consumes the node and renders it into an HTML string.
-    #[cfg_attr(
-        any(debug_assertions, feature = "ssr"),
-        instrument(level = "info", skip_all,)
-    )]
-    pub fn render_to_string(self, _cx: Scope) -> Cow<'static, str> {
-        #[cfg(all(feature = "web", feature = "ssr"))]
-        crate::console_error(
-            "\n[DANGER] You have both `csr` and `ssr` or `hydrate` and `ssr` \
-             enabled as features, which may cause issues like <Suspense/>` \
-             failing to work silently.\n",
-        );
+/// A part of a view that has not been written yet.
+enum Pending {
+    /// A view, and whether its text is written without being escaped.
+    View(View, bool),
+    Chunk(StringOrView, bool),
+    EachItem(EachItem, bool),
+    CloseMarker(HydrationKey, Cow<'static, str>),
+    #[cfg(debug_assertions)]
+    ViewMarker(Option<String>, &'static str),
+    SuspenseClose(HydrationKey),
+    CloseTag(Cow<'static, str>),
+    /// The end of an element, where the HTML can be cut into chunks.
+    ElementEnd,
+}
 
-        self.render_to_string_helper(false)
+/// Walks a view depth-first, writing one node at a time, so that the HTML
+/// can either be written into a single buffer or be cut into chunks while
+/// the rest of the view has not been rendered yet.
+struct HtmlWalker(Vec<Pending>);
+
+impl HtmlWalker {
+    fn new(view: View, dont_escape_text: bool) -> Self {
+        Self(vec![Pending::View(view, dont_escape_text)])
     }
 
-    #[cfg_attr(
-        any(debug_assertions, feature = "ssr"),
-        instrument(level = "trace", skip_all,)
-    )]
-    pub(crate) fn render_to_string_helper(
-        self,
+    /// Writes the next part of the view into `buf`, returning `None` once
+    /// the whole view has been written, or `Some(true)` where an element
+    /// ends.
+    fn step(&mut self, buf: &mut String) -> Option<bool> {
+        match self.0.pop()? {
+            Pending::View(view, dont_escape_text) => {
+                self.write_view(buf, view, dont_escape_text)
+            }
+            Pending::Chunk(StringOrView::String(string), _) => {
+                buf.push_str(&string)
+            }
+            Pending::Chunk(StringOrView::View(view), dont_escape_text) => {
+                self.0.push(Pending::View(view(), dont_escape_text))
+            }
+            Pending::EachItem(node, dont_escape_text) => {
+                buf.push_str(&node.key_marker());
+                write_open_marker(buf, &node.id, "each-item");
+                self.0
+                    .push(Pending::CloseMarker(node.id, "each-item".into()));
+                self.0.push(Pending::View(node.child, dont_escape_text));
+            }
+            Pending::CloseMarker(id, name) => {
+                write_close_marker(buf, &id, &name)
+            }
+            #[cfg(debug_assertions)]
+            Pending::ViewMarker(marker, edge) => {
+                write_view_marker(buf, &marker, edge)
+            }
+            Pending::SuspenseClose(id) => {
+                let _ = write!(buf, "<!--suspense-close-{id}-->");
+            }
+            Pending::CloseTag(tag_name) => {
+                buf.push_str("</");
+                buf.push_str(&tag_name);
+                buf.push('>');
+            }
+            Pending::ElementEnd => return Some(true),
+        }
+        Some(false)
+    }
+
+    /// Writes the start of a node, and queues its children and whatever
+    /// closes it.
+    fn write_view(
+        &mut self,
+        buf: &mut String,
+        view: View,
         dont_escape_text: bool,
-    ) -> Cow<'static, str> {
-        match self {
+    ) {
+        match view {
             View::Text(node) => {
-                if dont_escape_text {
-                    node.content
-                } else {
-                    html_escape::encode_safe(&node.content).to_string().into()
-                }
+                write_text(buf, &node.content, dont_escape_text)
             }
             View::Component(node) => {
-                let content = || {
-                    node.children
-                        .into_iter()
-                        .map(|node| {
-                            node.render_to_string_helper(dont_escape_text)
-                        })
-                        .join("")
-                };
-                cfg_if! {
-                  if #[cfg(debug_assertions)] {
-                    let content = format!(r#"<!--hk={}|leptos-{name}-start-->{}<!--hk={}|leptos-{name}-end-->"#,
-                      HydrationCtx::to_string(&node.id, false),
-                      content(),
-                      HydrationCtx::to_string(&node.id, true),
-                      name = to_kebab_case(&node.name)
+                #[cfg(debug_assertions)]
+                let name: Cow<'static, str> = to_kebab_case(&node.name).into();
+                #[cfg(not(debug_assertions))]
+                let name: Cow<'static, str> = "".into();
+
+                #[cfg(debug_assertions)]
+                write_view_marker(buf, &node.view_marker, "open");
+                write_open_marker(buf, &node.id, &name);
+                #[cfg(debug_assertions)]
+                self.0.push(Pending::ViewMarker(node.view_marker, "close"));
+                self.0.push(Pending::CloseMarker(node.id, name));
+                self.push_children(node.children, dont_escape_text);
+            }
+            View::Suspense(id, node) => {
+                let _ = write!(buf, "<!--suspense-open-{id}-->");
+                self.0.push(Pending::SuspenseClose(id));
+                self.0.push(Pending::View(
+                    View::CoreComponent(node),
+                    dont_escape_text,
+                ));
+            }
+            View::CoreComponent(node) => match node {
+                CoreComponent::Unit(u) => {
+                    #[cfg(debug_assertions)]
+                    let _ = write!(
+                        buf,
+                        "<!--hk={}|leptos-unit-->",
+                        HydrationCtx::to_string(&u.id, true)
+                    );
+
+                    #[cfg(not(debug_assertions))]
+                    let _ = write!(
+                        buf,
+                        "<!--hk={}-->",
+                        HydrationCtx::to_string(&u.id, true)
                     );
-                    if let Some(id) = node.view_marker {
-                        format!("<!--leptos-view|{id}|open-->{content}<!--leptos-view|{id}|close-->").into()
-                    } else {
-                        content.into()
-                    }
-                  } else {
-                    format!(
-                      r#"{}<!--hk={}-->"#,
-                      content(),
-                      HydrationCtx::to_string(&node.id, true)
-                    ).into()
-                  }
                 }
-            }
-            View::Suspense(id, node) => format!(
-                "<!--suspense-open-{id}-->{}<!--suspense-close-{id}-->",
-                View::CoreComponent(node)
-                    .render_to_string_helper(dont_escape_text)
-            )
-            .into(),
-            View::CoreComponent(node) => {
-                let (id, name, wrap, content) = match node {
-                    CoreComponent::Unit(u) => (
-                        u.id,
-                        "",
-                        false,
-                        Box::new(move || {
-                            #[cfg(debug_assertions)]
-                            {
-                                format!(
-                                    "<!--hk={}|leptos-unit-->",
-                                    HydrationCtx::to_string(&u.id, true)
-                                )
-                                .into()
+                CoreComponent::DynChild(node) => {
+                    let child = node.child.take();
+                    write_open_marker(buf, &node.id, "dyn-child");
+                    self.0.push(Pending::CloseMarker(
+                        node.id,
+                        "dyn-child".into(),
+                    ));
+                    match *child {
+                        Some(View::Text(t)) => {
+                            // On debug builds, `DynChild` has two marker nodes,
+                            // so there is no way for the text to be merged with
+                            // surrounding text when the browser parses the HTML,
+                            // but in release, `DynChild` only has a trailing marker,
+                            // and the browser automatically merges the dynamic text
+                            // into one single node, so we need to artificially make the
+                            // browser create the dynamic text as it's own text node
+                            if !cfg!(debug_assertions) {
+                                buf.push_str("<!>");
                             }
-
-                            #[cfg(not(debug_assertions))]
-                            format!(
-                                "<!--hk={}-->",
-                                HydrationCtx::to_string(&u.id, true)
-                            )
-                            .into()
-                        })
-                            as Box<dyn FnOnce() -> Cow<'static, str>>,
-                    ),
-                    CoreComponent::DynChild(node) => {
-                        let child = node.child.take();
-                        (
-                            node.id,
-                            "dyn-child",
-                            true,
-                            Box::new(move || {
-                                if let Some(child) = *child {
-                                    if let View::Text(t) = child {
-                                        // if we don't check if the string is empty,
-                                        // the HTML is an empty string; but an empty string
-                                        // is not a text node in HTML, so can't be updated
-                                        // in the future. so we put a one-space text node instead
-                                        let was_empty = t.content.is_empty();
-                                        let content = if was_empty {
-                                            " ".into()
-                                        } else {
-                                            t.content
-                                        };
-                                        // escape content unless we're in a <script> or <style>
-                                        let content = if dont_escape_text {
-                                            content
-                                        } else {
-                                            html_escape::encode_safe(&content)
-                                                .to_string()
-                                                .into()
-                                        };
-                                        // On debug builds, `DynChild` has two marker nodes,
-                                        // so there is no way for the text to be merged with
-                                        // surrounding text when the browser parses the HTML,
-                                        // but in release, `DynChild` only has a trailing marker,
-                                        // and the browser automatically merges the dynamic text
-                                        // into one single node, so we need to artificially make the
-                                        // browser create the dynamic text as it's own text node
-                                        if !cfg!(debug_assertions) {
-                                            format!("<!>{content}",).into()
-                                        } else {
-                                            content
-                                        }
-                                    } else {
-                                        child.render_to_string_helper(
-                                            dont_escape_text,
-                                        )
-                                    }
-                                } else {
-                                    "".into()
-                                }
-                            })
-                                as Box<dyn FnOnce() -> Cow<'static, str>>,
-                        )
-                    }
-                    CoreComponent::Each(node) => {
-                        let children = node.children.take();
-                        (
-                            node.id,
-                            "each",
-                            true,
-                            Box::new(move || {
-                                children
-                                    .into_iter()
-                                    .flatten()
-                                    .map(|node| {
-                                        let id = node.id;
-
-                                        let content = || {
-                                            node.child.render_to_string_helper(
-                                                dont_escape_text,
-                                            )
-                                        };
-
-                                        #[cfg(debug_assertions)]
-                                        {
-                                            format!(
-                        "<!--hk={}|leptos-each-item-start-->{}<!\
-                         --hk={}|leptos-each-item-end-->",
-                        HydrationCtx::to_string(&id, false),
-                        content(),
-                        HydrationCtx::to_string(&id, true),
-                      )
-                                        }
-
-                                        #[cfg(not(debug_assertions))]
-                                        format!(
-                                            "{}<!--hk={}-->",
-                                            content(),
-                                            HydrationCtx::to_string(&id, true)
-                                        )
-                                    })
-                                    .join("")
-                                    .into()
-                            })
-                                as Box<dyn FnOnce() -> Cow<'static, str>>,
-                        )
-                    }
-                };
-
-                if wrap {
-                    cfg_if! {
-                      if #[cfg(debug_assertions)] {
-                        format!(
-                          r#"<!--hk={}|leptos-{name}-start-->{}<!--hk={}|leptos-{name}-end-->"#,
-                          HydrationCtx::to_string(&id, false),
-                          content(),
-                          HydrationCtx::to_string(&id, true),
-                        ).into()
-                      } else {
-                        let _ = name;
-
-                        format!(
-                          r#"{}<!--hk={}-->"#,
-                          content(),
-                          HydrationCtx::to_string(&id, true)
-                        ).into()
-                      }
+                            // if we don't check if the string is empty,
+                            // the HTML is an empty string; but an empty string
+                            // is not a text node in HTML, so can't be updated
+                            // in the future. so we put a one-space text node instead
+                            if t.content.is_empty() {
+                                buf.push(' ');
+                            } else {
+                                // escape content unless we're in a <script> or <style>
+                                write_text(buf, &t.content, dont_escape_text);
+                            }
+                        }
+                        Some(child) => {
+                            self.0.push(Pending::View(child, dont_escape_text))
+                        }
+                        None => {}
                     }
-                } else {
-                    content()
                 }
-            }
+                CoreComponent::Each(node) => {
+                    let children = node.children.take();
+                    write_open_marker(buf, &node.id, "each");
+                    buf.push_str(&node.list_marker());
+                    self.0.push(Pending::CloseMarker(node.id, "each".into()));
+                    self.0.extend(
+                        children.into_iter().flatten().rev().map(|node| {
+                            Pending::EachItem(node, dont_escape_text)
+                        }),
+                    );
+                }
+            },
             View::Element(el) => {
+                #[cfg(debug_assertions)]
+                write_view_marker(buf, &el.view_marker, "open");
+
+                // queued in reverse, as the last thing written for the element
+                self.0.push(Pending::ElementEnd);
+                #[cfg(debug_assertions)]
+                self.0.push(Pending::ViewMarker(el.view_marker, "close"));
+
                 let is_script_or_style =
                     el.name == "script" || el.name == "style";
-                let el_html = if let ElementChildren::Chunks(chunks) =
-                    el.children
-                {
-                    chunks
-                        .into_iter()
-                        .map(|chunk| match chunk {
-                            StringOrView::String(string) => string,
-                            StringOrView::View(view) => view()
-                                .render_to_string_helper(is_script_or_style),
-                        })
-                        .join("")
-                        .into()
+                if let ElementChildren::Chunks(chunks) = el.children {
+                    self.0.extend(chunks.into_iter().rev().map(|chunk| {
+                        Pending::Chunk(chunk, is_script_or_style)
+                    }));
                 } else {
                     let tag_name = el.name;
 
-                    let mut inner_html = None;
-
-                    let attrs = el
-                        .attrs
-                        .into_iter()
-                        .filter_map(
-                            |(name, value)| -> Option<Cow<'static, str>> {
-                                if value.is_empty() {
-                                    Some(format!(" {name}").into())
-                                } else if name == "inner_html" {
-                                    inner_html = Some(value);
-                                    None
-                                } else {
-                                    Some(
-                                        format!(
-                    " {name}=\"{}\"",
-                    html_escape::encode_double_quoted_attribute(&value)
-                  )
-                                        .into(),
-                                    )
-                                }
-                            },
-                        )
-                        .join("");
+                    buf.push('<');
+                    buf.push_str(&tag_name);
+                    let inner_html = write_attrs(buf, el.attrs);
 
                     if el.is_void {
-                        format!("<{tag_name}{attrs}/>").into()
-                    } else if let Some(inner_html) = inner_html {
-                        format!("<{tag_name}{attrs}>{inner_html}</{tag_name}>")
-                            .into()
+                        buf.push_str("/>");
                     } else {
-                        let children = match el.children {
-                            ElementChildren::Empty => "".into(),
-                            ElementChildren::Children(c) => c
-                                .into_iter()
-                                .map(|v| {
-                                    v.render_to_string_helper(
-                                        is_script_or_style,
-                                    )
-                                })
-                                .join("")
-                                .into(),
-                            ElementChildren::InnerHtml(h) => h,
-                            // already handled this case above
-                            ElementChildren::Chunks(_) => unreachable!(),
-                        };
-
-                        format!("<{tag_name}{attrs}>{children}</{tag_name}>")
-                            .into()
-                    }
-                };
-                cfg_if! {
-                    if #[cfg(debug_assertions)] {
-                        if let Some(id) = el.view_marker {
-                            format!("<!--leptos-view|{id}|open-->{el_html}<!--leptos-view|{id}|close-->").into()
+                        buf.push('>');
+                        self.0.push(Pending::CloseTag(tag_name));
+                        if let Some(inner_html) = inner_html {
+                            buf.push_str(&inner_html);
                         } else {
-                            el_html
+                            match el.children {
+                                ElementChildren::Empty => {}
+                                ElementChildren::Children(c) => {
+                                    self.push_children(c, is_script_or_style)
+                                }
+                                ElementChildren::InnerHtml(h) => {
+                                    buf.push_str(&h)
+                                }
+                                // already handled this case above
+                                ElementChildren::Chunks(_) => unreachable!(),
+                            }
                         }
-                    } else {
-                        el_html
                     }
                 }
             }
-            View::Transparent(_) => Default::default(),
+            View::Transparent(_) => {}
         }
     }
+
+    fn push_children(&mut self, children: Vec<View>, dont_escape_text: bool) {
+        self.0.extend(
+            children
+                .into_iter()
+                .rev()
+                .map(|child| Pending::View(child, dont_escape_text)),
+        );
+    }
+}
+
+/// The HTML of a view, cut into chunks that each end at an element boundary.
+/// Each chunk is only rendered once it is asked for, so the first chunk can
+/// be sent before the rest of the view has been rendered.
+pub(crate) struct HtmlChunks {
+    walker: HtmlWalker,
+    started: bool,
+}
+
+impl Iterator for HtmlChunks {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let mut buf = String::new();
+        while let Some(element_end) = self.walker.step(&mut buf) {
+            if element_end && buf.len() >= SHELL_CHUNK_SIZE {
+                break;
+            }
+        }
+        // there is always at least one chunk, even if it is empty
+        let first = !std::mem::replace(&mut self.started, true);
+        (first || !buf.is_empty()).then_some(buf)
+    }
+}
+
+fn warn_if_web_and_ssr() {
+    #[cfg(all(feature = "web", feature = "ssr"))]
+    crate::console_error(
+        "\n[DANGER] You have both `csr` and `ssr` or `hydrate` and `ssr` \
+         enabled as features, which may cause issues like <Suspense/>` \
+         failing to work silently.\n",
+    );
+}
+
+impl View {
+    /// Consumes the node and renders it into an HTML string.
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        instrument(level = "info", skip_all,)
+    )]
+    pub fn render_to_string(self, _cx: Scope) -> Cow<'static, str> {
+        warn_if_web_and_ssr();
+
+        self.render_to_string_helper(false)
+    }
+
+    /// Consumes the node and renders it lazily into chunks of HTML, each of
+    /// which ends at an element boundary.
+    pub(crate) fn render_to_chunks(self, _cx: Scope) -> HtmlChunks {
+        warn_if_web_and_ssr();
+
+        HtmlChunks {
+            walker: HtmlWalker::new(self, false),
+            started: false,
+        }
+    }
+
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        instrument(level = "trace", skip_all,)
+    )]
+    pub(crate) fn render_to_string_helper(
+        self,
+        dont_escape_text: bool,
+    ) -> Cow<'static, str> {
+        match self {
+            View::Text(node) if dont_escape_text => node.content,
+            view => {
+                let mut buf = String::new();
+                view.write_html(&mut buf, dont_escape_text);
+                buf.into()
+            }
+        }
+    }
+
+    /// Renders the node into `buf`, which is shared by the whole tree, so
+    /// that no intermediate strings are built for each node.
+    pub(crate) fn write_html(self, buf: &mut String, dont_escape_text: bool) {
+        let mut walker = HtmlWalker::new(self, dont_escape_text);
+        while walker.step(buf).is_some() {}
+    }
+}
+
+/// Writes text content, escaping it unless it is inside a `<script>` or
+/// `<style>` element.
+fn write_text(buf: &mut String, text: &str, dont_escape_text: bool) {
+    if dont_escape_text {
+        buf.push_str(text);
+    } else {
+        escape_text_into(buf, text);
+    }
+}
+
+/// Writes the hydration marker that comes before the children of a
+/// component or control-flow node. Only debug builds have opening markers.
+fn write_open_marker(buf: &mut String, id: &HydrationKey, name: &str) {
+    #[cfg(debug_assertions)]
+    let _ = write!(
+        buf,
+        "<!--hk={}|leptos-{name}-start-->",
+        HydrationCtx::to_string(id, false)
+    );
+
+    #[cfg(not(debug_assertions))]
+    let _ = (buf, id, name);
+}
+
+/// Writes the hydration marker that comes after the children of a
+/// component or control-flow node.
+fn write_close_marker(buf: &mut String, id: &HydrationKey, name: &str) {
+    #[cfg(debug_assertions)]
+    let _ = write!(
+        buf,
+        "<!--hk={}|leptos-{name}-end-->",
+        HydrationCtx::to_string(id, true)
+    );
+
+    #[cfg(not(debug_assertions))]
+    {
+        let _ = name;
+        let _ = write!(buf, "<!--hk={}-->", HydrationCtx::to_string(id, true));
+    }
+}
+
+#[cfg(debug_assertions)]
+fn write_view_marker(buf: &mut String, marker: &Option<String>, edge: &str) {
+    if let Some(id) = marker {
+        let _ = write!(buf, "<!--leptos-view|{id}|{edge}-->");
+    }
 }
 
 #[cfg(debug_assertions)]
@@ -723,20 +830,119 @@ pub(crate) fn render_serializers(
     nonce_str: String,
     serializers: FuturesUnordered<PinnedFuture<(ResourceId, String)>>,
 ) -> impl Stream<Item = String> {
-    serializers.map(move |(id, json)| {
-        let id = serde_json::to_string(&id).unwrap();
-        let json = json.replace('<', "\\u003c");
-        format!(
-            r#"<script{nonce_str}>
-                  (function() {{ let val = {json:?};
-                  if(__LEPTOS_RESOURCE_RESOLVERS.get({id})) {{
-                      __LEPTOS_RESOURCE_RESOLVERS.get({id})(val)
-                  }} else {{
-                      __LEPTOS_RESOLVED_RESOURCES.set({id}, val);
-                  }} }})();
-              </script>"#,
-        )
-    })
+    serializers.map(move |(id, json)| render_serializer(&nonce_str, id, &json))
+}
+
+/// Writes an element’s attributes into `buf`, escaping each value as it is
+/// written. The `inner_html` pseudo-attribute is returned rather than written.
+pub(crate) fn write_attrs(
+    buf: &mut String,
+    attrs: impl IntoIterator<Item = (Cow<'static, str>, Oco<'static, str>)>,
+) -> Option<Oco<'static, str>> {
+    let mut inner_html = None;
+    for (name, value) in attrs {
+        if value.is_empty() {
+            buf.push(' ');
+            buf.push_str(&name);
+        } else if name == "inner_html" {
+            inner_html = Some(value);
+        } else {
+            buf.push(' ');
+            buf.push_str(&name);
+            buf.push_str("=\"");
+            escape_attr_into(buf, &value);
+            buf.push('"');
+        }
+    }
+    inner_html
+}
+
+/// An attribute that is escaped as it is written, so that the attributes of
+/// the templates that the `view!` macro renders on the server can be written
+/// into the HTML without copying their values.
+#[doc(hidden)]
+pub struct EscapedAttr {
+    name: Option<&'static str>,
+    value: Option<Oco<'static, str>>,
+}
+
+/// Writes the escaped value of an attribute, or nothing if it is not set.
+#[doc(hidden)]
+pub fn attr_value(attr: Attribute) -> EscapedAttr {
+    EscapedAttr {
+        name: None,
+        value: attr.into_nameless_value_string(),
+    }
+}
+
+/// Writes an attribute as `name="value"`, or nothing if it is not set.
+#[doc(hidden)]
+pub fn named_attr(name: &'static str, attr: Attribute) -> EscapedAttr {
+    EscapedAttr {
+        name: Some(name),
+        value: attr.into_nameless_value_string(),
+    }
+}
+
+impl std::fmt::Display for EscapedAttr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(value) = &self.value else {
+            return Ok(());
+        };
+        let value = crate::escape::escape_attr(value);
+        match self.name {
+            Some(name) => write!(f, "{name}=\"{value}\""),
+            None => f.write_str(&value),
+        }
+    }
+}
+
+// the owned attribute values set during one render, which is dropped with
+// the render's runtime
+#[derive(Clone, Default)]
+struct InternedAttrs(Rc<RefCell<HashSet<Rc<str>>>>);
+
+// owned values shorter than this are cheaper to copy than to look up
+const MIN_INTERNED_LEN: usize = 16;
+// values past this many are written as they are, so that a render with many
+// distinct values doesn't grow the table without bound
+const MAX_INTERNED_ATTRS: usize = 512;
+
+/// Starts a table of the owned attribute values set during the render that
+/// runs in `cx`, so that elements rendered with the same value share it.
+pub(crate) fn provide_interned_attrs(cx: Scope) {
+    provide_context(cx, InternedAttrs::default());
+}
+
+/// Stores owned attribute values in the table of the current render, so that
+/// an element that is rendered many times with the same value, like a long
+/// `class`, shares a single copy of it rather than holding its own until it
+/// is written. Borrowed values, and values set outside of a render, are
+/// returned as they are.
+pub(crate) fn intern_attr_value(
+    cx: Scope,
+    value: Oco<'static, str>,
+) -> Oco<'static, str> {
+    let Oco::Owned(owned) = value else {
+        return value;
+    };
+    if owned.len() < MIN_INTERNED_LEN {
+        return Oco::Owned(owned);
+    }
+    let Some(InternedAttrs(interned)) = use_context::<InternedAttrs>(cx)
+    else {
+        return Oco::Owned(owned);
+    };
+    let mut interned = interned.borrow_mut();
+    if let Some(value) = interned.get(owned.as_str()) {
+        return Oco::Counted(Rc::clone(value));
+    }
+    if interned.len() == MAX_INTERNED_ATTRS {
+        return Oco::Owned(owned);
+    }
+    let value: Rc<str> = owned.into();
+    interned.insert(Rc::clone(&value));
+    Oco::Counted(value)
 }
 
 #[doc(hidden)]
@@ -744,5 +950,5 @@ pub fn escape_attr<T>(value: &T) -> Cow<'_, str>
 where
     T: AsRef<str>,
 {
-    html_escape::encode_double_quoted_attribute(value)
+    crate::escape::escape_attr(value.as_ref())
 }
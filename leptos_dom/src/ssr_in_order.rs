@@ -3,14 +3,15 @@
 //! Server-side HTML rendering utilities for in-order streaming and async rendering.
 
 use crate::{
+    escape::escape_text,
     html::{ElementChildren, StringOrView},
-    ssr::render_serializers,
+    hydration::{render_stream_done, render_stream_start},
+    ssr::{render_serializers, resource_timings_comment, write_attrs},
     CoreComponent, HydrationCtx, View,
 };
 use async_recursion::async_recursion;
 use cfg_if::cfg_if;
 use futures::{channel::mpsc::UnboundedSender, Stream, StreamExt};
-use itertools::Itertools;
 use leptos_reactive::{
     create_runtime, run_scope_undisposed, suspense::StreamChunk, RuntimeId,
     Scope, ScopeId,
@@ -79,7 +80,8 @@ pub fn render_to_stream_in_order_with_prefix(
 /// 3. any serialized [Resource](leptos_reactive::Resource)s
 ///
 /// `additional_context` is injected before the `view` is rendered. The `prefix` is generated
-/// after the `view` is rendered, but before `<Suspense/>` nodes have resolved.
+/// after the `view` is rendered and any blocking resources have loaded, but before other
+/// `<Suspense/>` nodes have resolved.
 #[tracing::instrument(level = "trace", skip_all)]
 pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
     view: impl FnOnce(Scope) -> View + 'static,
@@ -92,10 +94,22 @@ pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
     let runtime = create_runtime();
 
     let (
-        (blocking_fragments_ready, chunks, prefix, pending_resources),
+        (
+            blocking_resources_ready,
+            blocking_fragments_ready,
+            chunks,
+            prefix,
+            pending_resources,
+        ),
         scope_id,
         _,
     ) = run_scope_undisposed(runtime, |cx| {
+        #[cfg(feature = "debug-timing")]
+        leptos_reactive::provide_context(
+            cx,
+            leptos_reactive::resource_timings(cx),
+        );
+        crate::ssr::provide_interned_attrs(cx);
         // add additional context
         additional_context(cx);
 
@@ -103,10 +117,11 @@ pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
         let view = view(cx);
 
         (
+            cx.blocking_resources_ready(),
             cx.blocking_fragments_ready(),
             view.into_stream_chunks(cx),
             prefix,
-            serde_json::to_string(&cx.pending_resources()).unwrap(),
+            cx.pending_resources(),
         )
     });
     let cx = Scope {
@@ -117,6 +132,7 @@ pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
     let (tx, rx) = futures::channel::mpsc::unbounded();
     let (prefix_tx, prefix_rx) = futures::channel::oneshot::channel();
     leptos_reactive::spawn_local(async move {
+        blocking_resources_ready.await;
         blocking_fragments_ready.await;
         let remaining_chunks = handle_blocking_chunks(tx.clone(), chunks).await;
         let prefix = prefix(cx);
@@ -134,26 +150,25 @@ pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
         let nonce_str = nonce_str.clone();
         async move {
             let prefix = prefix_rx.await.expect("to receive prefix");
-            format!(
-                r#"
-        {prefix}
-        <script{nonce_str}>
-            __LEPTOS_PENDING_RESOURCES = {pending_resources};
-            __LEPTOS_RESOLVED_RESOURCES = new Map();
-            __LEPTOS_RESOURCE_RESOLVERS = new Map();
-        </script>
-      "#
-            )
+            let start = render_stream_start(&nonce_str, &pending_resources);
+            format!("{prefix}{start}")
         }
     })
     .chain(rx)
     .chain(
-        futures::stream::once(async move {
-            let serializers = cx.serialization_resolvers();
-            render_serializers(nonce_str, serializers)
+        futures::stream::once({
+            let nonce_str = nonce_str.clone();
+            async move {
+                let serializers = cx.serialization_resolvers();
+                render_serializers(nonce_str, serializers)
+            }
         })
         .flatten(),
-    );
+    )
+    .chain(futures::stream::once(async move {
+        render_stream_done(&nonce_str)
+    }))
+    .chain(resource_timings_comment(cx));
 
     (stream, runtime, scope_id)
 }
@@ -259,7 +274,11 @@ impl View {
                 }
             }
             View::Text(node) => {
-                chunks.push_back(StreamChunk::Sync(node.content))
+                chunks.push_back(StreamChunk::Sync(if dont_escape_text {
+                    node.content
+                } else {
+                    escape_chunk(node.content)
+                }))
             }
             View::Component(node) => {
                 cfg_if! {
@@ -306,46 +325,23 @@ impl View {
                 } else {
                     let tag_name = el.name;
 
-                    let mut inner_html = None;
-
-                    let attrs = el
-                        .attrs
-                        .into_iter()
-                        .filter_map(
-                            |(name, value)| -> Option<Cow<'static, str>> {
-                                if value.is_empty() {
-                                    Some(format!(" {name}").into())
-                                } else if name == "inner_html" {
-                                    inner_html = Some(value);
-                                    None
-                                } else {
-                                    Some(
-                                        format!(
-                    " {name}=\"{}\"",
-                    html_escape::encode_double_quoted_attribute(&value)
-                  )
-                                        .into(),
-                                    )
-                                }
-                            },
-                        )
-                        .join("");
+                    let mut open_tag = String::from("<");
+                    open_tag.push_str(&tag_name);
+                    let inner_html = write_attrs(&mut open_tag, el.attrs);
 
                     if el.is_void {
-                        chunks.push_back(StreamChunk::Sync(
-                            format!("<{tag_name}{attrs}/>").into(),
-                        ));
+                        open_tag.push_str("/>");
+                        chunks.push_back(StreamChunk::Sync(open_tag.into()));
                     } else if let Some(inner_html) = inner_html {
-                        chunks.push_back(StreamChunk::Sync(
-                            format!(
-                                "<{tag_name}{attrs}>{inner_html}</{tag_name}>"
-                            )
-                            .into(),
-                        ));
+                        open_tag.push('>');
+                        open_tag.push_str(&inner_html);
+                        open_tag.push_str("</");
+                        open_tag.push_str(&tag_name);
+                        open_tag.push('>');
+                        chunks.push_back(StreamChunk::Sync(open_tag.into()));
                     } else {
-                        chunks.push_back(StreamChunk::Sync(
-                            format!("<{tag_name}{attrs}>").into(),
-                        ));
+                        open_tag.push('>');
+                        chunks.push_back(StreamChunk::Sync(open_tag.into()));
 
                         match el.children {
                             ElementChildren::Empty => {}
@@ -432,11 +428,7 @@ impl View {
                                             let content = if dont_escape_text {
                                                 content
                                             } else {
-                                                html_escape::encode_safe(
-                                                    &content,
-                                                )
-                                                .to_string()
-                                                .into()
+                                                escape_chunk(content)
                                             };
                                             // On debug builds, `DynChild` has two marker nodes,
                                             // so there is no way for the text to be merged with
@@ -448,18 +440,11 @@ impl View {
                                             chunks.push_back(
                                                 if !cfg!(debug_assertions) {
                                                     StreamChunk::Sync(
-                                                        format!(
-                                                            "<!>{}",
-                                                            html_escape::encode_safe(
-                                                                &content
-                                                            )
-                                                        )
-                                                        .into(),
+                                                        format!("<!>{content}")
+                                                            .into(),
                                                     )
                                                 } else {
-                                                    StreamChunk::Sync(html_escape::encode_safe(
-                                                        &content
-                                                    ).to_string().into())
+                                                    StreamChunk::Sync(content)
                                                 },
                                             );
                                         } else {
@@ -477,14 +462,21 @@ impl View {
                     }
                     CoreComponent::Each(node) => {
                         let children = node.children.take();
+                        let list_marker = node.list_marker();
                         (
                             node.id,
                             "each",
                             true,
                             Box::new(
                                 move |chunks: &mut VecDeque<StreamChunk>| {
+                                    chunks.push_back(StreamChunk::Sync(
+                                        list_marker.into(),
+                                    ));
                                     for node in children.into_iter().flatten() {
                                         let id = node.id;
+                                        chunks.push_back(StreamChunk::Sync(
+                                            node.key_marker().into(),
+                                        ));
 
                                         #[cfg(debug_assertions)]
                                         {
@@ -560,3 +552,12 @@ impl View {
         }
     }
 }
+
+/// Escapes text content, only allocating if there is something to escape.
+fn escape_chunk(content: Cow<'static, str>) -> Cow<'static, str> {
+    let escaped = match escape_text(&content) {
+        Cow::Owned(escaped) => Some(escaped),
+        Cow::Borrowed(_) => None,
+    };
+    escaped.map(Cow::Owned).unwrap_or(content)
+}
@@ -91,6 +91,12 @@ macro_rules! generate_svg_tags {
                 crate::warn!(
                   "element with id {id} not found, ignoring it for hydration"
                 );
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                  hydration_key = %id,
+                  element = stringify!($tag),
+                  "hydration mismatch: element not found"
+                );
 
                 [<$tag:upper $(_ $second:upper $(_ $third:upper)?)?>]
                   .with(|el|
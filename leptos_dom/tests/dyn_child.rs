@@ -0,0 +1,142 @@
+// the browser tests run with `wasm-pack test --headless --firefox --features web`
+use leptos::*;
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+use wasm_bindgen_test::*;
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn items(ul: &web_sys::HtmlUListElement) -> Vec<web_sys::Element> {
+    let items = ul.query_selector_all("li").unwrap();
+    (0..items.length())
+        .map(|i| wasm_bindgen::JsCast::unchecked_into(items.get(i).unwrap()))
+        .collect()
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[wasm_bindgen_test]
+fn list_updates_keep_nodes_that_render_the_same() {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let (messages, set_messages) = create_signal(cx, vec!["a", "b", "c"]);
+    let ul = html::ul(cx).child(move || {
+        messages
+            .get()
+            .into_iter()
+            .map(|message| html::li(cx).child(message))
+            .collect::<Vec<_>>()
+    });
+
+    let before = items(&ul);
+    assert_eq!(before.len(), 3);
+
+    // appending keeps every existing item
+    set_messages.set(vec!["a", "b", "c", "d"]);
+    let after = items(&ul);
+    assert_eq!(after.len(), 4);
+    assert!(before
+        .iter()
+        .zip(&after)
+        .all(|(before, after)| before == after));
+
+    // changing an item in the middle only replaces that item
+    set_messages.set(vec!["a", "x", "c", "d"]);
+    let changed = items(&ul);
+    assert_eq!(changed[0], after[0]);
+    assert_ne!(changed[1], after[1]);
+    assert_eq!(changed[1].text_content().unwrap(), "x");
+    assert_eq!(changed[2], after[2]);
+    assert_eq!(changed[3], after[3]);
+
+    // removing an item keeps the ones around it, in order
+    set_messages.set(vec!["a", "c", "d"]);
+    let removed = items(&ul);
+    assert_eq!(
+        removed,
+        vec![changed[0].clone(), changed[2].clone(), changed[3].clone()]
+    );
+    assert_eq!(ul.text_content().unwrap(), "acd");
+
+    disposer.dispose();
+    runtime.dispose();
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[wasm_bindgen_test]
+fn list_updates_replace_nodes_with_handlers_so_they_see_the_new_data() {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    // the IDs aren't rendered, so both lists render the same HTML
+    let (messages, set_messages) =
+        create_signal(cx, vec![(1, "a"), (2, "b")]);
+    let clicked = create_rw_signal(cx, None);
+    let ul = html::ul(cx).child(move || {
+        messages
+            .get()
+            .into_iter()
+            .map(|(id, message)| {
+                html::li(cx)
+                    .on(ev::click, move |_| clicked.set(Some(id)))
+                    .child(message)
+            })
+            .collect::<Vec<_>>()
+    });
+    // delegated events are only dispatched within the document
+    document().body().unwrap().append_child(&ul).unwrap();
+
+    let before = items(&ul);
+    set_messages.set(vec![(3, "a"), (4, "b")]);
+    let after = items(&ul);
+    assert_ne!(before[0], after[0]);
+    assert_ne!(before[1], after[1]);
+
+    wasm_bindgen::JsCast::unchecked_ref::<web_sys::HtmlElement>(&after[0])
+        .click();
+    assert_eq!(clicked.get(), Some(3));
+
+    ul.remove();
+    disposer.dispose();
+    runtime.dispose();
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web", debug_assertions))]
+#[wasm_bindgen_test]
+fn rerendering_a_child_removes_the_handlers_of_the_old_elements() {
+    use leptos::{ev::EventDescriptor, js_sys::Reflect, wasm_bindgen::JsValue};
+
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let baseline = leptos_dom::live_event_handlers();
+    let (count, set_count) = create_signal(cx, 0);
+    let buttons = store_value(cx, Vec::new());
+    let div = html::div(cx).child(DynChild::new(move || {
+        // created with the long-lived `cx`, but while the child scope runs
+        let button = html::button(cx)
+            .on(ev::click, |_| ())
+            .on(ev::focus, |_| ())
+            .child(count.get());
+        buttons.update_value(|buttons| buttons.push(button.clone()));
+        button
+    }));
+
+    for n in 1..=100 {
+        set_count.set(n);
+    }
+    assert_eq!(div.text_content().unwrap(), "100");
+    assert_eq!(leptos_dom::live_event_handlers(), baseline + 2);
+
+    // the replaced buttons no longer hold on to their delegated handlers
+    let key = JsValue::from_str(&ev::click.event_delegation_key());
+    buttons.with_value(|buttons| {
+        let (current, replaced) = buttons.split_last().unwrap();
+        assert!(Reflect::has(current, &key).unwrap());
+        assert!(replaced
+            .iter()
+            .all(|button| !Reflect::has(button, &key).unwrap()));
+    });
+
+    disposer.dispose();
+    assert_eq!(leptos_dom::live_event_handlers(), baseline);
+    runtime.dispose();
+}
@@ -0,0 +1,91 @@
+// run with `wasm-pack test --headless --firefox --features hydrate`
+#![cfg(all(target_arch = "wasm32", feature = "hydrate"))]
+
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// what the server renders for `list` with the rows in the order `[B, A]`, in
+// a debug build
+const SERVER_HTML: &str = "<!--hk=_0-1o|leptos--start--><ul id=\"_0-2\">\
+     <!--hk=_0-3o|leptos-each-start--><!--hk=_0-3l|leptos-each-->\
+     <!--hk=_14463946626115202761-0l|leptos-each-item-->\
+     <!--hk=_14463946626115202761-3o|leptos-each-item-start-->\
+     <li id=\"_14463946626115202761-1\">\
+     <button id=\"_14463946626115202761-2\">B</button></li>\
+     <!--hk=_14463946626115202761-3c|leptos-each-item-end-->\
+     <!--hk=_14466761375882855246-0l|leptos-each-item-->\
+     <!--hk=_14466761375882855246-3o|leptos-each-item-start-->\
+     <li id=\"_14466761375882855246-1\">\
+     <button id=\"_14466761375882855246-2\">A</button></li>\
+     <!--hk=_14466761375882855246-3c|leptos-each-item-end-->\
+     <!--hk=_0-3c|leptos-each-end--></ul><p id=\"_0-4\">after</p>\
+     <!--hk=_0-1c|leptos--end-->";
+
+fn list(
+    cx: Scope,
+    order: [&'static str; 2],
+    clicked: Rc<Cell<Option<&'static str>>>,
+) -> impl IntoView {
+    view! { cx,
+        <ul>
+            <For
+                each=move || order
+                key=|row| *row
+                view=move |cx, row| {
+                    let clicked = Rc::clone(&clicked);
+                    view! { cx,
+                        <li><button on:click=move |_| clicked.set(Some(row))>{row}</button></li>
+                    }
+                }
+            />
+        </ul>
+        <p>"after"</p>
+    }
+}
+
+fn buttons(root: &web_sys::Element) -> Vec<web_sys::HtmlElement> {
+    let buttons = root.query_selector_all("button").unwrap();
+    (0..buttons.length())
+        .map(|i| buttons.get(i).unwrap().unchecked_into())
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn rows_are_claimed_by_key_when_the_client_order_differs() {
+    let root = document().create_element("div").unwrap();
+    root.set_inner_html(SERVER_HTML);
+    document().body().unwrap().append_child(&root).unwrap();
+    let server_buttons = buttons(&root);
+    assert_eq!(server_buttons[0].text_content().as_deref(), Some("B"));
+
+    let clicked = Rc::new(Cell::new(None));
+    let disposer = hydrate_to(root.clone().unchecked_into(), {
+        let clicked = Rc::clone(&clicked);
+        move |cx| list(cx, ["A", "B"], clicked)
+    });
+
+    // the rows are in the client's order, and are the server's nodes
+    let client_buttons = buttons(&root);
+    let text = |button: &web_sys::HtmlElement| button.text_content().unwrap();
+    assert_eq!(
+        client_buttons.iter().map(text).collect::<Vec<_>>(),
+        ["A", "B"],
+        "{}",
+        root.inner_html()
+    );
+    assert_eq!(client_buttons[0], server_buttons[1]);
+    assert_eq!(client_buttons[1], server_buttons[0]);
+    assert_eq!(root.query_selector_all("li").unwrap().length(), 2);
+
+    // each row's listener is attached to the row with its key
+    for button in &client_buttons {
+        button.click();
+        assert_eq!(clicked.get().map(str::to_string), Some(text(button)));
+    }
+
+    disposer.dispose();
+}
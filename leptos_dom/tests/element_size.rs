@@ -0,0 +1,94 @@
+// run with `wasm-pack test --headless --firefox --features web`
+#![cfg(all(target_arch = "wasm32", feature = "web"))]
+
+use leptos::*;
+use wasm_bindgen_futures::{js_sys, JsFuture};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// resize observations are delivered before the next paint
+async fn next_frames() {
+    for _ in 0..3 {
+        let frame = js_sys::Promise::new(&mut |resolve, _| {
+            window().request_animation_frame(&resolve).unwrap();
+        });
+        JsFuture::from(frame).await.unwrap();
+    }
+}
+
+#[wasm_bindgen_test]
+async fn size_follows_the_element_and_ignores_subpixel_changes() {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let container = create_node_ref::<html::Div>(cx);
+    let size = use_element_size(cx, container);
+    // a second hook on the same element gets its size too
+    let other = use_element_size(cx, container);
+    let runs = create_rw_signal(cx, 0);
+    create_effect(cx, move |_| {
+        size.width.get();
+        runs.update(|runs| *runs += 1);
+    });
+
+    let div = html::div(cx).attr("style", "width: 200px; height: 100px");
+    document().body().unwrap().append_child(&div).unwrap();
+    container.load(&div);
+    next_frames().await;
+
+    assert_eq!(size.width.get_untracked(), 200.0);
+    assert_eq!(size.height.get_untracked(), 100.0);
+    assert_eq!(other.width.get_untracked(), 200.0);
+    let runs_before = runs.get_untracked();
+
+    div.set_attribute("style", "width: 200.2px; height: 100px")
+        .unwrap();
+    next_frames().await;
+    assert_eq!(size.width.get_untracked(), 200.0);
+    assert_eq!(runs.get_untracked(), runs_before);
+
+    div.set_attribute("style", "width: 320px; height: 100px")
+        .unwrap();
+    next_frames().await;
+    assert_eq!(size.width.get_untracked(), 320.0);
+    assert_eq!(other.width.get_untracked(), 320.0);
+
+    disposer.dispose();
+    div.remove();
+    runtime.dispose();
+}
+
+#[wasm_bindgen_test]
+async fn bounding_follows_scrolling() {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let target = create_node_ref::<html::Div>(cx);
+    let bounding = use_element_bounding(cx, target);
+
+    let spacer = html::div(cx).attr("style", "height: 3000px");
+    let div = html::div(cx)
+        .attr("style", "margin-left: 40px; width: 50px; height: 20px");
+    let body = document().body().unwrap();
+    body.append_child(&div).unwrap();
+    body.append_child(&spacer).unwrap();
+    target.load(&div);
+    next_frames().await;
+
+    let top = bounding.top.get_untracked();
+    assert_eq!(
+        bounding.left.get_untracked(),
+        div.get_bounding_client_rect().left()
+    );
+    assert_eq!(bounding.width.get_untracked(), 50.0);
+    assert_eq!(bounding.bottom.get_untracked(), top + 20.0);
+
+    window().scroll_to_with_x_and_y(0.0, 100.0);
+    next_frames().await;
+    assert_eq!(bounding.top.get_untracked(), top - 100.0);
+
+    window().scroll_to_with_x_and_y(0.0, 0.0);
+    disposer.dispose();
+    div.remove();
+    spacer.remove();
+    runtime.dispose();
+}
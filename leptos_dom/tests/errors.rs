@@ -0,0 +1,201 @@
+use leptos::*;
+
+#[derive(Debug, Clone)]
+struct RowError(u32);
+
+impl std::fmt::Display for RowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "row {} failed", self.0)
+    }
+}
+
+impl std::error::Error for RowError {}
+
+#[test]
+fn errors_keep_the_order_they_occurred_in() {
+    let mut errors = Errors::default();
+    for row in [3, 1, 2] {
+        errors.insert(ErrorKey::from(format!("row-{row}")), RowError(row));
+    }
+    // replacing an error keeps its position
+    errors.insert("row-1".into(), RowError(10));
+
+    let messages = |errors: &Errors| {
+        errors
+            .iter()
+            .map(|(key, error)| format!("{key}: {error}"))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        messages(&errors),
+        [
+            "row-3: row 3 failed",
+            "row-1: row 10 failed",
+            "row-2: row 2 failed"
+        ]
+    );
+
+    assert!(errors.remove(&"row-1".into()).is_some());
+    assert!(errors.remove(&"row-1".into()).is_none());
+    assert_eq!(
+        messages(&errors),
+        ["row-3: row 3 failed", "row-2: row 2 failed"]
+    );
+    assert_eq!(errors.len(), 2);
+    assert!(errors.contains_key(&"row-2".into()));
+
+    let keys = errors
+        .clone()
+        .into_iter()
+        .map(|(key, _)| key.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(keys, ["row-3", "row-2"]);
+
+    errors.clear();
+    assert!(errors.is_empty());
+}
+
+// run with `wasm-pack test --headless --firefox --features web`
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod retry {
+    use super::RowError;
+    use leptos::*;
+    use std::{cell::Cell, rc::Rc};
+    use wasm_bindgen_futures::{js_sys, JsFuture};
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn tick() {
+        for _ in 0..5 {
+            JsFuture::from(js_sys::Promise::resolve(
+                &wasm_bindgen::JsValue::NULL,
+            ))
+            .await
+            .unwrap();
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn removing_an_error_reruns_only_the_view_that_returned_it() {
+        let runtime = create_runtime();
+        let (cx, disposer) = raw_scope_and_disposer(runtime);
+        let runs = Rc::new([Cell::new(0), Cell::new(0), Cell::new(0)]);
+        // rows 1 and 2 fail until they are retried
+        let fixed = create_rw_signal(cx, false);
+        let errors = Rc::new(Cell::new(None::<RwSignal<Errors>>));
+
+        let view = view! { cx,
+            <ErrorBoundary fallback={
+                let errors = Rc::clone(&errors);
+                move |_, boundary_errors| errors.set(Some(boundary_errors))
+            }>
+                {(0..3)
+                    .map(|row| {
+                        let runs = Rc::clone(&runs);
+                        view! { cx,
+                            <p>
+                                {move || {
+                                    runs[row].set(runs[row].get() + 1);
+                                    if row == 0 || fixed.get_untracked() {
+                                        Ok(format!("row {row}"))
+                                    } else {
+                                        Err(RowError(row as u32))
+                                    }
+                                }}
+                            </p>
+                        }
+                    })
+                    .collect_view(cx)}
+            </ErrorBoundary>
+        };
+        let el = html::div(cx).child(view);
+        tick().await;
+
+        let errors = errors.get().expect("fallback was shown");
+        let keys = errors.with_untracked(|errors| {
+            errors
+                .iter()
+                .map(|(key, _)| key.clone())
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(keys.len(), 2);
+        let counts = || runs.iter().map(Cell::get).collect::<Vec<_>>();
+        assert_eq!(counts(), [1, 1, 1]);
+
+        // retrying row 1 runs it again, and leaves the error from row 2
+        fixed.set(true);
+        errors.update(|errors| _ = errors.remove(&keys[0]));
+        tick().await;
+        assert_eq!(counts(), [1, 2, 1]);
+        assert_eq!(errors.with_untracked(Errors::len), 1);
+        assert!(errors.with_untracked(|errors| errors.contains_key(&keys[1])));
+
+        errors.update(|errors| _ = errors.remove(&keys[1]));
+        tick().await;
+        assert_eq!(counts(), [1, 2, 2]);
+        assert!(errors.with_untracked(Errors::is_empty));
+        assert!(el.text_content().unwrap().contains("row 2"));
+
+        disposer.dispose();
+        runtime.dispose();
+    }
+
+    #[wasm_bindgen_test]
+    async fn clearing_the_errors_reruns_the_views_that_returned_them() {
+        let runtime = create_runtime();
+        let (cx, disposer) = raw_scope_and_disposer(runtime);
+        let runs = Rc::new([Cell::new(0), Cell::new(0)]);
+        let fixed = create_rw_signal(cx, false);
+        let errors = Rc::new(Cell::new(None::<RwSignal<Errors>>));
+
+        let view = view! { cx,
+            <ErrorBoundary fallback={
+                let errors = Rc::clone(&errors);
+                move |_, boundary_errors| errors.set(Some(boundary_errors))
+            }>
+                {(0..2)
+                    .map(|row| {
+                        let runs = Rc::clone(&runs);
+                        view! { cx,
+                            <p>
+                                {move || {
+                                    runs[row].set(runs[row].get() + 1);
+                                    if fixed.get_untracked() {
+                                        Ok(format!("row {row}"))
+                                    } else {
+                                        Err(RowError(row as u32))
+                                    }
+                                }}
+                            </p>
+                        }
+                    })
+                    .collect_view(cx)}
+            </ErrorBoundary>
+        };
+        let el = html::div(cx).child(view);
+        tick().await;
+
+        let errors = errors.get().expect("fallback was shown");
+        assert_eq!(errors.with_untracked(Errors::len), 2);
+        let counts = || runs.iter().map(Cell::get).collect::<Vec<_>>();
+        assert_eq!(counts(), [1, 1]);
+
+        // a view that fails again puts its error back
+        errors.update(Errors::clear);
+        tick().await;
+        assert_eq!(counts(), [2, 2]);
+        assert_eq!(errors.with_untracked(Errors::len), 2);
+
+        fixed.set(true);
+        errors.update(Errors::clear);
+        tick().await;
+        assert_eq!(counts(), [3, 3]);
+        assert!(errors.with_untracked(Errors::is_empty));
+        let text = el.text_content().unwrap();
+        assert!(text.contains("row 0") && text.contains("row 1"), "{text}");
+
+        disposer.dispose();
+        runtime.dispose();
+    }
+}
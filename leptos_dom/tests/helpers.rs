@@ -0,0 +1,53 @@
+#![cfg(not(all(target_arch = "wasm32", feature = "web")))]
+
+use leptos::*;
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+#[test]
+fn visibility_and_online_default_to_active_on_the_server() {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+
+    assert_eq!(use_document_visibility(cx).get(), VisibilityState::Visible);
+    assert!(use_online(cx).get());
+
+    disposer.dispose();
+    runtime.dispose();
+}
+
+#[test]
+fn element_size_and_bounding_are_zero_on_the_server() {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let target = create_node_ref::<html::Div>(cx);
+
+    let size = use_element_size(cx, target);
+    assert_eq!((size.width.get(), size.height.get()), (0.0, 0.0));
+    let bounding = use_element_bounding(cx, target);
+    assert_eq!((bounding.top.get(), bounding.width.get()), (0.0, 0.0));
+
+    disposer.dispose();
+    runtime.dispose();
+}
+
+#[test]
+fn poll_interval_does_not_refetch_on_the_server() {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let fetches = Rc::new(Cell::new(0));
+    let resource = create_resource(cx, || (), {
+        let fetches = Rc::clone(&fetches);
+        move |_| {
+            fetches.set(fetches.get() + 1);
+            async {}
+        }
+    });
+    let initial = fetches.get();
+
+    poll_interval(cx, resource, Duration::from_millis(1));
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(fetches.get(), initial);
+
+    disposer.dispose();
+    runtime.dispose();
+}
@@ -0,0 +1,78 @@
+// run with `wasm-pack test --headless --firefox --features hydrate`
+#![cfg(all(target_arch = "wasm32", feature = "hydrate"))]
+
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// what the server renders for `app`, in a debug build
+const SERVER_HTML: &str = "<!--hk=_0-1o|leptos--start-->\
+     <!--hk=_0-2o|leptos-lazy-hydrate-start-->\
+     <leptos-lazy-hydrate style=\"display: contents\" data-hk-end=\"0-5\" \
+     id=\"_0-3\"><!--leptos-view|<LazyHydrate/>-children|open-->\
+     <!--hk=_0-4o|leptos--start--><button id=\"_0-5\">lazy</button>\
+     <!--hk=_0-4c|leptos--end-->\
+     <!--leptos-view|<LazyHydrate/>-children|close--></leptos-lazy-hydrate>\
+     <!--hk=_0-2c|leptos-lazy-hydrate-end--><p id=\"_0-6\">after</p>\
+     <!--hk=_0-1c|leptos--end-->";
+
+fn app(
+    cx: Scope,
+    hydrated: Rc<Cell<bool>>,
+    clicks: Rc<Cell<usize>>,
+) -> impl IntoView {
+    view! { cx,
+        <LazyHydrate on=HydrateOn::Interaction>
+            {
+                hydrated.set(true);
+                let clicks = Rc::clone(&clicks);
+                view! { cx,
+                    <button on:click=move |_| clicks.set(clicks.get() + 1)>"lazy"</button>
+                }
+            }
+        </LazyHydrate>
+        <p>"after"</p>
+    }
+}
+
+#[wasm_bindgen_test]
+fn first_interaction_hydrates_the_subtree_and_reaches_its_handler() {
+    let root = document().create_element("div").unwrap();
+    root.set_inner_html(SERVER_HTML);
+    document().body().unwrap().append_child(&root).unwrap();
+    let server_button = root.query_selector("button").unwrap().unwrap();
+    let button: web_sys::HtmlElement = server_button.clone().unchecked_into();
+    let after = root.query_selector("p").unwrap().unwrap();
+
+    let hydrated = Rc::new(Cell::new(false));
+    let clicks = Rc::new(Cell::new(0));
+    let disposer = hydrate_to(root.clone().unchecked_into(), {
+        let hydrated = Rc::clone(&hydrated);
+        let clicks = Rc::clone(&clicks);
+        move |cx| app(cx, hydrated, clicks)
+    });
+
+    // the rest of the page is hydrated without waiting for the subtree
+    assert!(!hydrated.get());
+    assert_eq!(root.query_selector("p").unwrap().unwrap(), after);
+
+    // the click hydrates the subtree on its way to the button, and is then
+    // handled by the button's new listener
+    button.click();
+    assert!(hydrated.get());
+    assert_eq!(clicks.get(), 1);
+
+    // the server's button was claimed rather than replaced
+    assert_eq!(
+        root.query_selector("button").unwrap().unwrap(),
+        server_button
+    );
+    assert_eq!(root.query_selector_all("button").unwrap().length(), 1);
+    button.click();
+    assert_eq!(clicks.get(), 2);
+
+    disposer.dispose();
+}
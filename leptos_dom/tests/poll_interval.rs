@@ -0,0 +1,47 @@
+// run with `wasm-pack test --headless --firefox --features web`
+#![cfg(all(target_arch = "wasm32", feature = "web"))]
+
+use leptos::*;
+use std::{cell::Cell, rc::Rc, time::Duration};
+use wasm_bindgen_futures::{js_sys::Promise, JsFuture};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    JsFuture::from(Promise::new(&mut |resolve, _| {
+        window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    }))
+    .await
+    .unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn resource_is_refetched_every_interval_until_disposed() {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let fetches = Rc::new(Cell::new(0));
+    let resource = create_local_resource(cx, || (), {
+        let fetches = Rc::clone(&fetches);
+        move |_| {
+            fetches.set(fetches.get() + 1);
+            async {}
+        }
+    });
+    poll_interval(cx, resource, Duration::from_millis(50));
+    sleep(10).await;
+    let initial = fetches.get();
+
+    // the test page is visible and online, so polling is active
+    sleep(175).await;
+    assert!(fetches.get() >= initial + 3, "{}", fetches.get());
+
+    disposer.dispose();
+    let disposed = fetches.get();
+    sleep(150).await;
+    assert_eq!(fetches.get(), disposed);
+
+    runtime.dispose();
+}
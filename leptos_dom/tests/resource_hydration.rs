@@ -0,0 +1,141 @@
+// run with `wasm-pack test --headless --firefox --features hydrate`
+#![cfg(all(target_arch = "wasm32", feature = "hydrate"))]
+
+use leptos::{
+    leptos_dom::{render_serializer, render_stream_done, render_stream_start},
+    *,
+};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_futures::{js_sys, JsFuture};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// the first resource created in a new runtime
+fn id() -> ResourceId {
+    serde_json::from_str(r#"{"idx":1,"version":1}"#).unwrap()
+}
+
+// runs a `<script>` as the server renders it
+fn run_script(html: &str) {
+    let script = html
+        .trim()
+        .strip_prefix("<script>")
+        .and_then(|script| script.strip_suffix("</script>"))
+        .unwrap();
+    js_sys::eval(script).unwrap();
+}
+
+// the script at the start of the stream, which marks the resource as pending
+fn start_stream() {
+    run_script(&render_stream_start("", &[id()]));
+}
+
+// the script that streams the resource's value
+fn send_value(json: &str) {
+    run_script(&render_serializer("", id(), json));
+}
+
+// the script at the end of the stream
+fn end_stream() {
+    run_script(&render_stream_done(""));
+}
+
+async fn tick() {
+    for _ in 0..5 {
+        JsFuture::from(js_sys::Promise::resolve(&wasm_bindgen::JsValue::NULL))
+            .await
+            .unwrap();
+    }
+}
+
+// a resource that counts how many times it is fetched on the client
+fn create_post(cx: Scope, fetches: Rc<Cell<usize>>) -> Resource<(), String> {
+    create_resource(
+        cx,
+        || (),
+        move |_| {
+            fetches.set(fetches.get() + 1);
+            async { "from the client".to_string() }
+        },
+    )
+}
+
+#[wasm_bindgen_test]
+async fn values_that_arrive_before_hydrating_are_used() {
+    start_stream();
+    send_value("\"from the server\"");
+
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let fetches = Rc::new(Cell::new(0));
+    let post = create_post(cx, Rc::clone(&fetches));
+
+    assert_eq!(post.get(), Some("from the server".to_string()));
+    assert_eq!(fetches.get(), 0);
+
+    disposer.dispose();
+    runtime.dispose();
+}
+
+#[wasm_bindgen_test]
+async fn values_that_arrive_while_hydrating_are_used() {
+    start_stream();
+
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    // arrives after the runtime has read the pending resources, but before
+    // the component that reads it has hydrated
+    send_value("\"from the server\"");
+    let fetches = Rc::new(Cell::new(0));
+    let post = create_post(cx, Rc::clone(&fetches));
+
+    assert_eq!(post.get(), Some("from the server".to_string()));
+    assert_eq!(fetches.get(), 0);
+
+    disposer.dispose();
+    runtime.dispose();
+}
+
+#[wasm_bindgen_test]
+async fn values_that_arrive_after_hydrating_resolve_the_resource() {
+    start_stream();
+
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let fetches = Rc::new(Cell::new(0));
+    let post = create_post(cx, Rc::clone(&fetches));
+    assert_eq!(post.get(), None);
+    assert!(post.loading().get_untracked());
+
+    send_value("\"from the server\"");
+    assert_eq!(post.get(), Some("from the server".to_string()));
+    assert!(!post.loading().get_untracked());
+
+    // the end of the stream doesn't fetch a resource that has resolved
+    end_stream();
+    tick().await;
+    assert_eq!(fetches.get(), 0);
+
+    disposer.dispose();
+    runtime.dispose();
+}
+
+#[wasm_bindgen_test]
+async fn values_that_never_arrive_are_fetched_on_the_client() {
+    start_stream();
+
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let fetches = Rc::new(Cell::new(0));
+    let post = create_post(cx, Rc::clone(&fetches));
+    assert_eq!(post.get(), None);
+
+    end_stream();
+    tick().await;
+    assert_eq!(fetches.get(), 1);
+    assert_eq!(post.get(), Some("from the client".to_string()));
+
+    disposer.dispose();
+    runtime.dispose();
+}
@@ -0,0 +1,84 @@
+#![cfg(not(all(target_arch = "wasm32", feature = "web")))]
+
+use futures::StreamExt;
+use leptos::*;
+
+// a list that is long enough for its shell to be streamed in several chunks
+fn long_list(cx: Scope) -> View {
+    view! { cx,
+        <ul>
+            {(0..2000).map(|n| view! { cx, <li>"item " {n}</li> }).collect_view(cx)}
+        </ul>
+    }
+    .into_view(cx)
+}
+
+#[test]
+fn streamed_shell_is_split_at_element_boundaries() {
+    let html = leptos::ssr::render_to_string(long_list);
+    let chunks = futures::executor::block_on(
+        leptos::ssr::render_to_stream(long_list).collect::<Vec<_>>(),
+    );
+
+    // the last chunk of the shell ends with the script that stores resources
+    let last = chunks
+        .iter()
+        .position(|chunk| chunk.contains("__LEPTOS_PENDING_RESOURCES"))
+        .expect("the shell should be streamed");
+    assert!(last > 0, "the shell should be split into several chunks");
+    for pair in chunks[..=last].windows(2) {
+        assert!(pair[0].ends_with('>'), "{}", pair[0]);
+        assert!(pair[1].starts_with('<'), "{}", pair[1]);
+    }
+
+    // the chunks add up to the same HTML as rendering to a string
+    let shell = chunks[..=last].concat();
+    let (shell, _) = shell.split_once("<script").unwrap();
+    assert_eq!(shell, html);
+}
+
+#[test]
+fn streamed_shell_is_rendered_as_it_is_sent() {
+    use leptos::leptos_dom::html::StringOrView;
+    use std::{cell::Cell, rc::Rc};
+
+    let rendered = Rc::new(Cell::new(false));
+    let mut stream = Box::pin(leptos::ssr::render_to_stream({
+        let rendered = Rc::clone(&rendered);
+        move |cx| {
+            // the end of the shell, which notes when it is rendered; an
+            // element built from chunks writes only its chunks
+            let end = HtmlElement::from_chunks(
+                cx,
+                html::Div::default(),
+                [StringOrView::View(Rc::new(move || {
+                    rendered.set(true);
+                    "end".into_view(cx)
+                }))],
+            );
+            view! { cx, <main>{long_list(cx)} {end}</main> }.into_view(cx)
+        }
+    }));
+
+    let first = futures::executor::block_on(stream.next()).unwrap();
+    assert!(first.contains("item 0"), "{first}");
+    assert!(!rendered.get(), "the end of the shell was already rendered");
+
+    let rest = futures::executor::block_on(stream.collect::<Vec<_>>()).concat();
+    assert!(rendered.get());
+    assert!(rest.contains("</ul>end</main>"), "{rest}");
+}
+
+#[test]
+fn short_shell_is_streamed_in_one_chunk() {
+    let chunks = futures::executor::block_on(
+        leptos::ssr::render_to_stream(|cx| {
+            view! { cx, <p>"Hello, world!"</p> }.into_view(cx)
+        })
+        .collect::<Vec<_>>(),
+    );
+
+    assert!(chunks[0].contains("Hello, world!</p>"), "{}", chunks[0]);
+    assert!(chunks[1].starts_with("<script"), "{}", chunks[1]);
+    assert!(chunks[1].contains("__LEPTOS_PENDING_RESOURCES"));
+}
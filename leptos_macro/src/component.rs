@@ -187,16 +187,12 @@ impl ToTokens for Model {
             (
                 quote! {
                     #[allow(clippy::let_with_type_underscore)]
-                    #[cfg_attr(
-                        any(debug_assertions, feature="ssr"),
-                        ::leptos::leptos_dom::tracing::instrument(level = "info", name = #trace_name, skip_all)
-                    )]
+                    #[::leptos::leptos_dom::tracing::instrument(level = "info", name = #trace_name, skip_all)]
                 },
                 quote! {
                     let span = ::leptos::leptos_dom::tracing::Span::current();
                 },
                 quote! {
-                    #[cfg(debug_assertions)]
                     let _guard = span.entered();
                 },
                 if no_props {
@@ -91,6 +91,8 @@ mod slot;
 ///
 ///    Attributes can take a wide variety of primitive types that can be converted to strings. They can also
 ///    take an `Option`, in which case `Some` sets the attribute and `None` removes the attribute.
+///    A `bool` (or `Option<bool>`) sets a boolean attribute like `disabled` with no value when `true`,
+///    and leaves it out entirely when `false` or `None`, rather than setting it to `"false"`.
 ///
 /// ```rust,ignore
 /// # use leptos::*;
@@ -131,9 +133,35 @@ mod slot;
 /// # });
 /// ```
 ///
+///    The name of the event can be followed by modifiers, in any order: `:prevent_default` and
+///    `:stop_propagation` call the event method of the same name before the handler runs, and
+///    `:undelegated` attaches the listener directly to the element instead of delegating it.
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// view! {
+///   cx,
+///   <form on:submit:prevent_default=|_| log::debug!("submitted")>
+///     <button on:click:prevent_default:stop_propagation=|_| log::debug!("clicked")>
+///       "Click me"
+///     </button>
+///   </form>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// 6. DOM properties can be set with `prop:` attributes, which take any primitive type or `JsValue` (or a signal
 ///    that returns a primitive or JsValue). They can also take an `Option`, in which case `Some` sets the property
 ///    and `None` deletes the property.
+///
+///    `value`, `checked`, and `selected` on `<input>`, `<textarea>`, `<select>`, and `<option>` are a special
+///    case: as attributes they only set the control’s initial state, so in the browser a reactive value is set as a
+///    property, and kept in sync with the control’s current state. Static values, and every value on the server,
+///    are still rendered as attributes, so the server-rendered form starts out right; a `<textarea>`’s value is
+///    rendered as its text content. Use `attr:value` if you really want the attribute.
 /// ```rust
 /// # use leptos::*;
 /// # run_scope(create_runtime(), |cx| {
@@ -145,8 +173,8 @@ mod slot;
 ///   <input
 ///     type="text"
 ///     name="user_name"
-///     value={move || name.get()} // this only sets the default value!
-///     prop:value={move || name.get()} // here's how you update values. Sorry, I didn’t invent the DOM.
+///     value={move || name.get()} // rendered on the server, and kept up to date in the browser
+///     prop:autocomplete="off" // sets the `autocomplete` property in the browser
 ///     on:click=move |ev| set_name.set(event_target_value(&ev)) // `event_target_value` is a useful little Leptos helper
 ///   />
 /// }
@@ -233,6 +261,28 @@ mod slot;
 /// # });
 /// ```
 ///
+///    `style` can also be a list of `("property-name", value)` pairs. Each property is set on its
+///    own, so it doesn't overwrite other inline styles, and a property whose value is `None`, or
+///    that is left out of the list the next time it changes, is removed.
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let (x, set_x) = create_signal(cx, 0.0);
+/// let (fading, set_fading) = create_signal(cx, false);
+/// view! { cx,
+///   <div style=move || [
+///     ("transform", Some(format!("translateX({}px)", x.get()))),
+///     ("opacity", fading.get().then(|| "0.5".to_string())),
+///   ]>
+///     "Slides and fades"
+///   </div>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// 9. You can use the `node_ref` or `_ref` attribute to store a reference to its DOM element in a
 ///    [NodeRef](https://docs.rs/leptos/latest/leptos/struct.NodeRef.html) to use later.
 /// ```rust
@@ -250,6 +300,29 @@ mod slot;
 /// # });
 /// ```
 ///
+///    A component can accept a `node_ref` in the same way by declaring an optional `node_ref`
+///    prop and passing it on to one of its elements.
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// use leptos::html::Input;
+///
+/// #[component]
+/// fn TextInput(
+///     cx: Scope,
+///     #[prop(optional)] node_ref: Option<NodeRef<Input>>,
+/// ) -> impl IntoView {
+///     view! { cx, <input type="text" node_ref=node_ref/> }
+/// }
+///
+/// let my_input = create_node_ref::<Input>(cx);
+/// view! { cx, <TextInput _ref=my_input/> }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// 10. You can add the same class to every element in the view by passing in a special
 ///    `class = {/* ... */},` argument after `cx, `. This is useful for injecting a class
 ///    provided by a scoped styling library.
@@ -284,6 +357,67 @@ mod slot;
 /// # });
 /// ```
 ///
+/// 12. HTML comments are stripped from the output. Their content can be a string or any other
+///     Rust tokens.
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// view! { cx,
+///   <!-- the list of items -->
+///   <ul>
+///     <!-- "a quoted comment" -->
+///     <li>"First"</li>
+///   </ul>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
+/// 13. Preformatted text, like a code sample, can be written as a raw string literal, so that
+///     quotes and braces do not need to be escaped. It is rendered as text, exactly as written.
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// view! { cx,
+///   <pre>r#"fn main() {
+///     println!("Hello, world!");
+/// }"#</pre>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
+/// 14. The markup is checked when it is compiled: an element name that isn’t HTML, SVG or MathML
+///     (unless it is a custom element, whose name contains a dash), an attribute given twice on the
+///     same element, or an element nested where the browser would move it out when it parses the
+///     server-rendered HTML, like a `<div>` inside a `<p>` or a link inside a `<button>`, is an error.
+///     A void element with children, like `<img>"text"</img>`, is a warning on `nightly`.
+/// ```rust,compile_fail
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// // ❌ `<div>` can’t be inside a `<p>`
+/// view! { cx, <p><div>"Hello"</div></p> }
+/// # ;
+/// # }
+/// # });
+/// ```
+///     Markup that is meant to be this way can opt out for the whole view with
+///     `#[allow(invalid_html)]` at its start.
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// view! { cx, #[allow(invalid_html)] <p><div>"Hello"</div></p> }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// Here’s a simple example that shows off several of these features, put together
 /// ```rust
 /// # use leptos::*;
@@ -328,6 +462,9 @@ pub fn view(tokens: TokenStream) -> TokenStream {
         (Some(TokenTree::Ident(cx)), Some(TokenTree::Punct(punct)))
             if punct.as_char() == ',' =>
         {
+            let (allow_invalid_html, tokens) =
+                view::validate::take_allow_attribute(tokens.collect());
+            let mut tokens = tokens.into_iter();
             let first = tokens.next();
             let second = tokens.next();
             let third = tokens.next();
@@ -363,8 +500,15 @@ pub fn view(tokens: TokenStream) -> TokenStream {
             };
             let config = rstml::ParserConfig::default().recover_block(true);
             let parser = rstml::Parser::new(config);
-            let (nodes, errors) = parser.parse_recoverable(tokens).split_vec();
+            let (nodes, errors) = parser
+                .parse_recoverable(view::strip_comments(tokens))
+                .split_vec();
             let errors = errors.into_iter().map(|e| e.emit_as_expr_tokens());
+            if !allow_invalid_html {
+                for problem in view::validate::check_nodes(&nodes) {
+                    problem.emit();
+                }
+            }
             let nodes_output = render_view(
                 &cx,
                 &nodes,
@@ -418,8 +562,8 @@ pub fn template(tokens: TokenStream) -> TokenStream {
             (Some(TokenTree::Ident(cx)), Some(TokenTree::Punct(punct)))
                 if punct.as_char() == ',' =>
             {
-                match parse(tokens.collect::<proc_macro2::TokenStream>().into())
-                {
+                let tokens = tokens.collect::<proc_macro2::TokenStream>();
+                match parse(view::strip_comments(tokens).into()) {
                     Ok(nodes) => render_template(
                         &proc_macro2::Ident::new(&cx.to_string(), cx.span()),
                         &nodes,
@@ -726,7 +870,48 @@ pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// 2. Specifying only `slot` without a name (such as in `<HelloSlot slot>`) will default the chosen slot to
 /// the a snake case version of the slot struct name (`hello_slot` for `<HelloSlot>`).
 ///
-/// 3. Event handlers cannot be specified directly on the slot.
+/// 3. A slot prop can be a single slot, an `Option` of one, or a `Vec` of them. Every child marked
+/// with the same slot name is collected into that prop, in order; a `Vec` or `Option` prop should
+/// be `#[prop(optional)]` so that the slot can be left out.
+///
+/// 4. Slots can be mixed with regular children, which are passed to the component's `children`
+/// prop as usual. A slot can only be passed directly to a component: naming a slot the component
+/// doesn't declare is reported at the `slot` attribute.
+///
+/// ```rust
+/// # use leptos::*;
+/// #[slot]
+/// struct Tab {
+///     label: &'static str,
+///     children: ChildrenFn,
+/// }
+///
+/// #[component]
+/// fn Tabs(
+///     cx: Scope,
+///     #[prop(optional)] tab: Vec<Tab>,
+///     children: Children,
+/// ) -> impl IntoView {
+///     view! { cx,
+///         <nav>{tab.iter().map(|tab| tab.label).collect::<Vec<_>>().join(" | ")}</nav>
+///         {tab.into_iter().map(|tab| (tab.children)(cx)).collect::<Vec<_>>()}
+///         {children(cx)}
+///     }
+/// }
+///
+/// #[component]
+/// fn App(cx: Scope) -> impl IntoView {
+///     view! { cx,
+///         <Tabs>
+///             <Tab slot label="One">"First tab"</Tab>
+///             <Tab slot label="Two">"Second tab"</Tab>
+///             <p>"Shown under the tabs"</p>
+///         </Tabs>
+///     }
+/// }
+/// ```
+///
+/// 5. Event handlers cannot be specified directly on the slot.
 ///
 /// ```compile_error
 /// // ❌ This won't work
@@ -808,6 +993,9 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 ///   (e.g., `MyServerFn`).
 /// 2. *Optional*: A URL prefix at which the function will be mounted when it’s registered
 ///   (e.g., `"/api"`). Defaults to `"/"`.
+///   The prefix can also be an absolute URL (e.g., `"https://api.example.com/api"`), in which
+///   case the client sends requests for this function there, instead of to the URL set with
+///   [`set_server_url`](https://docs.rs/server_fn/latest/server_fn/client/fn.set_server_url.html).
 /// 3. *Optional*: The encoding for the server function (`"Url"`, `"Cbor"`, `"GetJson"`, or `"GetCbor`". See **Server Function Encodings** below.)
 /// 4. *Optional*: A specific endpoint path to be used in the URL. (By default, a unique path will be generated.)
 ///
@@ -852,6 +1040,9 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// - **The `Scope` comes from the server.** Optionally, the first argument of a server function
 ///   can be a Leptos `Scope`. This scope can be used to inject dependencies like the HTTP request
 ///   or response or other server-only dependencies, but it does *not* have access to reactive state that exists in the client.
+///   When the server integration provides a [`ServerFnContext`](https://docs.rs/leptos_server/latest/leptos_server/struct.ServerFnContext.html),
+///   the function body runs in a child of the request's root scope, so `use_context` resolves the same way
+///   whether the function was called over HTTP or directly while rendering on the server.
 /// - Your server must be ready to handle the server functions at the API prefix you list. The easiest way to do this
 ///   is to use the `handle_server_fns` function from [`leptos_actix`](https://docs.rs/leptos_actix/latest/leptos_actix/fn.handle_server_fns.html)
 ///   or [`leptos_axum`](https://docs.rs/leptos_axum/latest/leptos_axum/fn.handle_server_fns.html).
@@ -885,6 +1076,64 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// - Plain text (arguments sent with URL/form encoding, results sent as JSON) or a binary format (CBOR, encoded as a base64
 /// string)?
 ///
+/// ## Caching
+///
+/// With the `server-cache` feature, a server function can cache its results on the server for a
+/// given time by adding a `cache` argument:
+///
+/// ```rust,ignore
+/// #[server(GetExchangeRates, "/api", cache = "30s")]
+/// ```
+///
+/// Results are keyed by the serialized arguments and shared across requests, whether the function
+/// is called while rendering on the server or by the client. Only annotate functions whose result
+/// doesn’t depend on the user making the request. Cached results can be cleared early with
+/// [`invalidate_server_cache`](https://docs.rs/server_fn/latest/server_fn/cache/fn.invalidate_server_cache.html).
+///
+/// ## Middleware
+///
+/// Code that should run around some server functions, like an authorization check or logging, can
+/// be implemented once as a [`ServerFnMiddleware`](https://docs.rs/server_fn/latest/server_fn/middleware/trait.ServerFnMiddleware.html)
+/// and listed in a `#[middleware]` attribute, below `#[server]`:
+///
+/// ```rust,ignore
+/// #[server(DeletePost, "/api")]
+/// #[middleware(RequireAuth, LogCalls)]
+/// pub async fn delete_post(cx: Scope, id: u32) -> Result<(), ServerFnError> {
+///     todo!()
+/// }
+/// ```
+///
+/// Middleware runs in order on the server before the function body, both when the function is
+/// called over HTTP and when it is called directly while rendering on the server, and can stop the
+/// call by returning an error. It gets the function’s `Scope`, so functions with middleware must
+/// take one.
+///
+/// ## Optional and Default Arguments
+///
+/// Arguments that are missing from a request, e.g., because it was sent by a form or a cached page
+/// from before the argument was added, don’t have to be an error:
+/// - an `Option<T>` argument is `None` when it is missing, and is left out of the request when it is `None`;
+/// - any other argument can be given a default value with `#[server_default = expr]`.
+///
+/// ```rust,ignore
+/// #[server(SearchPosts, "/api")]
+/// pub async fn search_posts(
+///     query: String,
+///     tag: Option<String>,
+///     #[server_default = 20] per_page: u32,
+/// ) -> Result<Vec<Post>, ServerFnError> {
+///     todo!()
+/// }
+/// ```
+///
+/// ## Schema
+///
+/// With the `server-schema` feature, the arguments, return type and error type of every server
+/// function must implement [`JsonSchema`](https://docs.rs/schemars/latest/schemars/trait.JsonSchema.html),
+/// and [`registry_schema`](https://docs.rs/server_fn/latest/server_fn/schema/fn.registry_schema.html)
+/// describes each function’s URL, encoding and types, e.g., to generate a client in another language.
+///
 /// ## Why not `PUT` or `DELETE`? Why URL/form encoding, and not JSON?**
 ///
 /// These are reasonable questions. Much of the web is built on REST API patterns that encourage the use of semantic HTTP
@@ -908,7 +1157,7 @@ pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
     };
     match server_macro_impl(
         args.into(),
-        s.into(),
+        run_in_server_fn_context(s.into()),
         syn::parse_quote!(::leptos::leptos_server::ServerFnTraitObj),
         Some(context),
         Some(syn::parse_quote!(::leptos::server_fn)),
@@ -918,6 +1167,39 @@ pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
     }
 }
 
+// Wraps the body of a server function that takes a `Scope` so that it runs in
+// a child of the scope marked by the request's `ServerFnContext`, whether it is
+// called over HTTP or directly while rendering on the server.
+fn run_in_server_fn_context(
+    s: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let Ok(mut item) = syn::parse2::<syn::ItemFn>(s.clone()) else {
+        // let the server function macro report the error
+        return s;
+    };
+    let cx = match item.sig.inputs.first() {
+        Some(syn::FnArg::Typed(arg)) => match (&*arg.pat, &*arg.ty) {
+            (syn::Pat::Ident(pat), syn::Type::Path(ty))
+                if ty
+                    .path
+                    .segments
+                    .iter()
+                    .any(|segment| segment.ident == "Scope") =>
+            {
+                pat.ident.clone()
+            }
+            _ => return s,
+        },
+        _ => return s,
+    };
+    let block = &item.block;
+    item.block = syn::parse_quote!({
+        ::leptos::leptos_server::run_server_fn(#cx, move |#cx| async move #block)
+            .await
+    });
+    item.into_token_stream()
+}
+
 /// Derives a trait that parses a map of string keys and values into a typed
 /// data structure, e.g., for route params.
 #[proc_macro_derive(Params, attributes(params))]
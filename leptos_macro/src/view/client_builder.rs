@@ -2,9 +2,10 @@ use super::{
     component_builder::component_to_tokens,
     expr_to_ident, fancy_class_name, fancy_style_name,
     ide_helper::IdeTagHelper,
-    is_ambiguous_element, is_custom_element, is_math_ml_element,
-    is_self_closing, is_svg_element, parse_event_name,
+    is_ambiguous_element, is_custom_element, is_form_state_attr,
+    is_math_ml_element, is_self_closing, is_svg_element, parse_event_name,
     slot_helper::{get_slot, slot_to_tokens},
+    style_attribute_value,
 };
 use crate::attribute_value;
 use leptos_hot_reload::parsing::{is_component_node, value_to_string};
@@ -29,7 +30,7 @@ pub(crate) fn fragment_to_tokens(
     nodes: &[Node],
     lazy: bool,
     parent_type: TagType,
-    parent_slots: Option<&mut HashMap<String, Vec<TokenStream>>>,
+    parent_slots: Option<&mut HashMap<Ident, Vec<TokenStream>>>,
     global_class: Option<&TokenTree>,
     view_marker: Option<String>,
 ) -> Option<TokenStream> {
@@ -109,7 +110,7 @@ pub(crate) fn node_to_tokens(
     cx: &Ident,
     node: &Node,
     parent_type: TagType,
-    parent_slots: Option<&mut HashMap<String, Vec<TokenStream>>>,
+    parent_slots: Option<&mut HashMap<Ident, Vec<TokenStream>>>,
     global_class: Option<&TokenTree>,
     view_marker: Option<String>,
 ) -> Option<TokenStream> {
@@ -149,7 +150,7 @@ pub(crate) fn element_to_tokens(
     cx: &Ident,
     node: &NodeElement,
     mut parent_type: TagType,
-    parent_slots: Option<&mut HashMap<String, Vec<TokenStream>>>,
+    parent_slots: Option<&mut HashMap<Ident, Vec<TokenStream>>>,
     global_class: Option<&TokenTree>,
     view_marker: Option<String>,
 ) -> Option<TokenStream> {
@@ -216,6 +217,8 @@ pub(crate) fn element_to_tokens(
                     || fancy_style_name(name, cx, node).is_some()
                 {
                     None
+                } else if is_form_state_attr(&tag, name) {
+                    Some(form_attribute_to_tokens(cx, node))
                 } else {
                     Some(attribute_to_tokens(cx, node, global_class))
                 }
@@ -350,6 +353,20 @@ pub(crate) fn element_to_tokens(
     }
 }
 
+// a bare `value`, `checked`, or `selected` on a form control
+fn form_attribute_to_tokens(cx: &Ident, node: &KeyedAttribute) -> TokenStream {
+    let span = node.key.span();
+    let name = node.key.to_string();
+    let value = match node.value() {
+        Some(value) => quote! { #value },
+        None => quote_spanned! { span => "" },
+    };
+    let form_attr = quote_spanned! { span => .form_attr };
+    quote! {
+        #form_attr(#name, (#cx, #value))
+    }
+}
+
 pub(crate) fn attribute_to_tokens(
     cx: &Ident,
     node: &KeyedAttribute,
@@ -367,8 +384,7 @@ pub(crate) fn attribute_to_tokens(
     } else if let Some(name) = name.strip_prefix("on:") {
         let handler = attribute_value(node);
 
-        let (event_type, is_custom, is_force_undelegated) =
-            parse_event_name(name);
+        let (event_type, is_custom, modifiers) = parse_event_name(name);
 
         let event_name_ident = match &node.key {
             NodeName::Punctuated(parts) => {
@@ -381,13 +397,10 @@ pub(crate) fn attribute_to_tokens(
             _ => unreachable!(),
         };
         let undelegated_ident = match &node.key {
-            NodeName::Punctuated(parts) => parts.last().and_then(|last| {
-                if last.to_string() == "undelegated" {
-                    Some(last)
-                } else {
-                    None
-                }
-            }),
+            NodeName::Punctuated(parts) => parts
+                .iter()
+                .skip(2)
+                .find(|part| part.to_string() == "undelegated"),
             _ => unreachable!(),
         };
         let on = match &node.key {
@@ -411,7 +424,7 @@ pub(crate) fn attribute_to_tokens(
             event_type
         };
 
-        let event_type = if is_force_undelegated {
+        let event_type = if modifiers.undelegated {
             let undelegated = if let Some(undelegated) = undelegated_ident {
                 let span = undelegated.span();
                 quote_spanned! {
@@ -424,6 +437,7 @@ pub(crate) fn attribute_to_tokens(
         } else {
             quote! { ::leptos::ev::#event_type }
         };
+        let event_type = modifiers.wrap_handler_modifiers(event_type);
 
         quote! {
             #on(#event_type, #handler)
@@ -494,6 +508,11 @@ pub(crate) fn attribute_to_tokens(
 
         // all other attributes
         let value = match node.value() {
+            Some(value)
+                if name == "style" && value_to_string(value).is_none() =>
+            {
+                style_attribute_value(cx, value)
+            }
             Some(value) => {
                 quote! { #value }
             }
@@ -1,4 +1,7 @@
-use super::{component_builder::component_to_tokens, IdeTagHelper};
+use super::{
+    component_builder::component_to_tokens, is_form_state_attr,
+    style_attribute_value, IdeTagHelper,
+};
 use crate::attribute_value;
 use itertools::Either;
 use leptos_hot_reload::parsing::{
@@ -138,7 +141,14 @@ fn element_to_tokens(
 
     // attributes
     for attr in attributes(node) {
-        attr_to_tokens(cx, attr, &this_el_ident, template, expressions);
+        attr_to_tokens(
+            cx,
+            attr,
+            &name_str,
+            &this_el_ident,
+            template,
+            expressions,
+        );
     }
 
     // navigation for this el
@@ -267,6 +277,7 @@ fn next_sibling_node(
 fn attr_to_tokens(
     cx: &Ident,
     node: &KeyedAttribute,
+    tag: &str,
     el_id: &Ident,
     template: &mut String,
     expressions: &mut Vec<TokenStream>,
@@ -300,7 +311,7 @@ fn attr_to_tokens(
         let (event_type, handler) =
             crate::view::event_from_attribute_node(node, false);
         expressions.push(quote! {
-            ::leptos::leptos_dom::add_event_helper(::leptos::wasm_bindgen::JsCast::unchecked_ref(&#el_id), #event_type, #handler);
+            ::leptos::leptos_dom::add_event_helper(#cx, ::leptos::wasm_bindgen::JsCast::unchecked_ref(&#el_id), #event_type, #handler);
         })
     }
     // Properties
@@ -327,6 +338,15 @@ fn attr_to_tokens(
                 template.push_str(name);
             }
 
+            // `<textarea>` and `<select>` have no `value` attribute for the template to hold
+            AttributeValue::Static(value)
+                if name == "value"
+                    && (tag == "textarea" || tag == "select") =>
+            {
+                expressions.push(quote_spanned! {
+                    span => ::leptos::leptos_dom::form_attr_helper(leptos::wasm_bindgen::JsCast::unchecked_ref(&#el_id), #name.into(), #value.into_attribute(#cx))
+                });
+            }
             // Static attributes (i.e., just a literal given as value, not an expression)
             // are just set in the template — again, nothing programmatic
             AttributeValue::Static(value) => {
@@ -339,8 +359,19 @@ fn attr_to_tokens(
             AttributeValue::Dynamic(value) => {
                 // For client-side rendering, dynamic attributes don't need to be rendered in the template
                 // They'll immediately be set synchronously before the cloned template is mounted
+                let helper =
+                    if is_form_state_attr(tag, node.key.to_string().as_str()) {
+                        quote! { form_attr_helper }
+                    } else {
+                        quote! { attribute_helper }
+                    };
+                let value = if name == "style" {
+                    style_attribute_value(cx, value)
+                } else {
+                    quote! { {#value}.into_attribute(#cx) }
+                };
                 expressions.push(quote_spanned! {
-                    span => ::leptos::leptos_dom::attribute_helper(leptos::wasm_bindgen::JsCast::unchecked_ref(&#el_id), #name.into(), {#value}.into_attribute(#cx))
+                    span => ::leptos::leptos_dom::#helper(leptos::wasm_bindgen::JsCast::unchecked_ref(&#el_id), #name.into(), #value)
                 });
             }
         }
@@ -3,7 +3,7 @@ use super::{
     event_from_attribute_node, ident_from_tag_name,
 };
 use proc_macro2::{Ident, TokenStream, TokenTree};
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
 use rstml::node::{NodeAttribute, NodeElement};
 use std::collections::HashMap;
 use syn::spanned::Spanned;
@@ -43,6 +43,14 @@ pub(crate) fn component_to_tokens(
                 })
                 .unwrap_or_else(|| quote! { #name });
 
+            // like on elements, `ref`, `_ref`, and `ref_` set the `node_ref` prop
+            let name = match name.to_string().as_str() {
+                "ref" | "_ref" | "ref_" => {
+                    quote_spanned! { name.span() => node_ref }
+                }
+                _ => quote! { #name },
+            };
+
             quote! {
                 .#name(#[allow(unused_braces)] #value)
             }
@@ -134,7 +142,6 @@ pub(crate) fn component_to_tokens(
     };
 
     let slots = slots.drain().map(|(slot, values)| {
-        let slot = Ident::new(&slot, span);
         if values.len() > 1 {
             quote! {
                 .#slot(vec![
@@ -13,6 +13,7 @@ pub mod server_template;
 pub mod slot_helper;
 #[cfg(test)]
 mod tests;
+pub mod validate;
 
 pub(crate) use ide_helper::*;
 
@@ -74,6 +75,38 @@ pub(crate) fn render_view(
     }
 }
 
+/// Removes HTML comments (`<!-- ... -->`) from the tokens of a view before it is parsed, so
+/// that a comment can contain any Rust tokens rather than only a string literal. Comments are
+/// never included in the rendered view.
+pub(crate) fn strip_comments(tokens: TokenStream) -> TokenStream {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut idx = 0;
+    while idx < tokens.len() {
+        if puncts_at(&tokens, idx, "<!--") {
+            let end = (idx + 4..tokens.len())
+                .find(|&end| puncts_at(&tokens, end, "-->"));
+            // an unterminated comment is left for the parser to report
+            if let Some(end) = end {
+                idx = end + 3;
+                continue;
+            }
+        }
+        output.push(tokens[idx].clone());
+        idx += 1;
+    }
+    output.into_iter().collect()
+}
+
+fn puncts_at(tokens: &[TokenTree], start: usize, puncts: &str) -> bool {
+    puncts.chars().enumerate().all(|(offset, c)| {
+        matches!(
+            tokens.get(start + offset),
+            Some(TokenTree::Punct(punct)) if punct.as_char() == c
+        )
+    })
+}
+
 // Keep list alphabetized for binary search
 const TYPED_EVENTS: [&str; 126] = [
     "DOMContentLoaded",
@@ -206,8 +239,10 @@ const TYPED_EVENTS: [&str; 126] = [
 
 const CUSTOM_EVENT: &str = "Custom";
 
-pub(crate) fn parse_event_name(name: &str) -> (TokenStream, bool, bool) {
-    let (name, is_force_undelegated) = parse_event(name);
+pub(crate) fn parse_event_name(
+    name: &str,
+) -> (TokenStream, bool, EventModifiers) {
+    let (name, modifiers) = parse_event(name);
 
     let (event_type, is_custom) = TYPED_EVENTS
         .binary_search(&name)
@@ -223,7 +258,7 @@ pub(crate) fn parse_event_name(name: &str) -> (TokenStream, bool, bool) {
     } else {
         event_type
     };
-    (event_type, is_custom, is_force_undelegated)
+    (event_type, is_custom, modifiers)
 }
 
 fn expr_to_ident(expr: &syn::Expr) -> Option<&ExprPath> {
@@ -252,6 +287,17 @@ fn is_custom_element(tag: &str) -> bool {
     tag.contains('-')
 }
 
+// `value`, `checked`, and `selected` only set the initial state of a form
+// control as attributes, so they are set as properties in the browser instead
+fn is_form_state_attr(tag: &str, attr: &str) -> bool {
+    matches!(
+        (tag, attr),
+        ("input", "value" | "checked")
+            | ("textarea" | "select", "value")
+            | ("option", "value" | "selected")
+    )
+}
+
 fn is_self_closing(node: &NodeElement) -> bool {
     // self-closing tags
     // https://developer.mozilla.org/en-US/docs/Glossary/Empty_element
@@ -385,15 +431,179 @@ fn is_math_ml_element(tag: &str) -> bool {
     .is_ok()
 }
 
+fn is_html_element(tag: &str) -> bool {
+    // Keep list alphabetized for binary search
+    [
+        "a",
+        "abbr",
+        "address",
+        "area",
+        "article",
+        "aside",
+        "audio",
+        "b",
+        "base",
+        "bdi",
+        "bdo",
+        "blockquote",
+        "body",
+        "br",
+        "button",
+        "canvas",
+        "caption",
+        "cite",
+        "code",
+        "col",
+        "colgroup",
+        "data",
+        "datalist",
+        "dd",
+        "del",
+        "details",
+        "dfn",
+        "dialog",
+        "div",
+        "dl",
+        "dt",
+        "em",
+        "embed",
+        "fieldset",
+        "figcaption",
+        "figure",
+        "footer",
+        "form",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "head",
+        "header",
+        "hgroup",
+        "hr",
+        "html",
+        "i",
+        "iframe",
+        "img",
+        "input",
+        "ins",
+        "kbd",
+        "label",
+        "legend",
+        "li",
+        "link",
+        "main",
+        "map",
+        "mark",
+        "math",
+        "menu",
+        "meta",
+        "meter",
+        "nav",
+        "noscript",
+        "object",
+        "ol",
+        "optgroup",
+        "option",
+        "output",
+        "p",
+        "param",
+        "picture",
+        "portal",
+        "pre",
+        "progress",
+        "q",
+        "rp",
+        "rt",
+        "ruby",
+        "s",
+        "samp",
+        "script",
+        "search",
+        "section",
+        "select",
+        "slot",
+        "small",
+        "source",
+        "span",
+        "strong",
+        "style",
+        "sub",
+        "summary",
+        "sup",
+        "svg",
+        "table",
+        "tbody",
+        "td",
+        "template",
+        "textarea",
+        "tfoot",
+        "th",
+        "thead",
+        "time",
+        "title",
+        "tr",
+        "track",
+        "u",
+        "ul",
+        "var",
+        "video",
+        "wbr",
+    ]
+    .binary_search(&tag)
+    .is_ok()
+}
+
 fn is_ambiguous_element(tag: &str) -> bool {
     tag == "a" || tag == "script" || tag == "title"
 }
 
-fn parse_event(event_name: &str) -> (&str, bool) {
-    if let Some(event_name) = event_name.strip_suffix(":undelegated") {
-        (event_name, true)
-    } else {
-        (event_name, false)
+/// The modifiers that can follow the name of an event, in any order, as in
+/// `on:submit:prevent_default:stop_propagation`.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct EventModifiers {
+    pub undelegated: bool,
+    pub prevent_default: bool,
+    pub stop_propagation: bool,
+}
+
+impl EventModifiers {
+    /// Wraps the event type in the `prevent_default` and `stop_propagation`
+    /// descriptors, if they were given.
+    pub fn wrap_handler_modifiers(
+        &self,
+        event_type: TokenStream,
+    ) -> TokenStream {
+        let event_type = if self.stop_propagation {
+            quote! { ::leptos::ev::stop_propagation(#event_type) }
+        } else {
+            event_type
+        };
+        if self.prevent_default {
+            quote! { ::leptos::ev::prevent_default(#event_type) }
+        } else {
+            event_type
+        }
+    }
+}
+
+fn parse_event(mut event_name: &str) -> (&str, EventModifiers) {
+    let mut modifiers = EventModifiers::default();
+    loop {
+        if let Some(name) = event_name.strip_suffix(":undelegated") {
+            modifiers.undelegated = true;
+            event_name = name;
+        } else if let Some(name) = event_name.strip_suffix(":prevent_default") {
+            modifiers.prevent_default = true;
+            event_name = name;
+        } else if let Some(name) = event_name.strip_suffix(":stop_propagation")
+        {
+            modifiers.stop_propagation = true;
+            event_name = name;
+        } else {
+            return (event_name, modifiers);
+        }
     }
 }
 
@@ -468,6 +678,24 @@ fn ident_from_tag_name(tag_name: &NodeName) -> Ident {
     }
 }
 
+// a dynamic `style` value can be a list of properties as well as anything
+// that implements `IntoAttribute`, which only `style` accepts
+fn style_attribute_value(
+    cx: &Ident,
+    value: impl quote::ToTokens,
+) -> TokenStream {
+    quote! {
+        {
+            #[allow(unused_imports)]
+            use ::leptos::leptos_dom::{StyleAttributeMatch as _, StyleMapMatch as _};
+            (&&::leptos::leptos_dom::StyleAttributeValue(
+                ::std::cell::Cell::new(Some(#value))
+            ))
+            .to_style_attribute(#cx)
+        }
+    }
+}
+
 fn fancy_style_name<'a>(
     name: &str,
     cx: &Ident,
@@ -527,8 +755,7 @@ pub(crate) fn event_from_attribute_node(
 
     let handler = attribute_value(attr);
 
-    #[allow(unused_variables)]
-    let (name, name_undelegated) = parse_event(&event_name);
+    let (name, modifiers) = parse_event(&event_name);
 
     let event_type = TYPED_EVENTS
         .binary_search(&name)
@@ -539,10 +766,10 @@ pub(crate) fn event_from_attribute_node(
         abort!(attr.key, "couldn't parse event name");
     };
 
-    let event_type = if force_undelegated || name_undelegated {
+    let event_type = if force_undelegated || modifiers.undelegated {
         quote! { ::leptos::leptos_dom::ev::undelegated(::leptos::leptos_dom::ev::#event_type) }
     } else {
         quote! { ::leptos::leptos_dom::ev::#event_type }
     };
-    (event_type, handler)
+    (modifiers.wrap_handler_modifiers(event_type), handler)
 }
@@ -6,6 +6,7 @@ use super::{
     is_custom_element, is_math_ml_element, is_self_closing, is_svg_element,
     parse_event_name,
     slot_helper::{get_slot, slot_to_tokens},
+    style_attribute_value,
 };
 use crate::attribute_value;
 use leptos_hot_reload::parsing::{
@@ -217,7 +218,7 @@ pub(crate) fn root_element_to_tokens_ssr(
 fn element_to_tokens_ssr(
     cx: &Ident,
     node: &NodeElement,
-    parent_slots: Option<&mut HashMap<String, Vec<TokenStream>>>,
+    parent_slots: Option<&mut HashMap<Ident, Vec<TokenStream>>>,
     template: &mut String,
     holes: &mut Vec<TokenStream>,
     chunks: &mut Vec<SsrElementChunks>,
@@ -258,9 +259,15 @@ fn element_to_tokens_ssr(
         stmts_for_ide.save_element_completion(node);
 
         let mut inner_html = None;
+        // a `<textarea>` has no `value` attribute: its initial value is its text content
+        let mut textarea_value = None;
 
         for attr in node.attributes() {
             if let NodeAttribute::Attribute(attr) = attr {
+                if tag_name == "textarea" && attr.key.to_string() == "value" {
+                    textarea_value = attr.value();
+                    continue;
+                }
                 inner_html = attribute_to_tokens_ssr(
                     cx,
                     attr,
@@ -305,8 +312,26 @@ fn element_to_tokens_ssr(
                 let value = inner_html;
 
                 holes.push(quote! {
-                  (#value).into_attribute(#cx).as_nameless_value_string().unwrap_or_default()
+                  (#value).into_attribute(#cx).into_nameless_value_string().unwrap_or_default()
                 })
+            } else if let Some(value) = textarea_value {
+                if let Some(value) = value_to_string(value) {
+                    template.push_str(
+                        &html_escape::encode_safe(&value)
+                            .replace('{', "\\{")
+                            .replace('}', "\\}"),
+                    );
+                } else {
+                    template.push_str("{}");
+                    holes.push(quote! {
+                        ::leptos::leptos_dom::escape::escape_text(
+                            &{#value}
+                                .into_attribute(#cx)
+                                .into_nameless_value_string()
+                                .unwrap_or_default()
+                        )
+                    })
+                }
             } else {
                 for child in &node.children {
                     match child {
@@ -397,7 +422,12 @@ fn attribute_to_tokens_ssr<'a>(
 ) -> Option<&'a syn::Expr> {
     let name = attr.key.to_string();
     if name == "ref" || name == "_ref" || name == "ref_" || name == "node_ref" {
-        // ignore refs on SSR
+        // refs are not bound on SSR, but the value is still used, so that a `node_ref` prop
+        // forwarded to an element does not trigger an unused variable warning
+        let value = attribute_value(attr);
+        exprs_for_compiler.push(quote! {
+            let _ = #value;
+        });
     } else if let Some(name) = name.strip_prefix("on:") {
         let handler = attribute_value(attr);
         let (event_type, _, _) = parse_event_name(name);
@@ -449,10 +479,10 @@ fn attribute_to_tokens_ssr<'a>(
                 } else {
                     template.push_str("{}");
                     holes.push(quote! {
-                        &{#value}.into_attribute(#cx)
-                            .as_nameless_value_string()
-                            .map(|a| format!("{}=\"{}\"", #name, leptos::leptos_dom::ssr::escape_attr(&a)))
-                            .unwrap_or_default()
+                        leptos::leptos_dom::ssr::named_attr(
+                            #name,
+                            {#value}.into_attribute(#cx)
+                        )
                     })
                 }
             } else {
@@ -575,9 +605,9 @@ fn set_class_attribute_ssr(
             if let Some(value) = value {
                 template.push_str(" {}");
                 holes.push(quote! {
-                  &(#cx, #value).into_attribute(#cx).as_nameless_value_string()
-                    .map(|a| leptos::leptos_dom::ssr::escape_attr(&a).to_string())
-                    .unwrap_or_default()
+                  leptos::leptos_dom::ssr::attr_value(
+                    (#cx, #value).into_attribute(#cx)
+                  )
                 });
             }
         }
@@ -687,10 +717,11 @@ fn set_style_attribute_ssr(
         for (_span, value) in dyn_style_attr {
             if let Some(value) = value {
                 template.push_str(" {};");
+                let value = style_attribute_value(cx, value);
                 holes.push(quote! {
-                  &(#cx, #value).into_attribute(#cx).as_nameless_value_string()
-                    .map(|a| leptos::leptos_dom::ssr::escape_attr(&a).to_string())
-                    .unwrap_or_default()
+                  leptos::leptos_dom::ssr::attr_value(
+                    (#cx, #value).into_attribute(#cx)
+                  )
                 });
             }
         }
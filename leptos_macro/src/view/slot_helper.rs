@@ -12,7 +12,7 @@ pub(crate) fn slot_to_tokens(
     cx: &Ident,
     node: &NodeElement,
     slot: &KeyedAttribute,
-    parent_slots: Option<&mut HashMap<String, Vec<TokenStream>>>,
+    parent_slots: Option<&mut HashMap<Ident, Vec<TokenStream>>>,
     global_class: Option<&TokenTree>,
 ) {
     let name = slot.key.to_string();
@@ -22,6 +22,9 @@ pub(crate) fn slot_to_tokens(
     } else {
         node.name().to_string()
     });
+    // the prop is set with this name, so an unknown slot is reported at the
+    // `slot` attribute
+    let name = Ident::new(&name, slot.key.span());
 
     let component_name = ident_from_tag_name(node.name());
     let span = node.name().span();
@@ -29,7 +32,9 @@ pub(crate) fn slot_to_tokens(
     let Some(parent_slots) = parent_slots else {
         proc_macro_error::emit_error!(
             span,
-            "slots cannot be used inside HTML elements"
+            "slots can only be passed directly to a component, not used \
+             inside an HTML element or fragment, or at the top level of a \
+             view"
         );
         return;
     };
@@ -142,12 +147,11 @@ pub(crate) fn slot_to_tokens(
     };
 
     let slots = slots.drain().map(|(slot, values)| {
-        let slot = Ident::new(&slot, span);
         if values.len() > 1 {
             quote! {
-                .#slot([
+                .#slot(vec![
                     #(#values)*
-                ].to_vec())
+                ])
             }
         } else {
             let value = &values[0];
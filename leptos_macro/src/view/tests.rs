@@ -117,3 +117,86 @@ for_all_modes! {
         />
     "#
 }
+
+fn html_problems(input: &str) -> Vec<String> {
+    let tokens = TokenStream::from_str(input).unwrap();
+    let nodes = rstml::parse2(tokens).unwrap();
+    crate::view::validate::check_nodes(&nodes)
+        .into_iter()
+        .map(|problem| match problem.help {
+            Some(help) => format!("{} ({help})", problem.message),
+            None => problem.message,
+        })
+        .collect()
+}
+
+#[test]
+fn valid_html_has_no_problems() {
+    let problems = html_problems(
+        r#"
+        <form on:submit=submit on:submit=log>
+            <p class="intro" class:active=active class=("wide", wide)>
+                "Hello, " <strong>{name}</strong> <img src="a.png"/>
+            </p>
+            <button type="submit"><span>"Save"</span></button>
+            <a href="/"><input type="hidden" name="id" value="1"/></a>
+            <my-element><div/></my-element>
+            <svg><clipPath/><path d="M 0 0"/></svg>
+            <p><Card><div/></Card></p>
+        </form>
+        "#,
+    );
+    assert_eq!(problems, Vec::<String>::new());
+}
+
+#[test]
+fn invalid_html_is_reported() {
+    let problems = html_problems(
+        r#"
+        <buton class="a" class="b">"Save"</buton>
+        <p><span><div/></span></p>
+        <button><a href="/">"Home"</a></button>
+        <a href="/"><a href="/about">"About"</a></a>
+        <img>"text"</img>
+        <blah/>
+        "#,
+    );
+    assert_eq!(
+        problems,
+        [
+            "`<buton>` is not an HTML, SVG or MathML element (did you mean \
+             `<button>`?)",
+            "the attribute `class` is given more than once",
+            "`<div>` can’t be inside a `<p>` (the browser closes the `<p>` \
+             before the `<div>` when it parses the server-rendered HTML; use \
+             a `<div>` instead of the `<p>`)",
+            "`<a>` can’t be inside a `<button>` (the content model of \
+             `<button>` doesn’t allow a `<a>`, so the server-rendered HTML \
+             is invalid, and browsers may not parse it into the elements \
+             that are hydrated)",
+            "`<a>` can’t be inside a `<a>` (the browser moves the `<a>` out \
+             of the `<a>` when it parses the server-rendered HTML)",
+            "`<img>` is a void element, so it can’t have children (the \
+             browser moves them after the element when it parses the \
+             server-rendered HTML)",
+            "`<blah>` is not an HTML, SVG or MathML element (the name of a \
+             custom element must contain a dash, like `<my-element>`)",
+        ]
+    );
+}
+
+#[test]
+fn views_can_allow_invalid_html() {
+    let tokens =
+        TokenStream::from_str("#[allow(invalid_html)] <p><div/></p>").unwrap();
+    let (allowed, tokens) = crate::view::validate::take_allow_attribute(tokens);
+    assert!(allowed);
+    assert_eq!(
+        tokens.to_string(),
+        TokenStream::from_str("<p><div/></p>").unwrap().to_string()
+    );
+
+    let tokens = TokenStream::from_str("<p/>").unwrap();
+    let (allowed, _) = crate::view::validate::take_allow_attribute(tokens);
+    assert!(!allowed);
+}
@@ -0,0 +1,347 @@
+use super::{
+    is_custom_element, is_html_element, is_math_ml_element, is_self_closing,
+    is_svg_element,
+};
+use leptos_hot_reload::parsing::is_component_tag_name;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use rstml::node::{KeyedAttribute, Node, NodeAttribute, NodeElement};
+use std::collections::HashSet;
+use syn::{spanned::Spanned, Expr, ExprLit, Lit};
+
+/// The lint that turns off these checks for a whole view, with
+/// `view! { cx, #[allow(invalid_html)] ... }`.
+pub(crate) const ALLOW_INVALID_HTML: &str = "invalid_html";
+
+/// A mistake in the markup of a view.
+#[derive(Debug)]
+pub(crate) struct Problem {
+    pub span: Span,
+    pub message: String,
+    pub help: Option<String>,
+    /// Whether this is only a warning, which is shown on `nightly`.
+    pub is_warning: bool,
+}
+
+impl Problem {
+    fn error(span: Span, message: String, help: Option<String>) -> Self {
+        Self {
+            span,
+            message,
+            help,
+            is_warning: false,
+        }
+    }
+
+    pub fn emit(self) {
+        let Problem {
+            span,
+            message,
+            help,
+            is_warning,
+        } = self;
+        let allow = format!(
+            "if this is intended, add `#[allow({ALLOW_INVALID_HTML})]` at \
+             the start of the view"
+        );
+        if is_warning {
+            proc_macro_error::emit_warning!(
+                span, message;
+                help =? help;
+                note = allow
+            );
+        } else {
+            proc_macro_error::emit_error!(
+                span, message;
+                help =? help;
+                note = allow
+            );
+        }
+    }
+}
+
+/// Removes a leading `#[allow(invalid_html)]` from the tokens of a view,
+/// returning whether it was there.
+pub(crate) fn take_allow_attribute(tokens: TokenStream) -> (bool, TokenStream) {
+    let mut tokens = tokens.into_iter().collect::<Vec<_>>();
+    let attribute = match (tokens.first(), tokens.get(1)) {
+        (Some(TokenTree::Punct(hash)), Some(TokenTree::Group(group)))
+            if hash.as_char() == '#'
+                && group.delimiter() == proc_macro2::Delimiter::Bracket =>
+        {
+            group.clone()
+        }
+        _ => return (false, tokens.into_iter().collect()),
+    };
+    let allows_invalid_html = syn::parse2::<syn::MetaList>(attribute.stream())
+        .ok()
+        .filter(|meta| meta.path.is_ident("allow"))
+        .map(|meta| meta.tokens.to_string() == ALLOW_INVALID_HTML)
+        .unwrap_or(false);
+    if !allows_invalid_html {
+        proc_macro_error::abort!(
+            attribute.span(),
+            "the only attribute a view can start with is `#[allow({})]`",
+            ALLOW_INVALID_HTML
+        );
+    }
+    tokens.drain(..2);
+    (true, tokens.into_iter().collect())
+}
+
+/// Checks that the elements in a view are valid HTML, SVG or MathML:
+/// 1. each element name is known, unless it is a custom element;
+/// 2. no attribute is given twice on the same element;
+/// 3. void elements, like `<img>`, have no children;
+/// 4. elements aren’t nested in ways that make the browser move them when it
+///    parses the server-rendered HTML, like a `<div>` inside a `<p>` or a
+///    `<button>` inside a `<button>`.
+///
+/// Components and the contents of `{ }` blocks aren’t checked against the
+/// elements around them, as what they render isn’t known.
+pub(crate) fn check_nodes(nodes: &[Node]) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    check_children(nodes, &mut Vec::new(), &mut problems);
+    problems
+}
+
+fn check_children(
+    nodes: &[Node],
+    ancestors: &mut Vec<String>,
+    problems: &mut Vec<Problem>,
+) {
+    for node in nodes {
+        match node {
+            Node::Element(node) => check_element(node, ancestors, problems),
+            Node::Fragment(fragment) => {
+                check_children(&fragment.children, ancestors, problems)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_element(
+    node: &NodeElement,
+    ancestors: &mut Vec<String>,
+    problems: &mut Vec<Problem>,
+) {
+    let tag = node.name().to_string();
+    let span = node.name().span();
+
+    // a component's children are rendered wherever it puts them
+    if is_component_tag_name(&tag) {
+        check_children(&node.children, &mut Vec::new(), problems);
+        return;
+    }
+    // e.g., `svg::a` or `{tag}`
+    let is_plain_name = tag
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !is_plain_name {
+        check_children(&node.children, ancestors, problems);
+        return;
+    }
+
+    if !(is_html_element(&tag)
+        || is_svg_element(&tag)
+        || is_math_ml_element(&tag)
+        || is_custom_element(&tag))
+    {
+        let help = closest_element(&tag)
+            .map(|name| format!("did you mean `<{name}>`?"))
+            .unwrap_or_else(|| {
+                "the name of a custom element must contain a dash, like \
+                 `<my-element>`"
+                    .to_string()
+            });
+        problems.push(Problem::error(
+            span,
+            format!("`<{tag}>` is not an HTML, SVG or MathML element"),
+            Some(help),
+        ));
+    }
+
+    check_attributes(node, problems);
+
+    if is_self_closing(node) && !node.children.is_empty() {
+        problems.push(Problem {
+            span,
+            message: format!(
+                "`<{tag}>` is a void element, so it can’t have children"
+            ),
+            help: Some(
+                "the browser moves them after the element when it parses \
+                 the server-rendered HTML"
+                    .to_string(),
+            ),
+            is_warning: true,
+        });
+    }
+
+    if let Some(ancestor) = ancestors
+        .iter()
+        .rev()
+        .find(|ancestor| cannot_contain(ancestor, &tag, node))
+    {
+        let help = if ancestor == "p" {
+            format!(
+                "the browser closes the `<p>` before the `<{tag}>` when it \
+                 parses the server-rendered HTML; use a `<div>` instead of \
+                 the `<p>`"
+            )
+        } else if *ancestor == tag && (tag == "a" || tag == "button") {
+            format!(
+                "the browser moves the `<{tag}>` out of the `<{ancestor}>` \
+                 when it parses the server-rendered HTML"
+            )
+        } else {
+            format!(
+                "the content model of `<{ancestor}>` doesn’t allow a \
+                 `<{tag}>`, so the server-rendered HTML is invalid, and \
+                 browsers may not parse it into the elements that are \
+                 hydrated"
+            )
+        };
+        problems.push(Problem::error(
+            span,
+            format!("`<{tag}>` can’t be inside a `<{ancestor}>`"),
+            Some(help),
+        ));
+    }
+
+    ancestors.push(tag);
+    check_children(&node.children, ancestors, problems);
+    ancestors.pop();
+}
+
+fn check_attributes(node: &NodeElement, problems: &mut Vec<Problem>) {
+    let mut seen = HashSet::new();
+    for attr in node.attributes() {
+        let NodeAttribute::Attribute(attr) = attr else {
+            continue;
+        };
+        let name = attr.key.to_string();
+        // several event listeners and directives can be added for the same
+        // name, and `class=("name", value)` is like `class:name=value`
+        let can_repeat = name.starts_with("on:")
+            || name.starts_with("use:")
+            || matches!(attr.value(), Some(Expr::Tuple(_)));
+        if !can_repeat && !seen.insert(name.clone()) {
+            problems.push(Problem::error(
+                attr.key.span(),
+                format!("the attribute `{name}` is given more than once"),
+                None,
+            ));
+        }
+    }
+}
+
+// whether an element with the name `tag` can't be inside an `ancestor`,
+// either because the HTML parser would move it out, or because the
+// ancestor's content model doesn't allow it
+fn cannot_contain(ancestor: &str, tag: &str, node: &NodeElement) -> bool {
+    match ancestor {
+        "p" => closes_paragraph(tag),
+        "a" | "button" => is_interactive(tag, node),
+        "form" => tag == "form",
+        _ => false,
+    }
+}
+
+// the elements that close an open `<p>`
+// https://html.spec.whatwg.org/multipage/grouping-content.html#the-p-element
+fn closes_paragraph(tag: &str) -> bool {
+    // Keep list alphabetized for binary search
+    [
+        "address",
+        "article",
+        "aside",
+        "blockquote",
+        "details",
+        "dialog",
+        "div",
+        "dl",
+        "fieldset",
+        "figcaption",
+        "figure",
+        "footer",
+        "form",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "header",
+        "hgroup",
+        "hr",
+        "main",
+        "menu",
+        "nav",
+        "ol",
+        "p",
+        "pre",
+        "search",
+        "section",
+        "table",
+        "ul",
+    ]
+    .binary_search(&tag)
+    .is_ok()
+}
+
+// https://html.spec.whatwg.org/multipage/dom.html#interactive-content
+fn is_interactive(tag: &str, node: &NodeElement) -> bool {
+    match tag {
+        "a" | "button" | "details" | "embed" | "iframe" | "label"
+        | "select" | "textarea" => true,
+        "input" => !node.attributes().iter().any(|attr| {
+            matches!(attr, NodeAttribute::Attribute(attr) if is_hidden_type(attr))
+        }),
+        _ => false,
+    }
+}
+
+fn is_hidden_type(attr: &KeyedAttribute) -> bool {
+    attr.key.to_string() == "type"
+        && matches!(
+            attr.value(),
+            Some(Expr::Lit(ExprLit { lit: Lit::Str(s), .. }))
+                if s.value().eq_ignore_ascii_case("hidden")
+        )
+}
+
+// the commonly used element whose name is closest to a misspelled one
+fn closest_element(tag: &str) -> Option<&'static str> {
+    const COMMON: &[&str] = &[
+        "a", "article", "aside", "button", "canvas", "details", "dialog",
+        "div", "footer", "form", "header", "iframe", "img", "input", "label",
+        "li", "main", "nav", "option", "p", "path", "section", "select",
+        "span", "strong", "summary", "svg", "table", "tbody", "td", "textarea",
+        "th", "thead", "tr", "ul", "video",
+    ];
+    COMMON
+        .iter()
+        .copied()
+        .map(|name| (edit_distance(tag, name), name))
+        .filter(|(distance, name)| {
+            *distance <= 2 && *distance < name.len().min(tag.len())
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current[j + 1] =
+                substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,68 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const ITERATIONS: i32 = 1_000_000;
+
+fn l021_get_set(c: &mut Criterion) {
+    use l021::*;
+
+    c.bench_function("l021_get_set", |b| {
+        let runtime = create_runtime();
+        b.iter(|| {
+            create_scope(runtime, |cx| {
+                let sig = create_rw_signal(cx, 0);
+                for _ in 0..ITERATIONS {
+                    sig.set(sig.get() + 1);
+                }
+                assert_eq!(sig.get(), ITERATIONS);
+            })
+            .dispose()
+        });
+        runtime.dispose();
+    });
+}
+
+fn leptos_get_set(c: &mut Criterion) {
+    use leptos_reactive::*;
+    let runtime = create_runtime();
+
+    c.bench_function("leptos_get_set", |b| {
+        b.iter(|| {
+            create_scope(runtime, |cx| {
+                let sig = create_rw_signal(cx, 0);
+                for _ in 0..ITERATIONS {
+                    sig.set(sig.get() + 1);
+                }
+                assert_eq!(sig.get(), ITERATIONS);
+            })
+            .dispose()
+        });
+    });
+    runtime.dispose();
+}
+
+// the same value, but not a primitive, so that the signal boxes it rather
+// than storing it inline
+#[derive(Clone)]
+struct Counter(i32);
+
+fn leptos_get_set_boxed(c: &mut Criterion) {
+    use leptos_reactive::*;
+    let runtime = create_runtime();
+
+    c.bench_function("leptos_get_set_boxed", |b| {
+        b.iter(|| {
+            create_scope(runtime, |cx| {
+                let sig = create_rw_signal(cx, Counter(0));
+                for _ in 0..ITERATIONS {
+                    sig.set(Counter(sig.get().0 + 1));
+                }
+                assert_eq!(sig.get().0, ITERATIONS);
+            })
+            .dispose()
+        });
+    });
+    runtime.dispose();
+}
+
+criterion_group!(get_set, l021_get_set, leptos_get_set, leptos_get_set_boxed);
+criterion_main!(get_set);
@@ -0,0 +1,199 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_effect, create_signal, on_cleanup, spawn::spawn_local, use_context,
+    Scope, Signal, SignalSet, SuspenseContext,
+};
+use futures::future::{abortable, poll_fn, AbortHandle};
+use std::{
+    cell::RefCell,
+    future::Future,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+/// Creates a signal derived from an `async` computation, like a
+/// [`Memo`](crate::Memo) whose value takes a while to compute.
+///
+/// This is useful for work that is asynchronous but local, like hashing a
+/// file the user has selected or running a parser compiled to WASM. Unlike a
+/// [`Resource`](crate::Resource), the value does not need to be
+/// [`Serializable`](crate::Serializable), because it is never sent from the
+/// server to the client: the computation only runs in the browser, and the
+/// signal is `None` during server rendering.
+///
+/// The signal is `None` until the first computation finishes. After that,
+/// 1. the computation runs again whenever a signal it read _before its first
+///    `.await`_ changes, and the signal keeps its previous value meanwhile;
+/// 2. a computation that is still running when it starts again, or when its
+///    scope is disposed, is cancelled, so the signal only ever holds the
+///    result of the latest one.
+///
+/// The computation does not count as pending for `<Suspense/>`; use
+/// [`create_async_derived_with_suspense`] if it should.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn hash(bytes: Vec<u8>) -> u64 {
+///     // pretend this takes a while
+///     bytes.iter().map(|byte| *byte as u64).sum()
+/// }
+///
+/// let (file, set_file) = create_signal(cx, vec![1, 2, 3]);
+///
+/// // `file` is read before the first `.await`, so it is tracked
+/// let file_hash = create_async_derived(cx, move || {
+///     let bytes = file.get();
+///     async move { hash(bytes).await }
+/// });
+///
+/// # if cfg!(not(feature = "ssr")) {
+/// assert_eq!(file_hash.get(), Some(6));
+/// set_file.set(vec![4, 5, 6]);
+/// assert_eq!(file_hash.get(), Some(15));
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "info",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>(),
+        )
+    )
+)]
+#[track_caller]
+pub fn create_async_derived<T, Fu>(
+    cx: Scope,
+    f: impl Fn() -> Fu + 'static,
+) -> Signal<Option<T>>
+where
+    T: 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    create_async_derived_inner(cx, f, false)
+}
+
+/// Creates a signal derived from an `async` computation, exactly like
+/// [`create_async_derived`], except that every `<Suspense/>` it is created
+/// under counts it as pending while it is computing.
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "info",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>(),
+        )
+    )
+)]
+#[track_caller]
+pub fn create_async_derived_with_suspense<T, Fu>(
+    cx: Scope,
+    f: impl Fn() -> Fu + 'static,
+) -> Signal<Option<T>>
+where
+    T: 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    create_async_derived_inner(cx, f, true)
+}
+
+#[track_caller]
+fn create_async_derived_inner<T, Fu>(
+    cx: Scope,
+    f: impl Fn() -> Fu + 'static,
+    suspend: bool,
+) -> Signal<Option<T>>
+where
+    T: 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let (value, set_value) = create_signal(cx, None);
+    let state = Rc::new(AsyncDerivedState {
+        running: Default::default(),
+        suspense: if suspend {
+            use_context::<SuspenseContext>(cx)
+        } else {
+            None
+        },
+    });
+
+    on_cleanup(cx, {
+        let state = Rc::clone(&state);
+        move || state.cancel()
+    });
+
+    // like a local resource, this only runs on the client
+    create_effect(cx, move |_| {
+        let mut fut = Box::pin(f());
+
+        // the first poll happens while the effect is running, so that every
+        // signal read before the first `.await` is tracked
+        let waker = futures::task::noop_waker();
+        match fut.as_mut().poll(&mut Context::from_waker(&waker)) {
+            Poll::Ready(new_value) => {
+                state.cancel();
+                set_value.set(Some(new_value));
+            }
+            Poll::Pending => {
+                let rest =
+                    poll_fn(move |task| cx.untrack(|| fut.as_mut().poll(task)));
+                let (rest, handle) = abortable(rest);
+                state.start(handle);
+                let state = Rc::clone(&state);
+                spawn_local(async move {
+                    // `Err` means a newer computation has replaced this one
+                    if let Ok(new_value) = rest.await {
+                        state.finish();
+                        set_value.try_set(Some(new_value));
+                    }
+                });
+            }
+        }
+    });
+
+    value.into()
+}
+
+struct AsyncDerivedState {
+    running: RefCell<Option<AbortHandle>>,
+    suspense: Option<SuspenseContext>,
+}
+
+impl AsyncDerivedState {
+    /// Cancels the running computation, if any, and replaces it.
+    fn start(&self, handle: AbortHandle) {
+        match self.running.borrow_mut().replace(handle) {
+            Some(prev) => prev.abort(),
+            None => {
+                if let Some(suspense) = &self.suspense {
+                    suspense.increment(false);
+                }
+            }
+        }
+    }
+
+    /// Marks the running computation as finished.
+    fn finish(&self) {
+        if self.running.borrow_mut().take().is_some() {
+            if let Some(suspense) = &self.suspense {
+                suspense.decrement(false);
+            }
+        }
+    }
+
+    /// Cancels the running computation, if any.
+    fn cancel(&self) {
+        if let Some(prev) = self.running.borrow_mut().take() {
+            prev.abort();
+            if let Some(suspense) = &self.suspense {
+                suspense.decrement(false);
+            }
+        }
+    }
+}
@@ -45,20 +45,47 @@ use std::any::{Any, TypeId};
 ///     let set_value = use_context::<ValueSetter>(cx).unwrap().0;
 /// }
 /// ```
+///
+/// Providing a second value of the same type in the same [`Scope`](crate::Scope)
+/// replaces the first one, and logs a warning in debug mode. To intentionally
+/// shadow a context value for part of the tree, provide it in a child scope,
+/// for example with the `<Provider/>` component.
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     instrument(level = "info", skip_all,)
 )]
+#[track_caller]
 pub fn provide_context<T>(cx: Scope, value: T)
 where
     T: Clone + 'static,
 {
     let id = value.type_id();
+    #[cfg(debug_assertions)]
+    let defined_at = std::panic::Location::caller();
 
     _ = with_runtime(cx.runtime, |runtime| {
         let mut contexts = runtime.scope_contexts.borrow_mut();
         let context = contexts.entry(cx.id).unwrap().or_default();
-        context.insert(id, Box::new(value) as Box<dyn Any>);
+        let _prev = context.insert(id, Box::new(value) as Box<dyn Any>);
+
+        #[cfg(debug_assertions)]
+        {
+            let mut locations = runtime.scope_context_locations.borrow_mut();
+            let locations = locations.entry(cx.id).unwrap().or_default();
+            let prev_defined_at = locations.insert(id, defined_at);
+            if _prev.is_some() {
+                crate::macros::debug_warn!(
+                    "A context of type {} was provided at {defined_at}, \
+                     replacing the context of the same type provided in the \
+                     same scope at {}. To shadow a context for part of the \
+                     tree, provide it in a child scope instead.",
+                    std::any::type_name::<T>(),
+                    prev_defined_at
+                        .map(ToString::to_string)
+                        .unwrap_or_else(|| "an unknown location".into())
+                );
+            }
+        }
     });
 }
 
@@ -76,3 +76,198 @@ macro_rules! diagnostics {
         }
     }};
 }
+
+// With the `debug-timing` feature, these record when each resource was created,
+// fetched and serialized, so slow server-side rendering can be diagnosed.
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "debug-timing")] {
+        use crate::{resource_cache::now_ms, runtime::with_runtime, Scope};
+        use std::{
+            cell::RefCell, collections::VecDeque, fmt::Write, panic::Location,
+            rc::Rc,
+        };
+
+        /// When one resource was created, fetched and serialized, as recorded with
+        /// the `debug-timing` feature.
+        ///
+        /// Times are in milliseconds since the Unix epoch, so they can be compared
+        /// across resources. A resource that was fetched more than once keeps the
+        /// times of its latest fetch.
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct ResourceTiming {
+            /// The type of the resource’s value.
+            pub ty: &'static str,
+            /// Where the resource was created.
+            pub defined_at: &'static Location<'static>,
+            /// When the resource was created.
+            pub created_at: f64,
+            /// When the resource started fetching, if it has.
+            pub fetch_started_at: Option<f64>,
+            /// When the fetch finished, if it has.
+            pub fetch_ended_at: Option<f64>,
+            /// When the value was serialized to be sent to the client, if it was.
+            /// This is only recorded on the server.
+            pub serialized_at: Option<f64>,
+        }
+
+        /// The [`ResourceTiming`]s of every resource created in a runtime, which is
+        /// how to find out which resources made server-side rendering slow, and
+        /// whether they were fetched one after another.
+        ///
+        /// This is only available with the `debug-timing` feature. Get it with
+        /// [`resource_timings`], in the browser or on the server. It is a handle
+        /// that stays up to date, and can still be read once the runtime has been
+        /// disposed, e.g., after `render_to_string_async` has finished:
+        ///
+        /// ```
+        /// # use leptos_reactive::*;
+        /// # let runtime = create_runtime();
+        /// let timings = run_scope(runtime, |cx| {
+        ///     let timings = resource_timings(cx);
+        ///     // create resources and render the app...
+        ///     timings
+        /// });
+        /// runtime.dispose();
+        ///
+        /// for timing in timings.get() {
+        ///     if let (Some(start), Some(end)) =
+        ///         (timing.fetch_started_at, timing.fetch_ended_at)
+        ///     {
+        ///         println!("{} took {}ms", timing.defined_at, end - start);
+        ///     }
+        /// }
+        /// ```
+        #[derive(Debug, Clone, Default)]
+        pub struct ResourceTimings(Rc<RefCell<TimingEntries>>);
+
+        // on the client, where a runtime can live as long as the page, only the
+        // latest `MAX_CLIENT_RESOURCE_TIMINGS` are kept
+        #[derive(Debug, Default)]
+        struct TimingEntries {
+            entries: VecDeque<ResourceTiming>,
+            dropped: usize,
+        }
+
+        #[cfg(not(feature = "ssr"))]
+        const MAX_CLIENT_RESOURCE_TIMINGS: usize = 1000;
+
+        impl ResourceTimings {
+            /// Returns the timings of every resource, in the order they were
+            /// created. In the browser, only the latest 1,000 are kept.
+            pub fn get(&self) -> Vec<ResourceTiming> {
+                self.0.borrow().entries.iter().cloned().collect()
+            }
+
+            /// Summarizes the timings as an HTML comment, with the times relative
+            /// to when the first resource was created.
+            ///
+            /// With the `debug-timing` feature, this is added to the end of the
+            /// HTML streamed by server-side rendering.
+            pub fn to_html_comment(&self) -> String {
+                let timings = &self.0.borrow().entries;
+                let start = timings
+                    .iter()
+                    .map(|timing| timing.created_at)
+                    .reduce(f64::min)
+                    .unwrap_or_default();
+                let ms = |time: Option<f64>| match time {
+                    Some(time) => format!("{:.1}", time - start),
+                    None => "-".to_string(),
+                };
+
+                let mut rows = String::new();
+                for timing in timings.iter() {
+                    _ = writeln!(
+                        rows,
+                        "  {:>7}  {:>11}  {:>9}  {:>10}  {} at {}",
+                        ms(Some(timing.created_at)),
+                        ms(timing.fetch_started_at),
+                        ms(timing.fetch_ended_at),
+                        ms(timing.serialized_at),
+                        timing.ty,
+                        timing.defined_at
+                    );
+                }
+                // a type name can't end the comment early
+                format!(
+                    "<!-- resource timings, in ms since the first resource was \
+                     created\n  created  fetch start  fetch end  serialized  \
+                     resource\n{}-->",
+                    rows.replace("--", "- -")
+                )
+            }
+        }
+
+        /// Returns the timings of the resources created in the runtime of `cx`.
+        /// See [`ResourceTimings`].
+        ///
+        /// This requires the `debug-timing` feature.
+        pub fn resource_timings(cx: Scope) -> ResourceTimings {
+            with_runtime(cx.runtime, |runtime| runtime.resource_timings.clone())
+                .unwrap_or_default()
+        }
+
+        /// The entry for one resource in its runtime's [`ResourceTimings`].
+        #[derive(Clone)]
+        pub(crate) struct TimingSlot {
+            timings: ResourceTimings,
+            index: usize,
+        }
+
+        impl TimingSlot {
+            pub(crate) fn new<T>(
+                cx: Scope,
+                defined_at: &'static Location<'static>,
+            ) -> Self {
+                let timings = resource_timings(cx);
+                let index = {
+                    let mut timings = timings.0.borrow_mut();
+                    timings.entries.push_back(ResourceTiming {
+                        ty: std::any::type_name::<T>(),
+                        defined_at,
+                        created_at: now_ms(),
+                        fetch_started_at: None,
+                        fetch_ended_at: None,
+                        serialized_at: None,
+                    });
+                    #[cfg(not(feature = "ssr"))]
+                    if timings.entries.len() > MAX_CLIENT_RESOURCE_TIMINGS {
+                        timings.entries.pop_front();
+                        timings.dropped += 1;
+                    }
+                    timings.dropped + timings.entries.len() - 1
+                };
+                Self { timings, index }
+            }
+
+            pub(crate) fn fetch_started(&self) {
+                self.record(|timing| {
+                    timing.fetch_started_at = Some(now_ms());
+                    timing.fetch_ended_at = None;
+                });
+            }
+
+            pub(crate) fn fetch_ended(&self) {
+                self.record(|timing| timing.fetch_ended_at = Some(now_ms()));
+            }
+
+            pub(crate) fn serialized(&self) {
+                self.record(|timing| {
+                    timing.serialized_at.get_or_insert_with(now_ms);
+                });
+            }
+
+            // does nothing if the entry has been dropped
+            fn record(&self, f: impl FnOnce(&mut ResourceTiming)) {
+                let mut timings = self.timings.0.borrow_mut();
+                let Some(index) = self.index.checked_sub(timings.dropped) else {
+                    return;
+                };
+                if let Some(timing) = timings.entries.get_mut(index) {
+                    f(timing);
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,14 @@
 #![forbid(unsafe_code)]
-use crate::{Scope, ScopeProperty};
+use crate::{
+    node::NodeId,
+    runtime::{with_runtime, RuntimeId},
+    scope::with_owner,
+    Scope, ScopeId, ScopeProperty, SignalGet,
+};
 use cfg_if::cfg_if;
-use std::{any::Any, cell::RefCell, marker::PhantomData, rc::Rc};
+use std::{
+    any::Any, cell::RefCell, marker::PhantomData, panic::Location, rc::Rc,
+};
 
 /// Effects run a certain chunk of code whenever the signals they depend on change.
 /// `create_effect` immediately runs the given function once, tracks its dependence
@@ -59,19 +66,23 @@ use std::{any::Any, cell::RefCell, marker::PhantomData, rc::Rc};
 )]
 #[track_caller]
 #[inline(always)]
-pub fn create_effect<T>(cx: Scope, f: impl Fn(Option<T>) -> T + 'static)
+pub fn create_effect<T>(
+    cx: Scope,
+    f: impl Fn(Option<T>) -> T + 'static,
+) -> Effect
 where
     T: 'static,
 {
     cfg_if! {
         if #[cfg(not(feature = "ssr"))] {
-            let e = cx.runtime.create_effect(f);
+            let e = with_owner(Some(cx), || cx.runtime.create_effect(f));
             //eprintln!("created effect {e:?}");
-            cx.push_scope_property(ScopeProperty::Effect(e))
+            cx.push_scope_property(ScopeProperty::Effect(e));
+            Effect::new(cx, e)
         } else {
             // clear warnings
-            _ = cx;
             _ = f;
+            Effect::new(cx, NodeId::default())
         }
     }
 }
@@ -118,15 +129,21 @@ where
 pub fn create_isomorphic_effect<T>(
     cx: Scope,
     f: impl Fn(Option<T>) -> T + 'static,
-) where
+) -> Effect
+where
     T: 'static,
 {
-    let e = cx.runtime.create_effect(f);
+    let e = with_owner(Some(cx), || cx.runtime.create_effect(f));
     //eprintln!("created effect {e:?}");
-    cx.push_scope_property(ScopeProperty::Effect(e))
+    cx.push_scope_property(ScopeProperty::Effect(e));
+    Effect::new(cx, e)
 }
 
-#[doc(hidden)]
+/// Creates an effect that updates the DOM. Like [`create_effect`], it runs
+/// immediately and does not run on the server; however, when its
+/// dependencies change, it is queued rather than rerun right away, and all
+/// queued render effects are run together. See [`RenderFlush`](crate::RenderFlush)
+/// and [`flush_sync`](crate::flush_sync).
 #[cfg_attr(
     any(debug_assertions, feature="ssr"),
     instrument(
@@ -138,15 +155,185 @@ pub fn create_isomorphic_effect<T>(
         )
     )
 )]
+#[track_caller]
 #[inline(always)]
 pub fn create_render_effect<T>(cx: Scope, f: impl Fn(Option<T>) -> T + 'static)
 where
     T: 'static,
 {
-    create_effect(cx, f);
+    cfg_if! {
+        if #[cfg(not(feature = "ssr"))] {
+            let e =
+                with_owner(Some(cx), || cx.runtime.create_render_effect(f));
+            cx.push_scope_property(ScopeProperty::Effect(e))
+        } else {
+            // clear warnings
+            _ = cx;
+            _ = f;
+        }
+    }
+}
+
+/// Derives a value and runs a side effect whenever that value changes.
+///
+/// `compute` is tracked like the body of a [`create_memo`](crate::create_memo):
+/// it reruns whenever the signals it reads change, but `effect` only runs
+/// when the value it returns is different (as determined by [`PartialEq`])
+/// from the previous one. `effect` receives the new value and the previous
+/// one, which is `None` on the first run, and does not track the signals it
+/// reads.
+///
+/// Like [`create_effect`], this runs immediately, and does not run on the
+/// server.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (route, set_route) = create_signal(cx, "/".to_string());
+///
+/// create_memoized_effect(
+///     cx,
+///     move || route.with(|route| route.split('?').next().unwrap_or_default().to_string()),
+///     move |path, prev_path| {
+///         // runs for "/", then for "/about", but not again when only
+///         // the query string changes
+///         log::debug!("page view: {path} (from {prev_path:?})");
+///     },
+/// );
+///
+/// set_route.set("/about".to_string());
+/// set_route.set("/about?tab=team".to_string());
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_memoized_effect<T>(
+    cx: Scope,
+    compute: impl Fn() -> T + 'static,
+    effect: impl Fn(&T, Option<&T>) + 'static,
+) -> Effect
+where
+    T: PartialEq + Clone + 'static,
+{
+    let value = crate::create_memo(cx, move |_| compute());
+    create_effect(cx, move |prev: Option<T>| {
+        let value = value.get();
+        cx.untrack(|| effect(&value, prev.as_ref()));
+        value
+    })
+}
+
+/// A handle to an effect, returned by [`create_effect`] and
+/// [`create_isomorphic_effect`].
+///
+/// By default, an effect runs until its [`Scope`] is disposed. The handle
+/// can be used to stop it earlier with [`dispose`](Effect::dispose), or to
+/// stop it from running for a while with [`pause`](Effect::pause).
+///
+/// The handle is `Copy` and only refers to the effect; it does not keep the
+/// effect or its scope alive, and every method does nothing once the effect
+/// or its scope has been disposed.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (data, set_data) = create_signal(cx, vec![1, 2, 3]);
+/// let (tab_visible, set_tab_visible) = create_signal(cx, true);
+///
+/// let draw_chart = create_effect(cx, move |_| {
+///     // expensive: redraw the chart whenever the data changes
+///     log::debug!("drawing {:?}", data.get());
+/// });
+///
+/// // don't redraw while the chart can't be seen, but draw the
+/// // latest data as soon as it is visible again
+/// create_effect(cx, move |_| {
+///     if tab_visible.get() {
+///         draw_chart.resume();
+///     } else {
+///         draw_chart.pause();
+///     }
+/// });
+/// # if !cfg!(feature = "ssr") {
+/// set_tab_visible.set(false);
+/// assert!(draw_chart.is_paused());
+/// # }
+/// set_data.update(|data| data.push(4));
+/// set_tab_visible.set(true);
+/// assert!(!draw_chart.is_paused());
+/// # }).dispose();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Effect {
+    pub(crate) runtime: RuntimeId,
+    pub(crate) scope: ScopeId,
+    pub(crate) id: NodeId,
 }
 
-pub(crate) struct Effect<T, F>
+impl Effect {
+    pub(crate) fn new(cx: Scope, id: NodeId) -> Self {
+        Self {
+            runtime: cx.runtime,
+            scope: cx.id,
+            id,
+        }
+    }
+
+    /// Disposes of the effect: it is removed from its scope and unsubscribed
+    /// from all of its dependencies right away, and will never run again.
+    ///
+    /// Disposing of an effect that has already been disposed does nothing.
+    pub fn dispose(self) {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime.dispose_effect(self.scope, self.id)
+        });
+    }
+
+    /// Pauses the effect. While it is paused, it keeps tracking its
+    /// dependencies, but does not run when they change. See
+    /// [`resume`](Effect::resume).
+    pub fn pause(self) {
+        _ = with_runtime(self.runtime, |runtime| {
+            if runtime.nodes.borrow().contains_key(self.id) {
+                runtime
+                    .paused_effects
+                    .borrow_mut()
+                    .entry(self.id)
+                    .map(|entry| entry.or_insert(false));
+            }
+        });
+    }
+
+    /// Resumes a paused effect. If any of its dependencies changed while it
+    /// was paused, it runs once right away.
+    pub fn resume(self) {
+        _ = with_runtime(self.runtime, |runtime| {
+            let dirty = runtime.paused_effects.borrow_mut().remove(self.id);
+            if dirty == Some(true) {
+                runtime.mark_dirty(self.id);
+                runtime.run_effects();
+            }
+        });
+    }
+
+    /// Whether the effect is currently paused.
+    pub fn is_paused(self) -> bool {
+        with_runtime(self.runtime, |runtime| {
+            runtime.paused_effects.borrow().contains_key(self.id)
+        })
+        .unwrap_or(false)
+    }
+}
+
+pub(crate) struct EffectState<T, F>
 where
     T: 'static,
     F: Fn(Option<T>) -> T,
@@ -159,9 +346,14 @@ where
 
 pub(crate) trait AnyComputation {
     fn run(&self, value: Rc<RefCell<dyn Any>>) -> bool;
+
+    /// The location at which the computation was created, if known.
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        None
+    }
 }
 
-impl<T, F> AnyComputation for Effect<T, F>
+impl<T, F> AnyComputation for EffectState<T, F>
 where
     T: 'static,
     F: Fn(Option<T>) -> T,
@@ -203,4 +395,107 @@ where
 
         true
     }
+
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        cfg_if! {
+            if #[cfg(any(debug_assertions, feature = "ssr"))] {
+                Some(self.defined_at)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+type ErrorHook = Rc<dyn Fn(&EffectPanic<'_>)>;
+
+thread_local! {
+    static EFFECT_ERROR_HOOK: RefCell<Option<ErrorHook>> = Default::default();
+}
+
+/// Information about a panic that occurred while an effect was running, which
+/// is passed to the hook registered with [`set_error_hook`].
+#[derive(Debug)]
+pub struct EffectPanic<'a> {
+    payload: &'a (dyn Any + Send),
+    defined_at: Option<&'static Location<'static>>,
+}
+
+impl<'a> EffectPanic<'a> {
+    /// The payload the effect panicked with.
+    pub fn payload(&self) -> &'a (dyn Any + Send) {
+        self.payload
+    }
+
+    /// The panic message, if the effect panicked with a string.
+    pub fn message(&self) -> Option<&'a str> {
+        self.payload
+            .downcast_ref::<&'static str>()
+            .copied()
+            .or_else(|| {
+                self.payload.downcast_ref::<String>().map(String::as_str)
+            })
+    }
+
+    /// The location at which the effect was created. This is only available
+    /// in debug mode or when the `ssr` feature is enabled.
+    pub fn defined_at(&self) -> Option<&'static Location<'static>> {
+        self.defined_at
+    }
+}
+
+/// Registers a function that will be called whenever an effect panics, which
+/// can be used to log or report the error.
+///
+/// When an effect panics, the reactive system recovers and stays usable, and
+/// the effect that panicked is unsubscribed from all of its dependencies, so
+/// that it will not run again.
+///
+/// **Note**: Panics can only be caught where they unwind. On targets that
+/// abort on panic (like `wasm32-unknown-unknown` with the default settings),
+/// the hook will not be called.
+/// ```
+/// # use leptos_reactive::*;
+/// set_error_hook(|error| {
+///     log::error!(
+///         "effect defined at {:?} panicked: {:?}",
+///         error.defined_at(),
+///         error.message()
+///     );
+/// });
+/// ```
+pub fn set_error_hook(hook: impl Fn(&EffectPanic<'_>) + 'static) {
+    EFFECT_ERROR_HOOK
+        .with(|current| *current.borrow_mut() = Some(Rc::new(hook)));
+}
+
+/// Calls the error hook registered with [`set_error_hook`], if any.
+#[cfg(panic = "unwind")]
+pub(crate) fn report_effect_panic(
+    payload: &(dyn Any + Send),
+    defined_at: Option<&'static Location<'static>>,
+) {
+    let error = EffectPanic {
+        payload,
+        defined_at,
+    };
+    #[cfg(feature = "tracing")]
+    tracing::error!(
+        defined_at = ?error.defined_at(),
+        message = error.message(),
+        "effect panicked"
+    );
+    // clone the hook out so that it can call `set_error_hook` itself
+    let hook = EFFECT_ERROR_HOOK.with(|hook| hook.borrow().clone());
+    match hook {
+        Some(hook) => hook(&error),
+        None => {
+            crate::macros::debug_warn!(
+                "An effect defined at {} panicked and will no longer run.",
+                defined_at
+                    .map(ToString::to_string)
+                    .unwrap_or_else(|| "an unknown location".into())
+            );
+        }
+    }
 }
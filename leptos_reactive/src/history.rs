@@ -0,0 +1,295 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_isomorphic_effect, create_memo, create_rw_signal,
+    resource_cache::now_ms, store_value, RwSignal, Scope, Signal,
+    SignalGetUntracked, SignalSet, SignalWith, StoredValue,
+};
+use std::{collections::VecDeque, fmt, time::Duration};
+
+/// Options for [`create_history_signal`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HistoryOptions {
+    /// The number of entries that can be undone. Once it is reached, the
+    /// oldest entry is forgotten for each new one. Defaults to `100`.
+    pub capacity: usize,
+    /// Writes that follow the previous one within this window are merged
+    /// into the same entry, so that, e.g., typing a word is undone at once.
+    /// Defaults to zero, so that every write is its own entry.
+    pub merge_window: Duration,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 100,
+            merge_window: Duration::ZERO,
+        }
+    }
+}
+
+/// The undo and redo history of a signal created with
+/// [`create_history_signal`].
+pub struct SignalHistory<T>
+where
+    T: 'static,
+{
+    value: RwSignal<T>,
+    state: StoredValue<HistoryState<T>>,
+    // the number of entries that can be undone and redone
+    lengths: RwSignal<(usize, usize)>,
+    can_undo: Signal<bool>,
+    can_redo: Signal<bool>,
+}
+
+struct HistoryState<T> {
+    options: HistoryOptions,
+    // the values to go back to, the most recent last
+    past: VecDeque<T>,
+    // the values to go forward to, the next one last
+    future: Vec<T>,
+    // the last value that was recorded
+    current: T,
+    // when the value was last written, in milliseconds
+    last_write: f64,
+    // whether the next write starts a new entry, whatever the merge window
+    boundary: bool,
+    paused: bool,
+}
+
+impl<T> Clone for SignalHistory<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SignalHistory<T> {}
+
+impl<T> fmt::Debug for SignalHistory<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalHistory")
+            .field("value", &self.value)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<T> PartialEq for SignalHistory<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.state == other.state
+    }
+}
+
+impl<T> Eq for SignalHistory<T> {}
+
+/// Creates a signal, and a [`SignalHistory`] that records its values so that
+/// writes to it can be undone and redone.
+///
+/// Each write to the signal that changes its value adds an entry to the
+/// history, unless it follows the previous one within the
+/// [`merge_window`](HistoryOptions::merge_window), in which case both are
+/// undone at once. Writes inside a [`batch`](crate::batch) are recorded as
+/// one. Undoing and redoing write to the signal without adding entries, and
+/// writing after undoing forgets the entries that could have been redone.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (text, history) =
+///     create_history_signal(cx, String::new(), HistoryOptions::default());
+/// assert!(!history.can_undo().get());
+///
+/// text.set("Hello".to_string());
+/// text.set("Hello, world".to_string());
+/// history.undo();
+/// assert_eq!(text.get(), "Hello");
+/// history.undo();
+/// assert_eq!(text.get(), "");
+/// assert!(!history.can_undo().get());
+///
+/// history.redo();
+/// assert_eq!(text.get(), "Hello");
+/// assert!(history.can_redo().get());
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_history_signal<T>(
+    cx: Scope,
+    initial: T,
+    options: HistoryOptions,
+) -> (RwSignal<T>, SignalHistory<T>)
+where
+    T: Clone + PartialEq + 'static,
+{
+    let value = create_rw_signal(cx, initial.clone());
+    let lengths = create_rw_signal(cx, (0, 0));
+    let state = store_value(
+        cx,
+        HistoryState {
+            options,
+            past: VecDeque::new(),
+            future: Vec::new(),
+            current: initial,
+            last_write: 0.0,
+            boundary: true,
+            paused: false,
+        },
+    );
+
+    create_isomorphic_effect(cx, move |_| {
+        let written = value.with(Clone::clone);
+        let recorded = state
+            .try_update_value(|state| {
+                (!state.paused).then(|| state.record(written, false))
+            })
+            .flatten();
+        // set once the state is no longer borrowed, as it notifies the
+        // subscribers of `can_undo` and `can_redo` right away
+        if let Some(recorded) = recorded {
+            lengths.set(recorded);
+        }
+    });
+
+    let history = SignalHistory {
+        value,
+        state,
+        lengths,
+        can_undo: create_memo(cx, move |_| lengths.with(|(past, _)| *past > 0))
+            .into(),
+        can_redo: create_memo(cx, move |_| {
+            lengths.with(|(_, future)| *future > 0)
+        })
+        .into(),
+    };
+    (value, history)
+}
+
+impl<T> SignalHistory<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    /// Restores the value from before the last entry, if there is one.
+    ///
+    /// The writes made while recording was [paused](SignalHistory::pause_recording)
+    /// are recorded as an entry first, so they are what is undone.
+    pub fn undo(&self) {
+        self.commit_pending();
+        let previous = self
+            .state
+            .try_update_value(|state| {
+                let previous = state.past.pop_back()?;
+                let current =
+                    std::mem::replace(&mut state.current, previous.clone());
+                state.future.push(current);
+                state.boundary = true;
+                Some((previous, state.lengths()))
+            })
+            .flatten();
+        // the recorded value is already the new one, so this adds no entry
+        if let Some((previous, lengths)) = previous {
+            self.lengths.set(lengths);
+            self.value.set(previous);
+        }
+    }
+
+    /// Restores the value that was last undone, if nothing was written
+    /// since.
+    pub fn redo(&self) {
+        self.commit_pending();
+        let next = self
+            .state
+            .try_update_value(|state| {
+                let next = state.future.pop()?;
+                let current =
+                    std::mem::replace(&mut state.current, next.clone());
+                state.push_past(current);
+                state.boundary = true;
+                Some((next, state.lengths()))
+            })
+            .flatten();
+        if let Some((next, lengths)) = next {
+            self.lengths.set(lengths);
+            self.value.set(next);
+        }
+    }
+
+    /// Whether there is an entry to [undo](SignalHistory::undo).
+    pub fn can_undo(&self) -> Signal<bool> {
+        self.can_undo
+    }
+
+    /// Whether there is an entry to [redo](SignalHistory::redo).
+    pub fn can_redo(&self) -> Signal<bool> {
+        self.can_redo
+    }
+
+    /// Stops recording the writes to the signal, e.g., while the user drags
+    /// something, until [`resume`](SignalHistory::resume) is called. The writes
+    /// made in between are then recorded as a single entry.
+    pub fn pause_recording(&self) {
+        self.state.update_value(|state| state.paused = true);
+    }
+
+    /// Records the writes made since [`pause_recording`](SignalHistory::pause_recording)
+    /// as a single entry, and records the following writes again.
+    pub fn resume(&self) {
+        self.commit_pending();
+        self.state.update_value(|state| state.paused = false);
+    }
+
+    /// Makes the next write start a new entry, even if it is within the
+    /// [`merge_window`](HistoryOptions::merge_window) of the previous one.
+    pub fn checkpoint(&self) {
+        self.state.update_value(|state| state.boundary = true);
+    }
+
+    // records the value written while recording was paused, if any
+    fn commit_pending(&self) {
+        let written = self.value.get_untracked();
+        let recorded = self
+            .state
+            .try_update_value(|state| {
+                state.paused.then(|| state.record(written, true))
+            })
+            .flatten();
+        if let Some(recorded) = recorded {
+            self.lengths.set(recorded);
+        }
+    }
+}
+
+impl<T> HistoryState<T>
+where
+    T: Clone + PartialEq,
+{
+    // records a written value, returning the new lengths
+    fn record(&mut self, written: T, new_entry: bool) -> (usize, usize) {
+        if written == self.current {
+            return self.lengths();
+        }
+        let now = now_ms();
+        let merge = !new_entry
+            && !self.boundary
+            && !self.past.is_empty()
+            && now - self.last_write
+                < self.options.merge_window.as_secs_f64() * 1000.0;
+        let previous = std::mem::replace(&mut self.current, written);
+        if !merge {
+            self.push_past(previous);
+        }
+        self.future.clear();
+        self.last_write = now;
+        self.boundary = new_entry;
+        self.lengths()
+    }
+
+    fn push_past(&mut self, value: T) {
+        self.past.push_back(value);
+        while self.past.len() > self.options.capacity {
+            self.past.pop_front();
+        }
+    }
+
+    fn lengths(&self) -> (usize, usize) {
+        (self.past.len(), self.future.len())
+    }
+}
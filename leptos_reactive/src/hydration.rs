@@ -6,9 +6,18 @@ use std::collections::{HashMap, HashSet, VecDeque};
 pub struct SharedContext {
     pub events: Vec<()>,
     pub pending_resources: HashSet<ResourceId>,
+    #[deprecated(
+        note = "resource values that arrive from the server are read from \
+                `__LEPTOS_RESOLVED_RESOURCES` when each resource is created, \
+                so this is always empty"
+    )]
     pub resolved_resources: HashMap<ResourceId, String>,
     #[allow(clippy::type_complexity)]
     pub pending_fragments: HashMap<String, FragmentData>,
+    // the resources that are waiting for the server to stream their values
+    pub(crate) awaiting_server: HashSet<ResourceId>,
+    // called once none of them are waiting
+    pub(crate) on_settled: Vec<Box<dyn FnOnce()>>,
 }
 
 /// Represents its pending `<Suspense/>` fragment.
@@ -29,6 +38,7 @@ impl std::fmt::Debug for SharedContext {
     }
 }
 
+#[allow(deprecated)]
 impl PartialEq for SharedContext {
     fn eq(&self, other: &Self) -> bool {
         self.events == other.events
@@ -39,7 +49,7 @@ impl PartialEq for SharedContext {
 
 impl Eq for SharedContext {}
 
-#[allow(clippy::derivable_impls)]
+#[allow(clippy::derivable_impls, deprecated)]
 impl Default for SharedContext {
     fn default() -> Self {
         cfg_if! {
@@ -53,20 +63,16 @@ impl Default for SharedContext {
                     .and_then(|pr| serde_wasm_bindgen::from_value(pr).map_err(|_| ()))
                     .unwrap_or_default();
 
-                let resolved_resources = js_sys::Reflect::get(
-                    &web_sys::window().unwrap(),
-                    &wasm_bindgen::JsValue::from_str("__LEPTOS_RESOLVED_RESOURCES"),
-                )
-                .unwrap_or(wasm_bindgen::JsValue::NULL);
-
-                let resolved_resources =
-                    serde_wasm_bindgen::from_value(resolved_resources).unwrap_or_default();
-
+                // values that have already arrived are read from
+                // `__LEPTOS_RESOLVED_RESOURCES` when each resource is created,
+                // as more can arrive while hydrating; see `resolvers`
                 Self {
                     events: Default::default(),
                     pending_resources,
-                    resolved_resources,
+                    resolved_resources: Default::default(),
                     pending_fragments: Default::default(),
+                    awaiting_server: Default::default(),
+                    on_settled: Default::default(),
                 }
             } else {
                 Self {
@@ -74,8 +80,111 @@ impl Default for SharedContext {
                     pending_resources: Default::default(),
                     resolved_resources: Default::default(),
                     pending_fragments: Default::default(),
+                    awaiting_server: Default::default(),
+                    on_settled: Default::default(),
                 }
             }
         }
     }
 }
+
+impl SharedContext {
+    /// Stops waiting for the server to send the value of a resource, running
+    /// the callbacks from [`Scope::on_hydration_settled`](crate::Scope::on_hydration_settled)
+    /// if it was the last one.
+    #[cfg(feature = "hydrate")]
+    pub(crate) fn stop_awaiting(
+        runtime: crate::runtime::RuntimeId,
+        id: ResourceId,
+    ) {
+        let settled = crate::runtime::with_runtime(runtime, |runtime| {
+            let mut shared_context = runtime.shared_context.borrow_mut();
+            shared_context.awaiting_server.remove(&id);
+            if shared_context.awaiting_server.is_empty() {
+                std::mem::take(&mut shared_context.on_settled)
+            } else {
+                Vec::new()
+            }
+        })
+        .unwrap_or_default();
+        for f in settled {
+            f();
+        }
+    }
+}
+
+/// The client’s side of the handshake with the `<script>` tags in which the
+/// server streams resource values.
+///
+/// A value can arrive before, while, or after the resource that reads it is
+/// hydrated. Each script stores its value in `__LEPTOS_RESOLVED_RESOURCES`,
+/// unless a resolver for that resource is waiting in
+/// `__LEPTOS_RESOURCE_RESOLVERS`, in which case it calls the resolver. Once
+/// the server has sent everything, it calls the resolvers that are still
+/// waiting with no value, and sets `__LEPTOS_STREAM_DONE`.
+#[cfg(feature = "hydrate")]
+pub(crate) mod resolvers {
+    use crate::ResourceId;
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+    fn global(name: &str) -> Option<JsValue> {
+        let window = web_sys::window()?;
+        js_sys::Reflect::get(&window, &JsValue::from_str(name)).ok()
+    }
+
+    fn global_map(name: &str) -> Option<js_sys::Map> {
+        global(name)?.dyn_into().ok()
+    }
+
+    // the key of a resource in both maps: its ID, serialized as JSON
+    fn key(id: ResourceId) -> JsValue {
+        JsValue::from_str(
+            &serde_json::to_string(&id)
+                .expect("could not serialize Resource ID"),
+        )
+    }
+
+    /// Takes the serialized value of the resource, if the server has already
+    /// sent it.
+    pub(crate) fn take(id: ResourceId) -> Option<String> {
+        let resolved = global_map("__LEPTOS_RESOLVED_RESOURCES")?;
+        let key = key(id);
+        let value = resolved.get(&key).as_string()?;
+        resolved.delete(&key);
+        Some(value)
+    }
+
+    /// Calls `resolve` with the serialized value of the resource once the
+    /// server sends it, or `fallback` if the server has finished without
+    /// sending it.
+    pub(crate) fn wait_for(
+        id: ResourceId,
+        resolve: impl FnOnce(String) + 'static,
+        fallback: impl FnOnce() + 'static,
+    ) {
+        let fallback = move || {
+            crate::macros::debug_warn!(
+                "[Resource] the server finished streaming without sending \
+                 the value of resource {id:?}, so it will be loaded on the \
+                 client instead"
+            );
+            fallback();
+        };
+
+        let done = global("__LEPTOS_STREAM_DONE")
+            .map(|done| done.is_truthy())
+            .unwrap_or(true);
+        match global_map("__LEPTOS_RESOURCE_RESOLVERS") {
+            Some(resolvers) if !done => {
+                let resolver = Closure::once_into_js(move |value: JsValue| {
+                    match value.as_string() {
+                        Some(value) => resolve(value),
+                        None => fallback(),
+                    }
+                });
+                resolvers.set(&key(id), &resolver);
+            }
+            _ => fallback(),
+        }
+    }
+}
@@ -75,48 +75,70 @@ extern crate tracing;
 
 #[macro_use]
 mod signal;
+mod async_derived;
 mod context;
 #[macro_use]
 mod diagnostics;
 mod effect;
+mod history;
 mod hydration;
 mod memo;
 mod node;
+mod oco;
+mod paged_resource;
+mod query;
+mod render_flush;
 mod resource;
+mod resource_cache;
 mod runtime;
 mod scope;
 mod selector;
 mod serialization;
+mod signal_map;
 mod signal_wrappers_read;
 mod signal_wrappers_write;
 mod slice;
 mod spawn;
 mod spawn_microtask;
+mod stats;
 mod stored_value;
 pub mod suspense;
 mod trigger;
+mod unhandled_error;
 mod watch;
 
+pub use async_derived::*;
 pub use context::*;
 pub use diagnostics::SpecialNonReactiveZone;
+#[cfg(feature = "debug-timing")]
+pub use diagnostics::{resource_timings, ResourceTiming, ResourceTimings};
 pub use effect::*;
+pub use history::*;
 pub use hydration::FragmentData;
 pub use memo::*;
+pub use oco::Oco;
+pub use paged_resource::*;
+pub use query::*;
+pub use render_flush::*;
 pub use resource::*;
+pub use resource_cache::CachePolicy;
 use runtime::*;
 pub use runtime::{create_runtime, RuntimeId};
 pub use scope::*;
 pub use selector::*;
 pub use serialization::*;
 pub use signal::{prelude as signal_prelude, *};
+pub use signal_map::*;
 pub use signal_wrappers_read::*;
 pub use signal_wrappers_write::*;
 pub use slice::*;
 pub use spawn::*;
 pub use spawn_microtask::*;
+pub use stats::*;
 pub use stored_value::*;
 pub use suspense::{GlobalSuspenseContext, SuspenseContext};
 pub use trigger::*;
+pub use unhandled_error::*;
 pub use watch::*;
 
 mod macros {
@@ -147,3 +169,13 @@ pub(crate) fn console_warn(s: &str) {
         }
     }
 }
+
+pub(crate) fn console_error(s: &str) {
+    cfg_if::cfg_if! {
+        if #[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))] {
+            web_sys::console::error_1(&wasm_bindgen::JsValue::from_str(s));
+        } else {
+            eprintln!("{s}");
+        }
+    }
+}
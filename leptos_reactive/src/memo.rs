@@ -5,7 +5,10 @@ use crate::{
     SignalDispose, SignalGet, SignalGetUntracked, SignalStream, SignalWith,
     SignalWithUntracked,
 };
-use std::{any::Any, cell::RefCell, fmt, marker::PhantomData, rc::Rc};
+use cfg_if::cfg_if;
+use std::{
+    any::Any, cell::RefCell, fmt, marker::PhantomData, panic::Location, rc::Rc,
+};
 
 // IMPLEMENTATION NOTE:
 // Memos are implemented "lazily," i.e., the inner computation is not run
@@ -519,6 +522,16 @@ where
 
         is_different
     }
+
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        cfg_if! {
+            if #[cfg(any(debug_assertions, feature = "ssr"))] {
+                Some(self.defined_at)
+            } else {
+                None
+            }
+        }
+    }
 }
 
 #[cold]
@@ -1,5 +1,9 @@
 use crate::AnyComputation;
-use std::{any::Any, cell::RefCell, rc::Rc};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 slotmap::new_key_type! {
     /// Unique ID assigned to a signal.
@@ -8,25 +12,176 @@ slotmap::new_key_type! {
 
 #[derive(Clone)]
 pub(crate) struct ReactiveNode {
-    pub value: Option<Rc<RefCell<dyn Any>>>,
+    pub value: Option<NodeValue>,
     pub state: ReactiveNodeState,
     pub node_type: ReactiveNodeType,
 }
 
 impl ReactiveNode {
+    /// The value of a memo or an effect, which is always boxed.
     pub fn value(&self) -> Rc<RefCell<dyn Any>> {
-        self.value
-            .clone()
-            .expect("ReactiveNode.value to have a value")
+        match &self.value {
+            Some(NodeValue::Boxed(value)) => Rc::clone(value),
+            _ => panic!("ReactiveNode.value to have a boxed value"),
+        }
     }
 }
 
+/// The value of a signal, memo or effect.
+#[derive(Clone)]
+pub(crate) enum NodeValue {
+    /// Any value, borrowed and downcast to its type on each access.
+    Boxed(Rc<RefCell<dyn Any>>),
+    /// The value of a signal of a primitive type, which is copied in and out
+    /// of the node without a borrow or a downcast.
+    Inline(Rc<Cell<Primitive>>),
+}
+
+impl NodeValue {
+    /// Stores primitive values inline, and boxes anything else.
+    #[inline(always)]
+    pub fn new<T: 'static>(value: T) -> Self {
+        match Primitive::new(value) {
+            Ok(value) => NodeValue::Inline(Rc::new(Cell::new(value))),
+            Err(value) => NodeValue::Boxed(Rc::new(RefCell::new(value))),
+        }
+    }
+
+    /// Applies `f` to the value, or returns `None` if it isn't a `T`.
+    #[inline(always)]
+    pub fn with<T: 'static, U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
+        match self {
+            NodeValue::Boxed(value) => {
+                value.borrow().downcast_ref::<T>().map(f)
+            }
+            NodeValue::Inline(value) => value.get().get::<T>().map(|v| f(&v)),
+        }
+    }
+
+    /// Applies `f` to the value mutably, or returns `None` if it isn't a `T`.
+    #[inline(always)]
+    pub fn update<T: 'static, U>(
+        &self,
+        f: impl FnOnce(&mut T) -> U,
+    ) -> Option<U> {
+        match self {
+            NodeValue::Boxed(value) => {
+                value.borrow_mut().downcast_mut::<T>().map(f)
+            }
+            NodeValue::Inline(slot) => {
+                let mut value = slot.get().get::<T>()?;
+                let updated = f(&mut value);
+                if let Ok(value) = Primitive::new(value) {
+                    slot.set(value);
+                }
+                Some(updated)
+            }
+        }
+    }
+}
+
+macro_rules! primitives {
+    ($($variant:ident($ty:ty)),* $(,)?) => {
+        /// A value of one of the primitive types that signals store inline.
+        #[derive(Clone, Copy)]
+        pub(crate) enum Primitive {
+            $($variant($ty),)*
+        }
+
+        impl Primitive {
+            /// Wraps the value if it is a primitive, or gives it back.
+            ///
+            /// As `T` is known when this is monomorphized, the type checks
+            /// are resolved at compile time.
+            #[inline(always)]
+            pub fn new<T: 'static>(value: T) -> Result<Self, T> {
+                let mut value = Some(value);
+                let any = &mut value as &mut dyn Any;
+                $(
+                    if let Some(value) = any.downcast_mut::<Option<$ty>>() {
+                        return Ok(Primitive::$variant(value.take().unwrap()));
+                    }
+                )*
+                Err(value.unwrap())
+            }
+
+            /// The value, if it is a `T`.
+            #[inline(always)]
+            pub fn get<T: 'static>(self) -> Option<T> {
+                match self {
+                    $(
+                        Primitive::$variant(value) => {
+                            (&mut Some(value) as &mut dyn Any)
+                                .downcast_mut::<Option<T>>()
+                                .and_then(Option::take)
+                        }
+                    )*
+                }
+            }
+
+            /// Writes the value into `slot`, if it is a primitive of the
+            /// same type as the one already there, or leaves it in `value`.
+            #[inline(always)]
+            pub fn write<T: 'static>(
+                slot: &Cell<Self>,
+                value: &mut Option<T>,
+            ) -> bool {
+                let Some(new_value) = value.take() else {
+                    return false;
+                };
+                match Primitive::new(new_value) {
+                    Ok(new_value)
+                        if std::mem::discriminant(&new_value)
+                            == std::mem::discriminant(&slot.get()) =>
+                    {
+                        slot.set(new_value);
+                        true
+                    }
+                    Ok(new_value) => {
+                        *value = new_value.get();
+                        false
+                    }
+                    Err(new_value) => {
+                        *value = Some(new_value);
+                        false
+                    }
+                }
+            }
+        }
+    };
+}
+
+primitives! {
+    Bool(bool),
+    Char(char),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    Isize(isize),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Usize(usize),
+    F32(f32),
+    F64(f64),
+}
+
 #[derive(Clone)]
 pub(crate) enum ReactiveNodeType {
     Trigger,
     Signal,
-    Memo { f: Rc<dyn AnyComputation> },
-    Effect { f: Rc<dyn AnyComputation> },
+    Memo {
+        f: Rc<dyn AnyComputation>,
+    },
+    Effect {
+        f: Rc<dyn AnyComputation>,
+        /// Whether later runs are deferred until the next render flush.
+        render: bool,
+    },
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
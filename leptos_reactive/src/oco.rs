@@ -0,0 +1,266 @@
+#![forbid(unsafe_code)]
+use std::{
+    borrow::{Borrow, Cow},
+    fmt,
+    hash::Hash,
+    ops::Deref,
+    rc::Rc,
+};
+
+/// An “owned, counted, or borrowed” value: a cheaply-clonable smart pointer
+/// that can hold a `&'a T`, an `Rc<T>`, or an owned `T::Owned`.
+///
+/// This is mostly used as `Oco<'static, str>` for text and attribute values,
+/// most of which are string literals. Literals are stored as
+/// [`Oco::Borrowed`], so they can be rendered without allocating, and cloning
+/// them only copies the reference. Cloning an owned [`String`] copies it into
+/// an [`Oco::Counted`] every time, because the original is left as it is; use
+/// [`Oco::clone_inplace`] to upgrade the original as well, so that it is only
+/// copied once and every later clone only increments a reference count.
+///
+/// ```
+/// # use leptos_reactive::Oco;
+/// let literal: Oco<'static, str> = "hello".into();
+/// assert!(literal.is_borrowed());
+///
+/// let mut owned: Oco<'static, str> = String::from("hello").into();
+/// assert!(owned.is_owned());
+///
+/// let clone = owned.clone_inplace();
+/// assert!(owned.is_counted());
+/// assert!(clone.is_counted());
+/// assert_eq!(literal, clone);
+/// ```
+pub enum Oco<'a, T: ?Sized + ToOwned + 'a> {
+    /// A static or otherwise borrowed reference.
+    Borrowed(&'a T),
+    /// A reference-counted value.
+    Counted(Rc<T>),
+    /// An owned value.
+    Owned(<T as ToOwned>::Owned),
+}
+
+impl<T: ?Sized + ToOwned> Oco<'_, T> {
+    /// Converts the value into its owned form, cloning the value if it is
+    /// borrowed or counted.
+    pub fn into_owned(self) -> <T as ToOwned>::Owned {
+        match self {
+            Oco::Borrowed(v) => v.to_owned(),
+            Oco::Counted(v) => v.as_ref().to_owned(),
+            Oco::Owned(v) => v,
+        }
+    }
+
+    /// Checks whether the value is [`Oco::Borrowed`].
+    pub const fn is_borrowed(&self) -> bool {
+        matches!(self, Oco::Borrowed(_))
+    }
+
+    /// Checks whether the value is [`Oco::Counted`].
+    pub const fn is_counted(&self) -> bool {
+        matches!(self, Oco::Counted(_))
+    }
+
+    /// Checks whether the value is [`Oco::Owned`].
+    pub const fn is_owned(&self) -> bool {
+        matches!(self, Oco::Owned(_))
+    }
+}
+
+impl Oco<'_, str> {
+    /// Returns a `&str` slice of the value.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Converts an [`Oco::Owned`] value into an [`Oco::Counted`] one, so
+    /// that it can be cloned without copying the string again.
+    pub fn upgrade_inplace(&mut self) {
+        if let Oco::Owned(v) = self {
+            *self = Oco::Counted(Rc::from(std::mem::take(v)));
+        }
+    }
+
+    /// Clones the value, first converting an [`Oco::Owned`] value into an
+    /// [`Oco::Counted`] one, so that the string is copied at most once.
+    pub fn clone_inplace(&mut self) -> Self {
+        self.upgrade_inplace();
+        self.clone()
+    }
+}
+
+impl<T: ?Sized + ToOwned> Deref for Oco<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Oco::Borrowed(v) => v,
+            Oco::Counted(v) => v,
+            Oco::Owned(v) => v.borrow(),
+        }
+    }
+}
+
+impl<T: ?Sized + ToOwned> Borrow<T> for Oco<'_, T> {
+    #[inline(always)]
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T: ?Sized + ToOwned> AsRef<T> for Oco<'_, T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<'a> Clone for Oco<'a, str> {
+    /// Clones the value. An [`Oco::Owned`] string is copied into an
+    /// [`Oco::Counted`] one, so that clones of the clone are cheap; see
+    /// [`Oco::clone_inplace`] to avoid copying it more than once.
+    fn clone(&self) -> Self {
+        match self {
+            Oco::Borrowed(v) => Oco::Borrowed(v),
+            Oco::Counted(v) => Oco::Counted(Rc::clone(v)),
+            Oco::Owned(v) => Oco::Counted(Rc::from(v.as_str())),
+        }
+    }
+}
+
+impl Default for Oco<'_, str> {
+    fn default() -> Self {
+        Oco::Borrowed("")
+    }
+}
+
+impl<T: ?Sized + ToOwned + fmt::Debug> fmt::Debug for Oco<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+impl<T: ?Sized + ToOwned + fmt::Display> fmt::Display for Oco<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+impl<'b, A, B> PartialEq<Oco<'b, B>> for Oco<'_, A>
+where
+    A: ?Sized + ToOwned + PartialEq<B>,
+    B: ?Sized + ToOwned,
+{
+    fn eq(&self, other: &Oco<'b, B>) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: ?Sized + ToOwned + Eq> Eq for Oco<'_, T> {}
+
+impl<T: ?Sized + ToOwned + PartialOrd> PartialOrd for Oco<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: ?Sized + ToOwned + Ord> Ord for Oco<'_, T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: ?Sized + ToOwned + Hash> Hash for Oco<'_, T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl PartialEq<str> for Oco<'_, str> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'b> PartialEq<&'b str> for Oco<'_, str> {
+    fn eq(&self, other: &&'b str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Oco<'_, str> {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> From<&'a str> for Oco<'a, str> {
+    #[inline(always)]
+    fn from(v: &'a str) -> Self {
+        Oco::Borrowed(v)
+    }
+}
+
+impl From<String> for Oco<'_, str> {
+    #[inline(always)]
+    fn from(v: String) -> Self {
+        Oco::Owned(v)
+    }
+}
+
+impl From<&String> for Oco<'_, str> {
+    #[inline(always)]
+    fn from(v: &String) -> Self {
+        Oco::Owned(v.clone())
+    }
+}
+
+impl From<Rc<str>> for Oco<'_, str> {
+    #[inline(always)]
+    fn from(v: Rc<str>) -> Self {
+        Oco::Counted(v)
+    }
+}
+
+impl From<Box<str>> for Oco<'_, str> {
+    #[inline(always)]
+    fn from(v: Box<str>) -> Self {
+        Oco::Counted(v.into())
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Oco<'a, str> {
+    fn from(v: Cow<'a, str>) -> Self {
+        match v {
+            Cow::Borrowed(v) => Oco::Borrowed(v),
+            Cow::Owned(v) => Oco::Owned(v),
+        }
+    }
+}
+
+impl<'a> From<Oco<'a, str>> for Cow<'a, str> {
+    fn from(v: Oco<'a, str>) -> Self {
+        match v {
+            Oco::Borrowed(v) => Cow::Borrowed(v),
+            Oco::Counted(v) => Cow::Owned(v.as_ref().to_owned()),
+            Oco::Owned(v) => Cow::Owned(v),
+        }
+    }
+}
+
+impl From<Oco<'_, str>> for String {
+    #[inline(always)]
+    fn from(v: Oco<'_, str>) -> Self {
+        v.into_owned()
+    }
+}
+
+impl From<Oco<'_, str>> for Rc<str> {
+    fn from(v: Oco<'_, str>) -> Self {
+        match v {
+            Oco::Counted(v) => v,
+            v => Rc::from(v.as_str()),
+        }
+    }
+}
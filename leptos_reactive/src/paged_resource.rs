@@ -0,0 +1,245 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_isomorphic_effect, create_memo, create_resource, create_rw_signal,
+    serialization::Serializable, spawn::spawn_local, store_value, Memo,
+    Resource, RwSignal, Scope, Signal, SignalGet, SignalGetUntracked,
+    SignalSet, SignalUpdate, SignalWith, SignalWithUntracked, StoredValue,
+};
+use futures::{future::LocalBoxFuture, FutureExt};
+use serde::{Deserialize, Serialize};
+use std::{future::Future, rc::Rc};
+
+/// One page of items, as loaded by the fetcher of a
+/// [`create_paged_resource`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// The items on this page.
+    pub items: Vec<T>,
+    /// Whether there is another page after this one.
+    pub has_more: bool,
+}
+
+/// Creates a [`PagedResource`], which loads a list one page at a time, as
+/// for an infinite scroll.
+///
+/// The `fetcher` is called with the current value of `source` and the index
+/// of the page to load, starting from `0`, and returns a [`Page`]. The first
+/// page is loaded like a [`create_resource`], so it is loaded and serialized
+/// during server rendering, and is read by `<Suspense/>`. Each following page
+/// is only loaded when [`PagedResource::load_next`] is called, which usually
+/// happens in the browser, and is added to the ones already loaded, without
+/// fetching them again.
+///
+/// Whenever `source` changes, the pages loaded so far are discarded, along
+/// with any page that is still loading, and the list starts over from the
+/// first page.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// // any async function; maybe this is calling a server function
+/// async fn load_posts(author: String, page: usize) -> Page<String> {
+///     Page {
+///         items: vec![format!("post {} by {author}", page * 10)],
+///         has_more: page < 4,
+///     }
+/// }
+///
+/// # if false {
+/// let (author, set_author) = create_signal(cx, "Alice".to_string());
+/// let posts = create_paged_resource(cx, move || author.get(), load_posts);
+///
+/// // every post loaded so far
+/// let count = move || posts.pages().with(Vec::len);
+/// // e.g., when the user scrolls to the end of the list
+/// if posts.has_more().get() {
+///     posts.load_next();
+/// }
+/// // starts over from the first page
+/// set_author.set("Bob".to_string());
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "info",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>(),
+            signal_ty = %std::any::type_name::<S>(),
+        )
+    )
+)]
+#[track_caller]
+pub fn create_paged_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S, usize) -> Fu + 'static,
+) -> PagedResource<S, T>
+where
+    S: PartialEq + Clone + 'static,
+    T: Clone + 'static,
+    Page<T>: Serializable,
+    Fu: Future<Output = Page<T>> + 'static,
+{
+    let source = create_memo(cx, move |_| source());
+    let fetcher: Rc<PageFetcher<S, T>> =
+        Rc::new(move |source, page| fetcher(source, page).boxed_local());
+    let first_page = create_resource(cx, move || source.get(), {
+        let fetcher = Rc::clone(&fetcher);
+        move |source| fetcher(source, 0)
+    });
+    let later_pages = create_rw_signal(cx, Vec::<Page<T>>::new());
+    let loading_next = create_rw_signal(cx, false);
+    let generation = store_value(cx, 0);
+
+    // start over from the first page whenever the source changes
+    create_isomorphic_effect(cx, move |prev: Option<()>| {
+        source.track();
+        if prev.is_some() {
+            generation.update_value(|generation| *generation += 1);
+            later_pages.set(Vec::new());
+            loading_next.set(false);
+        }
+    });
+
+    let pages = Signal::derive(cx, move || {
+        let mut items = first_page
+            .map(|page| page.items.clone())
+            .unwrap_or_default();
+        later_pages.with(|pages| {
+            items.extend(pages.iter().flat_map(|page| page.items.clone()))
+        });
+        items
+    });
+    let has_more = Signal::derive(cx, move || {
+        later_pages
+            .with(|pages| pages.last().map(|page| page.has_more))
+            .or_else(|| first_page.map(|page| page.has_more))
+            .unwrap_or(false)
+    });
+
+    PagedResource {
+        source,
+        first_page,
+        later_pages,
+        loading_next,
+        generation,
+        fetcher: store_value(cx, fetcher),
+        pages,
+        has_more,
+    }
+}
+
+type PageFetcher<S, T> = dyn Fn(S, usize) -> LocalBoxFuture<'static, Page<T>>;
+
+/// A list that is loaded one page at a time, created with
+/// [`create_paged_resource`].
+pub struct PagedResource<S, T>
+where
+    S: 'static,
+    T: 'static,
+{
+    source: Memo<S>,
+    first_page: Resource<S, Page<T>>,
+    later_pages: RwSignal<Vec<Page<T>>>,
+    loading_next: RwSignal<bool>,
+    // increased whenever the source changes, so that the pages that were
+    // loading for the previous source are discarded
+    generation: StoredValue<usize>,
+    fetcher: StoredValue<Rc<PageFetcher<S, T>>>,
+    pages: Signal<Vec<T>>,
+    has_more: Signal<bool>,
+}
+
+impl<S, T> PagedResource<S, T>
+where
+    S: Clone + 'static,
+    T: Clone + 'static,
+{
+    /// The items of every page loaded so far, in order. This is empty until
+    /// the first page has loaded.
+    pub fn pages(&self) -> Signal<Vec<T>> {
+        self.pages
+    }
+
+    /// Whether there is another page to load after the ones loaded so far.
+    /// This is `false` until the first page has loaded.
+    pub fn has_more(&self) -> Signal<bool> {
+        self.has_more
+    }
+
+    /// Whether a page is being loaded by [`PagedResource::load_next`].
+    pub fn is_loading_next(&self) -> Signal<bool> {
+        self.loading_next.into()
+    }
+
+    /// The resource that loads the first page, which can be used to tell
+    /// whether it is loading.
+    pub fn first_page(&self) -> Resource<S, Page<T>> {
+        self.first_page
+    }
+
+    /// Starts loading the page after the ones loaded so far.
+    ///
+    /// This does nothing while a page is already loading, including the
+    /// first one, or if there are no more pages, so it can safely be called
+    /// every time the end of the list comes into view.
+    pub fn load_next(&self) {
+        let Self {
+            source,
+            first_page,
+            later_pages,
+            loading_next,
+            generation,
+            fetcher,
+            has_more,
+            ..
+        } = *self;
+
+        // the resource's signals are disposed along with these
+        let (Some(fetcher), Some(current)) =
+            (fetcher.try_get_value(), generation.try_get_value())
+        else {
+            return;
+        };
+        if loading_next.get_untracked()
+            || first_page.loading().get_untracked()
+            || !has_more.get_untracked()
+        {
+            return;
+        }
+
+        let page = later_pages.with_untracked(Vec::len) + 1;
+        loading_next.set(true);
+        let page = fetcher(source.get_untracked(), page);
+        spawn_local(async move {
+            let page = page.await;
+            // the source may have changed, or the resource been disposed,
+            // while the page was loading
+            if generation.try_get_value() == Some(current) {
+                later_pages.update(|pages| pages.push(page));
+                loading_next.set(false);
+            }
+        });
+    }
+}
+
+impl<S, T> Clone for PagedResource<S, T>
+where
+    S: 'static,
+    T: 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, T> Copy for PagedResource<S, T>
+where
+    S: 'static,
+    T: 'static,
+{
+}
@@ -0,0 +1,181 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_isomorphic_effect, create_resource, runtime::with_runtime, Resource,
+    Scope, Serializable, Trigger,
+};
+use futures::{
+    future::{LocalBoxFuture, Shared},
+    FutureExt,
+};
+use rustc_hash::FxHashMap;
+use std::{
+    any::Any, borrow::Cow, cell::RefCell, future::Future, hash::Hash, rc::Rc,
+};
+
+/// Creates a [`Resource`](crate::Resource) whose results are shared with
+/// every other query resource created with the same `key`.
+///
+/// When several components load the same data (for example, by calling the
+/// same server function), each of them can use a query resource with the
+/// same `key`. Then,
+/// 1. if they start loading the same `source` value at the same time, the
+///    `fetcher` only runs once, and all of them wait for the same request;
+/// 2. if one of them has already loaded that `source` value, the others
+///    reuse its result rather than loading it again.
+///
+/// Results are kept until they are invalidated with [`invalidate_query`],
+/// which also makes every resource that uses the `key` load its data again.
+///
+/// The cache belongs to the reactive runtime. During server rendering, each
+/// request is rendered in its own runtime, so results are never shared
+/// between requests.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// // any async function; maybe this is calling a server function
+/// async fn get_server_count() -> i32 {
+///     42
+/// }
+///
+/// # if false {
+/// // these will only call `get_server_count()` once
+/// let a = create_query_resource(cx, "count", || (), |_| get_server_count());
+/// let b = create_query_resource(cx, "count", || (), |_| get_server_count());
+///
+/// // ... and after the count has been changed, both will load it again
+/// invalidate_query(cx, "count");
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "info",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>(),
+            signal_ty = %std::any::type_name::<S>(),
+        )
+    )
+)]
+pub fn create_query_resource<S, T, Fu>(
+    cx: Scope,
+    key: impl Into<Cow<'static, str>>,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Eq + Hash + Clone + 'static,
+    T: Serializable + Clone + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let (invalidated, cache) = query_entry::<S, T>(cx, key.into());
+
+    let resource = create_resource(cx, source, move |source: S| {
+        let load = || fetcher(source.clone()).boxed_local().shared();
+        match &cache {
+            Some(cache) => cache
+                .borrow_mut()
+                .entry(source.clone())
+                .or_insert_with(load)
+                .clone(),
+            None => load(),
+        }
+    });
+
+    // refetch whenever the query is invalidated
+    create_isomorphic_effect(cx, move |prev: Option<()>| {
+        invalidated.track();
+        if prev.is_some() {
+            resource.refetch();
+        }
+    });
+
+    resource
+}
+
+/// Invalidates every result cached for the given query `key`, and makes
+/// every resource created with [`create_query_resource`] using that `key`
+/// load its data again.
+///
+/// This is typically called after an action has changed the data that the
+/// query loads.
+pub fn invalidate_query(cx: Scope, key: &str) {
+    let invalidated = with_runtime(cx.runtime, |runtime| {
+        runtime.queries.borrow().get(key).map(|query| {
+            query.cache.clear();
+            query.invalidated
+        })
+    })
+    .ok()
+    .flatten();
+
+    if let Some(invalidated) = invalidated {
+        invalidated.notify();
+    }
+}
+
+type QueryFuture<T> = Shared<LocalBoxFuture<'static, T>>;
+type TypedQueryCache<S, T> = Rc<RefCell<FxHashMap<S, QueryFuture<T>>>>;
+
+/// The shared state of all query resources that use the same key.
+pub(crate) struct QueryState {
+    invalidated: Trigger,
+    cache: Box<dyn AnyQueryCache>,
+}
+
+trait AnyQueryCache {
+    fn as_any(&self) -> &dyn Any;
+
+    fn clear(&self);
+}
+
+impl<S, T> AnyQueryCache for TypedQueryCache<S, T>
+where
+    S: 'static,
+    T: 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clear(&self) {
+        self.borrow_mut().clear();
+    }
+}
+
+/// Returns the invalidation trigger and cache for the given key, creating
+/// them if necessary. The cache is `None` if the key is already used by a
+/// query with different source or output types.
+fn query_entry<S, T>(
+    cx: Scope,
+    key: Cow<'static, str>,
+) -> (Trigger, Option<TypedQueryCache<S, T>>)
+where
+    S: 'static,
+    T: 'static,
+{
+    with_runtime(cx.runtime, |runtime| {
+        let mut queries = runtime.queries.borrow_mut();
+        let query = queries.entry(key.clone()).or_insert_with(|| QueryState {
+            invalidated: cx.runtime.create_trigger(),
+            cache: Box::<TypedQueryCache<S, T>>::default(),
+        });
+        let cache = query
+            .cache
+            .as_any()
+            .downcast_ref::<TypedQueryCache<S, T>>()
+            .cloned();
+        if cache.is_none() {
+            crate::macros::debug_warn!(
+                "The query key {key:?} is already used by a query with a \
+                 different source or value type, so its results will not be \
+                 shared."
+            );
+        }
+        (query.invalidated, cache)
+    })
+    .expect("tried to create a query in a runtime that has been disposed")
+}
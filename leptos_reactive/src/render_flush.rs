@@ -0,0 +1,93 @@
+#![forbid(unsafe_code)]
+use crate::{runtime::with_runtime, Scope};
+
+/// When the DOM updates queued by render effects are applied.
+///
+/// Reactive text, attributes, classes, properties and styles are updated by
+/// *render effects*. Rather than writing to the DOM as soon as one of their
+/// signals changes, render effects are queued and run together, so that
+/// several signal updates in the same task only touch the DOM once. Their
+/// first run, when the view is created, still happens immediately.
+///
+/// Outside the browser, queued render effects always run immediately.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderFlush {
+    /// Apply queued updates in a microtask, i.e., after the current task
+    /// (an event handler, for example) has finished. This is the default.
+    #[default]
+    Microtask,
+    /// Apply queued updates just before the browser paints the next frame,
+    /// using `requestAnimationFrame`.
+    AnimationFrame,
+}
+
+/// Sets when the DOM updates queued by render effects are applied, for the
+/// runtime the scope belongs to. See [`RenderFlush`].
+pub fn set_render_flush(cx: Scope, flush: RenderFlush) {
+    _ = with_runtime(cx.runtime, |runtime| runtime.render_flush.set(flush));
+}
+
+/// Immediately applies any DOM updates queued by render effects, and then
+/// runs the callbacks registered with [`queue_after_render`].
+///
+/// This is an escape hatch for code that needs to read the layout of the
+/// page right after changing a signal, like an event handler that measures
+/// an element:
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (expanded, set_expanded) = create_signal(cx, false);
+///
+/// let on_click = move || {
+///     set_expanded.set(true);
+///     // without this, the DOM would only be updated after this handler
+///     flush_sync(cx);
+///     // ... now it's safe to measure the expanded element
+/// };
+/// # on_click();
+/// # }).dispose();
+/// ```
+pub fn flush_sync(cx: Scope) {
+    _ = with_runtime(cx.runtime, |runtime| runtime.flush_render_effects());
+}
+
+/// Runs `f` once the DOM updates that are currently queued by render
+/// effects have been applied. If no updates are queued, `f` still runs
+/// asynchronously, after the current task.
+///
+/// This is useful for reading the layout of the page, for example to
+/// measure an element from a [`NodeRef`](https://docs.rs/leptos/latest/leptos/struct.NodeRef.html)
+/// after the signals it depends on have changed.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (count, set_count) = create_signal(cx, 0);
+///
+/// set_count.set(1);
+/// queue_after_render(cx, move || {
+///     // the DOM now shows the new count
+///     assert_eq!(count.get_untracked(), 1);
+/// });
+/// # }).dispose();
+/// ```
+pub fn queue_after_render(cx: Scope, f: impl FnOnce() + 'static) {
+    _ = with_runtime(cx.runtime, |runtime| {
+        runtime.after_render.borrow_mut().push(Box::new(f));
+        runtime.schedule_render_flush();
+    });
+}
+
+#[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+pub(crate) fn request_animation_frame(task: impl FnOnce() + 'static) {
+    use js_sys::{Function, Reflect};
+    use wasm_bindgen::prelude::*;
+
+    let task = Closure::once_into_js(task);
+    let window = web_sys::window().expect("window not available");
+    let request_animation_frame =
+        Reflect::get(&window, &JsValue::from_str("requestAnimationFrame"))
+            .expect("requestAnimationFrame not available");
+    let request_animation_frame =
+        request_animation_frame.unchecked_into::<Function>();
+    _ = request_animation_frame.call1(&window, &task);
+}
@@ -1,20 +1,29 @@
 #![forbid(unsafe_code)]
 use crate::{
     create_effect, create_isomorphic_effect, create_memo, create_signal,
-    queue_microtask,
+    queue_microtask, report_unhandled_error,
+    resource_cache::{
+        CachePolicy, CachedValue, LruResourceCache, ResourceCache,
+    },
     runtime::{with_runtime, RuntimeId},
     serialization::Serializable,
     spawn::spawn_local,
     use_context, GlobalSuspenseContext, Memo, ReadSignal, Scope, ScopeProperty,
-    SignalDispose, SignalGetUntracked, SignalSet, SignalUpdate, SignalWith,
-    SuspenseContext, WriteSignal,
+    Signal, SignalDispose, SignalGet, SignalGetUntracked, SignalSet,
+    SignalUpdate, SignalWith, SignalWithUntracked, SuspenseContext,
+    UnhandledError, WriteSignal,
+};
+use futures::{
+    channel::oneshot,
+    future::{abortable, AbortHandle},
 };
 use std::{
     any::Any,
     cell::{Cell, RefCell},
     collections::HashSet,
-    fmt::Debug,
+    fmt::{Debug, Display},
     future::Future,
+    hash::Hash,
     marker::PhantomData,
     panic::Location,
     pin::Pin,
@@ -56,11 +65,11 @@ use std::{
 /// // when we read the signal, it contains either
 /// // 1) None (if the Future isn't ready yet) or
 /// // 2) Some(T) (if the future's already resolved)
-/// assert_eq!(cats.read(cx), Some(vec!["1".to_string()]));
+/// assert_eq!(cats.get(), Some(vec!["1".to_string()]));
 ///
 /// // when the signal's value changes, the `Resource` will generate and run a new `Future`
 /// set_how_many_cats.set(2);
-/// assert_eq!(cats.read(cx), Some(vec!["2".to_string()]));
+/// assert_eq!(cats.get(), Some(vec!["2".to_string()]));
 /// # }
 /// # }).dispose();
 /// ```
@@ -76,6 +85,7 @@ use std::{
         )
     )
 )]
+#[track_caller]
 pub fn create_resource<S, T, Fu>(
     cx: Scope,
     source: impl Fn() -> S + 'static,
@@ -130,6 +140,8 @@ where
         fetcher,
         initial_value,
         ResourceSerialization::Serializable,
+        None,
+        None,
     )
 }
 
@@ -145,6 +157,11 @@ where
 /// might use a blocking resource to load blog post metadata, which will prevent the page from
 /// returning until that data has loaded.
 ///
+/// A blocking resource that is read outside any `<Suspense/>`, like one that is only used to
+/// set a `<Title/>` or `<Meta/>` tag, still delays the first chunk of HTML: once all blocking
+/// resources have loaded, `<head>` metadata is generated with their values. Any other
+/// `<Suspense/>` keeps streaming in afterward.
+///
 /// **Note**: This is not “blocking” in the sense that it blocks the current thread. Rather,
 /// it is blocking in the sense that it blocks the server from sending a response.
 #[cfg_attr(
@@ -176,15 +193,232 @@ where
         fetcher,
         None,
         ResourceSerialization::Blocking,
+        None,
+        None,
+    )
+}
+
+/// Creates a [`Resource`](crate::Resource) that caches the value it loads for
+/// each value of the `source`, according to the given [`CachePolicy`].
+///
+/// When the `source` changes to a value that has already been loaded, the
+/// cached value is returned immediately, without running the `fetcher`. If
+/// the cached value is older than [`CachePolicy::stale_after`], it is still
+/// returned immediately, but is also refetched in the background; while that
+/// happens, [`Resource::refreshing`] is `true` (but [`Resource::loading`] is
+/// not, and any `<Suspense/>` will not show its fallback).
+///
+/// The cache belongs to the resource, and is dropped along with it.
+/// [`Resource::refetch`] always runs the `fetcher`, bypassing the cache.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::time::Duration;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn load_contact(id: usize) -> String {
+///     // pretend we're fetching a contact
+///     format!("contact {id}")
+/// }
+///
+/// let (contact_id, set_contact_id) = create_signal(cx, 1);
+///
+/// # if false {
+/// let contact = create_resource_with_cache(
+///     cx,
+///     move || contact_id.get(),
+///     load_contact,
+///     CachePolicy {
+///         max_entries: 32,
+///         stale_after: Some(Duration::from_secs(30)),
+///     },
+/// );
+///
+/// set_contact_id.set(2);
+/// // switching back to `1` returns the cached value immediately
+/// set_contact_id.set(1);
+/// assert_eq!(contact.get(), Some("contact 1".to_string()));
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "info",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>(),
+            signal_ty = %std::any::type_name::<S>(),
+        )
+    )
+)]
+#[track_caller]
+pub fn create_resource_with_cache<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    policy: CachePolicy,
+) -> Resource<S, T>
+where
+    S: PartialEq + Eq + Hash + Clone + 'static,
+    T: Serializable + Clone + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    create_resource_helper(
+        cx,
+        source,
+        fetcher,
+        None,
+        ResourceSerialization::Serializable,
+        Some(Rc::new(LruResourceCache::new(policy))),
+        None,
+    )
+}
+
+/// Creates a [`Resource`](crate::Resource) that depends on another resource,
+/// or on anything else that may not be available yet.
+///
+/// The `source` returns `None` until the resource is ready to be fetched. The
+/// `fetcher` is only called once the `source` returns `Some`, so a resource
+/// that is chained to another one fetches exactly once when the first one has
+/// loaded, rather than once with an incomplete source and again when it
+/// changes. While it waits, the resource is loading, and any `<Suspense/>` it
+/// is read under shows its fallback.
+///
+/// Resources that don’t depend on each other should be created separately,
+/// so that they load concurrently, and combined with [`join_resources`] when
+/// something depends on all of them.
+///
+/// When server-side rendering is used, a chained resource whose `source` is
+/// still `None` once every other resource has loaded is sent to the client as
+/// skipped, and the client does not fetch it while hydrating either.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn load_user(id: usize) -> String {
+///     format!("user {id}")
+/// }
+/// async fn load_posts(user: String) -> Vec<String> {
+///     vec![format!("a post by {user}")]
+/// }
+///
+/// # if false {
+/// let user = create_resource(cx, || 1, load_user);
+/// // only fetched once `user` has loaded
+/// let posts = create_chained_resource(cx, move || user.get(), load_posts);
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "info",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>(),
+            signal_ty = %std::any::type_name::<S>(),
+        )
     )
+)]
+#[track_caller]
+pub fn create_chained_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> Option<S> + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+) -> Resource<Option<S>, T>
+where
+    S: PartialEq + Clone + 'static,
+    T: Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    create_resource_helper(
+        cx,
+        source,
+        move |source: Option<S>| {
+            fetcher(
+                source.expect("a chained resource fetched without a source"),
+            )
+        },
+        None,
+        ResourceSerialization::Serializable,
+        None,
+        Some(Rc::new(Option::is_some)),
+    )
+}
+
+/// Combines several resources into a signal that holds all of their values
+/// once every one of them has loaded, and `None` until then.
+///
+/// Each resource loads concurrently with the others. Reading the signal reads
+/// every resource, so that a `<Suspense/>` waits for all of them, and the
+/// signal can be used as the `source` of a [`create_chained_resource`] that
+/// depends on all of them, without waiting for them one after another.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// # if false {
+/// let user = create_resource(cx, || (), |_| async { "user".to_string() });
+/// let settings = create_resource(cx, || (), |_| async { 42 });
+/// let both = join_resources(cx, (user, settings));
+/// let dashboard = create_chained_resource(
+///     cx,
+///     move || both.get(),
+///     |(user, settings)| async move { format!("{user}: {settings}") },
+/// );
+/// # }
+/// # }).dispose();
+/// ```
+pub fn join_resources<R: JoinResources>(
+    cx: Scope,
+    resources: R,
+) -> Signal<Option<R::Output>> {
+    Signal::derive(cx, move || resources.read_all())
+}
+
+/// A tuple of resources that can be combined with [`join_resources`].
+pub trait JoinResources: Copy + 'static {
+    /// The values of all of the resources.
+    type Output: Clone;
+
+    /// Reads every resource, and returns all of their values once they have
+    /// all loaded.
+    fn read_all(&self) -> Option<Self::Output>;
+}
+
+macro_rules! impl_join_resources {
+    ($($s:ident $t:ident $idx:tt),+) => {
+        impl<$($s, $t),+> JoinResources for ($(Resource<$s, $t>,)+)
+        where
+            $($s: Clone + 'static, $t: Clone + 'static,)+
+        {
+            type Output = ($($t,)+);
+
+            fn read_all(&self) -> Option<Self::Output> {
+                // read all of them before returning, so each one is tracked
+                let values = ($(self.$idx.get(),)+);
+                Some(($(values.$idx?,)+))
+            }
+        }
+    };
 }
 
+impl_join_resources!(S0 T0 0, S1 T1 1);
+impl_join_resources!(S0 T0 0, S1 T1 1, S2 T2 2);
+impl_join_resources!(S0 T0 0, S1 T1 1, S2 T2 2, S3 T3 3);
+
+#[allow(clippy::type_complexity)]
+#[track_caller]
 fn create_resource_helper<S, T, Fu>(
     cx: Scope,
     source: impl Fn() -> S + 'static,
     fetcher: impl Fn(S) -> Fu + 'static,
     initial_value: Option<T>,
     serializable: ResourceSerialization,
+    cache: Option<Rc<dyn ResourceCache<S, T>>>,
+    is_ready: Option<Rc<dyn Fn(&S) -> bool>>,
 ) -> Resource<S, T>
 where
     S: PartialEq + Clone + 'static,
@@ -195,6 +429,8 @@ where
     let (value, set_value) = create_signal(cx, initial_value);
 
     let (loading, set_loading) = create_signal(cx, false);
+    let (refreshing, set_refreshing) = create_signal(cx, false);
+    let (waiting, set_waiting) = create_signal(cx, false);
 
     //crate::macros::debug_warn!("creating fetcher");
     let fetcher = Rc::new(move |s| {
@@ -207,6 +443,8 @@ where
         set_value,
         loading,
         set_loading,
+        refreshing,
+        set_refreshing,
         source,
         fetcher,
         resolved: Rc::new(Cell::new(resolved)),
@@ -214,6 +452,20 @@ where
         version: Rc::new(Cell::new(0)),
         suspense_contexts: Default::default(),
         serializable,
+        cache,
+        is_ready,
+        waiting,
+        set_waiting,
+        in_flight: Default::default(),
+        unread: Default::default(),
+        report_unhandled: Default::default(),
+        #[cfg(feature = "debug-timing")]
+        timing: crate::diagnostics::TimingSlot::new::<T>(
+            cx,
+            Location::caller(),
+        ),
+        #[cfg(feature = "tracing")]
+        defined_at: Location::caller(),
     });
 
     let id = with_runtime(cx.runtime, |runtime| {
@@ -230,6 +482,19 @@ where
         }
     });
 
+    // on the server, a chained resource that is still waiting once every
+    // other resource has loaded will never be fetched, so it stops loading
+    // rather than keeping its `<Suspense/>` pending forever
+    #[cfg(feature = "ssr")]
+    if r.is_ready.is_some() {
+        let r = Rc::clone(&r);
+        create_isomorphic_effect(cx, move |_| {
+            if r.waiting.get() && resources_settled(cx) && !r.source_ready() {
+                r.skip();
+            }
+        });
+    }
+
     cx.push_scope_property(ScopeProperty::Resource(id));
 
     Resource {
@@ -285,6 +550,7 @@ where
         )
     )
 )]
+#[track_caller]
 pub fn create_local_resource<S, T, Fu>(
     cx: Scope,
     source: impl Fn() -> S + 'static,
@@ -318,6 +584,7 @@ where
         )
     )
 )]
+#[track_caller]
 pub fn create_local_resource_with_initial_value<S, T, Fu>(
     cx: Scope,
     source: impl Fn() -> S + 'static,
@@ -333,6 +600,8 @@ where
     let (value, set_value) = create_signal(cx, initial_value);
 
     let (loading, set_loading) = create_signal(cx, false);
+    let (refreshing, set_refreshing) = create_signal(cx, false);
+    let (waiting, set_waiting) = create_signal(cx, false);
 
     let fetcher = Rc::new(move |s| {
         Box::pin(fetcher(s)) as Pin<Box<dyn Future<Output = T>>>
@@ -344,6 +613,8 @@ where
         set_value,
         loading,
         set_loading,
+        refreshing,
+        set_refreshing,
         source,
         fetcher,
         resolved: Rc::new(Cell::new(resolved)),
@@ -351,6 +622,20 @@ where
         version: Rc::new(Cell::new(0)),
         suspense_contexts: Default::default(),
         serializable: ResourceSerialization::Local,
+        cache: None,
+        is_ready: None,
+        waiting,
+        set_waiting,
+        in_flight: Default::default(),
+        unread: Default::default(),
+        report_unhandled: Default::default(),
+        #[cfg(feature = "debug-timing")]
+        timing: crate::diagnostics::TimingSlot::new::<T>(
+            cx,
+            Location::caller(),
+        ),
+        #[cfg(feature = "tracing")]
+        defined_at: Location::caller(),
     });
 
     let id = with_runtime(cx.runtime, |runtime| {
@@ -397,68 +682,121 @@ where
     S: PartialEq + Clone + 'static,
     T: Serializable + 'static,
 {
-    use wasm_bindgen::{JsCast, UnwrapThrowExt};
+    use crate::SignalWithUntracked;
+    use wasm_bindgen::UnwrapThrowExt;
+
+    // a chained resource that is waiting for its source keeps the server's
+    // data until it is ready to use it
+    if !r.source_ready() {
+        r.load(false);
+        return;
+    }
 
-    _ = with_runtime(cx.runtime, |runtime| {
-        let mut context = runtime.shared_context.borrow_mut();
-        if let Some(data) = context.resolved_resources.remove(&id) {
+    let pending = with_runtime(cx.runtime, |runtime| {
+        runtime
+            .shared_context
+            .borrow_mut()
+            .pending_resources
+            .remove(&id)
+    })
+    .unwrap_or(false);
+
+    // the value may have arrived before or while hydrating, even if the
+    // resource was pending when the server sent its first chunk
+    match crate::hydration::resolvers::take(id) {
+        Some(data) if data == SKIPPED_RESOURCE => {
+            // the server skipped this fetch, so the client doesn't run it either
+            r.skip();
+            r.source.track();
+        }
+        Some(data) => {
             // The server already sent us the serialized resource value, so
             // deserialize & set it now
-            context.pending_resources.remove(&id); // no longer pending
             r.resolved.set(true);
 
             let res = T::de(&data)
                 .expect_throw("could not deserialize Resource JSON");
 
+            if let Some(cache) = &r.cache {
+                r.source.with_untracked(|source| {
+                    cache.insert(source.clone(), &res)
+                });
+            }
             r.set_value.update(|n| *n = Some(res));
             r.set_loading.update(|n| *n = false);
 
             // for reactivity
             r.source.track();
-        } else if context.pending_resources.remove(&id) {
+        }
+        None if pending => {
             // We're still waiting for the resource, add a "resolver" closure so
             // that it will be set as soon as the server sends the serialized
             // value
             r.set_loading.update(|n| *n = true);
+            _ = with_runtime(cx.runtime, |runtime| {
+                runtime
+                    .shared_context
+                    .borrow_mut()
+                    .awaiting_server
+                    .insert(id)
+            });
 
             let resolve = {
+                let runtime = cx.runtime;
                 let resolved = r.resolved.clone();
                 let set_value = r.set_value;
                 let set_loading = r.set_loading;
+                let source = r.source;
+                let cache = r.cache.clone();
+                let r = Rc::clone(&r);
                 move |res: String| {
-                    let res = T::de(&res)
-                        .expect_throw("could not deserialize Resource JSON");
-                    resolved.set(true);
-                    set_value.update(|n| *n = Some(res));
-                    set_loading.update(|n| *n = false);
+                    if res == SKIPPED_RESOURCE {
+                        r.skip();
+                    } else {
+                        let res = T::de(&res).expect_throw(
+                            "could not deserialize Resource JSON",
+                        );
+                        if let Some(cache) = &cache {
+                            source.with_untracked(|source| {
+                                cache.insert(source.clone(), &res)
+                            });
+                        }
+                        resolved.set(true);
+                        // the resource may have been disposed while waiting
+                        // for the server
+                        if set_value.try_update(|n| *n = Some(res)).is_none() {
+                            crate::debug_dropped_value(set_value.defined_at());
+                        } else {
+                            set_loading.try_update(|n| *n = false);
+                        }
+                    }
+                    // after the views that read the value have been updated
+                    crate::hydration::SharedContext::stop_awaiting(runtime, id);
                 }
             };
-            let resolve = wasm_bindgen::closure::Closure::wrap(
-                Box::new(resolve) as Box<dyn Fn(String)>,
-            );
-            let resource_resolvers = js_sys::Reflect::get(
-                &web_sys::window().unwrap(),
-                &wasm_bindgen::JsValue::from_str("__LEPTOS_RESOURCE_RESOLVERS"),
-            )
-            .expect_throw(
-                "no __LEPTOS_RESOURCE_RESOLVERS found in the JS global scope",
-            );
-            let id = serde_json::to_string(&id)
-                .expect_throw("could not serialize Resource ID");
-            _ = js_sys::Reflect::set(
-                &resource_resolvers,
-                &wasm_bindgen::JsValue::from_str(&id),
-                resolve.as_ref().unchecked_ref(),
-            );
+            // the server has finished without sending the value, e.g.,
+            // because the resource was created after the page was rendered
+            let fallback = {
+                let runtime = cx.runtime;
+                let r = Rc::clone(&r);
+                move || {
+                    if !r.resolved.get() {
+                        r.load(false);
+                    }
+                    crate::hydration::SharedContext::stop_awaiting(runtime, id);
+                }
+            };
+            crate::hydration::resolvers::wait_for(id, resolve, fallback);
 
             // for reactivity
             r.source.track()
-        } else {
+        }
+        None => {
             // Server didn't mark the resource as pending, so load it on the
             // client
             r.load(false);
         }
-    })
+    }
 }
 
 impl<S, T> Resource<S, T>
@@ -470,8 +808,13 @@ where
     /// resource is still pending). Also subscribes the running effect to this
     /// resource.
     ///
+    /// The read is registered with the `<Suspense/>` that `cx` is rendered
+    /// under, if any.
+    ///
     /// If you want to get the value without cloning it, use [`Resource::with`].
     /// (`value.read(cx)` is equivalent to `value.with(cx, T::clone)`.)
+    #[deprecated = "`Resource::get` finds the current scope itself; use \
+                    `resource.get()` instead"]
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "info", skip_all,)
@@ -481,14 +824,30 @@ where
     where
         T: Clone,
     {
-        let location = std::panic::Location::caller();
-        with_runtime(self.runtime, |runtime| {
-            runtime.resource(self.id, |resource: &ResourceState<S, T>| {
-                resource.read(cx, location)
-            })
-        })
-        .ok()
-        .flatten()
+        self.with_owner(Some(cx), T::clone)
+    }
+
+    /// Clones and returns the current value of the resource ([Option::None] if the
+    /// resource is still pending). Also subscribes the running effect to this
+    /// resource.
+    ///
+    /// The read is registered with the `<Suspense/>` that the
+    /// [current scope](Scope::current) is rendered under, if any. A read in an
+    /// untracked function or in a spawned future has no current scope, so it
+    /// is never registered with a `<Suspense/>`.
+    ///
+    /// If you want to get the value without cloning it, use [`Resource::map`].
+    /// (`value.get()` is equivalent to `value.map(T::clone)`.)
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        instrument(level = "info", skip_all,)
+    )]
+    #[track_caller]
+    pub fn get(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.with_owner(Scope::current(), T::clone)
     }
 
     /// Applies a function to the current value of the resource, and subscribes
@@ -496,18 +855,67 @@ where
     /// resolved, the function won't be called and this will return
     /// [`Option::None`].
     ///
+    /// The read is registered with the `<Suspense/>` that `cx` is rendered
+    /// under, if any.
+    ///
     /// If you want to get the value by cloning it, you can use
-    /// [`Resource::read`].
+    /// [`Resource::get`].
+    #[deprecated = "`Resource::map` finds the current scope itself; use \
+                    `resource.map(f)` instead"]
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "info", skip_all,)
     )]
     #[track_caller]
     pub fn with<U>(&self, cx: Scope, f: impl FnOnce(&T) -> U) -> Option<U> {
+        self.with_owner(Some(cx), f)
+    }
+
+    /// Applies a function to the current value of the resource, and subscribes
+    /// the running effect to this resource. If the resource hasn't yet
+    /// resolved, the function won't be called and this will return
+    /// [`Option::None`].
+    ///
+    /// Like [`Resource::get`], this is registered with the `<Suspense/>` of
+    /// the [current scope](Scope::current).
+    ///
+    /// If you want to get the value by cloning it, you can use
+    /// [`Resource::get`].
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        instrument(level = "info", skip_all,)
+    )]
+    #[track_caller]
+    pub fn map<U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
+        self.with_owner(Scope::current(), f)
+    }
+
+    #[track_caller]
+    fn with_owner<U>(
+        &self,
+        owner: Option<Scope>,
+        f: impl FnOnce(&T) -> U,
+    ) -> Option<U> {
+        self.try_with_owner(owner, |value| value.as_ref().map(f))
+            .flatten()
+    }
+
+    // returns `None` if the resource has been disposed
+    #[track_caller]
+    fn try_with_owner<U>(
+        &self,
+        owner: Option<Scope>,
+        f: impl FnOnce(&Option<T>) -> U,
+    ) -> Option<U> {
         let location = std::panic::Location::caller();
+        // a scope from another runtime can't contain this resource's suspense
+        let owner = owner.filter(|owner| owner.runtime == self.runtime);
         with_runtime(self.runtime, |runtime| {
+            if !runtime.resources.borrow().contains_key(self.id) {
+                return None;
+            }
             runtime.resource(self.id, |resource: &ResourceState<S, T>| {
-                resource.with(cx, f, location)
+                resource.with(owner, f, location)
             })
         })
         .ok()
@@ -531,13 +939,37 @@ where
         )
     }
 
-    /// Re-runs the async function with the current source data.
+    /// Returns a signal that indicates whether the resource is refetching a
+    /// stale cached value in the background. This is only ever `true` for
+    /// resources created with [`create_resource_with_cache`].
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        instrument(level = "trace", skip_all,)
+    )]
+    pub fn refreshing(&self) -> ReadSignal<bool> {
+        with_runtime(self.runtime, |runtime| {
+            runtime.resource(self.id, |resource: &ResourceState<S, T>| {
+                resource.refreshing
+            })
+        })
+        .expect(
+            "tried to call Resource::refreshing() in a runtime that has \
+             already been disposed.",
+        )
+    }
+
+    /// Re-runs the async function with the current source data. For a
+    /// resource created with [`create_resource_with_cache`], this bypasses
+    /// the cache.
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "trace", skip_all,)
     )]
     pub fn refetch(&self) {
         _ = with_runtime(self.runtime, |runtime| {
+            if !runtime.resources.borrow().contains_key(self.id) {
+                return;
+            }
             runtime.resource(self.id, |resource: &ResourceState<S, T>| {
                 resource.refetch()
             })
@@ -571,6 +1003,120 @@ where
     }
 }
 
+impl<S, T, E> Resource<S, Result<T, E>>
+where
+    S: Clone + 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// Returns a signal with the value of the resource, with `f` applied to
+    /// any error, so that the resource can be defined with the error type the
+    /// rest of the app expects.
+    ///
+    /// Reading the signal reads the resource, exactly like
+    /// [`Resource::get`].
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum AppError {
+    ///     NotFound(String),
+    /// }
+    ///
+    /// async fn load_user(id: u32) -> Result<String, String> {
+    ///     Err(format!("no user {id}"))
+    /// }
+    ///
+    /// # if false {
+    /// let user = create_resource(cx, || 1, load_user).map_err(cx, |err| {
+    ///     AppError::NotFound(err.clone())
+    /// });
+    /// assert_eq!(
+    ///     user.get(),
+    ///     Some(Err(AppError::NotFound("no user 1".to_string())))
+    /// );
+    /// # }
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn map_err<E2>(
+        &self,
+        cx: Scope,
+        f: impl Fn(&E) -> E2 + 'static,
+    ) -> Signal<Option<Result<T, E2>>>
+    where
+        T: Clone,
+    {
+        let resource = *self;
+        Signal::derive(cx, move || {
+            resource.map(|value| match value {
+                Ok(value) => Ok(value.clone()),
+                Err(err) => Err(f(err)),
+            })
+        })
+    }
+
+    /// Reports each error the resource loads that nothing reads, by the end
+    /// of the flush in which it was loaded, to the hook set with
+    /// [`set_unhandled_error_hook`](crate::set_unhandled_error_hook).
+    ///
+    /// A read at any time while the resource is loading, like rendering it in
+    /// a `<Suspense/>` or an `<ErrorBoundary/>`, counts, because the reader is
+    /// updated when the value arrives. The error is reported with the
+    /// location this is called from.
+    ///
+    /// This works the same way for every kind of resource, whichever
+    /// serialization feature is enabled: the error is reported with its
+    /// [`Display`] output.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// # if false {
+    /// let count = create_local_resource(cx, || (), |_| async {
+    ///     Err::<i32, _>("the server is down".to_string())
+    /// })
+    /// .report_unhandled_errors();
+    /// # }
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn report_unhandled_errors(self) -> Self
+    where
+        E: Display + Clone,
+    {
+        let defined_at = Location::caller();
+        let report: ReportError<Result<T, E>> =
+            Rc::new(move |value: &Result<T, E>| {
+                if let Err(error) = value {
+                    report_unhandled_error(UnhandledError {
+                        error: Rc::new(error.clone()),
+                        defined_at,
+                    });
+                }
+            });
+        _ = with_runtime(self.runtime, |runtime| {
+            if !runtime.resources.borrow().contains_key(self.id) {
+                return;
+            }
+            runtime.resource(
+                self.id,
+                |resource: &ResourceState<S, Result<T, E>>| {
+                    *resource.report_unhandled.borrow_mut() = Some(report);
+                    // the resource may already have loaded
+                    report_if_unread(
+                        resource.value,
+                        &resource.unread,
+                        &resource.report_unhandled,
+                    );
+                },
+            )
+        });
+        self
+    }
+}
+
 impl<S, T> SignalUpdate<Option<T>> for Resource<S, T> {
     #[cfg_attr(
         debug_assertions,
@@ -606,6 +1152,9 @@ impl<S, T> SignalUpdate<Option<T>> for Resource<S, T> {
     #[inline(always)]
     fn try_update<O>(&self, f: impl FnOnce(&mut Option<T>) -> O) -> Option<O> {
         with_runtime(self.runtime, |runtime| {
+            if !runtime.resources.borrow().contains_key(self.id) {
+                return None;
+            }
             runtime.resource(self.id, |resource: &ResourceState<S, T>| {
                 if resource.loading.get_untracked() {
                     resource.version.set(resource.version.get() + 1);
@@ -618,7 +1167,7 @@ impl<S, T> SignalUpdate<Option<T>> for Resource<S, T> {
                         );
                     }
                 }
-                resource.set_loading.set(false);
+                resource.set_loading.try_set(false);
                 resource.set_value.try_update(f)
             })
         })
@@ -666,6 +1215,122 @@ impl<S, T> SignalSet<T> for Resource<S, T> {
     }
 }
 
+/// Reads the resource through the same traits as the other signal types, so
+/// that it can be passed to code that is generic over them. The value is
+/// `None` while the resource is pending.
+///
+/// Like [`Resource::get`], each read is registered with the `<Suspense/>` of
+/// the [current scope](Scope::current). Because the inherent methods take
+/// precedence, `resource.with(cx, f)` still calls the deprecated
+/// [`Resource::with`]; use `SignalWith::with(&resource, f)` to apply `f` to
+/// the `Option<T>` itself.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// fn is_ready<T>(value: &impl SignalWith<Option<T>>) -> bool {
+///     value.with(Option::is_some)
+/// }
+///
+/// # // like the other resource examples, this needs a browser or Tokio
+/// # if false {
+/// let resource = create_resource(cx, || (), |_| async { 1 });
+/// resource.set(2);
+/// assert!(is_ready(&resource));
+/// assert_eq!(SignalGet::get(&resource), Some(2));
+/// # }
+/// # }).dispose();
+/// ```
+impl<S, T> SignalGet<Option<T>> for Resource<S, T>
+where
+    S: Clone + 'static,
+    T: Clone + 'static,
+{
+    #[cfg_attr(
+        debug_assertions,
+        instrument(
+            level = "trace",
+            name = "Resource::get()",
+            skip_all,
+            fields(
+                id = ?self.id,
+                defined_at = %self.defined_at,
+                ty = %std::any::type_name::<T>()
+            )
+        )
+    )]
+    #[track_caller]
+    fn get(&self) -> Option<T> {
+        self.with_owner(Scope::current(), T::clone)
+    }
+
+    #[cfg_attr(
+        debug_assertions,
+        instrument(
+            level = "trace",
+            name = "Resource::try_get()",
+            skip_all,
+            fields(
+                id = ?self.id,
+                defined_at = %self.defined_at,
+                ty = %std::any::type_name::<T>()
+            )
+        )
+    )]
+    #[track_caller]
+    fn try_get(&self) -> Option<Option<T>> {
+        self.try_with_owner(Scope::current(), Option::clone)
+    }
+}
+
+impl<S, T> SignalWith<Option<T>> for Resource<S, T>
+where
+    S: Clone + 'static,
+    T: 'static,
+{
+    #[cfg_attr(
+        debug_assertions,
+        instrument(
+            level = "trace",
+            name = "Resource::with()",
+            skip_all,
+            fields(
+                id = ?self.id,
+                defined_at = %self.defined_at,
+                ty = %std::any::type_name::<T>()
+            )
+        )
+    )]
+    #[track_caller]
+    fn with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O {
+        match self.try_with_owner(Scope::current(), f) {
+            Some(o) => o,
+            None => crate::signal::panic_getting_dead_signal(
+                #[cfg(any(debug_assertions, feature = "ssr"))]
+                self.defined_at,
+            ),
+        }
+    }
+
+    #[cfg_attr(
+        debug_assertions,
+        instrument(
+            level = "trace",
+            name = "Resource::try_with()",
+            skip_all,
+            fields(
+                id = ?self.id,
+                defined_at = %self.defined_at,
+                ty = %std::any::type_name::<T>()
+            )
+        )
+    )]
+    #[track_caller]
+    fn try_with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        self.try_with_owner(Scope::current(), f)
+    }
+}
+
 /// A signal that reflects the
 /// current state of an asynchronous task, allowing you to integrate `async`
 /// [`Future`]s into the synchronous reactive system.
@@ -701,11 +1366,11 @@ impl<S, T> SignalSet<T> for Resource<S, T> {
 /// // when we read the signal, it contains either
 /// // 1) None (if the Future isn't ready yet) or
 /// // 2) Some(T) (if the future's already resolved)
-/// assert_eq!(cats.read(cx), Some(vec!["1".to_string()]));
+/// assert_eq!(cats.get(), Some(vec!["1".to_string()]));
 ///
 /// // when the signal's value changes, the `Resource` will generate and run a new `Future`
 /// set_how_many_cats.set(2);
-/// assert_eq!(cats.read(cx), Some(vec!["2".to_string()]));
+/// assert_eq!(cats.get(), Some(vec!["2".to_string()]));
 /// # }
 /// # }).dispose();
 /// ```
@@ -756,6 +1421,8 @@ where
     set_value: WriteSignal<Option<T>>,
     pub loading: ReadSignal<bool>,
     set_loading: WriteSignal<bool>,
+    refreshing: ReadSignal<bool>,
+    set_refreshing: WriteSignal<bool>,
     source: Memo<S>,
     #[allow(clippy::type_complexity)]
     fetcher: Rc<dyn Fn(S) -> Pin<Box<dyn Future<Output = T>>>>,
@@ -764,6 +1431,96 @@ where
     version: Rc<Cell<usize>>,
     suspense_contexts: Rc<RefCell<HashSet<SuspenseContext>>>,
     serializable: ResourceSerialization,
+    cache: Option<Rc<dyn ResourceCache<S, T>>>,
+    /// For a chained resource, whether a source is ready to be fetched.
+    #[allow(clippy::type_complexity)]
+    is_ready: Option<Rc<dyn Fn(&S) -> bool>>,
+    /// Whether the resource has no value, and is waiting for its source to
+    /// become ready.
+    waiting: ReadSignal<bool>,
+    set_waiting: WriteSignal<bool>,
+    in_flight: Rc<InFlightLoads>,
+    /// Whether the value hasn't been read since the resource started
+    /// loading it.
+    unread: Rc<Cell<bool>>,
+    /// Reports the value if it is an error.
+    report_unhandled: Rc<RefCell<Option<ReportError<T>>>>,
+    #[cfg(feature = "debug-timing")]
+    timing: crate::diagnostics::TimingSlot,
+    #[cfg(feature = "tracing")]
+    defined_at: &'static Location<'static>,
+}
+
+type ReportError<T> = Rc<dyn Fn(&T)>;
+
+// reports the resource's value as an unhandled error if it is one and
+// nothing has read it by the end of the current flush
+fn report_if_unread<T>(
+    value: ReadSignal<Option<T>>,
+    unread: &Rc<Cell<bool>>,
+    report_unhandled: &RefCell<Option<ReportError<T>>>,
+) where
+    T: 'static,
+{
+    let Some(report) = report_unhandled.borrow().clone() else {
+        return;
+    };
+    let unread = Rc::clone(unread);
+    queue_microtask(move || {
+        value.try_with_untracked(|value| {
+            if let Some(value) = value {
+                if unread.replace(false) {
+                    report(value)
+                }
+            }
+        });
+    });
+}
+
+/// The loads that a resource has started and that have not finished yet.
+#[derive(Default)]
+pub(crate) struct InFlightLoads(
+    RefCell<Vec<(AbortHandle, oneshot::Receiver<()>)>>,
+);
+
+impl InFlightLoads {
+    /// Tracks a new load, returning a sender that should be dropped when the
+    /// load’s task ends.
+    fn start(&self, handle: AbortHandle) -> oneshot::Sender<()> {
+        let (finished_tx, finished_rx) = oneshot::channel();
+        let mut loads = self.0.borrow_mut();
+        // forget about loads that have already finished
+        loads.retain_mut(|(_, finished)| {
+            matches!(finished.try_recv(), Ok(None))
+        });
+        loads.push((handle, finished_rx));
+        finished_tx
+    }
+
+    /// Aborts every load, returning receivers that resolve once each load’s
+    /// task has actually stopped.
+    pub(crate) fn abort_all(&self) -> Vec<oneshot::Receiver<()>> {
+        self.0
+            .take()
+            .into_iter()
+            .filter_map(|(handle, mut finished)| {
+                // loads that have already finished have nothing to abort
+                if !matches!(finished.try_recv(), Ok(None)) {
+                    return None;
+                }
+                handle.abort();
+                crate::count_aborted_request();
+                Some(finished)
+            })
+            .collect()
+    }
+}
+
+// records how a fetch ended on its `resource_fetch` span
+#[inline(always)]
+fn record_fetch_outcome(_outcome: &'static str) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("outcome", _outcome);
 }
 
 /// Whether and how the resource can be serialized.
@@ -783,22 +1540,6 @@ where
     S: Clone + 'static,
     T: 'static,
 {
-    #[cfg_attr(
-        any(debug_assertions, feature = "ssr"),
-        instrument(level = "info", skip_all,)
-    )]
-    #[track_caller]
-    pub fn read(
-        &self,
-        cx: Scope,
-        location: &'static Location<'static>,
-    ) -> Option<T>
-    where
-        T: Clone,
-    {
-        self.with(cx, T::clone, location)
-    }
-
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "info", skip_all,)
@@ -806,21 +1547,24 @@ where
     #[track_caller]
     pub fn with<U>(
         &self,
-        cx: Scope,
-        f: impl FnOnce(&T) -> U,
+        cx: Option<Scope>,
+        f: impl FnOnce(&Option<T>) -> U,
         location: &'static Location<'static>,
     ) -> Option<U> {
+        let (v, has_value) =
+            self.value.try_with(|n| (f(n), n.is_some())).ok()?;
+        self.unread.set(false);
+
+        // outside of any scope, the read is still tracked, but there is no
+        // `<Suspense/>` to register it with
+        let Some(cx) = cx else {
+            _ = location;
+            return Some(v);
+        };
         let global_suspense_cx = use_context::<GlobalSuspenseContext>(cx);
         let suspense_cx = use_context::<SuspenseContext>(cx);
 
-        let v = self
-            .value
-            .try_with(|n| n.as_ref().map(|n| Some(f(n))))
-            .ok()?
-            .flatten();
-
         let suspense_contexts = self.suspense_contexts.clone();
-        let has_value = v.is_some();
 
         let serializable = self.serializable;
         if let Some(suspense_cx) = &suspense_cx {
@@ -909,7 +1653,7 @@ where
         };
 
         create_isomorphic_effect(cx, increment);
-        v
+        Some(v)
     }
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
@@ -918,6 +1662,33 @@ where
     pub fn refetch(&self) {
         self.load(true);
     }
+
+    /// Whether the current source can be fetched, which is always the case
+    /// unless this is a chained resource. Tracks the source.
+    fn source_ready(&self) -> bool {
+        match &self.is_ready {
+            Some(is_ready) => {
+                self.source.try_with(|s| is_ready(s)).unwrap_or(false)
+            }
+            None => true,
+        }
+    }
+
+    /// Stops waiting for a source that the server never found ready.
+    #[cfg(any(feature = "ssr", feature = "hydrate"))]
+    fn skip(&self) {
+        // the `<Suspense/>`s that read the resource while it was waiting
+        // count it as pending
+        if self.loading.get_untracked() {
+            for suspense_context in self.suspense_contexts.borrow().iter() {
+                suspense_context.decrement(
+                    self.serializable != ResourceSerialization::Local,
+                );
+            }
+        }
+        self.set_waiting.update(|n| *n = false);
+        self.set_loading.update(|n| *n = false);
+    }
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -928,12 +1699,47 @@ where
             return;
         }
 
+        // a chained resource doesn't fetch until its source is ready
+        if !self.source_ready() {
+            if !self.resolved.get() && !self.waiting.get_untracked() {
+                self.set_waiting.update(|n| *n = true);
+                self.set_loading.update(|n| *n = true);
+            }
+            return;
+        }
+        // any `<Suspense/>` that read the resource while it was waiting
+        // already counts it as pending
+        let was_waiting = self.waiting.get_untracked();
+        if was_waiting {
+            self.set_waiting.update(|n| *n = false);
+        }
+
         let version = self.version.get() + 1;
         self.version.set(version);
         self.scheduled.set(false);
+        self.unread.set(true);
 
         _ = self.source.try_with(|source| {
+            // a refetch always bypasses the cache
+            let cached = self
+                .cache
+                .as_ref()
+                .filter(|_| !refetching)
+                .and_then(|cache| cache.get(source));
+            let revalidating = match cached {
+                Some(CachedValue { value, stale }) => {
+                    self.set_cached_value(value);
+                    if !stale {
+                        return;
+                    }
+                    true
+                }
+                None => false,
+            };
+
             let fut = (self.fetcher)(source.clone());
+            #[cfg(feature = "debug-timing")]
+            self.timing.fetch_started();
 
             // `scheduled` is true for the rest of this code only
             self.scheduled.set(true);
@@ -944,47 +1750,123 @@ where
                 }
             });
 
-            self.set_loading.update(|n| *n = true);
-
             // increment counter everywhere it's read
             let suspense_contexts = self.suspense_contexts.clone();
 
-            for suspense_context in suspense_contexts.borrow().iter() {
-                suspense_context.increment(
-                    self.serializable != ResourceSerialization::Local,
-                );
-                if self.serializable == ResourceSerialization::Blocking {
-                    suspense_context.should_block.set_value(true);
+            // while revalidating a stale value, the resource is not loading,
+            // so <Suspense/> keeps showing the stale value
+            if revalidating {
+                self.set_refreshing.update(|n| *n = true);
+            } else {
+                self.set_loading.update(|n| *n = true);
+
+                for suspense_context in
+                    suspense_contexts.borrow().iter().filter(|_| !was_waiting)
+                {
+                    suspense_context.increment(
+                        self.serializable != ResourceSerialization::Local,
+                    );
+                    if self.serializable == ResourceSerialization::Blocking {
+                        suspense_context.should_block.set_value(true);
+                    }
                 }
             }
 
-            // run the Future
+            // run the Future, which is aborted if the resource is disposed
+            let (fut, abort_handle) = abortable(fut);
+            let finished = self.in_flight.start(abort_handle);
             let serializable = self.serializable;
-            spawn_local({
+            #[cfg(feature = "tracing")]
+            let span = tracing::info_span!(
+                "resource_fetch",
+                defined_at = %self.defined_at,
+                ty = %std::any::type_name::<T>(),
+                source_ty = %std::any::type_name::<S>(),
+                revalidating,
+                outcome = tracing::field::Empty,
+            );
+            let task = {
                 let resolved = self.resolved.clone();
                 let set_value = self.set_value;
                 let set_loading = self.set_loading;
+                let refreshing = self.refreshing;
+                let set_refreshing = self.set_refreshing;
+                let value = self.value;
+                let unread = Rc::clone(&self.unread);
+                let report_unhandled = Rc::clone(&self.report_unhandled);
                 let last_version = self.version.clone();
+                let cache =
+                    self.cache.clone().map(|cache| (cache, source.clone()));
+                #[cfg(feature = "debug-timing")]
+                let timing = self.timing.clone();
                 async move {
-                    let res = fut.await;
-
-                    if version == last_version.get() {
+                    let _finished = finished;
+                    let res = match fut.await {
+                        Ok(res) => res,
+                        Err(_) => {
+                            record_fetch_outcome("aborted");
+                            return;
+                        }
+                    };
+
+                    if version != last_version.get() {
+                        record_fetch_outcome("superseded");
+                    } else {
+                        record_fetch_outcome("loaded");
+                        #[cfg(feature = "debug-timing")]
+                        timing.fetch_ended();
+                        if let Some((cache, source)) = cache {
+                            cache.insert(source, &res);
+                        }
                         resolved.set(true);
                         set_value.try_update(|n| *n = Some(res));
-                        set_loading.try_update(|n| *n = false);
 
-                        for suspense_context in
-                            suspense_contexts.borrow().iter()
-                        {
-                            suspense_context.decrement(
-                                serializable != ResourceSerialization::Local,
-                            );
+                        // a revalidation superseded by this load left
+                        // `refreshing` set, so it is cleared either way
+                        if refreshing.try_get_untracked() == Some(true) {
+                            set_refreshing.try_set(false);
                         }
+                        if !revalidating {
+                            set_loading.try_update(|n| *n = false);
+
+                            for suspense_context in
+                                suspense_contexts.borrow().iter()
+                            {
+                                suspense_context.decrement(
+                                    serializable
+                                        != ResourceSerialization::Local,
+                                );
+                            }
+                        }
+                        report_if_unread(value, &unread, &report_unhandled);
                     }
                 }
-            })
+            };
+            #[cfg(feature = "tracing")]
+            let task = tracing::Instrument::instrument(task, span);
+            spawn_local(task);
         });
     }
+
+    /// Sets the resource to a value taken from its cache, ending any load
+    /// that is currently in progress.
+    fn set_cached_value(&self, value: T) {
+        if self.loading.try_get_untracked() == Some(true) {
+            for suspense_context in self.suspense_contexts.borrow().iter() {
+                suspense_context.decrement(
+                    self.serializable != ResourceSerialization::Local,
+                );
+            }
+            self.set_loading.try_set(false);
+        }
+        if self.refreshing.try_get_untracked() == Some(true) {
+            self.set_refreshing.try_set(false);
+        }
+        self.resolved.set(true);
+        if self.set_value.try_set(Some(value)).is_some() {
+            crate::debug_dropped_value(self.set_value.defined_at());
+        }
+    }
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -1001,22 +1883,33 @@ where
 
         let (tx, mut rx) = futures::channel::mpsc::channel(1);
         let value = self.value;
+        let loading = self.loading;
+        let waiting = self.waiting;
+        let chained = self.is_ready.is_some();
+        #[cfg(feature = "debug-timing")]
+        let timing = self.timing.clone();
         create_isomorphic_effect(cx, move |_| {
-            value.with({
-                let mut tx = tx.clone();
-                move |value| {
-                    if let Some(value) = value.as_ref() {
-                        tx.try_send((
-                            id,
-                            value.ser().expect("could not serialize Resource"),
-                        ))
-                        .expect(
-                            "failed while trying to write to Resource \
-                             serializer",
-                        );
-                    }
-                }
-            })
+            let data = value.with(|value| {
+                value.as_ref().map(|value| {
+                    value.ser().expect("could not serialize Resource")
+                })
+            });
+            // a chained resource that is still waiting once every other
+            // resource has loaded will never be fetched, and one that has
+            // stopped loading without a value was skipped
+            let data = data.or_else(|| {
+                (chained
+                    && (!loading.get()
+                        || (waiting.get() && resources_settled(cx))))
+                .then(|| SKIPPED_RESOURCE.to_string())
+            });
+            if let Some(data) = data {
+                #[cfg(feature = "debug-timing")]
+                timing.serialized();
+                tx.clone().try_send((id, data)).expect(
+                    "failed while trying to write to Resource serializer",
+                );
+            }
         });
         Box::pin(async move {
             rx.next()
@@ -1032,14 +1925,34 @@ pub(crate) enum AnyResource {
     Serializable(Rc<dyn SerializableResource>),
 }
 
+impl AnyResource {
+    pub(crate) fn in_flight(&self) -> &InFlightLoads {
+        match self {
+            AnyResource::Unserializable(res) => res.in_flight(),
+            AnyResource::Serializable(res) => res.in_flight(),
+        }
+    }
+}
+
 pub(crate) trait SerializableResource {
     fn as_any(&self) -> &dyn Any;
 
+    fn in_flight(&self) -> &InFlightLoads;
+
     fn to_serialization_resolver(
         &self,
         cx: Scope,
         id: ResourceId,
     ) -> Pin<Box<dyn Future<Output = (ResourceId, String)>>>;
+
+    fn is_blocking(&self) -> bool;
+
+    /// Resolves once the resource has a value.
+    fn ready(&self, cx: Scope) -> Pin<Box<dyn Future<Output = ()>>>;
+
+    /// Whether the resource is not loading, or only waiting for its source.
+    /// Tracks both.
+    fn is_settled(&self) -> bool;
 }
 
 impl<S, T> SerializableResource for ResourceState<S, T>
@@ -1050,6 +1963,11 @@ where
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn in_flight(&self) -> &InFlightLoads {
+        &self.in_flight
+    }
+
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -1062,16 +1980,67 @@ where
         let fut = self.resource_to_serialization_resolver(cx, id);
         Box::pin(fut)
     }
+
+    fn is_blocking(&self) -> bool {
+        self.serializable == ResourceSerialization::Blocking
+    }
+
+    fn ready(&self, cx: Scope) -> Pin<Box<dyn Future<Output = ()>>> {
+        use futures::StreamExt;
+
+        let (tx, mut rx) = futures::channel::mpsc::unbounded();
+        let value = self.value;
+        create_isomorphic_effect(cx, move |_| {
+            if value.with(Option::is_some) {
+                _ = tx.unbounded_send(());
+            }
+        });
+        Box::pin(async move {
+            rx.next().await;
+        })
+    }
+
+    fn is_settled(&self) -> bool {
+        !self.loading.get() || self.waiting.get()
+    }
+}
+
+/// Sent by the server in place of the value of a chained resource whose
+/// fetch was skipped, because its source never became ready.
+const SKIPPED_RESOURCE: &str = "__LEPTOS_SKIPPED_RESOURCE";
+
+/// Whether every serializable resource has either loaded or is waiting for
+/// its source. Tracks them all.
+fn resources_settled(cx: Scope) -> bool {
+    let resources = with_runtime(cx.runtime, |runtime| {
+        runtime
+            .resources
+            .borrow()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+    resources.iter().all(|resource| match resource {
+        AnyResource::Serializable(resource) => resource.is_settled(),
+        AnyResource::Unserializable(_) => true,
+    })
 }
 
 pub(crate) trait UnserializableResource {
     fn as_any(&self) -> &dyn Any;
+
+    fn in_flight(&self) -> &InFlightLoads;
 }
 
 impl<S, T> UnserializableResource for ResourceState<S, T> {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn in_flight(&self) -> &InFlightLoads {
+        &self.in_flight
+    }
 }
 
 thread_local! {
@@ -1094,7 +2063,9 @@ where
             let mut resources = runtime.resources.borrow_mut();
             resources.remove(self.id)
         });
-        if res.ok().flatten().is_none() {
+        if let Some(res) = res.ok().flatten() {
+            res.in_flight().abort_all();
+        } else {
             crate::macros::debug_warn!(
                 "At {}, you are calling Resource::dispose() on a resource \
                  that no longer exists, probably because its Scope has \
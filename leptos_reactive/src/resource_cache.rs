@@ -0,0 +1,105 @@
+#![forbid(unsafe_code)]
+use cfg_if::cfg_if;
+use indexmap::IndexMap;
+use std::{cell::RefCell, hash::Hash, time::Duration};
+
+/// Describes how a resource created with
+/// [`create_resource_with_cache`](crate::create_resource_with_cache) caches
+/// the values it has loaded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CachePolicy {
+    /// The maximum number of source values whose results are kept. Once this
+    /// is exceeded, the least recently used entry is dropped.
+    pub max_entries: usize,
+    /// How long a cached value is considered fresh. A stale value is still
+    /// returned immediately, but is also refetched in the background
+    /// (“stale-while-revalidate”). If `None`, cached values never go stale.
+    pub stale_after: Option<Duration>,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self {
+            max_entries: 16,
+            stale_after: None,
+        }
+    }
+}
+
+/// A value that was found in a resource’s cache.
+pub(crate) struct CachedValue<T> {
+    pub value: T,
+    pub stale: bool,
+}
+
+/// Type-erased storage for a resource’s cached values, so that the resource
+/// itself doesn’t need to require `S: Hash + Eq` or `T: Clone`.
+pub(crate) trait ResourceCache<S, T> {
+    /// Returns the cached value for this source, if any, and marks it as
+    /// the most recently used entry.
+    fn get(&self, source: &S) -> Option<CachedValue<T>>;
+
+    /// Stores the value loaded for this source.
+    fn insert(&self, source: S, value: &T);
+}
+
+pub(crate) struct LruResourceCache<S, T> {
+    policy: CachePolicy,
+    entries: RefCell<IndexMap<S, (T, f64)>>,
+}
+
+impl<S, T> LruResourceCache<S, T> {
+    pub fn new(policy: CachePolicy) -> Self {
+        Self {
+            policy,
+            entries: Default::default(),
+        }
+    }
+}
+
+impl<S, T> ResourceCache<S, T> for LruResourceCache<S, T>
+where
+    S: Hash + Eq,
+    T: Clone,
+{
+    fn get(&self, source: &S) -> Option<CachedValue<T>> {
+        let mut entries = self.entries.borrow_mut();
+        // move the entry to the end, so the least recently used is first
+        let (source, (value, loaded_at)) =
+            entries.shift_remove_entry(source)?;
+        let stale = self.policy.stale_after.is_some_and(|stale_after| {
+            now_ms() - loaded_at >= stale_after.as_secs_f64() * 1000.0
+        });
+        let cached = CachedValue {
+            value: value.clone(),
+            stale,
+        };
+        entries.insert(source, (value, loaded_at));
+        Some(cached)
+    }
+
+    fn insert(&self, source: S, value: &T) {
+        let mut entries = self.entries.borrow_mut();
+        entries.shift_remove(&source);
+        entries.insert(source, (value.clone(), now_ms()));
+        while entries.len() > self.policy.max_entries {
+            entries.shift_remove_index(0);
+        }
+    }
+}
+
+pub(crate) fn now_ms() -> f64 {
+    cfg_if! {
+        if #[cfg(all(
+            target_arch = "wasm32",
+            any(feature = "csr", feature = "hydrate")
+        ))] {
+            js_sys::Date::now()
+        } else {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+                .unwrap_or_default()
+        }
+    }
+}
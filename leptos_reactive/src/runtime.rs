@@ -2,20 +2,25 @@
 
 use crate::{
     hydration::SharedContext,
-    node::{NodeId, ReactiveNode, ReactiveNodeState, ReactiveNodeType},
-    AnyComputation, AnyResource, Effect, Memo, MemoState, ReadSignal,
-    ResourceId, ResourceState, RwSignal, Scope, ScopeDisposer, ScopeId,
-    ScopeProperty, SerializableResource, SpecialNonReactiveZone, StoredValueId,
-    Trigger, UnserializableResource, WriteSignal,
+    node::{
+        NodeId, NodeValue, ReactiveNode, ReactiveNodeState, ReactiveNodeType,
+    },
+    query::QueryState,
+    scope::with_owner,
+    AnyComputation, AnyResource, EffectState, Memo, MemoState, ReadSignal,
+    RenderFlush, ResourceId, ResourceState, RwSignal, Scope, ScopeDisposer,
+    ScopeId, ScopeProperty, SerializableResource, SpecialNonReactiveZone,
+    StoredValueId, Trigger, UnserializableResource, WriteSignal,
 };
 use cfg_if::cfg_if;
 use core::hash::BuildHasherDefault;
 use futures::stream::FuturesUnordered;
 use indexmap::IndexSet;
-use rustc_hash::{FxHashMap, FxHasher};
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 use slotmap::{SecondaryMap, SlotMap, SparseSecondaryMap};
 use std::{
     any::{Any, TypeId},
+    borrow::Cow,
     cell::{Cell, RefCell},
     fmt::Debug,
     future::Future,
@@ -40,6 +45,18 @@ cfg_if! {
 
 type FxIndexSet<T> = IndexSet<T, BuildHasherDefault<FxHasher>>;
 
+/// How many times the render effects queued during a single flush can queue
+/// each other again before the rest are deferred to the next flush.
+pub(crate) const MAX_RENDER_FLUSH_PASSES: usize = 100;
+
+/// How deeply effects can run each other, by writing to signals that other
+/// effects read, before the rest are deferred to the next flush.
+pub(crate) const MAX_EFFECT_DEPTH: usize = 100;
+
+/// How many flushes in a row can defer effects before they are assumed to be
+/// in a cycle, and are not run again.
+pub(crate) const MAX_DEFERRED_FLUSHES: usize = 100;
+
 // The data structure that owns all the signals, memos, effects,
 // and other data included in the reactive system.
 #[derive(Default)]
@@ -52,18 +69,49 @@ pub(crate) struct Runtime {
     #[allow(clippy::type_complexity)]
     pub scope_contexts:
         RefCell<SparseSecondaryMap<ScopeId, FxHashMap<TypeId, Box<dyn Any>>>>,
+    #[cfg(debug_assertions)]
+    #[allow(clippy::type_complexity)]
+    pub scope_context_locations: RefCell<
+        SparseSecondaryMap<
+            ScopeId,
+            FxHashMap<TypeId, &'static std::panic::Location<'static>>,
+        >,
+    >,
+    #[cfg(debug_assertions)]
+    pub scope_locations: RefCell<
+        SparseSecondaryMap<ScopeId, &'static std::panic::Location<'static>>,
+    >,
     #[allow(clippy::type_complexity)]
     pub scope_cleanups:
         RefCell<SparseSecondaryMap<ScopeId, Vec<Box<dyn FnOnce()>>>>,
     pub stored_values: RefCell<SlotMap<StoredValueId, Rc<RefCell<dyn Any>>>>,
     pub nodes: RefCell<SlotMap<NodeId, ReactiveNode>>,
+    /// The scope each effect or memo was created in, which is the current
+    /// owner while it runs.
+    pub node_owners: RefCell<SecondaryMap<NodeId, Scope>>,
     pub node_subscribers:
         RefCell<SecondaryMap<NodeId, RefCell<FxIndexSet<NodeId>>>>,
     pub node_sources:
         RefCell<SecondaryMap<NodeId, RefCell<FxIndexSet<NodeId>>>>,
     pub pending_effects: RefCell<Vec<NodeId>>,
+    /// Paused effects, and whether they need to run when resumed.
+    pub paused_effects: RefCell<SparseSecondaryMap<NodeId, bool>>,
+    pub pending_render_effects: RefCell<Vec<NodeId>>,
+    pub after_render: RefCell<Vec<Box<dyn FnOnce()>>>,
+    pub render_flush_scheduled: Cell<bool>,
+    pub render_flush: Cell<RenderFlush>,
     pub resources: RefCell<SlotMap<ResourceId, AnyResource>>,
+    pub queries: RefCell<FxHashMap<Cow<'static, str>, QueryState>>,
     pub batching: Cell<bool>,
+    /// How many nested calls to `run_effects()` are running effects.
+    pub effect_depth: Cell<usize>,
+    /// Effects that were queued too deeply, to run in the next flush.
+    pub deferred_effects: RefCell<Vec<NodeId>>,
+    /// How many flushes in a row have deferred effects or render effects.
+    pub deferred_effect_flushes: Cell<usize>,
+    pub deferred_render_flushes: Cell<usize>,
+    #[cfg(feature = "debug-timing")]
+    pub resource_timings: crate::ResourceTimings,
 }
 
 // This core Runtime impl block handles all the work of marking and updating
@@ -74,6 +122,10 @@ pub(crate) struct Runtime {
 impl Runtime {
     pub(crate) fn update_if_necessary(&self, node_id: NodeId) {
         //crate::macros::debug_warn!("update_if_necessary {node_id:?}");
+        if self.update_source_if_necessary(node_id) {
+            return;
+        }
+
         if self.current_state(node_id) == ReactiveNodeState::Check {
             let sources = {
                 let sources = self.node_sources.borrow();
@@ -119,34 +171,52 @@ impl Runtime {
             // signals simply have their value
             let changed = match node.node_type {
                 ReactiveNodeType::Signal | ReactiveNodeType::Trigger => true,
-                ReactiveNodeType::Memo { ref f }
-                | ReactiveNodeType::Effect { ref f } => {
+                ReactiveNodeType::Memo { ref f } => {
                     let value = node.value();
                     // set this node as the observer
-                    self.with_observer(node_id, move || {
-                        // clean up sources of this memo/effect
+                    let changed = self.with_observer(node_id, move || {
+                        // clean up sources of this memo
                         self.cleanup(node_id);
 
                         f.run(value)
-                    })
+                    });
+                    // effects notified by the memo's writes wait until it
+                    // is done, or for the effects that are running
+                    if self.effect_depth.get() == 0
+                        && !self.is_running_memo()
+                        && !self.deferred_effects.borrow().is_empty()
+                    {
+                        self.schedule_deferred_effects();
+                    }
+                    changed
+                }
+                ReactiveNodeType::Effect { ref f, .. } => {
+                    let paused = self
+                        .paused_effects
+                        .borrow_mut()
+                        .get_mut(node_id)
+                        .map(|dirty| *dirty = true)
+                        .is_some();
+                    if paused {
+                        // keep the sources, so that it still knows when it
+                        // needs to run once it is resumed
+                        false
+                    } else {
+                        let value = node.value();
+                        // set this node as the observer
+                        self.with_observer(node_id, move || {
+                            // clean up sources of this effect
+                            self.cleanup(node_id);
+
+                            self.run_effect(node_id, f, value)
+                        })
+                    }
                 }
             };
 
             // mark children dirty
             if changed {
-                let subs = self.node_subscribers.borrow();
-
-                if let Some(subs) = subs.get(node_id) {
-                    let mut nodes = self.nodes.borrow_mut();
-                    for sub_id in subs.borrow().iter() {
-                        if let Some(sub) = nodes.get_mut(*sub_id) {
-                            //crate::macros::debug_warn!(
-                            //    "update is marking {sub_id:?} dirty"
-                            //);
-                            sub.state = ReactiveNodeState::Dirty;
-                        }
-                    }
-                }
+                self.mark_subscribers_dirty(node_id);
             }
 
             // mark clean
@@ -154,6 +224,48 @@ impl Runtime {
         }
     }
 
+    fn mark_subscribers_dirty(&self, node_id: NodeId) {
+        let subs = self.node_subscribers.borrow();
+
+        if let Some(subs) = subs.get(node_id) {
+            let mut nodes = self.nodes.borrow_mut();
+            for sub_id in subs.borrow().iter() {
+                if let Some(sub) = nodes.get_mut(*sub_id) {
+                    //crate::macros::debug_warn!(
+                    //    "update is marking {sub_id:?} dirty"
+                    //);
+                    sub.state = ReactiveNodeState::Dirty;
+                }
+            }
+        }
+    }
+
+    /// Does what [`Runtime::update_if_necessary`] would do for a signal or a
+    /// trigger, which have no sources to check and nothing to rerun, looking
+    /// the node up only once. Returns `false` for any other node.
+    fn update_source_if_necessary(&self, node_id: NodeId) -> bool {
+        let changed = {
+            let mut nodes = self.nodes.borrow_mut();
+            match nodes.get_mut(node_id) {
+                Some(ReactiveNode {
+                    node_type:
+                        ReactiveNodeType::Signal | ReactiveNodeType::Trigger,
+                    state,
+                    ..
+                }) => {
+                    let changed = *state >= ReactiveNodeState::Dirty;
+                    *state = ReactiveNodeState::Clean;
+                    changed
+                }
+                _ => return false,
+            }
+        };
+        if changed {
+            self.mark_subscribers_dirty(node_id);
+        }
+        true
+    }
+
     pub(crate) fn cleanup(&self, node_id: NodeId) {
         let sources = self.node_sources.borrow();
         if let Some(sources) = sources.get(node_id) {
@@ -174,11 +286,62 @@ impl Runtime {
     }
 
     fn with_observer<T>(&self, observer: NodeId, f: impl FnOnce() -> T) -> T {
-        let prev_observer = self.observer.take();
+        // restore the previous observer even if `f` panics
+        struct RestoreObserver<'a>(&'a Cell<Option<NodeId>>, Option<NodeId>);
+
+        impl Drop for RestoreObserver<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.1);
+            }
+        }
+
+        let _prev_observer =
+            RestoreObserver(&self.observer, self.observer.take());
         self.observer.set(Some(observer));
-        let v = f();
-        self.observer.set(prev_observer);
-        v
+        let owner = self.node_owners.borrow().get(observer).copied();
+        match owner {
+            Some(owner) => with_owner(Some(owner), f),
+            // an effect runs for the first time before it is registered in
+            // its scope, which is already the owner then
+            None => f(),
+        }
+    }
+
+    /// Runs an effect. If the effect panics (and the panic can be caught),
+    /// the effect is removed from the reactive graph so that it doesn't run
+    /// again, and the panic is passed to the error hook.
+    fn run_effect(
+        &self,
+        node_id: NodeId,
+        effect: &Rc<dyn AnyComputation>,
+        value: Rc<RefCell<dyn Any>>,
+    ) -> bool {
+        cfg_if! {
+            if #[cfg(panic = "unwind")] {
+                let result = std::panic::catch_unwind(
+                    std::panic::AssertUnwindSafe(|| effect.run(value)),
+                );
+                match result {
+                    Ok(changed) => changed,
+                    Err(payload) => {
+                        self.cleanup(node_id);
+                        if let Some(sources) =
+                            self.node_sources.borrow().get(node_id)
+                        {
+                            sources.borrow_mut().clear();
+                        }
+                        crate::effect::report_effect_panic(
+                            payload.as_ref(),
+                            effect.defined_at(),
+                        );
+                        false
+                    }
+                }
+            } else {
+                _ = node_id;
+                effect.run(value)
+            }
+        }
     }
 
     fn mark_clean(&self, node: NodeId) {
@@ -189,6 +352,23 @@ impl Runtime {
         }
     }
 
+    /// Notifies the subscribers of a signal that its value has been written.
+    pub(crate) fn notify_set(
+        &self,
+        node: NodeId,
+        #[cfg(debug_assertions)] defined_at: Option<
+            &'static std::panic::Location<'static>,
+        >,
+    ) {
+        #[cfg(debug_assertions)]
+        self.warn_if_writing_own_source(node, defined_at);
+
+        // mark descendants dirty
+        self.mark_dirty(node);
+
+        self.run_effects();
+    }
+
     pub(crate) fn mark_dirty(&self, node: NodeId) {
         //crate::macros::debug_warn!("marking {node:?} dirty");
         let mut nodes = self.nodes.borrow_mut();
@@ -335,10 +515,306 @@ impl Runtime {
     pub(crate) fn run_effects(&self) {
         if !self.batching.get() {
             let effects = self.pending_effects.take();
+            let depth = self.effect_depth.get();
+            if depth == MAX_EFFECT_DEPTH || self.is_running_memo() {
+                // effects that keep running each other are probably in a
+                // cycle, and the effects notified by a memo that is being
+                // computed may read it, so they are run once this unwinds,
+                // rather than overflowing the stack or rerunning the memo
+                // inside itself
+                self.deferred_effects.borrow_mut().extend(effects);
+                return;
+            }
+            self.effect_depth.set(depth + 1);
+            let mut queued_render_effects = false;
+            for effect_id in effects {
+                if self.is_render_effect(effect_id) {
+                    // render effects write to the DOM, so they're queued and
+                    // flushed together, rather than once per signal update
+                    self.pending_render_effects.borrow_mut().push(effect_id);
+                    queued_render_effects = true;
+                } else {
+                    self.update_if_necessary(effect_id);
+                }
+            }
+            self.effect_depth.set(depth);
+            if queued_render_effects || !self.after_render.borrow().is_empty() {
+                self.schedule_render_flush();
+            }
+            if depth == 0 && !self.deferred_effects.borrow().is_empty() {
+                self.schedule_deferred_effects();
+            }
+        }
+    }
+
+    fn schedule_deferred_effects(&self) {
+        cfg_if! {
+            if #[cfg(all(
+                target_arch = "wasm32",
+                any(feature = "csr", feature = "hydrate")
+            ))] {
+                crate::queue_microtask(|| {
+                    _ = with_runtime(RuntimeId, |runtime| {
+                        runtime.run_deferred_effects()
+                    });
+                });
+            } else {
+                self.run_deferred_effects();
+            }
+        }
+    }
+
+    fn run_deferred_effects(&self) {
+        let effects = self.deferred_effects.take();
+        if effects.is_empty() {
+            return;
+        }
+        let flushes = self.deferred_effect_flushes.get() + 1;
+        if flushes == MAX_DEFERRED_FLUSHES {
+            self.deferred_effect_flushes.set(0);
+            self.report_effect_cycle(&effects);
+            return;
+        }
+        self.deferred_effect_flushes.set(flushes);
+        // an effect that was still running further up the stack when it was
+        // deferred may have been marked clean since
+        {
+            let mut nodes = self.nodes.borrow_mut();
+            for effect in &effects {
+                if let Some(node) = nodes.get_mut(*effect) {
+                    node.state = ReactiveNodeState::Dirty;
+                }
+            }
+        }
+        self.pending_effects.borrow_mut().extend(effects);
+        self.run_effects();
+        if self.deferred_effects.borrow().is_empty() {
+            self.deferred_effect_flushes.set(0);
+        }
+    }
+
+    fn is_running_memo(&self) -> bool {
+        self.observer.get().is_some_and(|observer| {
+            matches!(
+                self.nodes.borrow().get(observer),
+                Some(ReactiveNode {
+                    node_type: ReactiveNodeType::Memo { .. },
+                    ..
+                })
+            )
+        })
+    }
+
+    fn is_render_effect(&self, node: NodeId) -> bool {
+        matches!(
+            self.nodes.borrow().get(node),
+            Some(ReactiveNode {
+                node_type: ReactiveNodeType::Effect { render: true, .. },
+                ..
+            })
+        )
+    }
+
+    /// Makes sure that queued render effects and after-render callbacks
+    /// will be run: in the browser, in a microtask or the next animation
+    /// frame; elsewhere, immediately.
+    pub(crate) fn schedule_render_flush(&self) {
+        if self.render_flush_scheduled.replace(true) {
+            return;
+        }
+
+        cfg_if! {
+            if #[cfg(all(
+                target_arch = "wasm32",
+                any(feature = "csr", feature = "hydrate")
+            ))] {
+                let flush = || {
+                    _ = with_runtime(RuntimeId, |runtime| {
+                        runtime.flush_render_effects()
+                    });
+                };
+                match self.render_flush.get() {
+                    RenderFlush::Microtask => crate::queue_microtask(flush),
+                    RenderFlush::AnimationFrame => {
+                        crate::render_flush::request_animation_frame(flush)
+                    }
+                }
+            } else {
+                if self.batching.get() {
+                    // flushed by `run_effects()` once the batch is done
+                    self.render_flush_scheduled.set(false);
+                } else {
+                    self.flush_render_effects();
+                }
+            }
+        }
+    }
+
+    /// Runs every queued render effect, in the order in which they were
+    /// queued, and then every after-render callback.
+    ///
+    /// Effects that are queued again while the flush is running are run in
+    /// another pass. If they are still being queued after
+    /// [`MAX_RENDER_FLUSH_PASSES`] passes, the rest are run in the next flush,
+    /// so that effects that write to each other’s dependencies in a cycle
+    /// don’t freeze the page. After [`MAX_DEFERRED_FLUSHES`] such flushes in a
+    /// row, they are dropped with an error.
+    pub(crate) fn flush_render_effects(&self) {
+        let mut passes = 0;
+        loop {
+            // running an effect may queue more of them
+            let effects = self.pending_render_effects.take();
+            if effects.is_empty() {
+                self.deferred_render_flushes.set(0);
+                break;
+            }
+            if passes == MAX_RENDER_FLUSH_PASSES {
+                let flushes = self.deferred_render_flushes.get() + 1;
+                if flushes == MAX_DEFERRED_FLUSHES {
+                    self.deferred_render_flushes.set(0);
+                    self.report_effect_cycle(&effects);
+                    break;
+                }
+                self.deferred_render_flushes.set(flushes);
+                // the after-render callbacks run once the next flush is done
+                *self.pending_render_effects.borrow_mut() = effects;
+                self.render_flush_scheduled.set(false);
+                self.schedule_render_flush();
+                return;
+            }
+            passes += 1;
             for effect_id in effects {
                 self.update_if_necessary(effect_id);
             }
         }
+        self.render_flush_scheduled.set(false);
+
+        let callbacks = self.after_render.take();
+        for callback in callbacks {
+            callback();
+        }
+    }
+
+    #[cold]
+    fn report_effect_cycle(&self, effects: &[NodeId]) {
+        self.unmark_dropped_effects(effects);
+        let nodes = self.nodes.borrow();
+        let locations = effects
+            .iter()
+            .filter_map(|effect| match nodes.get(*effect) {
+                Some(ReactiveNode {
+                    node_type: ReactiveNodeType::Effect { f, .. },
+                    ..
+                }) => f.defined_at(),
+                _ => None,
+            })
+            .map(|defined_at| format!("\n  - effect created at {defined_at}"))
+            .collect::<String>();
+        crate::console_error(&format!(
+            "Effects were still running each other after \
+             {MAX_DEFERRED_FLUSHES} flushes in a row, so they were not run \
+             again. This usually means that effects or memos write to signals \
+             that they, or each other, read, in a cycle.{locations}\n\nIf an \
+             effect only needs to write a signal, and not run again when it \
+             changes, read it with `.get_untracked()` or write it with \
+             `.update()` instead of `.set(signal.get() + ...)`. If the value \
+             can be derived from other signals, use `create_memo` instead of \
+             an effect."
+        ));
+    }
+
+    // Effects that are dropped without running leave their sources marked,
+    // which would stop later writes from reaching them, so the marks are
+    // cleared: signals and effects are clean, and memos will be rerun when
+    // they are next read.
+    fn unmark_dropped_effects(&self, effects: &[NodeId]) {
+        let mut nodes = self.nodes.borrow_mut();
+        let sources = self.node_sources.borrow();
+        let mut visited = FxHashSet::default();
+        let mut stack = effects.to_vec();
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            let Some(node) = nodes.get_mut(id) else {
+                continue;
+            };
+            node.state = match node.node_type {
+                ReactiveNodeType::Memo { .. }
+                    if node.state != ReactiveNodeState::Clean =>
+                {
+                    ReactiveNodeState::Dirty
+                }
+                _ => ReactiveNodeState::Clean,
+            };
+            if let Some(sources) = sources.get(id) {
+                stack.extend(sources.borrow().iter().copied());
+            }
+        }
+    }
+
+    /// Warns if the effect or memo that is running writes to a signal that
+    /// it has read during this run. Its own write doesn't make it run again,
+    /// but it is easy to turn into a cycle with other effects.
+    #[cfg(debug_assertions)]
+    pub(crate) fn warn_if_writing_own_source(
+        &self,
+        node: NodeId,
+        defined_at: Option<&'static std::panic::Location<'static>>,
+    ) {
+        let Some(observer) = self.observer.get() else {
+            return;
+        };
+        // a node's subscribers are cleared before each run, unlike its
+        // sources, so this only includes the reads of the current run
+        let reads_it = self
+            .node_subscribers
+            .borrow()
+            .get(node)
+            .map(|subs| subs.borrow().contains(&observer))
+            .unwrap_or(false);
+        if !reads_it {
+            return;
+        }
+        let (kind, observer_defined_at) =
+            match self.nodes.borrow().get(observer).map(|n| &n.node_type) {
+                Some(ReactiveNodeType::Effect { f, .. }) => {
+                    ("effect", f.defined_at())
+                }
+                Some(ReactiveNodeType::Memo { f }) => ("memo", f.defined_at()),
+                _ => return,
+            };
+        let location = |at: Option<&'static std::panic::Location<'static>>| {
+            at.map(ToString::to_string)
+                .unwrap_or_else(|| "an unknown location".to_string())
+        };
+        crate::macros::debug_warn!(
+            "The {kind} created at {} writes to a signal (created at {}) that \
+             it also reads. The {kind} is not run again because of its own \
+             write, but other effects that read the signal are, which can \
+             cause them to run in a loop.\n\nHere’s how to fix it:\n\n1. If \
+             the {kind} is updating the signal based on its current value, \
+             use `.update()`, which doesn't track it:\n  ❌ NO  \
+             set_count.set(count.get() + 1)\n  ✅ YES \
+             set_count.update(|n| *n += 1)\n\n2. If the {kind} doesn’t need \
+             to run again when the signal changes, read it with \
+             `.get_untracked()` or `.with_untracked()`.\n\n3. If the value can \
+             be derived from other signals, use `create_memo` instead of \
+             writing it from an effect.",
+            location(observer_defined_at),
+            location(defined_at)
+        );
+    }
+
+    pub(crate) fn dispose_effect(&self, scope: ScopeId, effect: NodeId) {
+        if let Some(properties) = self.scopes.borrow().get(scope) {
+            properties
+                .borrow_mut()
+                .retain(|property| *property != ScopeProperty::Effect(effect));
+        }
+        self.cleanup(effect);
+        self.paused_effects.borrow_mut().remove(effect);
+        self.dispose_node(effect);
     }
 
     pub(crate) fn dispose_node(&self, node: NodeId) {
@@ -392,6 +868,8 @@ pub(crate) fn with_runtime<T>(
 #[must_use = "Runtime will leak memory if Runtime::dispose() is never called."]
 /// Creates a new reactive [`Runtime`]. This should almost always be handled by the framework.
 pub fn create_runtime() -> RuntimeId {
+    crate::dispose_queued_scopes();
+
     cfg_if! {
         if #[cfg(any(feature = "csr", feature = "hydrate"))] {
             Default::default()
@@ -415,20 +893,32 @@ pub struct RuntimeId;
 impl RuntimeId {
     /// Removes the runtime, disposing all its child [`Scope`](crate::Scope)s.
     pub fn dispose(self) {
+        crate::dispose_queued_scopes();
+
         #[cfg(not(any(feature = "csr", feature = "hydrate")))]
         {
             let runtime = RUNTIMES.with(move |runtimes| runtimes.borrow_mut().remove(self))
                     .expect("Attempted to dispose of a reactive runtime that was not found. This suggests \
                     a possible memory leak. Please open an issue with details at https://github.com/leptos-rs/leptos");
+            // stop any loads that are still running, for example because a
+            // server-side render was cancelled when its client disconnected
+            for (_, resource) in runtime.resources.borrow().iter() {
+                resource.in_flight().abort_all();
+            }
             drop(runtime);
         }
     }
 
+    #[track_caller]
     pub(crate) fn raw_scope_and_disposer(self) -> (Scope, ScopeDisposer) {
+        #[cfg(debug_assertions)]
+        let defined_at = std::panic::Location::caller();
         with_runtime(self, |runtime| {
             let id = { runtime.scopes.borrow_mut().insert(Default::default()) };
+            #[cfg(debug_assertions)]
+            runtime.scope_locations.borrow_mut().insert(id, defined_at);
             let scope = Scope { runtime: self, id };
-            let disposer = ScopeDisposer(scope);
+            let disposer = ScopeDisposer::new(scope);
             (scope, disposer)
         })
         .expect(
@@ -437,23 +927,29 @@ impl RuntimeId {
         )
     }
 
+    #[track_caller]
     pub(crate) fn raw_scope_and_disposer_with_parent(
         self,
         parent: Option<Scope>,
     ) -> (Scope, ScopeDisposer) {
+        #[cfg(debug_assertions)]
+        let defined_at = std::panic::Location::caller();
         with_runtime(self, |runtime| {
             let id = { runtime.scopes.borrow_mut().insert(Default::default()) };
+            #[cfg(debug_assertions)]
+            runtime.scope_locations.borrow_mut().insert(id, defined_at);
             if let Some(parent) = parent {
                 runtime.scope_parents.borrow_mut().insert(id, parent.id);
             }
             let scope = Scope { runtime: self, id };
-            let disposer = ScopeDisposer(scope);
+            let disposer = ScopeDisposer::new(scope);
             (scope, disposer)
         })
         .expect("tried to crate scope in a runtime that has been disposed")
     }
 
     #[inline(always)]
+    #[track_caller]
     pub(crate) fn run_scope_undisposed<T>(
         self,
         f: impl FnOnce(Scope) -> T,
@@ -461,10 +957,39 @@ impl RuntimeId {
     ) -> (T, ScopeId, ScopeDisposer) {
         let (scope, disposer) = self.raw_scope_and_disposer_with_parent(parent);
 
-        (f(scope), scope.id, disposer)
+        (with_owner(Some(scope), || f(scope)), scope.id, disposer)
+    }
+
+    /// Creates a child scope of `parent`, registering it in the parent’s
+    /// children so that it is disposed along with the parent, and then runs
+    /// `f` in it.
+    #[inline(always)]
+    #[track_caller]
+    pub(crate) fn run_child_scope<T>(
+        self,
+        parent: Scope,
+        f: impl FnOnce(Scope) -> T,
+    ) -> (T, ScopeDisposer) {
+        let (scope, disposer) =
+            self.raw_scope_and_disposer_with_parent(Some(parent));
+        _ = with_runtime(self, |runtime| {
+            runtime
+                .scope_children
+                .borrow_mut()
+                .entry(parent.id)
+                .expect(
+                    "trying to add a child to a Scope that has already been \
+                     disposed",
+                )
+                .or_default()
+                .push(scope.id);
+        });
+
+        (with_owner(Some(scope), || f(scope)), disposer)
     }
 
     #[inline(always)]
+    #[track_caller]
     pub(crate) fn run_scope<T>(
         self,
         f: impl FnOnce(Scope) -> T,
@@ -495,7 +1020,13 @@ impl RuntimeId {
             let prev_observer =
                 SetObserverOnDrop(self, runtime.observer.take());
 
-            untracked_result = f();
+            // reads in an untracked function don't belong to any scope,
+            // except for the component bodies that are run with diagnostics
+            untracked_result = if diagnostics {
+                f()
+            } else {
+                with_owner(None, f)
+            };
 
             runtime.observer.set(prev_observer.1);
             std::mem::forget(prev_observer); // avoid Drop
@@ -534,10 +1065,7 @@ impl RuntimeId {
         }
     }
 
-    pub(crate) fn create_concrete_signal(
-        self,
-        value: Rc<RefCell<dyn Any>>,
-    ) -> NodeId {
+    pub(crate) fn create_concrete_signal(self, value: NodeValue) -> NodeId {
         with_runtime(self, |runtime| {
             runtime.nodes.borrow_mut().insert(ReactiveNode {
                 value: Some(value),
@@ -557,9 +1085,7 @@ impl RuntimeId {
     where
         T: Any + 'static,
     {
-        let id = self.create_concrete_signal(
-            Rc::new(RefCell::new(value)) as Rc<RefCell<dyn Any>>
-        );
+        let id = self.create_concrete_signal(NodeValue::new(value));
 
         (
             ReadSignal {
@@ -605,7 +1131,7 @@ impl RuntimeId {
             values
                 .map(|value| {
                     signals.insert(ReactiveNode {
-                        value: Some(Rc::new(RefCell::new(value))),
+                        value: Some(NodeValue::new(value)),
                         state: ReactiveNodeState::Clean,
                         node_type: ReactiveNodeType::Signal,
                     })
@@ -641,9 +1167,7 @@ impl RuntimeId {
     where
         T: Any + 'static,
     {
-        let id = self.create_concrete_signal(
-            Rc::new(RefCell::new(value)) as Rc<RefCell<dyn Any>>
-        );
+        let id = self.create_concrete_signal(NodeValue::new(value));
         //crate::macros::debug_warn!(
         //    "created RwSignal {id:?} at {:?}",
         //    std::panic::Location::caller()
@@ -661,23 +1185,33 @@ impl RuntimeId {
         self,
         value: Rc<RefCell<dyn Any>>,
         effect: Rc<dyn AnyComputation>,
+    ) -> NodeId {
+        self.create_concrete_effect_with_kind(value, effect, false)
+    }
+
+    fn create_concrete_effect_with_kind(
+        self,
+        value: Rc<RefCell<dyn Any>>,
+        effect: Rc<dyn AnyComputation>,
+        render: bool,
     ) -> NodeId {
         with_runtime(self, |runtime| {
             let id = runtime.nodes.borrow_mut().insert(ReactiveNode {
-                value: Some(Rc::clone(&value)),
+                value: Some(NodeValue::Boxed(Rc::clone(&value))),
                 state: ReactiveNodeState::Clean,
                 node_type: ReactiveNodeType::Effect {
                     f: Rc::clone(&effect),
+                    render,
                 },
             });
 
             // run the effect for the first time
-            let prev_observer = runtime.observer.take();
-            runtime.observer.set(Some(id));
-
-            effect.run(value);
-
-            runtime.observer.set(prev_observer);
+            runtime.with_observer(id, || {
+                runtime.run_effect(id, &effect, value);
+            });
+            // reading a memo for the first time marks it dirty, which later
+            // runs clear in `update_if_necessary`
+            runtime.mark_clean(id);
 
             id
         })
@@ -691,7 +1225,7 @@ impl RuntimeId {
     ) -> NodeId {
         with_runtime(self, |runtime| {
             runtime.nodes.borrow_mut().insert(ReactiveNode {
-                value: Some(value),
+                value: Some(NodeValue::Boxed(value)),
                 // memos are lazy, so are dirty when created
                 // will be run the first time we ask for it
                 state: ReactiveNodeState::Dirty,
@@ -712,12 +1246,36 @@ impl RuntimeId {
     {
         self.create_concrete_effect(
             Rc::new(RefCell::new(None::<T>)),
-            Rc::new(Effect {
+            Rc::new(EffectState {
+                f,
+                ty: PhantomData,
+                #[cfg(any(debug_assertions, feature = "ssr"))]
+                defined_at: std::panic::Location::caller(),
+            }),
+        )
+    }
+
+    /// Creates an effect that runs immediately, but whose later runs are
+    /// queued and flushed together. See [`flush_sync`](crate::flush_sync).
+    #[cfg(not(feature = "ssr"))]
+    #[track_caller]
+    #[inline(always)]
+    pub(crate) fn create_render_effect<T>(
+        self,
+        f: impl Fn(Option<T>) -> T + 'static,
+    ) -> NodeId
+    where
+        T: Any + 'static,
+    {
+        self.create_concrete_effect_with_kind(
+            Rc::new(RefCell::new(None::<T>)),
+            Rc::new(EffectState {
                 f,
                 ty: PhantomData,
                 #[cfg(any(debug_assertions, feature = "ssr"))]
                 defined_at: std::panic::Location::caller(),
             }),
+            true,
         )
     }
 
@@ -777,7 +1335,7 @@ impl RuntimeId {
 
         let id = self.create_concrete_effect(
             Rc::new(RefCell::new(None::<()>)),
-            Rc::new(Effect {
+            Rc::new(EffectState {
                 f: effect_fn,
                 ty: PhantomData,
                 #[cfg(any(debug_assertions, feature = "ssr"))]
@@ -920,13 +1478,28 @@ impl Runtime {
         f
     }
 
-    /// Do not call on triggers
-    pub(crate) fn get_value(
+    pub(crate) fn blocking_resources_ready(
         &self,
-        node_id: NodeId,
-    ) -> Option<Rc<RefCell<dyn Any>>> {
+        cx: Scope,
+    ) -> FuturesUnordered<PinnedFuture<()>> {
+        let resources = { self.resources.borrow().clone() };
+        resources
+            .values()
+            .filter_map(|resource| match resource {
+                AnyResource::Serializable(resource)
+                    if resource.is_blocking() =>
+                {
+                    Some(resource.ready(cx))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Do not call on triggers
+    pub(crate) fn get_value(&self, node_id: NodeId) -> Option<NodeValue> {
         let signals = self.nodes.borrow();
-        signals.get(node_id).map(|node| node.value())
+        signals.get(node_id).and_then(|node| node.value.clone())
     }
 }
 
@@ -3,14 +3,23 @@ use crate::{
     console_warn,
     hydration::FragmentData,
     node::NodeId,
+    queue_microtask,
     runtime::{with_runtime, RuntimeId},
     suspense::StreamChunk,
     PinnedFuture, ResourceId, StoredValueId, SuspenseContext,
 };
-use futures::stream::FuturesUnordered;
+use futures::{channel::oneshot, stream::FuturesUnordered};
 use std::{
+    cell::Cell,
     collections::{HashMap, VecDeque},
     fmt,
+    marker::PhantomData,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, PoisonError,
+    },
+    thread::ThreadId,
 };
 
 #[doc(hidden)]
@@ -21,6 +30,7 @@ use std::{
 /// values will not have access to values created under another `create_scope`.
 ///
 /// You usually don't need to call this manually.
+#[track_caller]
 pub fn create_scope(
     runtime: RuntimeId,
     f: impl FnOnce(Scope) + 'static,
@@ -40,6 +50,7 @@ pub fn create_scope(
     any(debug_assertions, feature = "ssr"),
     instrument(level = "trace", skip_all,)
 )]
+#[track_caller]
 pub fn raw_scope_and_disposer(runtime: RuntimeId) -> (Scope, ScopeDisposer) {
     runtime.raw_scope_and_disposer()
 }
@@ -55,6 +66,7 @@ pub fn raw_scope_and_disposer(runtime: RuntimeId) -> (Scope, ScopeDisposer) {
     any(debug_assertions, feature = "ssr"),
     instrument(level = "trace", skip_all,)
 )]
+#[track_caller]
 pub fn run_scope<T>(
     runtime: RuntimeId,
     f: impl FnOnce(Scope) -> T + 'static,
@@ -72,6 +84,7 @@ pub fn run_scope<T>(
     any(debug_assertions, feature = "ssr"),
     instrument(level = "trace", skip_all,)
 )]
+#[track_caller]
 pub fn run_scope_undisposed<T>(
     runtime: RuntimeId,
     f: impl FnOnce(Scope) -> T + 'static,
@@ -79,6 +92,13 @@ pub fn run_scope_undisposed<T>(
     runtime.run_scope_undisposed(f, None)
 }
 
+#[doc(hidden)]
+/// Runs `f` once the app has hydrated, including the resources whose values
+/// the server is still streaming. Used by `leptos_meta`.
+pub fn on_hydration_settled(cx: Scope, f: impl FnOnce() + 'static) {
+    cx.on_hydration_settled(f)
+}
+
 /// A Each scope can have
 /// child scopes, and may in turn have a parent.
 ///
@@ -132,6 +152,7 @@ impl Scope {
         instrument(level = "trace", skip_all,)
     )]
     #[inline(always)]
+    #[track_caller]
     pub fn child_scope(self, f: impl FnOnce(Scope)) -> ScopeDisposer {
         let (_, disposer) = self.run_child_scope(f);
         disposer
@@ -141,40 +162,37 @@ impl Scope {
     /// type and a handle to dispose of it.
     ///
     /// The child scope has its own lifetime and disposer, but will be disposed when the parent is
-    /// disposed, if it has not been already.
+    /// disposed, if it has not been already. It is registered with its parent before the function
+    /// runs. Once the parent has been disposed, calling the returned disposer does nothing.
     ///
     /// This is useful for applications like a list or a router, which may want to create child scopes and
     /// dispose of them when they are no longer needed (e.g., a list item has been destroyed or the user
-    /// has navigated away from the route.)
+    /// has navigated away from the route), while keeping whatever the function built, like a row’s view.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # run_scope(create_runtime(), |cx| {
+    /// let (count, disposer) = cx.run_child_scope(|cx| {
+    ///     let (count, _) = create_signal(cx, 1);
+    ///     count
+    /// });
+    /// assert_eq!(count.get_untracked(), 1);
+    ///
+    /// disposer.dispose();
+    /// assert_eq!(count.try_get_untracked(), None);
+    /// # });
+    /// ```
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "trace", skip_all,)
     )]
     #[inline(always)]
+    #[track_caller]
     pub fn run_child_scope<T>(
         self,
         f: impl FnOnce(Scope) -> T,
     ) -> (T, ScopeDisposer) {
-        let (res, child_id, disposer) =
-            self.runtime.run_scope_undisposed(f, Some(self));
-
-        self.push_child(child_id);
-
-        (res, disposer)
-    }
-
-    fn push_child(&self, child_id: ScopeId) {
-        _ = with_runtime(self.runtime, |runtime| {
-            let mut children = runtime.scope_children.borrow_mut();
-            children
-                .entry(self.id)
-                .expect(
-                    "trying to add a child to a Scope that has already been \
-                     disposed",
-                )
-                .or_default()
-                .push(child_id);
-        });
+        self.runtime.run_child_scope(self, f)
     }
 
     /// Suspends reactive tracking while running the given function.
@@ -217,6 +235,40 @@ impl Scope {
     pub fn untrack_with_diagnostics<T>(&self, f: impl FnOnce() -> T) -> T {
         self.runtime.untrack(f, true)
     }
+
+    /// Returns the scope whose code is currently running, if any.
+    ///
+    /// This is the scope passed to the function that
+    /// [`run_child_scope`](Scope::run_child_scope) (or [`create_scope`]) is
+    /// running, or the scope an effect or memo was created in while it runs.
+    /// There is no current scope in an untracked function run by
+    /// [`untrack`](Scope::untrack) or in a future run by
+    /// [`spawn_local`](crate::spawn_local).
+    ///
+    /// This is how a [`Resource`](crate::Resource) that is read finds the
+    /// `<Suspense/>` it is read under.
+    pub fn current() -> Option<Scope> {
+        OWNER.with(Cell::get)
+    }
+}
+
+thread_local! {
+    static OWNER: Cell<Option<Scope>> = const { Cell::new(None) };
+}
+
+/// Runs `f` with `owner` as the [current scope](Scope::current), restoring
+/// the previous one afterwards, even if `f` panics.
+pub(crate) fn with_owner<T>(owner: Option<Scope>, f: impl FnOnce() -> T) -> T {
+    struct RestoreOwner(Option<Scope>);
+
+    impl Drop for RestoreOwner {
+        fn drop(&mut self) {
+            OWNER.with(|owner| owner.set(self.0));
+        }
+    }
+
+    let _prev_owner = RestoreOwner(OWNER.with(|prev| prev.replace(owner)));
+    f()
 }
 
 // Internals
@@ -225,15 +277,38 @@ impl Scope {
     /// Disposes of this reactive scope.
     ///
     /// This will
-    /// 1. dispose of all child `Scope`s
-    /// 2. run all cleanup functions defined for this scope by [`on_cleanup`](crate::on_cleanup).
-    /// 3. dispose of all signals, effects, and resources owned by this `Scope`.
+    /// 1. dispose of all child `Scope`s, in the order they were created, each one disposing of
+    ///    its own children first,
+    /// 2. run all cleanup functions defined for this scope by [`on_cleanup`](crate::on_cleanup),
+    ///    in the order they were added,
+    /// 3. dispose of all contexts, signals, effects, and resources owned by this `Scope`, and
+    /// 4. remove this scope from its parent, which will not dispose of it again.
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "trace", skip_all,)
     )]
     pub fn dispose(self) {
+        self.dispose_and_abort_loads(&mut Vec::new());
+    }
+
+    /// Disposes of this scope, aborting any loads that its resources have
+    /// in flight and collecting receivers that resolve once they have stopped.
+    fn dispose_and_abort_loads(
+        self,
+        aborted_loads: &mut Vec<oneshot::Receiver<()>>,
+    ) {
         _ = with_runtime(self.runtime, |runtime| {
+            // a scope that is disposed before its parent is no longer one of
+            // its children
+            let parent = runtime.scope_parents.borrow().get(self.id).copied();
+            if let Some(parent) = parent {
+                if let Some(siblings) =
+                    runtime.scope_children.borrow_mut().get_mut(parent)
+                {
+                    siblings.retain(|id| *id != self.id);
+                }
+            }
+
             // dispose of all child scopes
             let children = {
                 let mut children = runtime.scope_children.borrow_mut();
@@ -246,7 +321,7 @@ impl Scope {
                         runtime: self.runtime,
                         id,
                     }
-                    .dispose();
+                    .dispose_and_abort_loads(aborted_loads);
                 }
             }
             // run cleanups
@@ -259,6 +334,11 @@ impl Scope {
             }
 
             runtime.scope_parents.borrow_mut().remove(self.id);
+            #[cfg(debug_assertions)]
+            runtime.scope_locations.borrow_mut().remove(self.id);
+            runtime.scope_contexts.borrow_mut().remove(self.id);
+            #[cfg(debug_assertions)]
+            runtime.scope_context_locations.borrow_mut().remove(self.id);
 
             // remove everything we own and run cleanups
             let owned = {
@@ -292,10 +372,17 @@ impl Scope {
                         }
                         ScopeProperty::Effect(id) => {
                             runtime.nodes.borrow_mut().remove(id);
+                            runtime.node_owners.borrow_mut().remove(id);
                             runtime.node_sources.borrow_mut().remove(id);
+                            runtime.paused_effects.borrow_mut().remove(id);
                         }
                         ScopeProperty::Resource(id) => {
-                            runtime.resources.borrow_mut().remove(id);
+                            let resource =
+                                runtime.resources.borrow_mut().remove(id);
+                            if let Some(resource) = resource {
+                                aborted_loads
+                                    .extend(resource.in_flight().abort_all());
+                            }
                         }
                         ScopeProperty::StoredValue(id) => {
                             runtime.stored_values.borrow_mut().remove(id);
@@ -314,6 +401,9 @@ impl Scope {
             let scopes = runtime.scopes.borrow();
             if let Some(scope) = scopes.get(self.id) {
                 scope.borrow_mut().push(prop);
+                if let ScopeProperty::Effect(id) = prop {
+                    runtime.node_owners.borrow_mut().insert(id, *self);
+                }
             } else {
                 console_warn(
                     "tried to add property to a scope that has been disposed",
@@ -402,23 +492,120 @@ pub(crate) enum ScopeProperty {
 /// to dispose of that reactive scope.
 ///
 /// This will
-/// 1. dispose of all child `Scope`s
-/// 2. run all cleanup functions defined for this scope by [`on_cleanup`](crate::on_cleanup).
-/// 3. dispose of all signals, effects, and resources owned by this `Scope`.
+/// 1. dispose of all child `Scope`s, in the order they were created, each one disposing of
+///    its own children first,
+/// 2. run all cleanup functions defined for this scope by [`on_cleanup`](crate::on_cleanup),
+///    in the order they were added,
+/// 3. dispose of all contexts, signals, effects, and resources owned by this `Scope`, and
+/// 4. remove this scope from its parent, which will not dispose of it again.
+///
+/// Each reactive runtime belongs to the thread that created it, so a
+/// `ScopeDisposer` cannot be sent to another thread. To dispose of a scope
+/// from another thread, convert it with [`ScopeDisposer::into_send`].
 #[repr(transparent)]
-pub struct ScopeDisposer(pub(crate) Scope);
+pub struct ScopeDisposer(pub(crate) Scope, PhantomData<Rc<()>>);
 
 impl ScopeDisposer {
+    pub(crate) fn new(scope: Scope) -> Self {
+        Self(scope, PhantomData)
+    }
+
     /// Disposes of a reactive [`Scope`](crate::Scope).
     ///
     /// This will
-    /// 1. dispose of all child `Scope`s
-    /// 2. run all cleanup functions defined for this scope by [`on_cleanup`](crate::on_cleanup).
-    /// 3. dispose of all signals, effects, and resources owned by this `Scope`.
+    /// 1. dispose of all child `Scope`s, in the order they were created, each one disposing of
+    ///    its own children first,
+    /// 2. run all cleanup functions defined for this scope by [`on_cleanup`](crate::on_cleanup),
+    ///    in the order they were added,
+    /// 3. dispose of all contexts, signals, effects, and resources owned by this `Scope`, and
+    /// 4. remove this scope from its parent, which will not dispose of it again.
     #[inline(always)]
     pub fn dispose(self) {
         self.0.dispose()
     }
+
+    /// Disposes of a reactive [`Scope`](crate::Scope), like
+    /// [`dispose`](ScopeDisposer::dispose), and then waits until every load
+    /// that the scope’s resources had in flight has been aborted.
+    ///
+    /// This is useful when the tasks running those loads would otherwise
+    /// keep running, and try to update the disposed resources, after a
+    /// server response has been sent.
+    pub async fn dispose_async(self) {
+        let mut aborted_loads = Vec::new();
+        self.0.dispose_and_abort_loads(&mut aborted_loads);
+        // each receiver resolves when its load's task has stopped
+        futures::future::join_all(aborted_loads).await;
+    }
+
+    /// Converts the disposer into one that can be sent to other threads.
+    pub fn into_send(self) -> SendScopeDisposer {
+        SendScopeDisposer {
+            scope: self.0,
+            owner: std::thread::current().id(),
+        }
+    }
+}
+
+/// A [`ScopeDisposer`] that can be sent to, and dropped on, any thread.
+///
+/// Because the scope’s runtime belongs to the thread that created it, the
+/// scope can only be disposed of on that thread.
+/// [`dispose_on_owner_thread`](SendScopeDisposer::dispose_on_owner_thread)
+/// disposes of it immediately when called on that thread. On any other
+/// thread, it queues the scope, which is then disposed of the next time the
+/// owning thread creates or disposes of a runtime, or calls
+/// [`dispose_queued_scopes`].
+#[derive(Debug)]
+#[must_use = "Scope will leak memory if the disposer function is never called"]
+pub struct SendScopeDisposer {
+    scope: Scope,
+    owner: ThreadId,
+}
+
+static QUEUED_DISPOSALS: Mutex<Vec<(ThreadId, Scope)>> = Mutex::new(Vec::new());
+static HAS_QUEUED_DISPOSALS: AtomicBool = AtomicBool::new(false);
+
+impl SendScopeDisposer {
+    /// Disposes of the scope if called on the thread that owns it, or queues
+    /// it to be disposed of on that thread otherwise.
+    pub fn dispose_on_owner_thread(self) {
+        if std::thread::current().id() == self.owner {
+            self.scope.dispose();
+        } else {
+            let mut queue = QUEUED_DISPOSALS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            queue.push((self.owner, self.scope));
+            HAS_QUEUED_DISPOSALS.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Disposes of every scope that another thread has queued to be disposed of
+/// on the current thread with
+/// [`SendScopeDisposer::dispose_on_owner_thread`].
+pub fn dispose_queued_scopes() {
+    if !HAS_QUEUED_DISPOSALS.load(Ordering::Acquire) {
+        return;
+    }
+
+    let current = std::thread::current().id();
+    let scopes = {
+        let mut queue = QUEUED_DISPOSALS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let (ours, others) = queue
+            .drain(..)
+            .partition::<Vec<_>, _>(|(owner, _)| *owner == current);
+        HAS_QUEUED_DISPOSALS.store(!others.is_empty(), Ordering::Release);
+        *queue = others;
+        ours
+    };
+
+    for (_, scope) in scopes {
+        scope.dispose();
+    }
 }
 
 impl Scope {
@@ -443,6 +630,30 @@ impl Scope {
             .unwrap_or_default()
     }
 
+    /// Runs `f` once the app has hydrated, including the resources whose
+    /// values the server is still streaming and the views that read them.
+    ///
+    /// If no resource is waiting for the server, or the app is not being
+    /// hydrated, `f` runs in a microtask, after the rest of the current
+    /// hydration pass.
+    pub(crate) fn on_hydration_settled(&self, f: impl FnOnce() + 'static) {
+        let runtime = self.runtime;
+        queue_microtask(move || {
+            let f = with_runtime(runtime, |runtime| {
+                let mut shared_context = runtime.shared_context.borrow_mut();
+                if shared_context.awaiting_server.is_empty() {
+                    Some(f)
+                } else {
+                    shared_context.on_settled.push(Box::new(f));
+                    None
+                }
+            });
+            if let Ok(Some(f)) = f {
+                f();
+            }
+        });
+    }
+
     /// Returns IDs for all [`Resource`](crate::Resource)s found on any scope.
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
@@ -492,17 +703,31 @@ impl Scope {
                 }
             });
 
+            let out_of_order = async move {
+                rx1.next().await;
+                out_of_order_resolver()
+            };
+            let in_order = async move {
+                rx2.next().await;
+                in_order_resolver()
+            };
+            #[cfg(feature = "tracing")]
+            let (out_of_order, in_order) = {
+                use tracing::Instrument;
+                let span = |mode: &'static str| {
+                    tracing::info_span!("suspense_flush", key, mode)
+                };
+                (
+                    out_of_order.instrument(span("out_of_order")),
+                    in_order.instrument(span("in_order")),
+                )
+            };
+
             shared_context.pending_fragments.insert(
                 key.to_string(),
                 FragmentData {
-                    out_of_order: Box::pin(async move {
-                        rx1.next().await;
-                        out_of_order_resolver()
-                    }),
-                    in_order: Box::pin(async move {
-                        rx2.next().await;
-                        in_order_resolver()
-                    }),
+                    out_of_order: Box::pin(out_of_order),
+                    in_order: Box::pin(in_order),
                     should_block: context.should_block(),
                     is_ready: Some(Box::pin(async move {
                         rx3.next().await;
@@ -552,6 +777,23 @@ impl Scope {
         Box::pin(async move { while ready.next().await.is_some() {} })
     }
 
+    /// A future that will resolve when every blocking resource has loaded,
+    /// whether or not it is read under a `<Suspense/>`. See
+    /// [`create_blocking_resource`](crate::create_blocking_resource).
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        instrument(level = "trace", skip_all,)
+    )]
+    pub fn blocking_resources_ready(self) -> PinnedFuture<()> {
+        use futures::StreamExt;
+
+        let mut ready = with_runtime(self.runtime, |runtime| {
+            runtime.blocking_resources_ready(self)
+        })
+        .unwrap_or_default();
+        Box::pin(async move { while ready.next().await.is_some() {} })
+    }
+
     /// Takes the pending HTML for a single `<Suspense/>` node.
     ///
     /// Returns a tuple of two pinned `Future`s that return content for out-of-order
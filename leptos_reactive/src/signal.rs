@@ -3,20 +3,17 @@ use crate::{
     console_warn, create_effect, diagnostics,
     diagnostics::*,
     macros::debug_warn,
-    node::NodeId,
+    node::{NodeId, NodeValue, Primitive, ReactiveNodeState},
     on_cleanup,
     runtime::{with_runtime, RuntimeId},
     Runtime, Scope, ScopeProperty,
 };
 use futures::Stream;
 use std::{
-    any::Any,
-    cell::RefCell,
     fmt,
     hash::{Hash, Hasher},
     marker::PhantomData,
     pin::Pin,
-    rc::Rc,
 };
 use thiserror::Error;
 
@@ -110,6 +107,33 @@ pub mod prelude {
 
 /// This trait allows getting an owned value of the signals
 /// inner type.
+///
+/// It is implemented by every readable signal type, including [`Memo`](crate::Memo),
+/// [`Signal`](crate::Signal), [`MaybeSignal`](crate::MaybeSignal) and
+/// [`Resource`](crate::Resource), so generic code can accept any of them with
+/// an `impl SignalGet<T>` bound. A derived closure can be passed by wrapping
+/// it in [`Signal::derive`](crate::Signal::derive).
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// fn doubled(value: &impl SignalGet<u32>) -> u32 {
+///     value.get() * 2
+/// }
+///
+/// let (count, set_count) = create_signal(cx, 1);
+/// let memo = create_memo(cx, move |_| count.get() + 1);
+/// let derived = Signal::derive(cx, move || count.get() + 2);
+///
+/// assert_eq!(doubled(&count), 2);
+/// assert_eq!(doubled(&memo), 4);
+/// assert_eq!(doubled(&derived), 6);
+/// assert_eq!(doubled(&MaybeSignal::from(5)), 10);
+///
+/// set_count.set(2);
+/// assert_eq!(doubled(&derived), 8);
+/// # }).dispose();
+/// ```
 pub trait SignalGet<T> {
     /// Clones and returns the current value of the signal, and subscribes
     /// the running effect to this signal.
@@ -413,7 +437,11 @@ pub fn create_signal_from_stream<T>(
             let (read, write) = create_signal(cx, None);
             spawn_local(async move {
                 while let Some(value) = stream.next().await {
-                    write.set(Some(value));
+                    // stop reading the stream once the signal's scope is gone
+                    if write.try_set(Some(value)).is_some() {
+                        debug_dropped_value(write.defined_at());
+                        break;
+                    }
                 }
             });
             read
@@ -498,7 +526,7 @@ impl<T: Clone> SignalGetUntracked<T> for ReadSignal<T> {
     )]
     fn get_untracked(&self) -> T {
         match with_runtime(self.runtime, |runtime| {
-            self.id.try_with_no_subscription(runtime, T::clone)
+            self.id.try_get_no_subscription(runtime)
         })
         .expect("runtime to be alive")
         {
@@ -526,7 +554,7 @@ impl<T: Clone> SignalGetUntracked<T> for ReadSignal<T> {
     #[track_caller]
     fn try_get_untracked(&self) -> Option<T> {
         with_runtime(self.runtime, |runtime| {
-            self.id.try_with_no_subscription(runtime, Clone::clone).ok()
+            self.id.try_get_no_subscription(runtime).ok()
         })
         .ok()
         .flatten()
@@ -687,7 +715,7 @@ impl<T: Clone> SignalGet<T> for ReadSignal<T> {
         let diagnostics = diagnostics!(self);
 
         match with_runtime(self.runtime, |runtime| {
-            self.id.try_with(runtime, T::clone, diagnostics)
+            self.id.try_get(runtime, diagnostics)
         })
         .expect("runtime to be alive")
         {
@@ -712,8 +740,15 @@ impl<T: Clone> SignalGet<T> for ReadSignal<T> {
             )
         )
     )]
+    #[track_caller]
     fn try_get(&self) -> Option<T> {
-        self.try_with(Clone::clone).ok()
+        let diagnostics = diagnostics!(self);
+
+        with_runtime(self.runtime, |runtime| {
+            self.id.try_get(runtime, diagnostics).ok()
+        })
+        .ok()
+        .flatten()
     }
 }
 
@@ -1071,9 +1106,9 @@ impl<T> SignalSet<T> for WriteSignal<T> {
         )
     )]
     fn set(&self, new_value: T) {
-        self.id.update(
+        self.id.set(
             self.runtime,
-            |n| *n = new_value,
+            new_value,
             #[cfg(debug_assertions)]
             Some(self.defined_at),
         );
@@ -1093,16 +1128,23 @@ impl<T> SignalSet<T> for WriteSignal<T> {
         )
     )]
     fn try_set(&self, new_value: T) -> Option<T> {
-        let mut new_value = Some(new_value);
-
-        self.id.update(
+        self.id.set(
             self.runtime,
-            |t| *t = new_value.take().unwrap(),
+            new_value,
             #[cfg(debug_assertions)]
             None,
-        );
+        )
+    }
+}
 
-        new_value
+impl<T> WriteSignal<T> {
+    /// The location at which the signal was created. This is only available
+    /// in debug mode or when the `ssr` feature is enabled.
+    pub fn defined_at(&self) -> Option<&'static std::panic::Location<'static>> {
+        defined_at(
+            #[cfg(any(debug_assertions, feature = "ssr"))]
+            self.defined_at,
+        )
     }
 }
 
@@ -1289,7 +1331,17 @@ impl<T: Clone> SignalGetUntracked<T> for RwSignal<T> {
         )
     )]
     fn get_untracked(&self) -> T {
-        self.id.with_no_subscription(self.runtime, Clone::clone)
+        match with_runtime(self.runtime, |runtime| {
+            self.id.try_get_no_subscription(runtime)
+        })
+        .expect("runtime to be alive")
+        {
+            Ok(t) => t,
+            Err(_) => panic_getting_dead_signal(
+                #[cfg(any(debug_assertions, feature = "ssr"))]
+                self.defined_at,
+            ),
+        }
     }
 
     #[cfg_attr(
@@ -1305,18 +1357,13 @@ impl<T: Clone> SignalGetUntracked<T> for RwSignal<T> {
             )
         )
     )]
+    #[track_caller]
     fn try_get_untracked(&self) -> Option<T> {
-        match with_runtime(self.runtime, |runtime| {
-            self.id.try_with_no_subscription(runtime, Clone::clone)
+        with_runtime(self.runtime, |runtime| {
+            self.id.try_get_no_subscription(runtime).ok()
         })
-        .expect("runtime to be alive")
-        {
-            Ok(t) => t,
-            Err(_) => panic_getting_dead_signal(
-                #[cfg(any(debug_assertions, feature = "ssr"))]
-                self.defined_at,
-            ),
-        }
+        .ok()
+        .flatten()
     }
 }
 
@@ -1580,7 +1627,7 @@ impl<T: Clone> SignalGet<T> for RwSignal<T> {
         let diagnostics = diagnostics!(self);
 
         match with_runtime(self.runtime, |runtime| {
-            self.id.try_with(runtime, T::clone, diagnostics)
+            self.id.try_get(runtime, diagnostics)
         })
         .expect("runtime to be alive")
         {
@@ -1610,7 +1657,7 @@ impl<T: Clone> SignalGet<T> for RwSignal<T> {
         let diagnostics = diagnostics!(self);
 
         with_runtime(self.runtime, |runtime| {
-            self.id.try_with(runtime, Clone::clone, diagnostics).ok()
+            self.id.try_get(runtime, diagnostics).ok()
         })
         .ok()
         .flatten()
@@ -1722,9 +1769,9 @@ impl<T> SignalSet<T> for RwSignal<T> {
         )
     )]
     fn set(&self, value: T) {
-        self.id.update(
+        self.id.set(
             self.runtime,
-            |n| *n = value,
+            value,
             #[cfg(debug_assertions)]
             Some(self.defined_at),
         );
@@ -1744,16 +1791,12 @@ impl<T> SignalSet<T> for RwSignal<T> {
         )
     )]
     fn try_set(&self, new_value: T) -> Option<T> {
-        let mut new_value = Some(new_value);
-
-        self.id.update(
+        self.id.set(
             self.runtime,
-            |t| *t = new_value.take().unwrap(),
+            new_value,
             #[cfg(debug_assertions)]
             None,
-        );
-
-        new_value
+        )
     }
 }
 
@@ -1782,6 +1825,37 @@ impl<T> SignalDispose for RwSignal<T> {
 }
 
 impl<T> RwSignal<T> {
+    /// The location at which the signal was created. This is only available
+    /// in debug mode or when the `ssr` feature is enabled.
+    pub fn defined_at(&self) -> Option<&'static std::panic::Location<'static>> {
+        defined_at(
+            #[cfg(any(debug_assertions, feature = "ssr"))]
+            self.defined_at,
+        )
+    }
+
+    /// Whether any effect or memo is currently subscribed to the signal.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let count = create_rw_signal(cx, 0);
+    /// assert!(!count.is_tracked());
+    /// create_isomorphic_effect(cx, move |_| count.get());
+    /// assert!(count.is_tracked());
+    /// # }).dispose();
+    /// ```
+    pub fn is_tracked(&self) -> bool {
+        with_runtime(self.runtime, |runtime| {
+            runtime
+                .node_subscribers
+                .borrow()
+                .get(self.id)
+                .map(|subs| !subs.borrow().is_empty())
+                .unwrap_or(false)
+        })
+        .unwrap_or(false)
+    }
+
     /// Returns a read-only handle to the signal.
     ///
     /// Useful if you're trying to give read access to another component but ensure that it can't write
@@ -1970,11 +2044,19 @@ impl NodeId {
     fn try_with_no_subscription_inner(
         &self,
         runtime: &Runtime,
-    ) -> Result<Rc<RefCell<dyn Any>>, SignalError> {
+    ) -> Result<NodeValue, SignalError> {
+        {
+            let nodes = runtime.nodes.borrow();
+            let node = nodes.get(*self).ok_or(SignalError::Disposed)?;
+            // a node that is clean doesn't need to be updated first
+            if node.state == ReactiveNodeState::Clean {
+                return Ok(node.value.clone().expect("node to have a value"));
+            }
+        }
         runtime.update_if_necessary(*self);
         let nodes = runtime.nodes.borrow();
         let node = nodes.get(*self).ok_or(SignalError::Disposed)?;
-        Ok(node.value())
+        Ok(node.value.clone().expect("node to have a value"))
     }
 
     #[track_caller]
@@ -1988,12 +2070,11 @@ impl NodeId {
         T: 'static,
     {
         let value = self.try_with_no_subscription_inner(runtime)?;
-        let value = value.borrow();
         let value = value
-            .downcast_ref::<T>()
+            .with(f)
             .ok_or_else(|| SignalError::Type(std::any::type_name::<T>()))
             .expect("to downcast signal type");
-        Ok(f(value))
+        Ok(value)
     }
 
     #[track_caller]
@@ -2012,6 +2093,109 @@ impl NodeId {
         self.try_with_no_subscription(runtime, f)
     }
 
+    /// Clones the value of the signal, without subscribing to it.
+    ///
+    /// The values of primitive types, which signals store inline, are
+    /// copied out of the node while the runtime’s nodes are borrowed, without
+    /// cloning the `Rc` that holds them or borrowing and downcasting it.
+    #[track_caller]
+    #[inline(always)]
+    pub(crate) fn try_get_no_subscription<T>(
+        &self,
+        runtime: &Runtime,
+    ) -> Result<T, SignalError>
+    where
+        T: Clone + 'static,
+    {
+        {
+            let nodes = runtime.nodes.borrow();
+            let node = nodes.get(*self).ok_or(SignalError::Disposed)?;
+            // a node that isn't clean may need to be updated first
+            if let (ReactiveNodeState::Clean, Some(NodeValue::Inline(value))) =
+                (node.state, &node.value)
+            {
+                return Ok(value
+                    .get()
+                    .get::<T>()
+                    .ok_or_else(
+                        || SignalError::Type(std::any::type_name::<T>()),
+                    )
+                    .expect("to downcast signal type"));
+            }
+        }
+        self.try_with_no_subscription(runtime, T::clone)
+    }
+
+    #[track_caller]
+    #[inline(always)]
+    pub(crate) fn try_get<T>(
+        &self,
+        runtime: &Runtime,
+        diagnostics: AccessDiagnostics,
+    ) -> Result<T, SignalError>
+    where
+        T: Clone + 'static,
+    {
+        self.subscribe(runtime, diagnostics);
+
+        self.try_get_no_subscription(runtime)
+    }
+
+    /// Replaces the value of the signal and notifies its subscribers,
+    /// returning the new value back if the signal has been disposed.
+    ///
+    /// Like [`NodeId::try_get_no_subscription`], the values of primitive
+    /// types are written into the node in place.
+    #[inline(always)]
+    pub(crate) fn set<T>(
+        &self,
+        runtime_id: RuntimeId,
+        new_value: T,
+        #[cfg(debug_assertions)] defined_at: Option<
+            &'static std::panic::Location<'static>,
+        >,
+    ) -> Option<T>
+    where
+        T: 'static,
+    {
+        let mut new_value = Some(new_value);
+        let written = with_runtime(runtime_id, |runtime| {
+            let written = match runtime
+                .nodes
+                .borrow()
+                .get(*self)
+                .and_then(|node| node.value.as_ref())
+            {
+                Some(NodeValue::Inline(slot)) => {
+                    Primitive::write(slot, &mut new_value)
+                }
+                _ => false,
+            };
+            if written {
+                runtime.notify_set(
+                    *self,
+                    #[cfg(debug_assertions)]
+                    defined_at,
+                );
+            }
+            written
+        })
+        .unwrap_or_default();
+        if written {
+            return None;
+        }
+
+        // boxed values, and signals that can't be written to, which this
+        // reports
+        self.update(
+            runtime_id,
+            |n| *n = new_value.take().unwrap(),
+            #[cfg(debug_assertions)]
+            defined_at,
+        );
+        new_value
+    }
+
     #[inline(always)]
     pub(crate) fn with_no_subscription<T, U>(
         &self,
@@ -2041,9 +2225,8 @@ impl NodeId {
     {
         with_runtime(runtime, |runtime| {
             if let Some(value) = runtime.get_value(*self) {
-                let mut value = value.borrow_mut();
-                if let Some(value) = value.downcast_mut::<T>() {
-                    Some(f(value))
+                if let Some(updated) = value.update(f) {
+                    Some(updated)
                 } else {
                     debug_warn!(
                         "[Signal::update] failed when downcasting to \
@@ -2088,9 +2271,8 @@ impl NodeId {
     {
         with_runtime(runtime_id, |runtime| {
             let updated = if let Some(value) = runtime.get_value(*self) {
-                let mut value = value.borrow_mut();
-                if let Some(value) = value.downcast_mut::<T>() {
-                    Some(f(value))
+                if let Some(updated) = value.update(f) {
+                    Some(updated)
                 } else {
                     debug_warn!(
                         "[Signal::update] failed when downcasting to \
@@ -2120,10 +2302,11 @@ impl NodeId {
 
             // notify subscribers
             if updated.is_some() {
-                // mark descendants dirty
-                runtime.mark_dirty(*self);
-
-                runtime.run_effects();
+                runtime.notify_set(
+                    *self,
+                    #[cfg(debug_assertions)]
+                    defined_at,
+                );
             }
 
             updated
@@ -2195,6 +2378,43 @@ pub(crate) fn panic_getting_dead_signal(
     )
 }
 
+fn defined_at(
+    #[cfg(any(debug_assertions, feature = "ssr"))]
+    defined_at: &'static std::panic::Location<'static>,
+) -> Option<&'static std::panic::Location<'static>> {
+    #[cfg(any(debug_assertions, feature = "ssr"))]
+    {
+        Some(defined_at)
+    }
+    #[cfg(not(any(debug_assertions, feature = "ssr")))]
+    {
+        None
+    }
+}
+
+/// Logs, in debug mode, that a value was dropped because the signal it was
+/// meant for has been disposed. This is how the framework’s own async tasks,
+/// like actions and resources, handle finishing after their scope has been
+/// disposed, instead of panicking.
+#[doc(hidden)]
+#[cold]
+#[inline(never)]
+pub fn debug_dropped_value(
+    defined_at: Option<&'static std::panic::Location<'static>>,
+) {
+    #[cfg(debug_assertions)]
+    match defined_at {
+        Some(defined_at) => console_warn(&format!(
+            "value for disposed signal dropped, created at {defined_at}"
+        )),
+        None => console_warn("value for disposed signal dropped"),
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        _ = defined_at;
+    }
+}
+
 #[cold]
 #[inline(never)]
 #[track_caller]
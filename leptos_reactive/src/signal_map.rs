@@ -0,0 +1,274 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_rw_signal, store_value, RwSignal, Scope, ScopeDisposer, Signal,
+    SignalGetUntracked, SignalUpdate, SignalWith, SignalWithUntracked,
+    StoredValue,
+};
+use std::{collections::HashMap, fmt, hash::Hash};
+
+/// A keyed collection of reactive values, in which each entry is tracked
+/// separately.
+///
+/// Storing a `HashMap` in a single signal means that every subscriber reruns
+/// whenever any entry changes. A `SignalMap` instead gives each key its own
+/// signal: reading an entry with [`get`](SignalMap::get) or
+/// [`with`](SignalMap::with) only subscribes to that key, and
+/// [`keys`](SignalMap::keys) only changes when a key is inserted or removed.
+///
+/// This makes it a good fit for `<For/>`, where `keys` drives the list and
+/// each row subscribes to its own entry:
+///
+/// ```ignore
+/// let todos = create_signal_map::<usize, Todo>(cx);
+///
+/// view! { cx,
+///     <For
+///         each=move || todos.keys().get()
+///         key=|id| *id
+///         view=move |cx, id| {
+///             let todo = todos.get(id);
+///             view! { cx, <li>{move || todo.get().map(|todo| todo.title)}</li> }
+///         }
+///     />
+/// }
+/// ```
+///
+/// The per-key signals are disposed of when their entry is removed, or when
+/// the scope the map was created in is disposed.
+pub struct SignalMap<K, V>
+where
+    K: 'static,
+    V: 'static,
+{
+    cx: Scope,
+    slots: StoredValue<HashMap<K, Slot<V>>>,
+    keys: RwSignal<Vec<K>>,
+}
+
+struct Slot<V>
+where
+    V: 'static,
+{
+    value: RwSignal<Option<V>>,
+    disposer: ScopeDisposer,
+}
+
+impl<K, V> Clone for SignalMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for SignalMap<K, V> {}
+
+impl<K, V> fmt::Debug for SignalMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalMap")
+            .field("slots", &self.slots)
+            .field("keys", &self.keys)
+            .finish()
+    }
+}
+
+impl<K, V> PartialEq for SignalMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.slots == other.slots && self.keys == other.keys
+    }
+}
+
+impl<K, V> Eq for SignalMap<K, V> {}
+
+/// Creates an empty [`SignalMap`], a keyed collection in which each entry
+/// notifies only its own subscribers when it changes.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let map = create_signal_map::<&str, i32>(cx);
+/// map.insert("a", 1);
+/// map.insert("b", 2);
+///
+/// let a = map.get("a");
+/// let runs = Rc::new(Cell::new(0));
+/// create_isomorphic_effect(cx, {
+///     let runs = Rc::clone(&runs);
+///     move |_| {
+///         a.track();
+///         runs.set(runs.get() + 1);
+///     }
+/// });
+/// assert_eq!(runs.get(), 1);
+///
+/// // updating another entry doesn't rerun the effect
+/// map.update(&"b", |b| *b += 1);
+/// assert_eq!(runs.get(), 1);
+///
+/// map.update(&"a", |a| *a += 1);
+/// assert_eq!(runs.get(), 2);
+/// assert_eq!(a.get(), Some(2));
+/// assert_eq!(map.keys().get(), vec!["a", "b"]);
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_signal_map<K, V>(cx: Scope) -> SignalMap<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: 'static,
+{
+    SignalMap {
+        cx,
+        slots: store_value(cx, HashMap::new()),
+        keys: create_rw_signal(cx, Vec::new()),
+    }
+}
+
+impl<K, V> SignalMap<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: 'static,
+{
+    /// Returns the signal for `key`, creating an empty one if the key is not
+    /// present.
+    #[track_caller]
+    fn slot(&self, key: &K) -> RwSignal<Option<V>> {
+        let cx = self.cx;
+        self.slots
+            .try_update_value(|slots| {
+                slots
+                    .entry(key.clone())
+                    .or_insert_with(|| {
+                        let (value, disposer) =
+                            cx.run_child_scope(|cx| create_rw_signal(cx, None));
+                        Slot { value, disposer }
+                    })
+                    .value
+            })
+            .expect("SignalMap used after its scope was disposed")
+    }
+
+    fn existing_slot(&self, key: &K) -> Option<RwSignal<Option<V>>> {
+        self.slots
+            .try_with_value(|slots| slots.get(key).map(|slot| slot.value))
+            .flatten()
+    }
+
+    /// Inserts a value, returning the previous value for that key, if any.
+    ///
+    /// This notifies subscribers of `key` and, if the key is new, of
+    /// [`keys`](SignalMap::keys).
+    #[track_caller]
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let prev = self
+            .slot(&key)
+            .try_update(|slot| slot.replace(value))
+            .flatten();
+        if prev.is_none() {
+            self.keys.update(|keys| keys.push(key));
+        }
+        prev
+    }
+
+    /// Removes an entry, returning its value if it was present, and disposes
+    /// of its signal.
+    ///
+    /// This notifies subscribers of `key` and of [`keys`](SignalMap::keys).
+    #[track_caller]
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let slot = self
+            .slots
+            .try_update_value(|slots| slots.remove(key))
+            .flatten()?;
+        let prev = slot.value.try_update(Option::take).flatten();
+        if prev.is_some() {
+            self.keys.update(|keys| keys.retain(|k| k != key));
+        }
+        slot.disposer.dispose();
+        prev
+    }
+
+    /// Updates the value for `key` in place, notifying only the subscribers
+    /// of that key. Returns `false`, without notifying anyone, if the key is
+    /// not present.
+    #[track_caller]
+    pub fn update(&self, key: &K, f: impl FnOnce(&mut V)) -> bool {
+        let Some(slot) = self.existing_slot(key) else {
+            return false;
+        };
+        if !slot.with_untracked(Option::is_some) {
+            return false;
+        }
+        slot.update(|value| {
+            if let Some(value) = value {
+                f(value)
+            }
+        });
+        true
+    }
+
+    /// Returns a signal that reads the value for `key`, or `None` if it is
+    /// not present, and only notifies its subscribers when that entry is
+    /// inserted, updated or removed.
+    ///
+    /// The returned signal belongs to the scope the map was created in, so
+    /// it should be created once (for example, once per row) rather than
+    /// in every run of an effect.
+    #[track_caller]
+    pub fn get(&self, key: K) -> Signal<Option<V>>
+    where
+        V: Clone,
+    {
+        let map = *self;
+        Signal::derive(self.cx, move || map.with(&key, |value| value.cloned()))
+    }
+
+    /// Applies a function to the value for `key`, subscribing to changes to
+    /// that entry only. While the key is not present, this subscribes to
+    /// [`keys`](SignalMap::keys) instead, so reading a missing key doesn't
+    /// store anything for it.
+    #[track_caller]
+    pub fn with<O>(&self, key: &K, f: impl FnOnce(Option<&V>) -> O) -> O {
+        match self.existing_slot(key) {
+            Some(slot) => slot.with(|value| f(value.as_ref())),
+            None => {
+                self.keys.track();
+                f(None)
+            }
+        }
+    }
+
+    /// Returns a clone of the value for `key`, without subscribing to it.
+    pub fn get_untracked(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.existing_slot(key)
+            .and_then(|slot| slot.get_untracked())
+    }
+
+    /// Whether the map contains `key`, without subscribing to it.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.existing_slot(key)
+            .map(|slot| slot.with_untracked(Option::is_some))
+            .unwrap_or(false)
+    }
+
+    /// A signal of the map’s keys, in insertion order, which only changes
+    /// when an entry is inserted or removed.
+    pub fn keys(&self) -> Signal<Vec<K>> {
+        self.keys.into()
+    }
+
+    /// The number of entries in the map, subscribing to
+    /// [`keys`](SignalMap::keys).
+    #[track_caller]
+    pub fn len(&self) -> usize {
+        self.keys.with(Vec::len)
+    }
+
+    /// Whether the map is empty, subscribing to [`keys`](SignalMap::keys).
+    #[track_caller]
+    pub fn is_empty(&self) -> bool {
+        self.keys.with(Vec::is_empty)
+    }
+}
@@ -1,4 +1,5 @@
 #![forbid(unsafe_code)]
+use crate::scope::with_owner;
 use cfg_if::cfg_if;
 use std::future::Future;
 
@@ -57,7 +58,7 @@ use std::future::Future;
 ///             // handles the error from the resource
 ///             <ErrorBoundary fallback=|cx, _| {view! {cx, <p>"Something went wrong"</p>}}>
 ///                 {move || {
-///                     user.read(cx).map(move |x| {
+///                     user.get().map(move |x| {
 ///                         // the resource has a result
 ///                         x.map(move |y| {
 ///                             // successful call from the server fn
@@ -75,6 +76,13 @@ pub fn spawn_local<F>(fut: F)
 where
     F: Future<Output = ()> + 'static,
 {
+    // a future runs outside of the scope that spawned it, even when it is
+    // polled right away
+    let mut fut = Box::pin(fut);
+    let fut = futures::future::poll_fn(move |cx| {
+        with_owner(None, || fut.as_mut().poll(cx))
+    });
+
     cfg_if! {
         if #[cfg(target_arch = "wasm32")] {
             wasm_bindgen_futures::spawn_local(fut)
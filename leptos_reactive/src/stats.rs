@@ -0,0 +1,170 @@
+use crate::{node::ReactiveNodeType, runtime::with_runtime, Scope};
+use std::cell::Cell;
+
+thread_local! {
+    #[cfg(debug_assertions)]
+    static LIVE_EVENT_HANDLERS: Cell<usize> = Default::default();
+    static ABORTED_REQUESTS: Cell<usize> = Default::default();
+}
+
+/// The number of reactive values that are currently alive in a runtime,
+/// as returned by [`runtime_stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeStats {
+    /// Scopes that have not yet been disposed.
+    pub scopes: usize,
+    /// Signals, including the signals of a [`Trigger`](crate::Trigger).
+    pub signals: usize,
+    /// Memos, including selectors and [`Signal`](crate::Signal)s derived
+    /// from a memo.
+    pub memos: usize,
+    /// Effects, including render effects.
+    pub effects: usize,
+    /// Resources.
+    pub resources: usize,
+    /// Stored values.
+    pub stored_values: usize,
+    /// Values provided as contexts with [`provide_context`](crate::provide_context).
+    pub contexts: usize,
+    /// Event handlers that have been added to elements and not yet removed.
+    /// They are only counted in debug mode, so this is always `0` in release.
+    pub event_handlers: usize,
+    /// Resource loads and action dispatches that have been aborted, since
+    /// the thread started, because their scope was disposed before they
+    /// finished. Unlike the other fields, this only ever grows.
+    pub aborted_requests: usize,
+}
+
+/// Returns the number of scopes, signals, memos, effects and resources that
+/// are currently alive in the runtime of `cx`, the number of event
+/// handlers that have been added to elements, and the number of requests
+/// that have been aborted.
+///
+/// Every value is owned by a scope and is dropped when that scope is
+/// disposed, so in a long-running app these numbers should stay roughly
+/// stable while navigating back and forth. If one grows monotonically,
+/// something is holding on to scopes that are no longer used; see
+/// [`leak_report`] in debug mode.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # let runtime = create_runtime();
+/// # _ = create_scope(runtime, |cx| {
+/// let before = runtime_stats(cx);
+/// let disposer = cx.child_scope(|cx| {
+///     let (count, _) = create_signal(cx, 0);
+///     create_memo(cx, move |_| count.get() * 2);
+/// });
+/// assert_eq!(runtime_stats(cx).scopes, before.scopes + 1);
+/// assert_eq!(runtime_stats(cx).memos, before.memos + 1);
+///
+/// disposer.dispose();
+/// assert_eq!(runtime_stats(cx), before);
+/// # });
+/// # runtime.dispose();
+/// ```
+pub fn runtime_stats(cx: Scope) -> RuntimeStats {
+    with_runtime(cx.runtime, |runtime| {
+        let mut stats = RuntimeStats {
+            scopes: runtime.scopes.borrow().len(),
+            resources: runtime.resources.borrow().len(),
+            stored_values: runtime.stored_values.borrow().len(),
+            contexts: runtime
+                .scope_contexts
+                .borrow()
+                .values()
+                .map(|contexts| contexts.len())
+                .sum(),
+            #[cfg(debug_assertions)]
+            event_handlers: LIVE_EVENT_HANDLERS.with(Cell::get),
+            aborted_requests: ABORTED_REQUESTS.with(Cell::get),
+            ..Default::default()
+        };
+        for node in runtime.nodes.borrow().values() {
+            match node.node_type {
+                ReactiveNodeType::Trigger | ReactiveNodeType::Signal => {
+                    stats.signals += 1
+                }
+                ReactiveNodeType::Memo { .. } => stats.memos += 1,
+                ReactiveNodeType::Effect { .. } => stats.effects += 1,
+            }
+        }
+        stats
+    })
+    .unwrap_or_default()
+}
+
+#[doc(hidden)]
+/// Counts an event handler that has been added to or removed from an
+/// element. Used by `leptos_dom` in debug mode.
+#[cfg(debug_assertions)]
+pub fn count_event_handler(added: bool) {
+    LIVE_EVENT_HANDLERS.with(|count| {
+        count.set(if added {
+            count.get() + 1
+        } else {
+            count.get().saturating_sub(1)
+        })
+    });
+}
+
+#[doc(hidden)]
+/// Returns the number of event handlers counted by [`count_event_handler`].
+#[cfg(debug_assertions)]
+pub fn live_event_handler_count() -> usize {
+    LIVE_EVENT_HANDLERS.with(Cell::get)
+}
+
+#[doc(hidden)]
+/// Counts a resource load or action dispatch that was aborted before it
+/// finished. Used by `leptos_server`.
+pub fn count_aborted_request() {
+    ABORTED_REQUESTS.with(|count| count.set(count.get() + 1));
+}
+
+/// A scope that is still alive, although the scope it was created under has
+/// been disposed. See [`leak_report`].
+#[cfg(debug_assertions)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeakedScope {
+    /// The leaked scope.
+    pub id: crate::ScopeId,
+    /// The parent scope, which has already been disposed.
+    pub parent: crate::ScopeId,
+    /// Where the leaked scope was created.
+    pub defined_at: Option<&'static std::panic::Location<'static>>,
+    /// The number of signals, effects, resources and other values that the
+    /// leaked scope still owns.
+    pub owned: usize,
+}
+
+/// Lists the scopes that are still alive even though their parent scope has
+/// been disposed, with the location at which each was created.
+///
+/// Disposing of a scope disposes of its children, so a scope only outlives
+/// its parent if it was not registered as a child, for example if it was
+/// created with the parent’s [`ScopeId`](crate::ScopeId) but without
+/// [`Scope::run_child_scope`]. Its values are never dropped unless its own
+/// disposer is called. Only available in debug mode.
+#[cfg(debug_assertions)]
+pub fn leak_report(cx: Scope) -> Vec<LeakedScope> {
+    with_runtime(cx.runtime, |runtime| {
+        let scopes = runtime.scopes.borrow();
+        let locations = runtime.scope_locations.borrow();
+        runtime
+            .scope_parents
+            .borrow()
+            .iter()
+            .filter(|(id, parent)| {
+                scopes.contains_key(*id) && !scopes.contains_key(**parent)
+            })
+            .map(|(id, parent)| LeakedScope {
+                id,
+                parent: *parent,
+                defined_at: locations.get(id).copied(),
+                owned: scopes.get(id).map_or(0, |owned| owned.borrow().len()),
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
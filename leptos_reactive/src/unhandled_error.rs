@@ -0,0 +1,70 @@
+#![forbid(unsafe_code)]
+use std::{cell::RefCell, fmt, panic::Location, rc::Rc};
+
+/// An error that a resource or action returned, but that nothing read.
+///
+/// A failed server function in a resource that isn’t rendered, or in an
+/// action whose [`value`](https://docs.rs/leptos/latest/leptos/struct.Action.html#method.value)
+/// is never used, would otherwise go unnoticed. These errors are passed to
+/// the hook set with [`set_unhandled_error_hook`], which logs them by default.
+#[derive(Clone)]
+pub struct UnhandledError {
+    /// The error that was returned.
+    pub error: Rc<dyn fmt::Display>,
+    /// Where the resource that returned it was created, or where the resource
+    /// or action was set up to report its errors.
+    pub defined_at: &'static Location<'static>,
+}
+
+impl fmt::Debug for UnhandledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnhandledError")
+            .field("error", &self.error.to_string())
+            .field("defined_at", &self.defined_at)
+            .finish()
+    }
+}
+
+impl fmt::Display for UnhandledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unhandled error from the resource or action at {}: {}",
+            self.defined_at, self.error
+        )
+    }
+}
+
+type Hook = Rc<dyn Fn(UnhandledError)>;
+
+thread_local! {
+    static HOOK: RefCell<Option<Hook>> = RefCell::new(None);
+}
+
+/// Sets the function that is called with every [`UnhandledError`] on this
+/// thread, replacing the default hook, which logs them with `log::error!`.
+///
+/// Use this to send errors that would otherwise be lost to an error
+/// reporting service.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// set_unhandled_error_hook(|err| {
+///     // send it somewhere
+///     eprintln!("{} (at {})", err.error, err.defined_at);
+/// });
+/// ```
+pub fn set_unhandled_error_hook(hook: impl Fn(UnhandledError) + 'static) {
+    HOOK.with(|current| *current.borrow_mut() = Some(Rc::new(hook)));
+}
+
+/// Passes an error to the hook set with [`set_unhandled_error_hook`], or
+/// logs it if no hook has been set.
+pub fn report_unhandled_error(error: UnhandledError) {
+    // the hook is cloned out, so that it can replace itself
+    let hook = HOOK.with(|hook| hook.borrow().clone());
+    match hook {
+        Some(hook) => hook(error),
+        None => log::error!("{error}"),
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Scope, ScopeProperty};
+use crate::{scope::with_owner, Scope, ScopeProperty};
 
 /// A version of [`create_effect`](crate::create_effect) that listens to any dependency that is accessed inside `deps` and returns
 /// a stop handler.
@@ -103,7 +103,8 @@ where
     W: Clone + 'static,
     T: 'static,
 {
-    let (e, stop) = cx.runtime.watch(deps, callback, immediate);
+    let (e, stop) =
+        with_owner(Some(cx), || cx.runtime.watch(deps, callback, immediate));
     let prop = ScopeProperty::Effect(e);
     cx.push_scope_property(prop);
 
@@ -0,0 +1,60 @@
+// async derived signals only compute on the client
+#[cfg(not(feature = "ssr"))]
+use leptos_reactive::{
+    create_async_derived, create_runtime, create_scope, create_signal,
+    SignalGet, SignalSet,
+};
+
+// a future that is pending the first time it is polled
+#[cfg(not(feature = "ssr"))]
+async fn yield_now() {
+    let mut yielded = false;
+    futures::future::poll_fn(|cx| {
+        if yielded {
+            std::task::Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            std::task::Poll::Pending
+        }
+    })
+    .await
+}
+
+#[test]
+fn async_derived_tracks_signals_read_before_first_await() {
+    #[cfg(not(feature = "ssr"))]
+    {
+        use std::{cell::Cell, rc::Rc};
+
+        create_scope(create_runtime(), |cx| {
+            let (a, set_a) = create_signal(cx, 1);
+            let (b, set_b) = create_signal(cx, 10);
+            let runs = Rc::new(Cell::new(0));
+
+            let sum = create_async_derived(cx, {
+                let runs = Rc::clone(&runs);
+                move || {
+                    runs.set(runs.get() + 1);
+                    async move {
+                        let a = a.get();
+                        yield_now().await;
+                        a + b.get()
+                    }
+                }
+            });
+            assert_eq!(sum.get(), Some(11));
+            assert_eq!(runs.get(), 1);
+
+            set_a.set(2);
+            assert_eq!(sum.get(), Some(12));
+            assert_eq!(runs.get(), 2);
+
+            // `b` is only read after the `.await`
+            set_b.set(20);
+            assert_eq!(sum.get(), Some(12));
+            assert_eq!(runs.get(), 2);
+        })
+        .dispose()
+    }
+}
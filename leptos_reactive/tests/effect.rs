@@ -1,6 +1,7 @@
 use leptos_reactive::{
     create_isomorphic_effect, create_memo, create_runtime, create_rw_signal,
-    create_scope, create_signal, SignalGet, SignalSet,
+    create_scope, create_signal, SignalGet, SignalGetUntracked, SignalSet,
+    SignalWith,
 };
 
 #[test]
@@ -129,3 +130,370 @@ fn batching_actually_batches() {
     })
     .dispose()
 }
+
+#[test]
+fn disposed_effect_stops_running() {
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+
+        let effect = create_isomorphic_effect(cx, {
+            let runs = runs.clone();
+            move |_| {
+                a.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        effect.dispose();
+        set_a.set(1);
+        assert_eq!(runs.get(), 1);
+
+        // disposing twice does nothing
+        effect.dispose();
+        assert!(!effect.is_paused());
+    })
+    .dispose()
+}
+
+#[test]
+fn paused_effect_runs_once_when_resumed() {
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+
+        let effect = create_isomorphic_effect(cx, {
+            let seen = seen.clone();
+            move |_| seen.borrow_mut().push(a.get())
+        });
+
+        effect.pause();
+        assert!(effect.is_paused());
+        set_a.set(1);
+        set_a.set(2);
+        assert_eq!(*seen.borrow(), [0]);
+
+        effect.resume();
+        assert!(!effect.is_paused());
+        assert_eq!(*seen.borrow(), [0, 2]);
+
+        // it is still tracking its dependencies
+        set_a.set(3);
+        assert_eq!(*seen.borrow(), [0, 2, 3]);
+
+        // nothing changed while it was paused, so it doesn't run
+        effect.pause();
+        effect.resume();
+        assert_eq!(*seen.borrow(), [0, 2, 3]);
+    })
+    .dispose()
+}
+
+#[cfg(panic = "unwind")]
+#[test]
+fn effect_panic_is_reported_and_effect_stops_running() {
+    use leptos_reactive::set_error_hook;
+    use std::{cell::RefCell, rc::Rc};
+
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    set_error_hook({
+        let errors = errors.clone();
+        move |error| {
+            errors.borrow_mut().push((
+                error.message().map(String::from),
+                error.defined_at().is_some(),
+            ))
+        }
+    });
+
+    create_scope(create_runtime(), move |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let runs = Rc::new(RefCell::new(0));
+        let last = Rc::new(RefCell::new(0));
+
+        create_isomorphic_effect(cx, {
+            let runs = runs.clone();
+            move |_| {
+                *runs.borrow_mut() += 1;
+                if a.get() == 1 {
+                    panic!("bad value");
+                }
+            }
+        });
+        create_isomorphic_effect(cx, {
+            let last = last.clone();
+            move |_| *last.borrow_mut() = a.get()
+        });
+
+        set_a.set(1);
+        assert_eq!(*runs.borrow(), 2);
+        assert_eq!(*last.borrow(), 1);
+        assert_eq!(
+            *errors.borrow(),
+            vec![(Some("bad value".to_string()), cfg!(debug_assertions))]
+        );
+
+        // the runtime is still usable, and the broken effect no longer runs
+        set_a.set(2);
+        assert_eq!(*runs.borrow(), 2);
+        assert_eq!(*last.borrow(), 2);
+        assert_eq!(errors.borrow().len(), 1);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn after_render_callbacks_run_after_render_effects() {
+    use leptos_reactive::{create_render_effect, queue_after_render};
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        create_render_effect(cx, {
+            let log = Rc::clone(&log);
+            move |_| log.borrow_mut().push(format!("render {}", a.get()))
+        });
+
+        cx.batch(|| {
+            queue_after_render(cx, {
+                let log = Rc::clone(&log);
+                move || log.borrow_mut().push("after render".to_string())
+            });
+            set_a.set(1);
+            set_a.set(2);
+        });
+
+        assert_eq!(*log.borrow(), ["render 0", "render 2", "after render"]);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn memoized_effect_only_runs_when_value_changes() {
+    use leptos_reactive::create_memoized_effect;
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 1);
+        let (unrelated, set_unrelated) = create_signal(cx, 0);
+        let runs = Rc::new(RefCell::new(Vec::new()));
+
+        create_memoized_effect(cx, move || count.get() % 2 == 0, {
+            let runs = Rc::clone(&runs);
+            move |is_even, was_even| {
+                // reading a signal in the side effect doesn't subscribe to it
+                _ = unrelated.get();
+                runs.borrow_mut().push((*is_even, was_even.copied()));
+            }
+        });
+        assert_eq!(*runs.borrow(), [(false, None)]);
+
+        // the derived value doesn't change
+        set_count.set(3);
+        set_count.set(5);
+        set_unrelated.set(1);
+        assert_eq!(runs.borrow().len(), 1);
+
+        set_count.set(6);
+        assert_eq!(*runs.borrow(), [(false, None), (true, Some(false))]);
+
+        set_count.set(8);
+        assert_eq!(runs.borrow().len(), 2);
+    })
+    .dispose()
+}
+
+#[test]
+fn effect_writing_its_own_dependency_does_not_rerun_itself() {
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let count = create_rw_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+
+        // warns in debug builds, but doesn't loop
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                if count.get() % 2 == 1 {
+                    count.set(count.get() + 1);
+                }
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        count.set(1);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(count.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn render_effects_that_queue_each_other_in_a_cycle_are_stopped() {
+    use leptos_reactive::create_render_effect;
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let a = create_rw_signal(cx, 0);
+        let b = create_rw_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+
+        create_render_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                b.set(a.get() + 1);
+            }
+        });
+        create_render_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                a.set(b.get() + 1);
+            }
+        });
+
+        // the effects are deferred to later flushes, until they give up
+        // instead of running forever
+        let runs_before = runs.get();
+        a.set(100);
+        let runs = runs.get() - runs_before;
+        assert!(runs > 2 * 100, "{runs} runs");
+        assert!(runs <= 2 * 100 * 100 + 2, "{runs} runs");
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn render_effects_that_settle_after_many_passes_are_not_dropped() {
+    use leptos_reactive::create_render_effect;
+
+    create_scope(create_runtime(), |cx| {
+        let a = create_rw_signal(cx, 0);
+        let b = create_rw_signal(cx, 0);
+
+        // each pass queues the other effect again, until they reach 250
+        create_render_effect(cx, move |_| {
+            let b = b.get();
+            if b < 250 {
+                a.set(b + 1);
+            }
+        });
+        create_render_effect(cx, move |_| {
+            let a = a.get();
+            if a < 250 {
+                b.set(a + 1);
+            }
+        });
+
+        a.set(1);
+        assert_eq!(a.get_untracked().max(b.get_untracked()), 250);
+    })
+    .dispose()
+}
+
+#[test]
+fn effects_that_run_each_other_in_a_cycle_are_stopped() {
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let a = create_rw_signal(cx, 0);
+        let b = create_rw_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                b.set(a.get() + 1);
+            }
+        });
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                a.set(b.get() + 1);
+            }
+        });
+
+        // without overflowing the stack
+        let runs_before = runs.get();
+        a.set(100);
+        let runs = runs.get() - runs_before;
+        assert!(runs > 100, "{runs} runs");
+        assert!(runs <= 100 * 100 + 2, "{runs} runs");
+    })
+    .dispose()
+}
+
+#[test]
+fn memos_that_write_to_their_effects_dependencies_are_stopped() {
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let a = create_rw_signal(cx, 0);
+        let b = create_rw_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+
+        // the effects the memo notifies run once it has been computed,
+        // rather than reading it while it is being computed
+        let next = create_memo(cx, move |_| {
+            let next = a.get() + 1;
+            b.set(next);
+            next
+        });
+        create_isomorphic_effect(cx, move |_| next.track());
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                a.set(b.get() + 1);
+            }
+        });
+
+        let runs_before = runs.get();
+        a.set(100);
+        let runs = runs.get() - runs_before;
+        assert!(runs > 1, "{runs} runs");
+        assert!(runs <= 100 * 100 + 2, "{runs} runs");
+    })
+    .dispose()
+}
+
+#[test]
+fn effects_that_settle_after_running_each_other_deeply_are_not_dropped() {
+    create_scope(create_runtime(), |cx| {
+        let a = create_rw_signal(cx, 0);
+        let b = create_rw_signal(cx, 0);
+
+        create_isomorphic_effect(cx, move |_| {
+            let b = b.get();
+            if b < 250 {
+                a.set(b + 1);
+            }
+        });
+        create_isomorphic_effect(cx, move |_| {
+            let a = a.get();
+            if a < 250 {
+                b.set(a + 1);
+            }
+        });
+
+        a.set(1);
+        assert_eq!(a.get_untracked().max(b.get_untracked()), 250);
+    })
+    .dispose()
+}
@@ -0,0 +1,112 @@
+use leptos_reactive::*;
+use std::time::Duration;
+
+#[test]
+fn history_signal_undoes_and_redoes_writes() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let (count, history) =
+        create_history_signal(cx, 0, HistoryOptions::default());
+
+    count.set(1);
+    count.set(2);
+    count.set(3);
+    assert!(history.can_undo().get());
+    assert!(!history.can_redo().get());
+
+    history.undo();
+    history.undo();
+    assert_eq!(count.get(), 1);
+    assert!(history.can_redo().get());
+
+    // undoing and redoing adds no entries
+    history.redo();
+    assert_eq!(count.get(), 2);
+    history.undo();
+    history.undo();
+    assert_eq!(count.get(), 0);
+    assert!(!history.can_undo().get());
+    history.undo();
+    assert_eq!(count.get(), 0);
+
+    // writing forgets what could have been redone
+    history.redo();
+    count.set(10);
+    assert!(!history.can_redo().get());
+    history.undo();
+    assert_eq!(count.get(), 1);
+
+    disposer.dispose();
+}
+
+#[test]
+fn history_signal_merges_writes_within_the_merge_window() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let (text, history) = create_history_signal(
+        cx,
+        String::new(),
+        HistoryOptions {
+            merge_window: Duration::from_secs(60),
+            ..Default::default()
+        },
+    );
+
+    text.set("H".to_string());
+    text.set("Hi".to_string());
+    history.checkpoint();
+    text.set("Hi!".to_string());
+    text.set("Hi!!".to_string());
+
+    history.undo();
+    assert_eq!(text.get(), "Hi");
+    history.undo();
+    assert_eq!(text.get(), "");
+    assert!(!history.can_undo().get());
+
+    disposer.dispose();
+}
+
+#[test]
+fn history_signal_records_paused_writes_as_one_entry() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let (position, history) =
+        create_history_signal(cx, (0, 0), HistoryOptions::default());
+
+    history.pause_recording();
+    for x in 1..=10 {
+        position.set((x, x));
+    }
+    assert!(!history.can_undo().get());
+    history.resume();
+    assert!(history.can_undo().get());
+
+    history.undo();
+    assert_eq!(position.get(), (0, 0));
+    assert!(!history.can_undo().get());
+    history.redo();
+    assert_eq!(position.get(), (10, 10));
+
+    disposer.dispose();
+}
+
+#[test]
+fn history_signal_forgets_the_oldest_entries_past_its_capacity() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let (count, history) = create_history_signal(
+        cx,
+        0,
+        HistoryOptions {
+            capacity: 2,
+            ..Default::default()
+        },
+    );
+
+    for n in 1..=5 {
+        count.set(n);
+    }
+    history.undo();
+    history.undo();
+    assert_eq!(count.get(), 3);
+    assert!(!history.can_undo().get());
+
+    disposer.dispose();
+}
@@ -204,3 +204,31 @@ fn dynamic_dependencies() {
     })
     .dispose()
 }
+
+#[test]
+fn effect_does_not_rerun_when_memo_value_is_unchanged() {
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (pair, set_pair) = create_signal(cx, (1, 1));
+        let first = create_memo(cx, move |_| pair.with(|pair| pair.0));
+
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                first.track();
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        // the first change after the memo is created is cut off, too
+        set_pair.set((1, 2));
+        assert_eq!(runs.get(), 1);
+
+        set_pair.set((2, 2));
+        assert_eq!(runs.get(), 2);
+    })
+    .dispose()
+}
@@ -48,14 +48,820 @@ fn resource_returns_last_future() {
                 task::yield_now().await;
 
                 // Resource should still be loading
-                assert_eq!(resource.read(cx), None);
+                assert_eq!(resource.get(), None);
 
                 // Resolve second future
                 tx_2.send(()).unwrap();
                 task::yield_now().await;
 
                 // Resource should now be loaded
-                assert_eq!(resource.read(cx), Some(2));
+                assert_eq!(resource.get(), Some(2));
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+        }));
+    }
+}
+
+#[test]
+fn cached_resource_reuses_values_for_previous_sources() {
+    #[cfg(feature = "ssr")]
+    {
+        use leptos_reactive::{
+            create_resource_with_cache, create_runtime, create_signal,
+            raw_scope_and_disposer, CachePolicy, SignalGet, SignalSet,
+        };
+        use std::{cell::Cell, rc::Rc};
+        use tokio::task;
+        use tokio_test::block_on;
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            task::spawn_local(async move {
+                let fetches = Rc::new(Cell::new(0));
+                let (id, set_id) = create_signal(cx, 1);
+
+                let resource = create_resource_with_cache(
+                    cx,
+                    move || id.get(),
+                    {
+                        let fetches = Rc::clone(&fetches);
+                        move |id| {
+                            fetches.set(fetches.get() + 1);
+                            async move { id * 10 }
+                        }
+                    },
+                    CachePolicy {
+                        max_entries: 1,
+                        stale_after: None,
+                    },
+                );
+                task::yield_now().await;
+                assert_eq!(resource.get(), Some(10));
+
+                set_id.set(2);
+                task::yield_now().await;
+                assert_eq!(resource.get(), Some(20));
+                assert_eq!(fetches.get(), 2);
+
+                // `1` was evicted, because only one entry is kept
+                set_id.set(1);
+                task::yield_now().await;
+                assert_eq!(fetches.get(), 3);
+
+                // `1` is cached now, so it is returned without fetching
+                set_id.set(2);
+                set_id.set(1);
+                assert_eq!(resource.get(), Some(10));
+                assert!(!resource.loading().get());
+                assert!(!resource.refreshing().get());
+                assert_eq!(fetches.get(), 4);
+
+                // refetching bypasses the cache
+                resource.refetch();
+                task::yield_now().await;
+                assert_eq!(fetches.get(), 5);
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+        }));
+    }
+}
+
+#[test]
+fn superseded_revalidations_stop_refreshing() {
+    #[cfg(feature = "ssr")]
+    {
+        use futures::channel::oneshot;
+        use leptos_reactive::{
+            create_resource_with_cache, create_runtime, create_signal,
+            raw_scope_and_disposer, CachePolicy, SignalGet, SignalSet,
+        };
+        use std::{cell::RefCell, rc::Rc, time::Duration};
+        use tokio::task;
+        use tokio_test::block_on;
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            task::spawn_local(async move {
+                // each fetch waits until its sender is used
+                let pending = Rc::new(RefCell::new(Vec::new()));
+                let (id, set_id) = create_signal(cx, 1);
+
+                let resource = create_resource_with_cache(
+                    cx,
+                    move || id.get(),
+                    {
+                        let pending = Rc::clone(&pending);
+                        move |id| {
+                            let (tx, rx) = oneshot::channel::<i32>();
+                            pending.borrow_mut().push(tx);
+                            async move { id * 10 + rx.await.unwrap() }
+                        }
+                    },
+                    // every cached value is stale at once
+                    CachePolicy {
+                        max_entries: 2,
+                        stale_after: Some(Duration::ZERO),
+                    },
+                );
+                let resolve = |n: i32| {
+                    pending.borrow_mut().remove(0).send(n).unwrap();
+                };
+
+                resolve(0);
+                task::yield_now().await;
+                set_id.set(2);
+                resolve(0);
+                task::yield_now().await;
+                assert_eq!(resource.get(), Some(20));
+
+                // `1` is stale, so it is shown while being revalidated
+                set_id.set(1);
+                assert_eq!(resource.get(), Some(10));
+                assert!(resource.refreshing().get());
+                task::yield_now().await;
+
+                // a refetch supersedes the revalidation
+                resource.refetch();
+                assert!(resource.loading().get());
+                resolve(1);
+                task::yield_now().await;
+                resolve(2);
+                task::yield_now().await;
+
+                assert_eq!(resource.get(), Some(12));
+                assert!(!resource.loading().get());
+                assert!(!resource.refreshing().get());
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+        }));
+    }
+}
+
+#[test]
+fn query_resources_share_requests_until_invalidated() {
+    #[cfg(feature = "ssr")]
+    {
+        use leptos_reactive::{
+            create_query_resource, create_runtime, invalidate_query,
+            raw_scope_and_disposer,
+        };
+        use std::{cell::Cell, rc::Rc};
+        use tokio::task;
+        use tokio_test::block_on;
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            task::spawn_local(async move {
+                let fetches = Rc::new(Cell::new(0));
+                let fetcher = {
+                    let fetches = Rc::clone(&fetches);
+                    move |_| {
+                        fetches.set(fetches.get() + 1);
+                        let count = fetches.get();
+                        async move { count }
+                    }
+                };
+
+                let a =
+                    create_query_resource(cx, "count", || (), fetcher.clone());
+                let b =
+                    create_query_resource(cx, "count", || (), fetcher.clone());
+                task::yield_now().await;
+                assert_eq!(fetches.get(), 1);
+                assert_eq!(a.get(), Some(1));
+                assert_eq!(b.get(), Some(1));
+
+                // a later resource reuses the cached result
+                let c = create_query_resource(cx, "count", || (), fetcher);
+                task::yield_now().await;
+                assert_eq!(fetches.get(), 1);
+                assert_eq!(c.get(), Some(1));
+
+                invalidate_query(cx, "count");
+                task::yield_now().await;
+                assert_eq!(fetches.get(), 2);
+                assert_eq!(a.get(), Some(2));
+                assert_eq!(b.get(), Some(2));
+                assert_eq!(c.get(), Some(2));
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+        }));
+    }
+}
+
+#[test]
+fn dispose_async_waits_for_loads_to_be_aborted() {
+    #[cfg(feature = "ssr")]
+    {
+        use leptos_reactive::{
+            create_resource, create_runtime, raw_scope_and_disposer,
+        };
+        use std::{cell::Cell, rc::Rc};
+        use tokio::task;
+        use tokio_test::block_on;
+
+        // sets the flag when the load's future is dropped
+        struct DropFlag(Rc<Cell<bool>>);
+
+        impl Drop for DropFlag {
+            fn drop(&mut self) {
+                self.0.set(true);
+            }
+        }
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            let dropped = Rc::new(Cell::new(false));
+
+            create_resource(cx, || (), {
+                let dropped = Rc::clone(&dropped);
+                move |_| {
+                    let flag = DropFlag(Rc::clone(&dropped));
+                    async move {
+                        let _flag = flag;
+                        futures::future::pending::<()>().await;
+                    }
+                }
+            });
+            task::yield_now().await;
+            assert!(!dropped.get());
+
+            disposer.dispose_async().await;
+            assert!(dropped.get());
+        }));
+    }
+}
+
+#[test]
+fn chained_resources_fetch_once_their_source_is_ready() {
+    #[cfg(feature = "ssr")]
+    {
+        use futures::{channel::oneshot::channel, FutureExt};
+        use leptos_reactive::{
+            create_chained_resource, create_resource, create_runtime,
+            join_resources, provide_context, raw_scope_and_disposer, SignalGet,
+            SuspenseContext,
+        };
+        use std::{cell::Cell, rc::Rc};
+        use tokio::task;
+        use tokio_test::block_on;
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            task::spawn_local(async move {
+                let suspense = SuspenseContext::new(cx);
+                provide_context(cx, suspense);
+
+                let (tx_user, rx_user) = channel::<()>();
+                let (tx_settings, rx_settings) = channel::<()>();
+                let rx_user = rx_user.shared();
+                let rx_settings = rx_settings.shared();
+
+                let user = create_resource(
+                    cx,
+                    || (),
+                    move |_| {
+                        let rx = rx_user.clone();
+                        async move {
+                            rx.await.unwrap();
+                            "user".to_string()
+                        }
+                    },
+                );
+                let settings = create_resource(
+                    cx,
+                    || (),
+                    move |_| {
+                        let rx = rx_settings.clone();
+                        async move {
+                            rx.await.unwrap();
+                            42
+                        }
+                    },
+                );
+
+                let fetches = Rc::new(Cell::new(0));
+                let both = join_resources(cx, (user, settings));
+                let dashboard =
+                    create_chained_resource(cx, move || both.get(), {
+                        let fetches = Rc::clone(&fetches);
+                        move |(user, settings)| {
+                            fetches.set(fetches.get() + 1);
+                            async move { format!("{user}: {settings}") }
+                        }
+                    });
+
+                // a chained resource that is waiting counts as loading, along
+                // with the resources it is waiting for; this future doesn't
+                // belong to a scope, so it reads it in one, like a component
+                let (value, _) = cx.run_child_scope(|_| dashboard.get());
+                assert_eq!(value, None);
+                assert!(dashboard.loading().get());
+                assert_eq!(suspense.pending_resources.get(), 3);
+
+                tx_user.send(()).unwrap();
+                task::yield_now().await;
+                assert_eq!(fetches.get(), 0);
+
+                tx_settings.send(()).unwrap();
+                task::yield_now().await;
+                task::yield_now().await;
+                assert_eq!(fetches.get(), 1);
+                assert_eq!(dashboard.get(), Some("user: 42".to_string()));
+                assert!(!dashboard.loading().get());
+                assert_eq!(suspense.pending_resources.get(), 0);
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+        }));
+    }
+}
+
+#[test]
+fn chained_resources_that_never_become_ready_are_serialized_as_skipped() {
+    #[cfg(feature = "ssr")]
+    {
+        use leptos_reactive::{
+            create_chained_resource, create_resource, create_runtime,
+            raw_scope_and_disposer,
+        };
+        use std::{cell::Cell, rc::Rc};
+        use tokio::task;
+        use tokio_test::block_on;
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            task::spawn_local(async move {
+                let fetches = Rc::new(Cell::new(0));
+                let user =
+                    create_resource(cx, || (), |_| async { None::<u32> });
+                let posts = create_chained_resource(
+                    cx,
+                    // this user has no ID, so their posts are never loaded
+                    move || user.get().flatten(),
+                    {
+                        let fetches = Rc::clone(&fetches);
+                        move |id| {
+                            fetches.set(fetches.get() + 1);
+                            async move { vec![id] }
+                        }
+                    },
+                );
+
+                let (_, data) = posts.to_serialization_resolver(cx).await;
+                assert_eq!(data, "__LEPTOS_SKIPPED_RESOURCE");
+                assert_eq!(fetches.get(), 0);
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+        }));
+    }
+}
+
+#[test]
+fn chained_resources_that_never_become_ready_stop_suspending() {
+    #[cfg(feature = "ssr")]
+    {
+        use futures::{channel::oneshot::channel, FutureExt};
+        use leptos_reactive::{
+            create_chained_resource, create_resource, create_runtime,
+            provide_context, raw_scope_and_disposer, SignalGet,
+            SuspenseContext,
+        };
+        use tokio::task;
+        use tokio_test::block_on;
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            task::spawn_local(async move {
+                let suspense = SuspenseContext::new(cx);
+                provide_context(cx, suspense);
+
+                let (tx, rx) = channel::<()>();
+                let rx = rx.shared();
+                let user = create_resource(
+                    cx,
+                    || (),
+                    move |_| {
+                        let rx = rx.clone();
+                        async move {
+                            rx.await.unwrap();
+                            None::<u32>
+                        }
+                    },
+                );
+                let posts = create_chained_resource(
+                    cx,
+                    move || user.get().flatten(),
+                    |id| async move { vec![id] },
+                );
+
+                // read under the `<Suspense/>`, like a component would; the
+                // user it is waiting for counts as loading too
+                let (value, _) = cx.run_child_scope(|_| posts.get());
+                assert_eq!(value, None);
+                task::yield_now().await;
+                assert_eq!(suspense.pending_resources.get(), 2);
+
+                // the user has no ID, so the posts are never fetched
+                tx.send(()).unwrap();
+                task::yield_now().await;
+                task::yield_now().await;
+                assert!(!posts.loading().get());
+                assert_eq!(posts.get(), None);
+                assert_eq!(suspense.pending_resources.get(), 0);
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+        }));
+    }
+}
+
+#[test]
+fn resource_reads_register_with_the_suspense_of_the_current_scope() {
+    #[cfg(feature = "ssr")]
+    {
+        use futures::{channel::oneshot::channel, FutureExt};
+        use leptos_reactive::{
+            create_isomorphic_effect, create_resource, create_runtime,
+            create_rw_signal, provide_context, raw_scope_and_disposer,
+            spawn_local, SignalGet, SignalSet, SuspenseContext,
+        };
+        use tokio::task;
+        use tokio_test::block_on;
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            task::spawn_local(async move {
+                let suspense = SuspenseContext::new(cx);
+                provide_context(cx, suspense);
+
+                let (tx, rx) = channel::<()>();
+                let rx = rx.shared();
+                let resource = create_resource(
+                    cx,
+                    || (),
+                    move |_| {
+                        let rx = rx.clone();
+                        async move {
+                            rx.await.unwrap();
+                            1
+                        }
+                    },
+                );
+
+                // reads in untracked functions and in spawned futures don't
+                // belong to any scope, so they are not registered
+                _ = cx.run_child_scope(|cx| {
+                    assert_eq!(cx.untrack(|| resource.get()), None);
+                    spawn_local(async move {
+                        assert_eq!(resource.get(), None);
+                    });
+                });
+                task::yield_now().await;
+                assert_eq!(suspense.pending_resources.get(), 0);
+
+                // a read in an effect is registered with the suspense of the
+                // scope the effect was created in
+                let (value, _) = cx.run_child_scope(|cx| {
+                    let value = create_rw_signal(cx, None);
+                    create_isomorphic_effect(cx, move |_| {
+                        value.set(resource.get())
+                    });
+                    value
+                });
+                assert_eq!(value.get(), None);
+                assert_eq!(suspense.pending_resources.get(), 1);
+
+                tx.send(()).unwrap();
+                task::yield_now().await;
+                assert_eq!(value.get(), Some(1));
+                assert_eq!(suspense.pending_resources.get(), 0);
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+        }));
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_reads_register_with_the_suspense_of_the_given_scope() {
+    #[cfg(feature = "ssr")]
+    {
+        use futures::{channel::oneshot::channel, FutureExt};
+        use leptos_reactive::{
+            create_resource, create_runtime, provide_context,
+            raw_scope_and_disposer, SignalGet, SuspenseContext,
+        };
+        use tokio::task;
+        use tokio_test::block_on;
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            task::spawn_local(async move {
+                let suspense = SuspenseContext::new(cx);
+                provide_context(cx, suspense);
+
+                let (tx, rx) = channel::<()>();
+                let rx = rx.shared();
+                let resource = create_resource(
+                    cx,
+                    || (),
+                    move |_| {
+                        let rx = rx.clone();
+                        async move {
+                            rx.await.unwrap();
+                            1
+                        }
+                    },
+                );
+
+                // the scope is given, so even an untracked read is registered
+                assert_eq!(cx.untrack(|| resource.read(cx)), None);
+                assert_eq!(suspense.pending_resources.get(), 1);
+
+                tx.send(()).unwrap();
+                task::yield_now().await;
+                assert_eq!(resource.read(cx), Some(1));
+                assert_eq!(resource.with(cx, |n| n * 2), Some(2));
+                assert_eq!(suspense.pending_resources.get(), 0);
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+        }));
+    }
+}
+
+#[test]
+fn resources_can_be_read_through_the_signal_traits() {
+    #[cfg(feature = "ssr")]
+    {
+        use leptos_reactive::{
+            create_resource, create_runtime, raw_scope_and_disposer,
+            SignalDispose, SignalGet, SignalSet, SignalWith,
+        };
+        use tokio::task;
+        use tokio_test::block_on;
+
+        fn is_ready<T>(value: &impl SignalWith<Option<T>>) -> bool {
+            value.with(Option::is_some)
+        }
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            task::spawn_local(async move {
+                let resource = create_resource(
+                    cx,
+                    || (),
+                    |_| futures::future::pending::<i32>(),
+                );
+                assert!(!is_ready(&resource));
+                assert_eq!(SignalGet::get(&resource), None);
+                assert_eq!(SignalGet::try_get(&resource), Some(None));
+
+                resource.set(2);
+                assert!(is_ready(&resource));
+                assert_eq!(SignalGet::get(&resource), Some(2));
+
+                resource.dispose();
+                assert_eq!(SignalGet::try_get(&resource), None);
+                assert_eq!(SignalWith::try_with(&resource, |_| ()), None);
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+        }));
+    }
+}
+
+#[test]
+fn resource_timings_record_each_stage_of_a_resource() {
+    #[cfg(all(feature = "ssr", feature = "debug-timing"))]
+    {
+        use leptos_reactive::{
+            create_resource, create_runtime, raw_scope_and_disposer,
+            resource_timings,
+        };
+        use tokio::task;
+        use tokio_test::block_on;
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            let timings = resource_timings(cx);
+            let timings_in_task = timings.clone();
+            task::spawn_local(async move {
+                let timings = timings_in_task;
+                let user = create_resource(cx, || (), |_| async { 1 });
+                let started = timings.get();
+                assert_eq!(started.len(), 1);
+                assert!(started[0].ty.contains("i32"));
+                assert!(started[0].defined_at.file().ends_with("resource.rs"));
+                assert!(started[0].fetch_started_at.is_some());
+                assert_eq!(started[0].serialized_at, None);
+
+                user.to_serialization_resolver(cx).await;
+                let timing = &timings.get()[0];
+                let fetch_ended = timing.fetch_ended_at.unwrap();
+                assert!(fetch_ended >= timing.fetch_started_at.unwrap());
+                assert!(timing.serialized_at.unwrap() >= fetch_ended);
+                assert!(timings
+                    .to_html_comment()
+                    .starts_with("<!-- resource timings"));
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+            runtime.dispose();
+
+            // the timings can still be read once the runtime is gone
+            assert_eq!(timings.get().len(), 1);
+        }));
+    }
+}
+
+#[test]
+fn client_resource_timings_keep_only_the_latest_resources() {
+    #[cfg(all(not(feature = "ssr"), feature = "debug-timing"))]
+    {
+        use leptos_reactive::{
+            create_local_resource, create_runtime, create_scope,
+            resource_timings,
+        };
+
+        _ = create_scope(create_runtime(), |cx| {
+            let timings = resource_timings(cx);
+            let first = create_local_resource(cx, || (), |_| async { 1 });
+            for _ in 0..1000 {
+                create_local_resource(cx, || (), |_| async {});
+            }
+            let kept = timings.get();
+            assert_eq!(kept.len(), 1000);
+            assert!(kept.iter().all(|timing| timing.ty == "()"));
+
+            // a resource whose timing was dropped still refetches
+            first.refetch();
+            assert_eq!(timings.get(), kept);
+        });
+    }
+}
+
+#[cfg(feature = "ssr")]
+mod paged {
+    use futures::{
+        channel::oneshot::{channel, Sender},
+        future::LocalBoxFuture,
+        FutureExt,
+    };
+    use leptos_reactive::Page;
+    use std::{cell::RefCell, rc::Rc};
+    use tokio::task;
+
+    type Requests = Rc<RefCell<Vec<((u32, usize), Option<Sender<()>>)>>>;
+
+    // a fetcher whose pages load once they are resolved by the test
+    pub fn fetcher(
+        requests: &Requests,
+    ) -> impl Fn(u32, usize) -> LocalBoxFuture<'static, Page<u32>> {
+        let requests = Rc::clone(requests);
+        move |filter, page| {
+            let (tx, rx) = channel::<()>();
+            requests.borrow_mut().push(((filter, page), Some(tx)));
+            async move {
+                rx.await.unwrap();
+                Page {
+                    items: vec![filter * 10 + page as u32],
+                    has_more: page < 2,
+                }
+            }
+            .boxed_local()
+        }
+    }
+
+    pub async fn resolve(requests: &Requests, request: (u32, usize)) {
+        let tx = requests
+            .borrow_mut()
+            .iter_mut()
+            .find(|(key, _)| *key == request)
+            .and_then(|(_, tx)| tx.take())
+            .expect("the page to have been requested");
+        tx.send(()).unwrap();
+        for _ in 0..3 {
+            task::yield_now().await;
+        }
+    }
+
+    pub fn requested(requests: &Requests) -> Vec<(u32, usize)> {
+        requests.borrow().iter().map(|(key, _)| *key).collect()
+    }
+}
+
+#[test]
+fn paged_resources_load_each_page_once() {
+    #[cfg(feature = "ssr")]
+    {
+        use leptos_reactive::{
+            create_paged_resource, create_runtime, raw_scope_and_disposer,
+            SignalGet,
+        };
+        use paged::*;
+        use tokio::task;
+        use tokio_test::block_on;
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            task::spawn_local(async move {
+                let requests = Default::default();
+                let posts = create_paged_resource(cx, || 1, fetcher(&requests));
+                task::yield_now().await;
+                assert_eq!(posts.pages().get(), Vec::<u32>::new());
+                assert!(!posts.has_more().get());
+
+                // the next page waits for the first one
+                posts.load_next();
+                resolve(&requests, (1, 0)).await;
+                assert_eq!(posts.pages().get(), vec![10]);
+                assert!(posts.has_more().get());
+
+                // calls made while a page is loading are ignored
+                posts.load_next();
+                posts.load_next();
+                assert!(posts.is_loading_next().get());
+                resolve(&requests, (1, 1)).await;
+                assert!(!posts.is_loading_next().get());
+                assert_eq!(posts.pages().get(), vec![10, 11]);
+
+                posts.load_next();
+                resolve(&requests, (1, 2)).await;
+                assert_eq!(posts.pages().get(), vec![10, 11, 12]);
+                assert!(!posts.has_more().get());
+
+                // there are no more pages
+                posts.load_next();
+                assert_eq!(requested(&requests), [(1, 0), (1, 1), (1, 2)]);
+            })
+            .await
+            .unwrap();
+            disposer.dispose();
+        }));
+    }
+}
+
+#[test]
+fn paged_resources_start_over_when_their_source_changes() {
+    #[cfg(feature = "ssr")]
+    {
+        use leptos_reactive::{
+            create_paged_resource, create_runtime, create_signal,
+            raw_scope_and_disposer, SignalGet, SignalSet,
+        };
+        use paged::*;
+        use tokio::task;
+        use tokio_test::block_on;
+
+        block_on(task::LocalSet::new().run_until(async move {
+            let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+            task::spawn_local(async move {
+                let requests = Default::default();
+                let (filter, set_filter) = create_signal(cx, 1);
+                let posts = create_paged_resource(
+                    cx,
+                    move || filter.get(),
+                    fetcher(&requests),
+                );
+                task::yield_now().await;
+                resolve(&requests, (1, 0)).await;
+                posts.load_next();
+                assert!(posts.is_loading_next().get());
+
+                // the page that is loading for the previous filter is dropped
+                set_filter.set(2);
+                assert!(!posts.is_loading_next().get());
+                resolve(&requests, (1, 1)).await;
+                resolve(&requests, (2, 0)).await;
+                assert_eq!(posts.pages().get(), vec![20]);
+
+                posts.load_next();
+                resolve(&requests, (2, 1)).await;
+                assert_eq!(posts.pages().get(), vec![20, 21]);
+                assert_eq!(
+                    requested(&requests),
+                    [(1, 0), (1, 1), (2, 0), (2, 1)]
+                );
             })
             .await
             .unwrap();
@@ -0,0 +1,266 @@
+use leptos_reactive::{
+    create_isomorphic_effect, create_memo, create_runtime, create_scope,
+    create_signal, dispose_queued_scopes, on_cleanup, provide_context,
+    runtime_stats, store_value, use_context, Scope, SignalGet,
+    SignalGetUntracked,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+#[test]
+fn scopes_can_be_disposed_from_other_threads() {
+    let runtime = create_runtime();
+    let cleaned_up = Arc::new(AtomicBool::new(false));
+
+    let disposer = create_scope(runtime, {
+        let cleaned_up = Arc::clone(&cleaned_up);
+        move |cx| {
+            on_cleanup(cx, move || cleaned_up.store(true, Ordering::SeqCst));
+        }
+    })
+    .into_send();
+
+    // disposing from another thread only queues the scope...
+    std::thread::spawn(move || disposer.dispose_on_owner_thread())
+        .join()
+        .unwrap();
+    assert!(!cleaned_up.load(Ordering::SeqCst));
+
+    // ...which is then disposed of on the thread that owns it
+    dispose_queued_scopes();
+    assert!(cleaned_up.load(Ordering::SeqCst));
+
+    runtime.dispose();
+}
+
+#[test]
+fn runtime_stats_count_live_values() {
+    let runtime = create_runtime();
+
+    _ = create_scope(runtime, |cx| {
+        let before = runtime_stats(cx);
+        let disposers = (0..3)
+            .map(|_| {
+                cx.child_scope(|cx| {
+                    _ = create_signal(cx, 0);
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let during = runtime_stats(cx);
+        assert_eq!(during.scopes, before.scopes + 3);
+        assert_eq!(during.signals, before.signals + 3);
+
+        for disposer in disposers {
+            disposer.dispose();
+        }
+        assert_eq!(runtime_stats(cx), before);
+    });
+
+    runtime.dispose();
+}
+
+#[test]
+fn run_child_scope_returns_value_and_is_disposed_with_parent() {
+    let runtime = create_runtime();
+
+    _ = create_scope(runtime, |cx| {
+        let before = runtime_stats(cx);
+        let cleanups = Rc::new(Cell::new(0));
+
+        let (parent, parent_disposer) = cx.run_child_scope(|cx| cx);
+        let (count, child_disposer) = parent.run_child_scope({
+            let cleanups = Rc::clone(&cleanups);
+            move |cx| {
+                on_cleanup(cx, move || cleanups.set(cleanups.get() + 1));
+                let (count, _) = create_signal(cx, 1);
+                count
+            }
+        });
+        assert_eq!(count.get_untracked(), 1);
+        assert_eq!(runtime_stats(cx).scopes, before.scopes + 2);
+
+        // disposing the parent first disposes the child...
+        parent_disposer.dispose();
+        assert_eq!(cleanups.get(), 1);
+        assert_eq!(count.try_get_untracked(), None);
+        assert_eq!(runtime_stats(cx), before);
+
+        // ...after which the child's own disposer does nothing
+        child_disposer.dispose();
+        assert_eq!(cleanups.get(), 1);
+        assert_eq!(runtime_stats(cx), before);
+    });
+
+    runtime.dispose();
+}
+
+#[test]
+fn disposing_child_scope_before_parent_runs_cleanups_once() {
+    let runtime = create_runtime();
+
+    _ = create_scope(runtime, |cx| {
+        let cleanups = Rc::new(Cell::new(0));
+        let (parent, parent_disposer) = cx.run_child_scope(|cx| cx);
+        let (_, child_disposer) = parent.run_child_scope({
+            let cleanups = Rc::clone(&cleanups);
+            move |cx| on_cleanup(cx, move || cleanups.set(cleanups.get() + 1))
+        });
+
+        child_disposer.dispose();
+        assert_eq!(cleanups.get(), 1);
+        parent_disposer.dispose();
+        assert_eq!(cleanups.get(), 1);
+    });
+
+    runtime.dispose();
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn leak_report_lists_scopes_that_outlive_their_parent() {
+    use leptos_reactive::leak_report;
+
+    let runtime = create_runtime();
+
+    _ = create_scope(runtime, |cx| {
+        let (child, disposer) = cx.run_child_scope(|cx| cx);
+        disposer.dispose();
+        assert!(leak_report(cx).is_empty());
+
+        // e.g., a callback that runs after its component has been unmounted
+        let line = line!() + 1;
+        _ = child.child_scope(|cx| {
+            _ = create_signal(cx, 0);
+        });
+
+        let leaks = leak_report(cx);
+        assert_eq!(leaks.len(), 1);
+        assert_eq!(leaks[0].parent, child.id());
+        assert_eq!(leaks[0].owned, 1);
+        let defined_at = leaks[0].defined_at.unwrap();
+        assert_eq!(defined_at.file(), file!());
+        assert_eq!(defined_at.line(), line);
+    });
+
+    runtime.dispose();
+}
+
+// creates a scope under `parent` that owns one of each kind of value, and
+// logs its name when it is cleaned up
+fn named_child_scope(
+    parent: Scope,
+    name: &'static str,
+    log: &Rc<RefCell<Vec<String>>>,
+) -> Scope {
+    let log = Rc::clone(log);
+    let (cx, _) = parent.run_child_scope(move |cx| {
+        let (count, _) = create_signal(cx, 0);
+        let double = create_memo(cx, move |_| count.get() * 2);
+        create_isomorphic_effect(cx, move |_| double.get());
+        store_value(cx, name);
+        provide_context(cx, name);
+        on_cleanup(cx, move || {
+            // the scope's own values, and its parent's, are still there
+            let context = use_context::<&'static str>(cx);
+            log.borrow_mut()
+                .push(format!("{name} {}", context.unwrap_or("gone")));
+        });
+        cx
+    });
+    cx
+}
+
+#[test]
+fn dispose_cleans_up_whole_tree_children_first() {
+    let runtime = create_runtime();
+
+    _ = create_scope(runtime, |cx| {
+        let before = runtime_stats(cx);
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let top = named_child_scope(cx, "top", &log);
+        let a = named_child_scope(top, "a", &log);
+        named_child_scope(a, "a1", &log);
+        named_child_scope(a, "a2", &log);
+        let b = named_child_scope(top, "b", &log);
+        named_child_scope(b, "b1", &log);
+
+        let during = runtime_stats(cx);
+        assert_eq!(during.scopes, before.scopes + 6);
+        assert_eq!(during.signals, before.signals + 6);
+        assert_eq!(during.memos, before.memos + 6);
+        assert_eq!(during.effects, before.effects + 6);
+        assert_eq!(during.stored_values, before.stored_values + 6);
+        assert_eq!(during.contexts, before.contexts + 6);
+
+        top.dispose();
+        assert_eq!(
+            *log.borrow(),
+            ["a1 a1", "a2 a2", "a a", "b1 b1", "b b", "top top"]
+        );
+        assert_eq!(runtime_stats(cx), before);
+    });
+
+    runtime.dispose();
+}
+
+#[test]
+fn scope_disposed_early_is_removed_from_its_parent() {
+    let runtime = create_runtime();
+
+    _ = create_scope(runtime, |cx| {
+        let before = runtime_stats(cx);
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let top = named_child_scope(cx, "top", &log);
+        let a = named_child_scope(top, "a", &log);
+        named_child_scope(a, "a1", &log);
+        named_child_scope(top, "b", &log);
+
+        a.dispose();
+        assert_eq!(*log.borrow(), ["a1 a1", "a a"]);
+        assert_eq!(runtime_stats(cx).scopes, before.scopes + 2);
+
+        // `a` and its child are not cleaned up again
+        top.dispose();
+        assert_eq!(*log.borrow(), ["a1 a1", "a a", "b b", "top top"]);
+        assert_eq!(runtime_stats(cx), before);
+    });
+
+    runtime.dispose();
+}
+
+#[test]
+fn contexts_are_dropped_when_their_scope_is_disposed() {
+    let runtime = create_runtime();
+
+    _ = create_scope(runtime, |cx| {
+        let before = runtime_stats(cx);
+        let value = Rc::new(());
+
+        let disposer = cx.child_scope({
+            let value = Rc::clone(&value);
+            move |cx| {
+                provide_context(cx, Rc::clone(&value));
+                // replacing it records where each one was provided
+                provide_context(cx, value);
+                provide_context(cx, "name");
+            }
+        });
+        assert_eq!(runtime_stats(cx).contexts, before.contexts + 2);
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        disposer.dispose();
+        assert_eq!(runtime_stats(cx), before);
+        assert_eq!(Rc::strong_count(&value), 1);
+    });
+
+    runtime.dispose();
+}
@@ -25,3 +25,114 @@ fn derived_signals() {
     })
     .dispose()
 }
+
+#[test]
+fn signal_traits_are_shared_by_every_handle_type() {
+    fn doubled(value: &impl SignalGet<u32>) -> u32 {
+        value.get() * 2
+    }
+
+    fn len(value: &impl SignalWith<String>) -> usize {
+        value.with(String::len)
+    }
+
+    fn bump(value: &impl SignalUpdate<u32>) {
+        value.update(|n| *n += 1)
+    }
+
+    create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 1);
+        let rw = create_rw_signal(cx, 2);
+        let memo = create_memo(cx, move |_| count.get() + 10);
+        let derived = Signal::derive(cx, move || count.get() + 20);
+        let maybe: MaybeSignal<u32> = 3.into();
+        let maybe_dynamic: MaybeSignal<u32> = memo.into();
+
+        assert_eq!(doubled(&count), 2);
+        assert_eq!(doubled(&rw), 4);
+        assert_eq!(doubled(&memo), 22);
+        assert_eq!(doubled(&derived), 42);
+        assert_eq!(doubled(&maybe), 6);
+        assert_eq!(doubled(&maybe_dynamic), 22);
+
+        bump(&set_count);
+        bump(&rw);
+        assert_eq!(doubled(&maybe_dynamic), 24);
+        assert_eq!(doubled(&rw), 6);
+
+        let (name, _) = create_signal(cx, "Alice".to_string());
+        let name: Signal<String> = name.into();
+        assert_eq!(len(&name), 5);
+        assert_eq!(len(&MaybeSignal::from("Bob")), 3);
+    })
+    .dispose()
+}
+
+#[test]
+fn try_variants_return_none_once_disposed() {
+    create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 1);
+        assert_eq!(count.try_get(), Some(1));
+        assert_eq!(set_count.try_update(|n| *n + 1), Some(2));
+
+        count.dispose();
+        assert_eq!(count.try_get(), None);
+        assert_eq!(count.try_with(|n| *n), None);
+        assert_eq!(set_count.try_update(|n| *n + 1), None);
+    })
+    .dispose()
+}
+
+// effects don't run on the server
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn signals_of_copy_types_are_tracked_like_any_other() {
+    use std::{cell::RefCell, rc::Rc};
+
+    // what an effect, a memo and untracked reads see of a signal
+    fn history<T: Clone + 'static>(
+        cx: Scope,
+        value: impl Fn(i32) -> T + 'static,
+        number: fn(&T) -> i32,
+    ) -> Vec<i32> {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let sig = create_rw_signal(cx, value(0));
+        let (read, write) = sig.split();
+        let other = create_rw_signal(cx, value(100));
+        let memo = create_memo(cx, move |_| number(&read.get()) * 2);
+        create_effect(cx, {
+            let log = Rc::clone(&log);
+            move |_| {
+                log.borrow_mut().push(memo.get());
+                log.borrow_mut().push(number(&other.get_untracked()));
+            }
+        });
+
+        write.set(value(1));
+        sig.set(value(2));
+        other.set(value(101));
+        assert!(write.try_set(value(3)).is_none());
+        sig.update(|_| {});
+        log.borrow_mut().push(number(&sig.get_untracked()));
+
+        sig.dispose();
+        assert!(read.try_get().is_none());
+        assert!(read.try_get_untracked().is_none());
+        assert_eq!(write.try_set(value(4)).as_ref().map(number), Some(4));
+        log.take()
+    }
+
+    // a value that isn't a primitive is boxed, rather than stored inline
+    #[derive(Clone)]
+    struct Boxed(Box<i32>);
+
+    create_scope(create_runtime(), |cx| {
+        let copied = history(cx, |n| n, |n| *n);
+        let float = history(cx, |n| n as f64, |n| *n as i32);
+        let boxed = history(cx, |n| Boxed(Box::new(n)), |b| *b.0);
+        assert_eq!(copied, vec![0, 100, 2, 100, 4, 100, 6, 101, 3]);
+        assert_eq!(copied, float);
+        assert_eq!(copied, boxed);
+    })
+    .dispose()
+}
@@ -0,0 +1,112 @@
+use leptos_reactive::*;
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn signal_map_notifies_only_the_changed_key() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let map = create_signal_map::<usize, String>(cx);
+    map.insert(1, "one".to_string());
+    map.insert(2, "two".to_string());
+
+    let first = map.get(1);
+    let first_runs = Rc::new(Cell::new(0));
+    create_isomorphic_effect(cx, {
+        let first_runs = Rc::clone(&first_runs);
+        move |_| {
+            first.track();
+            first_runs.set(first_runs.get() + 1);
+        }
+    });
+
+    let keys = map.keys();
+    let keys_runs = Rc::new(Cell::new(0));
+    create_isomorphic_effect(cx, {
+        let keys_runs = Rc::clone(&keys_runs);
+        move |_| {
+            keys.track();
+            keys_runs.set(keys_runs.get() + 1);
+        }
+    });
+
+    assert_eq!(first_runs.get(), 1);
+    assert_eq!(keys_runs.get(), 1);
+
+    // updating or replacing another entry notifies neither
+    assert!(map.update(&2, |two| two.push('!')));
+    assert_eq!(map.insert(2, "deux".to_string()), Some("two!".to_string()));
+    assert_eq!(first_runs.get(), 1);
+    assert_eq!(keys_runs.get(), 1);
+
+    // updating this entry doesn't change the keys
+    assert!(map.update(&1, |one| one.push('!')));
+    assert_eq!(first.get(), Some("one!".to_string()));
+    assert_eq!(first_runs.get(), 2);
+    assert_eq!(keys_runs.get(), 1);
+
+    // updating a missing key does nothing
+    assert!(!map.update(&3, |three| three.push('!')));
+    assert_eq!(keys_runs.get(), 1);
+
+    map.insert(3, "three".to_string());
+    assert_eq!(keys.get(), vec![1, 2, 3]);
+    assert_eq!(first_runs.get(), 2);
+    assert_eq!(keys_runs.get(), 2);
+
+    disposer.dispose();
+}
+
+#[test]
+fn signal_map_remove_and_reinsert() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let map = create_signal_map::<&str, i32>(cx);
+    map.insert("a", 1);
+    map.insert("b", 2);
+
+    let a = map.get("a");
+    let seen = Rc::new(Cell::new(None));
+    create_isomorphic_effect(cx, {
+        let seen = Rc::clone(&seen);
+        move |_| seen.set(Some(a.get()))
+    });
+    assert_eq!(seen.get(), Some(Some(1)));
+
+    assert_eq!(map.remove(&"a"), Some(1));
+    assert_eq!(seen.get(), Some(None));
+    assert!(!map.contains_key(&"a"));
+    assert_eq!(map.keys().get(), vec!["b"]);
+    assert_eq!(map.remove(&"a"), None);
+
+    // subscribers of a removed key see it again when it's re-inserted
+    map.insert("a", 10);
+    assert_eq!(seen.get(), Some(Some(10)));
+    assert_eq!(map.get_untracked(&"a"), Some(10));
+    assert_eq!(map.keys().get(), vec!["b", "a"]);
+    assert_eq!(map.len(), 2);
+
+    disposer.dispose();
+}
+
+#[test]
+fn signal_map_reading_missing_keys_stores_nothing() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let map = create_signal_map::<usize, i32>(cx);
+
+    let before = runtime_stats(cx).signals;
+    for key in 0..100 {
+        assert_eq!(map.with(&key, |value| value.copied()), None);
+    }
+    assert_eq!(runtime_stats(cx).signals, before);
+
+    // a subscriber of a missing key still sees it once it's inserted
+    let missing = map.get(7);
+    let seen = Rc::new(Cell::new(None));
+    create_isomorphic_effect(cx, {
+        let seen = Rc::clone(&seen);
+        move |_| seen.set(Some(missing.get()))
+    });
+    assert_eq!(seen.get(), Some(None));
+    map.insert(7, 70);
+    assert_eq!(seen.get(), Some(Some(70)));
+
+    disposer.dispose();
+}
@@ -1,9 +1,18 @@
 use crate::{ServerFn, ServerFnError};
+use futures::future::{abortable, AbortHandle};
 use leptos_reactive::{
-    create_rw_signal, signal_prelude::*, spawn_local, store_value, ReadSignal,
-    RwSignal, Scope, StoredValue,
+    count_aborted_request, create_isomorphic_effect, create_rw_signal,
+    debug_dropped_value, on_cleanup, report_unhandled_error, signal_prelude::*,
+    spawn_local, store_value, ReadSignal, Resource, RwSignal, Scope,
+    StoredValue, UnhandledError,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
 };
-use std::{cell::Cell, future::Future, pin::Pin, rc::Rc};
 
 /// An action synchronizes an imperative `async` call to the synchronous reactive system.
 ///
@@ -179,6 +188,190 @@ where
     pub fn value(&self) -> RwSignal<Option<O>> {
         self.0.with_value(|a| a.value)
     }
+
+    /// Refetches the given [`Resource`] each time the action resolves, so that any data the
+    /// action has changed is loaded again. This also applies when the action is submitted by an
+    /// `<ActionForm/>`.
+    ///
+    /// This replaces tracking the action’s [`version`](Action::version) in the resource’s
+    /// source. To skip refetching when a server action returns an error, use
+    /// [`invalidates_on_success`](Action::invalidates_on_success).
+    ///
+    /// ```rust
+    /// # use leptos::*;
+    /// # run_scope(create_runtime(), |cx| {
+    /// #[server(AdjustCount)]
+    /// async fn adjust_count(delta: i32) -> Result<i32, ServerFnError> {
+    ///     todo!()
+    /// }
+    ///
+    /// #[server(GetCount)]
+    /// async fn get_count() -> Result<i32, ServerFnError> {
+    ///     todo!()
+    /// }
+    ///
+    /// let count = create_resource(cx, || (), |_| get_count());
+    /// let adjust = create_server_action::<AdjustCount>(cx).invalidates(count);
+    /// # });
+    /// ```
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        tracing::instrument(level = "trace", skip_all,)
+    )]
+    pub fn invalidates<S, T>(self, resource: Resource<S, T>) -> Self
+    where
+        S: Clone + 'static,
+        T: 'static,
+    {
+        self.invalidates_if(resource, |_| true)
+    }
+
+    fn invalidates_if<S, T>(
+        self,
+        resource: Resource<S, T>,
+        should_invalidate: impl Fn(&O) -> bool + 'static,
+    ) -> Self
+    where
+        S: Clone + 'static,
+        T: 'static,
+    {
+        let (cx, version, value) =
+            self.0.with_value(|a| (a.cx, a.version, a.value));
+        create_isomorphic_effect(cx, move |prev_version| {
+            let version = version.get();
+            let resolved =
+                prev_version.is_some() && prev_version != Some(version);
+            if resolved
+                && value.with_untracked(|value| {
+                    value.as_ref().map(&should_invalidate).unwrap_or(true)
+                })
+            {
+                cx.untrack(|| resource.refetch());
+            }
+            version
+        });
+        self
+    }
+}
+
+impl<I, T, E> Action<I, Result<T, E>>
+where
+    I: 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// Refetches the given [`Resource`] each time the action resolves successfully, i.e., with
+    /// an `Ok` value. See [`invalidates`](Action::invalidates).
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        tracing::instrument(level = "trace", skip_all,)
+    )]
+    pub fn invalidates_on_success<S, U>(self, resource: Resource<S, U>) -> Self
+    where
+        S: Clone + 'static,
+        U: 'static,
+    {
+        self.invalidates_if(resource, Result::is_ok)
+    }
+
+    /// Tells the app’s other browser tabs that the data with this `key` has changed each time
+    /// the action resolves successfully, so that they refetch the resources that were registered
+    /// with [`invalidate_on_broadcast`](crate::invalidate_on_broadcast) for the same key.
+    ///
+    /// This tab doesn’t receive its own message; use [`invalidates_on_success`](Action::invalidates_on_success)
+    /// to refetch its resources.
+    ///
+    /// ```rust
+    /// # use leptos::*;
+    /// # run_scope(create_runtime(), |cx| {
+    /// #[server(AdjustCount)]
+    /// async fn adjust_count(delta: i32) -> Result<i32, ServerFnError> {
+    ///     todo!()
+    /// }
+    ///
+    /// #[server(GetCount)]
+    /// async fn get_count() -> Result<i32, ServerFnError> {
+    ///     todo!()
+    /// }
+    ///
+    /// let count = create_resource(cx, || (), |_| get_count());
+    /// invalidate_on_broadcast(cx, "count", count);
+    ///
+    /// let adjust = create_server_action::<AdjustCount>(cx)
+    ///     .invalidates_on_success(count)
+    ///     .broadcast_invalidation("count");
+    /// # });
+    /// ```
+    ///
+    /// Messages are sent with a
+    /// [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel),
+    /// or through `localStorage` in browsers that don’t support it. This does nothing on the
+    /// server.
+    #[cfg(feature = "broadcast")]
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        tracing::instrument(level = "trace", skip_all,)
+    )]
+    pub fn broadcast_invalidation(self, key: impl Into<String>) -> Self {
+        let key = key.into();
+        let (cx, version, value) =
+            self.0.with_value(|a| (a.cx, a.version, a.value));
+        create_isomorphic_effect(cx, move |prev_version| {
+            let version = version.get();
+            let resolved =
+                prev_version.is_some() && prev_version != Some(version);
+            if resolved
+                && value.with_untracked(|value| matches!(value, Some(Ok(_))))
+            {
+                crate::broadcast::post(&key);
+            }
+            version
+        });
+        self
+    }
+}
+
+impl<I, T, E> Action<I, Result<T, E>>
+where
+    I: 'static,
+    T: 'static,
+    E: std::fmt::Display + Clone + 'static,
+{
+    /// Reports each error the action resolves with to the hook set with
+    /// [`set_unhandled_error_hook`](leptos_reactive::set_unhandled_error_hook),
+    /// unless an effect, memo or view is tracking its [`value`](Action::value)
+    /// when it resolves, for example to show the error. The error is reported
+    /// with the location this is called from.
+    ///
+    /// Actions created with [`create_server_action`] already report their
+    /// errors.
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        tracing::instrument(level = "trace", skip_all,)
+    )]
+    #[track_caller]
+    pub fn report_unhandled_errors(self) -> Self {
+        let defined_at = std::panic::Location::caller();
+        let (cx, version, value) =
+            self.0.with_value(|a| (a.cx, a.version, a.value));
+        create_isomorphic_effect(cx, move |prev_version| {
+            let version = version.get();
+            let resolved =
+                prev_version.is_some() && prev_version != Some(version);
+            if resolved && !value.is_tracked() {
+                value.with_untracked(|value| {
+                    if let Some(Err(error)) = value {
+                        report_unhandled_error(UnhandledError {
+                            error: Rc::new(error.clone()),
+                            defined_at,
+                        });
+                    }
+                });
+            }
+            version
+        });
+        self
+    }
 }
 
 impl<I, O> Clone for Action<I, O>
@@ -215,10 +408,58 @@ where
     url: Option<String>,
     /// How many dispatched actions are still pending.
     pending_dispatches: Rc<Cell<usize>>,
+    in_flight: Rc<InFlightDispatches>,
     #[allow(clippy::complexity)]
     action_fn: Rc<dyn Fn(&I) -> Pin<Box<dyn Future<Output = O>>>>,
 }
 
+/// The dispatches of an action that have not resolved yet. They are aborted
+/// when the action’s scope is disposed, for example when navigating away
+/// from the route that created it, which drops the request’s future and so
+/// cancels a server function call that is still in flight.
+#[derive(Default)]
+pub(crate) struct InFlightDispatches {
+    next_id: Cell<usize>,
+    handles: RefCell<HashMap<usize, AbortHandle>>,
+}
+
+impl InFlightDispatches {
+    /// Wraps a dispatch’s future so that it can be aborted, returning it
+    /// with an id to pass to [`finish`](Self::finish) once it resolves.
+    pub(crate) fn start<F: Future>(
+        &self,
+        fut: F,
+    ) -> (usize, futures::future::Abortable<F>) {
+        let id = self.next_id.get();
+        self.next_id.set(id.wrapping_add(1));
+        let (fut, handle) = abortable(fut);
+        self.handles.borrow_mut().insert(id, handle);
+        (id, fut)
+    }
+
+    pub(crate) fn finish(&self, id: usize) {
+        self.handles.borrow_mut().remove(&id);
+    }
+
+    /// Aborts every dispatch that has not resolved yet.
+    pub(crate) fn abort_all(&self) {
+        for (_, handle) in self.handles.take() {
+            handle.abort();
+            count_aborted_request();
+        }
+    }
+
+    /// Creates a tracker whose dispatches are aborted when `cx` is disposed.
+    pub(crate) fn new_in_scope(cx: Scope) -> Rc<Self> {
+        let in_flight = Rc::new(Self::default());
+        on_cleanup(cx, {
+            let in_flight = Rc::clone(&in_flight);
+            move || in_flight.abort_all()
+        });
+        in_flight
+    }
+}
+
 impl<I, O> ActionState<I, O>
 where
     I: 'static,
@@ -238,18 +479,29 @@ where
         let pending_dispatches = Rc::clone(&self.pending_dispatches);
         let value = self.value;
         let cx = self.cx;
+        let in_flight = Rc::clone(&self.in_flight);
+        let (id, fut) = in_flight.start(fut);
         pending.set(true);
         pending_dispatches.set(pending_dispatches.get().saturating_sub(1));
         spawn_local(async move {
-            let new_value = fut.await;
+            // aborted because the action's scope has been disposed
+            let Ok(new_value) = fut.await else {
+                return;
+            };
+            in_flight.finish(id);
+            // the runtime may have been disposed while the future ran
+            if value.try_with_untracked(|_| ()).is_none() {
+                debug_dropped_value(value.defined_at());
+                return;
+            }
             cx.batch(move || {
-                value.set(Some(new_value));
-                input.set(None);
-                version.update(|n| *n += 1);
+                value.try_set(Some(new_value));
+                input.try_set(None);
+                version.try_update(|n| *n += 1);
                 pending_dispatches
                     .set(pending_dispatches.get().saturating_sub(1));
                 if pending_dispatches.get() == 0 {
-                    pending.set(false);
+                    pending.try_set(false);
                 }
             });
         })
@@ -334,6 +586,7 @@ where
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "trace", skip_all,)
 )]
+#[track_caller]
 pub fn create_action<I, O, F, Fu>(cx: Scope, action_fn: F) -> Action<I, O>
 where
     I: 'static,
@@ -361,6 +614,7 @@ where
             value,
             pending,
             pending_dispatches,
+            in_flight: InFlightDispatches::new_in_scope(cx),
             action_fn,
         },
     ))
@@ -384,6 +638,7 @@ where
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "trace", skip_all,)
 )]
+#[track_caller]
 pub fn create_server_action<S>(
     cx: Scope,
 ) -> Action<S, Result<S::Output, ServerFnError>>
@@ -394,5 +649,7 @@ where
     let c = move |args: &S| S::call_fn(args.clone(), cx);
     #[cfg(not(feature = "ssr"))]
     let c = move |args: &S| S::call_fn_client(args.clone(), cx);
-    create_action(cx, c).using_server_fn::<S>()
+    create_action(cx, c)
+        .using_server_fn::<S>()
+        .report_unhandled_errors()
 }
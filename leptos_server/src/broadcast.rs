@@ -0,0 +1,255 @@
+//! Invalidating resources in the app’s other browser tabs.
+
+use leptos_reactive::{Resource, Scope};
+
+/// Refetches `resource` whenever an action in another tab of the app that
+/// uses the same `key` for [`broadcast_invalidation`](crate::Action::broadcast_invalidation)
+/// succeeds, until `cx` is disposed.
+///
+/// An [`Action`](crate::Action) that changes some data can refetch the
+/// resources that show it with [`invalidates`](crate::Action::invalidates),
+/// but only in the tab it was dispatched in. Other tabs keep showing the old
+/// data until they are reloaded. To refetch them too, the action announces
+/// that the data has changed with `broadcast_invalidation`, and each resource
+/// that shows it listens with `invalidate_on_broadcast`, using the same key.
+///
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// #[server(AdjustCount)]
+/// async fn adjust_count(delta: i32) -> Result<i32, ServerFnError> {
+///     todo!()
+/// }
+///
+/// #[server(GetCount)]
+/// async fn get_count() -> Result<i32, ServerFnError> {
+///     todo!()
+/// }
+///
+/// let count = create_resource(cx, || (), |_| get_count());
+/// // refetched when `adjust` succeeds in another tab
+/// invalidate_on_broadcast(cx, "count", count);
+///
+/// let adjust = create_server_action::<AdjustCount>(cx)
+///     // refetched when `adjust` succeeds in this tab
+///     .invalidates_on_success(count)
+///     .broadcast_invalidation("count");
+/// # });
+/// ```
+///
+/// Messages are sent with a
+/// [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel),
+/// or through `localStorage` in browsers that don’t support it. A tab never
+/// receives its own messages. This does nothing on the server.
+pub fn invalidate_on_broadcast<S, T>(
+    cx: Scope,
+    key: impl Into<String>,
+    resource: Resource<S, T>,
+) where
+    S: Clone + 'static,
+    T: 'static,
+{
+    cfg_if::cfg_if! {
+        if #[cfg(all(
+            target_arch = "wasm32",
+            any(feature = "csr", feature = "hydrate")
+        ))] {
+            client::listen(cx, key.into(), move || resource.refetch());
+        } else {
+            _ = (cx, key, resource);
+        }
+    }
+}
+
+/// Tells the app’s other tabs that the data with this key has changed.
+pub(crate) fn post(key: &str) {
+    cfg_if::cfg_if! {
+        if #[cfg(all(
+            target_arch = "wasm32",
+            any(feature = "csr", feature = "hydrate")
+        ))] {
+            client::post(key);
+        } else {
+            _ = key;
+        }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+mod client {
+    use leptos_reactive::{on_cleanup, Scope};
+    use std::{
+        cell::{Cell, RefCell},
+        collections::HashMap,
+        rc::Rc,
+    };
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+    const NAME: &str = "leptos-invalidation";
+
+    thread_local! {
+        static BROADCAST: Broadcast = Broadcast::new();
+    }
+
+    enum Transport {
+        Channel(web_sys::BroadcastChannel),
+        Storage(web_sys::Storage),
+        None,
+    }
+
+    type Listener = (usize, Rc<dyn Fn()>);
+
+    struct Broadcast {
+        // identifies this tab’s messages, so that it ignores any that are
+        // delivered back to it
+        tab_id: String,
+        transport: Transport,
+        listeners: Rc<RefCell<HashMap<String, Vec<Listener>>>>,
+        next_id: Cell<usize>,
+        sent: Cell<u64>,
+    }
+
+    impl Broadcast {
+        fn new() -> Self {
+            let tab_id = js_sys::Math::random().to_string();
+            let listeners: Rc<RefCell<HashMap<String, Vec<Listener>>>> =
+                Default::default();
+            let receive = {
+                let tab_id = tab_id.clone();
+                let listeners = Rc::clone(&listeners);
+                move |message: Option<String>| {
+                    let Some(message) = message else { return };
+                    let Some((sender, key)) = decode(&message) else {
+                        return;
+                    };
+                    if sender == tab_id {
+                        return;
+                    }
+                    // the listeners can register or remove others while
+                    // refetching, so they are cloned out first
+                    let to_notify = listeners
+                        .borrow()
+                        .get(key)
+                        .map(|listeners| {
+                            listeners
+                                .iter()
+                                .map(|(_, f)| Rc::clone(f))
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default();
+                    for f in to_notify {
+                        f();
+                    }
+                }
+            };
+
+            // the constructor throws where `BroadcastChannel` isn't supported
+            let transport = match web_sys::BroadcastChannel::new(NAME) {
+                Ok(channel) => {
+                    let on_message =
+                        Closure::<dyn FnMut(web_sys::MessageEvent)>::new(
+                            move |ev: web_sys::MessageEvent| {
+                                receive(ev.data().as_string())
+                            },
+                        );
+                    channel.set_onmessage(Some(
+                        on_message.as_ref().unchecked_ref(),
+                    ));
+                    // the channel is kept open for as long as the tab is
+                    on_message.forget();
+                    Transport::Channel(channel)
+                }
+                Err(_) => storage_transport(receive),
+            };
+
+            Self {
+                tab_id,
+                transport,
+                listeners,
+                next_id: Cell::new(0),
+                sent: Cell::new(0),
+            }
+        }
+
+        fn post(&self, key: &str) {
+            // a `storage` event is only fired when the value changes, so each
+            // message is made unique with a counter
+            let sent = self.sent.get();
+            self.sent.set(sent + 1);
+            let message = format!("{}\n{sent}\n{key}", self.tab_id);
+            match &self.transport {
+                Transport::Channel(channel) => {
+                    _ = channel.post_message(&JsValue::from_str(&message));
+                }
+                Transport::Storage(storage) => {
+                    _ = storage.set_item(NAME, &message);
+                    _ = storage.remove_item(NAME);
+                }
+                Transport::None => {}
+            }
+        }
+    }
+
+    // falls back to the `storage` event, which other tabs get when this one
+    // changes `localStorage`
+    fn storage_transport(
+        receive: impl Fn(Option<String>) + 'static,
+    ) -> Transport {
+        let Some(window) = web_sys::window() else {
+            return Transport::None;
+        };
+        let Ok(Some(storage)) = window.local_storage() else {
+            return Transport::None;
+        };
+        let on_storage = Closure::<dyn FnMut(web_sys::StorageEvent)>::new(
+            move |ev: web_sys::StorageEvent| {
+                if ev.key().as_deref() == Some(NAME) {
+                    receive(ev.new_value())
+                }
+            },
+        );
+        _ = window.add_event_listener_with_callback(
+            "storage",
+            on_storage.as_ref().unchecked_ref(),
+        );
+        on_storage.forget();
+        Transport::Storage(storage)
+    }
+
+    // a message is the sending tab’s ID, a counter, and the key, on
+    // separate lines
+    fn decode(message: &str) -> Option<(&str, &str)> {
+        let (tab_id, rest) = message.split_once('\n')?;
+        let (_, key) = rest.split_once('\n')?;
+        Some((tab_id, key))
+    }
+
+    pub(super) fn listen(cx: Scope, key: String, f: impl Fn() + 'static) {
+        let id = BROADCAST.with(|broadcast| {
+            let id = broadcast.next_id.get();
+            broadcast.next_id.set(id + 1);
+            broadcast
+                .listeners
+                .borrow_mut()
+                .entry(key.clone())
+                .or_default()
+                .push((id, Rc::new(f)));
+            id
+        });
+        on_cleanup(cx, move || {
+            BROADCAST.with(|broadcast| {
+                let mut listeners = broadcast.listeners.borrow_mut();
+                if let Some(for_key) = listeners.get_mut(&key) {
+                    for_key.retain(|(listener, _)| *listener != id);
+                    if for_key.is_empty() {
+                        listeners.remove(&key);
+                    }
+                }
+            })
+        });
+    }
+
+    pub(super) fn post(key: &str) {
+        BROADCAST.with(|broadcast| broadcast.post(key));
+    }
+}
@@ -121,9 +121,23 @@ pub use server_fn::{
 };
 
 mod action;
+#[cfg(feature = "broadcast")]
+mod broadcast;
 mod multi_action;
+#[cfg(any(feature = "ssr", doc))]
+mod server_context;
+mod server_fn_context;
+#[cfg(feature = "server-signal")]
+mod server_signal;
 pub use action::*;
+#[cfg(feature = "broadcast")]
+pub use broadcast::*;
 pub use multi_action::*;
+#[cfg(any(feature = "ssr", doc))]
+pub use server_context::*;
+pub use server_fn_context::*;
+#[cfg(feature = "server-signal")]
+pub use server_signal::*;
 extern crate tracing;
 
 #[cfg(any(feature = "ssr", doc))]
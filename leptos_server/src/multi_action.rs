@@ -1,7 +1,7 @@
-use crate::{ServerFn, ServerFnError};
+use crate::{action::InFlightDispatches, ServerFn, ServerFnError};
 use leptos_reactive::{
-    create_rw_signal, signal_prelude::*, spawn_local, store_value, ReadSignal,
-    RwSignal, Scope, StoredValue,
+    create_rw_signal, debug_dropped_value, signal_prelude::*, spawn_local,
+    store_value, ReadSignal, Resource, RwSignal, Scope, StoredValue,
 };
 use std::{future::Future, pin::Pin, rc::Rc};
 
@@ -144,6 +144,63 @@ where
 
         self
     }
+
+    /// Refetches the given [`Resource`] each time a submission resolves, so that any data the
+    /// action has changed is loaded again. Each completed submission refetches the resource once.
+    ///
+    /// To skip refetching when a server action returns an error, use
+    /// [`invalidates_on_success`](MultiAction::invalidates_on_success).
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        tracing::instrument(level = "trace", skip_all,)
+    )]
+    pub fn invalidates<S, T>(self, resource: Resource<S, T>) -> Self
+    where
+        S: Clone + 'static,
+        T: 'static,
+    {
+        self.invalidates_if(resource, |_| true)
+    }
+
+    fn invalidates_if<S, T>(
+        self,
+        resource: Resource<S, T>,
+        should_invalidate: impl Fn(&O) -> bool + 'static,
+    ) -> Self
+    where
+        S: Clone + 'static,
+        T: 'static,
+    {
+        self.0.update_value(|a| {
+            a.invalidations.push(Rc::new(move |value| {
+                if should_invalidate(value) {
+                    resource.refetch();
+                }
+            }))
+        });
+        self
+    }
+}
+
+impl<I, T, E> MultiAction<I, Result<T, E>>
+where
+    I: 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// Refetches the given [`Resource`] each time a submission resolves successfully, i.e.,
+    /// with an `Ok` value. See [`invalidates`](MultiAction::invalidates).
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        tracing::instrument(level = "trace", skip_all,)
+    )]
+    pub fn invalidates_on_success<S, U>(self, resource: Resource<S, U>) -> Self
+    where
+        S: Clone + 'static,
+        U: 'static,
+    {
+        self.invalidates_if(resource, Result::is_ok)
+    }
 }
 
 struct MultiActionState<I, O>
@@ -156,8 +213,12 @@ where
     pub version: RwSignal<usize>,
     submissions: RwSignal<Vec<Submission<I, O>>>,
     url: Option<String>,
+    in_flight: Rc<InFlightDispatches>,
     #[allow(clippy::complexity)]
     action_fn: Rc<dyn Fn(&I) -> Pin<Box<dyn Future<Output = O>>>>,
+    /// Refetches resources when a submission resolves.
+    #[allow(clippy::complexity)]
+    invalidations: Vec<Rc<dyn Fn(&O)>>,
 }
 
 /// An action that has been submitted by dispatching it to a [MultiAction](crate::MultiAction).
@@ -228,16 +289,33 @@ where
         let pending = submission.pending;
         let value = submission.value;
         let version = self.version;
+        let invalidations = self.invalidations.clone();
 
+        let in_flight = Rc::clone(&self.in_flight);
+        let (id, fut) = in_flight.start(fut);
         spawn_local(async move {
-            let new_value = fut.await;
-            let canceled = cx.untrack(move || canceled.get());
+            // aborted because the action's scope has been disposed
+            let Ok(new_value) = fut.await else {
+                return;
+            };
+            in_flight.finish(id);
+            // the runtime may have been disposed while the future ran
+            let Some(canceled) = canceled.try_get_untracked() else {
+                debug_dropped_value(value.defined_at());
+                return;
+            };
+            // a canceled submission has still run, so it can still invalidate data
+            cx.untrack(|| {
+                for invalidate in &invalidations {
+                    invalidate(&new_value);
+                }
+            });
             if !canceled {
-                value.set(Some(new_value));
+                value.try_set(Some(new_value));
             }
-            input.set(None);
-            pending.set(false);
-            version.update(|n| *n += 1);
+            input.try_set(None);
+            pending.try_set(false);
+            version.try_update(|n| *n += 1);
         })
     }
 
@@ -302,6 +380,7 @@ where
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "trace", skip_all,)
 )]
+#[track_caller]
 pub fn create_multi_action<I, O, F, Fu>(
     cx: Scope,
     action_fn: F,
@@ -326,7 +405,9 @@ where
             version,
             submissions,
             url: None,
+            in_flight: InFlightDispatches::new_in_scope(cx),
             action_fn,
+            invalidations: Vec::new(),
         },
     ))
 }
@@ -0,0 +1,229 @@
+use crate::ServerFnError;
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock},
+    task::{Context, Poll},
+};
+
+type Registry = HashMap<TypeId, Arc<dyn Any + Send + Sync>>;
+
+lazy_static::lazy_static! {
+    static ref SERVER_CONTEXT: RwLock<Registry> = Default::default();
+}
+
+thread_local! {
+    // the registry of the `with_server_context` call being polled, if any
+    static SCOPED_CONTEXT: RefCell<Option<Registry>> = RefCell::new(None);
+}
+
+/// A registry of the services that server functions depend on, like a
+/// database pool, an API client or the app’s configuration.
+///
+/// Services are registered once, when the server is set up, and can then be
+/// retrieved by type with [`use_server_context`] in the body of any server
+/// function, whether it was called over HTTP or directly while rendering on
+/// the server. Unlike a global `static`, the services can be replaced with
+/// fakes in tests; see [`with_server_context`].
+///
+/// ```rust,ignore
+/// #[tokio::main]
+/// async fn main() {
+///     let pool = PgPool::connect(&database_url).await.unwrap();
+///     LeptosServerContext::insert(pool);
+///     LeptosServerContext::insert(AppConfig::from_env());
+///
+///     // build the router and serve the app...
+/// }
+///
+/// #[server(ListTodos, "/api")]
+/// async fn list_todos() -> Result<Vec<Todo>, ServerFnError> {
+///     let pool = use_server_context::<PgPool>()?;
+///     // query the database...
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LeptosServerContext;
+
+impl LeptosServerContext {
+    /// Registers a service, replacing any earlier value of the same type.
+    ///
+    /// Inside [`with_server_context`], the service is only registered until
+    /// that call ends.
+    pub fn insert<T>(value: T)
+    where
+        T: Send + Sync + 'static,
+    {
+        let value: Arc<dyn Any + Send + Sync> = Arc::new(value);
+        let scoped = SCOPED_CONTEXT.with(|scoped| {
+            scoped
+                .borrow_mut()
+                .as_mut()
+                .map(|scoped| scoped.insert(TypeId::of::<T>(), value.clone()))
+                .is_some()
+        });
+        if !scoped {
+            SERVER_CONTEXT
+                .write()
+                .unwrap()
+                .insert(TypeId::of::<T>(), value);
+        }
+    }
+
+    /// Removes a service, returning whether it was registered.
+    pub fn remove<T>() -> bool
+    where
+        T: Send + Sync + 'static,
+    {
+        SCOPED_CONTEXT
+            .with(|scoped| {
+                scoped
+                    .borrow_mut()
+                    .as_mut()
+                    .map(|scoped| scoped.remove(&TypeId::of::<T>()).is_some())
+            })
+            .unwrap_or_else(|| {
+                SERVER_CONTEXT
+                    .write()
+                    .unwrap()
+                    .remove(&TypeId::of::<T>())
+                    .is_some()
+            })
+    }
+
+    fn get<T>() -> Option<Arc<dyn Any + Send + Sync>>
+    where
+        T: 'static,
+    {
+        SCOPED_CONTEXT
+            .with(|scoped| {
+                scoped
+                    .borrow()
+                    .as_ref()
+                    .map(|scoped| scoped.get(&TypeId::of::<T>()).cloned())
+            })
+            .unwrap_or_else(|| {
+                SERVER_CONTEXT
+                    .read()
+                    .unwrap()
+                    .get(&TypeId::of::<T>())
+                    .cloned()
+            })
+    }
+}
+
+/// Returns a clone of the service of type `T` that was registered with
+/// [`LeptosServerContext::insert`], or an error naming the type if none was.
+///
+/// Services are usually cheap to clone, like a connection pool or a value
+/// wrapped in an [`Arc`].
+///
+/// ```rust,ignore
+/// #[server(UploadAvatar, "/api")]
+/// async fn upload_avatar(bytes: Vec<u8>) -> Result<(), ServerFnError> {
+///     let s3 = use_server_context::<S3Client>()?;
+///     let config = use_server_context::<AppConfig>()?;
+///     // ...
+/// }
+/// ```
+pub fn use_server_context<T>() -> Result<T, ServerFnError>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    LeptosServerContext::get::<T>()
+        .and_then(|value| value.downcast_ref::<T>().cloned())
+        .ok_or_else(|| {
+            ServerFnError::ServerError(format!(
+                "no server context of type `{}` has been registered; add it \
+                 with LeptosServerContext::insert() when setting up the \
+                 server",
+                std::any::type_name::<T>()
+            ))
+        })
+}
+
+/// Runs the future returned by `f` with its own, initially empty, server
+/// context, so that tests can register fakes for the services their server
+/// functions use without affecting other tests.
+///
+/// Inside the call, [`LeptosServerContext::insert`] and
+/// [`use_server_context`] only see this context, not the services
+/// registered for the whole server.
+///
+/// ```
+/// # use leptos_server::*;
+/// #[derive(Clone)]
+/// struct Greeting(&'static str);
+///
+/// # futures::executor::block_on(async {
+/// let greeting = with_server_context(|| async {
+///     LeptosServerContext::insert(Greeting("hello"));
+///     use_server_context::<Greeting>().map(|greeting| greeting.0)
+/// })
+/// .await;
+/// assert_eq!(greeting.unwrap(), "hello");
+///
+/// // the fake is gone once the call ends
+/// assert!(use_server_context::<Greeting>().is_err());
+/// # });
+/// ```
+pub async fn with_server_context<F, Fut>(f: F) -> Fut::Output
+where
+    F: FnOnce() -> Fut,
+    Fut: Future,
+{
+    let mut registry = Some(Registry::new());
+    let fut = enter(&mut registry, f);
+    WithServerContext {
+        registry,
+        fut: Box::pin(fut),
+    }
+    .await
+}
+
+// runs `f` with `registry` as the current scoped context, putting back the
+// previous one afterwards
+fn enter<T>(registry: &mut Option<Registry>, f: impl FnOnce() -> T) -> T {
+    struct Restore<'a> {
+        registry: &'a mut Option<Registry>,
+        prev: Option<Registry>,
+    }
+
+    impl Drop for Restore<'_> {
+        fn drop(&mut self) {
+            let prev = self.prev.take();
+            *self.registry = SCOPED_CONTEXT.with(|scoped| {
+                std::mem::replace(&mut *scoped.borrow_mut(), prev)
+            });
+        }
+    }
+
+    let prev = SCOPED_CONTEXT.with(|scoped| {
+        std::mem::replace(&mut *scoped.borrow_mut(), registry.take())
+    });
+    let _restore = Restore { registry, prev };
+    f()
+}
+
+// installs its registry every time it is polled, so that the context follows
+// the future even if it moves between threads
+struct WithServerContext<Fut> {
+    registry: Option<Registry>,
+    fut: Pin<Box<Fut>>,
+}
+
+impl<Fut> Future for WithServerContext<Fut>
+where
+    Fut: Future,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let fut = &mut this.fut;
+        enter(&mut this.registry, || fut.as_mut().poll(cx))
+    }
+}
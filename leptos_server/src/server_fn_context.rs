@@ -0,0 +1,71 @@
+use leptos_reactive::{provide_context, use_context, Scope};
+use std::future::Future;
+
+/// Marks the scope that the request-scoped context server functions run in
+/// has been provided to.
+///
+/// Server functions can be called in two ways: over HTTP, when the client
+/// calls them, or directly in-process, when a resource calls them during
+/// server-side rendering. Server integrations provide the per-request
+/// context (the request parts,
+/// [`ResponseOptions`](https://docs.rs/leptos_axum/latest/leptos_axum/struct.ResponseOptions.html),
+/// anything added in `additional_context`, and so on) to the root scope of
+/// the request in both cases, and mark it with a `ServerFnContext`.
+///
+/// A server function that takes a [`Scope`] argument then runs in a child of
+/// that scope, so [`use_context`] inside its body resolves to the same values
+/// whichever way it was called, and does not see contexts provided by the
+/// component that happened to call it during rendering.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_server::ServerFnContext;
+/// #[derive(Clone)]
+/// struct Session {
+///     user: String,
+/// }
+///
+/// # let runtime = create_runtime();
+/// # run_scope(runtime, |cx| {
+/// provide_context(
+///     cx,
+///     Session {
+///         user: "alice".to_string(),
+///     },
+/// );
+/// ServerFnContext::provide(cx);
+/// # });
+/// # runtime.dispose();
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ServerFnContext(Scope);
+
+impl ServerFnContext {
+    /// Marks `cx`, which the per-request context has been provided to, as
+    /// the scope that server functions run in a child of.
+    pub fn provide(cx: Scope) {
+        provide_context(cx, ServerFnContext(cx));
+    }
+}
+
+/// Runs the body of a server function in a child of the scope marked by the
+/// current [`ServerFnContext`], if there is one, or in `cx` otherwise.
+///
+/// This is used by the [`#[server]`](https://docs.rs/leptos/latest/leptos/attr.server.html)
+/// macro; you shouldn't need to call it yourself.
+#[doc(hidden)]
+pub async fn run_server_fn<F, Fut, T>(cx: Scope, body: F) -> T
+where
+    F: FnOnce(Scope) -> Fut,
+    Fut: Future<Output = T>,
+{
+    match use_context::<ServerFnContext>(cx) {
+        Some(ServerFnContext(request_cx)) => {
+            let (fn_cx, disposer) = request_cx.run_child_scope(|cx| cx);
+            let value = body(fn_cx).await;
+            disposer.dispose();
+            value
+        }
+        None => body(cx).await,
+    }
+}
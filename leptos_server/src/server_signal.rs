@@ -0,0 +1,550 @@
+//! Live state that is shared by every client connected to a server.
+//!
+//! A [`ServerSignal`] holds a value on the server, under a name. Setting or
+//! updating it broadcasts the change to every subscriber as a small JSON
+//! patch, so a large state only sends the fields that actually changed. Its
+//! [`events`](ServerSignal::events) stream can be served as
+//! [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events),
+//! and [`use_server_signal`] turns that stream back into a read-only
+//! [`Signal`] in the browser.
+//!
+//! ```rust,ignore
+//! # use leptos::*;
+//! // on the server
+//! lazy_static::lazy_static! {
+//!     static ref COUNT: ServerSignal<i32> = create_server_signal("count");
+//! }
+//!
+//! #[server(Increment, "/api")]
+//! async fn increment() -> Result<(), ServerFnError> {
+//!     COUNT.update(|count| *count += 1);
+//!     Ok(())
+//! }
+//!
+//! // serve `COUNT.events()` as `text/event-stream` at `/api/events`
+//!
+//! // in a component
+//! let count = use_server_signal::<i32>(cx, "count", "/api/events");
+//! view! { cx, <p>"Count: " {count}</p> }
+//! ```
+
+use leptos_reactive::{
+    create_resource, create_signal, Scope, Serializable, Signal, SignalGet,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A message sent to the subscribers of a [`ServerSignal`].
+///
+/// Every subscription starts with the [`Full`](ServerSignalMessage::Full)
+/// value, followed by a [`Patch`](ServerSignalMessage::Patch) for each
+/// change. Versions increase by one with each change, so a subscriber that
+/// sees a gap knows it missed a patch and should resubscribe.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ServerSignalMessage {
+    /// The whole current value.
+    Full {
+        /// The version of the value.
+        version: u64,
+        /// The value, as JSON.
+        value: Value,
+    },
+    /// The changes from the previous version to this one.
+    Patch {
+        /// The version of the value after the patch is applied.
+        version: u64,
+        /// The changes to apply, in order.
+        ops: Vec<PatchOp>,
+    },
+}
+
+/// One change to the JSON representation of a [`ServerSignal`]’s value.
+///
+/// Paths are the keys of nested objects, starting from the root; an empty
+/// path is the whole value. Arrays are replaced as a whole.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    /// Sets the value at the path, adding the key if it is not there yet.
+    Set {
+        /// The keys leading to the value.
+        path: Vec<String>,
+        /// The new value.
+        value: Value,
+    },
+    /// Removes the key at the end of the path.
+    Remove {
+        /// The keys leading to the value.
+        path: Vec<String>,
+    },
+}
+
+impl PatchOp {
+    /// Applies the change to a JSON value.
+    pub fn apply(&self, target: &mut Value) {
+        match self {
+            PatchOp::Set { path, value } => {
+                let mut target = target;
+                for key in path {
+                    if !target.is_object() {
+                        *target = Value::Object(Map::new());
+                    }
+                    target = target
+                        .as_object_mut()
+                        .expect("just made this an object")
+                        .entry(key.clone())
+                        .or_insert(Value::Null);
+                }
+                *target = value.clone();
+            }
+            PatchOp::Remove { path } => {
+                let Some((last, parents)) = path.split_last() else {
+                    return;
+                };
+                let mut target = target;
+                for key in parents {
+                    match target.get_mut(key) {
+                        Some(child) => target = child,
+                        None => return,
+                    }
+                }
+                if let Some(object) = target.as_object_mut() {
+                    object.remove(last);
+                }
+            }
+        }
+    }
+}
+
+// the changes that turn `old` into `new`, recursing into objects
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+fn diff(
+    old: &Value,
+    new: &Value,
+    path: &mut Vec<String>,
+    ops: &mut Vec<PatchOp>,
+) {
+    match (old, new) {
+        _ if old == new => {}
+        (Value::Object(old), Value::Object(new)) => {
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                path.push(key.clone());
+                ops.push(PatchOp::Remove { path: path.clone() });
+                path.pop();
+            }
+            for (key, new_value) in new {
+                path.push(key.clone());
+                match old.get(key) {
+                    Some(old_value) => diff(old_value, new_value, path, ops),
+                    None => ops.push(PatchOp::Set {
+                        path: path.clone(),
+                        value: new_value.clone(),
+                    }),
+                }
+                path.pop();
+            }
+        }
+        _ => ops.push(PatchOp::Set {
+            path: path.clone(),
+            value: new.clone(),
+        }),
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub use server::*;
+
+#[cfg(feature = "ssr")]
+mod server {
+    use super::{diff, ServerSignalMessage};
+    use futures::{
+        channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+        Stream, StreamExt,
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::Value;
+    use std::{
+        collections::HashMap,
+        marker::PhantomData,
+        sync::{Arc, Mutex, RwLock},
+    };
+
+    struct State {
+        version: u64,
+        value: Value,
+        subscribers: Vec<UnboundedSender<ServerSignalMessage>>,
+    }
+
+    lazy_static::lazy_static! {
+        static ref SERVER_SIGNALS: RwLock<HashMap<String, Arc<Mutex<State>>>> =
+            Default::default();
+    }
+
+    /// A value shared by everyone connected to the server, which broadcasts
+    /// its changes to subscribers. Created with [`create_server_signal`].
+    ///
+    /// The value is stored as JSON, so [`get`](ServerSignal::get) and
+    /// [`update`](ServerSignal::update) deserialize it each time they are
+    /// called.
+    pub struct ServerSignal<T> {
+        name: Arc<str>,
+        state: Arc<Mutex<State>>,
+        ty: PhantomData<fn() -> T>,
+    }
+
+    impl<T> Clone for ServerSignal<T> {
+        fn clone(&self) -> Self {
+            Self {
+                name: Arc::clone(&self.name),
+                state: Arc::clone(&self.state),
+                ty: PhantomData,
+            }
+        }
+    }
+
+    impl<T> std::fmt::Debug for ServerSignal<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ServerSignal")
+                .field("name", &self.name)
+                .finish()
+        }
+    }
+
+    /// Creates a [`ServerSignal`] with the given name, starting from
+    /// `T::default()`, or returns the existing one if a signal with that
+    /// name has already been created.
+    ///
+    /// The name is used to find the value when rendering
+    /// [`use_server_signal`](super::use_server_signal) on the server, and as
+    /// the event name in [`events`](ServerSignal::events).
+    pub fn create_server_signal<T>(name: impl Into<String>) -> ServerSignal<T>
+    where
+        T: Serialize + DeserializeOwned + Default,
+    {
+        let name = name.into();
+        let state = Arc::clone(
+            SERVER_SIGNALS
+                .write()
+                .expect("server signal registry is poisoned")
+                .entry(name.clone())
+                .or_insert_with(|| {
+                    Arc::new(Mutex::new(State {
+                        version: 0,
+                        value: serde_json::to_value(T::default())
+                            .expect("couldn't serialize server signal value"),
+                        subscribers: Vec::new(),
+                    }))
+                }),
+        );
+        ServerSignal {
+            name: name.into(),
+            state,
+            ty: PhantomData,
+        }
+    }
+
+    // the current JSON value of the server signal with this name, if any
+    pub(super) fn current_value(name: &str) -> Option<Value> {
+        let signals = SERVER_SIGNALS.read().ok()?;
+        let state = signals.get(name)?.lock().ok()?;
+        Some(state.value.clone())
+    }
+
+    impl<T> ServerSignal<T>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        /// The name the signal was created with.
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        /// Returns a copy of the current value.
+        ///
+        /// # Panics
+        /// Panics if the value was stored by a signal of another type with
+        /// the same name, and cannot be read as a `T`.
+        pub fn get(&self) -> T {
+            let state = self.state.lock().expect("server signal is poisoned");
+            self.deserialize(&state.value)
+        }
+
+        /// Sets the value, and broadcasts the change to subscribers.
+        pub fn set(&self, value: T) {
+            self.update(|current| *current = value);
+        }
+
+        /// Updates the value in place, and broadcasts the change to
+        /// subscribers. Nothing is sent if the value did not change.
+        ///
+        /// # Panics
+        /// Panics if the value was stored by a signal of another type with
+        /// the same name, and cannot be read as a `T`.
+        pub fn update(&self, f: impl FnOnce(&mut T)) {
+            let mut state =
+                self.state.lock().expect("server signal is poisoned");
+            let mut value = self.deserialize(&state.value);
+            f(&mut value);
+            let value = serde_json::to_value(value)
+                .expect("couldn't serialize server signal value");
+
+            let mut ops = Vec::new();
+            diff(&state.value, &value, &mut Vec::new(), &mut ops);
+            if ops.is_empty() {
+                return;
+            }
+            state.version += 1;
+            state.value = value;
+            let message = ServerSignalMessage::Patch {
+                version: state.version,
+                ops,
+            };
+            // drop the subscribers that have disconnected
+            state
+                .subscribers
+                .retain(|tx| tx.unbounded_send(message.clone()).is_ok());
+        }
+
+        /// Subscribes to the value. The stream starts with the full current
+        /// value, followed by a patch for each change.
+        pub fn subscribe(&self) -> UnboundedReceiver<ServerSignalMessage> {
+            let mut state =
+                self.state.lock().expect("server signal is poisoned");
+            let (tx, rx) = unbounded();
+            _ = tx.unbounded_send(ServerSignalMessage::Full {
+                version: state.version,
+                value: state.value.clone(),
+            });
+            state.subscribers.push(tx);
+            rx
+        }
+
+        /// Subscribes to the value as a stream of
+        /// [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events#event_stream_format),
+        /// named after the signal. Serve this with the
+        /// `Content-Type: text/event-stream` header to the URL passed to
+        /// [`use_server_signal`](super::use_server_signal).
+        ///
+        /// Each connection starts with the full current value, so a client
+        /// that reconnects is brought back up to date.
+        pub fn events(&self) -> impl Stream<Item = String> {
+            let name = Arc::clone(&self.name);
+            self.subscribe().map(move |message| {
+                let data = serde_json::to_string(&message)
+                    .expect("couldn't serialize server signal message");
+                format!("event: {name}\ndata: {data}\n\n")
+            })
+        }
+
+        fn deserialize(&self, value: &Value) -> T {
+            serde_json::from_value(value.clone()).unwrap_or_else(|e| {
+                panic!(
+                    "server signal {:?} holds a value of another type: {e}",
+                    self.name
+                )
+            })
+        }
+    }
+}
+
+/// Returns a read-only [`Signal`] that follows the [`ServerSignal`] with the
+/// given name, by listening to its [`events`](ServerSignal::events) served
+/// at `url`.
+///
+/// When rendered on the server, the signal starts with the current value,
+/// which is serialized into the page like a resource, so there is no flash
+/// of the default value before the browser connects. If the connection drops
+/// and is reestablished, or a patch is missed, the signal resyncs from the
+/// full current value.
+pub fn use_server_signal<T>(
+    cx: Scope,
+    name: impl Into<String>,
+    url: impl Into<String>,
+) -> Signal<T>
+where
+    T: Serializable + Serialize + DeserializeOwned + Clone + Default + 'static,
+{
+    let name: String = name.into();
+    let initial = create_resource(cx, || (), {
+        let name = name.clone();
+        move |_| {
+            let value = initial_value::<T>(&name);
+            async move { value }
+        }
+    });
+    let (live, set_live) = create_signal(cx, None::<T>);
+
+    cfg_if::cfg_if! {
+        if #[cfg(all(
+            target_arch = "wasm32",
+            any(feature = "csr", feature = "hydrate")
+        ))] {
+            let url: String = url.into();
+            client::subscribe(cx, name.into(), url.into(), set_live);
+        } else {
+            _ = (url, set_live);
+        }
+    }
+
+    Signal::derive(cx, move || {
+        live.get().or_else(|| initial.get()).unwrap_or_default()
+    })
+}
+
+fn initial_value<T>(name: &str) -> T
+where
+    T: DeserializeOwned + Default,
+{
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "ssr")] {
+            server::current_value(name)
+                .and_then(|value| serde_json::from_value(value).ok())
+                .unwrap_or_default()
+        } else {
+            _ = name;
+            T::default()
+        }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+mod client {
+    use super::ServerSignalMessage;
+    use leptos_reactive::{on_cleanup, Scope, SignalSet, WriteSignal};
+    use serde::de::DeserializeOwned;
+    use serde_json::Value;
+    use std::{cell::RefCell, rc::Rc};
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    type Connection = (
+        web_sys::EventSource,
+        Closure<dyn FnMut(web_sys::MessageEvent)>,
+    );
+
+    struct Subscription<T: 'static> {
+        name: Rc<str>,
+        url: Rc<str>,
+        // the version and JSON value last received
+        state: Rc<RefCell<Option<(u64, Value)>>>,
+        connection: Rc<RefCell<Option<Connection>>>,
+        set_live: WriteSignal<Option<T>>,
+    }
+
+    impl<T> Clone for Subscription<T> {
+        fn clone(&self) -> Self {
+            Self {
+                name: Rc::clone(&self.name),
+                url: Rc::clone(&self.url),
+                state: Rc::clone(&self.state),
+                connection: Rc::clone(&self.connection),
+                set_live: self.set_live,
+            }
+        }
+    }
+
+    pub(super) fn subscribe<T>(
+        cx: Scope,
+        name: Rc<str>,
+        url: Rc<str>,
+        set_live: WriteSignal<Option<T>>,
+    ) where
+        T: DeserializeOwned + 'static,
+    {
+        let subscription = Subscription {
+            name,
+            url,
+            state: Default::default(),
+            connection: Default::default(),
+            set_live,
+        };
+        subscription.connect();
+        on_cleanup(cx, move || subscription.close());
+    }
+
+    impl<T> Subscription<T>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        fn connect(&self) {
+            let source = match web_sys::EventSource::new(&self.url) {
+                Ok(source) => source,
+                Err(e) => {
+                    web_sys::console::error_1(
+                        &format!(
+                            "[use_server_signal] couldn't connect to {}: {e:?}",
+                            self.url
+                        )
+                        .into(),
+                    );
+                    return;
+                }
+            };
+            let on_message = Closure::new({
+                let subscription = self.clone();
+                move |ev: web_sys::MessageEvent| subscription.receive(ev)
+            });
+            _ = source.add_event_listener_with_callback(
+                &self.name,
+                on_message.as_ref().unchecked_ref(),
+            );
+            *self.connection.borrow_mut() = Some((source, on_message));
+        }
+
+        fn close(&self) {
+            if let Some((source, _)) = self.connection.borrow_mut().take() {
+                source.close();
+            }
+        }
+
+        fn receive(&self, ev: web_sys::MessageEvent) {
+            let Some(message) = ev
+                .data()
+                .as_string()
+                .and_then(|data| serde_json::from_str(&data).ok())
+            else {
+                return;
+            };
+
+            let mut state = self.state.borrow_mut();
+            let missed_patch = match message {
+                ServerSignalMessage::Full { version, value } => {
+                    *state = Some((version, value));
+                    false
+                }
+                ServerSignalMessage::Patch { version, ops } => {
+                    match &mut *state {
+                        Some((current, value)) if version == *current + 1 => {
+                            for op in &ops {
+                                op.apply(value);
+                            }
+                            *current = version;
+                            false
+                        }
+                        // already up to date
+                        Some((current, _)) if version <= *current => return,
+                        _ => true,
+                    }
+                }
+            };
+
+            // reconnect, which starts from the full value, once this
+            // listener has returned
+            if missed_patch {
+                *state = None;
+                let subscription = self.clone();
+                leptos_reactive::spawn_local(async move {
+                    subscription.close();
+                    subscription.connect();
+                });
+                return;
+            }
+
+            if let Some((_, value)) = &*state {
+                if let Ok(value) = serde_json::from_value(value.clone()) {
+                    self.set_live.set(Some(value));
+                }
+            }
+        }
+    }
+}
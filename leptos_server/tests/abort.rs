@@ -0,0 +1,96 @@
+#![cfg(feature = "ssr")]
+
+mod common;
+
+use common::settle;
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+
+// sets the flag when a request's future is dropped
+struct DropFlag(Rc<Cell<bool>>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.set(true);
+    }
+}
+
+#[tokio::test]
+async fn disposing_scope_aborts_pending_resources_and_actions() {
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            let before = runtime_stats(cx).aborted_requests;
+
+            let resource_dropped = Rc::new(Cell::new(false));
+            let action_dropped = Rc::new(Cell::new(false));
+            let (action, route_disposer) = cx.run_child_scope({
+                let resource_dropped = Rc::clone(&resource_dropped);
+                let action_dropped = Rc::clone(&action_dropped);
+                move |cx| {
+                    create_resource(
+                        cx,
+                        || (),
+                        move |_| {
+                            let flag = DropFlag(Rc::clone(&resource_dropped));
+                            async move {
+                                let _flag = flag;
+                                futures::future::pending::<()>().await;
+                            }
+                        },
+                    );
+                    create_action(cx, move |_: &()| {
+                        let flag = DropFlag(Rc::clone(&action_dropped));
+                        async move {
+                            let _flag = flag;
+                            futures::future::pending::<()>().await;
+                        }
+                    })
+                }
+            });
+            action.dispatch(());
+            settle().await;
+            assert!(!resource_dropped.get());
+            assert!(!action_dropped.get());
+
+            // e.g., navigating away from the route that created them
+            route_disposer.dispose();
+            settle().await;
+            assert!(resource_dropped.get());
+            assert!(action_dropped.get());
+            assert_eq!(runtime_stats(cx).aborted_requests, before + 2);
+
+            disposer.dispose();
+            runtime.dispose();
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn disposing_runtime_aborts_pending_resources() {
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let runtime = create_runtime();
+            let (cx, _) = raw_scope_and_disposer(runtime);
+            let dropped = Rc::new(Cell::new(false));
+            create_resource(cx, || (), {
+                let dropped = Rc::clone(&dropped);
+                move |_| {
+                    let flag = DropFlag(Rc::clone(&dropped));
+                    async move {
+                        let _flag = flag;
+                        futures::future::pending::<()>().await;
+                    }
+                }
+            });
+            settle().await;
+            assert!(!dropped.get());
+
+            // e.g., a server-side render whose client has disconnected
+            runtime.dispose();
+            settle().await;
+            assert!(dropped.get());
+        })
+        .await;
+}
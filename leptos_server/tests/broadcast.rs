@@ -0,0 +1,42 @@
+// cross-tab invalidation only happens in the browser
+#![cfg(all(feature = "ssr", feature = "broadcast"))]
+
+mod common;
+
+use common::settle;
+use leptos::*;
+use leptos_server::invalidate_on_broadcast;
+use std::{cell::Cell, rc::Rc};
+
+#[tokio::test]
+async fn broadcast_invalidation_does_nothing_on_the_server() {
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let runtime = create_runtime();
+            let ((action, fetches), _, _) =
+                run_scope_undisposed(runtime, |cx| {
+                    let fetches = Rc::new(Cell::new(0));
+                    let resource = create_resource(cx, || (), {
+                        let fetches = Rc::clone(&fetches);
+                        move |_| {
+                            fetches.set(fetches.get() + 1);
+                            async {}
+                        }
+                    });
+                    invalidate_on_broadcast(cx, "count", resource);
+                    let action =
+                        create_action(cx, |_: &()| async { Ok::<_, ()>(1) })
+                            .broadcast_invalidation("count");
+                    (action, fetches)
+                });
+            settle().await;
+            let initial = fetches.get();
+
+            action.dispatch(());
+            settle().await;
+            assert_eq!(action.value().get_untracked(), Some(Ok(1)));
+            assert_eq!(fetches.get(), initial);
+            runtime.dispose();
+        })
+        .await;
+}
@@ -0,0 +1,9 @@
+/// Lets spawned actions and resource loads run to completion.
+///
+/// Actions and resources are spawned on the Tokio `LocalSet` when running on
+/// the server, so tests run inside one and yield to it.
+pub async fn settle() {
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+}
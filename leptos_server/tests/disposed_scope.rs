@@ -0,0 +1,103 @@
+#![cfg(feature = "ssr")]
+
+mod common;
+
+use common::settle;
+use futures::channel::oneshot;
+use leptos::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+thread_local! {
+    static PANICKED: Cell<bool> = Cell::new(false);
+}
+
+// a panic in a spawned task doesn't fail the test, so it is recorded instead
+fn record_panics() {
+    std::panic::set_hook(Box::new(|_| PANICKED.with(|p| p.set(true))));
+}
+
+// an action whose future waits until it is sent a value
+fn create_save_action(cx: Scope) -> (Action<(), u32>, oneshot::Sender<u32>) {
+    let (tx, rx) = oneshot::channel();
+    let rx = Rc::new(RefCell::new(Some(rx)));
+    let action = create_action(cx, move |_: &()| {
+        let rx = rx.borrow_mut().take().expect("dispatched once");
+        async move { rx.await.unwrap_or_default() }
+    });
+    (action, tx)
+}
+
+// a multi-action whose only submission waits until it is sent a value
+fn create_save_multi_action(
+    cx: Scope,
+) -> (MultiAction<(), ()>, oneshot::Sender<()>) {
+    let (tx, rx) = oneshot::channel();
+    let rx = Rc::new(RefCell::new(Some(rx)));
+    let multi_action = create_multi_action(cx, move |_: &()| {
+        let rx = rx.borrow_mut().take().expect("dispatched once");
+        async move { _ = rx.await }
+    });
+    (multi_action, tx)
+}
+
+#[tokio::test]
+async fn actions_that_finish_after_disposal_do_not_panic() {
+    record_panics();
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let runtime = create_runtime();
+            let (cx, _) = raw_scope_and_disposer(runtime);
+
+            // e.g., the user closes a modal while its save is in flight
+            let (((action, tx), (multi_action, multi_tx)), modal_disposer) = cx
+                .run_child_scope(|cx| {
+                    (create_save_action(cx), create_save_multi_action(cx))
+                });
+            action.dispatch(());
+            multi_action.dispatch(());
+            settle().await;
+            modal_disposer.dispose();
+            _ = tx.send(1);
+            _ = multi_tx.send(());
+            settle().await;
+
+            // the runtime is disposed while the action's scope is still alive,
+            // e.g., a server-side render that has finished
+            let (action, tx) = create_save_action(cx);
+            let (multi_action, multi_tx) = create_save_multi_action(cx);
+            action.dispatch(());
+            multi_action.dispatch(());
+            settle().await;
+            runtime.dispose();
+            _ = tx.send(2);
+            _ = multi_tx.send(());
+            settle().await;
+        })
+        .await;
+    assert!(!PANICKED.with(Cell::get));
+}
+
+#[tokio::test]
+async fn setting_a_disposed_resource_does_not_panic() {
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            let (resource, child_disposer) = cx.run_child_scope(|cx| {
+                create_resource(cx, || (), |_| async { 1 })
+            });
+            settle().await;
+            child_disposer.dispose();
+
+            resource.set(2);
+            resource.refetch();
+            assert_eq!(resource.try_update(|value| *value = Some(3)), None);
+
+            disposer.dispose();
+            runtime.dispose();
+        })
+        .await;
+}
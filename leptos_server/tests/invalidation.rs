@@ -0,0 +1,126 @@
+#![cfg(feature = "ssr")]
+
+mod common;
+
+use common::settle;
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+
+fn counting_resource(cx: Scope) -> (Resource<(), usize>, Rc<Cell<usize>>) {
+    let fetches = Rc::new(Cell::new(0));
+    let resource = create_resource(cx, || (), {
+        let fetches = Rc::clone(&fetches);
+        move |_| {
+            fetches.set(fetches.get() + 1);
+            let fetches = fetches.get();
+            async move { fetches }
+        }
+    });
+    (resource, fetches)
+}
+
+#[tokio::test]
+async fn action_refetches_resource_each_time_it_resolves() {
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let runtime = create_runtime();
+            let ((action, fetches), _, _) =
+                run_scope_undisposed(runtime, |cx| {
+                    let (resource, fetches) = counting_resource(cx);
+                    let action = create_action(cx, |ok: &bool| {
+                        let ok = *ok;
+                        async move {
+                            if ok {
+                                Ok(())
+                            } else {
+                                Err(())
+                            }
+                        }
+                    })
+                    .invalidates(resource);
+                    (action, fetches)
+                });
+            settle().await;
+            let initial = fetches.get();
+
+            action.dispatch(true);
+            settle().await;
+            assert_eq!(fetches.get(), initial + 1);
+
+            action.dispatch(false);
+            settle().await;
+            assert_eq!(fetches.get(), initial + 2);
+            runtime.dispose();
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn action_can_skip_refetching_on_error() {
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let runtime = create_runtime();
+            let ((action, fetches), _, _) =
+                run_scope_undisposed(runtime, |cx| {
+                    let (resource, fetches) = counting_resource(cx);
+                    let action = create_action(cx, |ok: &bool| {
+                        let ok = *ok;
+                        async move {
+                            if ok {
+                                Ok(())
+                            } else {
+                                Err(())
+                            }
+                        }
+                    })
+                    .invalidates_on_success(resource);
+                    (action, fetches)
+                });
+            settle().await;
+            let initial = fetches.get();
+
+            action.dispatch(false);
+            settle().await;
+            assert_eq!(fetches.get(), initial);
+
+            action.dispatch(true);
+            settle().await;
+            assert_eq!(fetches.get(), initial + 1);
+            runtime.dispose();
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn multi_action_refetches_resource_once_per_submission() {
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let runtime = create_runtime();
+            let ((action, fetches), _, _) =
+                run_scope_undisposed(runtime, |cx| {
+                    let (resource, fetches) = counting_resource(cx);
+                    let action = create_multi_action(cx, |ok: &bool| {
+                        let ok = *ok;
+                        async move {
+                            if ok {
+                                Ok(())
+                            } else {
+                                Err(())
+                            }
+                        }
+                    })
+                    .invalidates_on_success(resource);
+                    (action, fetches)
+                });
+            settle().await;
+            let initial = fetches.get();
+
+            action.dispatch(true);
+            action.dispatch(false);
+            action.dispatch(true);
+            settle().await;
+            assert_eq!(fetches.get(), initial + 2);
+            runtime.dispose();
+        })
+        .await;
+}
@@ -0,0 +1,136 @@
+#![cfg(feature = "ssr")]
+
+use leptos::{
+    server_fn::middleware::{ServerFnInfo, ServerFnMiddleware},
+    *,
+};
+use leptos_server::{server_fn_by_path, Payload};
+use std::{cell::RefCell, future::Future, pin::Pin};
+
+#[derive(Clone)]
+struct SignedIn(bool);
+
+thread_local! {
+    static LOG: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+fn log(entry: String) {
+    LOG.with(|log| log.borrow_mut().push(entry));
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|log| std::mem::take(&mut *log.borrow_mut()))
+}
+
+struct RequireAuth;
+
+impl ServerFnMiddleware<Scope> for RequireAuth {
+    fn before<'a>(
+        &'a self,
+        cx: &'a Scope,
+        info: &'a ServerFnInfo,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ServerFnError>> + 'a>> {
+        Box::pin(async move {
+            log(format!("auth {}", info.name));
+            match use_context::<SignedIn>(*cx) {
+                Some(SignedIn(true)) => Ok(()),
+                _ => Err(ServerFnError::ServerError("not signed in".into())),
+            }
+        })
+    }
+}
+
+struct LogCalls(&'static str);
+
+impl<T> ServerFnMiddleware<T> for LogCalls {
+    fn before<'a>(
+        &'a self,
+        _cx: &'a T,
+        info: &'a ServerFnInfo,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ServerFnError>> + 'a>> {
+        Box::pin(async move {
+            log(format!("{} before {}", self.0, info.name));
+            Ok(())
+        })
+    }
+
+    fn after(&self, _cx: &T, info: &ServerFnInfo, succeeded: bool) {
+        log(format!("{} after {} {succeeded}", self.0, info.name));
+    }
+}
+
+#[server(DeletePost, "/api")]
+#[middleware(LogCalls("outer"), RequireAuth)]
+#[middleware(LogCalls("inner"))]
+async fn delete_post(cx: Scope) -> Result<(), ServerFnError> {
+    log("body".to_string());
+    Ok(())
+}
+
+async fn call_directly(signed_in: bool) -> Result<(), ServerFnError> {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    provide_context(cx, SignedIn(signed_in));
+    let result = delete_post(cx).await;
+    disposer.dispose();
+    runtime.dispose();
+    result
+}
+
+async fn call_over_http(signed_in: bool) -> Result<Payload, ServerFnError> {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    provide_context(cx, SignedIn(signed_in));
+    let server_fn = server_fn_by_path(DeletePost::url()).unwrap();
+    let result = server_fn.call(cx, &[]).await;
+    disposer.dispose();
+    runtime.dispose();
+    result
+}
+
+#[tokio::test]
+async fn middleware_runs_in_order_around_the_body() {
+    call_directly(true).await.unwrap();
+    let direct = take_log();
+    call_over_http(true).await.unwrap();
+    let fetched = take_log();
+
+    let expected = [
+        "outer before delete_post",
+        "auth delete_post",
+        "inner before delete_post",
+        "body",
+        "inner after delete_post true",
+        "outer after delete_post true",
+    ];
+    assert_eq!(direct, expected);
+    assert_eq!(fetched, expected);
+}
+
+#[tokio::test]
+async fn middleware_can_stop_the_call() {
+    let direct = call_directly(false).await;
+    assert!(
+        matches!(&direct, Err(ServerFnError::ServerError(e)) if e == "not signed in"),
+        "{direct:?}"
+    );
+    assert_eq!(
+        take_log(),
+        [
+            "outer before delete_post",
+            "auth delete_post",
+            "outer after delete_post false",
+        ]
+    );
+
+    let fetched = call_over_http(false).await;
+    assert!(fetched.is_err());
+    assert_eq!(
+        take_log(),
+        [
+            "outer before delete_post",
+            "auth delete_post",
+            "outer after delete_post false",
+        ]
+    );
+}
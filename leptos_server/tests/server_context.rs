@@ -0,0 +1,68 @@
+#![cfg(feature = "ssr")]
+
+use leptos::*;
+use leptos_server::{
+    server_fn_by_path, use_server_context, with_server_context,
+    LeptosServerContext, Payload,
+};
+
+#[derive(Clone)]
+struct Config {
+    greeting: String,
+}
+
+#[server(Greet, "/api")]
+async fn greet(name: String) -> Result<String, ServerFnError> {
+    let config = use_server_context::<Config>()?;
+    Ok(format!("{}, {name}!", config.greeting))
+}
+
+fn fake_config() {
+    LeptosServerContext::insert(Config {
+        greeting: "Hello".to_string(),
+    });
+}
+
+#[tokio::test]
+async fn server_fn_uses_injected_service_when_called_directly() {
+    let greeting = with_server_context(|| async {
+        fake_config();
+        greet("alice".to_string()).await
+    })
+    .await;
+
+    assert_eq!(greeting.unwrap(), "Hello, alice!");
+}
+
+#[tokio::test]
+async fn server_fn_uses_injected_service_when_called_over_http() {
+    let fetched = with_server_context(|| async {
+        fake_config();
+        let runtime = create_runtime();
+        let (cx, disposer) = raw_scope_and_disposer(runtime);
+        let server_fn = server_fn_by_path(Greet::url()).unwrap();
+        let fetched = server_fn.call(cx, b"name=bob").await;
+        disposer.dispose();
+        runtime.dispose();
+        fetched
+    })
+    .await
+    .unwrap();
+
+    assert!(
+        matches!(&fetched, Payload::Url(json) if json == "\"Hello, bob!\""),
+        "{fetched:?}"
+    );
+}
+
+#[tokio::test]
+async fn missing_service_is_a_descriptive_error() {
+    let err = with_server_context(|| greet("carol".to_string()))
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&err, ServerFnError::ServerError(msg) if msg.contains("Config")),
+        "{err:?}"
+    );
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "ssr")]
+
+use leptos::*;
+use leptos_server::{server_fn_by_path, Payload};
+
+// the first version of this server fn only took a `query`
+#[server(SearchPosts, "/api")]
+async fn search_posts(
+    query: String,
+    tag: Option<String>,
+    #[server_default = 20] per_page: u32,
+) -> Result<String, ServerFnError> {
+    Ok(format!("{query} {tag:?} {per_page}"))
+}
+
+async fn call_with(payload: &[u8]) -> Result<Payload, ServerFnError> {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let server_fn = server_fn_by_path(SearchPosts::url()).unwrap();
+    let result = server_fn.call(cx, payload).await;
+    disposer.dispose();
+    runtime.dispose();
+    result
+}
+
+#[tokio::test]
+async fn old_payloads_are_accepted_by_the_new_signature() {
+    let old = call_with(b"query=rust").await.unwrap();
+    assert!(
+        matches!(&old, Payload::Url(json) if json == r#""rust None 20""#),
+        "{old:?}"
+    );
+
+    let new = call_with(b"query=rust&tag=async&per_page=5").await.unwrap();
+    assert!(
+        matches!(&new, Payload::Url(json) if json == r#""rust Some(\"async\") 5""#),
+        "{new:?}"
+    );
+}
+
+#[test]
+fn missing_json_arguments_are_defaulted() {
+    let args =
+        serde_json::from_str::<SearchPosts>(r#"{"query":"rust"}"#).unwrap();
+    assert_eq!(args.query, "rust");
+    assert_eq!(args.tag, None);
+    assert_eq!(args.per_page, 20);
+}
+
+#[test]
+fn none_arguments_are_left_out_of_the_query_string() {
+    let args = SearchPosts {
+        query: "rust".to_string(),
+        tag: None,
+        per_page: 20,
+    };
+    assert_eq!(
+        server_fn::serde_qs::to_string(&args).unwrap(),
+        "query=rust&per_page=20"
+    );
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "ssr")]
+
+use leptos::*;
+use leptos_server::{server_fn_by_path, Payload, ServerFnContext};
+
+#[derive(Clone)]
+struct Session {
+    user: String,
+}
+
+#[server(SessionUser, "/api")]
+async fn session_user(cx: Scope) -> Result<String, ServerFnError> {
+    use_context::<Session>(cx)
+        .map(|session| session.user)
+        .ok_or_else(|| ServerFnError::ServerError("no session".to_string()))
+}
+
+// what a server integration sets up for each request
+fn provide_request_context(cx: Scope) {
+    provide_context(
+        cx,
+        Session {
+            user: "alice".to_string(),
+        },
+    );
+    ServerFnContext::provide(cx);
+}
+
+#[tokio::test]
+async fn server_fn_sees_the_same_context_when_rendering_and_fetching() {
+    // called directly by a component while rendering on the server
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    provide_request_context(cx);
+    let (component_cx, _) = cx.run_child_scope(|cx| {
+        // not part of the request context, so not visible to the server fn
+        provide_context(
+            cx,
+            Session {
+                user: "mallory".to_string(),
+            },
+        );
+        cx
+    });
+    let rendered = session_user(component_cx).await.unwrap();
+    disposer.dispose();
+    runtime.dispose();
+
+    // called over HTTP, the way the integrations' `handle_server_fns` does
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    provide_request_context(cx);
+    let server_fn = server_fn_by_path(SessionUser::url()).unwrap();
+    let fetched = server_fn.call(cx, &[]).await.unwrap();
+    disposer.dispose();
+    runtime.dispose();
+
+    assert_eq!(rendered, "alice");
+    assert!(
+        matches!(&fetched, Payload::Url(json) if json == "\"alice\""),
+        "{fetched:?}"
+    );
+}
+
+#[tokio::test]
+async fn server_fn_sees_contexts_provided_to_the_request_scope_later() {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    ServerFnContext::provide(cx);
+    // like a context provided by the app's root component while rendering
+    provide_context(
+        cx,
+        Session {
+            user: "bob".to_string(),
+        },
+    );
+    let user = session_user(cx).await.unwrap();
+    disposer.dispose();
+    runtime.dispose();
+
+    assert_eq!(user, "bob");
+}
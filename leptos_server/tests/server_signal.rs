@@ -0,0 +1,114 @@
+#![cfg(all(feature = "ssr", feature = "server-signal"))]
+
+use futures::StreamExt;
+use leptos_server::{create_server_signal, PatchOp, ServerSignalMessage};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Room {
+    title: String,
+    members: Vec<String>,
+    topic: Option<String>,
+}
+
+#[tokio::test]
+async fn subscribers_get_the_full_value_then_patches() {
+    let count = create_server_signal::<i32>("test-count");
+    count.set(1);
+
+    let mut updates = count.subscribe();
+    count.update(|count| *count += 1);
+    // unchanged, so nothing is sent
+    count.set(2);
+    count.set(5);
+
+    assert_eq!(
+        updates.next().await,
+        Some(ServerSignalMessage::Full {
+            version: 1,
+            value: json!(1)
+        })
+    );
+    assert_eq!(
+        updates.next().await,
+        Some(ServerSignalMessage::Patch {
+            version: 2,
+            ops: vec![PatchOp::Set {
+                path: vec![],
+                value: json!(2)
+            }]
+        })
+    );
+    assert_eq!(
+        updates.next().await,
+        Some(ServerSignalMessage::Patch {
+            version: 3,
+            ops: vec![PatchOp::Set {
+                path: vec![],
+                value: json!(5)
+            }]
+        })
+    );
+
+    // signals with the same name share the value
+    assert_eq!(create_server_signal::<i32>("test-count").get(), 5);
+}
+
+#[tokio::test]
+async fn struct_patches_only_contain_changed_fields() {
+    let room = create_server_signal::<Room>("test-room");
+    let mut updates = room.subscribe();
+    let Some(ServerSignalMessage::Full { mut value, .. }) =
+        updates.next().await
+    else {
+        panic!("expected the full value first");
+    };
+
+    room.update(|room| {
+        room.title = "Lobby".to_string();
+        room.members.push("alice".to_string());
+    });
+    room.update(|room| room.topic = Some("hello".to_string()));
+
+    let Some(ServerSignalMessage::Patch { ops, .. }) = updates.next().await
+    else {
+        panic!("expected a patch");
+    };
+    // the order of keys depends on serde_json's features
+    assert_eq!(ops.len(), 2);
+    assert!(ops.contains(&PatchOp::Set {
+        path: vec!["members".to_string()],
+        value: json!(["alice"])
+    }));
+    assert!(ops.contains(&PatchOp::Set {
+        path: vec!["title".to_string()],
+        value: json!("Lobby")
+    }));
+    ops.iter().for_each(|op| op.apply(&mut value));
+
+    let Some(ServerSignalMessage::Patch { ops, .. }) = updates.next().await
+    else {
+        panic!("expected a patch");
+    };
+    assert_eq!(ops.len(), 1);
+    ops.iter().for_each(|op| op.apply(&mut value));
+
+    assert_eq!(serde_json::from_value::<Room>(value).unwrap(), room.get());
+}
+
+#[test]
+fn remove_deletes_keys() {
+    let mut value = json!({ "a": { "b": 1, "c": 2 } });
+    PatchOp::Remove {
+        path: vec!["a".to_string(), "b".to_string()],
+    }
+    .apply(&mut value);
+    assert_eq!(value, json!({ "a": { "c": 2 } }));
+    PatchOp::Set {
+        path: vec!["d".to_string(), "e".to_string()],
+        value: Value::Bool(true),
+    }
+    .apply(&mut value);
+    assert_eq!(value, json!({ "a": { "c": 2 }, "d": { "e": true } }));
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "ssr")]
+
+mod common;
+
+use common::settle;
+use leptos::*;
+use std::{cell::RefCell, rc::Rc};
+
+// the hook is per thread, and each test runs on its own thread
+fn collect_unhandled_errors() -> Rc<RefCell<Vec<String>>> {
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    set_unhandled_error_hook({
+        let errors = Rc::clone(&errors);
+        move |err| errors.borrow_mut().push(err.error.to_string())
+    });
+    errors
+}
+
+async fn failing(message: &'static str) -> Result<(), String> {
+    Err(message.to_string())
+}
+
+#[tokio::test]
+async fn resource_errors_that_nothing_reads_are_reported() {
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let errors = collect_unhandled_errors();
+            let runtime = create_runtime();
+            let ((read_value, mapped_value), _, _) =
+                run_scope_undisposed(runtime, |cx| {
+                    create_resource(cx, || (), |_| failing("unread"))
+                        .report_unhandled_errors();
+                    create_resource(
+                        cx,
+                        || (),
+                        |_| async { Ok::<_, String>(()) },
+                    )
+                    .report_unhandled_errors();
+                    // only resources set up to report their errors do
+                    create_resource(cx, || (), |_| failing("not reported"));
+                    let read = create_resource(cx, || (), |_| failing("read"))
+                        .report_unhandled_errors();
+                    let mapped =
+                        create_resource(cx, || (), |_| failing("mapped"))
+                            .report_unhandled_errors()
+                            .map_err(cx, |err| err.len());
+
+                    // reading the resources while they load handles their
+                    // errors
+                    let read_value = create_rw_signal(cx, None);
+                    create_isomorphic_effect(cx, move |_| {
+                        read_value.set(read.get())
+                    });
+                    let mapped_value = create_rw_signal(cx, None);
+                    create_isomorphic_effect(cx, move |_| {
+                        mapped_value.set(mapped.get())
+                    });
+                    (read_value, mapped_value)
+                });
+            settle().await;
+
+            assert_eq!(*errors.borrow(), ["unread"]);
+            assert_eq!(read_value.get(), Some(Err("read".to_string())));
+            assert_eq!(mapped_value.get(), Some(Err(6)));
+            runtime.dispose();
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn action_errors_are_reported_unless_the_value_is_used() {
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let errors = collect_unhandled_errors();
+            let runtime = create_runtime();
+            let ((ignored, shown, peeked), _, _) =
+                run_scope_undisposed(runtime, |cx| {
+                    let ignored =
+                        create_action(cx, |_: &()| failing("ignored"))
+                            .report_unhandled_errors();
+                    let shown = create_action(cx, |_: &()| failing("shown"))
+                        .report_unhandled_errors();
+                    create_isomorphic_effect(cx, move |_| {
+                        shown.value().track()
+                    });
+                    // asking for the value without tracking it doesn't
+                    // handle the errors
+                    let peeked = create_action(cx, |_: &()| failing("peeked"))
+                        .report_unhandled_errors();
+                    _ = peeked.value();
+                    (ignored, shown, peeked)
+                });
+
+            ignored.dispatch(());
+            shown.dispatch(());
+            peeked.dispatch(());
+            settle().await;
+
+            assert_eq!(*errors.borrow(), ["ignored", "peeked"]);
+            runtime.dispose();
+        })
+        .await;
+}
@@ -43,6 +43,13 @@
 //!
 //! **Important Note:** You must enable one of `csr`, `hydrate`, or `ssr` to tell Leptos
 //! which mode your app is operating in.
+//!
+//! # Hydration
+//! Each tag rendered on the server is marked with a `data-leptos-meta` attribute. When the app
+//! hydrates, every component claims the server-rendered tag with its key, and the tags that no
+//! component claimed, because the client rendered a different set of them than the server
+//! did, are removed once hydration has finished, including any `<Suspense/>` whose resources
+//! the server is still streaming. Other content of the `<head>` is left alone.
 
 use cfg_if::cfg_if;
 use indexmap::IndexMap;
@@ -90,6 +97,16 @@ pub struct MetaContext {
     pub body: BodyContext,
     /// Other metadata tags.
     pub tags: MetaTagsContext,
+    /// The title and tags that have already been sent to the client.
+    #[cfg(feature = "ssr")]
+    streamed: Rc<RefCell<StreamedHead>>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Default, Debug)]
+struct StreamedHead {
+    title: Option<String>,
+    tags: IndexMap<Cow<'static, str>, String>,
 }
 
 /// Manages all of the element created by components.
@@ -113,15 +130,26 @@ impl std::fmt::Debug for MetaTagsContext {
     }
 }
 
+// the attribute that marks the tags rendered by this crate, with their ids
+const KEY_ATTR: &str = "data-leptos-meta";
+
 impl MetaTagsContext {
     /// Converts metadata tags into an HTML string.
     #[cfg(any(feature = "ssr", docs))]
     pub fn as_string(&self) -> String {
+        self.rendered().into_values().collect()
+    }
+
+    // renders each tag, by id
+    #[cfg(feature = "ssr")]
+    fn rendered(&self) -> IndexMap<Cow<'static, str>, String> {
         self.els
             .borrow()
             .iter()
-            .map(|(_, (builder_el, cx, _))| {
-                builder_el.clone().into_view(*cx).render_to_string(*cx)
+            .map(|(id, (builder_el, cx, _))| {
+                let html =
+                    builder_el.clone().into_view(*cx).render_to_string(*cx);
+                (id.clone(), html.into_owned())
             })
             .collect()
     }
@@ -137,12 +165,10 @@ impl MetaTagsContext {
             if #[cfg(any(feature = "csr", feature = "hydrate"))] {
                 use leptos::document;
 
-                let element_to_hydrate = document()
-                    .get_element_by_id(&id);
-
-                let el = element_to_hydrate.unwrap_or_else({
-                    let builder_el = builder_el.clone();
-                    move || {
+                _ = builder_el.set_attribute(KEY_ATTR, &id);
+                let el = match self.unclaimed(&id) {
+                    Some(server_el) => self.claim(server_el, &builder_el),
+                    None => {
                         let head = document().head().unwrap_throw();
                         head
                             .append_child(&builder_el)
@@ -150,7 +176,7 @@ impl MetaTagsContext {
 
                         (*builder_el).clone().unchecked_into()
                     }
-                });
+                };
 
                 on_cleanup(cx, {
                     let el = el.clone();
@@ -169,10 +195,82 @@ impl MetaTagsContext {
                     .insert(id, (builder_el.into_any(), cx, Some(el)));
 
             } else {
+                let builder_el = builder_el.attr(KEY_ATTR, id.clone());
                 self.els.borrow_mut().insert(id, (builder_el, cx, None));
             }
         }
     }
+
+    // the server-rendered tag with the given key, if no component has
+    // claimed it yet
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn unclaimed(&self, id: &str) -> Option<web_sys::Element> {
+        if self.els.borrow().contains_key(id) {
+            return None;
+        }
+        document()
+            .head()?
+            .query_selector(&format!("[{KEY_ATTR}=\"{id}\"]"))
+            .ok()
+            .flatten()
+    }
+
+    // keeps the server's tag if it is the same as the one the client
+    // rendered, so that scripts don't run again and stylesheets aren't
+    // fetched again, and replaces it otherwise; `<meta>` tags are always
+    // replaced, as their attributes can be reactive
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn claim(
+        &self,
+        server_el: web_sys::Element,
+        builder_el: &HtmlElement<AnyElement>,
+    ) -> web_sys::Element {
+        // the client's tags don't have hydration keys
+        _ = server_el.remove_attribute("leptos-hk");
+        if !builder_el.has_attribute("id") {
+            _ = server_el.remove_attribute("id");
+        }
+
+        let builder_node: &web_sys::Node = builder_el;
+        if !server_el.tag_name().eq_ignore_ascii_case("meta")
+            && server_el.is_equal_node(Some(builder_node))
+        {
+            server_el
+        } else {
+            _ = server_el.replace_with_with_node_1(builder_el);
+            (**builder_el).clone().unchecked_into()
+        }
+    }
+
+    // removes the server-rendered tags that no component claimed while
+    // hydrating
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn remove_unclaimed(&self) {
+        let Some(Ok(tags)) = document()
+            .head()
+            .map(|head| head.query_selector_all(&format!("[{KEY_ATTR}]")))
+        else {
+            return;
+        };
+        let els = self.els.borrow();
+        for index in 0..tags.length() {
+            let Some(tag) = tags
+                .get(index)
+                .and_then(|tag| tag.dyn_into::<web_sys::Element>().ok())
+            else {
+                continue;
+            };
+            let claimed = tag
+                .get_attribute(KEY_ATTR)
+                .and_then(|id| els.get(id.as_str()))
+                .and_then(|(_, _, el)| el.as_ref())
+                .map(|el| el == &tag)
+                .unwrap_or(false);
+            if !claimed {
+                tag.remove();
+            }
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
@@ -192,7 +290,7 @@ impl MetaTagsContext {
 /// (for example, by a server-rendering integration.)
 pub fn provide_meta_context(cx: Scope) {
     if use_context::<MetaContext>(cx).is_none() {
-        provide_context(cx, MetaContext::new());
+        provide_context(cx, MetaContext::hydrating(cx));
     }
 }
 
@@ -217,7 +315,7 @@ pub fn use_head(cx: Scope) -> MetaContext {
                  be safe, you should provide_meta_context(cx) somewhere in \
                  the root of the app."
             );
-            let meta = MetaContext::new();
+            let meta = MetaContext::hydrating(cx);
             provide_context(cx, meta.clone());
             meta
         }
@@ -228,11 +326,29 @@ pub fn use_head(cx: Scope) -> MetaContext {
 impl MetaContext {
     /// Creates an empty [MetaContext].
     pub fn new() -> Self {
-        Default::default()
+        Self::default()
+    }
+
+    // creates the context provided to `cx`; when it is created while hydrating, the
+    // server-rendered tags that no component has claimed are removed once hydration has
+    // settled, as tags rendered under a `<Suspense/>` are only claimed once the resources
+    // they read arrive from the server
+    fn hydrating(cx: Scope) -> Self {
+        let meta = Self::new();
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        if leptos::leptos_dom::HydrationCtx::is_hydrating() {
+            let tags = meta.tags.clone();
+            leptos::on_hydration_settled(cx, move || tags.remove_unclaimed());
+        }
+        #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+        let _ = cx;
+        meta
     }
 
     #[cfg(feature = "ssr")]
-    /// Converts the existing metadata tags into HTML that can be injected into the document head.
+    /// Converts the existing metadata into HTML that can be injected into the document, split
+    /// into [`HeadParts`] so that a custom HTML shell can place each part where it wants it.
+    /// Use [`to_string`](ToString::to_string) on the result to get the whole `<head>` content.
     ///
     /// This should be called *after* the app’s component tree has been rendered into HTML, so that
     /// components can set meta tags.
@@ -258,29 +374,205 @@ impl MetaContext {
     ///      !app.into_view(cx).render_to_string(cx).contains("my title")
     ///   );
     ///   // `MetaContext::dehydrate()` gives you HTML that should be in the `<head>`
-    ///   assert!(use_head(cx).dehydrate().contains("<title>my title</title>"))
+    ///   let head = use_head(cx).dehydrate();
+    ///   assert_eq!(head.title.as_deref(), Some("<title>my title</title>"));
+    ///   assert!(head.to_string().contains("<title>my title</title>"))
     /// });
     /// # }
     /// ```
-    pub fn dehydrate(&self) -> String {
+    pub fn dehydrate(&self) -> HeadParts {
         use leptos::leptos_dom::HydrationCtx;
 
         let prev_key = HydrationCtx::peek();
-        let mut tags = String::new();
+        let mut parts = HeadParts {
+            html_attrs: self.html.as_string().unwrap_or_default(),
+            body_attrs: self.body.as_string().unwrap_or_default(),
+            ..Default::default()
+        };
 
         // Title
-        if let Some(title) = self.title.as_string() {
-            tags.push_str("<title>");
-            tags.push_str(&title);
-            tags.push_str("</title>");
+        let title = self.title.as_string();
+        parts.title = title.as_ref().map(|title| {
+            format!(
+                "<title>{}</title>",
+                leptos::leptos_dom::escape::escape_text(title)
+            )
+        });
+
+        // Other tags, in order and grouped by element
+        let rendered = self.tags.rendered();
+        for html in rendered.values() {
+            let name = html
+                .trim_start_matches('<')
+                .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                .next()
+                .unwrap_or_default();
+            let group = match name {
+                "meta" => Some(&mut parts.metas),
+                "link" => Some(&mut parts.links),
+                "script" => Some(&mut parts.scripts),
+                "style" => Some(&mut parts.styles),
+                _ => None,
+            };
+            if let Some(group) = group {
+                group.push(html.clone());
+            }
+            parts.tags.push(html.clone());
         }
-        tags.push_str(&self.tags.as_string());
 
         HydrationCtx::continue_from(prev_key);
-        tags
+        *self.streamed.borrow_mut() = StreamedHead {
+            title,
+            tags: rendered,
+        };
+        parts
+    }
+
+    #[cfg(feature = "ssr")]
+    /// Returns JavaScript that updates the `<head>` sent by [`dehydrate`](MetaContext::dehydrate)
+    /// to match the current title and metadata tags, or `None` if they have not changed since.
+    ///
+    /// When streaming HTML, the `<head>` is sent before any `<Suspense/>` has resolved, so a
+    /// [`Title`] or [`Meta`] that depends on a resource, or that is rendered inside a
+    /// `<Suspense/>`, only has its final value once the stream is complete. Integrations send
+    /// this script at the end of the stream, so that the document ends up with the right
+    /// metadata even before the app is hydrated. See [`generate_head_update_script`].
+    pub fn head_update_script(&self) -> Option<String> {
+        use leptos::leptos_dom::HydrationCtx;
+        use std::fmt::Write;
+
+        let prev_key = HydrationCtx::peek();
+        let title = self.title.as_string();
+        let rendered = self.tags.rendered();
+        HydrationCtx::continue_from(prev_key);
+
+        let mut streamed = self.streamed.borrow_mut();
+        let mut script = String::new();
+        if title.is_some() && title != streamed.title {
+            _ = write!(
+                script,
+                "document.title={};",
+                js_string(title.as_deref().unwrap_or_default())
+            );
+        }
+        for (id, html) in &streamed.tags {
+            if rendered.get(id) != Some(html) {
+                _ = write!(
+                    script,
+                    "document.head.querySelector({})?.remove();",
+                    js_string(&format!("[{KEY_ATTR}=\"{id}\"]"))
+                );
+            }
+        }
+        let added = rendered
+            .iter()
+            .filter(|(id, html)| streamed.tags.get(*id) != Some(*html))
+            .map(|(_, html)| html.as_str())
+            .collect::<String>();
+        if !added.is_empty() {
+            _ = write!(
+                script,
+                "document.head.insertAdjacentHTML(\"beforeend\",{});",
+                js_string(&added)
+            );
+        }
+        *streamed = StreamedHead {
+            title,
+            tags: rendered,
+        };
+
+        (!script.is_empty()).then_some(script)
     }
 }
 
+/// The document metadata returned by [`MetaContext::dehydrate`], split up by the part of the
+/// document it belongs to.
+///
+/// Each tag is already rendered to HTML. Its [`Display`](std::fmt::Display) implementation writes
+/// the content of the `<head>`: the title, then the other tags in the order they were created,
+/// so that styles and scripts apply in the order the app declared them. A custom shell that
+/// places each kind of tag itself can use the grouped fields instead. The attributes for
+/// `<html>` and `<body>` are not included, and should be written into the opening tags by the
+/// shell.
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// # #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+/// run_scope(create_runtime(), |cx| {
+///   provide_meta_context(cx);
+///
+///   let app = view! { cx,
+///     <Html lang="en"/>
+///     <Script src="/app.js"/>
+///     <Meta name="description" content="A page"/>
+///     <Title text="my title"/>
+///   };
+///   _ = app.into_view(cx).render_to_string(cx);
+///
+///   let head = use_head(cx).dehydrate();
+///   assert_eq!(head.html_attrs, " lang=\"en\"");
+///   assert_eq!(head.metas.len(), 1);
+///   assert_eq!(head.scripts.len(), 1);
+///   let html = head.to_string();
+///   assert!(html.starts_with("<title>my title</title><script"));
+///   assert!(html.ends_with(&head.metas[0]));
+/// });
+/// # }
+/// ```
+#[cfg(feature = "ssr")]
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct HeadParts {
+    /// The `<title>` element, if a title has been set.
+    pub title: Option<String>,
+    /// Every other element, in the order they were created.
+    pub tags: Vec<String>,
+    /// The `<meta>` elements, in the order they were created.
+    pub metas: Vec<String>,
+    /// The `<link>` elements, including stylesheets, in the order they were created.
+    pub links: Vec<String>,
+    /// The `<script>` elements, in the order they were created.
+    pub scripts: Vec<String>,
+    /// The `<style>` elements, in the order they were created.
+    pub styles: Vec<String>,
+    /// The attributes for the `<html>` element, with a leading space, or an empty string.
+    pub html_attrs: String,
+    /// The attributes for the `<body>` element, with a leading space, or an empty string.
+    pub body_attrs: String,
+}
+
+#[cfg(feature = "ssr")]
+impl std::fmt::Display for HeadParts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(title) = &self.title {
+            f.write_str(title)?;
+        }
+        for tag in &self.tags {
+            f.write_str(tag)?;
+        }
+        Ok(())
+    }
+}
+
+// quotes a string as a JavaScript string literal that can be used in a `<script>`
+#[cfg(feature = "ssr")]
+fn js_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    leptos::leptos_dom::escape::escape_script_json(&quoted).into_owned()
+}
+
 /// Extracts the metadata that should be used to close the `<head>` tag
 /// and open the `<body>` tag. This is a helper function used in implementing
 /// server-side HTML rendering across crates.
@@ -295,16 +587,22 @@ pub fn generate_head_metadata(cx: Scope) -> String {
 /// server-side HTML rendering across crates.
 #[cfg(feature = "ssr")]
 pub fn generate_head_metadata_separated(cx: Scope) -> (String, String) {
-    let meta = use_context::<MetaContext>(cx);
-    let head = meta
-        .as_ref()
+    let parts = use_context::<MetaContext>(cx)
         .map(|meta| meta.dehydrate())
         .unwrap_or_default();
-    let body_meta = meta
-        .as_ref()
-        .and_then(|meta| meta.body.as_string())
+    (parts.to_string(), format!("<body{}>", parts.body_attrs))
+}
+
+/// Returns a `<script>` that updates the title and metadata tags in the streamed `<head>`, if
+/// they have changed since it was sent. This is a helper function used in implementing
+/// server-side HTML streaming across crates. See [`MetaContext::head_update_script`].
+#[cfg(feature = "ssr")]
+pub fn generate_head_update_script(cx: Scope) -> Option<String> {
+    let script = use_context::<MetaContext>(cx)?.head_update_script()?;
+    let nonce = leptos::nonce::use_nonce(cx)
+        .map(|nonce| format!(" nonce=\"{nonce}\""))
         .unwrap_or_default();
-    (head, format!("<body{body_meta}>"))
+    Some(format!("<script{nonce}>{script}</script>"))
 }
 
 #[cfg(debug_assertions)]
@@ -0,0 +1,147 @@
+// run with `wasm-pack test --headless --firefox --features hydrate`
+#![cfg(all(target_arch = "wasm32", feature = "hydrate"))]
+
+use leptos::*;
+use leptos_meta::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{
+    js_sys::{self, Promise},
+    JsFuture,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn head_tags() -> Vec<web_sys::Element> {
+    let tags = document().head().unwrap().query_selector_all("*").unwrap();
+    (0..tags.length())
+        .map(|i| tags.get(i).unwrap().unchecked_into())
+        .collect()
+}
+
+async fn tick() {
+    for _ in 0..5 {
+        JsFuture::from(Promise::resolve(&wasm_bindgen::JsValue::NULL))
+            .await
+            .unwrap();
+    }
+}
+
+#[wasm_bindgen_test]
+async fn hydration_removes_server_tags_the_client_did_not_render() {
+    // the server rendered a conditional `<Meta/>` that the client doesn't
+    document().head().unwrap().set_inner_html(
+        "<meta name=\"viewport\" content=\"width=device-width\">\
+         <meta name=\"description\" content=\"Signed in\" \
+         data-leptos-meta=\"leptos-link-1\" id=\"_0-2\">\
+         <meta name=\"robots\" content=\"noindex\" \
+         data-leptos-meta=\"leptos-link-2\" id=\"_0-3\">\
+         <link id=\"main-css\" rel=\"stylesheet\" href=\"/main.css\" \
+         data-leptos-meta=\"main-css\" leptos-hk=\"_0-4\">",
+    );
+    let server_link = document().get_element_by_id("main-css").unwrap();
+
+    let root = document().create_element("div").unwrap();
+    document().body().unwrap().append_child(&root).unwrap();
+    let disposer = hydrate_to(root.unchecked_into(), |cx| {
+        provide_meta_context(cx);
+        view! { cx,
+            <Meta name="description" content="Signed out"/>
+            <Link id="main-css" rel="stylesheet" href="/main.css"/>
+        }
+    });
+
+    // let hydration finish
+    tick().await;
+
+    let tags = head_tags();
+    assert_eq!(
+        tags.len(),
+        3,
+        "{:?}",
+        document().head().unwrap().inner_html()
+    );
+    // content that isn't from `leptos_meta` is left alone
+    assert_eq!(tags[0].get_attribute("name").as_deref(), Some("viewport"));
+    assert_eq!(
+        tags[1].get_attribute("name").as_deref(),
+        Some("description")
+    );
+    assert_eq!(
+        tags[1].get_attribute("content").as_deref(),
+        Some("Signed out")
+    );
+    // the stylesheet that matches is kept, rather than loaded again
+    assert_eq!(tags[2], server_link);
+    assert!(document()
+        .head()
+        .unwrap()
+        .query_selector("[name=robots]")
+        .unwrap()
+        .is_none());
+
+    disposer.dispose();
+}
+
+// the first resource created in the runtime of a new root
+fn resource_id() -> ResourceId {
+    serde_json::from_str(r#"{"idx":1,"version":1}"#).unwrap()
+}
+
+// runs a `<script>` as the server renders it
+fn run_script(html: &str) {
+    let script = html
+        .trim()
+        .strip_prefix("<script>")
+        .and_then(|script| script.strip_suffix("</script>"))
+        .unwrap();
+    js_sys::eval(script).unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn tags_under_suspense_are_claimed_once_their_resources_arrive() {
+    document().head().unwrap().set_inner_html(
+        "<link id=\"post-css\" rel=\"stylesheet\" href=\"/post.css\" \
+         data-leptos-meta=\"post-css\">\
+         <meta name=\"robots\" content=\"noindex\" \
+         data-leptos-meta=\"leptos-link-9\">",
+    );
+    let server_link = document().get_element_by_id("post-css").unwrap();
+    // the start of the stream, while the resource is still pending
+    run_script(&leptos_dom::render_stream_start("", &[resource_id()]));
+
+    let root = document().create_element("div").unwrap();
+    document().body().unwrap().append_child(&root).unwrap();
+    let disposer = hydrate_to(root.unchecked_into(), |cx| {
+        provide_meta_context(cx);
+        let post = create_resource(cx, || (), |_| async { String::new() });
+        view! { cx,
+            <Suspense fallback=|| ()>
+                {move || post.get().map(|_| view! { cx,
+                    <Link id="post-css" rel="stylesheet" href="/post.css"/>
+                })}
+            </Suspense>
+        }
+    });
+
+    // hydrating doesn't remove the tags that are still to be claimed
+    tick().await;
+    assert_eq!(head_tags().len(), 2);
+
+    // the script that streams the resource's value
+    run_script(&leptos_dom::render_serializer("", resource_id(), "\"\""));
+    tick().await;
+
+    // the stylesheet is claimed, rather than loaded again, and the tag that
+    // wasn't is removed
+    let tags = head_tags();
+    assert_eq!(
+        tags.len(),
+        1,
+        "{:?}",
+        document().head().unwrap().inner_html()
+    );
+    assert_eq!(tags[0], server_link);
+
+    disposer.dispose();
+}
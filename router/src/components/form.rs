@@ -1,4 +1,7 @@
-use crate::{use_navigate, use_resolved_path, NavigateOptions, ToHref, Url};
+use crate::{
+    expect_location, use_navigate, use_resolved_path, NavigateOptions,
+    RouterContext, ToHref, Url,
+};
 use leptos::{html::form, *};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{error::Error, rc::Rc};
@@ -10,8 +13,21 @@ type OnFormData = Rc<dyn Fn(&web_sys::FormData)>;
 type OnResponse = Rc<dyn Fn(&web_sys::Response)>;
 type OnError = Rc<dyn Fn(&gloo_net::Error)>;
 
+// dims the buttons that don't submit the form they are in, and labels them,
+// for users who have JavaScript turned off
+const NOSCRIPT_HINT_STYLE: &str = "form:has([data-leptos-noscript-hint]) \
+                                   button[type=\"button\"] { opacity: 0.5; \
+                                   cursor: not-allowed; } \
+                                   form:has([data-leptos-noscript-hint]) \
+                                   button[type=\"button\"]::after { \
+                                   content: \" (requires JavaScript)\"; }";
+
 /// An HTML [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form) progressively
 /// enhanced to use client-side routing.
+///
+/// Forms that the router can’t handle are submitted by the browser: forms with a `target`, and forms
+/// whose `action` is a URL on another origin or with another scheme, like `mailto:`. Set `external` to
+/// leave any other form to the browser.
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "trace", skip_all,)
@@ -57,6 +73,11 @@ pub fn Form<A>(
     /// Sets whether the page should be scrolled to the top when the form is submitted.
     #[prop(optional)]
     noscroll: bool,
+    /// If `true`, the `action` is used as it is, instead of being resolved
+    /// relative to the current route, and the browser submits the form,
+    /// instead of the router.
+    #[prop(optional)]
+    external: bool,
     /// Arbitrary attributes to add to the `<form>`
     #[prop(optional, into)]
     attributes: Option<MaybeSignal<AdditionalAttributes>>,
@@ -80,12 +101,13 @@ where
         children: Children,
         node_ref: Option<NodeRef<html::Form>>,
         noscroll: bool,
+        external: bool,
         attributes: Option<MaybeSignal<AdditionalAttributes>>,
     ) -> HtmlElement<html::Form> {
         let action_version = version;
         let on_submit = {
             move |ev: web_sys::SubmitEvent| {
-                if ev.default_prevented() {
+                if ev.default_prevented() || external {
                     return;
                 }
                 let navigate = use_navigate(cx);
@@ -96,6 +118,9 @@ where
 
                 let (form, method, action, enctype) =
                     extract_form_attributes(&ev);
+                if is_native_submission(&form, &action) {
+                    return;
+                }
 
                 let form_data =
                     web_sys::FormData::new_with_form(&form).unwrap_throw();
@@ -308,7 +333,11 @@ where
         form
     }
 
-    let action = use_resolved_path(cx, move || action.to_href()());
+    let action = if external {
+        create_memo(cx, move |_| Some(action.to_href()()))
+    } else {
+        use_resolved_path(cx, move || action.to_href()())
+    };
     let class = class.map(|bx| bx.into_attribute_boxed(cx));
     inner(
         cx,
@@ -324,10 +353,24 @@ where
         children,
         node_ref,
         noscroll,
+        external,
         attributes,
     )
 }
 
+// whether the browser should submit the form, rather than the router: forms
+// with a target, and forms whose action is a URL on another origin or with
+// another scheme
+fn is_native_submission(form: &web_sys::HtmlFormElement, action: &str) -> bool {
+    let target = form.get_attribute("target").unwrap_or_default();
+    if !target.is_empty() && !target.eq_ignore_ascii_case("_self") {
+        return true;
+    }
+    crate::matching::has_scheme(action)
+        && Url::try_from(action)
+            .map_or(true, |url| url.origin != current_window_origin())
+}
+
 fn current_window_origin() -> String {
     let location = window().location();
     let protocol = location.protocol().unwrap_or_default();
@@ -350,6 +393,12 @@ fn current_window_origin() -> String {
 /// **Note:** `<ActionForm/>` only works with server functions that use the
 /// default `Url` encoding. This is to ensure that `<ActionForm/>` works correctly
 /// both before and after WASM has loaded.
+///
+/// ## Field names
+/// Inputs are deserialized into the server function’s arguments by name. Nested structs
+/// and `Vec`s use bracketed names, like `address[street]` or `items[0][qty]`; see
+/// [`server_fn::form`](leptos::server_fn::form) for how checkboxes and repeated fields
+/// are handled. The form data is decoded the same way on the client and on the server.
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "trace", skip_all,)
@@ -514,6 +563,33 @@ where
         }
     }
 
+    // lets the server redirect back to this page if the form is submitted
+    // before (or without) WASM being loaded
+    let location = expect_location(cx);
+    let children = Box::new(move |cx| {
+        let redirect_path = move || {
+            let path = location.pathname.get();
+            let search = location.search.get();
+            if search.is_empty() {
+                path
+            } else {
+                format!("{path}?{search}")
+            }
+        };
+        Fragment::new(vec![
+            view! { cx,
+                <input
+                    type="hidden"
+                    name=leptos::server_fn::form::REDIRECT_FIELD
+                    value=redirect_path
+                />
+            }
+            .into_view(cx),
+            children(cx).into_view(cx),
+            noscript_hint(cx).into_view(cx),
+        ])
+    });
+
     let mut props = FormProps::builder()
         .action(action_url)
         .version(version)
@@ -604,7 +680,8 @@ where
         .attr("action", action)
         .on(ev::submit, on_submit)
         .attr("class", class)
-        .child(children(cx));
+        .child(children(cx))
+        .child(noscript_hint(cx));
     if let Some(node_ref) = node_ref {
         form = form.node_ref(node_ref)
     };
@@ -618,6 +695,23 @@ where
     }
     form
 }
+
+// the hint for the JS-only controls in an action form, if the router asks for
+// progressive enhancement hints
+fn noscript_hint(cx: Scope) -> Option<View> {
+    let enabled = use_context::<RouterContext>(cx)
+        .map(|router| router.inner.progressive_enhancement_hints.get())
+        .unwrap_or(false);
+    enabled.then(|| {
+        view! { cx,
+            <NoScript>
+                <style data-leptos-noscript-hint="" inner_html=NOSCRIPT_HINT_STYLE/>
+            </NoScript>
+        }
+        .into_view(cx)
+    })
+}
+
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "trace", skip_all,)
@@ -730,6 +824,10 @@ fn extract_form_attributes(
 
 /// Tries to deserialize a type from form data. This can be used for client-side
 /// validation during form submission.
+///
+/// The arguments of a [server function](leptos::server) also have inherent
+/// `from_event` and `from_form_data` methods, which do the same thing. See
+/// [`server_fn::form`](leptos::server_fn::form) for how fields are named.
 pub trait FromFormData
 where
     Self: Sized + serde::de::DeserializeOwned,
@@ -752,11 +850,16 @@ where
         tracing::instrument(level = "trace", skip_all,)
     )]
     fn from_event(ev: &web_sys::Event) -> Result<Self, serde_qs::Error> {
-        let (form, _, _, _) = extract_form_attributes(ev);
-
-        let form_data = web_sys::FormData::new_with_form(&form).unwrap_throw();
-
-        Self::from_form_data(&form_data)
+        cfg_if::cfg_if! {
+            if #[cfg(target_arch = "wasm32")] {
+                leptos::server_fn::form::from_event(ev)
+            } else {
+                _ = ev;
+                Err(serde::de::Error::custom(
+                    "form data can only be read in the browser",
+                ))
+            }
+        }
     }
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
@@ -765,10 +868,15 @@ where
     fn from_form_data(
         form_data: &web_sys::FormData,
     ) -> Result<Self, serde_qs::Error> {
-        let data =
-            web_sys::UrlSearchParams::new_with_str_sequence_sequence(form_data)
-                .unwrap_throw();
-        let data = data.to_string().as_string().unwrap_or_default();
-        serde_qs::Config::new(5, false).deserialize_str::<Self>(&data)
+        cfg_if::cfg_if! {
+            if #[cfg(target_arch = "wasm32")] {
+                leptos::server_fn::form::from_form_data(form_data)
+            } else {
+                _ = form_data;
+                Err(serde::de::Error::custom(
+                    "form data can only be read in the browser",
+                ))
+            }
+        }
     }
 }
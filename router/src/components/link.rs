@@ -1,4 +1,4 @@
-use crate::{use_location, use_resolved_path, State};
+use crate::{expect_location, use_resolved_path, State};
 use leptos::{leptos_dom::IntoView, *};
 use std::borrow::Cow;
 
@@ -43,6 +43,14 @@ where
 /// 2) Sets the `aria-current` attribute if this link is the active link (i.e., it’s a link to the page you’re on).
 ///    This is helpful for accessibility and for styling. For example, maybe you want to set the link a
 ///    different color if it’s a link to the page you’re currently on.
+///
+/// Links that the router can’t handle are left to the browser: links to another origin, links with a
+/// `mailto:` or `tel:` scheme, links with a `target` or `download`, and clicks with a modifier key held
+/// down or a button other than the main one. Absolute URLs are used as they are, rather than being
+/// resolved relative to the current route. Set `external` to hand any other link to the browser.
+///
+/// Links that open in a new browsing context (`target="_blank"`) are given `rel="noopener noreferrer"`,
+/// unless you provide a `rel` of your own.
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "info", skip_all,)
@@ -67,7 +75,8 @@ pub fn A<H>(
     /// SSR optimizations.
     #[prop(optional, into)]
     active_class: Option<Cow<'static, str>>,
-    /// An object of any type that will be pushed to router state
+    /// State that will be pushed with the history entry, created with
+    /// [`State::new`](crate::State::new).
     #[prop(optional)]
     state: Option<State>,
     /// If `true`, the link will not add to the browser's history (so, pressing `Back`
@@ -80,6 +89,24 @@ pub fn A<H>(
     /// Sets the `id` attribute on the underlying `<a>` tag, making it easier to target.
     #[prop(optional, into)]
     id: Option<String>,
+    /// Sets the `target` attribute on the underlying `<a>` tag. The browser
+    /// handles links with a `target`, instead of the router.
+    #[prop(optional, into)]
+    target: Option<Cow<'static, str>>,
+    /// Sets the `rel` attribute on the underlying `<a>` tag. If it’s not set,
+    /// links with `target="_blank"` get `rel="noopener noreferrer"`.
+    #[prop(optional, into)]
+    rel: Option<Cow<'static, str>>,
+    /// Sets the `download` attribute on the underlying `<a>` tag, with the
+    /// suggested file name (or `""` to keep the URL’s). The browser handles
+    /// downloads, instead of the router.
+    #[prop(optional, into)]
+    download: Option<Cow<'static, str>>,
+    /// If `true`, the `href` is used as it is, instead of being resolved
+    /// relative to the current route, and the browser handles the link,
+    /// instead of the router.
+    #[prop(optional)]
+    external: bool,
     /// The nodes or elements to be shown inside the link.
     children: Children,
 ) -> impl IntoView
@@ -99,6 +126,9 @@ where
         class: Option<AttributeValue>,
         #[allow(unused)] active_class: Option<Cow<'static, str>>,
         id: Option<String>,
+        target: Option<Cow<'static, str>>,
+        rel: Option<String>,
+        download: Option<Cow<'static, str>>,
         children: Children,
     ) -> View {
         #[cfg(not(any(feature = "hydrate", feature = "csr")))]
@@ -111,7 +141,7 @@ where
             _ = replace;
         }
 
-        let location = use_location(cx);
+        let location = expect_location(cx);
         let is_active = create_memo(cx, move |_| match href.get() {
             None => false,
 
@@ -153,7 +183,12 @@ where
                     a = a.class(class_name.to_string(), move || is_active.get())
                 }
 
-                a.attr("id", id).child(children(cx)).into_view(cx)
+                a.attr("id", id)
+                    .attr("target", target)
+                    .attr("rel", rel)
+                    .attr("download", download)
+                    .child(children(cx))
+                    .into_view(cx)
             }
             // but keep the nice SSR optimization in most cases
             else {
@@ -163,6 +198,9 @@ where
                         aria-current=move || if is_active.get() { Some("page") } else { None }
                         class=class
                         id=id
+                        target=target
+                        rel=rel
+                        download=download
                     >
                         {children(cx)}
                     </a>
@@ -183,6 +221,9 @@ where
                     aria-current=move || if is_active.get() { Some("page") } else { None }
                     class=class
                     id=id
+                    target=target
+                    rel=rel
+                    download=download
                 >
                     {children(cx)}
                 </a>
@@ -200,7 +241,12 @@ where
         }
     }
 
-    let href = use_resolved_path(cx, move || href.to_href()());
+    let href = if external {
+        create_memo(cx, move |_| Some(href.to_href()()))
+    } else {
+        use_resolved_path(cx, move || href.to_href()())
+    };
+    let rel = link_rel(rel, target.as_deref(), external);
     inner(
         cx,
         href,
@@ -210,6 +256,40 @@ where
         class,
         active_class,
         id,
+        target,
+        rel,
+        download,
         children,
     )
 }
+
+/// The `rel` of a link: the one given by the caller, if any, or else
+/// `noopener noreferrer` for a link that opens in a new browsing context.
+/// `external` is added to links that should be left to the browser, which is
+/// how the router recognizes them.
+///
+/// This only depends on the link’s props, so the server renders the same
+/// `rel` as the client.
+fn link_rel(
+    rel: Option<Cow<'static, str>>,
+    target: Option<&str>,
+    external: bool,
+) -> Option<String> {
+    let rel = rel.map(Cow::into_owned).or_else(|| {
+        target
+            .filter(|target| target.eq_ignore_ascii_case("_blank"))
+            .map(|_| "noopener noreferrer".to_string())
+    });
+    if !external {
+        return rel;
+    }
+    match rel {
+        Some(rel)
+            if rel.split_ascii_whitespace().any(|part| part == "external") =>
+        {
+            Some(rel)
+        }
+        Some(rel) if !rel.trim().is_empty() => Some(format!("{rel} external")),
+        _ => Some("external".to_string()),
+    }
+}
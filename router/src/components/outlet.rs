@@ -1,9 +1,9 @@
 use crate::{
     animation::{Animation, AnimationState},
-    use_is_back_navigation, use_route, SetIsRouting,
+    expect_route, use_is_back_navigation, RouteContext, SetIsRouting,
 };
 use leptos::{leptos_dom::HydrationCtx, *};
-use std::{cell::Cell, rc::Rc};
+use std::{cell::RefCell, rc::Rc};
 use web_sys::AnimationEvent;
 
 /// Displays the child route nested in a parent route, allowing you to control exactly where
@@ -15,32 +15,33 @@ use web_sys::AnimationEvent;
 #[component]
 pub fn Outlet(cx: Scope) -> impl IntoView {
     let id = HydrationCtx::id();
-    let route = use_route(cx);
-    let is_showing = Rc::new(Cell::new(None::<(usize, Scope)>));
+    let route = expect_route(cx);
+    let is_showing =
+        Rc::new(RefCell::new(None::<(RouteContext, ScopeDisposer)>));
     let (outlet, set_outlet) = create_signal(cx, None::<View>);
     create_isomorphic_effect(cx, move |_| {
-        match (route.child(cx), &is_showing.get()) {
-            (None, prev) => {
-                if let Some(prev_scope) = prev.map(|(_, scope)| scope) {
-                    prev_scope.dispose();
-                }
-                set_outlet.set(None);
-            }
-            (Some(child), Some((is_showing_val, _)))
-                if child.id() == *is_showing_val =>
+        let prev = is_showing.borrow_mut().take();
+        match (route.child(cx), prev) {
+            (Some(child), Some((is_showing_val, disposer)))
+                if child.is_same_instance(&is_showing_val) =>
             {
-                // do nothing: we don't need to rerender the component, because it's the same
+                // keep showing it: we don't need to rerender the component, because it's the same
+                is_showing.replace(Some((is_showing_val, disposer)));
             }
-            (Some(child), prev) => {
-                if let Some(prev_scope) = prev.map(|(_, scope)| scope) {
-                    prev_scope.dispose();
+            (child, prev) => {
+                if let Some((_, prev_disposer)) = prev {
+                    prev_disposer.dispose();
                 }
-                _ = cx.child_scope(|child_cx| {
+                let Some(child) = child else {
+                    set_outlet.set(None);
+                    return;
+                };
+                let (view, disposer) = cx.run_child_scope(|child_cx| {
                     provide_context(child_cx, child.clone());
-                    set_outlet
-                        .set(Some(child.outlet(child_cx).into_view(child_cx)));
-                    is_showing.set(Some((child.id(), child_cx)));
+                    child.outlet(child_cx).into_view(child_cx)
                 });
+                is_showing.replace(Some((child, disposer)));
+                set_outlet.set(Some(view));
             }
         }
     });
@@ -123,8 +124,9 @@ pub fn AnimatedOutlet(
     #[prop(optional)]
     finally: Option<&'static str>,
 ) -> impl IntoView {
-    let route = use_route(cx);
-    let is_showing = Rc::new(Cell::new(None::<(usize, Scope)>));
+    let route = expect_route(cx);
+    let is_showing =
+        Rc::new(RefCell::new(None::<(RouteContext, ScopeDisposer)>));
     let (outlet, set_outlet) = create_signal(cx, None::<View>);
 
     let animation = Animation {
@@ -164,29 +166,29 @@ pub fn AnimatedOutlet(
     let current_outlet = create_memo(cx, move |_| animation_and_outlet.get().1);
 
     create_isomorphic_effect(cx, move |_| {
-        match (route.child(cx), &is_showing.get()) {
-            (None, prev) => {
-                if let Some(prev_scope) = prev.map(|(_, scope)| scope) {
-                    prev_scope.dispose();
-                }
-                set_outlet.set(None);
-            }
-            (Some(child), Some((is_showing_val, _)))
-                if child.id() == *is_showing_val =>
+        let prev = is_showing.borrow_mut().take();
+        match (route.child(cx), prev) {
+            (Some(child), Some((is_showing_val, disposer)))
+                if child.is_same_instance(&is_showing_val) =>
             {
-                // do nothing: we don't need to rerender the component, because it's the same
+                // keep showing it: we don't need to rerender the component, because it's the same
+                is_showing.replace(Some((is_showing_val, disposer)));
                 trigger_animation.set(());
             }
-            (Some(child), prev) => {
-                if let Some(prev_scope) = prev.map(|(_, scope)| scope) {
-                    prev_scope.dispose();
+            (child, prev) => {
+                if let Some((_, prev_disposer)) = prev {
+                    prev_disposer.dispose();
                 }
-                _ = cx.child_scope(|child_cx| {
+                let Some(child) = child else {
+                    set_outlet.set(None);
+                    return;
+                };
+                let (view, disposer) = cx.run_child_scope(|child_cx| {
                     provide_context(child_cx, child.clone());
-                    set_outlet
-                        .set(Some(child.outlet(child_cx).into_view(child_cx)));
-                    is_showing.set(Some((child.id(), child_cx)));
+                    child.outlet(child_cx).into_view(child_cx)
                 });
+                is_showing.replace(Some((child, disposer)));
+                set_outlet.set(Some(view));
             }
         }
     });
@@ -1,23 +1,32 @@
-use leptos::{leptos_dom::helpers::IntervalHandle, *};
+use crate::use_is_routing;
+use leptos::{
+    leptos_dom::helpers::{IntervalHandle, TimeoutHandle},
+    *,
+};
 
 /// A visible indicator that the router is in the process of navigating
 /// to another route.
 ///
-/// This is used when `<Router set_is_routing>` has been provided, to
-/// provide some visual indicator that the page is currently loading
-/// async data, so that it is does not appear to have frozen. It can be
-/// styled independently.
+/// This provides some visual indicator that the page is currently loading
+/// async data, so that it is does not appear to have frozen. By default, it
+/// is driven by the router’s own [`use_is_routing`] signal, but any other
+/// signal can be passed via `is_routing`. It can be styled independently.
 #[component]
 pub fn RoutingProgress(
     cx: Scope,
-    /// Whether the router is currently loading the new page.
-    #[prop(into)]
-    is_routing: Signal<bool>,
+    /// Whether the router is currently loading the new page. Defaults to
+    /// the router’s [`use_is_routing`] signal.
+    #[prop(optional, into)]
+    is_routing: Option<Signal<bool>>,
     /// The maximum expected time for loading, which is used to
     /// calibrate the animation process.
     #[prop(optional, into)]
     max_time: std::time::Duration,
-    /// The time to show the full progress bar after page has loaded, before hiding it. (Defaults to 100ms.)
+    /// The minimum time the progress bar is shown once it appears, so that
+    /// very quick navigations do not cause it to flicker. (Defaults to 0ms.)
+    #[prop(optional, into)]
+    min_display_time: std::time::Duration,
+    /// The time to show the full progress bar after page has loaded, before hiding it. (Defaults to 250ms.)
     #[prop(default = std::time::Duration::from_millis(250))]
     before_hiding: std::time::Duration,
     /// CSS classes to be applied to the `<progress>`.
@@ -29,38 +38,80 @@ pub fn RoutingProgress(
         max_time.as_secs_f32() / (INCREMENT_EVERY_MS / 1000.0);
     let percent_per_increment = 100.0 / expected_increments;
 
+    let is_routing = is_routing.unwrap_or_else(|| use_is_routing(cx));
     let (is_showing, set_is_showing) = create_signal(cx, false);
     let (progress, set_progress) = create_signal(cx, 0.0);
+    let (min_elapsed, set_min_elapsed) = create_signal(cx, true);
+    let hide_timeout = store_value(cx, None::<TimeoutHandle>);
+    let min_timeout = store_value(cx, None::<TimeoutHandle>);
+
+    let clear_timeout = move |handle: StoredValue<Option<TimeoutHandle>>| {
+        if let Some(handle) = handle.try_update_value(Option::take).flatten() {
+            handle.clear();
+        }
+    };
 
     create_effect(cx, move |prev: Option<Option<IntervalHandle>>| {
-        if is_routing.get() && !is_showing.get() {
-            set_is_showing.set(true);
-            set_interval_with_handle(
-                move || {
-                    set_progress.update(|n| *n += percent_per_increment);
-                },
-                std::time::Duration::from_millis(INCREMENT_EVERY_MS as u64),
-            )
-            .ok()
-        } else if is_routing.get() && is_showing.get() {
-            set_progress.set(0.0);
-            prev?
-        } else {
-            set_progress.set(100.0);
-            set_timeout(
-                move || {
-                    set_progress.set(0.0);
-                    set_is_showing.set(false);
-                },
-                before_hiding,
-            );
-            if let Some(Some(interval)) = prev {
+        let routing = is_routing.get();
+        let min_elapsed = min_elapsed.get();
+        let prev = prev.flatten();
+
+        match (routing, prev) {
+            // a new navigation began while the bar is still animating
+            (true, Some(interval)) => Some(interval),
+            // begin a new cycle
+            (true, None) => {
+                clear_timeout(hide_timeout);
+                clear_timeout(min_timeout);
+                set_progress.set(0.0);
+                set_is_showing.set(true);
+                if !min_display_time.is_zero() {
+                    set_min_elapsed.set(false);
+                    min_timeout.set_value(
+                        set_timeout_with_handle(
+                            move || set_min_elapsed.set(true),
+                            min_display_time,
+                        )
+                        .ok(),
+                    );
+                }
+                set_interval_with_handle(
+                    move || {
+                        set_progress.update(|n| {
+                            *n = (*n + percent_per_increment).min(100.0)
+                        });
+                    },
+                    std::time::Duration::from_millis(INCREMENT_EVERY_MS as u64),
+                )
+                .ok()
+            }
+            // finished loading, but keep showing until the minimum time elapses
+            (false, Some(interval)) if !min_elapsed => Some(interval),
+            // finished loading: fill the bar, then hide it
+            (false, Some(interval)) => {
                 interval.clear();
+                set_progress.set(100.0);
+                hide_timeout.set_value(
+                    set_timeout_with_handle(
+                        move || {
+                            set_progress.set(0.0);
+                            set_is_showing.set(false);
+                        },
+                        before_hiding,
+                    )
+                    .ok(),
+                );
+                None
             }
-            None
+            (false, None) => None,
         }
     });
 
+    on_cleanup(cx, move || {
+        clear_timeout(hide_timeout);
+        clear_timeout(min_timeout);
+    });
+
     view! { cx,
         <Show when=move || is_showing.get() fallback=|_| ()>
             <progress class=class.clone() min="0" max="100" value=move || progress.get()/>
@@ -1,13 +1,13 @@
 use crate::{
-    matching::{resolve_path, PathMatch, RouteDefinition, RouteMatch},
-    ParamsMap, RouterContext, SsrMode,
+    matching::{
+        resolve_path, PathMatch, RouteDefinition, RouteMatch, RouteTitle,
+    },
+    use_route, use_router, ParamsMap, RouterContext, SsrMode,
 };
 use leptos::{leptos_dom::Transparent, *};
-use std::{cell::Cell, rc::Rc};
-
-thread_local! {
-    static ROUTE_ID: Cell<usize> = Cell::new(0);
-}
+#[cfg(feature = "meta")]
+use leptos_meta::Title;
+use std::rc::Rc;
 
 /// Represents an HTTP method that can be handled by this route.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
@@ -54,6 +54,23 @@ pub fn Route<E, F, P>(
     /// The HTTP methods that this route can handle (defaults to only `GET`).
     #[prop(default = &[Method::Get])]
     methods: &'static [Method],
+    /// If `true`, the view will be recreated whenever the route’s params
+    /// change, rather than being kept mounted and updating its params
+    /// reactively.
+    #[prop(optional)]
+    force_remount: bool,
+    /// If `true`, state created with [`use_preserved_state`](crate::use_preserved_state)
+    /// in this route’s view is snapshotted when the user navigates away, and
+    /// restored, along with the scroll position, when they return to the
+    /// same history entry with the browser’s back or forward buttons.
+    #[prop(optional)]
+    preserve_state: bool,
+    /// The document title to set, with `leptos_meta`, when this route is
+    /// matched. A nested route’s title overrides its parent’s, and a
+    /// `<Title/>` rendered by the route’s view overrides both. This requires
+    /// the `meta` feature.
+    #[prop(optional, into)]
+    title: Option<RouteTitle>,
     /// `children` may be empty or include nested routes.
     #[prop(optional)]
     children: Option<Children>,
@@ -70,6 +87,9 @@ where
         Rc::new(move |cx| view(cx).into_view(cx)),
         ssr,
         methods,
+        force_remount,
+        preserve_state,
+        title,
     )
 }
 
@@ -99,6 +119,23 @@ pub fn ProtectedRoute<P, E, F, C>(
     /// The HTTP methods that this route can handle (defaults to only `GET`).
     #[prop(default = &[Method::Get])]
     methods: &'static [Method],
+    /// If `true`, the view will be recreated whenever the route’s params
+    /// change, rather than being kept mounted and updating its params
+    /// reactively.
+    #[prop(optional)]
+    force_remount: bool,
+    /// If `true`, state created with [`use_preserved_state`](crate::use_preserved_state)
+    /// in this route’s view is snapshotted when the user navigates away, and
+    /// restored, along with the scroll position, when they return to the
+    /// same history entry with the browser’s back or forward buttons.
+    #[prop(optional)]
+    preserve_state: bool,
+    /// The document title to set, with `leptos_meta`, when this route is
+    /// matched. A nested route’s title overrides its parent’s, and a
+    /// `<Title/>` rendered by the route’s view overrides both. This requires
+    /// the `meta` feature.
+    #[prop(optional, into)]
+    title: Option<RouteTitle>,
     /// `children` may be empty or include nested routes.
     #[prop(optional)]
     children: Option<Children>,
@@ -126,12 +163,16 @@ where
         }),
         ssr,
         methods,
+        force_remount,
+        preserve_state,
+        title,
     )
 }
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "info", skip_all,)
 )]
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn define_route(
     cx: Scope,
     children: Option<Children>,
@@ -139,38 +180,56 @@ pub(crate) fn define_route(
     view: Rc<dyn Fn(Scope) -> View>,
     ssr_mode: SsrMode,
     methods: &'static [Method],
+    force_remount: bool,
+    preserve_state: bool,
+    title: Option<RouteTitle>,
 ) -> RouteDefinition {
-    let children = children
-        .map(|children| {
-            children(cx)
-                .as_children()
-                .iter()
-                .filter_map(|child| {
-                    child
-                        .as_transparent()
-                        .and_then(|t| t.downcast_ref::<RouteDefinition>())
-                })
-                .cloned()
-                .collect::<Vec<_>>()
-        })
-        .unwrap_or_default();
-
-    let id = ROUTE_ID.with(|id| {
-        let next = id.get() + 1;
-        id.set(next);
-        next
-    });
+    let mut route_defs = Vec::new();
+    if let Some(children) = children {
+        RouteDefinition::collect_from(
+            children(cx).as_children(),
+            &mut route_defs,
+        );
+    }
 
     RouteDefinition {
-        id,
+        id: RouteDefinition::next_id(),
         path,
-        children,
-        view,
+        children: route_defs,
+        view: if preserve_state {
+            preserving_state(view)
+        } else {
+            view
+        },
         ssr_mode,
         methods,
+        force_remount,
+        title,
     }
 }
 
+/// Wraps a route’s view so that the state created in it with
+/// [`use_preserved_state`](crate::use_preserved_state), and the scroll
+/// position, are preserved when the route is left.
+pub(crate) fn preserving_state(
+    view: Rc<dyn Fn(Scope) -> View>,
+) -> Rc<dyn Fn(Scope) -> View> {
+    Rc::new(move |cx| {
+        if let (Some(route), Ok(router)) = (use_route(cx), use_router(cx)) {
+            router
+                .inner
+                .entries
+                .preserve_scroll(cx, &router.pathname().get_untracked());
+            provide_context(cx, PreservedStateRoute(route));
+        }
+        view(cx)
+    })
+}
+
+/// Marks the route whose view preserves its state.
+#[derive(Clone)]
+pub(crate) struct PreservedStateRoute(pub RouteContext);
+
 impl IntoView for RouteDefinition {
     fn into_view(self, cx: Scope) -> View {
         Transparent::new(self).into_view(cx)
@@ -195,12 +254,12 @@ impl RouteContext {
         matcher: impl Fn() -> Option<RouteMatch> + 'static,
     ) -> Option<Self> {
         let base = router.base();
-        let base = base.path();
+        let base = base.path_untracked();
         let RouteMatch { path_match, route } = matcher()?;
         let PathMatch { path, .. } = path_match;
-        let RouteDefinition {
-            view: element, id, ..
-        } = route.key;
+        #[cfg(feature = "meta")]
+        let title = route.key.title.clone();
+        let RouteDefinition { view: element, .. } = route.key;
         let params = create_memo(cx, move |_| {
             matcher()
                 .map(|matched| matched.path_match.params)
@@ -210,13 +269,26 @@ impl RouteContext {
         Some(Self {
             inner: Rc::new(RouteContextInner {
                 cx,
-                id,
                 base_path: base,
                 child: Box::new(child),
                 path: create_rw_signal(cx, path),
-                original_path: route.original_path.to_string(),
+                original_path: route.pattern.to_string(),
                 params,
-                outlet: Box::new(move |cx| Some(element(cx))),
+                outlet: Box::new(move |cx| {
+                    // set before rendering the view, so that a nested
+                    // route's title or a `<Title/>` in the view wins
+                    #[cfg(feature = "meta")]
+                    if let Some(title) = &title {
+                        let title = title.clone();
+                        let text = move || {
+                            params
+                                .try_with(|params| title.get(params))
+                                .unwrap_or_default()
+                        };
+                        _ = view! { cx, <Title text/> };
+                    }
+                    Some(element(cx))
+                }),
             }),
         })
     }
@@ -226,8 +298,10 @@ impl RouteContext {
         self.inner.cx
     }
 
-    pub(crate) fn id(&self) -> usize {
-        self.inner.id
+    /// Whether this is the same matched route instance as `other`, i.e., the
+    /// route was kept mounted rather than being recreated.
+    pub(crate) fn is_same_instance(&self, other: &RouteContext) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
     }
 
     /// Returns the URL path of the current route,
@@ -235,7 +309,17 @@ impl RouteContext {
     ///
     /// e.g., this will return `/article/0` rather than `/article/:id`.
     /// For the opposite behavior, see [RouteContext::original_path].
+    ///
+    /// This is reactive: when navigating between two paths that match the
+    /// same route, like `/article/0` and `/article/1`, the route is kept
+    /// mounted and its path is updated.
     pub fn path(&self) -> String {
+        self.inner.path.get()
+    }
+
+    /// Returns the URL path of the current route, like [RouteContext::path],
+    /// without tracking it.
+    pub fn path_untracked(&self) -> String {
         self.inner.path.get_untracked()
     }
 
@@ -265,7 +349,6 @@ impl RouteContext {
         Self {
             inner: Rc::new(RouteContextInner {
                 cx,
-                id: 0,
                 base_path: path.to_string(),
                 child: Box::new(|_| None),
                 path: create_rw_signal(cx, path.to_string()),
@@ -307,7 +390,6 @@ impl RouteContext {
 pub(crate) struct RouteContextInner {
     cx: Scope,
     base_path: String,
-    pub(crate) id: usize,
     pub(crate) child: Box<dyn Fn(Scope) -> Option<RouteContext>>,
     pub(crate) path: RwSignal<String>,
     pub(crate) original_path: String,
@@ -1,14 +1,19 @@
 use crate::{
-    create_location, matching::resolve_path, Branch, History, Location,
-    LocationChange, RouteContext, RouterIntegrationContext, State,
+    create_location, matching::resolve_path, Branch, History, HistoryEntries,
+    Location, LocationChange, RouteContext, RouterIntegrationContext, State,
+    StateError,
 };
 #[cfg(not(feature = "ssr"))]
 use crate::{unescape, Url};
 use cfg_if::cfg_if;
+use futures::future::{AbortHandle, AbortRegistration, Either};
 use leptos::*;
 #[cfg(feature = "transition")]
 use leptos_reactive::use_transition;
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 use thiserror::Error;
 #[cfg(not(feature = "ssr"))]
 use wasm_bindgen::JsCast;
@@ -27,25 +32,118 @@ pub fn Router(
     /// A signal that will be set while the navigation process is underway.
     #[prop(optional, into)]
     set_is_routing: Option<SignalSetter<bool>>,
+    /// A callback that will be called with the path being navigated to
+    /// whenever a navigation begins.
+    #[prop(optional)]
+    on_navigation_start: Option<OnNavigation>,
+    /// A callback that will be called with the path that was navigated to
+    /// once the navigation has ended, i.e., once the new route’s resources
+    /// have settled, or once it has been superseded by another navigation.
+    #[prop(optional)]
+    on_navigation_end: Option<OnNavigation>,
+    /// The [History] the router uses to read the current location and to
+    /// navigate. Defaults to a [RouterIntegrationContext] provided as
+    /// context, or in the browser, to the
+    /// [`BrowserIntegration`](crate::BrowserIntegration).
+    #[prop(optional, into)]
+    history: Option<RouterIntegrationContext>,
+    /// If `true`, the router manages focus for keyboard and screen reader
+    /// users. Once a navigation has settled, focus moves to the first
+    /// element with an `autofocus` or `data-route-focus` attribute, or else
+    /// to the page’s `<h1>` or `<main>` landmark, and the new document
+    /// title (as set by `leptos_meta`’s `<Title/>`) is announced through a
+    /// visually-hidden ARIA live region. A navigation that only changes the
+    /// URL’s hash focuses the element it targets instead.
+    ///
+    /// This can be skipped for a single navigation with
+    /// [`NavigateOptions::focus`]. Defaults to `false`.
+    #[prop(optional)]
+    focus_on_navigate: bool,
+    /// If `true`, route changes are animated with the browser’s
+    /// [View Transitions API](https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API)
+    /// where it is supported. The old page stays on screen until the new
+    /// route’s resources have loaded, and then the browser animates from one
+    /// to the other, which by default is a cross-fade that can be
+    /// customized with the `::view-transition-*` CSS pseudo-elements. Shared
+    /// elements can be tagged with [`view_transition_name`](crate::view_transition_name).
+    ///
+    /// While the transition for a back navigation runs, the `<html>` element
+    /// has the [`back-transition`](crate::VIEW_TRANSITION_BACK_CLASS) class,
+    /// so it can be animated in reverse. Route changes are not animated when
+    /// the API is missing or the user prefers reduced motion. Defaults to
+    /// `false`.
+    #[prop(optional)]
+    view_transitions: bool,
+    /// If `true`, [`ActionForm`](crate::ActionForm) and
+    /// [`MultiActionForm`](crate::MultiActionForm) render a
+    /// [`NoScript`](leptos::NoScript) hint into the server-rendered page that
+    /// marks the controls inside them that only work with JavaScript, i.e.,
+    /// `<button type="button">`s, which don’t submit the form. Users who have JavaScript turned off see
+    /// those buttons dimmed and labeled as requiring it; the hint is removed
+    /// when the page is hydrated. Defaults to `false`.
+    #[prop(optional)]
+    progressive_enhancement_hints: bool,
     /// The `<Router/>` should usually wrap your whole page. It can contain
     /// any elements, and should include a [Routes](crate::Routes) component somewhere
     /// to define and display [Route](crate::Route)s.
     children: Children,
 ) -> impl IntoView {
     // create a new RouterContext and provide it to every component beneath the router
-    let router = RouterContext::new(cx, base, fallback);
+    let router = RouterContext::new(cx, base, fallback, history);
+    router.inner.hooks.replace(NavigationHooks {
+        on_start: on_navigation_start,
+        on_end: on_navigation_end,
+    });
+    router.inner.view_transitions.set(view_transitions);
+    router
+        .inner
+        .progressive_enhancement_hints
+        .set(progressive_enhancement_hints);
+    let announcement = focus_on_navigate.then(|| {
+        let (announcement, announce) = create_signal(cx, String::new());
+        router.inner.announcer.set(Some(announce));
+        announcement
+    });
     provide_context(cx, router);
     provide_context(cx, GlobalSuspenseContext::new(cx));
     if let Some(set_is_routing) = set_is_routing {
         provide_context(cx, SetIsRouting(set_is_routing));
     }
 
-    children(cx)
+    let children = children(cx);
+    if let Some(announcement) = announcement {
+        view! { cx,
+            {children}
+            <div aria-live="polite" aria-atomic="true" style=VISUALLY_HIDDEN>
+                {announcement}
+            </div>
+        }
+        .into_view(cx)
+    } else {
+        children.into_view(cx)
+    }
 }
 
+// hides the route announcer visually, while keeping it in the accessibility tree
+const VISUALLY_HIDDEN: &str = "position: absolute; width: 1px; height: 1px; \
+                               padding: 0; margin: -1px; overflow: hidden; \
+                               clip: rect(0, 0, 0, 0); white-space: nowrap; \
+                               border: 0;";
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub(crate) struct SetIsRouting(pub SignalSetter<bool>);
 
+/// A callback that is called with a path at some point in the navigation
+/// lifecycle. See the `on_navigation_start` and `on_navigation_end` props
+/// on [`Router`].
+pub type OnNavigation = Rc<dyn Fn(&str)>;
+
+#[derive(Default)]
+pub(crate) struct NavigationHooks {
+    on_start: Option<OnNavigation>,
+    on_end: Option<OnNavigation>,
+}
+
 /// Context type that contains information about the current router state.
 #[derive(Debug, Clone)]
 pub struct RouterContext {
@@ -64,8 +162,20 @@ pub(crate) struct RouterContextInner {
     referrers: Rc<RefCell<Vec<LocationChange>>>,
     state: ReadSignal<State>,
     set_state: WriteSignal<State>,
+    pub(crate) background_location: Memo<Option<String>>,
+    pub(crate) routed_path: Memo<String>,
     pub(crate) is_back: RwSignal<bool>,
+    view_transitions: Rc<Cell<bool>>,
+    pub(crate) progressive_enhancement_hints: Cell<bool>,
     pub(crate) path_stack: StoredValue<Vec<String>>,
+    pub(crate) entries: Rc<HistoryEntries>,
+    is_routing: RwSignal<bool>,
+    navigation_id: Cell<usize>,
+    pending_navigation: RefCell<Option<String>>,
+    hooks: RefCell<NavigationHooks>,
+    announcer: Cell<Option<WriteSignal<String>>>,
+    pending_focus: RefCell<Option<FocusTarget>>,
+    navigation_aborts: Rc<RefCell<Vec<AbortHandle>>>,
 }
 
 impl std::fmt::Debug for RouterContextInner {
@@ -80,6 +190,7 @@ impl std::fmt::Debug for RouterContextInner {
             .field("state", &self.state)
             .field("set_state", &self.set_state)
             .field("path_stack", &self.path_stack)
+            .field("is_routing", &self.is_routing)
             .finish()
     }
 }
@@ -93,18 +204,20 @@ impl RouterContext {
         cx: Scope,
         base: Option<&'static str>,
         fallback: Option<fn(Scope) -> View>,
+        history: Option<RouterIntegrationContext>,
     ) -> Self {
+        let history = history.or_else(|| use_context(cx));
         cfg_if! {
             if #[cfg(any(feature = "csr", feature = "hydrate"))] {
-                let history = use_context::<RouterIntegrationContext>(cx)
+                let history = history
                     .unwrap_or_else(|| RouterIntegrationContext(Rc::new(crate::BrowserIntegration {})));
             } else {
-                let history = use_context::<RouterIntegrationContext>(cx).unwrap_or_else(|| {
+                let history = history.unwrap_or_else(|| {
                     let msg = "No router integration found.\n\nIf you are using this in the browser, \
                         you should enable `features = [\"csr\"]` or `features = [\"hydrate\"] in your \
                         `leptos_router` import.\n\nIf you are using this on the server without a \
                         Leptos server integration, you must call provide_context::<RouterIntegrationContext>(cx, ...) \
-                        somewhere above the <Router/>.";
+                        somewhere above the <Router/>, or pass one to its `history` prop.";
                     leptos::debug_warn!("{}", msg);
                     panic!("{}", msg);
                 });
@@ -126,7 +239,7 @@ impl RouterContext {
                     value: base_path.to_string(),
                     replace: true,
                     scroll: false,
-                    state: State(None),
+                    state: State::default(),
                 });
             }
         }
@@ -135,9 +248,12 @@ impl RouterContext {
         let (reference, set_reference) =
             create_signal(cx, source.with_untracked(|s| s.value.clone()));
 
-        // the current History.state
-        let (state, set_state) =
-            create_signal(cx, source.with_untracked(|s| s.state.clone()));
+        // the current History.state; a modal route that is loaded directly,
+        // like the server renders it, is shown as a page of its own
+        let (state, set_state) = create_signal(
+            cx,
+            source.with_untracked(|s| s.state.clone().without_background()),
+        );
 
         // we'll use this transition to wait for async resources to load when navigating to a new route
         #[cfg(feature = "transition")]
@@ -145,6 +261,21 @@ impl RouterContext {
 
         // Each field of `location` reactively represents a different part of the current location
         let location = create_location(cx, reference, state);
+        let pathname = location.pathname;
+        let background_location = create_memo(cx, move |_| {
+            state.with(|state| state.background_location().map(String::from))
+        });
+        // the path displayed by the `<Routes/>`
+        let routed_path = create_memo(cx, move |_| {
+            background_location.with(|background| match background {
+                Some(background) => background
+                    .split(['?', '#'])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                None => pathname.get(),
+            })
+        });
         let referrers: Rc<RefCell<Vec<LocationChange>>> =
             Rc::new(RefCell::new(Vec::new()));
 
@@ -152,20 +283,61 @@ impl RouterContext {
         let base_path = base_path.unwrap_or_default();
         let base = RouteContext::base(cx, &base_path, fallback);
 
+        // Keeps track of the current history entry, and the state preserved for each entry
+        let entries = Rc::new(HistoryEntries::new(&history));
+
+        // Work tied to the current navigation, aborted when the next one starts
+        let navigation_aborts: Rc<RefCell<Vec<AbortHandle>>> =
+            Default::default();
+
+        let view_transitions: Rc<Cell<bool>> = Default::default();
+
         // Every time the History gives us a new location,
         // 1) start a transition
         // 2) update the reference (URL)
         // 3) update the state
         // this will trigger the new route match below
 
-        create_render_effect(cx, move |_| {
-            let LocationChange { value, state, .. } = source.get();
-            cx.untrack(move || {
-                if value != reference.get() {
-                    set_reference.update(move |r| *r = value);
-                    set_state.update(move |s| *s = state);
-                }
-            });
+        create_render_effect(cx, {
+            let history = history.clone();
+            let entries = Rc::clone(&entries);
+            let navigation_aborts = Rc::clone(&navigation_aborts);
+            let view_transitions = Rc::clone(&view_transitions);
+            move |_| {
+                let LocationChange { value, state, .. } = source.get();
+                cx.untrack(|| {
+                    if value != reference.get() {
+                        abort_all(&navigation_aborts);
+                        // the history has already moved to this entry
+                        let previous = entries.current();
+                        let (key, revisited) = entries.enter(&history, false);
+                        if !revisited {
+                            history.set_entry_key(key);
+                        }
+                        // new entries get higher keys than the ones before them
+                        let back = revisited && key < previous;
+                        let entries = Rc::clone(&entries);
+                        _ = change_route(
+                            cx,
+                            view_transitions.get(),
+                            back,
+                            move || {
+                                cx.batch(move || {
+                                    set_reference.update(move |r| *r = value);
+                                    set_state.update(move |s| *s = state);
+                                });
+                                // the entry's route has been rendered again
+                                if revisited {
+                                    entries.restore_scroll(
+                                        key,
+                                        &pathname.get_untracked(),
+                                    );
+                                }
+                            },
+                        );
+                    }
+                });
+            }
         });
 
         let inner = Rc::new(RouterContextInner {
@@ -176,6 +348,7 @@ impl RouterContext {
             ),
             location,
             base,
+            entries,
             history: Box::new(history),
             cx,
             reference,
@@ -183,8 +356,19 @@ impl RouterContext {
             referrers,
             state,
             set_state,
+            background_location,
+            routed_path,
             possible_routes: Default::default(),
             is_back: create_rw_signal(cx, false),
+            view_transitions,
+            progressive_enhancement_hints: Cell::new(false),
+            is_routing: create_rw_signal(cx, false),
+            navigation_id: Cell::new(0),
+            pending_navigation: Default::default(),
+            hooks: Default::default(),
+            announcer: Default::default(),
+            pending_focus: Default::default(),
+            navigation_aborts,
         });
 
         // handle all click events on anchor tags
@@ -216,6 +400,38 @@ impl RouterContext {
             .clone()
             .unwrap_or_default()
     }
+
+    /// A signal that is `true` from the moment a navigation begins until
+    /// the new route’s resources have settled.
+    pub fn is_routing(&self) -> Signal<bool> {
+        self.inner.is_routing.into()
+    }
+
+    /// Returns an [`AbortRegistration`] that is aborted when the next
+    /// navigation begins, whether from a link, [`use_navigate`](crate::use_navigate)
+    /// or the browser’s back and forward buttons.
+    ///
+    /// Resources and actions are already aborted when the route that
+    /// created them is left. This is for other work that should not outlive
+    /// the current page, like a request made outside a resource, which can
+    /// be wrapped in [`Abortable`](futures::future::Abortable).
+    ///
+    /// ```rust,ignore
+    /// let registration = expect_router(cx).abort_registration();
+    /// spawn_local(async move {
+    ///     if let Ok(report) = Abortable::new(load_report(), registration).await {
+    ///         // ...
+    ///     }
+    /// });
+    /// ```
+    pub fn abort_registration(&self) -> AbortRegistration {
+        let (handle, registration) = AbortHandle::new_pair();
+        let mut aborts = self.inner.navigation_aborts.borrow_mut();
+        // forget about work that has already been aborted
+        aborts.retain(|handle| !handle.is_aborted());
+        aborts.push(handle);
+        registration
+    }
 }
 
 impl RouterContextInner {
@@ -231,6 +447,10 @@ impl RouterContextInner {
         let cx = self.cx;
         let this = Rc::clone(&self);
 
+        if let Some(e) = options.state.error() {
+            return Err(NavigationError::State(e.clone()));
+        }
+
         cx.untrack(move || {
             let resolved_to = if options.resolve {
                 this.base.resolve_path(to)
@@ -261,6 +481,14 @@ impl RouterContextInner {
                         }
                         let len = self.referrers.borrow().len();
 
+                        let focus = options.focus.then(|| {
+                            FocusTarget::new(
+                                &self.reference.get(),
+                                &resolved_to,
+                            )
+                        });
+                        let (entry, revisited) =
+                            self.entries.enter(&*self.history, options.replace);
                         let set_reference = self.set_reference;
                         let set_state = self.set_state;
                         let referrers = self.referrers.clone();
@@ -268,17 +496,29 @@ impl RouterContextInner {
 
                         let resolved = resolved_to.to_string();
                         let state = options.state.clone();
-                        set_reference.update(move |r| *r = resolved);
-
-                        set_state.update({
-                            let next_state = state.clone();
-                            move |state| *state = next_state
-                        });
+                        // set by the history when it goes back
+                        let is_navigating_back = self.is_back.get_untracked();
+                        let route_changed = change_route(
+                            cx,
+                            self.view_transitions.get(),
+                            is_navigating_back,
+                            {
+                                let next_state = state.clone();
+                                move || {
+                                    cx.batch(move || {
+                                        set_reference
+                                            .update(move |r| *r = resolved);
+                                        set_state.update(move |state| {
+                                            *state = next_state
+                                        });
+                                    });
+                                }
+                            },
+                        );
 
                         let global_suspense =
                             expect_context::<GlobalSuspenseContext>(cx);
                         let path_stack = self.path_stack;
-                        let is_navigating_back = self.is_back.get_untracked();
                         if !is_navigating_back {
                             path_stack.update_value(|stack| {
                                 stack.push(resolved_to.clone())
@@ -286,25 +526,40 @@ impl RouterContextInner {
                         }
 
                         let set_is_routing = use_context::<SetIsRouting>(cx);
-                        if let Some(set_is_routing) = set_is_routing {
-                            set_is_routing.0.set(true);
-                        }
+                        let navigation_id = self.start_navigation(&resolved_to);
+                        self.pending_focus.replace(focus);
                         spawn_local(async move {
-                            if let Some(set_is_routing) = set_is_routing {
+                            // with view transitions, the route only changes
+                            // once the browser has captured the old page
+                            route_changed.await;
+
+                            // if `set_is_routing` is provided, the new route
+                            // isn't committed until its resources have loaded
+                            if set_is_routing.is_some() {
                                 global_suspense
                                     .with_inner(|s| s.to_future(cx))
                                     .await;
-                                set_is_routing.0.set(false);
                             }
 
                             if referrers.borrow().len() == len {
-                                this.navigate_end(LocationChange {
-                                    value: resolved_to,
-                                    replace: false,
-                                    scroll: true,
-                                    state,
-                                });
+                                this.clone().navigate_end(
+                                    LocationChange {
+                                        value: resolved_to,
+                                        replace: false,
+                                        scroll: true,
+                                        state,
+                                    },
+                                    entry,
+                                    revisited,
+                                );
+                            }
+
+                            if set_is_routing.is_none() {
+                                global_suspense
+                                    .with_inner(|s| s.to_future(cx))
+                                    .await;
                             }
+                            this.end_navigation(navigation_id);
                         });
                     }
 
@@ -314,13 +569,82 @@ impl RouterContextInner {
         })
     }
 
-    pub(crate) fn navigate_end(self: Rc<Self>, mut next: LocationChange) {
+    /// Begins a new navigation cycle, ending any cycle that is still
+    /// in progress, and returns an identifier for the new cycle.
+    fn start_navigation(&self, to: &str) -> usize {
+        let id = self.navigation_id.get().wrapping_add(1);
+        self.navigation_id.set(id);
+
+        abort_all(&self.navigation_aborts);
+
+        let hooks = self.hooks.borrow();
+        let superseded = self.pending_navigation.replace(Some(to.to_string()));
+        if let (Some(superseded), Some(on_end)) = (superseded, &hooks.on_end) {
+            on_end(&superseded);
+        }
+        if let Some(on_start) = &hooks.on_start {
+            on_start(to);
+        }
+
+        self.is_routing.set(true);
+        if let Some(set_is_routing) = use_context::<SetIsRouting>(self.cx) {
+            set_is_routing.0.set(true);
+        }
+        id
+    }
+
+    /// Ends the navigation cycle with the given identifier, unless it has
+    /// already been superseded by a newer navigation.
+    fn end_navigation(&self, id: usize) {
+        if self.navigation_id.get() != id {
+            return;
+        }
+
+        let finished = self.pending_navigation.take();
+        if let (Some(finished), Some(on_end)) =
+            (finished, &self.hooks.borrow().on_end)
+        {
+            on_end(&finished);
+        }
+
+        self.is_routing.set(false);
+        if let Some(set_is_routing) = use_context::<SetIsRouting>(self.cx) {
+            set_is_routing.0.set(false);
+        }
+
+        // wait for the new route to be rendered before looking for its content
+        if let (Some(target), Some(announce)) =
+            (self.pending_focus.take(), self.announcer.get())
+        {
+            queue_after_render(self.cx, move || target.apply(announce));
+        }
+    }
+
+    /// Commits a navigation to the history, as the entry with the given key,
+    /// restoring the entry’s scroll position if it is being `revisited`.
+    pub(crate) fn navigate_end(
+        self: Rc<Self>,
+        mut next: LocationChange,
+        entry: u64,
+        revisited: bool,
+    ) {
         let first = self.referrers.borrow().get(0).cloned();
         if let Some(first) = first {
             if next.value != first.value || next.state != first.state {
                 next.replace = first.replace;
                 next.scroll = first.scroll;
+                // the page underneath a modal route stays where it was
+                if next.state.background_location().is_some() {
+                    next.scroll = false;
+                }
                 self.history.navigate(&next);
+                self.history.set_entry_key(entry);
+                if revisited {
+                    self.entries.restore_scroll(
+                        entry,
+                        &self.location.pathname.get_untracked(),
+                    );
+                }
             }
             self.referrers.borrow_mut().clear();
         }
@@ -372,7 +696,15 @@ impl RouterContextInner {
                 return;
             }
 
-            let url = Url::try_from(href.as_str()).unwrap();
+            // let browser handle other schemes, like mailto: or tel:
+            let protocol = a.protocol();
+            if protocol != "http:" && protocol != "https:" {
+                return;
+            }
+
+            let Ok(url) = Url::try_from(href.as_str()) else {
+                return;
+            };
             let path_name = unescape(&url.pathname);
 
             // let browser handle this event if it leaves our domain
@@ -392,6 +724,8 @@ impl RouterContextInner {
                 + if url.search.is_empty() { "" } else { "?" }
                 + &unescape(&url.search)
                 + &unescape(&url.hash);
+            // a link whose state could not be serialized carries the error,
+            // which fails the navigation below, as it does for `navigate`
             let state =
                 leptos_dom::helpers::get_property(a.unchecked_ref(), "state")
                     .ok()
@@ -401,7 +735,9 @@ impl RouterContextInner {
                         } else {
                             Some(value)
                         }
-                    });
+                    })
+                    .map(|state| State::from_js_value(&state))
+                    .unwrap_or_default();
 
             ev.prevent_default();
 
@@ -416,7 +752,8 @@ impl RouterContextInner {
                     resolve: false,
                     replace,
                     scroll: !a.has_attribute("noscroll"),
-                    state: State(state),
+                    state,
+                    focus: true,
                 },
             ) {
                 leptos::error!("{e:#?}");
@@ -434,6 +771,52 @@ pub enum NavigationError {
     /// Too many redirects occurred during routing (prevents and infinite loop.)
     #[error("Too many redirects")]
     MaxRedirects,
+    /// The navigation function was created outside a [`Router`].
+    #[error(transparent)]
+    Router(#[from] RouterError),
+    /// The [`NavigateOptions::state`] could not be stored with the history
+    /// entry.
+    #[error(transparent)]
+    State(#[from] StateError),
+}
+
+/// An error returned by the router’s hooks.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum RouterError {
+    /// The hook was called from a component that is not rendered inside a
+    /// [`Router`], like a header rendered next to it.
+    #[error(
+        "{hook}() was called at {location}, outside a <Router/>. Render the \
+         component that calls it inside the <Router/>."
+    )]
+    OutsideRouter {
+        /// The name of the hook.
+        hook: &'static str,
+        /// Where the hook was called.
+        location: &'static std::panic::Location<'static>,
+    },
+}
+
+impl RouterError {
+    #[track_caller]
+    pub(crate) fn outside_router(hook: &'static str) -> Self {
+        RouterError::OutsideRouter {
+            hook,
+            location: std::panic::Location::caller(),
+        }
+    }
+
+    // panics with the error, adding the component that called the hook
+    #[track_caller]
+    pub(crate) fn panic_outside_router(hook: &'static str) -> ! {
+        let error = Self::outside_router(hook);
+        match leptos::leptos_dom::current_component_name() {
+            Some(component) => {
+                panic!("{error} It was called by <{component}/>.")
+            }
+            None => panic!("{error}"),
+        }
+    }
 }
 
 /// Options that can be used to configure a navigation. Used with [use_navigate](crate::use_navigate).
@@ -448,8 +831,14 @@ pub struct NavigateOptions {
     /// Defaults to `true`.
     pub scroll: bool,
     /// [State](https://developer.mozilla.org/en-US/docs/Web/API/History/state) that should be pushed
-    /// onto the history stack during navigation.
+    /// onto the history stack during navigation, created with [`State::new`].
+    /// A `replace` navigation without state clears the state of the entry
+    /// it replaces.
     pub state: State,
+    /// If `true`, and the [`Router`] was created with `focus_on_navigate`,
+    /// focus moves to the new route’s content and its title is announced
+    /// to screen readers once the navigation has settled. Defaults to `true`.
+    pub focus: bool,
 }
 
 impl Default for NavigateOptions {
@@ -458,7 +847,104 @@ impl Default for NavigateOptions {
             resolve: true,
             replace: false,
             scroll: true,
-            state: State(None),
+            state: State::default(),
+            focus: true,
+        }
+    }
+}
+
+/// Where focus should move once a navigation has settled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum FocusTarget {
+    /// The element with the given `id`, after a navigation that only
+    /// changed the URL’s hash.
+    Anchor(String),
+    /// The main content of the new route.
+    Route,
+}
+
+impl FocusTarget {
+    fn new(from: &str, to: &str) -> Self {
+        let (to_path, hash) = to.split_once('#').unwrap_or((to, ""));
+        let from_path = from.split('#').next().unwrap_or_default();
+        if !hash.is_empty() && to_path == from_path {
+            FocusTarget::Anchor(hash.to_string())
+        } else {
+            FocusTarget::Route
         }
     }
+
+    fn apply(self, announce: WriteSignal<String>) {
+        cfg_if! {
+            if #[cfg(feature = "ssr")] {
+                _ = announce;
+            } else {
+                let document = document();
+                match self {
+                    FocusTarget::Anchor(id) => {
+                        if let Some(el) = document.get_element_by_id(&id) {
+                            focus_element(el);
+                        }
+                    }
+                    FocusTarget::Route => {
+                        let el = ["[autofocus], [data-route-focus]", "h1", "main"]
+                            .into_iter()
+                            .find_map(|selector| {
+                                document.query_selector(selector).ok().flatten()
+                            });
+                        if let Some(el) = el {
+                            focus_element(el);
+                        }
+
+                        let title = document.title();
+                        announce.set(if title.is_empty() {
+                            leptos_dom::helpers::location()
+                                .pathname()
+                                .unwrap_or_default()
+                        } else {
+                            title
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+// runs `update`, which changes the route, in a view transition if they are
+// enabled, returning a future that resolves once it has run
+fn change_route(
+    cx: Scope,
+    view_transitions: bool,
+    back: bool,
+    update: impl FnOnce() + 'static,
+) -> impl std::future::Future<Output = ()> {
+    if view_transitions {
+        Either::Left(crate::view_transition::start(cx, back, update))
+    } else {
+        update();
+        Either::Right(futures::future::ready(()))
+    }
+}
+
+// aborts the work that was tied to the navigation that is ending
+fn abort_all(aborts: &RefCell<Vec<AbortHandle>>) {
+    for handle in aborts.take() {
+        handle.abort();
+    }
+}
+
+// focuses an element, making it focusable from script first if necessary
+#[cfg(not(feature = "ssr"))]
+fn focus_element(el: web_sys::Element) {
+    const FOCUSABLE: [&str; 6] =
+        ["A", "BUTTON", "INPUT", "SELECT", "TEXTAREA", "SUMMARY"];
+    if !el.has_attribute("tabindex")
+        && !FOCUSABLE.contains(&el.tag_name().to_uppercase().as_str())
+    {
+        _ = el.set_attribute("tabindex", "-1");
+    }
+    if let Some(el) = el.dyn_ref::<web_sys::HtmlElement>() {
+        _ = el.focus();
+    }
 }
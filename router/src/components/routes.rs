@@ -2,15 +2,14 @@ use crate::{
     animation::*,
     matching::{
         expand_optionals, get_route_matches, join_paths, Branch, Matcher,
-        RouteDefinition, RouteMatch,
+        RouteDefinition, RouteMatch, RouteRank,
     },
     use_is_back_navigation, RouteContext, RouterContext, SetIsRouting,
 };
 use leptos::{leptos_dom::HydrationCtx, *};
 use std::{
     cell::{Cell, RefCell},
-    cmp::Reverse,
-    collections::HashMap,
+    collections::{hash_map::Entry, HashMap},
     ops::IndexMut,
     rc::Rc,
 };
@@ -20,6 +19,13 @@ use std::{
 /// You should locate the `<Routes/>` component wherever on the page you want the routes to appear.
 ///
 /// **Note:** Your application should only include one `<Routes/>` or `<AnimatedRoutes/>` component.
+///
+/// ## Matching
+/// When several routes match the current URL, the most specific one is displayed, regardless
+/// of the order in which the routes are declared. Paths are compared segment by segment: a
+/// static segment (`/users/new`) beats a param (`/users/:id`), which beats a wildcard
+/// (`/users/*any`). See [`RouteRank`] for the details. If two routes can match exactly the
+/// same paths, a warning is logged and the one declared first is displayed.
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "info", skip_all,)
@@ -47,7 +53,7 @@ pub fn Routes(
         });
     }
 
-    let next_route = router.pathname();
+    let next_route = router.inner.routed_path;
     let current_route = next_route;
 
     let root_equal = Rc::new(Cell::new(true));
@@ -134,7 +140,7 @@ pub fn AnimatedRoutes(
     let is_back = use_is_back_navigation(cx);
     let (animation_state, set_animation_state) =
         create_signal(cx, AnimationState::Finally);
-    let next_route = router.pathname();
+    let next_route = router.inner.routed_path;
 
     let is_complete = Rc::new(Cell::new(true));
     let animation_and_route = create_memo(cx, {
@@ -223,6 +229,99 @@ pub fn AnimatedRoutes(
         .into_view(cx)
 }
 
+/// Displays the route that matches the current URL when it is a modal
+/// route, i.e., when it was navigated to with a
+/// [background location](crate::State::with_background_location) in its
+/// state, and nothing otherwise.
+///
+/// While a modal route is open, the `<Routes/>` keep displaying the page at
+/// its background location, and this shows the route matching the actual
+/// URL, typically in an overlay. Going back, e.g. with the browser’s back
+/// button, closes it and leaves the page underneath as it was, without
+/// refetching its resources. A modal route’s URL that is loaded directly is
+/// displayed by the `<Routes/>`, like any other page.
+///
+/// This must be placed after the `<Routes/>` whose routes it displays, with
+/// the same `base`. See [`use_background_location`](crate::use_background_location)
+/// to know whether a modal route is open, e.g., to lock the page’s scrolling.
+///
+/// ```rust
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # #[component] fn Gallery(cx: Scope) -> impl IntoView {}
+/// # #[component] fn Photo(cx: Scope) -> impl IntoView {}
+/// #[component]
+/// fn App(cx: Scope) -> impl IntoView {
+///     view! { cx,
+///         <Router>
+///             <Routes>
+///                 <Route path="/photos" view=Gallery/>
+///                 <Route path="/photos/:id" view=Photo/>
+///             </Routes>
+///             <div class="modal">
+///                 <ModalOutlet/>
+///             </div>
+///         </Router>
+///     }
+/// }
+///
+/// #[component]
+/// fn Thumbnail(cx: Scope, id: usize) -> impl IntoView {
+///     view! { cx,
+///         <A
+///             href=format!("/photos/{id}")
+///             state=State::default().with_background_location("/photos")
+///         >
+///             <img src=format!("/thumbnails/{id}.jpg")/>
+///         </A>
+///     }
+/// }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all,)
+)]
+#[component]
+pub fn ModalOutlet(
+    cx: Scope,
+    /// Base path relative at which the routes are mounted, as given to the
+    /// `<Routes/>`.
+    #[prop(optional)]
+    base: Option<String>,
+) -> impl IntoView {
+    let router = use_context::<RouterContext>(cx).expect(
+        "<ModalOutlet/> component should be nested within a <Router/>.",
+    );
+    let base = base.unwrap_or_default();
+    let background_location = router.inner.background_location;
+    let is_open =
+        create_memo(cx, move |_| background_location.with(Option::is_some));
+    let prev_disposer = Rc::new(RefCell::new(None::<ScopeDisposer>));
+
+    move || {
+        if let Some(disposer) = prev_disposer.take() {
+            disposer.dispose();
+        }
+        if !is_open.get() {
+            return None;
+        }
+        let (view, disposer) = cx.run_child_scope(|cx| {
+            let root_equal = Rc::new(Cell::new(true));
+            let route_states = route_states(
+                cx,
+                base.clone(),
+                &router,
+                router.pathname(),
+                &root_equal,
+            );
+            let root = root_route(cx, router.base(), route_states, root_equal);
+            (move || root.get()).into_view(cx)
+        });
+        prev_disposer.replace(Some(disposer));
+        Some(view)
+    }
+}
+
 pub(crate) struct Branches;
 
 thread_local! {
@@ -235,32 +334,26 @@ impl Branches {
             let mut current = branches.borrow_mut();
             if !current.contains_key(base) {
                 let mut branches = Vec::new();
-                let children = children
-                    .as_children()
-                    .iter()
-                    .filter_map(|child| {
-                        let def = child
-                            .as_transparent()
-                            .and_then(|t| t.downcast_ref::<RouteDefinition>());
-                        if def.is_none() {
-                            warn!(
-                                "[NOTE] The <Routes/> component should \
-                                 include *only* <Route/>or <ProtectedRoute/> \
-                                 components, or some \
-                                 #[component(transparent)] that returns a \
-                                 RouteDefinition."
-                            );
-                        }
-                        def
-                    })
-                    .cloned()
-                    .collect::<Vec<_>>();
+                let mut route_defs = Vec::new();
+                if !RouteDefinition::collect_from(
+                    children.as_children(),
+                    &mut route_defs,
+                ) {
+                    warn!(
+                        "[NOTE] The <Routes/> component should include \
+                         *only* <Route/>or <ProtectedRoute/> components, \
+                         RouteDefinitions, or some \
+                         #[component(transparent)] that returns a \
+                         RouteDefinition."
+                    );
+                }
                 create_branches(
-                    &children,
+                    &route_defs,
                     base,
                     &mut Vec::new(),
                     &mut branches,
                 );
+                warn_on_duplicate_branches(&branches);
                 current.insert(base.to_string(), branches);
             }
         })
@@ -308,6 +401,9 @@ fn route_states(
             let mut equal = prev_matches
                 .map(|prev_matches| next_matches.len() == prev_matches.len())
                 .unwrap_or(false);
+            // once one level of the route tree has been replaced, every level
+            // nested beneath it needs to be replaced as well
+            let mut remounted = false;
 
             for i in 0..next_matches.len() {
                 let next = next.clone();
@@ -316,11 +412,13 @@ fn route_states(
 
                 match (prev_routes, prev_match) {
                     (Some(prev), Some(prev_match))
-                        if next_match.route.key == prev_match.route.key
-                            && next_match.route.id == prev_match.route.id =>
+                        if !remounted
+                            && can_update_in_place(prev_match, next_match) =>
                     {
                         let prev_one = { prev.borrow()[i].clone() };
-                        if next_match.path_match.path != prev_one.path() {
+                        if next_match.path_match.path
+                            != prev_one.path_untracked()
+                        {
                             prev_one
                                 .set_path(next_match.path_match.path.clone());
                         }
@@ -332,6 +430,7 @@ fn route_states(
                     }
                     _ => {
                         equal = false;
+                        remounted = true;
                         if i == 0 {
                             root_equal.set(false);
                         }
@@ -401,6 +500,20 @@ fn route_states(
     })
 }
 
+/// Whether the view for a matched route can be kept mounted, with only its
+/// path and params updated, rather than being recreated.
+///
+/// This is the case when the same route definition has been matched using the
+/// same pattern, and only the values of its params have changed, unless the
+/// route has opted out with `force_remount`.
+fn can_update_in_place(prev: &RouteMatch, next: &RouteMatch) -> bool {
+    next.route.key == prev.route.key
+        && next.route.id == prev.route.id
+        && next.route.pattern == prev.route.pattern
+        && (!next.route.key.force_remount
+            || next.path_match.path == prev.path_match.path)
+}
+
 fn root_route(
     cx: Scope,
     base_route: RouteContext,
@@ -493,26 +606,6 @@ pub struct RouteData {
     pub matcher: Matcher,
 }
 
-impl RouteData {
-    fn score(&self) -> i32 {
-        let (pattern, splat) = match self.pattern.split_once("/*") {
-            Some((p, s)) => (p, Some(s)),
-            None => (self.pattern.as_str(), None),
-        };
-        let segments = pattern
-            .split('/')
-            .filter(|n| !n.is_empty())
-            .collect::<Vec<_>>();
-        #[allow(clippy::bool_to_int_with_if)] // on the splat.is_none()
-        segments.iter().fold(
-            (segments.len() as i32) - if splat.is_none() { 0 } else { 1 },
-            |score, segment| {
-                score + if segment.starts_with(':') { 2 } else { 3 }
-            },
-        )
-    }
-}
-
 fn create_branches(
     route_defs: &[RouteDefinition],
     base: &str,
@@ -525,7 +618,7 @@ fn create_branches(
             stack.push(route.clone());
 
             if def.children.is_empty() {
-                let branch = create_branch(stack, branches.len());
+                let branch = create_branch(stack);
                 branches.push(branch);
             } else {
                 create_branches(&def.children, &route.pattern, stack, branches);
@@ -536,14 +629,57 @@ fn create_branches(
     }
 
     if stack.is_empty() {
-        branches.sort_by_key(|branch| Reverse(branch.score));
+        // the sort is stable, so routes that can never match the same
+        // path stay in the order in which they were declared
+        branches.sort_by(|a, b| b.rank.cmp(&a.rank));
+    }
+}
+
+/// Warns about routes that match exactly the same paths, as only the first
+/// of them can ever be displayed.
+fn warn_on_duplicate_branches(branches: &[Branch]) {
+    // params and wildcards match the same paths, whatever they're called
+    fn normalize(pattern: &str) -> String {
+        pattern
+            .split('/')
+            .map(|segment| {
+                if segment.starts_with(':') {
+                    ":"
+                } else if segment.starts_with('*') {
+                    "*"
+                } else {
+                    segment
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    // branches are sorted by priority, so the first one wins
+    for branch in branches {
+        let Some(route) = branch.routes.last() else {
+            continue;
+        };
+        match seen.entry(normalize(&route.pattern)) {
+            Entry::Occupied(first) => warn!(
+                "[Leptos Router] The routes {:?} and {:?} match the same \
+                 paths, so only {:?} will be displayed.",
+                first.get(),
+                route.pattern,
+                first.get()
+            ),
+            Entry::Vacant(entry) => {
+                entry.insert(&route.pattern);
+            }
+        }
     }
 }
 
-pub(crate) fn create_branch(routes: &[RouteData], index: usize) -> Branch {
+pub(crate) fn create_branch(routes: &[RouteData]) -> Branch {
     Branch {
         routes: routes.to_vec(),
-        score: routes.last().unwrap().score() * 10000 - (index as i32),
+        rank: RouteRank::new(&routes.last().unwrap().pattern),
     }
 }
 #[cfg_attr(
@@ -0,0 +1,248 @@
+use super::History;
+use leptos::*;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+};
+
+/// The most history entries whose preserved state is kept in memory. The
+/// entries that were left longest ago are dropped first.
+const MAX_PRESERVED_ENTRIES: usize = 32;
+
+/// The largest size, in bytes of serialized JSON, of the state preserved
+/// for a single history entry.
+const MAX_PRESERVED_SIZE: usize = 64 * 1024;
+
+/// The name under which the scroll position of an entry is preserved.
+const SCROLL_KEY: &str = "leptos_router::scroll";
+
+/// Tracks which history entry the router is showing, and stores the state
+/// that routes with `preserve_state` snapshot for each entry.
+///
+/// Each entry is identified by a key that the [`History`] keeps alongside
+/// the entry, so that the router can tell which entry it is returning to
+/// when the user moves through the history.
+#[derive(Debug, Default)]
+pub(crate) struct HistoryEntries {
+    current: Cell<u64>,
+    last: Cell<u64>,
+    snapshots: RefCell<VecDeque<EntrySnapshot>>,
+}
+
+#[derive(Debug)]
+struct EntrySnapshot {
+    key: u64,
+    path: String,
+    values: HashMap<String, String>,
+}
+
+impl EntrySnapshot {
+    fn size(&self) -> usize {
+        self.values.iter().map(|(k, v)| k.len() + v.len()).sum()
+    }
+}
+
+impl HistoryEntries {
+    /// Starts tracking the current entry of the given history, giving it a
+    /// key if it does not have one yet.
+    pub fn new(history: &dyn History) -> Self {
+        let key = history.entry_key().unwrap_or_else(|| {
+            history.set_entry_key(1);
+            1
+        });
+        Self {
+            current: Cell::new(key),
+            last: Cell::new(key),
+            snapshots: Default::default(),
+        }
+    }
+
+    /// The key of the entry that is currently shown.
+    pub fn current(&self) -> u64 {
+        self.current.get()
+    }
+
+    /// Moves to the entry the history is about to show, and returns its key,
+    /// and whether it is an entry that has been shown before.
+    ///
+    /// The history either already shows an entry the router has seen, after
+    /// the user moved through it, or the router is about to push a new
+    /// entry, or to `replace` the current one. A new entry gets a new key,
+    /// which has to be stored with [`History::set_entry_key`] once the
+    /// history shows it.
+    pub fn enter(&self, history: &dyn History, replace: bool) -> (u64, bool) {
+        let current = self.current.get();
+        match history.entry_key() {
+            Some(key) if key != current => {
+                self.current.set(key);
+                self.last.set(self.last.get().max(key));
+                (key, true)
+            }
+            Some(_) if replace => (current, false),
+            _ => {
+                let key = self.last.get() + 1;
+                self.current.set(key);
+                self.last.set(key);
+                (key, false)
+            }
+        }
+    }
+
+    /// Returns the serialized value preserved under `name` for the entry
+    /// with the given key, if it was preserved while showing `path`.
+    pub fn load(&self, key: u64, path: &str, name: &str) -> Option<String> {
+        self.snapshots
+            .borrow()
+            .iter()
+            .find(|snapshot| snapshot.key == key && snapshot.path == path)
+            .and_then(|snapshot| snapshot.values.get(name).cloned())
+    }
+
+    /// Preserves a serialized value under `name` for the entry with the
+    /// given key, which is showing `path`.
+    ///
+    /// Values that would take the entry’s preserved state over its size
+    /// limit are dropped, and will fall back to their defaults.
+    pub fn save(&self, key: u64, path: &str, name: &str, value: String) {
+        let mut snapshots = self.snapshots.borrow_mut();
+        let mut snapshot = snapshots
+            .iter()
+            .position(|snapshot| snapshot.key == key)
+            .and_then(|idx| snapshots.remove(idx))
+            .filter(|snapshot| snapshot.path == path)
+            .unwrap_or_else(|| EntrySnapshot {
+                key,
+                path: path.to_string(),
+                values: HashMap::new(),
+            });
+
+        snapshot.values.remove(name);
+        if snapshot.size() + name.len() + value.len() > MAX_PRESERVED_SIZE {
+            debug_warn!(
+                "The state preserved as {name:?} for {path:?} is larger than \
+                 {MAX_PRESERVED_SIZE} bytes, and will not be restored."
+            );
+        } else {
+            snapshot.values.insert(name.to_string(), value);
+        }
+
+        // the most recently left entries are kept at the back
+        snapshots.push_back(snapshot);
+        while snapshots.len() > MAX_PRESERVED_ENTRIES {
+            snapshots.pop_front();
+        }
+    }
+
+    /// Preserves the scroll position of the current entry when `cx` is
+    /// disposed, and lets the browser leave scrolling to the router until
+    /// then.
+    pub fn preserve_scroll(self: &Rc<Self>, cx: Scope, path: &str) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                use web_sys::ScrollRestoration;
+
+                let history = window().history().ok();
+                if let Some(history) = &history {
+                    _ = history.set_scroll_restoration(ScrollRestoration::Manual);
+                }
+
+                let key = self.current();
+                let path = path.to_string();
+                let entries = Rc::clone(self);
+                on_cleanup(cx, move || {
+                    if let Some(history) = &history {
+                        _ = history.set_scroll_restoration(ScrollRestoration::Auto);
+                    }
+                    let window = window();
+                    let (x, y) = (
+                        window.scroll_x().unwrap_or_default(),
+                        window.scroll_y().unwrap_or_default(),
+                    );
+                    entries.save(key, &path, SCROLL_KEY, format!("[{x},{y}]"));
+                });
+            } else {
+                _ = (cx, path);
+            }
+        }
+    }
+
+    /// Scrolls back to the position preserved for the entry with the given
+    /// key, if there is one.
+    pub fn restore_scroll(&self, key: u64, path: &str) {
+        let scroll = self.load(key, path, SCROLL_KEY).and_then(|scroll| {
+            serde_json::from_str::<(f64, f64)>(&scroll).ok()
+        });
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                if let Some((x, y)) = scroll {
+                    window().scroll_to_with_x_and_y(x, y);
+                }
+            } else {
+                _ = scroll;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LocationChange, State, TestingIntegration};
+
+    #[test]
+    fn entries_are_keyed_on_push_and_recognized_on_return() {
+        let history = TestingIntegration::new("/");
+        let entries = HistoryEntries::new(&history);
+        assert_eq!(history.entry_key(), Some(1));
+
+        // the router enters a new entry before the history pushes it
+        let (key, revisited) = entries.enter(&history, false);
+        assert_eq!((key, revisited), (2, false));
+        history.navigate(&LocationChange {
+            value: "/about".into(),
+            replace: false,
+            scroll: true,
+            state: State::default(),
+        });
+        history.set_entry_key(key);
+
+        // replacing keeps the entry
+        assert_eq!(entries.enter(&history, true), (2, false));
+
+        history.back();
+        assert_eq!(entries.enter(&history, true), (1, true));
+        assert_eq!(entries.current(), 1);
+    }
+
+    #[test]
+    fn preserved_state_is_kept_per_entry_and_path() {
+        let history = TestingIntegration::new("/");
+        let entries = HistoryEntries::new(&history);
+        entries.save(1, "/", "draft", "\"hello\"".into());
+        assert_eq!(entries.load(1, "/", "draft").as_deref(), Some("\"hello\""));
+        assert_eq!(entries.load(1, "/", "other"), None);
+        assert_eq!(entries.load(2, "/", "draft"), None);
+        // a different page replaced the entry
+        assert_eq!(entries.load(1, "/about", "draft"), None);
+        entries.save(1, "/about", "page", "1".into());
+        assert_eq!(entries.load(1, "/", "draft"), None);
+    }
+
+    #[test]
+    fn preserved_state_is_size_limited() {
+        let history = TestingIntegration::new("/");
+        let entries = HistoryEntries::new(&history);
+        entries.save(1, "/", "small", "1".into());
+        entries.save(1, "/", "large", "x".repeat(MAX_PRESERVED_SIZE));
+        assert_eq!(entries.load(1, "/", "small").as_deref(), Some("1"));
+        assert_eq!(entries.load(1, "/", "large"), None);
+
+        for key in 2..=(MAX_PRESERVED_ENTRIES as u64 + 1) {
+            entries.save(key, "/", "small", "1".into());
+        }
+        // the entry left longest ago has been dropped
+        assert_eq!(entries.load(1, "/", "small"), None);
+        assert_eq!(entries.load(2, "/", "small").as_deref(), Some("1"));
+    }
+}
@@ -47,6 +47,7 @@ pub struct Location {
     /// The hash fragment.
     pub hash: Memo<String>,
     /// The [`state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state) at the top of the history stack.
+    /// Read it as a typed value with [`State::try_get`].
     pub state: ReadSignal<State>,
 }
 
@@ -1,13 +1,18 @@
 use leptos::*;
-use std::rc::Rc;
-use wasm_bindgen::UnwrapThrowExt;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::{JsValue, UnwrapThrowExt};
 
+mod entries;
 mod location;
 mod params;
 mod state;
 mod url;
 
 pub use self::url::*;
+pub(crate) use entries::*;
 pub use location::*;
 pub use params::*;
 pub use state::*;
@@ -27,6 +32,21 @@ pub trait History {
 
     /// Called to navigate to a new location.
     fn navigate(&self, loc: &LocationChange);
+
+    /// The key the router stored for the current history entry with
+    /// [`History::set_entry_key`], if any.
+    ///
+    /// The router uses these keys to tell history entries apart, so that it
+    /// can restore [preserved state](crate::use_preserved_state) when the user
+    /// returns to an entry. Histories that cannot store them can leave this
+    /// unimplemented, and state will not be restored.
+    fn entry_key(&self) -> Option<u64> {
+        None
+    }
+
+    /// Stores a key for the current history entry, which should be returned
+    /// by [`History::entry_key`] whenever this entry is shown again.
+    fn set_entry_key(&self, _key: u64) {}
 }
 
 /// The default integration when you are running in the browser, which uses
@@ -37,13 +57,19 @@ pub struct BrowserIntegration {}
 impl BrowserIntegration {
     fn current() -> LocationChange {
         let loc = leptos_dom::helpers::location();
+        // the state is kept in `history.state`, so it survives reloads
+        let state = leptos_dom::window()
+            .history()
+            .and_then(|history| history.state())
+            .map(|state| State::from_js_value(&state))
+            .unwrap_or_default();
         LocationChange {
             value: loc.pathname().unwrap_or_default()
                 + &loc.search().unwrap_or_default()
                 + &loc.hash().unwrap_or_default(),
             replace: true,
             scroll: true,
-            state: State(None),
+            state,
         }
     }
 }
@@ -86,6 +112,7 @@ impl History for BrowserIntegration {
                         replace: change.replace,
                         scroll: change.scroll,
                         state: change.state,
+                        focus: true,
                     },
                 ) {
                     leptos::error!("{e:#?}");
@@ -137,8 +164,39 @@ impl History for BrowserIntegration {
             leptos_dom::window().scroll_to_with_x_and_y(0.0, 0.0);
         }
     }
+
+    fn entry_key(&self) -> Option<u64> {
+        let state = leptos_dom::window().history().ok()?.state().ok()?;
+        js_sys::Reflect::get(&state, &JsValue::from_str(ENTRY_KEY))
+            .ok()?
+            .as_f64()
+            .map(|key| key as u64)
+    }
+
+    fn set_entry_key(&self, key: u64) {
+        let history = leptos_dom::window().history().unwrap_throw();
+        // the key is added to the entry's state object; entries with other
+        // kinds of state can't be told apart
+        let state = history.state().unwrap_or(JsValue::NULL);
+        let state = if state.is_null() || state.is_undefined() {
+            js_sys::Object::new().into()
+        } else if state.is_object() {
+            state
+        } else {
+            return;
+        };
+        _ = js_sys::Reflect::set(
+            &state,
+            &JsValue::from_str(ENTRY_KEY),
+            &JsValue::from_f64(key as f64),
+        );
+        _ = history.replace_state(&state, "");
+    }
 }
 
+// the property of `history.state` under which the router keeps entry keys
+const ENTRY_KEY: &str = "__leptos_entry_key";
+
 /// The wrapper type that the [Router](crate::Router) uses to interact with a [History].
 /// This is automatically provided in the browser. For the server, it should be provided
 /// as a context. Be sure that it can survive conversion to a URL in the browser.
@@ -171,6 +229,14 @@ impl History for RouterIntegrationContext {
     fn navigate(&self, loc: &LocationChange) {
         self.0.navigate(loc)
     }
+
+    fn entry_key(&self) -> Option<u64> {
+        self.0.entry_key()
+    }
+
+    fn set_entry_key(&self, key: u64) {
+        self.0.set_entry_key(key)
+    }
 }
 
 /// A generic router integration for the server side.
@@ -204,7 +270,7 @@ impl History for ServerIntegration {
                 value: self.path.clone(),
                 replace: false,
                 scroll: true,
-                state: State(None),
+                state: State::default(),
             },
         )
         .0
@@ -212,3 +278,208 @@ impl History for ServerIntegration {
 
     fn navigate(&self, _loc: &LocationChange) {}
 }
+
+/// A router integration for tests and other environments without a browser.
+///
+/// It keeps a stack of history entries in memory and records every
+/// navigation made by the router, so that tests can check where the router
+/// went, and it lets tests change the location programmatically, as if the
+/// user had entered a URL, or used the browser’s back and forward buttons.
+///
+/// ```
+/// # use leptos_router::*;
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let history = TestingIntegration::new("/");
+/// view! { cx,
+///     <Router history=history.clone()>
+///         <Routes>
+///             <Route path="/" view=|cx| view! { cx, "Home" }/>
+///             <Route path="/about" view=|cx| view! { cx, "About" }/>
+///         </Routes>
+///     </Router>
+/// };
+///
+/// history.push("/about");
+/// assert_eq!(history.current(), "/about");
+/// history.back();
+/// assert_eq!(history.current(), "/");
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct TestingIntegration {
+    inner: Rc<TestingHistory>,
+}
+
+#[derive(Debug)]
+struct TestingHistory {
+    entries: RefCell<Vec<TestingEntry>>,
+    index: Cell<usize>,
+    set_location: RefCell<Vec<WriteSignal<LocationChange>>>,
+    navigations: RefCell<Vec<LocationChange>>,
+}
+
+#[derive(Debug)]
+struct TestingEntry {
+    path: String,
+    // the key the router stored for the entry
+    key: Option<u64>,
+    state: State,
+}
+
+impl TestingEntry {
+    fn new(path: String, state: State) -> Self {
+        Self {
+            path,
+            key: None,
+            state,
+        }
+    }
+}
+
+impl TestingIntegration {
+    /// Creates a new integration, starting at the given path.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            inner: Rc::new(TestingHistory {
+                entries: RefCell::new(vec![TestingEntry::new(
+                    path.into(),
+                    State::default(),
+                )]),
+                index: Cell::new(0),
+                set_location: Default::default(),
+                navigations: Default::default(),
+            }),
+        }
+    }
+
+    /// The current location.
+    pub fn current(&self) -> String {
+        self.inner.entries.borrow()[self.inner.index.get()]
+            .path
+            .clone()
+    }
+
+    /// The [`State`] stored with the current history entry.
+    pub fn current_state(&self) -> State {
+        self.inner.entries.borrow()[self.inner.index.get()]
+            .state
+            .clone()
+    }
+
+    /// Every navigation made by the router so far, in order.
+    pub fn navigations(&self) -> Vec<LocationChange> {
+        self.inner.navigations.borrow().clone()
+    }
+
+    /// Pushes a new entry for `path` onto the history stack, dropping any
+    /// entries ahead of the current one, and sets the router's location to
+    /// it. Unlike a navigation made by the router, it isn't recorded in
+    /// [`navigations`](Self::navigations).
+    pub fn push(&self, path: impl Into<String>) {
+        self.inner.push(path.into(), State::default());
+        self.notify();
+    }
+
+    /// Pushes a new entry with the given state onto the history stack, and
+    /// notifies the router, like [`push`](Self::push).
+    pub fn push_with_state(&self, path: impl Into<String>, state: State) {
+        self.inner.push(path.into(), state);
+        self.notify();
+    }
+
+    /// Moves back to the previous history entry, if there is one, and
+    /// notifies the router, as if the user had pressed the back button.
+    pub fn back(&self) {
+        let index = self.inner.index.get();
+        if index > 0 {
+            self.inner.index.set(index - 1);
+            self.notify();
+        }
+    }
+
+    /// Moves forward to the next history entry, if there is one, and
+    /// notifies the router, as if the user had pressed the forward button.
+    pub fn forward(&self) {
+        let index = self.inner.index.get();
+        if index + 1 < self.inner.entries.borrow().len() {
+            self.inner.index.set(index + 1);
+            self.notify();
+        }
+    }
+
+    fn notify(&self) {
+        let path = self.current();
+        let state = self.current_state();
+        for set_location in self.inner.set_location.borrow().iter() {
+            set_location.set(LocationChange {
+                value: path.clone(),
+                replace: false,
+                scroll: true,
+                state: state.clone(),
+            });
+        }
+    }
+}
+
+impl TestingHistory {
+    fn push(&self, path: String, state: State) {
+        let mut entries = self.entries.borrow_mut();
+        let index = self.index.get() + 1;
+        entries.truncate(index);
+        entries.push(TestingEntry::new(path, state));
+        self.index.set(index);
+    }
+}
+
+impl History for TestingIntegration {
+    fn location(&self, cx: Scope) -> ReadSignal<LocationChange> {
+        let (location, set_location) = create_signal(
+            cx,
+            LocationChange {
+                value: self.current(),
+                replace: true,
+                scroll: true,
+                state: self.current_state(),
+            },
+        );
+        self.inner.set_location.borrow_mut().push(set_location);
+        location
+    }
+
+    fn navigate(&self, loc: &LocationChange) {
+        if loc.replace {
+            self.inner.entries.borrow_mut()[self.inner.index.get()] =
+                TestingEntry::new(loc.value.clone(), loc.state.clone());
+        } else {
+            self.inner.push(loc.value.clone(), loc.state.clone());
+        }
+        self.inner.navigations.borrow_mut().push(loc.clone());
+    }
+
+    fn entry_key(&self) -> Option<u64> {
+        self.inner.entries.borrow()[self.inner.index.get()].key
+    }
+
+    fn set_entry_key(&self, key: u64) {
+        self.inner.entries.borrow_mut()[self.inner.index.get()].key = Some(key);
+    }
+}
+
+impl From<BrowserIntegration> for RouterIntegrationContext {
+    fn from(history: BrowserIntegration) -> Self {
+        Self::new(history)
+    }
+}
+
+impl From<ServerIntegration> for RouterIntegrationContext {
+    fn from(history: ServerIntegration) -> Self {
+        Self::new(history)
+    }
+}
+
+impl From<TestingIntegration> for RouterIntegrationContext {
+    fn from(history: TestingIntegration) -> Self {
+        Self::new(history)
+    }
+}
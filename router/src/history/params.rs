@@ -1,3 +1,4 @@
+use crate::RouterError;
 use linear_map::LinearMap;
 use serde::{Deserialize, Serialize};
 use std::{str::FromStr, sync::Arc};
@@ -168,6 +169,9 @@ pub enum ParamsError {
     /// Something went wrong while deserializing a field.
     #[error("failed to deserialize parameters")]
     Params(Arc<dyn std::error::Error + Send + Sync>),
+    /// The params were read outside a [`Router`](crate::Router).
+    #[error(transparent)]
+    Router(#[from] RouterError),
 }
 
 impl PartialEq for ParamsError {
@@ -175,6 +179,7 @@ impl PartialEq for ParamsError {
         match (self, other) {
             (Self::MissingParam(l0), Self::MissingParam(r0)) => l0 == r0,
             (Self::Params(_), Self::Params(_)) => false,
+            (Self::Router(l0), Self::Router(r0)) => l0 == r0,
             _ => false,
         }
     }
@@ -1,22 +1,250 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::rc::Rc;
+use thiserror::Error;
 use wasm_bindgen::JsValue;
 
+/// The largest [`State`] that can be pushed with a history entry, in bytes
+/// of JSON. Some browsers refuse to store larger values in `history.state`.
+pub const MAX_STATE_SIZE: usize = 640 * 1024;
+
+// the property of `history.state` under which the router keeps the state
+const STATE_KEY: &str = "__leptos_state";
+// the property under which it keeps the background location of a modal route
+const BACKGROUND_KEY: &str = "__leptos_background";
+// the property under which a link keeps the error from serializing its state,
+// so that following it fails like navigating with the state would
+const ERROR_KEY: &str = "__leptos_state_error";
+
+/// A value stored with a history entry, as its
+/// [`state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state).
+///
+/// State is pushed along with a navigation, with [`NavigateOptions::state`](crate::NavigateOptions::state)
+/// or the `state` prop of [`A`](crate::A), and can be read back, for as long
+/// as the user stays on that entry, from [`Location::state`](crate::Location::state).
+/// It is stored as JSON, so it is still there if the user reloads the page
+/// or comes back to the entry with the back and forward buttons.
+///
+/// ```
+/// # use leptos_router::*;
+/// #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+/// struct Scroll {
+///     top: u32,
+/// }
+///
+/// let state = State::new(&Scroll { top: 120 });
+/// assert_eq!(state.try_get::<Scroll>(), Ok(Some(Scroll { top: 120 })));
+///
+/// // an entry without state
+/// assert_eq!(State::default().try_get::<Scroll>(), Ok(None));
+/// ```
+///
+/// A value that can’t be serialized, or that is larger than
+/// [`MAX_STATE_SIZE`], makes the navigation that pushes it fail with a
+/// [`NavigationError::State`](crate::NavigationError::State). An [`A`](crate::A)
+/// with such state doesn't navigate when it is clicked, and logs the error.
+///
+/// State can also carry a [background location](State::with_background_location),
+/// which turns the entry into a modal route.
 #[derive(Debug, Clone, Default, PartialEq)]
-pub struct State(pub Option<JsValue>);
+pub struct State {
+    value: Option<Result<Rc<str>, StateError>>,
+    background_location: Option<Rc<str>>,
+}
 
 impl State {
+    /// Serializes a value to be stored with a history entry.
+    pub fn new<T>(value: &T) -> Self
+    where
+        T: Serialize + ?Sized,
+    {
+        let json = serde_json::to_string(value)
+            .map_err(|e| StateError::Serialize(e.to_string()))
+            .and_then(|json| {
+                if json.len() > MAX_STATE_SIZE {
+                    Err(StateError::TooLarge { size: json.len() })
+                } else {
+                    Ok(json.into())
+                }
+            });
+        Self {
+            value: Some(json),
+            background_location: None,
+        }
+    }
+
+    /// Sets the location, like `"/photos"`, that is displayed by the
+    /// `<Routes/>` while this entry is the current one, making it a modal
+    /// route.
+    ///
+    /// The routes keep showing the page at the background location, without
+    /// rerendering it if it was already displayed, and the route matching
+    /// the entry’s actual URL is shown by a [`ModalOutlet`](crate::ModalOutlet)
+    /// instead. Going back to the background location closes the modal.
+    /// Loading the entry’s URL directly, or on the server, shows its route
+    /// in the `<Routes/>` as usual.
+    ///
+    /// ```
+    /// # use leptos_router::*;
+    /// let state = State::default().with_background_location("/photos");
+    /// assert_eq!(state.background_location(), Some("/photos"));
+    /// ```
+    pub fn with_background_location(
+        mut self,
+        location: impl Into<String>,
+    ) -> Self {
+        self.background_location = Some(location.into().into());
+        self
+    }
+
+    /// The location displayed by the `<Routes/>` while this entry is the
+    /// current one, if it is a modal route.
+    pub fn background_location(&self) -> Option<&str> {
+        self.background_location.as_deref()
+    }
+
+    pub(crate) fn without_background(mut self) -> Self {
+        self.background_location = None;
+        self
+    }
+
+    /// Whether this entry has no state.
+    pub fn is_none(&self) -> bool {
+        self.value.is_none() && self.background_location.is_none()
+    }
+
+    /// Deserializes the state as a `T`, returning `Ok(None)` if there is no
+    /// state, or an error if it was stored as a different type.
+    pub fn try_get<T>(&self) -> Result<Option<T>, StateError>
+    where
+        T: DeserializeOwned,
+    {
+        match &self.value {
+            None => Ok(None),
+            Some(Err(e)) => Err(e.clone()),
+            Some(Ok(json)) => {
+                serde_json::from_str(json).map(Some).map_err(|e| {
+                    StateError::Deserialize {
+                        ty: std::any::type_name::<T>(),
+                        message: e.to_string(),
+                    }
+                })
+            }
+        }
+    }
+
+    /// Deserializes the state as a `T`, if there is state of that type.
+    pub fn get<T>(&self) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.try_get().ok().flatten()
+    }
+
+    /// The error that occurred when serializing the state, if any.
+    pub fn error(&self) -> Option<&StateError> {
+        match &self.value {
+            Some(Err(e)) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The state as a JavaScript object that can be stored in
+    /// `history.state`, or `undefined` if there is none.
+    ///
+    /// If the state could not be serialized, the object holds the error
+    /// instead, which [`State::from_js_value`] reads back.
     pub fn to_js_value(&self) -> JsValue {
-        match &self.0 {
-            Some(v) => v.clone(),
-            None => JsValue::UNDEFINED,
+        let (json, error) = match &self.value {
+            Some(Ok(json)) => (Some(json), None),
+            Some(Err(e)) => (None, Some(e)),
+            None => (None, None),
+        };
+        if json.is_none()
+            && error.is_none()
+            && self.background_location.is_none()
+        {
+            return JsValue::UNDEFINED;
         }
+        let object = js_sys::Object::new();
+        for (key, value) in [
+            (STATE_KEY, json),
+            (BACKGROUND_KEY, self.background_location.as_ref()),
+        ] {
+            if let Some(value) = value {
+                _ = js_sys::Reflect::set(
+                    &object,
+                    &JsValue::from_str(key),
+                    &JsValue::from_str(value),
+                );
+            }
+        }
+        if let Some(error) = error {
+            let error = match error {
+                StateError::TooLarge { size } => {
+                    JsValue::from_f64(*size as f64)
+                }
+                StateError::Serialize(message)
+                | StateError::Deserialize { message, .. } => {
+                    JsValue::from_str(message)
+                }
+            };
+            _ = js_sys::Reflect::set(
+                &object,
+                &JsValue::from_str(ERROR_KEY),
+                &error,
+            );
+        }
+        object.into()
     }
-}
 
-impl<T> From<T> for State
-where
-    T: Into<JsValue>,
-{
-    fn from(value: T) -> Self {
-        State(Some(value.into()))
+    /// Reads the state from a value created by [`State::to_js_value`], such
+    /// as `history.state`. Any other value is treated as no state.
+    pub fn from_js_value(value: &JsValue) -> Self {
+        if !value.is_object() {
+            return Self::default();
+        }
+        let get = |key| {
+            js_sys::Reflect::get(value, &JsValue::from_str(key))
+                .ok()
+                .and_then(|value| value.as_string())
+                .map(Rc::from)
+        };
+        let error = js_sys::Reflect::get(value, &JsValue::from_str(ERROR_KEY))
+            .ok()
+            .and_then(|error| match error.as_f64() {
+                Some(size) => Some(StateError::TooLarge {
+                    size: size as usize,
+                }),
+                None => error.as_string().map(StateError::Serialize),
+            });
+        Self {
+            value: error.map(Err).or_else(|| get(STATE_KEY).map(Ok)),
+            background_location: get(BACKGROUND_KEY),
+        }
     }
 }
+
+/// An error that occurred while storing or reading a [`State`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum StateError {
+    /// The value could not be serialized as JSON.
+    #[error("history state could not be serialized: {0}")]
+    Serialize(String),
+    /// The value is larger than [`MAX_STATE_SIZE`] once serialized.
+    #[error(
+        "history state is {size} bytes when serialized, over the limit of \
+         {MAX_STATE_SIZE} bytes"
+    )]
+    TooLarge {
+        /// The size of the serialized value, in bytes.
+        size: usize,
+    },
+    /// The stored state is not a value of the requested type.
+    #[error("history state could not be read as `{ty}`: {message}")]
+    Deserialize {
+        /// The requested type.
+        ty: &'static str,
+        /// Why the state could not be deserialized.
+        message: String,
+    },
+}
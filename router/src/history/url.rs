@@ -1,9 +1,9 @@
 use crate::ParamsMap;
-#[cfg(not(feature = "ssr"))]
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
 use js_sys::{try_iter, Array, JsString};
-#[cfg(not(feature = "ssr"))]
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
 use wasm_bindgen::JsCast;
-#[cfg(not(feature = "ssr"))]
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
 use wasm_bindgen::JsValue;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -15,23 +15,30 @@ pub struct Url {
     pub hash: String,
 }
 
-#[cfg(not(feature = "ssr"))]
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
 pub fn unescape(s: &str) -> String {
     js_sys::decode_uri(s).unwrap().into()
 }
 
-#[cfg(feature = "ssr")]
+#[cfg(any(feature = "ssr", not(target_arch = "wasm32")))]
+pub fn unescape(s: &str) -> String {
+    percent_encoding::percent_decode_str(s)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+#[cfg(any(feature = "ssr", not(target_arch = "wasm32")))]
 pub fn escape(s: &str) -> String {
     percent_encoding::utf8_percent_encode(s, percent_encoding::NON_ALPHANUMERIC)
         .to_string()
 }
 
-#[cfg(not(feature = "ssr"))]
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
 pub fn escape(s: &str) -> String {
     js_sys::encode_uri(s).as_string().unwrap()
 }
 
-#[cfg(not(feature = "ssr"))]
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
 impl TryFrom<&str> for Url {
     type Error = String;
 
@@ -79,12 +86,16 @@ impl TryFrom<&str> for Url {
     }
 }
 
-#[cfg(feature = "ssr")]
+#[cfg(any(feature = "ssr", not(target_arch = "wasm32")))]
 impl TryFrom<&str> for Url {
     type Error = String;
 
     fn try_from(url: &str) -> Result<Self, Self::Error> {
-        let url = url::Url::parse(url).map_err(|e| e.to_string())?;
+        let fake_host = url::Url::parse("http://leptos").unwrap();
+        let url = url::Url::options()
+            .base_url(Some(&fake_host))
+            .parse(url)
+            .map_err(|e| e.to_string())?;
         Ok(Self {
             origin: url.origin().unicode_serialization(),
             pathname: url.path().to_string(),
@@ -94,17 +105,20 @@ impl TryFrom<&str> for Url {
                     .map(|(key, value)| (key.to_string(), value.to_string()))
                     .collect::<linear_map::LinearMap<String, String>>(),
             ),
-            hash: Default::default(),
+            hash: url
+                .fragment()
+                .map(|hash| format!("#{hash}"))
+                .unwrap_or_default(),
         })
     }
 }
 
-#[cfg(not(feature = "ssr"))]
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
 trait MapJsError<T> {
     fn map_js_error(self) -> Result<T, String>;
 }
 
-#[cfg(not(feature = "ssr"))]
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
 impl<T> MapJsError<T> for Result<T, JsValue> {
     fn map_js_error(self) -> Result<T, String> {
         self.map_err(|e| e.as_string().unwrap_or_default())
@@ -1,8 +1,11 @@
 use crate::{
     Location, NavigateOptions, NavigationError, Params, ParamsError, ParamsMap,
-    RouteContext, RouterContext,
+    PreservedStateRoute, RouteContext, RouterContext, RouterError, SsrMode,
 };
-use leptos::{create_memo, signal_prelude::*, use_context, Memo, Scope};
+use leptos::{
+    create_memo, on_cleanup, signal_prelude::*, use_context, Memo, Scope,
+};
+use serde::{de::DeserializeOwned, Serialize};
 use std::{borrow::Cow, rc::Rc, str::FromStr};
 
 /// Constructs a signal synchronized with a specific URL query parameter.
@@ -41,6 +44,10 @@ use std::{borrow::Cow, rc::Rc, str::FromStr};
 ///     }
 /// }
 /// ```
+///
+/// Outside a [`Router`](crate::Router), the value is always `None`, and setting
+/// it does nothing.
+#[track_caller]
 pub fn create_query_signal<T>(
     cx: Scope,
     key: impl Into<Cow<'static, str>>,
@@ -51,7 +58,7 @@ where
     let key = key.into();
     let query_map = use_query_map(cx);
     let navigate = use_navigate(cx);
-    let location = use_location(cx);
+    let pathname = use_location(cx).ok().map(|location| location.pathname);
 
     let get = create_memo(cx, {
         let key = key.clone();
@@ -71,8 +78,11 @@ where
                 new_query_map.remove(&key);
             }
         }
+        let Some(pathname) = pathname else {
+            return;
+        };
         let qs = new_query_map.to_query_string();
-        let path = location.pathname.get();
+        let path = pathname.get();
         let new_url = format!("{path}{qs}");
         let _ = navigate(&new_url, NavigateOptions::default());
     });
@@ -80,60 +90,253 @@ where
     (get, set)
 }
 
-/// Returns the current [RouterContext], containing information about the router's state.
-pub fn use_router(cx: Scope) -> RouterContext {
-    if let Some(router) = use_context::<RouterContext>(cx) {
-        router
-    } else {
-        leptos::leptos_dom::debug_warn!(
-            "You must call use_router() within a <Router/> component"
-        );
-        panic!("You must call use_router() within a <Router/> component");
+/// Returns the current [RouterContext], containing information about the router's state,
+/// or an error if it is called from a component that is not rendered inside a
+/// [`Router`](crate::Router).
+#[track_caller]
+pub fn use_router(cx: Scope) -> Result<RouterContext, RouterError> {
+    match use_context::<RouterContext>(cx) {
+        Some(router) => Ok(router),
+        None => Err(RouterError::outside_router("use_router")),
+    }
+}
+
+/// Returns the current [RouterContext], like [use_router].
+///
+/// ## Panics
+/// Panics, naming the place it was called from and, in debug builds, the
+/// component that called it, if it is called from a component that is not
+/// rendered inside a [`Router`](crate::Router).
+#[track_caller]
+pub fn expect_router(cx: Scope) -> RouterContext {
+    match use_context::<RouterContext>(cx) {
+        Some(router) => router,
+        None => RouterError::panic_outside_router("expect_router"),
     }
 }
 
 /// Returns the current [RouteContext], containing information about the matched route.
-pub fn use_route(cx: Scope) -> RouteContext {
-    use_context::<RouteContext>(cx).unwrap_or_else(|| use_router(cx).base())
+///
+/// The route is the one at the nesting level of the component that calls
+/// `use_route`: a component rendered by a parent route gets the parent route,
+/// and can walk down to the matched leaf with [RouteContext::child]. This makes
+/// it possible to tell the route’s pattern apart from the concrete URL, for
+/// example to build breadcrumbs.
+///
+/// Outside `<Routes/>`, this is the router’s base route, and outside a
+/// [`Router`](crate::Router), it is `None`.
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[component]
+/// pub fn Breadcrumbs(cx: Scope) -> impl IntoView {
+///     let route = expect_route(cx);
+///     // e.g., "/contacts/:id" when the URL is "/contacts/42"
+///     let pattern = route.original_path().to_string();
+///     // e.g., "/contacts/42", which updates when navigating to another contact
+///     let path = move || route.path();
+///
+///     view! { cx, <a href=path>{pattern}</a> }
+/// }
+/// ```
+pub fn use_route(cx: Scope) -> Option<RouteContext> {
+    use_context::<RouteContext>(cx).or_else(|| {
+        use_context::<RouterContext>(cx).map(|router| router.base())
+    })
+}
+
+/// Returns the current [RouteContext], like [use_route].
+///
+/// ## Panics
+/// Panics, naming the place it was called from and, in debug builds, the
+/// component that called it, if it is called from a component that is not
+/// rendered inside a [`Router`](crate::Router).
+#[track_caller]
+pub fn expect_route(cx: Scope) -> RouteContext {
+    match use_route(cx) {
+        Some(route) => route,
+        None => RouterError::panic_outside_router("expect_route"),
+    }
+}
+
+/// Returns the mode that the server is rendering the current request with,
+/// which is the most restrictive of the modes asked for by the routes that
+/// match its path. See [`SsrMode`].
+///
+/// This is only set on the server, by the route handlers of the server
+/// integrations, and is mostly useful for debugging. It is `None` in the
+/// browser, and for requests that are not handled by those route handlers.
+pub fn use_ssr_mode(cx: Scope) -> Option<SsrMode> {
+    use_context::<SsrMode>(cx)
+}
+
+/// Returns a signal that is `true` from the moment a navigation begins until
+/// the new route’s resources have settled.
+///
+/// This can be used to show a loading indicator during slow navigations, for
+/// example with [`RoutingProgress`](crate::RoutingProgress).
+///
+/// Outside a [`Router`](crate::Router), this is always `false`.
+pub fn use_is_routing(cx: Scope) -> Signal<bool> {
+    match use_context::<RouterContext>(cx) {
+        Some(router) => router.is_routing(),
+        None => Signal::derive(cx, || false),
+    }
+}
+
+/// Returns the [background location](crate::State::with_background_location)
+/// of the current history entry, which is `Some` while a modal route is open
+/// in a [`ModalOutlet`](crate::ModalOutlet).
+///
+/// This can be used to change the page underneath the modal, e.g., to lock
+/// its scrolling with `leptos_meta`’s `<Body/>`:
+///
+/// ```rust,ignore
+/// let background = use_background_location(cx);
+/// view! { cx,
+///     <Body class=move || background.with(Option::is_some).then_some("scroll-locked")/>
+/// }
+/// ```
+///
+/// Outside a [`Router`](crate::Router), this is always `None`.
+pub fn use_background_location(cx: Scope) -> Signal<Option<String>> {
+    match use_context::<RouterContext>(cx) {
+        Some(router) => router.inner.background_location.into(),
+        None => Signal::derive(cx, || None),
+    }
+}
+
+/// Returns the current [Location], which contains reactive variables, or an
+/// error if it is called from a component that is not rendered inside a
+/// [`Router`](crate::Router).
+#[track_caller]
+pub fn use_location(cx: Scope) -> Result<Location, RouterError> {
+    match use_context::<RouterContext>(cx) {
+        Some(router) => Ok(router.inner.location.clone()),
+        None => Err(RouterError::outside_router("use_location")),
+    }
 }
 
-/// Returns the current [Location], which contains reactive variables
-pub fn use_location(cx: Scope) -> Location {
-    use_router(cx).inner.location.clone()
+/// Returns the current [Location], like [use_location].
+///
+/// ## Panics
+/// Panics, naming the place it was called from and, in debug builds, the
+/// component that called it, if it is called from a component that is not
+/// rendered inside a [`Router`](crate::Router).
+#[track_caller]
+pub fn expect_location(cx: Scope) -> Location {
+    match use_context::<RouterContext>(cx) {
+        Some(router) => router.inner.location.clone(),
+        None => RouterError::panic_outside_router("expect_location"),
+    }
 }
 
 /// Returns a raw key-value map of route params.
+///
+/// Outside a [`Router`](crate::Router), the map is always empty.
 pub fn use_params_map(cx: Scope) -> Memo<ParamsMap> {
-    let route = use_route(cx);
-    route.params()
+    match use_route(cx) {
+        Some(route) => route.params(),
+        None => create_memo(cx, |_| ParamsMap::new()),
+    }
 }
 
 /// Returns the current route params, parsed into the given type, or an error.
+///
+/// Outside a [`Router`](crate::Router), the error is [`ParamsError::Router`].
+#[track_caller]
 pub fn use_params<T: Params>(cx: Scope) -> Memo<Result<T, ParamsError>>
 where
     T: PartialEq,
 {
-    let route = use_route(cx);
-    create_memo(cx, move |_| route.params().with(T::from_map))
+    match use_route(cx) {
+        Some(route) => {
+            create_memo(cx, move |_| route.params().with(T::from_map))
+        }
+        None => {
+            let error = RouterError::outside_router("use_params");
+            create_memo(cx, move |_| Err(error.into()))
+        }
+    }
 }
 
 /// Returns a raw key-value map of the URL search query.
+///
+/// Outside a [`Router`](crate::Router), the map is always empty.
 pub fn use_query_map(cx: Scope) -> Memo<ParamsMap> {
-    use_router(cx).inner.location.query
+    match use_context::<RouterContext>(cx) {
+        Some(router) => router.inner.location.query,
+        None => create_memo(cx, |_| ParamsMap::new()),
+    }
 }
 
 /// Returns the current URL search query, parsed into the given type, or an error.
+///
+/// Outside a [`Router`](crate::Router), the error is [`ParamsError::Router`].
+#[track_caller]
 pub fn use_query<T: Params>(cx: Scope) -> Memo<Result<T, ParamsError>>
 where
     T: PartialEq,
 {
-    let router = use_router(cx);
-    create_memo(cx, move |_| {
-        router.inner.location.query.with(|m| T::from_map(m))
-    })
+    match use_context::<RouterContext>(cx) {
+        Some(router) => create_memo(cx, move |_| {
+            router.inner.location.query.with(|m| T::from_map(m))
+        }),
+        None => {
+            let error = RouterError::outside_router("use_query");
+            create_memo(cx, move |_| Err(error.into()))
+        }
+    }
+}
+
+/// Returns a memo of a single route param, or `None` if the route has no param
+/// with that name.
+///
+/// Reading the whole [ParamsMap] from [use_params_map] re-runs the reader
+/// whenever any param changes. The memo returned here only notifies its
+/// subscribers when the value of `key` changes, so a component that only reads
+/// `:tab` is not updated when the URL changes from `/users/1/posts` to
+/// `/users/2/posts`.
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[component]
+/// pub fn Tabs(cx: Scope) -> impl IntoView {
+///     let tab = create_param_memo(cx, "tab");
+///     view! { cx, <p>"Current tab: " {move || tab.get()}</p> }
+/// }
+/// ```
+pub fn create_param_memo(
+    cx: Scope,
+    key: impl Into<Cow<'static, str>>,
+) -> Memo<Option<String>> {
+    let key = key.into();
+    let params = use_params_map(cx);
+    create_memo(cx, move |_| params.with(|map| map.get(&key).cloned()))
+}
+
+/// Returns a memo of a single URL query parameter, or `None` if the query has
+/// no parameter with that name.
+///
+/// Like [create_param_memo], the memo only notifies its subscribers when the
+/// value of `key` changes, and not when another query parameter does. To update
+/// the parameter, too, use [create_query_signal].
+pub fn create_query_memo(
+    cx: Scope,
+    key: impl Into<Cow<'static, str>>,
+) -> Memo<Option<String>> {
+    let key = key.into();
+    let query = use_query_map(cx);
+    create_memo(cx, move |_| query.with(|map| map.get(&key).cloned()))
 }
 
 /// Resolves the given path relative to the current route.
+///
+/// Absolute URLs, with a scheme like `https://` or `mailto:`, are returned
+/// as they are. Outside a [`Router`](crate::Router), relative paths can’t be
+/// resolved, and are `None`.
 pub fn use_resolved_path(
     cx: Scope,
     path: impl Fn() -> String + 'static,
@@ -142,16 +345,19 @@ pub fn use_resolved_path(
 
     create_memo(cx, move |_| {
         let path = path();
-        if path.starts_with('/') {
+        if path.starts_with('/') || crate::matching::has_scheme(&path) {
             Some(path)
         } else {
-            route.resolve_path_tracked(&path).map(String::from)
+            route.as_ref()?.resolve_path_tracked(&path)
         }
     })
 }
 
 /// Returns a function that can be used to navigate to a new route.
 ///
+/// Outside a [`Router`](crate::Router), the function returns
+/// [`NavigationError::Router`] instead of navigating.
+///
 /// ## Panics
 /// `use_navigate` can sometimes panic due to a `BorrowMut` runtime error
 /// if it is called immediately during routing/rendering. In this case, you should
@@ -168,17 +374,105 @@ pub fn use_resolved_path(
 /// # }
 /// # });
 /// ```
+#[track_caller]
 pub fn use_navigate(
     cx: Scope,
 ) -> impl Fn(&str, NavigateOptions) -> Result<(), NavigationError> {
-    let router = use_router(cx);
-    move |to, options| {
-        Rc::clone(&router.inner).navigate_from_route(to, &options)
+    let router = match use_context::<RouterContext>(cx) {
+        Some(router) => Ok(router),
+        None => Err(RouterError::outside_router("use_navigate")),
+    };
+    move |to, options| match &router {
+        Ok(router) => {
+            Rc::clone(&router.inner).navigate_from_route(to, &options)
+        }
+        Err(error) => Err((*error).into()),
     }
 }
 ///
 /// Returns a signal that tells you whether you are currently navigating backwards.
 pub(crate) fn use_is_back_navigation(cx: Scope) -> ReadSignal<bool> {
-    let router = use_router(cx);
+    let router = expect_router(cx);
     router.inner.is_back.read_only()
 }
+
+/// Creates a signal whose value is preserved in the current history entry,
+/// so that it can be restored when the user returns to this entry with the
+/// browser’s back or forward buttons, for example to keep the contents of a
+/// form or the state of a list that a user had scrolled through.
+///
+/// This is opt-in per route: the value is only preserved inside a route
+/// with `preserve_state=true`. It is snapshotted under `key` when the route
+/// is left, and serialized as JSON, so it must be small (the state preserved
+/// for each entry is limited to 64 KiB). In every other case, such as a
+/// first visit, an entry whose state is no longer kept, or a snapshot that
+/// can no longer be deserialized, the signal starts with `default`.
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[component]
+/// pub fn Search(cx: Scope) -> impl IntoView {
+///     let query = use_preserved_state(cx, "query", String::new());
+///
+///     view! { cx,
+///         <input
+///             prop:value=query
+///             on:input=move |ev| query.set(event_target_value(&ev))
+///         />
+///     }
+/// }
+///
+/// # if false {
+/// # run_scope(create_runtime(), |cx| {
+/// view! { cx,
+///     <Router>
+///         <Routes>
+///             <Route path="/search" view=Search preserve_state=true/>
+///         </Routes>
+///     </Router>
+/// }
+/// # ;
+/// # });
+/// # }
+/// ```
+pub fn use_preserved_state<T>(cx: Scope, key: &str, default: T) -> RwSignal<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let preserved = use_context::<PreservedStateRoute>(cx);
+    if !use_route(cx).is_some_and(|route| {
+        preserved.is_some_and(|preserved| preserved.0.is_same_instance(&route))
+    }) {
+        return create_rw_signal(cx, default);
+    }
+
+    let router = expect_router(cx);
+    let entries = Rc::clone(&router.inner.entries);
+    let entry = entries.current();
+    let path = router.pathname().get_untracked();
+
+    let value = entries
+        .load(entry, &path, key)
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or(default);
+    let signal = create_rw_signal(cx, value);
+
+    // cleanups run before the signal is disposed
+    let key = key.to_string();
+    on_cleanup(cx, move || {
+        match signal.try_with_untracked(serde_json::to_string) {
+            Some(Ok(value)) => entries.save(entry, &path, &key, value),
+            Some(Err(e)) => {
+                leptos::debug_warn!(
+                    "The state preserved as {:?} could not be serialized: {}",
+                    key,
+                    e
+                );
+            }
+            None => {}
+        }
+    });
+    signal
+}
@@ -28,7 +28,7 @@
 //! ## Example
 //!
 //! ```rust
-//! 
+//!
 //! use leptos::*;
 //! use leptos_router::*;
 //!
@@ -104,7 +104,7 @@
 //!     <div>
 //!       // show the contacts
 //!       <ul>
-//!         {move || contacts.read(cx).map(|contacts| view! { cx, <li>"todo contact info"</li> } )}
+//!         {move || contacts.get().map(|contacts| view! { cx, <li>"todo contact info"</li> } )}
 //!       </ul>
 //!
 //!       // insert the nested child route here
@@ -177,6 +177,7 @@
 //! - `csr` Client-side rendering: Generate DOM nodes in the browser
 //! - `ssr` Server-side rendering: Generate an HTML string (typically on the server)
 //! - `hydrate` Hydration: use this to add interactivity to an SSRed Leptos app
+//! - `meta` Sets the document title given by a route’s `title` prop with `leptos_meta`
 //! - `stable` By default, Leptos requires `nightly` Rust, which is what allows the ergonomics
 //!   of calling signals as functions. Enable this feature to support `stable` Rust.
 //!
@@ -196,6 +197,7 @@ mod hooks;
 #[doc(hidden)]
 pub mod matching;
 mod render_mode;
+mod view_transition;
 pub use components::*;
 #[cfg(any(feature = "ssr", doc))]
 pub use extract_routes::*;
@@ -203,4 +205,5 @@ pub use history::*;
 pub use hooks::*;
 pub use matching::{RouteDefinition, *};
 pub use render_mode::*;
+pub use view_transition::{view_transition_name, VIEW_TRANSITION_BACK_CLASS};
 extern crate tracing;
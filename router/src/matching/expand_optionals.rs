@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 
 #[doc(hidden)]
-#[cfg(not(feature = "ssr"))]
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
 pub fn expand_optionals(pattern: &str) -> Vec<Cow<str>> {
     use js_sys::RegExp;
     use once_cell::unsync::Lazy;
@@ -57,7 +57,7 @@ pub fn expand_optionals(pattern: &str) -> Vec<Cow<str>> {
 }
 
 #[doc(hidden)]
-#[cfg(feature = "ssr")]
+#[cfg(any(feature = "ssr", not(target_arch = "wasm32")))]
 pub fn expand_optionals(pattern: &str) -> Vec<Cow<str>> {
     use regex::Regex;
 
@@ -56,8 +56,55 @@ fn build_route_matches(base: &str, location: String) -> Rc<Vec<RouteMatch>> {
 pub struct Branch {
     /// All the routes contained in the branch.
     pub routes: Vec<RouteData>,
-    /// How closely this branch matches the current URL.
-    pub score: i32,
+    /// How specific the branch’s path is.
+    pub rank: RouteRank,
+}
+
+/// How specific a route’s path is. When several routes match the same URL,
+/// the one with the highest rank is displayed.
+///
+/// Paths are compared segment by segment, from the start:
+/// 1. a static segment (`users`) outranks a param (`:id`),
+/// 2. a param outranks the end of the path,
+/// 3. the end of the path outranks a wildcard (`*any`).
+///
+/// So `/users/new` outranks `/users/:id`, which outranks `/users/*any`, and
+/// `/users` outranks `/users/*any` for the URL `/users`. Routes with the
+/// same rank never match the same URL, unless their paths only differ in
+/// the names of their params, in which case a warning is logged.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RouteRank(Vec<SegmentRank>);
+
+// declared from lowest to highest rank
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum SegmentRank {
+    Splat,
+    End,
+    Param,
+    Static,
+}
+
+impl RouteRank {
+    /// Ranks a full route path, like `/users/:id`.
+    pub fn new(pattern: &str) -> Self {
+        let mut ranks = Vec::new();
+        let mut splat = false;
+        for segment in pattern.split('/').filter(|s| !s.is_empty()) {
+            if segment.starts_with('*') {
+                ranks.push(SegmentRank::Splat);
+                splat = true;
+                break;
+            } else if segment.starts_with(':') {
+                ranks.push(SegmentRank::Param);
+            } else {
+                ranks.push(SegmentRank::Static);
+            }
+        }
+        if !splat {
+            ranks.push(SegmentRank::End);
+        }
+        Self(ranks)
+    }
 }
 
 impl Branch {
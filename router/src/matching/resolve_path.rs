@@ -35,7 +35,9 @@ pub fn resolve_path<'a>(
     }
 }
 
-fn has_scheme(path: &str) -> bool {
+/// Whether the path is a URL with a scheme (like `https://`, `mailto:` or
+/// `tel:`) or a protocol-relative URL, which can’t be resolved against a route.
+pub(crate) fn has_scheme(path: &str) -> bool {
     path.starts_with("//")
         || path.starts_with("tel:")
         || path.starts_with("mailto:")
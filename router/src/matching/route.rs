@@ -1,6 +1,10 @@
-use crate::{Method, SsrMode};
+use crate::{Method, ParamsMap, SsrMode};
 use leptos::{leptos_dom::View, *};
-use std::rc::Rc;
+use std::{cell::Cell, rc::Rc};
+
+thread_local! {
+    static ROUTE_ID: Cell<usize> = const { Cell::new(0) };
+}
 
 /// Defines a single route in a nested route tree. This is the return
 /// type of the [`<Route/>`](crate::Route) component, but can also be
@@ -19,6 +23,71 @@ pub struct RouteDefinition {
     pub ssr_mode: SsrMode,
     /// The HTTP request methods this route is able to handle.
     pub methods: &'static [Method],
+    /// Whether the view should be recreated when only the route’s params
+    /// change, rather than being updated in place.
+    pub force_remount: bool,
+    /// The document title to set when this route is matched.
+    pub title: Option<RouteTitle>,
+}
+
+/// The document title of a route, set with
+/// [`leptos_meta`](https://docs.rs/leptos_meta) when the route is matched,
+/// if the `meta` feature is enabled.
+///
+/// This can be a string, or a function of the route’s params that is
+/// called again whenever they change.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if false {
+/// view! { cx,
+///     <Route path="/about" view=|cx| view! { cx, <h1>"About"</h1> } title="About"/>
+///     <Route
+///         path="/users/:id"
+///         view=|cx| view! { cx, <h1>"User"</h1> }
+///         title=|params: &ParamsMap| format!("User {}", params.get("id").cloned().unwrap_or_default())
+///     />
+/// };
+/// # }
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct RouteTitle(Rc<dyn Fn(&ParamsMap) -> String>);
+
+impl RouteTitle {
+    /// Returns the title for the given params.
+    pub fn get(&self, params: &ParamsMap) -> String {
+        (self.0)(params)
+    }
+}
+
+impl std::fmt::Debug for RouteTitle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RouteTitle").finish()
+    }
+}
+
+impl From<&'static str> for RouteTitle {
+    fn from(title: &'static str) -> Self {
+        Self(Rc::new(move |_| title.to_string()))
+    }
+}
+
+impl From<String> for RouteTitle {
+    fn from(title: String) -> Self {
+        Self(Rc::new(move |_| title.clone()))
+    }
+}
+
+impl<F> From<F> for RouteTitle
+where
+    F: Fn(&ParamsMap) -> String + 'static,
+{
+    fn from(title: F) -> Self {
+        Self(Rc::new(title))
+    }
 }
 
 impl std::fmt::Debug for RouteDefinition {
@@ -27,10 +96,141 @@ impl std::fmt::Debug for RouteDefinition {
             .field("path", &self.path)
             .field("children", &self.children)
             .field("ssr_mode", &self.ssr_mode)
+            .field("force_remount", &self.force_remount)
             .finish()
     }
 }
 
+impl RouteDefinition {
+    /// Creates a new route definition for the given path, with an empty view.
+    ///
+    /// This can be used to build routes from data, rather than declaring
+    /// them with [`<Route/>`](crate::Route) components. Definitions can be
+    /// passed to [`<Routes/>`](crate::Routes) as its children, and are
+    /// treated exactly like the ones created by `<Route/>`.
+    ///
+    /// ```
+    /// # use leptos::*;
+    /// # use leptos_router::*;
+    /// # run_scope(create_runtime(), |cx| {
+    /// let pages = ["about", "contact"];
+    ///
+    /// let routes = pages
+    ///     .into_iter()
+    ///     .map(|page| {
+    ///         RouteDefinition::new(page)
+    ///             .view(move |cx| view! { cx, <h1>{page}</h1> })
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// # if false {
+    /// view! { cx,
+    ///     <Router>
+    ///         <Routes>
+    ///             <Route path="/" view=|cx| view! { cx, <h1>"Home"</h1> }/>
+    ///             {routes}
+    ///         </Routes>
+    ///     </Router>
+    /// };
+    /// # }
+    /// # });
+    /// ```
+    pub fn new(path: impl std::fmt::Display) -> Self {
+        Self {
+            id: Self::next_id(),
+            path: path.to_string(),
+            children: Vec::new(),
+            view: Rc::new(|_| View::default()),
+            ssr_mode: Default::default(),
+            methods: &[Method::Get],
+            force_remount: false,
+            title: None,
+        }
+    }
+
+    /// Sets the view that should be displayed when this route is matched.
+    pub fn view<E: IntoView>(
+        mut self,
+        view: impl Fn(Scope) -> E + 'static,
+    ) -> Self {
+        self.view = Rc::new(move |cx| view(cx).into_view(cx));
+        self
+    }
+
+    /// Sets the routes nested within this one.
+    pub fn children(
+        mut self,
+        children: impl IntoIterator<Item = RouteDefinition>,
+    ) -> Self {
+        self.children = children.into_iter().collect();
+        self
+    }
+
+    /// Sets the mode this route prefers during server-side rendering.
+    pub fn ssr_mode(mut self, ssr_mode: SsrMode) -> Self {
+        self.ssr_mode = ssr_mode;
+        self
+    }
+
+    /// Sets the HTTP request methods this route is able to handle.
+    pub fn methods(mut self, methods: &'static [Method]) -> Self {
+        self.methods = methods;
+        self
+    }
+
+    /// Sets whether the view should be recreated when only the route’s
+    /// params change.
+    pub fn force_remount(mut self, force_remount: bool) -> Self {
+        self.force_remount = force_remount;
+        self
+    }
+
+    /// Preserves the state created with [`use_preserved_state`](crate::use_preserved_state)
+    /// in the route’s view, and the scroll position, so that they are
+    /// restored when the user returns to this route through the history.
+    ///
+    /// This wraps the current view, so it has to be called after
+    /// [`view`](RouteDefinition::view).
+    pub fn preserve_state(mut self) -> Self {
+        self.view = crate::preserving_state(self.view);
+        self
+    }
+
+    /// Sets the document title to use when this route is matched.
+    pub fn title(mut self, title: impl Into<RouteTitle>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub(crate) fn next_id() -> usize {
+        ROUTE_ID.with(|id| {
+            let next = id.get() + 1;
+            id.set(next);
+            next
+        })
+    }
+
+    /// Collects the route definitions from a list of views, including the
+    /// ones nested in fragments, like a `Vec<RouteDefinition>`.
+    pub(crate) fn collect_from(views: &[View], defs: &mut Vec<Self>) -> bool {
+        let mut only_routes = true;
+        for view in views {
+            match view {
+                View::Transparent(t) => match t.downcast_ref::<Self>() {
+                    Some(def) => defs.push(def.clone()),
+                    None => only_routes = false,
+                },
+                View::Component(component) => {
+                    only_routes &=
+                        Self::collect_from(&component.children, defs);
+                }
+                _ => only_routes = false,
+            }
+        }
+        only_routes
+    }
+}
+
 impl PartialEq for RouteDefinition {
     fn eq(&self, other: &Self) -> bool {
         self.path == other.path && self.children == other.children
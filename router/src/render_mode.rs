@@ -21,7 +21,34 @@
 ///
 /// The mode defaults to out-of-order streaming. For a path that includes multiple nested routes, the most
 /// restrictive mode will be used: i.e., if even a single nested route asks for `async` rendering, the whole initial
-/// request will be rendered `async`. (`async` is the most restricted requirement, followed by in-order, out-of-order, and synchronous.)
+/// request will be rendered `async`. (`async` is the most restricted requirement, followed by in-order, partially-blocked,
+/// and out-of-order.) This means nested routes inherit their parent’s mode, and can only override it with a more
+/// restrictive one.
+///
+/// The mode of each path is listed by `generate_route_list` in the server integrations, whose route handlers render
+/// each path accordingly. While rendering, the mode that was chosen is available with [`use_ssr_mode`](crate::use_ssr_mode).
+///
+/// ```rust
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # if false {
+/// # run_scope(create_runtime(), |cx| {
+/// view! { cx,
+///     <Router>
+///         <Routes>
+///             <Route path="/dashboard" view=|cx| view! { cx, <p>"Streamed"</p> }/>
+///             // complete HTML for search engines
+///             <Route path="/blog" view=|cx| view! { cx, <Outlet/> } ssr=SsrMode::Async>
+///                 // rendered `async`, like its parent
+///                 <Route path=":slug" view=|cx| view! { cx, <p>"Post"</p> }/>
+///             </Route>
+///         </Routes>
+///     </Router>
+/// }
+/// # ;
+/// # });
+/// # }
+/// ```
 #[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum SsrMode {
     #[default]
@@ -0,0 +1,161 @@
+//! Route changes wrapped in the browser’s
+//! [View Transitions API](https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API),
+//! when the `<Router/>` is given `view_transitions=true`.
+
+use leptos::*;
+use std::future::Future;
+
+/// The class added to the `<html>` element while the view transition for a
+/// back navigation runs, so that it can be animated in reverse.
+pub const VIEW_TRANSITION_BACK_CLASS: &str = "back-transition";
+
+/// Returns a `style` attribute value that tags an element as a shared element
+/// in route transitions with the given
+/// [`view-transition-name`](https://developer.mozilla.org/en-US/docs/Web/CSS/view-transition-name).
+///
+/// An element with the same name on the old and the new page is animated
+/// from one to the other when the `<Router/>` has `view_transitions=true`.
+/// Each name can only be used by one element on a page at a time.
+///
+/// ```rust,ignore
+/// view! { cx,
+///     <img src=post.cover style=view_transition_name(format!("cover-{}", post.id))/>
+/// }
+/// ```
+pub fn view_transition_name(name: impl AsRef<str>) -> String {
+    format!("view-transition-name: {}", name.as_ref())
+}
+
+/// Runs `update`, which changes the route, inside a view transition if the
+/// browser supports them and the user doesn’t prefer reduced motion, or
+/// right away otherwise.
+///
+/// The browser keeps showing the old page until the new route’s resources
+/// have loaded, and then animates to it. The returned future resolves once
+/// `update` has run.
+pub(crate) fn start(
+    cx: Scope,
+    back: bool,
+    update: impl FnOnce() + 'static,
+) -> impl Future<Output = ()> {
+    cfg_if::cfg_if! {
+        if #[cfg(all(target_arch = "wasm32", not(feature = "ssr")))] {
+            client::start(cx, back, update)
+        } else {
+            _ = (cx, back);
+            update();
+            async {}
+        }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
+mod client {
+    use futures::channel::oneshot;
+    use leptos::*;
+    use std::{cell::RefCell, future::Future, rc::Rc};
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+    pub(super) fn start(
+        cx: Scope,
+        back: bool,
+        update: impl FnOnce() + 'static,
+    ) -> impl Future<Output = ()> {
+        let (updated_tx, updated_rx) = oneshot::channel();
+        let document = document();
+        let start_view_transition =
+            js_sys::Reflect::get(&document, &"startViewTransition".into())
+                .ok()
+                .and_then(|f| f.dyn_into::<js_sys::Function>().ok());
+
+        match start_view_transition.filter(|_| !prefers_reduced_motion()) {
+            None => {
+                update();
+                _ = updated_tx.send(());
+            }
+            Some(start_view_transition) => {
+                let root = document.document_element();
+                if back {
+                    if let Some(root) = &root {
+                        _ = root
+                            .class_list()
+                            .add_1(super::VIEW_TRANSITION_BACK_CLASS);
+                    }
+                }
+
+                let pending = Rc::new(RefCell::new(Some((update, updated_tx))));
+
+                // called by the browser once it has captured the old page
+                let callback = Closure::once_into_js({
+                    let pending = Rc::clone(&pending);
+                    move || {
+                        run_update(&pending);
+                        // the new page is captured once this promise resolves
+                        wasm_bindgen_futures::future_to_promise(async move {
+                            if let Some(suspense) =
+                                use_context::<GlobalSuspenseContext>(cx)
+                            {
+                                suspense.with_inner(|s| s.to_future(cx)).await;
+                            }
+                            flush_sync(cx);
+                            Ok(JsValue::UNDEFINED)
+                        })
+                    }
+                });
+                let transition =
+                    start_view_transition.call1(&document, &callback);
+                if transition.is_err() {
+                    run_update(&pending);
+                }
+
+                let finished = transition.ok().and_then(|transition| {
+                    js_sys::Reflect::get(&transition, &"finished".into())
+                        .ok()
+                        .and_then(|finished| {
+                            finished.dyn_into::<js_sys::Promise>().ok()
+                        })
+                });
+                match finished {
+                    Some(finished) => spawn_local(async move {
+                        // a skipped transition rejects, but still ends
+                        _ = wasm_bindgen_futures::JsFuture::from(finished)
+                            .await;
+                        remove_back_class(root);
+                    }),
+                    None => remove_back_class(root),
+                }
+            }
+        }
+
+        async move {
+            _ = updated_rx.await;
+        }
+    }
+
+    // runs the update, unless it has already run
+    fn run_update(
+        pending: &RefCell<Option<(impl FnOnce(), oneshot::Sender<()>)>>,
+    ) {
+        if let Some((update, updated_tx)) = pending.take() {
+            update();
+            _ = updated_tx.send(());
+        }
+    }
+
+    fn remove_back_class(root: Option<web_sys::Element>) {
+        if let Some(root) = root {
+            _ = root
+                .class_list()
+                .remove_1(super::VIEW_TRANSITION_BACK_CLASS);
+        }
+    }
+
+    fn prefers_reduced_motion() -> bool {
+        window()
+            .match_media("(prefers-reduced-motion: reduce)")
+            .ok()
+            .flatten()
+            .map(|query| query.matches())
+            .unwrap_or(false)
+    }
+}
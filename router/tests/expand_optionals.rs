@@ -1,7 +1,7 @@
 use cfg_if::cfg_if;
 
 cfg_if! {
-    if #[cfg(feature = "ssr")] {
+    if #[cfg(any(feature = "ssr", not(target_arch = "wasm32")))] {
         use leptos_router::expand_optionals;
 
         #[test]
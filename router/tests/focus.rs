@@ -0,0 +1,115 @@
+// run with `wasm-pack test --headless --firefox --features csr`
+#![cfg(all(target_arch = "wasm32", feature = "csr"))]
+
+use leptos::*;
+use leptos_router::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_futures::{
+    js_sys::{self, Promise},
+    JsFuture,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn tick() {
+    for _ in 0..5 {
+        JsFuture::from(Promise::resolve(&wasm_bindgen::JsValue::NULL))
+            .await
+            .unwrap();
+    }
+    // focus moves after the new route has been rendered
+    JsFuture::from(Promise::new(&mut |resolve, _| {
+        _ = window()
+            .request_animation_frame(&resolve)
+            .expect("requestAnimationFrame");
+    }))
+    .await
+    .unwrap();
+}
+
+fn focused_id() -> Option<String> {
+    document().active_element().map(|el| el.id())
+}
+
+fn announcement() -> String {
+    document()
+        .query_selector("[aria-live]")
+        .unwrap()
+        .expect("route announcer")
+        .text_content()
+        .unwrap_or_default()
+}
+
+type Navigate =
+    Box<dyn Fn(&str, NavigateOptions) -> Result<(), NavigationError>>;
+
+#[wasm_bindgen_test]
+async fn focus_moves_to_the_new_route_and_skips_navigations_without_scroll() {
+    let history = TestingIntegration::new("/");
+    let navigate: Rc<RefCell<Option<Navigate>>> = Default::default();
+    mount_to_body({
+        let history = history.clone();
+        let navigate = Rc::clone(&navigate);
+        move |cx| {
+            view! { cx,
+                <Router history focus_on_navigate=true>
+                    {
+                        *navigate.borrow_mut() = Some(Box::new(use_navigate(cx)));
+                    }
+                    <Routes>
+                        <Route path="/" view=|cx| view! { cx, <button id="home">"Home"</button> }/>
+                        <Route path="/about" view=|cx| view! { cx,
+                            <h1 id="about-title">"About"</h1>
+                            <p id="details">"Details"</p>
+                        }/>
+                        <Route path="/form" view=|cx| view! { cx,
+                            <h1 id="form-title">"Form"</h1>
+                            <input id="name" data-route-focus/>
+                        }/>
+                    </Routes>
+                </Router>
+            }
+        }
+    });
+    tick().await;
+
+    // the route's heading is focused, and made focusable to do so
+    history.push("/about");
+    tick().await;
+    assert_eq!(focused_id().as_deref(), Some("about-title"));
+    assert_eq!(
+        document()
+            .get_element_by_id("about-title")
+            .unwrap()
+            .get_attribute("tabindex")
+            .as_deref(),
+        Some("-1")
+    );
+    assert!(!announcement().is_empty());
+
+    // `data-route-focus` wins over the heading
+    history.push("/form");
+    tick().await;
+    assert_eq!(focused_id().as_deref(), Some("name"));
+
+    // a hash-only navigation focuses its target
+    history.push("/about");
+    tick().await;
+    history.push("/about#details");
+    tick().await;
+    assert_eq!(focused_id().as_deref(), Some("details"));
+
+    // a navigation that opts out leaves focus alone
+    let navigate = navigate.borrow_mut().take().unwrap();
+    navigate(
+        "/form",
+        NavigateOptions {
+            focus: false,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    tick().await;
+    assert_ne!(focused_id().as_deref(), Some("name"));
+}
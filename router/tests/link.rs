@@ -0,0 +1,63 @@
+use leptos::*;
+use leptos_router::*;
+
+fn render_link(link: fn(Scope) -> View) -> String {
+    leptos::ssr::render_to_string(move |cx| {
+        view! { cx,
+            <Router history=TestingIntegration::new("/docs/intro")>
+                <Routes>
+                    <Route path="/docs/:page" view=link/>
+                </Routes>
+            </Router>
+        }
+    })
+}
+
+#[test]
+fn absolute_urls_are_not_resolved_against_the_route() {
+    let html = render_link(|cx| {
+        view! { cx,
+            <A href="https://example.com/a?b=c">"Example"</A>
+            <A href="mailto:hi@example.com">"Mail"</A>
+            <A href="faq">"FAQ"</A>
+        }
+        .into_view(cx)
+    });
+
+    assert!(html.contains(r#"href="https://example.com/a?b=c""#));
+    assert!(html.contains(r#"href="mailto:hi@example.com""#));
+    assert!(html.contains(r#"href="/docs/intro/faq""#));
+}
+
+#[test]
+fn blank_target_gets_safe_rel_unless_given_one() {
+    let html = render_link(|cx| {
+        view! { cx,
+            <A href="https://example.com" target="_blank">"Safe"</A>
+            <A href="https://example.org" target="_blank" rel="author">"Own"</A>
+        }
+        .into_view(cx)
+    });
+
+    assert!(html.contains(r#"target="_blank" rel="noopener noreferrer""#));
+    assert!(html.contains(r#"rel="author""#));
+    assert!(!html.contains(r#"rel="author noopener"#));
+}
+
+#[test]
+fn external_links_are_marked_for_the_browser() {
+    let html = render_link(|cx| {
+        view! { cx,
+            <A href="/api/export.csv" external=true download="">"Export"</A>
+            <A href="legacy" external=true rel="nofollow">"Legacy"</A>
+        }
+        .into_view(cx)
+    });
+
+    assert!(html.contains(r#"href="/api/export.csv""#));
+    assert!(html.contains(r#"rel="external""#));
+    assert!(html.contains(r#"download="""#));
+    // external hrefs are used as they are
+    assert!(html.contains(r#"href="legacy""#));
+    assert!(html.contains(r#"rel="nofollow external""#));
+}
@@ -4,7 +4,7 @@ use cfg_if::cfg_if;
 // see https://github.com/solidjs/solid-router/blob/main/test/utils.spec.ts
 
 cfg_if! {
-    if #[cfg(feature = "ssr")] {
+    if #[cfg(any(feature = "ssr", not(target_arch = "wasm32")))] {
         use leptos_router::{params_map, Matcher, PathMatch};
 
         #[test]
@@ -0,0 +1,978 @@
+use leptos::*;
+use leptos_router::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use tokio::task;
+
+type Navigate = Rc<dyn Fn(&str)>;
+
+// stores the router's location and navigation function, so the test can
+// drive the router from outside
+#[component]
+fn Capture(
+    cx: Scope,
+    captured: Rc<RefCell<Option<(Location, Navigate)>>>,
+) -> impl IntoView {
+    let navigate = use_navigate(cx);
+    let navigate: Navigate = Rc::new(move |path: &str| {
+        navigate(path, Default::default()).expect("path should be routable")
+    });
+    *captured.borrow_mut() = Some((expect_location(cx), navigate));
+}
+
+#[test]
+fn router_navigates_with_testing_integration() {
+    tokio_test::block_on(task::LocalSet::new().run_until(async {
+        let runtime = create_runtime();
+        let history = TestingIntegration::new("/");
+        let captured = Rc::new(RefCell::new(None));
+
+        _ = create_scope(runtime, {
+            let history = history.clone();
+            let captured = Rc::clone(&captured);
+            move |cx| {
+                _ = view! { cx,
+                    <Router history=history>
+                        <Routes>
+                            <Route path="/" view=|cx| view! { cx, <p>"Home"</p> }/>
+                            <Route path="/about" view=|cx| view! { cx, <p>"About"</p> }/>
+                        </Routes>
+                        <Capture captured/>
+                    </Router>
+                }
+                .into_view(cx);
+            }
+        });
+
+        let (location, navigate) =
+            captured.take().expect("<Capture/> should have rendered");
+        assert_eq!(location.pathname.get_untracked(), "/");
+
+        navigate("/about");
+        // let the navigation finish
+        task::yield_now().await;
+
+        assert_eq!(location.pathname.get_untracked(), "/about");
+        assert_eq!(history.current(), "/about");
+        assert_eq!(
+            history
+                .navigations()
+                .into_iter()
+                .map(|change| change.value)
+                .collect::<Vec<_>>(),
+            ["/about"]
+        );
+
+        // the router follows locations pushed from outside, like the back
+        // button in the browser; effects don't run on the server
+        if !cfg!(feature = "ssr") {
+            history.push("/");
+            assert_eq!(location.pathname.get_untracked(), "/");
+        }
+
+        runtime.dispose();
+    }));
+}
+
+#[test]
+fn routes_can_be_built_from_data() {
+    let html = leptos::ssr::render_to_string(|cx| {
+        let pages = ["intro", "faq"].into_iter().map(|page| {
+            RouteDefinition::new(page)
+                .view(move |cx| view! { cx, <p>{page}</p> })
+        });
+        let routes = vec![RouteDefinition::new("docs")
+            .view(|cx| view! { cx, <main><Outlet/></main> })
+            .children(pages)];
+
+        view! { cx,
+            <Router history=TestingIntegration::new("/app/docs/faq")>
+                // route definitions are cached per base path and thread
+                <Routes base="/app".to_string()>
+                    <Route path="/" view=|cx| view! { cx, <p>"Home"</p> }/>
+                    {routes}
+                </Routes>
+            </Router>
+        }
+    });
+
+    assert!(html.contains("<main"));
+    assert!(html.contains(">faq</p>"));
+    assert!(!html.contains("Home"));
+}
+
+#[component]
+fn ContactList(
+    cx: Scope,
+    captured: Rc<RefCell<Option<RouteContext>>>,
+) -> impl IntoView {
+    *captured.borrow_mut() = Some(expect_route(cx));
+    view! { cx, <Outlet/> }
+}
+
+#[test]
+fn use_route_is_scoped_to_its_nesting_level() {
+    tokio_test::block_on(task::LocalSet::new().run_until(async {
+        let runtime = create_runtime();
+        let history = TestingIntegration::new("/scoped/contacts/1");
+        let captured = Rc::new(RefCell::new(None));
+        let navigation = Rc::new(RefCell::new(None));
+
+        _ = create_scope(runtime, {
+            let captured = Rc::clone(&captured);
+            let navigation = Rc::clone(&navigation);
+            move |cx| {
+                _ = view! { cx,
+                    <Router history>
+                        <Routes base="/scoped".to_string()>
+                            <Route
+                                path="contacts"
+                                view=move |cx| {
+                                    let captured = Rc::clone(&captured);
+                                    view! { cx, <ContactList captured/> }
+                                }
+                            >
+                                <Route path=":id" view=|cx| view! { cx, <p>"Contact"</p> }/>
+                            </Route>
+                        </Routes>
+                        <Capture captured=navigation/>
+                    </Router>
+                }
+                .into_view(cx);
+            }
+        });
+
+        let parent = captured.take().expect("<ContactList/> should have rendered");
+        let (_, navigate) = navigation.take().expect("<Capture/> should have rendered");
+        assert_eq!(parent.path(), "/scoped/contacts");
+        assert_eq!(parent.original_path(), "/scoped/contacts");
+
+        let child = parent.child(parent.cx()).expect("a contact should be matched");
+        let path = create_memo(child.cx(), {
+            let child = child.clone();
+            move |_| child.path()
+        });
+        assert_eq!(child.original_path(), "/scoped/contacts/:id");
+        assert_eq!(path.get_untracked(), "/scoped/contacts/1");
+        assert_eq!(
+            child.params().with_untracked(|params| params.get("id").cloned()),
+            Some("1".to_string())
+        );
+
+        navigate("/scoped/contacts/2");
+        task::yield_now().await;
+
+        // the contact is updated in place rather than remounted
+        let next = parent.child(parent.cx()).expect("a contact should be matched");
+        assert_eq!(next, child);
+        assert_eq!(path.get_untracked(), "/scoped/contacts/2");
+        assert_eq!(
+            child.params().with_untracked(|params| params.get("id").cloned()),
+            Some("2".to_string())
+        );
+        assert_eq!(parent.path(), "/scoped/contacts");
+
+        runtime.dispose();
+    }));
+}
+
+#[test]
+fn focus_on_navigate_renders_a_route_announcer() {
+    let render = |focus_on_navigate: bool| {
+        leptos::ssr::render_to_string(move |cx| {
+            view! { cx,
+                <Router
+                    history=TestingIntegration::new("/announced")
+                    focus_on_navigate
+                >
+                    <Routes base="/announced".to_string()>
+                        <Route path="" view=|cx| view! { cx, <h1>"Home"</h1> }/>
+                    </Routes>
+                </Router>
+            }
+        })
+    };
+
+    let html = render(true);
+    assert!(html.contains("<h1"), "{html}");
+    assert!(html.contains("aria-live=\"polite\""), "{html}");
+    assert!(!render(false).contains("aria-live"));
+    assert!(NavigateOptions::default().focus);
+}
+
+// counts how often a memo notifies its subscribers
+fn count_runs(cx: Scope, memo: Memo<Option<String>>) -> Rc<Cell<usize>> {
+    let runs = Rc::new(Cell::new(0));
+    create_isomorphic_effect(cx, {
+        let runs = Rc::clone(&runs);
+        move |_| {
+            memo.track();
+            runs.set(runs.get() + 1);
+        }
+    });
+    runs
+}
+
+// the memos for `id`, `tab` and `page`, each with its run count
+type TrackedMemos = [(Memo<Option<String>>, Rc<Cell<usize>>); 3];
+
+#[component]
+fn UserTab(
+    cx: Scope,
+    captured: Rc<RefCell<Option<TrackedMemos>>>,
+) -> impl IntoView {
+    let memos = [
+        create_param_memo(cx, "id"),
+        create_param_memo(cx, "tab"),
+        create_query_memo(cx, "page"),
+    ];
+    *captured.borrow_mut() =
+        Some(memos.map(|memo| (memo, count_runs(cx, memo))));
+}
+
+#[test]
+fn param_and_query_memos_only_notify_on_their_own_key() {
+    tokio_test::block_on(task::LocalSet::new().run_until(async {
+        let runtime = create_runtime();
+        let history = TestingIntegration::new("/users/1/posts?page=1");
+        let captured = Rc::new(RefCell::new(None));
+        let navigation = Rc::new(RefCell::new(None));
+
+        _ = create_scope(runtime, {
+            let captured = Rc::clone(&captured);
+            let navigation = Rc::clone(&navigation);
+            move |cx| {
+                _ = view! { cx,
+                    <Router history>
+                        <Routes base="/users".to_string()>
+                            <Route
+                                path=":id/:tab"
+                                view=move |cx| {
+                                    let captured = Rc::clone(&captured);
+                                    view! { cx, <UserTab captured/> }
+                                }
+                            />
+                        </Routes>
+                        <Capture captured=navigation/>
+                    </Router>
+                }
+                .into_view(cx);
+            }
+        });
+
+        let [(id, id_runs), (tab, tab_runs), (page, page_runs)] =
+            captured.take().expect("<UserTab/> should have rendered");
+        let (_, navigate) =
+            navigation.take().expect("<Capture/> should have rendered");
+        let runs = || [id_runs.get(), tab_runs.get(), page_runs.get()];
+        assert_eq!(id.get_untracked().as_deref(), Some("1"));
+        assert_eq!(tab.get_untracked().as_deref(), Some("posts"));
+        assert_eq!(page.get_untracked().as_deref(), Some("1"));
+        assert_eq!(runs(), [1, 1, 1]);
+
+        navigate("/users/1/posts?page=2");
+        task::yield_now().await;
+        assert_eq!(page.get_untracked().as_deref(), Some("2"));
+        assert_eq!(runs(), [1, 1, 2]);
+
+        navigate("/users/2/posts?page=2");
+        task::yield_now().await;
+        assert_eq!(id.get_untracked().as_deref(), Some("2"));
+        assert_eq!(runs(), [2, 1, 2]);
+
+        navigate("/users/2/likes?page=2");
+        task::yield_now().await;
+        assert_eq!(tab.get_untracked().as_deref(), Some("likes"));
+        assert_eq!(runs(), [2, 2, 2]);
+
+        runtime.dispose();
+    }));
+}
+
+type Drafts = Rc<RefCell<Vec<RwSignal<String>>>>;
+
+// records the preserved state created each time its route is rendered
+#[component]
+fn Draft(cx: Scope, drafts: Drafts) -> impl IntoView {
+    drafts
+        .borrow_mut()
+        .push(use_preserved_state(cx, "draft", String::new()));
+}
+
+// renders its children again whenever the path changes, like the DOM
+// renderer updates `<Routes/>` in the browser
+#[component]
+fn RenderOnNavigation(cx: Scope, children: ChildrenFn) -> impl IntoView {
+    let location = expect_location(cx);
+    create_isomorphic_effect(cx, move |prev: Option<ScopeDisposer>| {
+        location.pathname.track();
+        if let Some(prev) = prev {
+            prev.dispose();
+        }
+        let (_, disposer) = cx.run_child_scope(&children);
+        disposer
+    });
+}
+
+#[test]
+fn router_keys_history_entries_for_preserved_state() {
+    tokio_test::block_on(task::LocalSet::new().run_until(async {
+        let runtime = create_runtime();
+        let history = TestingIntegration::new("/");
+        let drafts: Drafts = Default::default();
+        let captured = Rc::new(RefCell::new(None));
+
+        _ = create_scope(runtime, {
+            let history = history.clone();
+            let drafts = Rc::clone(&drafts);
+            let captured = Rc::clone(&captured);
+            move |cx| {
+                let draft = move |cx| {
+                    let drafts = Rc::clone(&drafts);
+                    view! { cx, <Draft drafts/> }
+                };
+                _ = view! { cx,
+                    <Router history=history>
+                        <RenderOnNavigation>
+                            {
+                                let draft = draft.clone();
+                                view! { cx,
+                                    <Routes>
+                                        <Route path="/" view=draft preserve_state=true/>
+                                        <Route path="/about" view=|cx| view! { cx, <p>"About"</p> }/>
+                                    </Routes>
+                                }
+                            }
+                        </RenderOnNavigation>
+                        <Capture captured/>
+                    </Router>
+                }
+                .into_view(cx);
+            }
+        });
+
+        let (_, navigate) =
+            captured.take().expect("<Capture/> should have rendered");
+        // nothing has been preserved for a new entry
+        let draft = drafts.borrow()[0];
+        assert_eq!(draft.get_untracked(), "");
+
+        draft.set("hello".to_string());
+
+        let first = history.entry_key().expect("entry should have a key");
+        navigate("/about");
+        task::yield_now().await;
+        let second = history.entry_key().expect("entry should have a key");
+        assert_ne!(first, second);
+
+        // the router recognizes entries it has keyed when they are revisited;
+        // effects don't run on the server, so it can't follow the back and
+        // forward buttons there
+        if !cfg!(feature = "ssr") {
+            history.back();
+            task::yield_now().await;
+            assert_eq!(history.current(), "/");
+            assert_eq!(history.entry_key(), Some(first));
+            // the route was rendered again, with the draft it was left with
+            let restored = drafts.borrow()[1];
+            assert_eq!(restored.get_untracked(), "hello");
+
+            history.forward();
+            assert_eq!(history.current(), "/about");
+            assert_eq!(history.entry_key(), Some(second));
+
+            // a new entry for the same route starts from the default
+            navigate("/");
+            task::yield_now().await;
+            let fresh = drafts.borrow()[2];
+            assert_eq!(fresh.get_untracked(), "");
+        }
+
+        runtime.dispose();
+    }));
+}
+
+#[derive(Params, Debug, PartialEq, Clone)]
+struct ContactParams {
+    id: Option<usize>,
+}
+
+#[test]
+fn hooks_outside_a_router_return_errors() {
+    create_scope(create_runtime(), |cx| {
+        let line = line!() + 1;
+        let error = use_router(cx).expect_err("there is no router");
+        match error {
+            RouterError::OutsideRouter { hook, location } => {
+                assert_eq!(hook, "use_router");
+                assert_eq!(location.line(), line);
+                assert_eq!(location.file(), file!());
+            }
+        }
+        assert!(error.to_string().contains("outside a <Router/>"));
+
+        assert!(use_route(cx).is_none());
+        assert!(use_location(cx).is_err());
+        assert!(use_query_map(cx).get_untracked().0.is_empty());
+        assert!(!use_is_routing(cx).get_untracked());
+        assert!(matches!(
+            use_params::<ContactParams>(cx).get_untracked(),
+            Err(ParamsError::Router(RouterError::OutsideRouter {
+                hook: "use_params",
+                ..
+            }))
+        ));
+        assert!(matches!(
+            use_navigate(cx)("/", Default::default()),
+            Err(NavigationError::Router(_))
+        ));
+    })
+    .dispose();
+}
+
+#[test]
+#[should_panic(expected = "expect_location() was called at")]
+fn expect_variants_panic_outside_a_router() {
+    create_scope(create_runtime(), |cx| {
+        _ = expect_location(cx);
+    })
+    .dispose();
+}
+
+#[component]
+fn Header(cx: Scope) -> impl IntoView {
+    let location = expect_location(cx);
+    view! { cx, <p>{move || location.pathname.get()}</p> }
+}
+
+#[test]
+#[should_panic(expected = "It was called by <Header/>.")]
+fn expect_variants_name_the_calling_component() {
+    create_scope(create_runtime(), |cx| {
+        _ = view! { cx, <Header/> }.into_view(cx);
+    })
+    .dispose();
+}
+
+#[test]
+fn view_transitions_fall_back_to_changing_the_route_right_away() {
+    tokio_test::block_on(task::LocalSet::new().run_until(async {
+        let runtime = create_runtime();
+        let history = TestingIntegration::new("/");
+        let captured = Rc::new(RefCell::new(None));
+
+        _ = create_scope(runtime, {
+            let history = history.clone();
+            let captured = Rc::clone(&captured);
+            move |cx| {
+                _ = view! { cx,
+                    <Router history=history view_transitions=true>
+                        <Routes>
+                            <Route path="/" view=|cx| view! { cx, <p>"Home"</p> }/>
+                            <Route path="/about" view=|cx| view! { cx, <p>"About"</p> }/>
+                        </Routes>
+                        <Capture captured/>
+                    </Router>
+                }
+                .into_view(cx);
+            }
+        });
+
+        let (location, navigate) =
+            captured.take().expect("<Capture/> should have rendered");
+        // there is no View Transitions API outside the browser
+        navigate("/about");
+        assert_eq!(location.pathname.get_untracked(), "/about");
+        task::yield_now().await;
+        assert_eq!(history.current(), "/about");
+
+        if !cfg!(feature = "ssr") {
+            history.back();
+            assert_eq!(location.pathname.get_untracked(), "/");
+        }
+
+        runtime.dispose();
+    }));
+
+    assert_eq!(
+        view_transition_name("cover-1"),
+        "view-transition-name: cover-1"
+    );
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+struct EditState {
+    title: String,
+    params: ParamsMap,
+}
+
+type NavigateWithOptions =
+    Rc<dyn Fn(&str, NavigateOptions) -> Result<(), NavigationError>>;
+
+#[component]
+fn CaptureNavigate(
+    cx: Scope,
+    captured: Rc<RefCell<Option<(Location, NavigateWithOptions)>>>,
+) -> impl IntoView {
+    let navigate = use_navigate(cx);
+    let navigate: NavigateWithOptions =
+        Rc::new(move |path: &str, options| navigate(path, options));
+    *captured.borrow_mut() = Some((expect_location(cx), navigate));
+}
+
+#[test]
+fn history_entries_carry_typed_state() {
+    tokio_test::block_on(task::LocalSet::new().run_until(async {
+        let runtime = create_runtime();
+        let history = TestingIntegration::new("/");
+        let captured = Rc::new(RefCell::new(None));
+
+        _ = create_scope(runtime, {
+            let history = history.clone();
+            let captured = Rc::clone(&captured);
+            move |cx| {
+                _ = view! { cx,
+                    <Router history=history>
+                        <Routes>
+                            <Route path="/" view=|cx| view! { cx, <p>"Home"</p> }/>
+                            <Route path="/edit" view=|cx| view! { cx, <p>"Edit"</p> }/>
+                        </Routes>
+                        <CaptureNavigate captured/>
+                    </Router>
+                }
+                .into_view(cx);
+            }
+        });
+
+        let (location, navigate) =
+            captured.take().expect("<CaptureNavigate/> should have rendered");
+        let mut params = ParamsMap::new();
+        params.insert("id".to_string(), "7".to_string());
+        let draft = EditState {
+            title: "Hello".to_string(),
+            params,
+        };
+
+        // push
+        navigate(
+            "/edit",
+            NavigateOptions {
+                state: State::new(&draft),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        task::yield_now().await;
+        assert_eq!(
+            location.state.get_untracked().try_get::<EditState>(),
+            Ok(Some(draft.clone()))
+        );
+        assert_eq!(history.current_state().get::<EditState>(), Some(draft.clone()));
+        // state of another type can't be read as an edit
+        assert!(matches!(
+            location.state.get_untracked().try_get::<u32>(),
+            Err(StateError::Deserialize { .. })
+        ));
+
+        // a replace without state clears it
+        navigate(
+            "/edit",
+            NavigateOptions {
+                replace: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        task::yield_now().await;
+        assert!(location.state.get_untracked().is_none());
+        assert!(history.current_state().is_none());
+
+        // a replace with state overwrites it
+        navigate(
+            "/edit",
+            NavigateOptions {
+                replace: true,
+                state: State::new(&draft),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        task::yield_now().await;
+        assert_eq!(history.current_state().get::<EditState>(), Some(draft.clone()));
+
+        // popstate brings back the state of each entry; effects don't run on
+        // the server
+        if !cfg!(feature = "ssr") {
+            history.back();
+            assert!(location.state.get_untracked().is_none());
+            history.forward();
+            assert_eq!(
+                location.state.get_untracked().get::<EditState>(),
+                Some(draft.clone())
+            );
+            history.push_with_state("/", State::new(&1_u32));
+            assert_eq!(location.state.get_untracked().get::<u32>(), Some(1));
+        }
+
+        runtime.dispose();
+    }));
+}
+
+#[test]
+fn oversized_state_fails_the_navigation() {
+    let state = State::new(&"x".repeat(MAX_STATE_SIZE));
+    assert!(matches!(
+        state.error(),
+        Some(StateError::TooLarge { size }) if *size > MAX_STATE_SIZE
+    ));
+
+    let runtime = create_runtime();
+    let history = TestingIntegration::new("/");
+    let captured = Rc::new(RefCell::new(None));
+    _ = create_scope(runtime, {
+        let history = history.clone();
+        let captured = Rc::clone(&captured);
+        move |cx| {
+            _ = view! { cx,
+                <Router history=history>
+                    <Routes>
+                        <Route path="/" view=|cx| view! { cx, <p>"Home"</p> }/>
+                        <Route path="/edit" view=|cx| view! { cx, <p>"Edit"</p> }/>
+                    </Routes>
+                    <CaptureNavigate captured/>
+                </Router>
+            }
+            .into_view(cx);
+        }
+    });
+
+    let (location, navigate) = captured
+        .take()
+        .expect("<CaptureNavigate/> should have rendered");
+    let result = navigate(
+        "/edit",
+        NavigateOptions {
+            state,
+            ..Default::default()
+        },
+    );
+    assert!(
+        matches!(
+            &result,
+            Err(NavigationError::State(StateError::TooLarge { .. }))
+        ),
+        "{result:?}"
+    );
+    assert_eq!(location.pathname.get_untracked(), "/");
+    assert!(history.navigations().is_empty());
+
+    runtime.dispose();
+}
+
+#[component]
+fn CaptureBackground(
+    cx: Scope,
+    captured: Rc<RefCell<Option<Signal<Option<String>>>>>,
+) -> impl IntoView {
+    *captured.borrow_mut() = Some(use_background_location(cx));
+}
+
+#[test]
+fn modal_routes_carry_their_background_location() {
+    tokio_test::block_on(task::LocalSet::new().run_until(async {
+        let runtime = create_runtime();
+        let history = TestingIntegration::new("/photos");
+        let captured = Rc::new(RefCell::new(None));
+        let background = Rc::new(RefCell::new(None));
+
+        _ = create_scope(runtime, {
+            let history = history.clone();
+            let captured = Rc::clone(&captured);
+            let background = Rc::clone(&background);
+            move |cx| {
+                _ = view! { cx,
+                    <Router history=history>
+                        <Routes>
+                            <Route path="/photos" view=|cx| view! { cx, <p>"Gallery"</p> }/>
+                            <Route path="/photos/:id" view=|cx| view! { cx, <p>"Photo"</p> }/>
+                        </Routes>
+                        <ModalOutlet/>
+                        <CaptureNavigate captured/>
+                        <CaptureBackground captured=background/>
+                    </Router>
+                }
+                .into_view(cx);
+            }
+        });
+
+        let (location, navigate) =
+            captured.take().expect("<CaptureNavigate/> should have rendered");
+        let background =
+            background.take().expect("<CaptureBackground/> should have rendered");
+        assert_eq!(background.get_untracked(), None);
+
+        navigate(
+            "/photos/42",
+            NavigateOptions {
+                state: State::default().with_background_location("/photos"),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        task::yield_now().await;
+        assert_eq!(location.pathname.get_untracked(), "/photos/42");
+        assert_eq!(background.get_untracked().as_deref(), Some("/photos"));
+        assert_eq!(
+            history.current_state().background_location(),
+            Some("/photos")
+        );
+        // the page underneath keeps its scroll position
+        let navigation = history.navigations().pop().unwrap();
+        assert!(!navigation.scroll);
+
+        // going back closes the modal; effects don't run on the server
+        if !cfg!(feature = "ssr") {
+            history.back();
+            assert_eq!(location.pathname.get_untracked(), "/photos");
+            assert_eq!(background.get_untracked(), None);
+        }
+
+        runtime.dispose();
+    }));
+}
+
+#[test]
+fn modal_routes_that_are_loaded_directly_render_as_pages() {
+    let history = TestingIntegration::new("/photos");
+    history.push_with_state(
+        "/photos/42",
+        State::default().with_background_location("/photos"),
+    );
+
+    let html = leptos::ssr::render_to_string(move |cx| {
+        view! { cx,
+            <Router history=history.clone()>
+                <Routes>
+                    <Route path="/photos" view=|cx| view! { cx, <p>"Gallery"</p> }/>
+                    <Route path="/photos/:id" view=|cx| view! { cx, <p>"Photo"</p> }/>
+                </Routes>
+                <aside>
+                    <ModalOutlet/>
+                </aside>
+            </Router>
+        }
+    });
+
+    assert!(html.contains("Photo</p>"), "{html}");
+    assert!(!html.contains("Gallery"), "{html}");
+    assert!(
+        !html[html.find("<aside").unwrap()..].contains("Photo"),
+        "{html}"
+    );
+}
+
+#[component]
+fn CaptureIsRouting(
+    cx: Scope,
+    captured: Rc<RefCell<Option<Signal<bool>>>>,
+) -> impl IntoView {
+    *captured.borrow_mut() = Some(use_is_routing(cx));
+}
+
+#[test]
+fn navigation_hooks_bracket_each_navigation() {
+    tokio_test::block_on(task::LocalSet::new().run_until(async {
+        let runtime = create_runtime();
+        let history = TestingIntegration::new("/");
+        let captured = Rc::new(RefCell::new(None));
+        let is_routing = Rc::new(RefCell::new(None));
+        let events = Rc::new(RefCell::new(Vec::<String>::new()));
+
+        _ = create_scope(runtime, {
+            let history = history.clone();
+            let captured = Rc::clone(&captured);
+            let is_routing = Rc::clone(&is_routing);
+            let events = Rc::clone(&events);
+            move |cx| {
+                let on_start = {
+                    let events = Rc::clone(&events);
+                    Rc::new(move |path: &str| {
+                        events.borrow_mut().push(format!("start {path}"))
+                    })
+                };
+                let on_end = Rc::new(move |path: &str| {
+                    events.borrow_mut().push(format!("end {path}"))
+                });
+                _ = view! { cx,
+                    <Router
+                        history=history
+                        on_navigation_start=on_start
+                        on_navigation_end=on_end
+                    >
+                        <Routes>
+                            <Route path="/" view=|cx| view! { cx, <p>"Home"</p> }/>
+                            <Route path="/about" view=|cx| view! { cx, <p>"About"</p> }/>
+                            <Route path="/contact" view=|cx| view! { cx, <p>"Contact"</p> }/>
+                        </Routes>
+                        <Capture captured/>
+                        <CaptureIsRouting captured=is_routing/>
+                    </Router>
+                }
+                .into_view(cx);
+            }
+        });
+
+        let (location, navigate) =
+            captured.take().expect("<Capture/> should have rendered");
+        let is_routing =
+            is_routing.take().expect("<CaptureIsRouting/> should have rendered");
+        assert!(!is_routing.get_untracked());
+
+        // without `ssr`, navigations outside the browser end right away
+        let in_flight = cfg!(feature = "ssr");
+
+        navigate("/about");
+        if in_flight {
+            assert!(is_routing.get_untracked());
+            assert_eq!(*events.borrow(), ["start /about"]);
+        }
+        for _ in 0..5 {
+            task::yield_now().await;
+        }
+        assert!(!is_routing.get_untracked());
+        assert_eq!(location.pathname.get_untracked(), "/about");
+        assert_eq!(*events.borrow(), ["start /about", "end /about"]);
+        events.borrow_mut().clear();
+
+        // a navigation that is superseded before it ends is ended right away,
+        // and only once
+        navigate("/");
+        navigate("/contact");
+        if in_flight {
+            assert!(is_routing.get_untracked());
+            assert_eq!(
+                *events.borrow(),
+                ["start /", "end /", "start /contact"]
+            );
+        }
+        for _ in 0..5 {
+            task::yield_now().await;
+        }
+        assert!(!is_routing.get_untracked());
+        assert_eq!(location.pathname.get_untracked(), "/contact");
+        assert_eq!(
+            *events.borrow(),
+            ["start /", "end /", "start /contact", "end /contact"]
+        );
+
+        runtime.dispose();
+    }));
+}
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+// logs each time the route's view is created, and each `id` param it sees
+#[component]
+fn Mounted(
+    cx: Scope,
+    name: &'static str,
+    mounts: Log,
+    ids: Log,
+) -> impl IntoView {
+    mounts.borrow_mut().push(name.to_string());
+    let params = use_params_map(cx);
+    create_isomorphic_effect(cx, move |_| {
+        if let Some(id) = params.with(|params| params.get("id").cloned()) {
+            ids.borrow_mut().push(format!("{name} {id}"));
+        }
+    });
+    view! { cx, <Outlet/> }
+}
+
+#[test]
+fn routes_are_only_remounted_where_their_pattern_changes() {
+    tokio_test::block_on(task::LocalSet::new().run_until(async {
+        let runtime = create_runtime();
+        let history = TestingIntegration::new("/contacts/1");
+        let captured = Rc::new(RefCell::new(None));
+        let mounts = Log::default();
+        let ids = Log::default();
+
+        _ = create_scope(runtime, {
+            let captured = Rc::clone(&captured);
+            let mounts = Rc::clone(&mounts);
+            let ids = Rc::clone(&ids);
+            move |cx| {
+                let mounted = |name: &'static str| {
+                    let mounts = Rc::clone(&mounts);
+                    let ids = Rc::clone(&ids);
+                    move |cx| {
+                        let mounts = Rc::clone(&mounts);
+                        let ids = Rc::clone(&ids);
+                        view! { cx, <Mounted name mounts ids/> }
+                    }
+                };
+                let (app, contacts, contact, edit, team) = (
+                    mounted("app"),
+                    mounted("contacts"),
+                    mounted("contact"),
+                    mounted("edit"),
+                    mounted("team"),
+                );
+                _ = view! { cx,
+                    <Router history>
+                        <Routes>
+                            <Route path="" view=app>
+                                <Route path="contacts" view=contacts>
+                                    <Route path=":id" view=contact/>
+                                    <Route path=":id/edit" view=edit/>
+                                </Route>
+                                <Route
+                                    path="teams/:id"
+                                    view=team
+                                    force_remount=true
+                                />
+                            </Route>
+                        </Routes>
+                        <Capture captured/>
+                    </Router>
+                }
+                .into_view(cx);
+            }
+        });
+
+        let (_, navigate) =
+            captured.take().expect("<Capture/> should have rendered");
+        let go = |path: &str| {
+            navigate(path);
+            task::yield_now()
+        };
+        let take = |log: &Log| std::mem::take(&mut *log.borrow_mut());
+        assert_eq!(take(&mounts), ["app", "contacts", "contact"]);
+        assert_eq!(take(&ids), ["contact 1"]);
+
+        // only a param changes: the contact is updated in place
+        go("/contacts/2").await;
+        assert!(take(&mounts).is_empty());
+        assert_eq!(take(&ids), ["contact 2"]);
+
+        // the child's pattern changes: only the child is remounted
+        go("/contacts/2/edit").await;
+        assert_eq!(take(&mounts), ["edit"]);
+        assert_eq!(take(&ids), ["edit 2"]);
+
+        // a sibling route replaces the contacts, but not the layout around them
+        go("/teams/1").await;
+        assert_eq!(take(&mounts), ["team"]);
+        assert_eq!(take(&ids).last().map(String::as_str), Some("team 1"));
+
+        // `force_remount` recreates the view even if only a param changes
+        go("/teams/2").await;
+        assert_eq!(take(&mounts), ["team"]);
+        assert_eq!(take(&ids).last().map(String::as_str), Some("team 2"));
+
+        runtime.dispose();
+    }));
+}
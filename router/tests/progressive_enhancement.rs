@@ -0,0 +1,51 @@
+use leptos::*;
+use leptos_router::*;
+
+#[server(SaveDraft, "/api")]
+pub async fn save_draft(title: String) -> Result<(), ServerFnError> {
+    _ = title;
+    Ok(())
+}
+
+fn render_forms(hints: bool) -> String {
+    leptos::ssr::render_to_string(move |cx| {
+        view! { cx,
+            <Router
+                history=TestingIntegration::new("/drafts")
+                progressive_enhancement_hints=hints
+            >
+                <Routes>
+                    <Route path="/drafts" view=|cx| {
+                        let save = create_server_action::<SaveDraft>(cx);
+                        let save_all = create_server_multi_action::<SaveDraft>(cx);
+                        view! { cx,
+                            <ActionForm action=save>
+                                <input name="title"/>
+                                <button type="button">"Preview"</button>
+                                <button type="submit">"Save"</button>
+                            </ActionForm>
+                            <MultiActionForm action=save_all>
+                                <input name="title"/>
+                            </MultiActionForm>
+                        }
+                    }/>
+                </Routes>
+            </Router>
+        }
+    })
+}
+
+#[test]
+fn action_forms_get_noscript_hints_only_when_enabled() {
+    let html = render_forms(true);
+    assert_eq!(html.matches("<noscript").count(), 2, "{html}");
+    // the CSS isn't escaped, as `<style>` content is never unescaped
+    assert_eq!(
+        html.matches("button[type=\"button\"]::after").count(),
+        2,
+        "{html}"
+    );
+
+    let html = render_forms(false);
+    assert!(!html.contains("<noscript"), "{html}");
+}
@@ -0,0 +1,92 @@
+use leptos::*;
+use leptos_router::*;
+
+fn page(name: &'static str) -> RouteDefinition {
+    RouteDefinition::new(name).view(move |cx| view! { cx, <p>{name}</p> })
+}
+
+fn routes() -> Vec<RouteDefinition> {
+    vec![
+        RouteDefinition::new("/").view(|cx| view! { cx, <p>"home"</p> }),
+        RouteDefinition::new("users/:id")
+            .view(|cx| view! { cx, <p>"user"</p> }),
+        RouteDefinition::new("users/new")
+            .view(|cx| view! { cx, <p>"new user"</p> }),
+        RouteDefinition::new("users/*any")
+            .view(|cx| view! { cx, <p>"users fallback"</p> }),
+        RouteDefinition::new("posts/:id?")
+            .view(|cx| view! { cx, <p>"post"</p> }),
+        RouteDefinition::new("settings")
+            .view(|cx| view! { cx, <section><Outlet/></section> })
+            .children([page("profile"), page(":tab")]),
+        RouteDefinition::new("*any")
+            .view(|cx| view! { cx, <p>"not found"</p> }),
+    ]
+}
+
+fn render(base: &'static str, reversed: bool, path: &str) -> String {
+    let path = format!("{base}{path}");
+    leptos::ssr::render_to_string(move |cx| {
+        let mut routes = routes();
+        if reversed {
+            routes.reverse();
+        }
+        view! { cx,
+            <Router history=TestingIntegration::new(path)>
+                // route definitions are cached per base path and thread
+                <Routes base=base.to_string()>
+                    {routes}
+                </Routes>
+            </Router>
+        }
+    })
+}
+
+#[test]
+fn most_specific_route_wins_regardless_of_order() {
+    let cases = [
+        ("/", "home"),
+        ("/users/new", "new user"),
+        ("/users/42", "user"),
+        ("/users/42/posts", "users fallback"),
+        ("/posts", "post"),
+        ("/posts/1", "post"),
+        ("/settings/profile", "profile"),
+        ("/settings/privacy", ":tab"),
+        ("/settings", "not found"),
+        ("/unknown", "not found"),
+    ];
+
+    for (base, reversed) in [("/forward", false), ("/reversed", true)] {
+        for (path, expected) in cases {
+            let html = render(base, reversed, path);
+            assert!(
+                html.contains(&format!(">{expected}</p>")),
+                "expected {path:?} to render {expected:?} with routes in \
+                 {base:?} order, got {html}"
+            );
+        }
+    }
+}
+
+#[test]
+fn route_rank_orders_segments() {
+    let ranked = [
+        "/users/new",
+        "/users/:id",
+        "/users",
+        "/users/*any",
+        "/:section",
+        "/",
+        "/*any",
+    ];
+    for pair in ranked.windows(2) {
+        assert!(
+            RouteRank::new(pair[0]) > RouteRank::new(pair[1]),
+            "{:?} should outrank {:?}",
+            pair[0],
+            pair[1]
+        );
+    }
+    assert_eq!(RouteRank::new("/users/:id"), RouteRank::new("/users/:name"));
+}
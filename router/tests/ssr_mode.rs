@@ -0,0 +1,51 @@
+use leptos::*;
+use leptos_router::*;
+
+#[cfg(feature = "ssr")]
+fn mode_of(routes: &[RouteListing], path: &str) -> SsrMode {
+    routes
+        .iter()
+        .find(|route| route.path() == path)
+        .unwrap_or_else(|| panic!("{path} should be listed"))
+        .mode()
+}
+
+// the route list is only collected on the server
+#[cfg(feature = "ssr")]
+#[test]
+fn nested_routes_use_the_most_restrictive_mode() {
+    let routes = generate_route_list_inner(|cx| {
+        view! { cx,
+            <Router>
+                <Routes>
+                    <Route path="/dashboard" view=|cx| view! { cx, <Outlet/> }>
+                        <Route path="stats" view=|cx| view! { cx, <p/> } ssr=SsrMode::InOrder/>
+                        <Route path="" view=|cx| view! { cx, <p/> }/>
+                    </Route>
+                    <Route path="/blog" view=|cx| view! { cx, <Outlet/> } ssr=SsrMode::Async>
+                        <Route path=":slug" view=|cx| view! { cx, <p/> }/>
+                        <Route path="drafts" view=|cx| view! { cx, <p/> } ssr=SsrMode::InOrder/>
+                    </Route>
+                    <Route path="/login" view=|cx| view! { cx, <p/> }/>
+                </Routes>
+            </Router>
+        }
+    });
+
+    assert_eq!(mode_of(&routes, "/login"), SsrMode::OutOfOrder);
+    assert_eq!(mode_of(&routes, "/dashboard"), SsrMode::OutOfOrder);
+    assert_eq!(mode_of(&routes, "/dashboard/stats"), SsrMode::InOrder);
+    // children inherit their parent's mode...
+    assert_eq!(mode_of(&routes, "/blog/:slug"), SsrMode::Async);
+    // ...and a less restrictive mode doesn't override it
+    assert_eq!(mode_of(&routes, "/blog/drafts"), SsrMode::Async);
+}
+
+#[test]
+fn ssr_mode_is_read_from_context() {
+    run_scope(create_runtime(), |cx| {
+        assert_eq!(use_ssr_mode(cx), None);
+        provide_context(cx, SsrMode::InOrder);
+        assert_eq!(use_ssr_mode(cx), Some(SsrMode::InOrder));
+    });
+}
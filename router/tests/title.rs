@@ -0,0 +1,67 @@
+#![cfg(feature = "meta")]
+
+use leptos::*;
+use leptos_meta::*;
+use leptos_router::*;
+
+// renders the app at `path`, returning the runtime and the metadata the app
+// sets
+fn app(path: &'static str) -> (RuntimeId, MetaContext) {
+    let runtime = create_runtime();
+    let history = TestingIntegration::new(path);
+    let (meta, _, _) = run_scope_undisposed(runtime, move |cx| {
+        provide_meta_context(cx);
+        _ = view! { cx,
+            <Title formatter=|text| format!("{text} | Blog")/>
+            <Router history>
+                <Routes>
+                    <Route path="/about" view=|cx| view! { cx, <p/> } title="About"/>
+                    <Route path="/posts" view=|cx| view! { cx, <Outlet/> } title="Posts">
+                        <Route
+                            path=":slug"
+                            view=|cx| view! { cx, <p/> }
+                            title=|params: &ParamsMap| {
+                                format!("Post {}", params.get("slug").cloned().unwrap_or_default())
+                            }
+                        />
+                        <Route path="" view=|cx| view! { cx, <p/> }/>
+                    </Route>
+                    <Route
+                        path="/contact"
+                        view=|cx| view! { cx, <Title text="Get in touch"/> }
+                        title="Contact"
+                    />
+                </Routes>
+            </Router>
+        }
+        .into_view(cx);
+        use_head(cx)
+    });
+    (runtime, meta)
+}
+
+fn title_at(path: &'static str) -> Option<String> {
+    let (runtime, meta) = app(path);
+    let title = meta.title.as_string();
+    runtime.dispose();
+    title
+}
+
+#[test]
+fn matched_route_sets_the_title() {
+    assert_eq!(title_at("/about").as_deref(), Some("About | Blog"));
+}
+
+#[test]
+fn nested_routes_override_their_parent_title() {
+    assert_eq!(title_at("/posts").as_deref(), Some("Posts | Blog"));
+    assert_eq!(
+        title_at("/posts/hello").as_deref(),
+        Some("Post hello | Blog")
+    );
+}
+
+#[test]
+fn title_component_overrides_the_route_title() {
+    assert_eq!(title_at("/contact").as_deref(), Some("Get in touch | Blog"));
+}
@@ -0,0 +1,71 @@
+// run with `wasm-pack test --headless --firefox --features csr,meta`
+#![cfg(all(target_arch = "wasm32", feature = "csr", feature = "meta"))]
+
+use leptos::*;
+use leptos_meta::*;
+use leptos_router::*;
+use wasm_bindgen_futures::{
+    js_sys::{self, Promise},
+    JsFuture,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn tick() {
+    for _ in 0..5 {
+        JsFuture::from(Promise::resolve(&wasm_bindgen::JsValue::NULL))
+            .await
+            .unwrap();
+    }
+}
+
+#[wasm_bindgen_test]
+async fn navigating_updates_the_document_title() {
+    let history = TestingIntegration::new("/about");
+    mount_to_body({
+        let history = history.clone();
+        move |cx| {
+            provide_meta_context(cx);
+            view! { cx,
+                <Title formatter=|text| format!("{text} | Blog")/>
+                <Router history>
+                    <Routes>
+                        <Route path="/about" view=|cx| view! { cx, <p/> } title="About"/>
+                        <Route path="/posts" view=|cx| view! { cx, <Outlet/> } title="Posts">
+                            <Route
+                                path=":slug"
+                                view=|cx| view! { cx, <p/> }
+                                title=|params: &ParamsMap| {
+                                    format!("Post {}", params.get("slug").cloned().unwrap_or_default())
+                                }
+                            />
+                            <Route path="" view=|cx| view! { cx, <p/> }/>
+                        </Route>
+                        <Route
+                            path="/contact"
+                            view=|cx| view! { cx, <Title text="Get in touch"/> }
+                            title="Contact"
+                        />
+                    </Routes>
+                </Router>
+            }
+        }
+    });
+    tick().await;
+    assert_eq!(document().title(), "About | Blog");
+
+    for (path, title) in [
+        ("/posts/hello", "Post hello | Blog"),
+        // the title follows the params
+        ("/posts/world", "Post world | Blog"),
+        // leaving a nested route goes back to its parent's title
+        ("/posts", "Posts | Blog"),
+        ("/contact", "Get in touch | Blog"),
+        ("/about", "About | Blog"),
+    ] {
+        history.push(path);
+        tick().await;
+        assert_eq!(document().title(), title, "after navigating to {path}");
+    }
+}
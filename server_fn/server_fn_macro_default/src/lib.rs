@@ -15,6 +15,9 @@ use syn::__private::ToTokens;
 ///   (e.g., `MyServerFn`).
 /// 2. *Optional*: A URL prefix at which the function will be mounted when it’s registered
 ///   (e.g., `"/api"`). Defaults to `"/"`.
+///    The prefix can also be an absolute URL (e.g., `"https://api.example.com/api"`), in which
+///    case the client sends requests for this function there, instead of to the URL set with
+///    [`set_server_url`](https://docs.rs/server_fn/latest/server_fn/client/fn.set_server_url.html).
 /// 3. *Optional*: The encoding for the server function (`"Url"`, `"Cbor"`, `"GetJson"`, or `"GetCbor`". See **Server Function Encodings** below.)
 /// 4. *Optional*: A specific endpoint path to be used in the URL. (By default, a unique path will be generated.)
 ///
@@ -0,0 +1,168 @@
+//! A process-wide cache for the results of server functions.
+//!
+//! A server function opts in to caching with a `cache` argument to the
+//! [`#[server]`](crate::server) macro, giving how long a result should be
+//! kept:
+//!
+//! ```rust,ignore
+//! #[server(GetExchangeRates, "/api", cache = "30s")]
+//! pub async fn get_exchange_rates(
+//!     base: String,
+//! ) -> Result<ExchangeRates, ServerFnError> {
+//!     // call the slow upstream API...
+//! }
+//! ```
+//!
+//! Results are keyed by the function and its serialized arguments, and are
+//! shared by every request: they are served both when the function is called
+//! directly during server-side rendering and when the client calls it over
+//! HTTP. If several calls miss the cache at the same time, only the first
+//! runs the function, and the others wait for and share its result. Errors
+//! are never cached.
+//!
+//! Because the cache is shared across requests, it should only be used for
+//! functions whose result doesn’t depend on who is asking. Functions
+//! without a `cache` argument are never cached.
+//!
+//! Cached results can be removed before they expire with
+//! [`invalidate_server_cache`] or [`invalidate_all_server_cache`]. The cache
+//! holds at most [`DEFAULT_CAPACITY`] results, evicting the least recently
+//! used ones first; this can be changed with [`set_server_cache_capacity`].
+//!
+//! This module requires the `cache` feature.
+
+use crate::ServerFnError;
+use futures::lock::Mutex as AsyncMutex;
+use lazy_static::lazy_static;
+use lru::LruCache;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    future::Future,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The number of results the cache holds unless it is changed with
+/// [`set_server_cache_capacity`].
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// A server function whose results are cached, because it was given a
+/// `cache` argument in the [`#[server]`](crate::server) macro.
+///
+/// This is implemented by the macro; you shouldn’t need to implement it
+/// yourself.
+pub trait CachedServerFn: Serialize {
+    /// The path at which the server function can be reached, which
+    /// identifies its entries in the cache.
+    fn cache_url() -> &'static str;
+}
+
+struct CachedValue {
+    json: String,
+    expires_at: Instant,
+}
+
+type Slot = Arc<AsyncMutex<Option<CachedValue>>>;
+
+lazy_static! {
+    static ref CACHE: Mutex<LruCache<(&'static str, String), Slot>> =
+        Mutex::new(LruCache::new(NonZeroUsize::new(DEFAULT_CAPACITY).unwrap()));
+}
+
+/// Sets the number of results the cache holds, evicting the least recently
+/// used ones if it currently holds more.
+pub fn set_server_cache_capacity(capacity: NonZeroUsize) {
+    CACHE.lock().unwrap().resize(capacity);
+}
+
+/// Serializes a server function’s arguments into the key its result is
+/// cached under, or returns `None` if they can’t be serialized.
+#[doc(hidden)]
+pub fn cache_key<F: Serialize>(args: &F) -> Option<String> {
+    serde_json::to_string(args).ok()
+}
+
+/// Returns the cached result for these arguments, if there is a fresh one,
+/// or runs `fetch` and caches its result for `ttl` otherwise.
+///
+/// Calls whose arguments couldn’t be serialized into a `key` always run
+/// `fetch`, and their results are never cached.
+///
+/// This is used by the [`#[server]`](crate::server) macro; you shouldn’t
+/// need to call it yourself.
+#[doc(hidden)]
+pub async fn cached<T, Fut>(
+    url: &'static str,
+    key: Option<String>,
+    ttl: Duration,
+    fetch: Fut,
+) -> Result<T, ServerFnError>
+where
+    T: Serialize + DeserializeOwned,
+    Fut: Future<Output = Result<T, ServerFnError>>,
+{
+    let Some(key) = key else {
+        return fetch.await;
+    };
+
+    // an evicted slot stays alive for the calls already waiting on it, but
+    // later calls will fetch again
+    let slot = Arc::clone(
+        CACHE
+            .lock()
+            .unwrap()
+            .get_or_insert((url, key), Default::default),
+    );
+
+    // concurrent misses wait here for the first one to finish fetching
+    let mut cached = slot.lock().await;
+    if let Some(value) = cached.as_ref() {
+        if value.expires_at > Instant::now() {
+            if let Ok(value) = serde_json::from_str(&value.json) {
+                return Ok(value);
+            }
+        }
+    }
+
+    let value = fetch.await?;
+    if let Ok(json) = serde_json::to_string(&value) {
+        *cached = Some(CachedValue {
+            json,
+            expires_at: Instant::now() + ttl,
+        });
+    }
+    Ok(value)
+}
+
+/// Removes the cached result of a server function for these arguments, so
+/// that the next call runs the function again.
+///
+/// ```rust,ignore
+/// invalidate_server_cache(&GetExchangeRates {
+///     base: "EUR".to_string(),
+/// });
+/// ```
+pub fn invalidate_server_cache<F: CachedServerFn>(args: &F) {
+    if let Some(key) = cache_key(args) {
+        CACHE.lock().unwrap().pop(&(F::cache_url(), key));
+    }
+}
+
+/// Removes every cached result of a server function, whatever its arguments.
+///
+/// ```rust,ignore
+/// invalidate_all_server_cache::<GetExchangeRates>();
+/// ```
+pub fn invalidate_all_server_cache<F: CachedServerFn>() {
+    let url = F::cache_url();
+    let mut cache = CACHE.lock().unwrap();
+    let keys = cache
+        .iter()
+        .filter(|((key_url, _), _)| *key_url == url)
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+    for key in keys {
+        cache.pop(&key);
+    }
+}
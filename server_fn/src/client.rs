@@ -0,0 +1,258 @@
+//! Configuration of the HTTP client that calls server functions.
+//!
+//! By default, a server function called in the browser sends its request to
+//! the origin the page was loaded from, and one called on any other platform
+//! sends it to the URL set with [`set_server_url`]. Every request can be
+//! changed before it is sent with [`set_request_interceptor`], for example to
+//! add an `Authorization` header, or to handle a `401 Unauthorized` response:
+//!
+//! ```rust
+//! use server_fn::client::{set_request_interceptor, Credentials};
+//!
+//! # fn token() -> String { String::new() }
+//! # fn redirect_to_login() {}
+//! // the API is served from a different origin than the app
+//! server_fn::set_server_url("https://api.example.com");
+//!
+//! set_request_interceptor(|req| {
+//!     req.set_header("Authorization", format!("Bearer {}", token()));
+//!     // send cookies to the API, too
+//!     req.set_credentials(Credentials::Include);
+//!     req.on_response(|res| {
+//!         if res.status() == 401 {
+//!             redirect_to_login();
+//!         }
+//!     });
+//! });
+//! ```
+//!
+//! The server URL and the interceptor only apply to requests sent by the
+//! client. When a server function is called on the server, during server-side
+//! rendering, it runs directly and neither is used.
+
+use std::{cell::RefCell, rc::Rc, sync::RwLock};
+
+type Interceptor = Rc<dyn Fn(&mut ClientRequest)>;
+type ResponseHandler = Box<dyn FnOnce(&ClientResponse)>;
+
+static SERVER_URL: RwLock<Option<&'static str>> = RwLock::new(None);
+
+thread_local! {
+    static INTERCEPTOR: RefCell<Option<Interceptor>> = RefCell::new(None);
+}
+
+/// Sets the root URL that all server function paths are relative to for the
+/// client, like `https://api.example.com`.
+///
+/// In the browser, this defaults to the origin of the page, and only needs to
+/// be set if the server functions are served from another origin. On any
+/// other platform, it must be set before calling a server function.
+///
+/// A single server function can also be sent to another URL by giving an
+/// absolute URL as its prefix in the `#[server]` macro, like
+/// `#[server(GetPosts, "https://blog.example.com/api")]`.
+pub fn set_server_url(url: &'static str) {
+    *SERVER_URL.write().unwrap() = Some(url.trim_end_matches('/'));
+}
+
+/// Returns the URL set with [`set_server_url`], if any.
+pub fn server_url() -> Option<&'static str> {
+    *SERVER_URL.read().unwrap()
+}
+
+/// Sets a function that is called with every request sent by a server
+/// function on the client, before it is sent. It can change the headers and
+/// the [`Credentials`] of the request, and register a function that inspects
+/// the response with [`ClientRequest::on_response`]. See the
+/// [module docs](self).
+///
+/// Setting a new interceptor replaces the previous one. The interceptor only
+/// runs on the client, on the thread that set it, so it can capture signals
+/// or a `navigate` function.
+pub fn set_request_interceptor(
+    interceptor: impl Fn(&mut ClientRequest) + 'static,
+) {
+    INTERCEPTOR.with(|current| {
+        *current.borrow_mut() = Some(Rc::new(interceptor));
+    });
+}
+
+/// Removes the function set with [`set_request_interceptor`].
+pub fn clear_request_interceptor() {
+    INTERCEPTOR.with(|current| *current.borrow_mut() = None);
+}
+
+/// Whether a request sent in the browser includes credentials, like cookies,
+/// when it is sent to another origin. See
+/// [`Request.credentials`](https://developer.mozilla.org/en-US/docs/Web/API/Request/credentials).
+///
+/// This is ignored on platforms other than the browser.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Credentials {
+    /// Never send credentials.
+    Omit,
+    /// Only send credentials to the same origin. This is the default.
+    #[default]
+    SameOrigin,
+    /// Always send credentials, even to another origin.
+    Include,
+}
+
+/// A request sent by a server function, as passed to the function set with
+/// [`set_request_interceptor`].
+pub struct ClientRequest {
+    method: &'static str,
+    url: String,
+    headers: Vec<(String, String)>,
+    credentials: Credentials,
+    on_response: Vec<ResponseHandler>,
+}
+
+impl ClientRequest {
+    #[cfg(not(feature = "ssr"))]
+    pub(crate) fn new(method: &'static str, url: String) -> Self {
+        Self {
+            method,
+            url,
+            headers: Vec::new(),
+            credentials: Credentials::default(),
+            on_response: Vec::new(),
+        }
+    }
+
+    /// The HTTP method of the request, `GET` or `POST`.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The URL the request is sent to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The headers of the request, including `Content-Type` and `Accept`.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Returns the value of the header `name`, if it is set.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets the header `name` to `value`, replacing any previous value.
+    pub fn set_header(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) {
+        let name = name.into();
+        self.headers
+            .retain(|(key, _)| !key.eq_ignore_ascii_case(&name));
+        self.headers.push((name, value.into()));
+    }
+
+    /// Removes the header `name`, if it is set.
+    pub fn remove_header(&mut self, name: &str) {
+        self.headers
+            .retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+    }
+
+    /// Whether the request includes credentials. See [`Credentials`].
+    pub fn credentials(&self) -> Credentials {
+        self.credentials
+    }
+
+    /// Sets whether the request includes credentials. See [`Credentials`].
+    pub fn set_credentials(&mut self, credentials: Credentials) {
+        self.credentials = credentials;
+    }
+
+    /// Registers a function that is called with the response to this
+    /// request, before the server function returns.
+    pub fn on_response(&mut self, f: impl FnOnce(&ClientResponse) + 'static) {
+        self.on_response.push(Box::new(f));
+    }
+
+    // runs the interceptor, if any, on the request
+    #[cfg(not(feature = "ssr"))]
+    pub(crate) fn intercept(mut self) -> Self {
+        // cloned out, so that the interceptor can replace itself
+        let interceptor = INTERCEPTOR.with(|current| current.borrow().clone());
+        if let Some(interceptor) = interceptor {
+            interceptor(&mut self);
+        }
+        self
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    pub(crate) fn respond(&mut self, response: &ClientResponse) {
+        for f in self.on_response.drain(..) {
+            f(response);
+        }
+    }
+}
+
+impl std::fmt::Debug for ClientRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientRequest")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("headers", &self.headers)
+            .field("credentials", &self.credentials)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The response to a request sent by a server function, as passed to the
+/// functions registered with [`ClientRequest::on_response`].
+#[derive(Debug, Clone)]
+pub struct ClientResponse {
+    pub(crate) status: u16,
+    pub(crate) headers: Vec<(String, String)>,
+}
+
+impl ClientResponse {
+    /// The HTTP status code of the response.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the value of the header `name`, if the response has it.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Aborts a `fetch` when dropped, so that dropping the future of a server
+/// function call cancels its request in the browser. Aborting a request
+/// whose response has already been read does nothing.
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
+pub(crate) struct AbortOnDrop(Option<web_sys::AbortController>);
+
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
+impl AbortOnDrop {
+    pub(crate) fn new() -> Self {
+        Self(web_sys::AbortController::new().ok())
+    }
+
+    /// The signal to pass to the request.
+    pub(crate) fn signal(&self) -> Option<web_sys::AbortSignal> {
+        self.0.as_ref().map(web_sys::AbortController::signal)
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        if let Some(controller) = self.0.take() {
+            controller.abort();
+        }
+    }
+}
@@ -55,6 +55,7 @@ impl From<ServerFnError> for Error {
 /// This means that other error types can easily be converted into it using the
 /// `?` operator.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ServerFnError {
     /// Error while trying to register the server function (only occurs in case of poisoned RwLock).
     Registration(String),
@@ -99,6 +100,24 @@ impl std::fmt::Display for ServerFnError {
     }
 }
 
+impl ServerFnError {
+    /// The HTTP status code that the server function handlers in the
+    /// integrations respond with by default when a server function returns
+    /// this error: `400 Bad Request` if the arguments could not be
+    /// deserialized, and `500 Internal Server Error` otherwise.
+    ///
+    /// A server function can respond with another status by setting it in the
+    /// `ResponseOptions` of the integration.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            ServerFnError::Args(_)
+            | ServerFnError::MissingArg(_)
+            | ServerFnError::Deserialization(_) => 400,
+            _ => 500,
+        }
+    }
+}
+
 impl<E> From<E> for ServerFnError
 where
     E: std::error::Error,
@@ -0,0 +1,246 @@
+//! Decoding of HTML form data into server function arguments.
+//!
+//! Server function arguments are encoded as `application/x-www-form-urlencoded`
+//! form data, which is exactly what an HTML `<form>` submits. Nested structs and
+//! `Vec`s are expressed with bracketed field names, in the style of
+//! [`serde_qs`](https://docs.rs/serde_qs/latest/serde_qs/):
+//!
+//! ```html
+//! <input name="address[street]"/>
+//! <input name="items[0][qty]"/>
+//! <input name="items[1][qty]"/>
+//! ```
+//!
+//! deserializes into
+//!
+//! ```rust
+//! # use serde::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     address: Address,
+//!     items: Vec<LineItem>,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Address {
+//!     street: String,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct LineItem {
+//!     qty: u32,
+//! }
+//! ```
+//!
+//! A few kinds of form fields need some care:
+//! - **Optional fields**: an `Option<T>` is `None` if the field is absent or empty.
+//! - **Checkboxes**: an unchecked checkbox is not submitted at all, and a checked
+//!   one is submitted as `on` by default. Use [`checkbox`] together with
+//!   `#[serde(default)]` for a `bool` field.
+//! - **Repeated fields**: several inputs with the same name, like the options of a
+//!   `<select multiple>`, are collected into a `Vec`. Use [`one_or_many`] together
+//!   with `#[serde(default)]`, so that a single value (or none) is accepted too.
+//!
+//! ```rust
+//! # use serde::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Subscribe {
+//!     #[serde(default, deserialize_with = "server_fn::form::checkbox")]
+//!     newsletter: bool,
+//!     #[serde(default, deserialize_with = "server_fn::form::one_or_many")]
+//!     topics: Vec<String>,
+//! }
+//!
+//! let args: Subscribe =
+//!     server_fn::form::from_bytes(b"newsletter=on&topics=rust&topics=web")
+//!         .unwrap();
+//! assert!(args.newsletter);
+//! assert_eq!(args.topics, ["rust", "web"]);
+//! ```
+//!
+//! In the browser, the arguments struct generated by the [`server`](crate::server)
+//! macro can also be read straight from a form, with `from_event` or
+//! `from_form_data`. This is the same deserialization used by `<ActionForm/>`, and
+//! is useful to validate the arguments before dispatching an action manually:
+//!
+//! ```rust,ignore
+//! #[server(AddTodo, "/api")]
+//! pub async fn add_todo(title: String) -> Result<(), ServerFnError> {
+//!     todo!()
+//! }
+//!
+//! let add_todo = create_server_action::<AddTodo>(cx);
+//! let on_submit = move |ev: SubmitEvent| {
+//!     ev.prevent_default();
+//!     match AddTodo::from_event(&ev) {
+//!         Ok(args) if !args.title.is_empty() => add_todo.dispatch(args),
+//!         Ok(_) => set_error.set(Some("Please enter a title.")),
+//!         Err(_) => set_error.set(Some("Please fill out the form.")),
+//!     }
+//! };
+//!
+//! // or, in a test
+//! let form_data = web_sys::FormData::new().unwrap();
+//! form_data.append_with_str("title", "Buy milk").unwrap();
+//! assert_eq!(AddTodo::from_form_data(&form_data).unwrap().title, "Buy milk");
+//! ```
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use std::{borrow::Cow, collections::HashMap};
+
+/// Deserializes form data, as submitted by an HTML `<form>` or sent by a
+/// server function call, into `T`. See the [module docs](self).
+pub fn from_bytes<T: DeserializeOwned>(
+    data: &[u8],
+) -> Result<T, serde_qs::Error> {
+    let data = collect_repeated_fields(data);
+    serde_qs::Config::new(5, false).deserialize_bytes(&data)
+}
+
+// turns every field that is submitted more than once, like `tags=a&tags=b`,
+// into a sequence, like `tags[]=a&tags[]=b`, which `serde_qs` otherwise rejects
+fn collect_repeated_fields(data: &[u8]) -> Cow<'_, [u8]> {
+    let pairs = || data.split(|b| *b == b'&').filter(|pair| !pair.is_empty());
+
+    let mut counts = HashMap::<&[u8], usize>::new();
+    for pair in pairs() {
+        *counts.entry(key(pair)).or_default() += 1;
+    }
+    let is_repeated = |key: &[u8]| {
+        counts.get(key).copied().unwrap_or_default() > 1
+            && !key.ends_with(b"[]")
+            && !key.to_ascii_uppercase().ends_with(b"%5B%5D")
+    };
+    if !counts.keys().any(|key| is_repeated(key)) {
+        return Cow::Borrowed(data);
+    }
+
+    let mut normalized = Vec::with_capacity(data.len());
+    for pair in pairs() {
+        if !normalized.is_empty() {
+            normalized.push(b'&');
+        }
+        let key = key(pair);
+        normalized.extend_from_slice(key);
+        if is_repeated(key) {
+            normalized.extend_from_slice(b"[]");
+        }
+        normalized.extend_from_slice(&pair[key.len()..]);
+    }
+    Cow::Owned(normalized)
+}
+
+/// Deserializes the data of the `<form>` that dispatched a `submit` event
+/// into `T`. See [`from_form_data`].
+#[cfg(target_arch = "wasm32")]
+pub fn from_event<T: DeserializeOwned>(
+    ev: &web_sys::Event,
+) -> Result<T, serde_qs::Error> {
+    use wasm_bindgen::JsCast;
+
+    let form = ev
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlFormElement>().ok())
+        .ok_or_else(|| error("the event was not dispatched by a <form>"))?;
+    let form_data =
+        web_sys::FormData::new_with_form(&form).map_err(js_error)?;
+    from_form_data(&form_data)
+}
+
+/// Deserializes [`FormData`](web_sys::FormData) into `T`, the same way the
+/// server deserializes a submitted form.
+///
+/// File inputs can’t be deserialized this way, so a form that contains one
+/// returns an error.
+#[cfg(target_arch = "wasm32")]
+pub fn from_form_data<T: DeserializeOwned>(
+    form_data: &web_sys::FormData,
+) -> Result<T, serde_qs::Error> {
+    use wasm_bindgen::JsCast;
+
+    let params = web_sys::UrlSearchParams::new().map_err(js_error)?;
+    let entries = js_sys::try_iter(form_data).map_err(js_error)?;
+    for entry in entries.into_iter().flatten() {
+        let entry = entry.map_err(js_error)?.unchecked_into::<js_sys::Array>();
+        let name = entry.get(0).as_string().unwrap_or_default();
+        let Some(value) = entry.get(1).as_string() else {
+            return Err(error(format!(
+                "the form field `{name}` is a file, which can’t be \
+                 deserialized from form data"
+            )));
+        };
+        params.append(&name, &value);
+    }
+    let data = params.to_string().as_string().unwrap_or_default();
+    from_bytes(data.as_bytes())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn error(msg: impl std::fmt::Display) -> serde_qs::Error {
+    serde::de::Error::custom(msg)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn js_error(e: wasm_bindgen::JsValue) -> serde_qs::Error {
+    error(format!("{e:?}"))
+}
+
+/// The name of the hidden field that `<ActionForm/>` uses to send the path of
+/// the page it was rendered on, so that the server can redirect back to it
+/// after handling a form that was submitted without JavaScript.
+pub const REDIRECT_FIELD: &str = "__leptos_redirect";
+
+/// Returns the path sent in the [`REDIRECT_FIELD`] of the form data, if any.
+///
+/// Only paths on the same site, like `/todos?page=2`, are returned, so that
+/// the field can’t be used to redirect to another site.
+pub fn redirect_path(data: &[u8]) -> Option<String> {
+    #[derive(Deserialize)]
+    struct Redirect {
+        #[serde(rename = "__leptos_redirect")]
+        path: Option<String>,
+    }
+
+    let path = from_bytes::<Redirect>(data).ok()?.path?;
+    (path.starts_with('/') && !path.starts_with("//") && !path.contains('\\'))
+        .then_some(path)
+}
+
+fn key(pair: &[u8]) -> &[u8] {
+    pair.split(|b| *b == b'=').next().unwrap_or_default()
+}
+
+/// Deserializes a checkbox into a `bool`.
+///
+/// A checked checkbox is `true`, whatever its `value`, unless the value is
+/// `false` or `off`. Since an unchecked checkbox is not submitted at all,
+/// the field should also be marked `#[serde(default)]`.
+pub fn checkbox<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    Ok(!matches!(value.as_str(), "false" | "off"))
+}
+
+/// Deserializes a field that may be submitted any number of times into a
+/// `Vec`, like `Vec<String>`. The field should also be marked
+/// `#[serde(default)]`, so that it is empty if the field is not submitted
+/// at all.
+pub fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        One(T),
+        Many(Vec<T>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
@@ -75,12 +75,13 @@
 //! - **Arguments must be implement [serde::Serialize].** They are serialized as an `application/x-www-form-urlencoded`
 //!   form data using [`serde_qs`](https://docs.rs/serde_qs/latest/serde_qs/) or as `application/cbor`
 //!   using [`cbor`](https://docs.rs/cbor/latest/cbor/).
+//!   See the [form] module for how nested structs, `Vec`s and checkboxes are encoded as form fields.
 
 // used by the macro
 #[doc(hidden)]
 pub use const_format;
 // used by the macro
-#[cfg(feature = "ssr")]
+#[cfg(any(feature = "ssr", feature = "schema"))]
 #[doc(hidden)]
 pub use inventory;
 #[cfg(any(feature = "ssr", doc))]
@@ -88,9 +89,16 @@ use proc_macro2::TokenStream;
 #[cfg(any(feature = "ssr", doc))]
 use quote::TokenStreamExt;
 // used by the macro
+#[cfg(feature = "schema")]
+#[doc(hidden)]
+pub use schemars;
+// used by the macro
 #[doc(hidden)]
 pub use serde;
 use serde::{de::DeserializeOwned, Serialize};
+// used by the macro
+#[doc(hidden)]
+pub use serde_qs;
 pub use server_fn_macro_default::server;
 use std::{future::Future, pin::Pin, str::FromStr};
 #[cfg(any(feature = "ssr", doc))]
@@ -98,13 +106,30 @@ use syn::parse_quote;
 // used by the macro
 #[doc(hidden)]
 pub use xxhash_rust;
+// used by the macro
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+pub use web_sys;
+pub mod client;
+pub use client::{set_request_interceptor, set_server_url};
 /// Error types used in server functions.
 pub mod error;
 pub use error::ServerFnError;
+pub mod form;
+pub mod middleware;
 
 /// Default server function registry
 pub mod default;
 
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "cache")]
+pub use cache::{invalidate_all_server_cache, invalidate_server_cache};
+#[cfg(feature = "schema")]
+pub mod schema;
+#[cfg(feature = "schema")]
+pub use schema::{registry_schema, write_registry_schema};
+
 /// Something that can register a server function.
 pub trait ServerFunctionRegistry<T> {
     /// An error that can occur when registering a server function.
@@ -182,9 +207,44 @@ impl<T> ServerFnTraitObj<T> {
         args: T,
         buffer: &[u8],
     ) -> Pin<Box<dyn Future<Output = Result<Payload, ServerFnError>>>> {
+        #[cfg(feature = "tracing")]
+        return self.instrument((self.run)(args, buffer));
+        #[cfg(not(feature = "tracing"))]
         (self.run)(args, buffer)
     }
 
+    // wraps a call in a `server_fn` span, which records how long it took
+    #[cfg(feature = "tracing")]
+    fn instrument(
+        &self,
+        fut: Pin<Box<dyn Future<Output = Result<Payload, ServerFnError>>>>,
+    ) -> Pin<Box<dyn Future<Output = Result<Payload, ServerFnError>>>> {
+        use tracing::{field, Instrument};
+
+        let span = tracing::info_span!(
+            "server_fn",
+            prefix = self.prefix,
+            name = self.url,
+            encoding = ?self.encoding,
+            duration_ms = field::Empty,
+            outcome = field::Empty,
+        );
+        Box::pin(
+            async move {
+                let start = std::time::Instant::now();
+                let res = fut.await;
+                let span = tracing::Span::current();
+                span.record("duration_ms", start.elapsed().as_millis() as u64);
+                span.record(
+                    "outcome",
+                    if res.is_ok() { "ok" } else { "error" },
+                );
+                res
+            }
+            .instrument(span),
+        )
+    }
+
     /// Returns the prefix of the server function.
     pub fn prefix(&self) -> &str {
         self.prefix
@@ -377,8 +437,7 @@ where
         // decode the args
         let value = match Self::encoding() {
             Encoding::Url | Encoding::GetJSON | Encoding::GetCBOR => {
-                serde_qs::Config::new(5, false)
-                    .deserialize_bytes(data)
+                form::from_bytes(data)
                     .map_err(|e| ServerFnError::Deserialization(e.to_string()))
             }
             Encoding::Cbor => ciborium::de::from_reader(data)
@@ -465,8 +524,18 @@ where
 {
     use ciborium::ser::into_writer;
     use serde_json::Deserializer as JSONDeserializer;
-    #[cfg(not(target_arch = "wasm32"))]
-    let url = format!("{}{}", get_server_url(), url);
+
+    let url = if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else {
+        #[cfg(target_arch = "wasm32")]
+        let server_url = client::server_url().unwrap_or_default();
+        #[cfg(not(target_arch = "wasm32"))]
+        let server_url = client::server_url().expect(
+            "Call server_fn::set_server_url before calling a server function.",
+        );
+        format!("{server_url}{url}")
+    };
 
     #[derive(Debug)]
     enum Payload {
@@ -500,95 +569,115 @@ where
         Encoding::Cbor | Encoding::GetCBOR => "application/cbor",
     };
 
+    let (mut req, body) = match (&enc, args_encoded) {
+        (Encoding::Url | Encoding::Cbor, body) => {
+            (client::ClientRequest::new("POST", url), Some(body))
+        }
+        (Encoding::GetCBOR | Encoding::GetJSON, Payload::Binary(_)) => panic!(
+            "Binary data cannot be transferred via GET request in a query \
+             string. Please try using the CBOR encoding."
+        ),
+        (Encoding::GetCBOR | Encoding::GetJSON, Payload::Url(s)) => (
+            client::ClientRequest::new("GET", format!("{url}?{s}")),
+            None,
+        ),
+    };
+    req.set_header("Content-Type", content_type_header);
+    req.set_header("Accept", accept_header);
+    let mut req = req.intercept();
+
+    // if this future is dropped before the response has been read, for
+    // example because the resource or action that called it was disposed
+    // when navigating away, the browser cancels the request
     #[cfg(target_arch = "wasm32")]
-    let resp = match &enc {
-        Encoding::Url | Encoding::Cbor => match args_encoded {
-            Payload::Binary(b) => {
+    let abort = client::AbortOnDrop::new();
+
+    #[cfg(target_arch = "wasm32")]
+    let resp = {
+        use web_sys::RequestCredentials;
+
+        let mut request = if req.method() == "GET" {
+            gloo_net::http::Request::get(req.url())
+        } else {
+            gloo_net::http::Request::post(req.url())
+        };
+        for (name, value) in req.headers() {
+            request = request.header(name, value);
+        }
+        request = request.credentials(match req.credentials() {
+            client::Credentials::Omit => RequestCredentials::Omit,
+            client::Credentials::SameOrigin => RequestCredentials::SameOrigin,
+            client::Credentials::Include => RequestCredentials::Include,
+        });
+        request = request.abort_signal(abort.signal().as_ref());
+        let request = match body {
+            Some(Payload::Binary(b)) => {
                 let slice_ref: &[u8] = &b;
-                let js_array = js_sys::Uint8Array::from(slice_ref).buffer();
-                gloo_net::http::Request::post(url)
-                    .header("Content-Type", content_type_header)
-                    .header("Accept", accept_header)
-                    .body(js_array)
-                    .send()
-                    .await
-                    .map_err(|e| ServerFnError::Request(e.to_string()))?
-            }
-            Payload::Url(s) => gloo_net::http::Request::post(url)
-                .header("Content-Type", content_type_header)
-                .header("Accept", accept_header)
-                .body(s)
-                .send()
-                .await
-                .map_err(|e| ServerFnError::Request(e.to_string()))?,
-        },
-        Encoding::GetCBOR | Encoding::GetJSON => match args_encoded {
-            Payload::Binary(_) => panic!(
-                "Binary data cannot be transferred via GET request in a query \
-                 string. Please try using the CBOR encoding."
-            ),
-            Payload::Url(s) => {
-                let full_url = format!("{url}?{s}");
-                gloo_net::http::Request::get(&full_url)
-                    .header("Content-Type", content_type_header)
-                    .header("Accept", accept_header)
-                    .send()
-                    .await
-                    .map_err(|e| ServerFnError::Request(e.to_string()))?
+                request.body(js_sys::Uint8Array::from(slice_ref).buffer())
             }
-        },
+            Some(Payload::Url(s)) => request.body(s),
+            None => request,
+        };
+        let resp = request
+            .send()
+            .await
+            .map_err(|e| ServerFnError::Request(e.to_string()))?;
+        req.respond(&client::ClientResponse {
+            status: resp.status(),
+            headers: resp.headers().entries().collect(),
+        });
+        resp
     };
     #[cfg(not(target_arch = "wasm32"))]
-    let resp = match &enc {
-        Encoding::Url | Encoding::Cbor => match args_encoded {
-            Payload::Binary(b) => CLIENT
-                .post(url)
-                .header("Content-Type", content_type_header)
-                .header("Accept", accept_header)
-                .body(b)
-                .send()
-                .await
-                .map_err(|e| ServerFnError::Request(e.to_string()))?,
-            Payload::Url(s) => CLIENT
-                .post(url)
-                .header("Content-Type", content_type_header)
-                .header("Accept", accept_header)
-                .body(s)
-                .send()
-                .await
-                .map_err(|e| ServerFnError::Request(e.to_string()))?,
-        },
-        Encoding::GetJSON | Encoding::GetCBOR => match args_encoded {
-            Payload::Binary(_) => panic!(
-                "Binary data cannot be transferred via GET request in a query \
-                 string. Please try using the CBOR encoding."
-            ),
-
-            Payload::Url(s) => {
-                let full_url = format!("{url}?{s}");
-                CLIENT
-                    .get(full_url)
-                    .header("Content-Type", content_type_header)
-                    .header("Accept", accept_header)
-                    .send()
-                    .await
-                    .map_err(|e| ServerFnError::Request(e.to_string()))?
-            }
-        },
+    let resp = {
+        let mut request = if req.method() == "GET" {
+            CLIENT.get(req.url())
+        } else {
+            CLIENT.post(req.url())
+        };
+        for (name, value) in req.headers() {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let request = match body {
+            Some(Payload::Binary(b)) => request.body(b),
+            Some(Payload::Url(s)) => request.body(s),
+            None => request,
+        };
+        let resp = request
+            .send()
+            .await
+            .map_err(|e| ServerFnError::Request(e.to_string()))?;
+        req.respond(&client::ClientResponse {
+            status: resp.status().as_u16(),
+            headers: resp
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+        });
+        resp
     };
 
-    // check for error status
+    // check for error status: the server sends any error as a serialized
+    // `ServerFnError`, whatever its status
     let status = resp.status();
     #[cfg(not(target_arch = "wasm32"))]
     let status = status.as_u16();
-    if (500..=599).contains(&status) {
+    if !(200..=299).contains(&status) {
         let text = resp.text().await.unwrap_or_default();
         #[cfg(target_arch = "wasm32")]
         let status_text = resp.status_text();
         #[cfg(not(target_arch = "wasm32"))]
         let status_text = status.to_string();
-        return Err(serde_json::from_str(&text)
-            .unwrap_or(ServerFnError::ServerError(status_text)));
+        return Err(serde_json::from_str(&text).unwrap_or(
+            ServerFnError::ServerError(if text.is_empty() {
+                status_text
+            } else {
+                text
+            }),
+        ));
     }
 
     // Decoding the body of the request
@@ -608,12 +697,6 @@ where
         #[cfg(not(target_arch = "wasm32"))]
         let binary = binary.as_ref();
 
-        if status == 400 {
-            return Err(ServerFnError::ServerError(
-                "No server function was found at this URL.".to_string(),
-            ));
-        }
-
         ciborium::de::from_reader(binary)
             .map_err(|e| ServerFnError::Deserialization(e.to_string()))
     } else {
@@ -622,10 +705,6 @@ where
             .await
             .map_err(|e| ServerFnError::Deserialization(e.to_string()))?;
 
-        if status == 400 {
-            return Err(ServerFnError::ServerError(text));
-        }
-
         let mut deserializer = JSONDeserializer::from_str(&text);
         T::deserialize(&mut deserializer)
             .map_err(|e| ServerFnError::Deserialization(e.to_string()))
@@ -636,20 +715,3 @@ where
 #[cfg(any(all(not(feature = "ssr"), not(target_arch = "wasm32")), doc))]
 static CLIENT: once_cell::sync::Lazy<reqwest::Client> =
     once_cell::sync::Lazy::new(reqwest::Client::new);
-
-#[cfg(any(all(not(feature = "ssr"), not(target_arch = "wasm32")), doc))]
-static ROOT_URL: once_cell::sync::OnceCell<&'static str> =
-    once_cell::sync::OnceCell::new();
-
-#[cfg(any(all(not(feature = "ssr"), not(target_arch = "wasm32")), doc))]
-/// Set the root server url that all server function paths are relative to for the client. On WASM this will default to the origin.
-pub fn set_server_url(url: &'static str) {
-    ROOT_URL.set(url).unwrap();
-}
-
-#[cfg(all(not(feature = "ssr"), not(target_arch = "wasm32")))]
-fn get_server_url() -> &'static str {
-    ROOT_URL
-        .get()
-        .expect("Call set_root_url before calling a server function.")
-}
@@ -0,0 +1,152 @@
+//! Middleware that runs around individual server functions.
+//!
+//! Concerns like authorization, rate limiting or logging that apply to some
+//! server functions but not others can be written once as a
+//! [`ServerFnMiddleware`], and added to each function with a `#[middleware]`
+//! attribute below the [`#[server]`](crate::server) macro:
+//!
+//! ```rust,ignore
+//! #[server(DeletePost, "/api")]
+//! #[middleware(RequireAuth, LogCalls)]
+//! pub async fn delete_post(cx: Scope, id: u32) -> Result<(), ServerFnError> {
+//!     // only reached by signed-in users
+//! }
+//! ```
+//!
+//! Middleware runs on the server, before the body of the function, whether
+//! the function was called by the client over HTTP or directly while
+//! rendering on the server. The HTTP request has already been handled by
+//! the server integration (and by any middleware of the web framework) by
+//! then, and the arguments have been deserialized.
+//!
+//! Several middleware run in the order they are listed, outermost first.
+//! Each one can stop the call by returning an error from
+//! [`before`](ServerFnMiddleware::before), in which case the function body
+//! and any later middleware don’t run, and the error is returned to the
+//! caller instead. Once the call has finished, the
+//! [`after`](ServerFnMiddleware::after) hook of each middleware that let it
+//! through runs, in reverse order.
+//!
+//! Middleware gets the server function’s context argument, which in Leptos is
+//! the [`Scope`](https://docs.rs/leptos/latest/leptos/struct.Scope.html) the
+//! function was called with, so it can read the request and set the response
+//! with `use_context`. A function that uses middleware must take that
+//! argument.
+//!
+//! An auth guard that redirects users who aren’t signed in, with
+//! `leptos_axum`:
+//!
+//! ```rust,ignore
+//! pub struct RequireAuth;
+//!
+//! impl ServerFnMiddleware<Scope> for RequireAuth {
+//!     fn before<'a>(
+//!         &'a self,
+//!         cx: &'a Scope,
+//!         info: &'a ServerFnInfo,
+//!     ) -> Pin<Box<dyn Future<Output = Result<(), ServerFnError>> + 'a>> {
+//!         Box::pin(async move {
+//!             let signed_in = use_context::<RequestParts>(*cx)
+//!                 .map(|req| req.headers.contains_key("authorization"))
+//!                 .unwrap_or(false);
+//!             if signed_in {
+//!                 Ok(())
+//!             } else {
+//!                 leptos_axum::redirect(*cx, "/login");
+//!                 Err(ServerFnError::ServerError(format!(
+//!                     "{} requires a signed-in user",
+//!                     info.name
+//!                 )))
+//!             }
+//!         })
+//!     }
+//! }
+//! ```
+//!
+//! A middleware that logs every call and whether it succeeded:
+//!
+//! ```rust,ignore
+//! pub struct LogCalls;
+//!
+//! impl<T> ServerFnMiddleware<T> for LogCalls {
+//!     fn after(&self, _cx: &T, info: &ServerFnInfo, succeeded: bool) {
+//!         log::info!("{} at {}/{}: ok = {succeeded}", info.name, info.prefix, info.url);
+//!     }
+//! }
+//! ```
+
+use crate::ServerFnError;
+use std::{future::Future, pin::Pin};
+
+/// Describes the server function that a [`ServerFnMiddleware`] is running
+/// around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerFnInfo {
+    /// The name of the function, e.g., `delete_post`.
+    pub name: &'static str,
+    /// The prefix the function is served under, e.g., `/api`.
+    pub prefix: &'static str,
+    /// The path of the function, relative to its prefix.
+    pub url: &'static str,
+}
+
+/// Code that runs before and after a server function that lists it in a
+/// `#[middleware]` attribute. See the [module docs](self) for examples.
+///
+/// `T` is the server function’s context argument; a middleware that doesn’t
+/// need it can be implemented for every `T`.
+pub trait ServerFnMiddleware<T> {
+    /// Runs before the server function. Returning an error stops the call,
+    /// and the error is returned to the caller instead.
+    ///
+    /// By default, this lets every call through.
+    fn before<'a>(
+        &'a self,
+        cx: &'a T,
+        info: &'a ServerFnInfo,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ServerFnError>> + 'a>> {
+        _ = (cx, info);
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Runs after the server function, or after a later middleware stopped
+    /// it, with whether the call succeeded.
+    ///
+    /// By default, this does nothing.
+    fn after(&self, cx: &T, info: &ServerFnInfo, succeeded: bool) {
+        _ = (cx, info, succeeded);
+    }
+}
+
+/// Runs `call` inside the given middleware.
+///
+/// This is used by the [`#[server]`](crate::server) macro; you shouldn't need
+/// to call it yourself.
+#[doc(hidden)]
+pub async fn run<T, O, E>(
+    cx: T,
+    info: ServerFnInfo,
+    middleware: Vec<Box<dyn ServerFnMiddleware<T>>>,
+    call: impl Future<Output = Result<O, E>>,
+) -> Result<O, E>
+where
+    E: From<ServerFnError>,
+{
+    let mut entered = 0;
+    let mut stopped = None;
+    for layer in &middleware {
+        if let Err(e) = layer.before(&cx, &info).await {
+            stopped = Some(e);
+            break;
+        }
+        entered += 1;
+    }
+    let result = match stopped {
+        Some(e) => Err(E::from(e)),
+        None => call.await,
+    };
+    for layer in middleware[..entered].iter().rev() {
+        layer.after(&cx, &info, result.is_ok());
+    }
+    result
+}
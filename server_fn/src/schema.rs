@@ -0,0 +1,122 @@
+//! A machine-readable description of every server function, for generating
+//! clients in other languages.
+//!
+//! With the `schema` feature, the [`#[server]`](crate::server) macro derives
+//! [`JsonSchema`] for the arguments of each server function, and registers
+//! a [`ServerFnSchema`] describing where it is served, how its arguments are
+//! encoded, and the [JSON Schema](https://json-schema.org/) of its
+//! arguments, its return type and its error type. This means that every
+//! argument, return and error type has to implement [`JsonSchema`] as well.
+//!
+//! [`registry_schema`] lists all of them, and [`write_registry_schema`]
+//! writes them to a file, e.g., from a small binary that runs as part of
+//! the build:
+//!
+//! ```rust,ignore
+//! #[server(AddTodo, "/api")]
+//! pub async fn add_todo(title: String) -> Result<Todo, ServerFnError> {
+//!     // ...
+//! }
+//!
+//! fn main() -> std::io::Result<()> {
+//!     server_fn::write_registry_schema("target/server_fns.json")
+//! }
+//! ```
+//!
+//! This module requires the `schema` feature.
+
+use crate::Encoding;
+use schemars::{gen::SchemaGenerator, schema::RootSchema, JsonSchema};
+use serde::Serialize;
+use std::{fs::File, io, path::Path};
+
+/// A description of one server function.
+#[derive(Clone, Debug, Serialize)]
+pub struct ServerFnSchema {
+    /// The name of the function, like `add_todo`.
+    pub name: &'static str,
+    /// The prefix of the URL at which the function is served, like `/api`.
+    pub prefix: &'static str,
+    /// The URL at which the function is served, after the prefix.
+    pub url: &'static str,
+    /// The HTTP method the function is called with, `POST` or `GET`.
+    pub method: &'static str,
+    /// How the arguments are sent and the result returned: `URL`, `Cbor`,
+    /// `GetJson` or `GetCbor`, as parsed by [`Encoding`]’s `FromStr`.
+    pub encoding: &'static str,
+    /// The schema of the arguments, as an object with a field for each.
+    pub args: RootSchema,
+    /// The schema of the value the function returns when it succeeds.
+    pub output: RootSchema,
+    /// The schema of the error the function returns when it fails.
+    pub error: RootSchema,
+}
+
+impl ServerFnSchema {
+    /// Describes a server function whose arguments are an `Args`, and which
+    /// returns a `Result<Output, Error>`.
+    pub fn new<Args, Output, Error>(
+        name: &'static str,
+        prefix: &'static str,
+        url: &'static str,
+        encoding: Encoding,
+    ) -> Self
+    where
+        Args: JsonSchema,
+        Output: JsonSchema,
+        Error: JsonSchema,
+    {
+        let (method, encoding) = match encoding {
+            Encoding::Url => ("POST", "URL"),
+            Encoding::Cbor => ("POST", "Cbor"),
+            Encoding::GetJSON => ("GET", "GetJson"),
+            Encoding::GetCBOR => ("GET", "GetCbor"),
+        };
+        Self {
+            name,
+            prefix,
+            url,
+            method,
+            encoding,
+            args: SchemaGenerator::default().into_root_schema_for::<Args>(),
+            output: SchemaGenerator::default().into_root_schema_for::<Output>(),
+            error: SchemaGenerator::default().into_root_schema_for::<Error>(),
+        }
+    }
+
+    /// The full path of the function, as the client calls it.
+    pub fn path(&self) -> String {
+        format!("{}/{}", self.prefix, self.url)
+    }
+}
+
+/// A [`ServerFnSchema`] registered by the [`#[server]`](crate::server) macro.
+#[doc(hidden)]
+pub struct ServerFnSchemaEntry(fn() -> ServerFnSchema);
+
+impl ServerFnSchemaEntry {
+    /// Registers the schema returned by `schema`.
+    pub const fn new(schema: fn() -> ServerFnSchema) -> Self {
+        Self(schema)
+    }
+}
+
+inventory::collect!(ServerFnSchemaEntry);
+
+/// Describes every server function in the binary, sorted by path.
+pub fn registry_schema() -> Vec<ServerFnSchema> {
+    let mut schemas = inventory::iter::<ServerFnSchemaEntry>
+        .into_iter()
+        .map(|entry| (entry.0)())
+        .collect::<Vec<_>>();
+    schemas.sort_by_key(ServerFnSchema::path);
+    schemas
+}
+
+/// Writes the [`registry_schema`] to a file as a JSON array, replacing it if
+/// it already exists.
+pub fn write_registry_schema(path: impl AsRef<Path>) -> io::Result<()> {
+    let file = File::create(path)?;
+    serde_json::to_writer_pretty(file, &registry_schema())
+        .map_err(io::Error::from)
+}
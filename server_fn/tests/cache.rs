@@ -0,0 +1,144 @@
+#![cfg(feature = "cache")]
+
+use futures::{executor::block_on, future::join};
+use serde::Serialize;
+use server_fn::{
+    cache::{cache_key, cached, CachedServerFn},
+    invalidate_server_cache, ServerFnError,
+};
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Returns `Pending` once, so that other futures get a chance to run.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GetRates {
+    base: String,
+}
+
+impl CachedServerFn for GetRates {
+    fn cache_url() -> &'static str {
+        "get_rates"
+    }
+}
+
+fn get_rates<'a>(
+    base: &str,
+    calls: &'a Cell<usize>,
+) -> impl Future<Output = Result<usize, ServerFnError>> + 'a {
+    let args = GetRates {
+        base: base.to_string(),
+    };
+    cached(
+        GetRates::cache_url(),
+        cache_key(&args),
+        Duration::from_secs(60),
+        async move {
+            YieldNow(false).await;
+            calls.set(calls.get() + 1);
+            Ok(calls.get())
+        },
+    )
+}
+
+#[test]
+fn cached_results_are_shared_until_invalidated() {
+    let calls = Cell::new(0);
+    block_on(async {
+        assert_eq!(get_rates("EUR", &calls).await.unwrap(), 1);
+        assert_eq!(get_rates("EUR", &calls).await.unwrap(), 1);
+        // different arguments are cached separately
+        assert_eq!(get_rates("USD", &calls).await.unwrap(), 2);
+
+        invalidate_server_cache(&GetRates {
+            base: "EUR".to_string(),
+        });
+        assert_eq!(get_rates("EUR", &calls).await.unwrap(), 3);
+        assert_eq!(get_rates("USD", &calls).await.unwrap(), 2);
+    });
+}
+
+#[test]
+fn concurrent_misses_fetch_once() {
+    let calls = Cell::new(0);
+    block_on(async {
+        let (a, b) =
+            join(get_rates("GBP", &calls), get_rates("GBP", &calls)).await;
+        assert_eq!(a.unwrap(), 1);
+        assert_eq!(b.unwrap(), 1);
+        assert_eq!(calls.get(), 1);
+    });
+}
+
+#[test]
+fn errors_are_not_cached() {
+    block_on(async {
+        let key = cache_key(&GetRates {
+            base: "JPY".to_string(),
+        });
+        let failed: Result<usize, _> = cached(
+            GetRates::cache_url(),
+            key.clone(),
+            Duration::from_secs(60),
+            async { Err(ServerFnError::ServerError("upstream down".into())) },
+        )
+        .await;
+        assert!(failed.is_err());
+
+        let value = cached(
+            GetRates::cache_url(),
+            key,
+            Duration::from_secs(60),
+            async { Ok(5) },
+        )
+        .await;
+        assert_eq!(value.unwrap(), 5);
+    });
+}
+
+#[test]
+fn unserializable_arguments_are_not_cached() {
+    // a map with non-string keys can't be serialized to JSON
+    let args = std::collections::HashMap::from([((1, 2), 3)]);
+    let key = cache_key(&args);
+    assert!(key.is_none());
+
+    block_on(async {
+        let first: Result<usize, _> = cached(
+            GetRates::cache_url(),
+            key.clone(),
+            Duration::from_secs(60),
+            async { Ok(1) },
+        )
+        .await;
+        let second: Result<usize, _> = cached(
+            GetRates::cache_url(),
+            key,
+            Duration::from_secs(60),
+            async { Ok(2) },
+        )
+        .await;
+        assert_eq!(first.unwrap(), 1);
+        assert_eq!(second.unwrap(), 2);
+    });
+}
@@ -0,0 +1,101 @@
+#![cfg(all(not(target_arch = "wasm32"), not(feature = "ssr")))]
+
+use serde::{Deserialize, Serialize};
+use server_fn::{
+    call_server_fn,
+    client::{
+        clear_request_interceptor, server_url, set_request_interceptor,
+        set_server_url,
+    },
+    Encoding, ServerFn, ServerFnError,
+};
+use std::{
+    cell::RefCell,
+    future::Future,
+    io::{Read, Write},
+    net::TcpListener,
+    pin::Pin,
+    rc::Rc,
+};
+
+#[test]
+fn server_url_can_be_replaced() {
+    assert_eq!(server_url(), None);
+
+    set_server_url("https://api.example.com/");
+    assert_eq!(server_url(), Some("https://api.example.com"));
+
+    set_server_url("http://localhost:3000");
+    assert_eq!(server_url(), Some("http://localhost:3000"));
+}
+
+#[derive(Serialize, Deserialize)]
+struct Ping {}
+
+impl ServerFn<()> for Ping {
+    type Output = ();
+
+    fn prefix() -> &'static str {
+        "/api"
+    }
+
+    fn url() -> &'static str {
+        "ping"
+    }
+
+    fn encoding() -> Encoding {
+        Encoding::Url
+    }
+
+    fn call_fn_client(
+        self,
+        _cx: (),
+    ) -> Pin<Box<dyn Future<Output = Result<(), ServerFnError>>>> {
+        unimplemented!()
+    }
+}
+
+// answers a single request with `401 Unauthorized`, returning the request
+// it received
+fn unauthorized_server() -> (String, std::thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/api/ping", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0; 4096];
+        let len = stream.read(&mut buf).unwrap();
+        stream
+            .write_all(
+                b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n",
+            )
+            .unwrap();
+        String::from_utf8_lossy(&buf[..len]).into_owned()
+    });
+    (url, handle)
+}
+
+#[tokio::test]
+async fn interceptor_can_capture_local_state() {
+    let (url, server) = unauthorized_server();
+
+    // neither `Send` nor `Sync`, like a signal or a `navigate` function
+    let statuses = Rc::new(RefCell::new(Vec::new()));
+    set_request_interceptor({
+        let statuses = Rc::clone(&statuses);
+        move |req| {
+            req.set_header("Authorization", "Bearer token");
+            let statuses = Rc::clone(&statuses);
+            req.on_response(move |res| {
+                statuses.borrow_mut().push(res.status())
+            });
+        }
+    });
+
+    let res = call_server_fn::<(), ()>(&url, Ping {}, Encoding::Url).await;
+    clear_request_interceptor();
+
+    assert!(res.is_err());
+    assert_eq!(*statuses.borrow(), [401]);
+    let request = server.join().unwrap().to_ascii_lowercase();
+    assert!(request.contains("authorization: bearer token"));
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use server_fn::form::from_bytes;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Order {
+    address: Address,
+    items: Vec<LineItem>,
+    note: Option<String>,
+    #[serde(default, deserialize_with = "server_fn::form::checkbox")]
+    gift: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Address {
+    street: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct LineItem {
+    qty: u32,
+    #[serde(default, deserialize_with = "server_fn::form::one_or_many")]
+    options: Vec<String>,
+}
+
+fn order(items: Vec<LineItem>) -> Order {
+    Order {
+        address: Address {
+            street: "Main St".to_string(),
+        },
+        items,
+        note: None,
+        gift: false,
+    }
+}
+
+fn item(qty: u32, options: &[&str]) -> LineItem {
+    LineItem {
+        qty,
+        options: options.iter().map(|o| o.to_string()).collect(),
+    }
+}
+
+#[test]
+fn nested_structs_and_vecs_use_bracketed_names() {
+    let expected = order(vec![item(2, &[]), item(3, &[])]);
+
+    // as typed in a form, and as percent-encoded by the browser
+    let raw = "address[street]=Main+St&items[0][qty]=2&items[1][qty]=3";
+    let encoded =
+        "address%5Bstreet%5D=Main+St&items%5B0%5D%5Bqty%5D=2&items%5B1%5D%5Bqty%5D=3";
+
+    assert_eq!(from_bytes::<Order>(raw.as_bytes()).unwrap(), expected);
+    assert_eq!(from_bytes::<Order>(encoded.as_bytes()).unwrap(), expected);
+}
+
+#[test]
+fn arguments_encoded_by_the_client_can_be_decoded() {
+    let mut expected = order(vec![item(1, &["red", "large"])]);
+    expected.note = Some("ring twice".to_string());
+
+    let encoded = serde_qs::to_string(&expected).unwrap();
+    assert_eq!(from_bytes::<Order>(encoded.as_bytes()).unwrap(), expected);
+}
+
+#[test]
+fn checkboxes_are_false_unless_submitted() {
+    let form = "address[street]=Main+St&items[0][qty]=1";
+    let gift = |extra: &str| {
+        from_bytes::<Order>(format!("{form}{extra}").as_bytes())
+            .unwrap()
+            .gift
+    };
+
+    assert!(!gift(""));
+    assert!(gift("&gift=on"));
+    assert!(gift("&gift=yes"));
+    assert!(!gift("&gift=false"));
+}
+
+#[test]
+fn optional_fields_are_none_if_absent_or_empty() {
+    let form = "address[street]=Main+St&items[0][qty]=1";
+    let note = |extra: &str| {
+        from_bytes::<Order>(format!("{form}{extra}").as_bytes())
+            .unwrap()
+            .note
+    };
+
+    assert_eq!(note(""), None);
+    assert_eq!(note("&note="), None);
+    assert_eq!(note("&note=hi"), Some("hi".to_string()));
+}
+
+#[test]
+fn repeated_fields_are_collected_into_vecs() {
+    let form = "address[street]=Main+St";
+    let options = |fields: &str| {
+        from_bytes::<Order>(format!("{form}&{fields}").as_bytes())
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|item| item.options)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(options("items[0][qty]=1"), [Vec::<String>::new()]);
+    assert_eq!(options("items[0][qty]=1&items[0][options]=red"), [["red"]]);
+    assert_eq!(
+        options(
+            "items[0][qty]=1&items[0][options]=red&items[0][options]=large"
+        ),
+        [["red", "large"]]
+    );
+    assert_eq!(
+        options(
+            "items%5B0%5D%5Bqty%5D=1&items%5B0%5D%5Boptions%5D%5B%5D=red&\
+             items%5B0%5D%5Boptions%5D%5B%5D=large"
+        ),
+        [["red", "large"]]
+    );
+}
+
+#[test]
+fn redirect_path_only_accepts_paths_on_the_same_site() {
+    use server_fn::form::redirect_path;
+
+    let path = |data: &str| redirect_path(data.as_bytes());
+    assert_eq!(
+        path("amount=1&__leptos_redirect=%2Ftodos%3Fpage%3D2"),
+        Some("/todos?page=2".to_string())
+    );
+    assert_eq!(path("amount=1"), None);
+    assert_eq!(path("__leptos_redirect=https://example.com"), None);
+    assert_eq!(path("__leptos_redirect=//example.com"), None);
+    assert_eq!(path("__leptos_redirect=/%5Cexample.com"), None);
+}
@@ -0,0 +1,85 @@
+// run with `wasm-pack test --headless --firefox`
+#![cfg(target_arch = "wasm32")]
+
+use server_fn::{server, ServerFnError};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[server(AddTodo, "/api")]
+pub async fn add_todo(
+    title: String,
+    tags: Vec<String>,
+    note: Option<String>,
+) -> Result<(), ServerFnError> {
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn from_form_data_decodes_like_the_server() {
+    let form_data = web_sys::FormData::new().unwrap();
+    form_data.append_with_str("title", "Buy milk").unwrap();
+    form_data.append_with_str("tags[0]", "errand").unwrap();
+    form_data.append_with_str("tags[1]", "food").unwrap();
+    form_data.append_with_str("note", "").unwrap();
+
+    let args = AddTodo::from_form_data(&form_data).unwrap();
+    assert_eq!(args.title, "Buy milk");
+    assert_eq!(args.tags, ["errand", "food"]);
+    assert_eq!(args.note, None);
+}
+
+#[wasm_bindgen_test]
+fn from_form_data_rejects_missing_fields() {
+    let form_data = web_sys::FormData::new().unwrap();
+    form_data.append_with_str("note", "hi").unwrap();
+
+    assert!(AddTodo::from_form_data(&form_data).is_err());
+}
+
+#[wasm_bindgen_test]
+fn from_event_reads_the_submitted_form() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let form = document
+        .create_element("form")
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlFormElement>();
+    for (name, value) in [("title", "Walk the dog"), ("tags[0]", "pet")] {
+        let input = document
+            .create_element("input")
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlInputElement>();
+        input.set_name(name);
+        input.set_value(value);
+        form.append_child(&input).unwrap();
+    }
+    document.body().unwrap().append_child(&form).unwrap();
+
+    let submitted = Rc::new(RefCell::new(None));
+    let on_submit = Closure::<dyn Fn(web_sys::Event)>::new({
+        let submitted = Rc::clone(&submitted);
+        move |ev: web_sys::Event| {
+            ev.prevent_default();
+            *submitted.borrow_mut() = Some(AddTodo::from_event(&ev));
+        }
+    });
+    form.add_event_listener_with_callback(
+        "submit",
+        on_submit.as_ref().unchecked_ref(),
+    )
+    .unwrap();
+    let submit = web_sys::Event::new("submit").unwrap();
+    form.dispatch_event(&submit).unwrap();
+
+    let args = submitted.borrow_mut().take().unwrap().unwrap();
+    assert_eq!(args.title, "Walk the dog");
+    assert_eq!(args.tags, ["pet"]);
+    assert_eq!(args.note, None);
+
+    // an event that wasn't dispatched by a form can't be read
+    let ev = web_sys::Event::new("submit").unwrap();
+    assert!(AddTodo::from_event(&ev).is_err());
+    form.remove();
+}
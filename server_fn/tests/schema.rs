@@ -0,0 +1,80 @@
+#![cfg(feature = "schema")]
+
+use serde::{Deserialize, Serialize};
+use server_fn::{registry_schema, server, ServerFnError};
+
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct Todo {
+    title: String,
+    done: bool,
+}
+
+#[server(AddTodo, "/api")]
+pub async fn add_todo(
+    title: String,
+    tags: Option<Vec<String>>,
+) -> Result<Todo, ServerFnError> {
+    Ok(Todo { title, done: false })
+}
+
+#[server(ListTodos, "/api", "GetJson", "todos")]
+pub async fn list_todos() -> Result<Vec<Todo>, ServerFnError> {
+    Ok(Vec::new())
+}
+
+#[server(GetTodo, "/api")]
+pub async fn get_todo(
+    title: String,
+) -> std::result::Result<Todo, server_fn::ServerFnError> {
+    Ok(Todo { title, done: true })
+}
+
+#[test]
+fn server_fns_are_described_by_the_registry_schema() {
+    let schemas = registry_schema();
+    let add_todo = schemas.iter().find(|s| s.name == "add_todo").unwrap();
+    let list_todos = schemas.iter().find(|s| s.name == "list_todos").unwrap();
+
+    assert_eq!(add_todo.prefix, "/api");
+    assert!(add_todo.url.starts_with("add_todo"));
+    assert_eq!((add_todo.method, add_todo.encoding), ("POST", "URL"));
+    assert_eq!(list_todos.path(), "/api/todos");
+    assert_eq!((list_todos.method, list_todos.encoding), ("GET", "GetJson"));
+
+    let args = serde_json::to_value(&add_todo.args).unwrap();
+    assert_eq!(args["title"], "AddTodo");
+    assert_eq!(args["properties"]["title"]["type"], "string");
+    assert_eq!(args["required"], serde_json::json!(["title"]));
+
+    let output = serde_json::to_value(&add_todo.output).unwrap();
+    assert_eq!(output["properties"]["done"]["type"], "boolean");
+    let output = serde_json::to_value(&list_todos.output).unwrap();
+    assert_eq!(output["type"], "array");
+
+    let error = serde_json::to_value(&add_todo.error).unwrap();
+    assert_eq!(error["title"], "ServerFnError");
+}
+
+#[test]
+fn qualified_result_types_are_described() {
+    let schemas = registry_schema();
+    let get_todo = schemas.iter().find(|s| s.name == "get_todo").unwrap();
+
+    let output = serde_json::to_value(&get_todo.output).unwrap();
+    assert_eq!(output["properties"]["done"]["type"], "boolean");
+    let error = serde_json::to_value(&get_todo.error).unwrap();
+    assert_eq!(error["title"], "ServerFnError");
+}
+
+#[test]
+fn registry_schema_can_be_written_to_a_file() {
+    let path = std::env::temp_dir().join("server_fn_registry_schema.json");
+    server_fn::write_registry_schema(&path).unwrap();
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    _ = std::fs::remove_file(&path);
+    assert_eq!(
+        written.as_array().map(Vec::len),
+        Some(registry_schema().len())
+    );
+}
@@ -7,7 +7,7 @@
 
 use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
 use proc_macro_error::abort;
-use quote::{quote, quote_spanned};
+use quote::{format_ident, quote, quote_spanned};
 use syn::{
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
@@ -78,29 +78,33 @@ pub fn server_macro_impl(
         prefix,
         encoding,
         fn_path,
+        cache_ttl_ms,
         ..
     } = syn::parse2::<ServerFnName>(args)?;
     let prefix = prefix.unwrap_or_else(|| Literal::string(""));
     let fn_path = fn_path.unwrap_or_else(|| Literal::string(""));
     let encoding = quote!(#server_fn_path::#encoding);
 
-    let body = syn::parse::<ServerFnBody>(body.into())?;
+    let mut body = syn::parse::<ServerFnBody>(body.into())?;
+    let defaults = take_arg_defaults(&mut body.inputs);
     let fn_name = &body.ident;
     let fn_name_as_str = body.ident.to_string();
     let vis = body.vis;
     let block = body.block;
 
+    let mut default_fns = Vec::new();
     let fields = body
         .inputs
         .iter()
-        .filter(|f| {
+        .zip(&defaults)
+        .filter(|(f, _)| {
             if let Some(ctx) = &server_context {
                 !fn_arg_is_cx(f, ctx)
             } else {
                 true
             }
         })
-        .map(|f| {
+        .map(|(f, default)| {
             let typed_arg = match f {
                 FnArg::Receiver(_) => {
                     abort!(
@@ -110,8 +114,37 @@ pub fn server_macro_impl(
                 }
                 FnArg::Typed(t) => t,
             };
-            quote! { pub #typed_arg }
-        });
+            // arguments that are missing from the payload, e.g., because it
+            // was sent by an older version of the client, are defaulted
+            let serde_attr = match default {
+                Some(default) => {
+                    let Pat::Ident(name) = &*typed_arg.pat else {
+                        abort!(
+                            typed_arg.pat,
+                            "arguments with a `#[server_default]` must be \
+                             named with a plain identifier"
+                        );
+                    };
+                    let default_fn =
+                        format_ident!("__server_default_{}", name.ident);
+                    let ty = &typed_arg.ty;
+                    default_fns.push(quote! {
+                        #[doc(hidden)]
+                        fn #default_fn() -> #ty {
+                            #default
+                        }
+                    });
+                    let default_path = format!("{struct_name}::{default_fn}");
+                    quote! { #[serde(default = #default_path)] }
+                }
+                None if is_option(&typed_arg.ty) => quote! {
+                    #[serde(default, skip_serializing_if = "Option::is_none")]
+                },
+                None => quote! {},
+            };
+            quote! { #serde_attr pub #typed_arg }
+        })
+        .collect::<Vec<_>>();
 
     let cx_arg = body.inputs.iter().next().and_then(|f| {
         server_context
@@ -166,17 +199,20 @@ pub fn server_macro_impl(
     let field_names_3 = field_names.clone();
     let field_names_4 = field_names.clone();
     let field_names_5 = field_names.clone();
+    let field_names_6 = field_names.clone();
 
     let output_arrow = body.output_arrow;
     let return_ty = body.return_ty;
 
     let output_ty = 'output_ty: {
         if let syn::Type::Path(pat) = &return_ty {
-            if pat.path.segments[0].ident == "Result" {
-                if let PathArguments::AngleBracketed(args) =
-                    &pat.path.segments[0].arguments
-                {
-                    break 'output_ty &args.args[0];
+            if let Some(segment) = pat.path.segments.last() {
+                if segment.ident == "Result" {
+                    if let PathArguments::AngleBracketed(args) =
+                        &segment.arguments
+                    {
+                        break 'output_ty &args.args[0];
+                    }
                 }
             }
         }
@@ -187,6 +223,17 @@ pub fn server_macro_impl(
         );
     };
 
+    // the error type, as written in the return type
+    let error_ty = match &return_ty {
+        syn::Type::Path(pat) => {
+            match pat.path.segments.last().map(|segment| &segment.arguments) {
+                Some(PathArguments::AngleBracketed(args)) => args.args.get(1),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
     let server_ctx_path = if let Some(ctx) = &server_context {
         let path = &ctx.path;
         quote!(#path)
@@ -194,6 +241,14 @@ pub fn server_macro_impl(
         quote!(())
     };
 
+    let schemars_path = {
+        let mut path = server_fn_path
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote!(server_fn));
+        path.segments.push(syn::parse_quote!(schemars));
+        path
+    };
+
     let server_fn_path = server_fn_path
         .map(|path| quote!(#path))
         .unwrap_or_else(|| quote! { server_fn });
@@ -232,6 +287,47 @@ pub fn server_macro_impl(
         quote! {}
     };
 
+    let (schema_derive, schema) = if cfg!(feature = "schema") {
+        // `#[schemars(crate = ...)]` takes the path as a string
+        let schemars_path = format!(
+            "{}{}",
+            if schemars_path.leading_colon.is_some() {
+                "::"
+            } else {
+                ""
+            },
+            schemars_path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::")
+        );
+        let error_ty = error_ty
+            .map(|ty| quote!(#ty))
+            .unwrap_or_else(|| quote!(#server_fn_path::ServerFnError));
+        (
+            quote! {
+                #[derive(#server_fn_path::schemars::JsonSchema)]
+                #[schemars(crate = #schemars_path)]
+            },
+            quote! {
+                #server_fn_path::inventory::submit! {
+                    #server_fn_path::schema::ServerFnSchemaEntry::new(|| {
+                        #server_fn_path::schema::ServerFnSchema::new::<#struct_name, #output_ty, #error_ty>(
+                            #fn_name_as_str,
+                            #struct_name::PREFIX,
+                            #struct_name::URL,
+                            #struct_name::ENCODING,
+                        )
+                    })
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
     let call_fn = if cfg!(feature = "ssr") {
         quote! {
             fn call_fn(self, cx: #server_ctx_path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self::Output, #server_fn_path::ServerFnError>>>> {
@@ -248,12 +344,92 @@ pub fn server_macro_impl(
         }
     };
 
+    let cache = match cache_ttl_ms {
+        Some(_) if cfg!(feature = "ssr") => quote! {
+            impl #server_fn_path::cache::CachedServerFn for #struct_name {
+                fn cache_url() -> &'static str {
+                    Self::URL
+                }
+            }
+        },
+        _ => quote! {},
+    };
+
+    let middleware = body
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("middleware"))
+        .map(|attr| {
+            attr.parse_args_with(
+                Punctuated::<Expr, Token![,]>::parse_terminated,
+            )
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
     let func = if cfg!(feature = "ssr") {
+        let block = match cache_ttl_ms {
+            Some(ttl_ms) => quote! {
+                {
+                    let cache_key = #server_fn_path::cache::cache_key(&#struct_name {
+                        #(#field_names_6: ::std::clone::Clone::clone(&#field_names_6)),*
+                    });
+                    #server_fn_path::cache::cached(
+                        #struct_name::URL,
+                        cache_key,
+                        ::std::time::Duration::from_millis(#ttl_ms),
+                        async move #block,
+                    )
+                    .await
+                }
+            },
+            None => quote! { #block },
+        };
+        // middleware runs outside the cache, so that it can stop calls
+        // before a cached result is returned
+        let block = if middleware.is_empty() {
+            block
+        } else {
+            let middleware_cx = match (&server_context, cx_arg) {
+                (None, _) => quote! { () },
+                (Some(_), Some(FnArg::Typed(arg))) => match &*arg.pat {
+                    Pat::Ident(pat) => {
+                        let cx = &pat.ident;
+                        quote! { ::std::clone::Clone::clone(&#cx) }
+                    }
+                    _ => abort!(arg.pat, "middleware needs the context argument to be named"),
+                },
+                (Some(ctx), _) => abort!(
+                    body.ident,
+                    "server functions with middleware must take a `{}` as their first argument",
+                    ctx.ty
+                ),
+            };
+            quote! {
+                {
+                    #server_fn_path::middleware::run(
+                        #middleware_cx,
+                        #server_fn_path::middleware::ServerFnInfo {
+                            name: #fn_name_as_str,
+                            prefix: #struct_name::PREFIX,
+                            url: #struct_name::URL,
+                        },
+                        ::std::vec![#(
+                            ::std::boxed::Box::new(#middleware)
+                                as ::std::boxed::Box<dyn #server_fn_path::middleware::ServerFnMiddleware<#server_ctx_path>>
+                        ),*],
+                        async move #block,
+                    )
+                    .await
+                }
+            }
+        };
         quote! {
             #docs
-            #vis async fn #fn_name(#(#fn_args),*) #output_arrow #return_ty {
+            #vis async fn #fn_name(#(#fn_args),*) #output_arrow #return_ty
                 #block
-            }
         }
     } else {
         quote! {
@@ -276,11 +452,14 @@ pub fn server_macro_impl(
         #args_docs
         #docs
         #[derive(Clone, Debug, #server_fn_path::serde::Serialize, #server_fn_path::serde::Deserialize)]
+        #schema_derive
         pub struct #struct_name {
             #(#fields),*
         }
 
         impl #struct_name {
+            #(#default_fns)*
+
             const URL: &str = if #fn_path.is_empty() {
                     #server_fn_path::const_format::concatcp!(
                     #fn_name_as_str,
@@ -296,8 +475,29 @@ pub fn server_macro_impl(
             const ENCODING: #server_fn_path::Encoding = #encoding;
         }
 
+        #[cfg(target_arch = "wasm32")]
+        impl #struct_name {
+            /// Deserializes the arguments from the `submit` event of a `<form>`,
+            /// the same way `<ActionForm/>` does.
+            #[allow(dead_code)]
+            pub fn from_event(ev: &#server_fn_path::web_sys::Event) -> Result<Self, #server_fn_path::serde_qs::Error> {
+                #server_fn_path::form::from_event(ev)
+            }
+
+            /// Deserializes the arguments from the data of a `<form>`, the same
+            /// way `<ActionForm/>` does.
+            #[allow(dead_code)]
+            pub fn from_form_data(form_data: &#server_fn_path::web_sys::FormData) -> Result<Self, #server_fn_path::serde_qs::Error> {
+                #server_fn_path::form::from_form_data(form_data)
+            }
+        }
+
         #inventory
 
+        #schema
+
+        #cache
+
         impl #server_fn_path::ServerFn<#server_ctx_path> for #struct_name {
             type Output = #output_ty;
 
@@ -328,6 +528,7 @@ struct ServerFnName {
     encoding: Path,
     _comma3: Option<Token![,]>,
     fn_path: Option<Literal>,
+    cache_ttl_ms: Option<u64>,
 }
 
 impl Parse for ServerFnName {
@@ -351,6 +552,29 @@ impl Parse for ServerFnName {
         let _comma3 = input.parse()?;
         let fn_path = input.parse()?;
 
+        let mut cache_ttl_ms = None;
+        while !input.is_empty() {
+            input.parse::<Option<Token![,]>>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse::<LitStr>()?;
+            if key == "cache" {
+                cache_ttl_ms = match parse_ttl_ms(&value.value()) {
+                    Some(ttl) => Some(ttl),
+                    None => abort!(
+                        value,
+                        "expected a duration like \"500ms\", \"30s\", \"5m\", \
+                         \"1h\" or \"1d\""
+                    ),
+                };
+            } else {
+                abort!(key, "unknown argument; expected `cache`");
+            }
+        }
+
         Ok(Self {
             struct_name,
             _comma,
@@ -359,10 +583,72 @@ impl Parse for ServerFnName {
             encoding,
             _comma3,
             fn_path,
+            cache_ttl_ms,
         })
     }
 }
 
+/// Removes the `#[server_default = expr]` attribute from each argument,
+/// returning its default value, if any.
+fn take_arg_defaults(
+    inputs: &mut Punctuated<FnArg, Token![,]>,
+) -> Vec<Option<Expr>> {
+    inputs
+        .iter_mut()
+        .map(|f| {
+            let FnArg::Typed(typed_arg) = f else {
+                return None;
+            };
+            let mut default = None;
+            for attr in std::mem::take(&mut typed_arg.attrs) {
+                if attr.path().is_ident("server_default") {
+                    let Meta::NameValue(meta) = &attr.meta else {
+                        abort!(
+                            attr,
+                            "expected a default value, like \
+                             `#[server_default = 10]`"
+                        );
+                    };
+                    default = Some(meta.value.clone());
+                } else {
+                    typed_arg.attrs.push(attr);
+                }
+            }
+            default
+        })
+        .collect()
+}
+
+/// Whether the type is an `Option<T>`.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Parses a duration like `"30s"` into milliseconds.
+fn parse_ttl_ms(ttl: &str) -> Option<u64> {
+    let ttl = ttl.trim();
+    let split = ttl.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = ttl.split_at(split);
+    let amount = amount.parse::<u64>().ok()?;
+    let unit_ms = match unit.trim() {
+        "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        _ => return None,
+    };
+    amount.checked_mul(unit_ms)
+}
+
 #[allow(unused)]
 struct ServerFnBody {
     pub attrs: Vec<Attribute>,